@@ -0,0 +1,110 @@
+use serenity::all::{
+    ChannelId, CommandInteraction, CommandType, Context, CreateAllowedMentions,
+    CreateInteractionResponse, CreateInteractionResponseMessage, GuildId, Interaction, Permissions,
+    ResolvedTarget,
+};
+use serenity::builder::CreateCommand;
+use serenity::http::Http;
+
+use super::price::PriceAlertManager;
+use super::routes::RelayRoutes;
+
+pub const COMMAND_NAME: &str = "Register price alert";
+
+/// Register the message context-menu command for a guild.
+///
+/// Only members with Manage Messages see the entry by default; server admins can
+/// adjust this in the integration settings.
+pub async fn register_command(http: &Http, guild_id: GuildId) -> serenity::Result<()> {
+    let command = CreateCommand::new(COMMAND_NAME)
+        .kind(CommandType::Message)
+        .default_member_permissions(Permissions::MANAGE_MESSAGES);
+
+    guild_id.create_command(http, command).await.map(|_| ())
+}
+
+pub async fn handle_interaction(
+    ctx: &Context,
+    interaction: &Interaction,
+    manager: Option<&PriceAlertManager>,
+    routes: &RelayRoutes,
+) -> serenity::Result<()> {
+    if let Interaction::Command(command) = interaction
+        && command.data.kind == CommandType::Message
+        && command.data.name == COMMAND_NAME
+    {
+        let content = register_target(command, manager, routes).await;
+        return respond(ctx, command, content).await;
+    }
+    Ok(())
+}
+
+async fn register_target(
+    command: &CommandInteraction,
+    manager: Option<&PriceAlertManager>,
+    routes: &RelayRoutes,
+) -> String {
+    let Some(manager) = manager else {
+        return "Price alerts are disabled on this bot.".to_string();
+    };
+
+    let Some(guild_id) = command.guild_id else {
+        return "Price alerts can only be registered inside a server.".to_string();
+    };
+
+    let Some(ResolvedTarget::Message(message)) = command.data.target() else {
+        return "Could not resolve the selected message.".to_string();
+    };
+
+    // Alerts fire into every channel mirroring the message's channel; outside a relay source
+    // they fire where the menu was used.
+    let mut targets: Vec<ChannelId> = routes.targets_for(message.channel_id).await;
+    if targets.is_empty() {
+        targets.push(command.channel_id);
+    }
+
+    let mut registered = Vec::new();
+    for target in targets {
+        match manager
+            .register_from_message(&message.content, guild_id, target, command.user.id)
+            .await
+        {
+            Ok(alert) => registered.push(format!(
+                "Registered price alert for {} ({} levels) → <#{}>",
+                alert.symbol,
+                alert.levels.len(),
+                target
+            )),
+            Err(err) => {
+                tracing::warn!(?err, "failed to register price alert from context menu");
+                return format!("Could not register price alert: {err}");
+            }
+        }
+    }
+    registered.join("\n")
+}
+
+async fn respond(
+    ctx: &Context,
+    command: &CommandInteraction,
+    content: String,
+) -> serenity::Result<()> {
+    let allowed = CreateAllowedMentions::new()
+        .everyone(false)
+        .all_users(false)
+        .all_roles(false)
+        .empty_users()
+        .empty_roles();
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .allowed_mentions(allowed)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+}
@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use serenity::all::{
+    ChannelId, CreateAllowedMentions, CreateAttachment, CreateEmbed, CreateMessage, EditMessage,
+    EditWebhookMessage, Embed, ExecuteWebhook, Message, MessageId,
+};
+use serenity::http::Http;
+use stacks_bot::service::caching::RedisCache;
+use stacks_bot::service::caching::collections::relay_messages::{
+    MirroredMessage, RelayMessageStoreError, claim_message, load_mirrors, record_mirror,
+    take_mirrors,
+};
+use tokio::sync::Mutex;
+
+use super::relay::RelayRules;
+use super::webhook::RelayWebhooks;
+
+/// Attachments above this size are linked instead of re-uploaded (Discord's default upload
+/// limit for servers without boosts).
+const MAX_REUPLOAD_BYTES: u32 = 10 * 1024 * 1024;
+
+/// How many source messages to remember when Redis is not configured.
+const LOCAL_CAPACITY: usize = 1000;
+
+/// Tracks which target messages were created from each source message, so edits and
+/// deletions can follow. Uses Redis when available and a bounded in-memory map otherwise.
+///
+/// When webhook mode is enabled, copies are posted (and later edited/deleted) through
+/// [`RelayWebhooks`] instead of as the bot.
+pub struct MirrorStore {
+    cache: Option<Arc<RedisCache>>,
+    local: Mutex<LocalMirrors>,
+    webhooks: Option<RelayWebhooks>,
+}
+
+#[derive(Default)]
+struct LocalMirrors {
+    order: VecDeque<u64>,
+    mirrors: HashMap<u64, Vec<MirroredMessage>>,
+    processed_order: VecDeque<u64>,
+    processed: HashSet<u64>,
+}
+
+impl MirrorStore {
+    pub fn new(cache: Option<Arc<RedisCache>>) -> Self {
+        Self {
+            cache,
+            local: Mutex::new(LocalMirrors::default()),
+            webhooks: None,
+        }
+    }
+
+    pub fn with_webhooks(mut self, webhooks: Option<RelayWebhooks>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
+    /// Claim a source message for processing so each one is relayed (and parsed for alerts)
+    /// exactly once, even when the gateway redelivers it. Fails open if Redis is unreachable.
+    pub async fn claim(&self, source: MessageId) -> bool {
+        if let Some(cache) = &self.cache {
+            return match claim_message(cache, source.get()).await {
+                Ok(claimed) => claimed,
+                Err(err) => {
+                    tracing::warn!(?err, "failed to claim message; processing anyway");
+                    true
+                }
+            };
+        }
+
+        let mut local = self.local.lock().await;
+        if !local.processed.insert(source.get()) {
+            return false;
+        }
+        local.processed_order.push_back(source.get());
+        if local.processed_order.len() > LOCAL_CAPACITY
+            && let Some(evicted) = local.processed_order.pop_front()
+        {
+            local.processed.remove(&evicted);
+        }
+        true
+    }
+
+    async fn record(
+        &self,
+        source: MessageId,
+        mirror: MirroredMessage,
+    ) -> Result<(), RelayMessageStoreError> {
+        if let Some(cache) = &self.cache {
+            return record_mirror(cache, source.get(), mirror).await;
+        }
+
+        let mut local = self.local.lock().await;
+        if !local.mirrors.contains_key(&source.get()) {
+            local.order.push_back(source.get());
+            if local.order.len() > LOCAL_CAPACITY
+                && let Some(evicted) = local.order.pop_front()
+            {
+                local.mirrors.remove(&evicted);
+            }
+        }
+        local.mirrors.entry(source.get()).or_default().push(mirror);
+        Ok(())
+    }
+
+    async fn get(&self, source: MessageId) -> Result<Vec<MirroredMessage>, RelayMessageStoreError> {
+        if let Some(cache) = &self.cache {
+            return load_mirrors(cache, source.get()).await;
+        }
+        Ok(self
+            .local
+            .lock()
+            .await
+            .mirrors
+            .get(&source.get())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn take(
+        &self,
+        source: MessageId,
+    ) -> Result<Vec<MirroredMessage>, RelayMessageStoreError> {
+        if let Some(cache) = &self.cache {
+            return take_mirrors(cache, source.get()).await;
+        }
+        let mut local = self.local.lock().await;
+        local.order.retain(|id| *id != source.get());
+        Ok(local.mirrors.remove(&source.get()).unwrap_or_default())
+    }
+}
+
+fn disallow_mentions() -> CreateAllowedMentions {
+    CreateAllowedMentions::new()
+        .everyone(false)
+        .all_users(false)
+        .all_roles(false)
+        .empty_users()
+        .empty_roles()
+}
+
+/// Only forward embeds the author attached themselves; link previews are regenerated by
+/// Discord in the target channel.
+fn forwarded_embeds(embeds: &[Embed]) -> Vec<CreateEmbed> {
+    embeds
+        .iter()
+        .filter(|e| e.kind.as_deref() == Some("rich"))
+        .cloned()
+        .map(CreateEmbed::from)
+        .collect()
+}
+
+/// Post `msg` to every target and remember the copies. `content` is the already rendered
+/// text (see [`RelayRules::render`]).
+pub async fn relay_message(
+    http: &Http,
+    store: &MirrorStore,
+    msg: &Message,
+    content: &str,
+    targets: &[ChannelId],
+) {
+    let mut content = content.to_string();
+    let mut files = Vec::new();
+    for attachment in &msg.attachments {
+        if attachment.size > MAX_REUPLOAD_BYTES {
+            content.push_str(&format!("\n{}", attachment.url));
+            continue;
+        }
+        match attachment.download().await {
+            Ok(bytes) => files.push(CreateAttachment::bytes(bytes, attachment.filename.clone())),
+            Err(err) => {
+                tracing::warn!(?err, file = %attachment.filename, "failed to download attachment");
+                content.push_str(&format!("\n{}", attachment.url));
+            }
+        }
+    }
+
+    let embeds = forwarded_embeds(&msg.embeds);
+    if content.trim().is_empty() && files.is_empty() && embeds.is_empty() {
+        return;
+    }
+
+    for target in targets {
+        let sent = match &store.webhooks {
+            Some(webhooks) => {
+                let builder = ExecuteWebhook::new()
+                    .content(content.clone())
+                    .embeds(embeds.clone())
+                    .add_files(files.clone())
+                    .allowed_mentions(disallow_mentions());
+                webhooks.send(http, *target, msg, builder).await
+            }
+            None => {
+                let builder = CreateMessage::new()
+                    .content(content.clone())
+                    .embeds(embeds.clone())
+                    .add_files(files.clone())
+                    .allowed_mentions(disallow_mentions());
+                target.send_message(http, builder).await
+            }
+        };
+
+        match sent {
+            Ok(sent) => {
+                let mirror = MirroredMessage {
+                    channel_id: target.get(),
+                    message_id: sent.id.get(),
+                };
+                if let Err(err) = store.record(msg.id, mirror).await {
+                    tracing::warn!(?err, "failed to record relayed message");
+                }
+            }
+            Err(err) => tracing::error!(?err, %target, "failed to relay message"),
+        }
+    }
+}
+
+/// Apply an edit of a source message to its copies. If the edited message no longer passes
+/// the relay rules, the copies are removed. Attachments are left as originally uploaded.
+pub async fn propagate_edit(http: &Http, store: &MirrorStore, rules: &RelayRules, msg: &Message) {
+    let Some(content) = rules.render(msg) else {
+        propagate_delete(http, store, msg.id).await;
+        return;
+    };
+
+    let mirrors = match store.get(msg.id).await {
+        Ok(mirrors) => mirrors,
+        Err(err) => {
+            tracing::warn!(?err, "failed to load relayed messages");
+            return;
+        }
+    };
+
+    let embeds = forwarded_embeds(&msg.embeds);
+    for mirror in mirrors {
+        let channel = ChannelId::new(mirror.channel_id);
+        let message_id = MessageId::new(mirror.message_id);
+        let result = match &store.webhooks {
+            Some(webhooks) => {
+                let edit = EditWebhookMessage::new()
+                    .content(content.clone())
+                    .embeds(embeds.clone())
+                    .allowed_mentions(disallow_mentions());
+                webhooks.edit(http, channel, message_id, edit).await
+            }
+            None => {
+                let edit = EditMessage::new()
+                    .content(content.clone())
+                    .embeds(embeds.clone())
+                    .allowed_mentions(disallow_mentions());
+                channel
+                    .edit_message(http, message_id, edit)
+                    .await
+                    .map(|_| ())
+            }
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, %channel, "failed to edit relayed message");
+        }
+    }
+}
+
+/// Delete every copy of a source message.
+pub async fn propagate_delete(http: &Http, store: &MirrorStore, source: MessageId) {
+    let mirrors = match store.take(source).await {
+        Ok(mirrors) => mirrors,
+        Err(err) => {
+            tracing::warn!(?err, "failed to load relayed messages");
+            return;
+        }
+    };
+
+    for mirror in mirrors {
+        let channel = ChannelId::new(mirror.channel_id);
+        let message_id = MessageId::new(mirror.message_id);
+        let result = match &store.webhooks {
+            Some(webhooks) => webhooks.delete(http, channel, message_id).await,
+            None => channel.delete_message(http, message_id).await,
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, %channel, "failed to delete relayed message");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_store_tracks_and_forgets_mirrors() {
+        let store = MirrorStore::new(None);
+        let source = MessageId::new(10);
+        let mirror = MirroredMessage {
+            channel_id: 2,
+            message_id: 20,
+        };
+
+        store.record(source, mirror).await.unwrap();
+        assert_eq!(store.get(source).await.unwrap(), vec![mirror]);
+        assert_eq!(store.take(source).await.unwrap(), vec![mirror]);
+        assert!(store.get(source).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn messages_are_claimed_once() {
+        let store = MirrorStore::new(None);
+        assert!(store.claim(MessageId::new(1)).await);
+        assert!(!store.claim(MessageId::new(1)).await);
+        assert!(store.claim(MessageId::new(2)).await);
+    }
+}
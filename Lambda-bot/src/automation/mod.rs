@@ -1,3 +1,8 @@
+pub mod alert_menu;
 pub mod test;
 pub mod price;
-
+pub mod mirror;
+pub mod relay;
+pub mod routes;
+pub mod template;
+pub mod webhook;
@@ -2,17 +2,20 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use chrono::Utc;
 use futures_util::StreamExt;
-use serenity::all::{ChannelId, GuildId, Http};
+use serenity::all::{ChannelId, GuildId, Http, UserId};
 use serenity::async_trait;
+use stacks_bot::service::audit::{self, AuditEvent};
 use stacks_bot::service::caching::collections::price_alerts::{
-    load_all, save_symbol_alerts, PriceAlert, PriceAlertLevel, PriceAlertStoreError, PriceDirection,
+    PriceAlert, PriceAlertLevel, PriceDirection,
 };
-use stacks_bot::service::caching::RedisCache;
+use stacks_bot::service::shutdown::Shutdown;
+use stacks_bot::service::store::{StateStore, StoreError};
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
+use crate::automation::template::{AlertTemplate, AlertTemplates, FieldRule};
 use crate::finance::price::PriceService;
 
 #[async_trait]
@@ -42,32 +45,25 @@ impl AlertNotifier for HttpNotifier {
 #[derive(Debug, Error)]
 pub enum PriceAlertError {
     #[error("missing field {0}")]
-    MissingField(&'static str),
+    MissingField(String),
     #[error("parse error for {0}: {1}")]
-    ParseError(&'static str, String),
+    ParseError(String, String),
     #[error(transparent)]
-    Store(#[from] PriceAlertStoreError),
+    Store(#[from] StoreError),
 }
 
-fn parse_field(lines: &[&str], label: &'static str) -> Result<String, PriceAlertError> {
-    lines
-        .windows(2)
-        .find(|w| w[0].eq_ignore_ascii_case(label))
-        .and_then(|w| {
-            let val = w[1].trim();
-            if val.is_empty() {
-                None
-            } else {
-                Some(val.to_string())
-            }
-        })
-        .ok_or(PriceAlertError::MissingField(label))
+fn parse_field(raw: &str, lines: &[&str], rule: &FieldRule) -> Result<String, PriceAlertError> {
+    rule.extract(raw, lines)
+        .ok_or_else(|| PriceAlertError::MissingField(rule.name().to_string()))
 }
 
-fn parse_f64_field(lines: &[&str], label: &'static str) -> Result<f64, PriceAlertError> {
-    let raw = parse_field(lines, label)?;
-    raw.parse::<f64>()
-        .map_err(move |e| PriceAlertError::ParseError(label, e.to_string()))
+fn parse_f64_field(raw: &str, lines: &[&str], rule: &FieldRule) -> Result<f64, PriceAlertError> {
+    let value = parse_field(raw, lines, rule)?;
+    value
+        .trim_start_matches('$')
+        .replace(',', "")
+        .parse::<f64>()
+        .map_err(|e| PriceAlertError::ParseError(rule.name().to_string(), e.to_string()))
 }
 
 fn choose_direction(target: f64, current: f64) -> PriceDirection {
@@ -87,10 +83,43 @@ fn make_level(label: &str, target: f64, current: f64) -> PriceAlertLevel {
     }
 }
 
+/// Parse an alert using the built-in Lambda message layout.
 pub fn parse_alert_message(
     raw: &str,
     guild_id: GuildId,
     channel_id: ChannelId,
+) -> Result<PriceAlert, PriceAlertError> {
+    parse_alert_message_with(&AlertTemplates::default(), raw, guild_id, channel_id)
+}
+
+/// Parse an alert with the first template that accepts the message. When every template
+/// fails, the error from the first template that claims the message (or the first template
+/// overall) is returned so it can be reported back to the author.
+pub fn parse_alert_message_with(
+    templates: &AlertTemplates,
+    raw: &str,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<PriceAlert, PriceAlertError> {
+    let mut first_err = None;
+    for template in templates.iter() {
+        match parse_with_template(template, raw, guild_id, channel_id) {
+            Ok(alert) => return Ok(alert),
+            Err(err) => {
+                if first_err.is_none() || template.matches(raw) {
+                    first_err = Some(err);
+                }
+            }
+        }
+    }
+    Err(first_err.unwrap_or_else(|| PriceAlertError::MissingField("template".into())))
+}
+
+fn parse_with_template(
+    template: &AlertTemplate,
+    raw: &str,
+    guild_id: GuildId,
+    channel_id: ChannelId,
 ) -> Result<PriceAlert, PriceAlertError> {
     let lines: Vec<&str> = raw
         .lines()
@@ -98,16 +127,17 @@ pub fn parse_alert_message(
         .filter(|l| !l.is_empty())
         .collect();
 
-    let symbol = parse_field(&lines, "Ticker")?;
-    let current_price = parse_f64_field(&lines, "Current Price")?;
-    let lambda = parse_f64_field(&lines, "Lambda Level")?;
-    let fail_safe = parse_f64_field(&lines, "Fail-Safe")?;
-    let up1 = parse_f64_field(&lines, "Upside PT1")?;
-    let up2 = parse_f64_field(&lines, "Upside PT2")?;
-    let up3 = parse_f64_field(&lines, "Upside PT3")?;
-    let dn1 = parse_f64_field(&lines, "Downside PT1")?;
-    let dn2 = parse_f64_field(&lines, "Downside PT2")?;
-    let dn3 = parse_f64_field(&lines, "Downside PT3")?;
+    let symbol = parse_field(raw, &lines, &template.symbol)?.to_ascii_uppercase();
+    let current_price = parse_f64_field(raw, &lines, &template.current_price)?;
+
+    let levels = template
+        .levels
+        .iter()
+        .map(|rule| {
+            parse_f64_field(raw, &lines, rule)
+                .map(|target| make_level(rule.name(), target, current_price))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     let id = format!(
         "{}-{}",
@@ -117,17 +147,6 @@ pub fn parse_alert_message(
             .unwrap_or_default()
     );
 
-    let levels = vec![
-        make_level("Lambda", lambda, current_price),
-        make_level("FAIL SAFE", fail_safe, current_price),
-        make_level("PT1 Upside", up1, current_price),
-        make_level("PT2 Upside", up2, current_price),
-        make_level("PT3 Upside", up3, current_price),
-        make_level("PT1 Downside", dn1, current_price),
-        make_level("PT2 Downside", dn2, current_price),
-        make_level("PT3 Downside", dn3, current_price),
-    ];
-
     Ok(PriceAlert {
         id,
         symbol,
@@ -142,63 +161,113 @@ pub fn parse_alert_message(
 pub struct PriceAlertManager {
     notifier: Arc<dyn AlertNotifier>,
     price_service: Arc<PriceService>,
-    cache: Option<Arc<RedisCache>>,
+    store: Option<Arc<dyn StateStore>>,
+    /// Posts registrations and completed alerts to the guild's audit channel; unset with a
+    /// custom notifier.
+    audit_http: Option<Arc<Http>>,
     state: Arc<Mutex<HashMap<String, Vec<PriceAlert>>>>,
     tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
     interval: Duration,
+    templates: Arc<AlertTemplates>,
+    shutdown: Shutdown,
 }
 
 impl PriceAlertManager {
     pub fn new(
         http: Arc<Http>,
         price_service: Arc<PriceService>,
-        cache: Option<Arc<RedisCache>>,
+        store: Option<Arc<dyn StateStore>>,
     ) -> Self {
         Self {
-            notifier: Arc::new(HttpNotifier::new(http)),
+            notifier: Arc::new(HttpNotifier::new(http.clone())),
             price_service,
-            cache,
+            store,
+            audit_http: Some(http),
             state: Arc::new(Mutex::new(HashMap::new())),
             tasks: Arc::new(Mutex::new(HashMap::new())),
             interval: Duration::from_secs(2),
+            templates: Arc::new(AlertTemplates::default()),
+            shutdown: Shutdown::new(),
         }
     }
 
     pub fn new_with_notifier(
         notifier: Arc<dyn AlertNotifier>,
         price_service: Arc<PriceService>,
-        cache: Option<Arc<RedisCache>>,
+        store: Option<Arc<dyn StateStore>>,
     ) -> Self {
         Self {
             notifier,
             price_service,
-            cache,
+            store,
+            audit_http: None,
             state: Arc::new(Mutex::new(HashMap::new())),
             tasks: Arc::new(Mutex::new(HashMap::new())),
             interval: Duration::from_secs(2),
+            templates: Arc::new(AlertTemplates::default()),
+            shutdown: Shutdown::new(),
         }
     }
 
-    pub async fn hydrate(&self) -> Result<(), PriceAlertStoreError> {
-        if let Some(cache) = &self.cache {
-            let all = load_all(cache).await?;
+    /// Replace the message templates used by `register_from_message`.
+    pub fn with_templates(mut self, templates: AlertTemplates) -> Self {
+        self.templates = Arc::new(templates);
+        self
+    }
+
+    /// Stop price streams when `shutdown` is triggered. Updates already being handled
+    /// (notifications and store writes) finish first.
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn templates(&self) -> &AlertTemplates {
+        &self.templates
+    }
+
+    /// Load persisted alerts from the state store and resume a price stream for every stored
+    /// symbol. Returns the number of alerts restored (0 when no store is configured).
+    pub async fn hydrate(&self) -> Result<usize, StoreError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+
+        let all = store.price_alerts().await?;
+        let mut restored = 0;
+        let mut symbols = Vec::with_capacity(all.len());
+        {
             let mut state = self.state.lock().await;
             for (symbol, alerts) in all {
-                state.insert(symbol.clone(), alerts);
-                self.ensure_stream(&symbol).await;
+                restored += alerts.len();
+                state.entry(symbol.clone()).or_default().extend(alerts);
+                symbols.push(symbol);
             }
         }
-        Ok(())
+
+        for symbol in symbols {
+            self.ensure_stream(&symbol).await;
+        }
+        Ok(restored)
     }
 
+    /// Register the alert described by `raw`; `user` is who asked, for the audit log.
     pub async fn register_from_message(
         &self,
         raw: &str,
         guild_id: GuildId,
         channel_id: ChannelId,
+        user: UserId,
     ) -> Result<PriceAlert, PriceAlertError> {
-        let alert = parse_alert_message(raw, guild_id, channel_id)?;
+        let alert = parse_alert_message_with(&self.templates, raw, guild_id, channel_id)?;
         self.insert_alert(alert.clone()).await?;
+        if let Some(http) = &self.audit_http {
+            let event = AuditEvent::AlertRegistered {
+                user,
+                alert: &alert,
+            };
+            audit::record(http, self.store.as_deref(), guild_id, event).await;
+        }
         Ok(alert)
     }
 
@@ -209,20 +278,19 @@ impl PriceAlertManager {
             state.entry(symbol.clone()).or_default().push(alert.clone());
         }
 
-        if let Some(cache) = &self.cache {
-            save_symbol_alerts(cache, &symbol, &self.snapshot_symbol(&symbol).await?).await?;
+        if let Some(store) = &self.store {
+            store
+                .save_price_alerts(&symbol, &self.snapshot_symbol(&symbol).await)
+                .await?;
         }
 
         self.ensure_stream(&symbol).await;
         Ok(())
     }
 
-    async fn snapshot_symbol(
-        &self,
-        symbol: &str,
-    ) -> Result<Vec<PriceAlert>, PriceAlertStoreError> {
+    async fn snapshot_symbol(&self, symbol: &str) -> Vec<PriceAlert> {
         let state = self.state.lock().await;
-        Ok(state.get(symbol).cloned().unwrap_or_default())
+        state.get(symbol).cloned().unwrap_or_default()
     }
 
     async fn ensure_stream(&self, symbol: &str) {
@@ -234,19 +302,23 @@ impl PriceAlertManager {
         let symbol_owned = symbol.to_string();
         let notifier = Arc::clone(&self.notifier);
         let price_service = Arc::clone(&self.price_service);
-        let cache = self.cache.clone();
+        let store = self.store.clone();
+        let audit_http = self.audit_http.clone();
         let state = Arc::clone(&self.state);
         let tasks_map = Arc::clone(&self.tasks);
         let interval = self.interval;
+        let shutdown = self.shutdown.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = self.shutdown.spawn(async move {
             run_symbol_loop(
                 symbol_owned.clone(),
                 notifier,
                 price_service,
-                cache,
+                store,
+                audit_http,
                 state,
                 interval,
+                shutdown,
             )
             .await;
             tasks_map.lock().await.remove(&symbol_owned);
@@ -256,20 +328,34 @@ impl PriceAlertManager {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_symbol_loop(
     symbol: String,
     notifier: Arc<dyn AlertNotifier>,
     price_service: Arc<PriceService>,
-    cache: Option<Arc<RedisCache>>,
+    store: Option<Arc<dyn StateStore>>,
+    audit_http: Option<Arc<Http>>,
     state: Arc<Mutex<HashMap<String, Vec<PriceAlert>>>>,
     interval: Duration,
+    shutdown: Shutdown,
 ) {
     info!("starting price alert stream for {symbol}");
     let mut stream = price_service
         .stream_prices(vec![symbol.clone()], interval)
         .boxed();
 
-    while let Some(next) = stream.next().await {
+    loop {
+        let next = tokio::select! {
+            _ = shutdown.cancelled() => {
+                info!("stopping price alert stream for {symbol}: shutting down");
+                break;
+            }
+            next = stream.next() => next,
+        };
+        let Some(next) = next else {
+            break;
+        };
+
         match next {
             Ok(update) => {
                 let price = update
@@ -284,16 +370,15 @@ async fn run_symbol_loop(
                     continue;
                 };
 
-                match handle_price(
-                    &symbol,
-                    price,
-                    &notifier,
-                    cache.as_ref(),
-                    &state,
-                )
-                .await
-                {
-                    Ok(stop) => {
+                match handle_price(&symbol, price, &notifier, store.as_ref(), &state).await {
+                    Ok((stop, completed)) => {
+                        if let Some(http) = &audit_http {
+                            for alert in &completed {
+                                let guild_id = GuildId::new(alert.target_guild_id);
+                                let event = AuditEvent::AlertCompleted { alert };
+                                audit::record(http, store.as_deref(), guild_id, event).await;
+                            }
+                        }
                         if stop {
                             info!("no remaining alerts for {symbol}; stopping stream");
                             break;
@@ -313,16 +398,19 @@ async fn run_symbol_loop(
     }
 }
 
+/// Fire the levels `price` hits. Returns whether the symbol has no alerts left, and the alerts
+/// that completed on this update.
 async fn handle_price(
     symbol: &str,
     price: f64,
     notifier: &Arc<dyn AlertNotifier>,
-    cache: Option<&Arc<RedisCache>>,
+    store: Option<&Arc<dyn StateStore>>,
     state: &Arc<Mutex<HashMap<String, Vec<PriceAlert>>>>,
-) -> Result<bool, PriceAlertStoreError> {
+) -> Result<(bool, Vec<PriceAlert>), StoreError> {
     let mut to_send = Vec::new();
-    let mut persist: Option<Vec<PriceAlert>> = None;
-    let mut stop = false;
+    let mut completed = Vec::new();
+    let persist: Option<Vec<PriceAlert>>;
+    let stop;
 
     {
         let mut guard = state.lock().await;
@@ -343,7 +431,11 @@ async fn handle_price(
                 }
             }
 
-            alerts.retain(|a| a.levels.iter().any(|lvl| !lvl.fired));
+            let (active, done) = std::mem::take(alerts)
+                .into_iter()
+                .partition(|a| a.levels.iter().any(|lvl| !lvl.fired));
+            *alerts = active;
+            completed = done;
             stop = alerts.is_empty();
             if stop {
                 guard.remove(symbol);
@@ -361,19 +453,16 @@ async fn handle_price(
         notifier.send(channel_id, content).await;
     }
 
-    if let Some(cache) = cache {
-        if let Some(alerts) = persist {
-            save_symbol_alerts(cache, symbol, &alerts).await?;
-        }
+    if let (Some(store), Some(alerts)) = (store, persist) {
+        store.save_price_alerts(symbol, &alerts).await?;
     }
 
-    Ok(stop)
+    Ok((stop, completed))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::price::{Price, PriceUpdate};
     use serenity::all::{ChannelId, GuildId, Http};
     use std::env;
     use std::sync::Arc;
@@ -391,26 +480,11 @@ mod tests {
         }
     }
 
-    fn sample_price(symbol: &str, price: f64) -> PriceUpdate {
-        PriceUpdate {
-            prices: vec![Price {
-                symbol: symbol.to_string(),
-                name: symbol.to_string(),
-                price,
-                change: 0.0,
-                percent_change: 0.0,
-                pre_market_price: None,
-                after_hours_price: None,
-            }],
-            timestamp: Utc::now(),
-        }
-    }
-
     #[tokio::test]
     async fn triggers_expected_alerts() {
         let notifier = Arc::new(MockNotifier::default());
         let notifier_dyn: Arc<dyn AlertNotifier> = notifier.clone();
-        let cache: Option<Arc<RedisCache>> = None;
+        let store: Option<Arc<dyn StateStore>> = None;
         let state: Arc<Mutex<HashMap<String, Vec<PriceAlert>>>> = Arc::new(Mutex::new(HashMap::new()));
 
         let raw = r#"Ticker
@@ -444,7 +518,7 @@ Downside PT3
         let symbol = "SPY";
         let prices = vec![683.0, 681.0, 680.0, 684.5, 687.0];
         for p in prices {
-            let _ = handle_price(symbol, p, &notifier_dyn, cache.as_ref(), &state)
+            let _ = handle_price(symbol, p, &notifier_dyn, store.as_ref(), &state)
                 .await
                 .unwrap();
         }
@@ -452,10 +526,10 @@ Downside PT3
         let sent = notifier.sent.lock().await.clone();
         let texts: Vec<_> = sent.iter().map(|(_, msg)| msg.as_str()).collect();
 
-        assert!(texts.iter().any(|m| *m == "FAIL SAFE 681.00 HIT"));
-        assert!(texts.iter().any(|m| *m == "PT1 Downside 680.00 HIT"));
-        assert!(texts.iter().any(|m| *m == "Lambda 684.50 HIT"));
-        assert!(texts.iter().any(|m| *m == "PT2 Upside 687.00 HIT"));
+        assert!(texts.contains(&"FAIL SAFE 681.00 HIT"));
+        assert!(texts.contains(&"PT1 Downside 680.00 HIT"));
+        assert!(texts.contains(&"Lambda 684.50 HIT"));
+        assert!(texts.contains(&"PT2 Upside 687.00 HIT"));
         // PT1 Upside (690) not reached in this sequence; ensure not sent
         assert!(!texts.iter().any(|m| m.contains("PT1 Upside 690.00")));
     }
@@ -477,7 +551,7 @@ Downside PT3
             .unwrap_or(0);
 
         let notifier: Arc<dyn AlertNotifier> = Arc::new(HttpNotifier::new(Arc::new(Http::new(&token))));
-        let cache: Option<Arc<RedisCache>> = None;
+        let store: Option<Arc<dyn StateStore>> = None;
         let state: Arc<Mutex<HashMap<String, Vec<PriceAlert>>>> = Arc::new(Mutex::new(HashMap::new()));
 
         let raw = r#"Ticker
@@ -510,7 +584,7 @@ Downside PT3
 
         let prices = [684.5, 687.0];
         for p in prices {
-            handle_price("SPY", p, &notifier, cache.as_ref(), &state).await?;
+            handle_price("SPY", p, &notifier, store.as_ref(), &state).await?;
         }
 
         Ok(())
@@ -0,0 +1,259 @@
+use std::{collections::HashSet, env};
+
+use regex::Regex;
+use serenity::all::{GuildId, Http, Message, RoleId, UserId};
+
+pub const DEFAULT_TEMPLATE: &str = "[{author}] {content}";
+/// Webhook copies already carry the author's name and avatar.
+pub const WEBHOOK_TEMPLATE: &str = "{content}";
+
+/// Filters and formatting applied to every relayed message.
+///
+/// Configured from the environment:
+/// - `RELAY_INCLUDE_PATTERN` / `RELAY_EXCLUDE_PATTERN`: regexes tested against the content
+/// - `RELAY_ALLOWED_USERS` / `RELAY_ALLOWED_ROLES`: comma-separated IDs; empty means everyone
+/// - `RELAY_STRIP_LINKS=1`: remove http(s) links before posting
+/// - `RELAY_TEMPLATE`: output format with `{author}`, `{display_name}`, `{content}`,
+///   `{channel}`, `{timestamp}` and `{jump_url}` placeholders
+#[derive(Debug, Clone)]
+pub struct RelayRules {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+    allowed_users: HashSet<UserId>,
+    allowed_roles: HashSet<RoleId>,
+    strip_links: bool,
+    template: String,
+}
+
+impl Default for RelayRules {
+    fn default() -> Self {
+        Self {
+            include: None,
+            exclude: None,
+            allowed_users: HashSet::new(),
+            allowed_roles: HashSet::new(),
+            strip_links: false,
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl RelayRules {
+    /// `default_template` is used when `RELAY_TEMPLATE` is unset.
+    pub fn from_env(default_template: &str) -> Result<Self, String> {
+        let pattern = |key: &str| -> Result<Option<Regex>, String> {
+            match env::var(key) {
+                Ok(raw) if !raw.trim().is_empty() => Regex::new(raw.trim())
+                    .map(Some)
+                    .map_err(|e| format!("{key} is not a valid regex: {e}")),
+                _ => Ok(None),
+            }
+        };
+
+        let ids = |key: &str| -> Result<HashSet<u64>, String> {
+            env::var(key)
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<u64>()
+                        .map_err(|e| format!("{key} must contain numeric IDs: {e}"))
+                })
+                .collect()
+        };
+
+        let template = env::var("RELAY_TEMPLATE")
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| t.replace("\\n", "\n"))
+            .unwrap_or_else(|| default_template.to_string());
+
+        Ok(Self {
+            include: pattern("RELAY_INCLUDE_PATTERN")?,
+            exclude: pattern("RELAY_EXCLUDE_PATTERN")?,
+            allowed_users: ids("RELAY_ALLOWED_USERS")?
+                .into_iter()
+                .map(UserId::new)
+                .collect(),
+            allowed_roles: ids("RELAY_ALLOWED_ROLES")?
+                .into_iter()
+                .map(RoleId::new)
+                .collect(),
+            strip_links: env::var("RELAY_STRIP_LINKS")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            template,
+        })
+    }
+
+    /// Whether the author is allowed to be relayed. When both user and role lists are set,
+    /// matching either one is enough.
+    fn author_allowed(&self, msg: &Message) -> bool {
+        if self.allowed_users.is_empty() && self.allowed_roles.is_empty() {
+            return true;
+        }
+        if self.allowed_users.contains(&msg.author.id) {
+            return true;
+        }
+        msg.member
+            .as_ref()
+            .is_some_and(|m| m.roles.iter().any(|r| self.allowed_roles.contains(r)))
+    }
+
+    fn content_allowed(&self, content: &str) -> bool {
+        if let Some(include) = &self.include
+            && !include.is_match(content)
+        {
+            return false;
+        }
+        if let Some(exclude) = &self.exclude
+            && exclude.is_match(content)
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Apply filters and the template; `None` means the message should not be relayed.
+    pub fn render(&self, msg: &Message) -> Option<String> {
+        if !self.author_allowed(msg) || !self.content_allowed(&msg.content) {
+            return None;
+        }
+
+        let content = if self.strip_links {
+            strip_links(&msg.content)
+        } else {
+            msg.content.clone()
+        };
+
+        let display_name = display_name(msg);
+
+        Some(render_template(
+            &self.template,
+            &[
+                ("author", msg.author.name.as_str()),
+                ("display_name", display_name.as_str()),
+                ("content", content.as_str()),
+                ("channel", &format!("<#{}>", msg.channel_id)),
+                (
+                    "timestamp",
+                    &format!("<t:{}:t>", msg.timestamp.unix_timestamp()),
+                ),
+                ("jump_url", &msg.link()),
+            ],
+        ))
+    }
+}
+
+/// Messages fetched over REST (the backfill and edits) carry no `member`, so role rules and
+/// nicknames would not apply. Look the member up in `guild_id`; when that fails the message
+/// is kept as is and falls back to the user's names.
+pub async fn with_member(http: &Http, guild_id: Option<GuildId>, mut msg: Message) -> Message {
+    let Some(guild_id) = guild_id.or(msg.guild_id) else {
+        return msg;
+    };
+    if msg.member.is_none() {
+        match guild_id.member(http, msg.author.id).await {
+            Ok(member) => msg.member = Some(Box::new(member.into())),
+            Err(err) => {
+                tracing::debug!(?err, author = %msg.author.id, "no member for relayed message")
+            }
+        }
+    }
+    msg
+}
+
+/// Server nickname, then global display name, then username.
+pub fn display_name(msg: &Message) -> String {
+    msg.member
+        .as_ref()
+        .and_then(|m| m.nick.clone())
+        .or_else(|| msg.author.global_name.clone())
+        .unwrap_or_else(|| msg.author.name.clone())
+}
+
+/// Single pass so placeholders inside substituted values (e.g. user content) are left alone.
+fn render_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(key, _)| *key == &after[..end])
+                .map(|(_, v)| (end, *v))
+        });
+        match value {
+            Some((end, v)) => {
+                out.push_str(v);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_links(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .filter(|word| {
+                    let w = word.trim_start_matches('<');
+                    !(w.starts_with("http://") || w.starts_with("https://"))
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_links_and_fills_template() {
+        assert_eq!(
+            strip_links("SPY calls https://example.com/x\n<https://a.b> done"),
+            "SPY calls\ndone"
+        );
+        assert_eq!(
+            render_template(
+                "{author}: {content} ({author})",
+                &[("author", "bob"), ("content", "hi")]
+            ),
+            "bob: hi (bob)"
+        );
+        assert_eq!(
+            render_template(
+                "{content} {x}",
+                &[("content", "{author}"), ("author", "bob")]
+            ),
+            "{author} {x}"
+        );
+    }
+
+    #[test]
+    fn include_and_exclude_patterns() {
+        let rules = RelayRules {
+            include: Some(Regex::new(r"(?i)\bticker\b").unwrap()),
+            exclude: Some(Regex::new(r"(?i)ignore").unwrap()),
+            ..RelayRules::default()
+        };
+        assert!(rules.content_allowed("Ticker SPY"));
+        assert!(!rules.content_allowed("random chat"));
+        assert!(!rules.content_allowed("Ticker SPY ignore"));
+    }
+}
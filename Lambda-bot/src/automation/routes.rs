@@ -0,0 +1,393 @@
+use std::{collections::BTreeSet, env, sync::Arc};
+
+use serenity::all::{
+    ChannelId, CommandDataOption, CommandDataOptionValue, CommandInteraction, CommandOptionType,
+    Context, CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseMessage,
+    GuildId, Interaction, Permissions,
+};
+use serenity::builder::CreateCommand;
+use serenity::http::Http;
+use stacks_bot::service::caching::RedisCache;
+use stacks_bot::service::caching::collections::relay_pairs::{
+    RelayPair, RelayPairStoreError, add_pair, load_pairs, remove_pair,
+};
+use tokio::sync::RwLock;
+
+pub const COMMAND_NAME: &str = "relay-pair";
+
+/// Source → target channel mappings used by the relay.
+///
+/// Pairs from the environment (`SOURCE_CHANNEL_ID`/`TARGET_CHANNEL_ID` and `RELAY_PAIRS`) are
+/// static; pairs added with `/relay-pair add` are persisted to Redis when it is configured.
+pub struct RelayRoutes {
+    static_pairs: BTreeSet<RelayPair>,
+    dynamic_pairs: RwLock<BTreeSet<RelayPair>>,
+    cache: Option<Arc<RedisCache>>,
+}
+
+impl RelayRoutes {
+    pub fn new(static_pairs: Vec<RelayPair>, cache: Option<Arc<RedisCache>>) -> Self {
+        Self {
+            static_pairs: static_pairs.into_iter().collect(),
+            dynamic_pairs: RwLock::new(BTreeSet::new()),
+            cache,
+        }
+    }
+
+    /// Parse pairs from the environment. `RELAY_PAIRS` is a comma-separated list of
+    /// `source:target` channel IDs; the legacy single pair is included when set.
+    pub fn pairs_from_env() -> Result<Vec<RelayPair>, String> {
+        let mut pairs = Vec::new();
+
+        let legacy_source = env::var("SOURCE_CHANNEL_ID").ok();
+        let legacy_target = env::var("TARGET_CHANNEL_ID").ok();
+        if let (Some(source), Some(target)) = (legacy_source, legacy_target) {
+            pairs.push(parse_pair(&format!("{}:{}", source.trim(), target.trim()))?);
+        }
+
+        if let Ok(raw) = env::var("RELAY_PAIRS") {
+            for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                pairs.push(parse_pair(entry)?);
+            }
+        }
+
+        Ok(pairs)
+    }
+
+    /// Load Redis-managed pairs on top of the static ones.
+    pub async fn hydrate(&self) -> Result<usize, RelayPairStoreError> {
+        let Some(cache) = &self.cache else {
+            return Ok(0);
+        };
+        let stored = load_pairs(cache).await?;
+        let count = stored.len();
+        self.dynamic_pairs.write().await.extend(stored);
+        Ok(count)
+    }
+
+    pub async fn all(&self) -> Vec<RelayPair> {
+        let dynamic = self.dynamic_pairs.read().await;
+        let mut all: BTreeSet<RelayPair> = self.static_pairs.clone();
+        all.extend(dynamic.iter().copied());
+        all.into_iter().collect()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.static_pairs.is_empty() && self.dynamic_pairs.read().await.is_empty()
+    }
+
+    /// Target channels that mirror the given source channel.
+    pub async fn targets_for(&self, source: ChannelId) -> Vec<ChannelId> {
+        self.all()
+            .await
+            .into_iter()
+            .filter(|p| p.source_channel_id == source.get())
+            .map(|p| ChannelId::new(p.target_channel_id))
+            .collect()
+    }
+
+    pub async fn add(&self, pair: RelayPair) -> Result<bool, RelayPairStoreError> {
+        if let Some(cache) = &self.cache {
+            add_pair(cache, pair).await?;
+        }
+        let inserted = self.dynamic_pairs.write().await.insert(pair);
+        Ok(inserted && !self.static_pairs.contains(&pair))
+    }
+
+    pub async fn remove(&self, pair: RelayPair) -> Result<bool, RelayPairStoreError> {
+        if let Some(cache) = &self.cache {
+            remove_pair(cache, pair).await?;
+        }
+        Ok(self.dynamic_pairs.write().await.remove(&pair))
+    }
+
+    pub fn is_static(&self, pair: &RelayPair) -> bool {
+        self.static_pairs.contains(pair)
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.cache.is_some()
+    }
+}
+
+fn parse_pair(raw: &str) -> Result<RelayPair, String> {
+    let (source, target) = raw
+        .split_once(':')
+        .ok_or_else(|| format!("relay pair '{raw}' must look like SOURCE_ID:TARGET_ID"))?;
+    Ok(RelayPair {
+        source_channel_id: parse_channel_id(source)
+            .ok_or_else(|| format!("relay pair '{raw}' has an invalid source channel id"))?,
+        target_channel_id: parse_channel_id(target)
+            .ok_or_else(|| format!("relay pair '{raw}' has an invalid target channel id"))?,
+    })
+}
+
+/// Accept raw IDs as well as `<#id>` channel mentions.
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    raw.trim()
+        .trim_start_matches("<#")
+        .trim_end_matches('>')
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+}
+
+/// Register `/relay-pair add|remove|list` for a guild.
+pub async fn register_command(http: &Http, guild_id: GuildId) -> serenity::Result<()> {
+    let channel_opts = |sub: CreateCommandOption| {
+        sub.add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "source",
+                "Source channel ID (or #mention)",
+            )
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "target",
+                "Target channel ID (or #mention) in this server",
+            )
+            .required(true),
+        )
+    };
+
+    let command = CreateCommand::new(COMMAND_NAME)
+        .description("Manage relay channel pairs")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(channel_opts(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "add",
+            "Relay messages from a source channel to a target channel",
+        )))
+        .add_option(channel_opts(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "remove",
+            "Stop relaying a source/target pair",
+        )))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "List all relay pairs",
+        ));
+
+    guild_id.create_command(http, command).await.map(|_| ())
+}
+
+pub async fn handle_interaction(
+    ctx: &Context,
+    interaction: &Interaction,
+    routes: &RelayRoutes,
+) -> serenity::Result<()> {
+    if let Interaction::Command(command) = interaction
+        && command.data.name == COMMAND_NAME
+    {
+        let content = run_command(ctx, command, routes).await;
+        return command
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(true),
+                ),
+            )
+            .await;
+    }
+    Ok(())
+}
+
+async fn run_command(ctx: &Context, command: &CommandInteraction, routes: &RelayRoutes) -> String {
+    let Some(sub) = command.data.options.first() else {
+        return "Missing subcommand.".to_string();
+    };
+
+    match sub.name.as_str() {
+        "list" => format_pairs(routes).await,
+        "add" | "remove" => {
+            let pair = match pair_from_options(sub) {
+                Ok(pair) => pair,
+                Err(err) => return err,
+            };
+            if let Err(err) = check_channels(ctx, command.guild_id, pair).await {
+                return err;
+            }
+
+            if sub.name == "add" {
+                match routes.add(pair).await {
+                    Ok(true) => format!(
+                        "Added relay <#{}> → <#{}>{}",
+                        pair.source_channel_id,
+                        pair.target_channel_id,
+                        persistence_note(routes)
+                    ),
+                    Ok(false) => "That relay pair already exists.".to_string(),
+                    Err(err) => format!("Failed to store relay pair: {err}"),
+                }
+            } else if routes.is_static(&pair) {
+                "That pair comes from the environment and cannot be removed at runtime.".to_string()
+            } else {
+                match routes.remove(pair).await {
+                    Ok(true) => format!(
+                        "Removed relay <#{}> → <#{}>",
+                        pair.source_channel_id, pair.target_channel_id
+                    ),
+                    Ok(false) => "No such relay pair.".to_string(),
+                    Err(err) => format!("Failed to remove relay pair: {err}"),
+                }
+            }
+        }
+        other => format!("Unknown subcommand: {other}"),
+    }
+}
+
+fn persistence_note(routes: &RelayRoutes) -> &'static str {
+    if routes.is_persistent() {
+        ""
+    } else {
+        " (not persisted: REDIS_URL is not set)"
+    }
+}
+
+fn pair_from_options(sub: &CommandDataOption) -> Result<RelayPair, String> {
+    let CommandDataOptionValue::SubCommand(options) = &sub.value else {
+        return Err("Malformed subcommand.".to_string());
+    };
+
+    let get = |name: &str| {
+        options
+            .iter()
+            .find(|o| o.name == name)
+            .and_then(|o| o.value.as_str())
+            .and_then(parse_channel_id)
+    };
+
+    let source = get("source").ok_or("Invalid source channel ID.")?;
+    let target = get("target").ok_or("Invalid target channel ID.")?;
+    if source == target {
+        return Err("Source and target must be different channels.".to_string());
+    }
+
+    Ok(RelayPair {
+        source_channel_id: source,
+        target_channel_id: target,
+    })
+}
+
+/// Both channels of a pair must belong to the server managing it, so one server's admins can't
+/// relay another server's messages or post into its channels.
+async fn check_channels(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    pair: RelayPair,
+) -> Result<(), String> {
+    let guild_id = guild_id.ok_or("Relay pairs can only be managed from a server.")?;
+    for (label, id) in [
+        ("source", pair.source_channel_id),
+        ("target", pair.target_channel_id),
+    ] {
+        let owner = ChannelId::new(id)
+            .to_channel(ctx)
+            .await
+            .ok()
+            .and_then(|channel| channel.guild())
+            .map(|channel| channel.guild_id);
+        check_owner(label, id, owner, guild_id)?;
+    }
+    Ok(())
+}
+
+fn check_owner(
+    label: &str,
+    channel_id: u64,
+    owner: Option<GuildId>,
+    guild_id: GuildId,
+) -> Result<(), String> {
+    if owner == Some(guild_id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "The {label} channel <#{channel_id}> is not a channel of this server."
+        ))
+    }
+}
+
+async fn format_pairs(routes: &RelayRoutes) -> String {
+    let pairs = routes.all().await;
+    if pairs.is_empty() {
+        return "No relay pairs configured.".to_string();
+    }
+
+    let mut lines = vec![format!("Relay pairs ({}):", pairs.len())];
+    for pair in pairs {
+        let origin = if routes.is_static(&pair) {
+            " (env)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "• <#{}> → <#{}>{}",
+            pair.source_channel_id, pair.target_channel_id, origin
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pairs_and_mentions() {
+        let pair = parse_pair("<#123>:456").unwrap();
+        assert_eq!(pair.source_channel_id, 123);
+        assert_eq!(pair.target_channel_id, 456);
+        assert!(parse_pair("123").is_err());
+        assert!(parse_pair("abc:456").is_err());
+    }
+
+    #[test]
+    fn channels_must_belong_to_the_invoking_server() {
+        let guild = GuildId::new(10);
+        assert!(check_owner("source", 1, Some(guild), guild).is_ok());
+        assert!(
+            check_owner("target", 2, Some(GuildId::new(11)), guild)
+                .unwrap_err()
+                .contains("target channel <#2>")
+        );
+        assert!(check_owner("target", 2, None, guild).is_err());
+    }
+
+    #[tokio::test]
+    async fn routes_fan_out_to_every_target() {
+        let routes = RelayRoutes::new(
+            vec![
+                RelayPair {
+                    source_channel_id: 1,
+                    target_channel_id: 2,
+                },
+                RelayPair {
+                    source_channel_id: 1,
+                    target_channel_id: 3,
+                },
+            ],
+            None,
+        );
+        routes
+            .add(RelayPair {
+                source_channel_id: 4,
+                target_channel_id: 5,
+            })
+            .await
+            .unwrap();
+
+        let targets = routes.targets_for(ChannelId::new(1)).await;
+        assert_eq!(targets, vec![ChannelId::new(2), ChannelId::new(3)]);
+        assert_eq!(
+            routes.targets_for(ChannelId::new(4)).await,
+            vec![ChannelId::new(5)]
+        );
+        assert!(routes.targets_for(ChannelId::new(9)).await.is_empty());
+    }
+}
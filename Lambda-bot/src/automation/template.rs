@@ -0,0 +1,336 @@
+use std::{env, fs};
+
+use regex::Regex;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("failed to read alert templates from {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("invalid alert template JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("template '{template}' field '{field}': {reason}")]
+    Invalid {
+        template: String,
+        field: String,
+        reason: String,
+    },
+}
+
+/// How a single value is located inside an alert message.
+///
+/// `label` matches either a line equal to the label followed by the value on the next line,
+/// or a `Label: value` line. `pattern` is a regex whose first capture group is the value.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldRuleConfig {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LevelRuleConfig {
+    /// Label used in the "HIT" notification, e.g. "PT1 Upside".
+    pub name: String,
+    #[serde(flatten)]
+    pub rule: FieldRuleConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertTemplateConfig {
+    pub name: String,
+    pub symbol: FieldRuleConfig,
+    pub current_price: FieldRuleConfig,
+    pub levels: Vec<LevelRuleConfig>,
+    /// Case-insensitive substrings that must all appear for a message to be treated as an alert.
+    /// Defaults to the labels of the symbol, current price and first level.
+    #[serde(default)]
+    pub detect: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldRule {
+    name: String,
+    label: Option<String>,
+    pattern: Option<Regex>,
+}
+
+impl FieldRule {
+    fn compile(template: &str, name: &str, cfg: &FieldRuleConfig) -> Result<Self, TemplateError> {
+        let invalid = |reason: String| TemplateError::Invalid {
+            template: template.to_string(),
+            field: name.to_string(),
+            reason,
+        };
+
+        let pattern = cfg
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| invalid(e.to_string()))?;
+
+        if let Some(re) = &pattern
+            && re.captures_len() < 2
+        {
+            return Err(invalid(
+                "pattern needs a capture group for the value".into(),
+            ));
+        }
+
+        let label = cfg
+            .label
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string);
+
+        if label.is_none() && pattern.is_none() {
+            return Err(invalid("either label or pattern is required".into()));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            label,
+            pattern,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Find the raw value for this field in the message.
+    pub fn extract(&self, raw: &str, lines: &[&str]) -> Option<String> {
+        if let Some(re) = &self.pattern {
+            return re
+                .captures(raw)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|v| !v.is_empty());
+        }
+
+        let label = self.label.as_deref()?;
+        for (idx, line) in lines.iter().enumerate() {
+            if line.eq_ignore_ascii_case(label) {
+                return lines
+                    .get(idx + 1)
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+            }
+
+            if let Some((head, tail)) = line.split_once(':')
+                && head.trim().eq_ignore_ascii_case(label)
+            {
+                let val = tail.trim();
+                if !val.is_empty() {
+                    return Some(val.to_string());
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertTemplate {
+    pub name: String,
+    pub symbol: FieldRule,
+    pub current_price: FieldRule,
+    pub levels: Vec<FieldRule>,
+    detect: Vec<String>,
+}
+
+impl AlertTemplate {
+    pub fn compile(cfg: &AlertTemplateConfig) -> Result<Self, TemplateError> {
+        let symbol = FieldRule::compile(&cfg.name, "symbol", &cfg.symbol)?;
+        let current_price = FieldRule::compile(&cfg.name, "current_price", &cfg.current_price)?;
+
+        if cfg.levels.is_empty() {
+            return Err(TemplateError::Invalid {
+                template: cfg.name.clone(),
+                field: "levels".into(),
+                reason: "at least one level is required".into(),
+            });
+        }
+
+        let levels = cfg
+            .levels
+            .iter()
+            .map(|lvl| FieldRule::compile(&cfg.name, &lvl.name, &lvl.rule))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let detect = if cfg.detect.is_empty() {
+            [&symbol, &current_price, &levels[0]]
+                .iter()
+                .filter_map(|r| r.label.clone())
+                .collect()
+        } else {
+            cfg.detect.clone()
+        };
+
+        Ok(Self {
+            name: cfg.name.clone(),
+            symbol,
+            current_price,
+            levels,
+            detect: detect.into_iter().map(|d| d.to_ascii_lowercase()).collect(),
+        })
+    }
+
+    /// Cheap check used to decide whether a relayed message should be parsed at all.
+    pub fn matches(&self, content: &str) -> bool {
+        if self.detect.is_empty() {
+            return false;
+        }
+        let lc = content.to_ascii_lowercase();
+        self.detect.iter().all(|label| lc.contains(label))
+    }
+}
+
+/// Ordered set of templates; the first one that parses a message wins.
+#[derive(Debug, Clone)]
+pub struct AlertTemplates {
+    templates: Vec<AlertTemplate>,
+}
+
+impl Default for AlertTemplates {
+    fn default() -> Self {
+        let cfg = default_template_config();
+        let template = AlertTemplate::compile(&cfg).expect("built-in alert template is valid");
+        Self {
+            templates: vec![template],
+        }
+    }
+}
+
+impl AlertTemplates {
+    pub fn from_configs(configs: &[AlertTemplateConfig]) -> Result<Self, TemplateError> {
+        let templates = configs
+            .iter()
+            .map(AlertTemplate::compile)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { templates })
+    }
+
+    pub fn from_json(raw: &str) -> Result<Self, TemplateError> {
+        let configs: Vec<AlertTemplateConfig> = serde_json::from_str(raw)?;
+        Self::from_configs(&configs)
+    }
+
+    /// Load templates from `ALERT_TEMPLATES_PATH` (JSON file) or `ALERT_TEMPLATES` (inline JSON).
+    /// Falls back to the built-in Lambda format when neither is set. Custom templates are tried
+    /// before the built-in one.
+    pub fn from_env() -> Result<Self, TemplateError> {
+        let raw = if let Ok(path) = env::var("ALERT_TEMPLATES_PATH") {
+            Some(fs::read_to_string(&path).map_err(|e| TemplateError::Io(path, e))?)
+        } else {
+            env::var("ALERT_TEMPLATES").ok()
+        };
+
+        let Some(raw) = raw.filter(|r| !r.trim().is_empty()) else {
+            return Ok(Self::default());
+        };
+
+        let mut custom = Self::from_json(&raw)?;
+        custom.templates.extend(Self::default().templates);
+        Ok(custom)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AlertTemplate> {
+        self.templates.iter()
+    }
+
+    pub fn matches(&self, content: &str) -> bool {
+        self.templates.iter().any(|t| t.matches(content))
+    }
+}
+
+fn label(label: &str) -> FieldRuleConfig {
+    FieldRuleConfig {
+        label: Some(label.to_string()),
+        pattern: None,
+    }
+}
+
+fn level(name: &str, message_label: &str) -> LevelRuleConfig {
+    LevelRuleConfig {
+        name: name.to_string(),
+        rule: label(message_label),
+    }
+}
+
+/// The original Lambda alert layout (label line followed by value line).
+fn default_template_config() -> AlertTemplateConfig {
+    AlertTemplateConfig {
+        name: "lambda".to_string(),
+        symbol: label("Ticker"),
+        current_price: label("Current Price"),
+        levels: vec![
+            level("Lambda", "Lambda Level"),
+            level("FAIL SAFE", "Fail-Safe"),
+            level("PT1 Upside", "Upside PT1"),
+            level("PT2 Upside", "Upside PT2"),
+            level("PT3 Upside", "Upside PT3"),
+            level("PT1 Downside", "Downside PT1"),
+            level("PT2 Downside", "Downside PT2"),
+            level("PT3 Downside", "Downside PT3"),
+        ],
+        detect: vec![
+            "ticker".into(),
+            "current price".into(),
+            "lambda level".into(),
+            "fail-safe".into(),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_pattern_without_capture_group() {
+        let raw = r#"[{
+            "name": "bad",
+            "symbol": { "pattern": "\\$[A-Z]+" },
+            "current_price": { "label": "Price" },
+            "levels": [{ "name": "Target", "label": "Target" }]
+        }]"#;
+
+        let err = AlertTemplates::from_json(raw).unwrap_err();
+        assert!(err.to_string().contains("capture group"));
+    }
+
+    #[test]
+    fn extracts_inline_and_regex_fields() {
+        let raw = r#"[{
+            "name": "inline",
+            "symbol": { "pattern": "\\$([A-Z]{1,5})" },
+            "current_price": { "label": "Price" },
+            "levels": [{ "name": "Target", "label": "Target" }],
+            "detect": ["price:", "target:"]
+        }]"#;
+        let templates = AlertTemplates::from_json(raw).unwrap();
+        let template = templates.iter().next().unwrap();
+
+        let msg = "Watching $NVDA\nPrice: 120.5\nTarget: 130";
+        let lines: Vec<&str> = msg.lines().map(str::trim).collect();
+
+        assert!(template.matches(msg));
+        assert_eq!(
+            template.symbol.extract(msg, &lines).as_deref(),
+            Some("NVDA")
+        );
+        assert_eq!(
+            template.current_price.extract(msg, &lines).as_deref(),
+            Some("120.5")
+        );
+        assert_eq!(
+            template.levels[0].extract(msg, &lines).as_deref(),
+            Some("130")
+        );
+    }
+}
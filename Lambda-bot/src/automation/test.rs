@@ -1,17 +1,21 @@
 use serenity::all::{
     ChannelId, CommandInteraction, Context, CreateAllowedMentions, CreateInteractionResponse,
-    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateMessage, GuildId,
-    Interaction, InteractionResponseFlags, MessageFlags,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, GuildId, Interaction,
+    InteractionResponseFlags, MessageFlags,
 };
 use serenity::builder::{CreateCommand, GetMessages};
 use serenity::http::Http;
 
+use super::mirror::{self, MirrorStore};
+use super::relay::{self, RelayRules};
+use super::routes::RelayRoutes;
+
 pub const COMMAND_NAME: &str = "relay_messages";
 
 /// Register the slash command for a guild (faster propagation).
 pub async fn register_commands(http: &Http, guild_id: GuildId) -> serenity::Result<()> {
     let command = CreateCommand::new(COMMAND_NAME)
-        .description("Relay recent messages from SOURCE_CHANNEL_ID to TARGET_CHANNEL_ID");
+        .description("Relay recent messages for every configured relay pair");
 
     guild_id.create_command(http, command).await.map(|_| ())
 }
@@ -19,13 +23,14 @@ pub async fn register_commands(http: &Http, guild_id: GuildId) -> serenity::Resu
 pub async fn handle_interaction(
     ctx: &Context,
     interaction: &Interaction,
-    source_channel: ChannelId,
-    target_channel: ChannelId,
+    routes: &RelayRoutes,
+    rules: &RelayRules,
+    mirrors: &MirrorStore,
 ) -> serenity::Result<()> {
-    if let Interaction::Command(command) = interaction {
-        if command.data.name == COMMAND_NAME {
-            return relay_messages(ctx, command, source_channel, target_channel).await;
-        }
+    if let Interaction::Command(command) = interaction
+        && command.data.name == COMMAND_NAME
+    {
+        return relay_messages(ctx, command, routes, rules, mirrors).await;
     }
     Ok(())
 }
@@ -33,16 +38,10 @@ pub async fn handle_interaction(
 async fn relay_messages(
     ctx: &Context,
     command: &CommandInteraction,
-    source_channel: ChannelId,
-    target_channel: ChannelId,
+    routes: &RelayRoutes,
+    rules: &RelayRules,
+    mirrors: &MirrorStore,
 ) -> serenity::Result<()> {
-    let allowed = CreateAllowedMentions::new()
-        .everyone(false)
-        .all_users(false)
-        .all_roles(false)
-        .empty_users()
-        .empty_roles();
-
     // Defer early to avoid 3s timeout and "Unknown interaction".
     command
         .create_response(
@@ -53,28 +52,31 @@ async fn relay_messages(
         )
         .await?;
 
-    let messages = source_channel
-        .messages(&ctx.http, GetMessages::new().limit(20))
-        .await?;
-
-    for msg in messages.iter().rev() {
-        if msg.author.bot {
-            continue;
-        }
+    for pair in routes.all().await {
+        let source_channel = ChannelId::new(pair.source_channel_id);
+        let target_channel = ChannelId::new(pair.target_channel_id);
+        let messages = source_channel
+            .messages(&ctx.http, GetMessages::new().limit(20))
+            .await?;
+        let guild_id = source_channel
+            .to_channel(ctx)
+            .await
+            .ok()
+            .and_then(|channel| channel.guild())
+            .map(|channel| channel.guild_id);
 
-        if msg.content.trim().is_empty() {
-            continue;
+        for msg in messages.into_iter().rev() {
+            // Same path as live messages: bots skipped, each message relayed once, the relay
+            // rules applied and attachments and embeds carried over.
+            if msg.author.bot || !mirrors.claim(msg.id).await {
+                continue;
+            }
+            let msg = relay::with_member(&ctx.http, guild_id, msg).await;
+            let Some(processed) = rules.render(&msg) else {
+                continue;
+            };
+            mirror::relay_message(&ctx.http, mirrors, &msg, &processed, &[target_channel]).await;
         }
-
-        let processed = format!("[{}] {}", msg.author.name, msg.content);
-        target_channel
-            .send_message(
-                &ctx.http,
-                CreateMessage::new()
-                    .content(processed)
-                    .allowed_mentions(allowed.clone()),
-            )
-            .await?;
     }
 
     command
@@ -82,7 +84,7 @@ async fn relay_messages(
             &ctx.http,
             CreateInteractionResponseFollowup::new()
                 .content("Relay completed.")
-                .allowed_mentions(allowed)
+                .allowed_mentions(CreateAllowedMentions::new())
                 .flags(MessageFlags::EPHEMERAL),
         )
         .await?;
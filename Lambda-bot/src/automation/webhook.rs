@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::env;
+
+use serenity::all::{
+    ChannelId, CreateWebhook, EditWebhookMessage, ExecuteWebhook, Message, MessageId, Webhook,
+};
+use serenity::http::Http;
+use tokio::sync::RwLock;
+
+use super::relay::display_name;
+
+/// Name of the webhook Lambda-bot creates (and reuses) in each target channel.
+pub const WEBHOOK_NAME: &str = "Lambda relay";
+
+/// Discord rejects webhook usernames longer than this.
+const MAX_USERNAME_LEN: usize = 80;
+
+/// Relays through a per-channel webhook so copies show the original author's name and avatar.
+///
+/// Enabled with `RELAY_MODE=webhook`; the bot needs Manage Webhooks in every target channel.
+#[derive(Default)]
+pub struct RelayWebhooks {
+    webhooks: RwLock<HashMap<ChannelId, Webhook>>,
+}
+
+impl RelayWebhooks {
+    pub fn from_env() -> Option<Self> {
+        env::var("RELAY_MODE")
+            .is_ok_and(|mode| mode.trim().eq_ignore_ascii_case("webhook"))
+            .then(Self::default)
+    }
+
+    /// Find the relay webhook in `channel`, creating it on first use.
+    async fn webhook_for(&self, http: &Http, channel: ChannelId) -> serenity::Result<Webhook> {
+        if let Some(hook) = self.webhooks.read().await.get(&channel) {
+            return Ok(hook.clone());
+        }
+
+        let existing = channel
+            .webhooks(http)
+            .await?
+            .into_iter()
+            .find(|w| w.name.as_deref() == Some(WEBHOOK_NAME) && w.token.is_some());
+        let hook = match existing {
+            Some(hook) => hook,
+            None => {
+                channel
+                    .create_webhook(http, CreateWebhook::new(WEBHOOK_NAME))
+                    .await?
+            }
+        };
+
+        self.webhooks.write().await.insert(channel, hook.clone());
+        Ok(hook)
+    }
+
+    /// Post as the author of `source`; returns the created message.
+    pub async fn send(
+        &self,
+        http: &Http,
+        channel: ChannelId,
+        source: &Message,
+        builder: ExecuteWebhook,
+    ) -> serenity::Result<Message> {
+        let hook = self.webhook_for(http, channel).await?;
+        let builder = builder
+            .username(webhook_username(&display_name(source)))
+            .avatar_url(source.author.face());
+        match hook.execute(http, true, builder).await? {
+            Some(message) => Ok(message),
+            None => Err(serenity::Error::Other("webhook returned no message")),
+        }
+    }
+
+    pub async fn edit(
+        &self,
+        http: &Http,
+        channel: ChannelId,
+        message_id: MessageId,
+        builder: EditWebhookMessage,
+    ) -> serenity::Result<()> {
+        let hook = self.webhook_for(http, channel).await?;
+        hook.edit_message(http, message_id, builder)
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn delete(
+        &self,
+        http: &Http,
+        channel: ChannelId,
+        message_id: MessageId,
+    ) -> serenity::Result<()> {
+        let hook = self.webhook_for(http, channel).await?;
+        hook.delete_message(http, None, message_id).await
+    }
+}
+
+/// Webhook usernames must be 1-80 characters.
+fn webhook_username(name: &str) -> String {
+    let trimmed: String = name.trim().chars().take(MAX_USERNAME_LEN).collect();
+    if trimmed.is_empty() {
+        WEBHOOK_NAME.to_string()
+    } else {
+        trimmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usernames_are_clamped() {
+        assert_eq!(webhook_username("  alice "), "alice");
+        assert_eq!(webhook_username(""), WEBHOOK_NAME);
+        assert_eq!(webhook_username(&"x".repeat(100)).len(), MAX_USERNAME_LEN);
+    }
+}
@@ -7,7 +7,6 @@ use std::sync::Arc;
 use std::time::Duration;
 
 pub struct PriceService {
-    auth: Arc<YahooAuthManager>,
     client: Arc<YahooFinanceClient>,
 }
 
@@ -21,7 +20,7 @@ impl PriceService {
 
         auth.refresh().await?;
 
-        Ok(Self { auth, client })
+        Ok(Self { client })
     }
 
     /// Stream price updates for a list of symbols at the given interval.
@@ -1,3 +1,5 @@
+#![allow(non_snake_case)]
+
 pub mod automation;
 pub mod finance;
 pub mod models;
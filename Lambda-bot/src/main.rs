@@ -1,16 +1,30 @@
-mod automation;
-mod finance;
-mod models;
-
-use automation::{price::PriceAlertManager, test};
-use finance::price::PriceService;
+use Lambda_bot::automation::{
+    alert_menu,
+    mirror::{self, MirrorStore},
+    price::PriceAlertManager,
+    relay::{self, DEFAULT_TEMPLATE, RelayRules, WEBHOOK_TEMPLATE},
+    routes,
+    routes::RelayRoutes,
+    template::AlertTemplates,
+    test,
+    webhook::RelayWebhooks,
+};
+use Lambda_bot::finance::price::PriceService;
 use serenity::all::{
-    ChannelId, Client, Context, CreateAllowedMentions, CreateMessage, EventHandler, GatewayIntents,
-    GuildId, Http, Interaction, Message, Ready,
+    ChannelId, Client, ConnectionStage, Context, CreateAllowedMentions, CreateMessage,
+    EventHandler, GatewayIntents, GuildId, Http, Interaction, Message, MessageId,
+    MessageUpdateEvent, Ready, ShardStageUpdateEvent,
 };
-use serenity::builder::GetMessages;
 use serenity::async_trait;
 use stacks_bot::service::caching::RedisCache;
+use stacks_bot::service::config::{
+    ErrorReportingConfig, HealthConfig, RedisConfig, ShutdownConfig, StorageConfig,
+};
+use stacks_bot::service::error_reporting;
+use stacks_bot::service::health;
+use stacks_bot::service::metrics::METRICS;
+use stacks_bot::service::shutdown::{self, Shutdown};
+use stacks_bot::service::store;
 use std::env;
 use std::sync::Arc;
 
@@ -23,12 +37,6 @@ fn read_env_var(key: &str) -> Result<String, Box<dyn std::error::Error>> {
     Ok(trimmed.to_string())
 }
 
-fn read_env_u64(key: &str) -> Result<u64, Box<dyn std::error::Error>> {
-    let val = read_env_var(key)?;
-    val.parse::<u64>()
-        .map_err(|e| format!("{key} must be a numeric ID: {e}").into())
-}
-
 fn disallow_mentions() -> CreateAllowedMentions {
     CreateAllowedMentions::new()
         .everyone(false) // block @everyone/@here
@@ -38,106 +46,169 @@ fn disallow_mentions() -> CreateAllowedMentions {
         .empty_roles()
 }
 
-fn looks_like_price_alert(content: &str) -> bool {
-    let lc = content.to_ascii_lowercase();
-    ["ticker", "current price", "lambda level", "fail-safe"]
-        .iter()
-        .all(|label| lc.contains(label))
-}
-
 struct Handler {
-    source_channel: ChannelId,
-    target_channel: ChannelId,
+    routes: Arc<RelayRoutes>,
+    rules: Arc<RelayRules>,
+    mirrors: Arc<MirrorStore>,
     register_guild: Option<GuildId>,
     price_manager: Option<Arc<PriceAlertManager>>,
+    auto_register_alerts: bool,
 }
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, _data: Ready) {
+        METRICS.set_shard_connected(ctx.shard_id.0, true);
         if let Some(guild_id) = self.register_guild {
             if let Err(err) = test::register_commands(&ctx.http, guild_id).await {
                 tracing::error!(?err, "failed to register slash commands");
             }
+            if let Err(err) = alert_menu::register_command(&ctx.http, guild_id).await {
+                tracing::error!(?err, "failed to register price alert context menu");
+            }
+            if let Err(err) = routes::register_command(&ctx.http, guild_id).await {
+                tracing::error!(?err, "failed to register relay pair command");
+            }
         } else {
             tracing::warn!("No REGISTER_GUILD_ID set; slash command not registered");
         }
     }
 
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        METRICS.set_shard_connected(event.shard_id.0, event.new == ConnectionStage::Connected);
+    }
+
     async fn message(&self, ctx: Context, msg: Message) {
         if msg.author.bot {
             return;
         }
 
-        if msg.channel_id != self.source_channel {
+        let targets = self.routes.targets_for(msg.channel_id).await;
+        if targets.is_empty() {
             return;
         }
+        let source_channel = msg.channel_id;
 
-        // Always operate on the latest message in the source channel to avoid stale processing.
-        let latest = match self
-            .source_channel
-            .messages(&ctx.http, GetMessages::new().limit(1))
-            .await
-            .ok()
-            .and_then(|mut msgs| msgs.pop())
-        {
-            Some(latest) if !latest.author.bot => latest,
-            _ => return,
-        };
+        // Handle the triggering message itself, once; redelivered events are ignored.
+        if !self.mirrors.claim(msg.id).await {
+            return;
+        }
 
-        if let (Some(manager), Some(guild_id)) = (&self.price_manager, latest.guild_id) {
-            if looks_like_price_alert(&latest.content) {
-                match manager
-                    .register_from_message(&latest.content, guild_id, self.target_channel)
-                    .await
-                {
-                    Ok(alert) => {
-                        let _ = self
-                            .source_channel
-                            .send_message(
-                                &ctx.http,
-                                CreateMessage::new()
-                                    .content(format!(
-                                        "Registered price alert for {} ({} levels)",
-                                        alert.symbol,
-                                        alert.levels.len()
-                                    ))
-                                    .allowed_mentions(disallow_mentions()),
-                            )
-                            .await;
-                    }
+        if let (Some(manager), Some(guild_id)) = (&self.price_manager, msg.guild_id)
+            && self.auto_register_alerts
+            && manager.templates().matches(&msg.content)
+        {
+            // Each pair gets its own alert so levels fire into every mirrored channel.
+            for target_channel in &targets {
+                let result = manager
+                    .register_from_message(&msg.content, guild_id, *target_channel, msg.author.id)
+                    .await;
+                let reply = match &result {
+                    Ok(alert) => format!(
+                        "Registered price alert for {} ({} levels)",
+                        alert.symbol,
+                        alert.levels.len()
+                    ),
                     Err(err) => {
                         tracing::warn!(?err, "failed to register price alert from message");
+                        format!("Could not register price alert: {err}")
                     }
+                };
+                let _ = source_channel
+                    .send_message(
+                        &ctx.http,
+                        CreateMessage::new()
+                            .content(reply)
+                            .allowed_mentions(disallow_mentions()),
+                    )
+                    .await;
+                // The message parses the same way for every pair, so report a failure once.
+                if result.is_err() {
+                    break;
                 }
             }
         }
 
-        let processed = format!("[{}] {}", latest.author.name, latest.content);
+        let Some(processed) = self.rules.render(&msg) else {
+            return;
+        };
 
-        let allowed = disallow_mentions();
+        mirror::relay_message(&ctx.http, &self.mirrors, &msg, &processed, &targets).await;
+    }
 
-        if let Err(err) = self
-            .target_channel
-            .send_message(
-                &ctx.http,
-                CreateMessage::new()
-                    .content(processed)
-                    .allowed_mentions(allowed),
-            )
-            .await
-        {
-            tracing::error!(?err, "failed to relay message");
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        _new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        // Embed unfurls also arrive as updates; only content edits need propagating.
+        if event.content.is_none() || self.routes.targets_for(event.channel_id).await.is_empty() {
+            return;
+        }
+
+        let msg = match event.channel_id.message(&ctx.http, event.id).await {
+            Ok(msg) if !msg.author.bot => relay::with_member(&ctx.http, event.guild_id, msg).await,
+            Ok(_) => return,
+            Err(err) => {
+                tracing::warn!(?err, "failed to fetch edited message");
+                return;
+            }
+        };
+        mirror::propagate_edit(&ctx.http, &self.mirrors, &self.rules, &msg).await;
+    }
+
+    async fn message_delete(
+        &self,
+        ctx: Context,
+        channel_id: ChannelId,
+        deleted_message_id: MessageId,
+        _guild_id: Option<GuildId>,
+    ) {
+        if self.routes.targets_for(channel_id).await.is_empty() {
+            return;
+        }
+        mirror::propagate_delete(&ctx.http, &self.mirrors, deleted_message_id).await;
+    }
+
+    async fn message_delete_bulk(
+        &self,
+        ctx: Context,
+        channel_id: ChannelId,
+        multiple_deleted_messages_ids: Vec<MessageId>,
+        _guild_id: Option<GuildId>,
+    ) {
+        if self.routes.targets_for(channel_id).await.is_empty() {
+            return;
+        }
+        for id in multiple_deleted_messages_ids {
+            mirror::propagate_delete(&ctx.http, &self.mirrors, id).await;
         }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         if let Err(err) =
-            test::handle_interaction(&ctx, &interaction, self.source_channel, self.target_channel)
+            test::handle_interaction(&ctx, &interaction, &self.routes, &self.rules, &self.mirrors)
                 .await
         {
             tracing::error!(?err, "failed to handle interaction");
         }
+
+        if let Err(err) = alert_menu::handle_interaction(
+            &ctx,
+            &interaction,
+            self.price_manager.as_deref(),
+            &self.routes,
+        )
+        .await
+        {
+            tracing::error!(?err, "failed to handle price alert context menu");
+        }
+
+        if let Err(err) = routes::handle_interaction(&ctx, &interaction, &self.routes).await {
+            tracing::error!(?err, "failed to handle relay pair command");
+        }
     }
 }
 
@@ -150,45 +221,111 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     let token = read_env_var("DISCORD_TOKEN")?;
-    let source_channel = read_env_u64("SOURCE_CHANNEL_ID")?;
-    let target_channel = read_env_u64("TARGET_CHANNEL_ID")?;
+    let static_pairs = RelayRoutes::pairs_from_env()?;
+    let webhooks = RelayWebhooks::from_env();
+    let rules = Arc::new(RelayRules::from_env(if webhooks.is_some() {
+        WEBHOOK_TEMPLATE
+    } else {
+        DEFAULT_TEMPLATE
+    })?);
     let register_guild = env::var("REGISTER_GUILD_ID")
         .ok()
         .and_then(|v| v.trim().parse::<u64>().ok())
         .map(GuildId::new);
+    // Channel-wide detection of alert-shaped messages; the context menu works regardless.
+    let auto_register_alerts = env::var("AUTO_REGISTER_ALERTS")
+        .map(|v| v != "0")
+        .unwrap_or(true);
+    let templates = AlertTemplates::from_env()?;
+    let health_config = HealthConfig::from_env()?;
+    let shutdown_config = ShutdownConfig::from_env()?;
+    let storage_config = StorageConfig::from_env()?;
+    let redis_config = RedisConfig::from_env();
+    let _error_reporting = error_reporting::init(&ErrorReportingConfig::from_env()?);
     let http = Arc::new(Http::new(&token));
+    let shutdown = Shutdown::new();
+
+    let cache: Option<Arc<RedisCache>> = match RedisCache::from_config(&redis_config).await {
+        Ok(c) => {
+            tracing::info!("Connected to Redis");
+            Some(Arc::new(c))
+        }
+        Err(err) => {
+            tracing::info!("Redis cache disabled: {err}");
+            None
+        }
+    };
+    let store = store::open(&storage_config, cache.clone()).await?;
 
     let price_manager: Option<Arc<PriceAlertManager>> = match PriceService::new().await {
         Ok(price_service) => {
             let price_service = Arc::new(price_service);
-            let cache: Option<Arc<RedisCache>> = None;
-            Some(Arc::new(PriceAlertManager::new(
-                http.clone(),
-                price_service,
-                cache,
-            )))
+            Some(Arc::new(
+                PriceAlertManager::new(http.clone(), price_service, store.clone())
+                    .with_templates(templates)
+                    .with_shutdown(shutdown.clone()),
+            ))
         }
         Err(err) => {
-            tracing::warn!(?err, "Price alerts disabled: failed to initialize PriceService");
+            tracing::warn!(
+                ?err,
+                "Price alerts disabled: failed to initialize PriceService"
+            );
             None
         }
     };
 
-    let intents = GatewayIntents::GUILDS
-        | GatewayIntents::GUILD_MESSAGES
-        | GatewayIntents::MESSAGE_CONTENT;
+    if let Some(manager) = &price_manager {
+        match manager.hydrate().await {
+            Ok(count) => tracing::info!("Resumed {count} persisted price alert(s)"),
+            Err(err) => tracing::warn!(?err, "failed to hydrate price alerts"),
+        }
+    }
+
+    if webhooks.is_some() {
+        tracing::info!("Relaying through channel webhooks (RELAY_MODE=webhook)");
+    }
+    health::spawn_health_server(health_config.addr, cache.clone(), &shutdown);
+    let mirrors = Arc::new(MirrorStore::new(cache.clone()).with_webhooks(webhooks));
+    let routes = Arc::new(RelayRoutes::new(static_pairs, cache));
+    match routes.hydrate().await {
+        Ok(count) if count > 0 => tracing::info!("Loaded {count} relay pair(s) from Redis"),
+        Ok(_) => {}
+        Err(err) => tracing::warn!(?err, "failed to load relay pairs from Redis"),
+    }
+    if routes.is_empty().await {
+        tracing::warn!(
+            "No relay pairs configured; set SOURCE_CHANNEL_ID/TARGET_CHANNEL_ID, RELAY_PAIRS, or use /relay-pair add"
+        );
+    }
+
+    let intents =
+        GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
 
     let handler = Handler {
-        source_channel: ChannelId::new(source_channel),
-        target_channel: ChannelId::new(target_channel),
+        routes,
+        rules,
+        mirrors,
         register_guild,
         price_manager,
+        auto_register_alerts,
     };
 
     let mut client = Client::builder(token, intents)
         .event_handler(handler)
         .await?;
 
-    client.start().await?;
+    // On SIGTERM/Ctrl-C: disconnect, then give alert streams time to finish persisting.
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        shutdown::wait_for_signal().await;
+        tracing::info!("Shutdown signal received; disconnecting from Discord");
+        shard_manager.shutdown_all().await;
+    });
+
+    let result = client.start().await;
+    shutdown.shutdown(shutdown::DEFAULT_GRACE_PERIOD).await;
+    shutdown::post_shutdown_notice(&http, &shutdown_config).await;
+    result?;
     Ok(())
 }
@@ -3,9 +3,11 @@ use futures_util::StreamExt;
 use std::time::Duration;
 use tokio::time::timeout;
 
+/// This test makes a real network call to Yahoo Finance. Run manually with:
+/// `cargo test -p Lambda-bot --test price_stream -- --ignored`.
 #[tokio::test]
+#[ignore = "requires network access to Yahoo Finance"]
 async fn stream_prices_returns_data() {
-    // This test makes a real network call to Yahoo Finance.
     let service = PriceService::new()
         .await
         .expect("failed to initialize PriceService");
@@ -0,0 +1,25 @@
+-- State shared by the SQL store backends. Keep statements portable between SQLite and
+-- Postgres: ids are BIGINT, dates ISO-8601 TEXT and nested data JSON TEXT.
+
+CREATE TABLE IF NOT EXISTS guild_command_roles (
+    guild_id BIGINT NOT NULL,
+    command TEXT NOT NULL,
+    roles TEXT NOT NULL,
+    PRIMARY KEY (guild_id, command)
+);
+
+CREATE TABLE IF NOT EXISTS guild_paused_jobs (
+    guild_id BIGINT NOT NULL,
+    job TEXT NOT NULL,
+    PRIMARY KEY (guild_id, job)
+);
+
+CREATE TABLE IF NOT EXISTS earnings_threads (
+    day TEXT PRIMARY KEY,
+    thread_id BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS price_alerts (
+    symbol TEXT PRIMARY KEY,
+    alerts TEXT NOT NULL
+);
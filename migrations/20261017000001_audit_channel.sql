@@ -0,0 +1,5 @@
+-- Channel each guild's audit log posts to (`/config audit-channel`).
+CREATE TABLE IF NOT EXISTS guild_audit_channels (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Language each guild answers in (`/config language`); absent means English.
+CREATE TABLE IF NOT EXISTS guild_locales (
+    guild_id BIGINT PRIMARY KEY,
+    locale TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Timezone for plain-text times (`/config timezone`); absent means US Eastern.
+CREATE TABLE IF NOT EXISTS guild_timezones (
+    guild_id BIGINT PRIMARY KEY,
+    timezone TEXT NOT NULL
+);
+
+-- A user's own timezone (`/timezone`), which overrides the guild's.
+CREATE TABLE IF NOT EXISTS user_timezones (
+    user_id BIGINT PRIMARY KEY,
+    timezone TEXT NOT NULL
+);
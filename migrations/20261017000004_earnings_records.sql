@@ -0,0 +1,8 @@
+-- What the daily and post-earnings reports saw per report (implied move, actuals, price
+-- reaction), as JSON, for the Friday summary. Pruned after two weeks.
+CREATE TABLE IF NOT EXISTS earnings_records (
+    day TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    record TEXT NOT NULL,
+    PRIMARY KEY (day, symbol)
+);
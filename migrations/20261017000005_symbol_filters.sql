@@ -0,0 +1,5 @@
+-- Symbol allow/deny lists (`/config symbols`) as JSON; absent means every symbol is shown.
+CREATE TABLE IF NOT EXISTS guild_symbol_filters (
+    guild_id BIGINT PRIMARY KEY,
+    filter TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Channel whose topic shows index levels (`/config topic-channel`).
+CREATE TABLE IF NOT EXISTS guild_topic_channels (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Symbol commands in a channel default to (`/bind-ticker`).
+CREATE TABLE IF NOT EXISTS channel_tickers (
+    channel_id BIGINT PRIMARY KEY,
+    ticker TEXT NOT NULL
+);
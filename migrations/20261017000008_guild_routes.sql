@@ -0,0 +1,7 @@
+-- Channel each category of a guild's automation posts goes to (`/config routes`).
+CREATE TABLE IF NOT EXISTS guild_routes (
+    guild_id BIGINT NOT NULL,
+    route TEXT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, route)
+);
@@ -0,0 +1,8 @@
+-- Institutional ownership per symbol and 13F quarter end, as JSON, for `/holders-trend`.
+-- Only the latest eight quarters of a symbol are kept.
+CREATE TABLE IF NOT EXISTS ownership_snapshots (
+    symbol TEXT NOT NULL,
+    quarter TEXT NOT NULL,
+    snapshot TEXT NOT NULL,
+    PRIMARY KEY (symbol, quarter)
+);
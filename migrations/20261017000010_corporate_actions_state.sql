@@ -0,0 +1,7 @@
+-- Dividend and split state per symbol, as JSON, that the split and dividend alerts compare
+-- the next run against. `updated` is the ISO day it was stored; rows are pruned after 90 days.
+CREATE TABLE IF NOT EXISTS corporate_actions_state (
+    symbol TEXT NOT NULL PRIMARY KEY,
+    state TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
@@ -0,0 +1,6 @@
+-- `/event-alert` definitions per symbol, as JSON. The snapshots each alert compares against
+-- are not stored here; they live in Redis, or in memory without it.
+CREATE TABLE IF NOT EXISTS event_alerts (
+    symbol TEXT PRIMARY KEY,
+    alerts TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- `/brief` subscriptions per user, as JSON, including the local date of the last brief sent.
+CREATE TABLE IF NOT EXISTS morning_briefs (
+    user_id BIGINT PRIMARY KEY,
+    brief TEXT NOT NULL
+);
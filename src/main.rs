@@ -1,36 +1,62 @@
 #![allow(non_snake_case)]
 
-use std::{env, sync::Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{sync::Arc, time::Instant};
 
 use anyhow::Result;
 use dotenv::dotenv;
 use serenity::all::{
-    ApplicationId, Command, CreateAttachment, CreateCommand, CreateInteractionResponse,
-    CreateInteractionResponseMessage, CreateMessage, EditAttachments, EditMessage, GatewayIntents,
-    GuildId, Interaction, Message,
+    ApplicationId, ConnectionStage, CreateMessage, EditAttachments, EditMessage, GatewayIntents,
+    GuildId, Interaction, Message, ShardStageUpdateEvent,
 };
 use serenity::{async_trait, model::gateway::Ready, prelude::*, Client};
-use tracing::info;
+use tracing::{info, warn};
 
-use stacks_bot::models::StatementType;
-use stacks_bot::service::automation::{earnings, options_data};
+use stacks_bot::service::automation::{
+    channel_topic, corporate_actions, earnings, event_alerts, insider_buying, leader,
+    morning_brief, options_data, presence, quote_board,
+};
 use stacks_bot::service::caching::RedisCache;
-use stacks_bot::service::command::earnings as earnings_cmd;
-use stacks_bot::service::command::fundamentals as fundamentals_cmd;
-use stacks_bot::service::command::holders as holders_cmd;
+use stacks_bot::service::chunks;
+use stacks_bot::service::command::framework::{CommandRegistry, Invoker};
 use stacks_bot::service::command::mention as mention_cmd;
-use stacks_bot::service::command::news as news_cmd;
-use stacks_bot::service::command::quotes as quotes_cmd;
+use stacks_bot::service::command::stats as stats_cmd;
+use stacks_bot::service::config::{Config, ConfigHandle};
+use stacks_bot::service::error_reporting;
 use stacks_bot::service::finance::FinanceService;
+use stacks_bot::service::health;
+use stacks_bot::service::metrics::METRICS;
+use stacks_bot::service::shutdown::{self, Shutdown};
+use stacks_bot::service::store::{self, StateStore};
+use stacks_bot::service::timefmt;
 
 struct Handler {
     finance: Arc<FinanceService>,
     cache: Option<Arc<RedisCache>>,
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: Shutdown,
+    commands: CommandRegistry,
+    /// Set by the first shard to become ready; commands and automations start once.
+    started: AtomicBool,
 }
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
+        METRICS.set_shard_connected(ctx.shard_id.0, true);
+        let total = ready.shard.map_or(1, |shard| shard.total);
+        info!(
+            "Shard {}/{} ready with {} guild(s)",
+            ctx.shard_id.0,
+            total,
+            ready.guilds.len()
+        );
+        // `ready` fires for every shard and again after each full reconnect.
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
         // Determine if we should use guild commands (dev) or global commands (prod)
         #[cfg(debug_assertions)]
         let use_guild_commands = true;
@@ -39,54 +65,11 @@ impl EventHandler for Handler {
 
         if use_guild_commands {
             // Development: Register guild commands (instant!) to multiple servers
-            let guild_ids_str = env::var("GUILD_IDS")
-                .unwrap_or_else(|_| env::var("GUILD_ID").unwrap_or_default());
-            
-            let guild_ids: Vec<GuildId> = guild_ids_str
-                .split(',')
-                .filter_map(|id| id.trim().parse::<u64>().ok())
-                .map(GuildId::new)
-                .collect();
+            let guild_ids: Vec<GuildId> = self.config.current().guild_ids();
 
             if !guild_ids.is_empty() {
                 for guild_id in guild_ids.iter() {
-                    let _ = guild_id.create_command(&ctx.http, ping_command()).await;
-                    let _ = guild_id
-                        .create_command(
-                            &ctx.http,
-                            fundamentals_cmd::register_command(StatementType::IncomeStatement),
-                        )
-                        .await;
-                    let _ = guild_id
-                        .create_command(
-                            &ctx.http,
-                            fundamentals_cmd::register_command(StatementType::BalanceSheet),
-                        )
-                        .await;
-                    let _ = guild_id
-                        .create_command(
-                            &ctx.http,
-                            fundamentals_cmd::register_command(StatementType::CashFlow),
-                        )
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, quotes_cmd::register_command())
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, holders_cmd::register_command())
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, news_cmd::register_command())
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, earnings_cmd::register_weekly_command())
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, earnings_cmd::register_daily_command())
-                        .await;
-                    let _ = guild_id
-                        .create_command(&ctx.http, earnings_cmd::register_after_daily_command())
-                        .await;
+                    self.commands.register_guild(&ctx.http, *guild_id).await;
                     info!("Guild commands registered for guild ID: {}", guild_id);
                 }
                 info!(
@@ -99,11 +82,11 @@ impl EventHandler for Handler {
                     "{} is connected. [DEV MODE] No GUILD_IDS found, falling back to global commands.",
                     ready.user.name
                 );
-                register_global_commands(&ctx).await;
+                self.commands.register_global(&ctx.http).await;
             }
         } else {
             // Production: Register global commands (takes up to 1 hour)
-            register_global_commands(&ctx).await;
+            self.commands.register_global(&ctx.http).await;
             info!(
                 "{} is connected. [PRODUCTION MODE] Global commands registered (may take up to 1 hour).",
                 ready.user.name
@@ -111,206 +94,133 @@ impl EventHandler for Handler {
         }
 
         // Start SPY options pinger (every 15 minutes) if configured
-        options_data::spawn_options_pinger(ctx.http.clone(), self.finance.clone(), self.cache.clone());
+        options_data::spawn_options_pinger(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.cache.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
         // Start daily earnings poster
-        earnings::spawn_earnings_poster(ctx.http.clone(), self.finance.clone());
+        earnings::spawn_earnings_poster(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
         // Start daily earnings (IV/IM) poster at 6pm ET
-        earnings::spawn_daily_report_poster(ctx.http.clone(), self.finance.clone());
+        earnings::spawn_daily_report_poster(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
         // Start post-earnings (actuals) poster at 8:45am ET (BMO) and 5:50pm ET (AMC)
-        earnings::spawn_after_daily_poster(ctx.http.clone(), self.finance.clone());
+        earnings::spawn_after_daily_poster(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
+        // Start the weekly earnings summary at 7pm ET on Fridays
+        earnings::spawn_weekly_summary_poster(
+            ctx.http.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
+        // Start the weekly insider net-buying scan at 10am ET on Saturdays
+        insider_buying::spawn_insider_scanner(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
+        // Start the split and dividend alerts at 8am ET on weekdays
+        corporate_actions::spawn_corporate_actions_watcher(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
+        // Check /event-alert earnings dates and analyst targets at 8:10am ET on weekdays
+        event_alerts::spawn_event_alert_watcher(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.cache.clone(),
+            self.store.clone(),
+            &self.shutdown,
+        );
+        // DM each /brief subscriber their morning brief at their chosen time on weekdays
+        morning_brief::spawn_morning_brief_sender(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            &self.shutdown,
+        );
+        // Start the live quote board (edited in place during market hours) if configured
+        quote_board::spawn_quote_board(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.cache.clone(),
+            self.config.clone(),
+            &self.shutdown,
+        );
+        // Keep index levels in the topic of each guild's `/config topic-channel`
+        channel_topic::spawn_topic_updater(
+            ctx.http.clone(),
+            self.finance.clone(),
+            self.store.clone(),
+            &self.shutdown,
+        );
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         if let Interaction::Command(command) = interaction {
-            match command.data.name.as_str() {
-                "ping" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new().content("Pong!"),
-                            ),
-                        )
-                        .await;
-                }
-                "income" | "balance" | "cashflow" => {
-                    // Defer immediately to avoid 3-second timeout
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    // Now we have up to 15 minutes to send the actual response
-                    let content = match fundamentals_cmd::handle(&command, &self.finance).await {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    // Send the follow-up message
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                "quote" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let content = match quotes_cmd::handle(&command, &self.finance).await {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                "holders" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let content = match holders_cmd::handle(&command, &self.finance).await {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                "news" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let content = match news_cmd::handle(&command, &self.finance).await {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                "weekly-earnings" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let response = match earnings_cmd::handle_weekly(&command, &self.finance).await
-                    {
-                        Ok(resp) => resp,
-                        Err(err) => {
-                            let _ = command
-                                .edit_response(
-                                    &ctx.http,
-                                    serenity::all::EditInteractionResponse::new()
-                                        .content(format!("❌ {}", err)),
-                                )
-                                .await;
-                            return;
-                        }
-                    };
-
-                    let mut edit =
-                        serenity::all::EditInteractionResponse::new().content(response.content);
-
-                    if let Some(bytes) = response.image {
-                        let attachment = CreateAttachment::bytes(bytes, "earnings-calendar.png");
-                        let attachments = EditAttachments::new().add(attachment);
-                        edit = edit.attachments(attachments);
-                    }
-
-                    let _ = command.edit_response(&ctx.http, edit).await;
-                }
-                "daily-earnings" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let content = match earnings_cmd::handle_daily(
-                        &command,
-                        &self.finance,
-                        &ctx.http,
-                    )
-                    .await
-                    {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                "er-reports" => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Defer(Default::default()),
-                        )
-                        .await;
-
-                    let content =
-                        match earnings_cmd::handle_after_daily(&command, &self.finance, &ctx.http)
-                            .await
-                        {
-                        Ok(msg) => msg,
-                        Err(err) => format!("❌ {}", err),
-                    };
-
-                    let _ = command
-                        .edit_response(
-                            &ctx.http,
-                            serenity::all::EditInteractionResponse::new().content(content),
-                        )
-                        .await;
-                }
-                _ => {
-                    let _ = command
-                        .create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("Command not implemented."),
-                            ),
-                        )
-                        .await;
-                }
+            let started = Instant::now();
+            let ok = self
+                .commands
+                .dispatch(
+                    &ctx,
+                    &command,
+                    &self.finance,
+                    self.cache.as_deref(),
+                    self.store.as_deref(),
+                    &self.config,
+                )
+                .await;
+            let elapsed = started.elapsed();
+            METRICS.record_command(&command.data.name, elapsed, ok);
+            if let Some(cache) = &self.cache {
+                stats_cmd::record(cache, &command, elapsed, ok).await;
             }
+        } else if let Interaction::Component(component) = interaction {
+            self.commands
+                .dispatch_component(
+                    &ctx,
+                    &component,
+                    &self.finance,
+                    self.cache.as_deref(),
+                    self.store.as_deref(),
+                    &self.config,
+                )
+                .await;
+        }
+    }
+
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        let connected = event.new == ConnectionStage::Connected;
+        METRICS.set_shard_connected(event.shard_id.0, connected);
+        if connected {
+            info!("Shard {} connected", event.shard_id.0);
+        } else if event.old == ConnectionStage::Connected {
+            warn!("Shard {} disconnected ({:?})", event.shard_id.0, event.new);
         }
     }
 
@@ -320,11 +230,16 @@ impl EventHandler for Handler {
         }
 
         let bot_id = ctx.cache.current_user().id;
-        let prefixes = [format!("<@{}>", bot_id), format!("<@!{}>", bot_id)];
-
         let content = msg.content.trim();
-        let rest = match prefixes.iter().find_map(|p| content.strip_prefix(p)) {
-            Some(r) => r.trim(),
+        let is_dm = msg.guild_id.is_none();
+        // Attachment- or sticker-only DMs have no text to answer.
+        if is_dm && content.is_empty() {
+            return;
+        }
+        let rest = match mention_cmd::strip_mention(content, bot_id) {
+            Some(r) => r,
+            // DMs are already addressed to the bot, so the mention is optional there.
+            None if is_dm => content,
             None => return, // ignore messages that don't start with a mention of the bot
         };
 
@@ -333,7 +248,21 @@ impl EventHandler for Handler {
             return;
         }
 
-        match mention_cmd::handle(rest, &ctx.http, msg.channel_id, &self.finance).await {
+        let timezone =
+            timefmt::preferred_timezone(self.store.as_deref(), msg.guild_id, msg.author.id).await;
+        let invoker = Invoker::from_message(&ctx, &msg).await;
+        let reply = mention_cmd::handle(
+            rest,
+            &ctx.http,
+            msg.channel_id,
+            &self.finance,
+            self.store.as_deref(),
+            invoker.as_ref(),
+            timezone,
+            &self.config.current().images,
+        )
+        .await;
+        match reply {
             Ok(resp) => {
                 // Send a placeholder message immediately, then edit with the real response.
                 let mut placeholder = match msg
@@ -348,9 +277,14 @@ impl EventHandler for Handler {
                     }
                 };
 
-                let mut edit = EditMessage::new().content(resp.content);
-                if let Some(attachment) = resp.attachment {
-                    let attachments = EditAttachments::new().add(attachment);
+                // Long replies continue in further messages after the edited placeholder.
+                let mut parts = chunks::split(&resp.content, chunks::MESSAGE_LIMIT).into_iter();
+                let mut edit = EditMessage::new().content(parts.next().unwrap_or_default());
+                if !resp.attachments.is_empty() {
+                    let attachments = resp
+                        .attachments
+                        .into_iter()
+                        .fold(EditAttachments::new(), EditAttachments::add);
                     edit = edit.attachments(attachments);
                 }
 
@@ -358,92 +292,130 @@ impl EventHandler for Handler {
                     let _ = msg
                         .reply(&ctx.http, format!("❌ failed to edit message: {}", err))
                         .await;
+                    return;
+                }
+                for part in parts {
+                    if let Err(err) = msg.channel_id.say(&ctx.http, part).await {
+                        warn!("failed to send mention reply continuation: {err}");
+                        break;
+                    }
                 }
             }
             Err(err) => {
-                let _ = msg.reply(&ctx.http, format!("❌ {}", err)).await;
+                err.log(if is_dm { "dm" } else { "mention" });
+                let _ = msg.reply(&ctx.http, err.user_message()).await;
             }
         }
     }
 }
 
-// Helper function to register all global commands
-async fn register_global_commands(ctx: &Context) {
-    let _ = Command::create_global_command(&ctx.http, ping_command()).await;
-    let _ = Command::create_global_command(
-        &ctx.http,
-        fundamentals_cmd::register_command(StatementType::IncomeStatement),
-    )
-    .await;
-    let _ = Command::create_global_command(
-        &ctx.http,
-        fundamentals_cmd::register_command(StatementType::BalanceSheet),
-    )
-    .await;
-    let _ = Command::create_global_command(
-        &ctx.http,
-        fundamentals_cmd::register_command(StatementType::CashFlow),
-    )
-    .await;
-    let _ = Command::create_global_command(&ctx.http, quotes_cmd::register_command()).await;
-    let _ = Command::create_global_command(&ctx.http, holders_cmd::register_command()).await;
-    let _ = Command::create_global_command(&ctx.http, news_cmd::register_command()).await;
-    let _ = Command::create_global_command(&ctx.http, earnings_cmd::register_weekly_command())
-        .await;
-    let _ = Command::create_global_command(&ctx.http, earnings_cmd::register_daily_command())
-        .await;
-    let _ = Command::create_global_command(
-        &ctx.http,
-        earnings_cmd::register_after_daily_command(),
-    )
-    .await;
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv().ok();
     tracing_subscriber::fmt::init();
 
-    let token = env::var("DISCORD_TOKEN")?;
-    let app_id_raw: u64 = env::var("APPLICATION_ID")?.parse()?;
-    let app_id: ApplicationId = app_id_raw.into();
+    let config = ConfigHandle::new(Config::load()?);
+    let startup = config.current();
+    let _error_reporting = error_reporting::init(&startup.errors);
+    let app_id = ApplicationId::new(startup.discord.application_id);
 
-    let intents = GatewayIntents::GUILDS
+    let mut intents = GatewayIntents::GUILDS
         | GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::DIRECT_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT;
-
-    info!("Initializing FinanceService...");
-    let finance = Arc::new(FinanceService::new(None)?);
+    if startup.discord.disable_message_content {
+        // Discord still sends the content of DMs and of messages that mention the bot, which
+        // is all the mention handler reads.
+        intents.remove(GatewayIntents::MESSAGE_CONTENT);
+        info!("Running without the Message Content intent");
+    }
 
     info!("Initializing Redis cache (optional)...");
-    let cache = match RedisCache::from_env().await {
-        Ok(c) => {
-            info!("Connected to Redis cache");
-            Some(Arc::new(c))
-        }
-        Err(err) => {
-            info!("Redis cache disabled: {err}");
+    let cache = match startup.redis.url.as_deref() {
+        Some(url) => match RedisCache::new(url).await {
+            Ok(c) => {
+                info!("Connected to Redis cache");
+                Some(Arc::new(c))
+            }
+            Err(err) => {
+                info!("Redis cache disabled: {err}");
+                None
+            }
+        },
+        None => {
+            info!("Redis cache disabled: redis.url (REDIS_URL) not set");
             None
         }
     };
 
+    info!("Initializing FinanceService...");
+    let mut finance =
+        FinanceService::new(None)?.with_constituent_sources(startup.constituents.clone());
+    if let Some(cache) = &cache {
+        finance = finance.with_cache(cache.as_ref().clone());
+    }
+    let finance = Arc::new(finance);
+    let presence_finance = finance.clone();
+
+    let store = store::open(&startup.storage, cache.clone()).await?;
+
+    let shutdown = Shutdown::new();
+    health::spawn_health_server(startup.health.addr, cache.clone(), &shutdown);
+    leader::spawn_leader_election(cache.clone(), &shutdown);
+
     info!("Starting Discord client...");
-    let mut client = Client::builder(token, intents)
+    let mut client = Client::builder(&startup.discord.token, intents)
         .application_id(app_id)
         .event_handler(Handler {
             finance,
             cache,
+            store,
+            config: config.clone(),
+            shutdown: shutdown.clone(),
+            commands: CommandRegistry::standard(),
+            started: AtomicBool::new(false),
         })
         .await?;
 
-    if let Err(why) = client.start().await {
+    // On SIGTERM/Ctrl-C: stop the gateway so `start` returns, then let automations finish.
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        shutdown::wait_for_signal().await;
+        info!("Shutdown signal received; disconnecting from Discord...");
+        shard_manager.shutdown_all().await;
+    });
+
+    // Every process shows the market clock on the shards it runs.
+    presence::spawn_market_presence(
+        client.shard_manager.clone(),
+        presence_finance,
+        config.clone(),
+        &shutdown,
+    );
+
+    let http = client.http.clone();
+    let started = match (startup.discord.shard_count, startup.discord.shard_range) {
+        (Some(total), Some([first, last])) => {
+            info!("Starting shards {first}-{last} of {total}...");
+            // serenity treats the end of this range as inclusive.
+            client.start_shard_range(first..last, total).await
+        }
+        (Some(total), None) => {
+            info!("Starting {total} shard(s)...");
+            client.start_shards(total).await
+        }
+        _ => {
+            info!("Starting with the shard count Discord recommends...");
+            client.start_autosharded().await
+        }
+    };
+    if let Err(why) = started {
         eprintln!("Client error: {why}");
     }
 
-    Ok(())
-}
+    shutdown.shutdown(shutdown::DEFAULT_GRACE_PERIOD).await;
+    shutdown::post_shutdown_notice(&http, &config.current().shutdown).await;
+    info!("Shutdown complete");
 
-fn ping_command() -> CreateCommand {
-    CreateCommand::new("ping").description("Simple ping command")
+    Ok(())
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Earnings event used by the bot for calendar displays.
@@ -19,6 +19,8 @@ pub struct EarningsEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub revenue_actual: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub importance: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -27,3 +29,132 @@ pub struct EarningsEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo: Option<String>, // Base64 encoded logo data from API
 }
+
+impl EarningsEvent {
+    /// Whether any of the estimate or market cap columns is missing.
+    pub fn needs_estimates(&self) -> bool {
+        self.eps_estimate.is_none() || self.revenue_estimate.is_none() || self.market_cap.is_none()
+    }
+
+    /// Fill the estimate and market cap columns the calendar API left empty. The EPS and
+    /// revenue estimates are for the symbol's next report, so they are only used when that
+    /// report is this event.
+    pub fn fill_estimates(&mut self, estimate: &EarningsEstimate) {
+        if estimate.is_for(self.date.date_naive()) {
+            self.eps_estimate = self.eps_estimate.or(estimate.eps);
+            self.revenue_estimate = self.revenue_estimate.or(estimate.revenue);
+        }
+        self.market_cap = self.market_cap.or(estimate.market_cap);
+    }
+}
+
+/// The earnings calendar for a date range. A range fetched day by day can come back partial;
+/// `missing_days` lists the days whose events could not be loaded.
+#[derive(Debug, Clone, Default)]
+pub struct EarningsRange {
+    pub events: Vec<EarningsEvent>,
+    pub missing_days: Vec<NaiveDate>,
+}
+
+impl EarningsRange {
+    /// A range every day of which was loaded.
+    pub fn complete(events: Vec<EarningsEvent>) -> Self {
+        Self {
+            events,
+            missing_days: Vec::new(),
+        }
+    }
+
+    /// A warning line naming the missing days, for replies and posts built from a partial range.
+    pub fn missing_note(&self) -> Option<String> {
+        if self.missing_days.is_empty() {
+            return None;
+        }
+        let days: Vec<String> = self
+            .missing_days
+            .iter()
+            .map(|day| day.format("%a %b %-d").to_string())
+            .collect();
+        Some(format!(
+            "⚠️ Earnings for {} could not be loaded; the list may be incomplete.",
+            days.join(", ")
+        ))
+    }
+}
+
+/// Consensus estimates for a symbol's next report, from Yahoo `calendarEvents`, with its
+/// current market cap.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EarningsEstimate {
+    pub eps: Option<f64>,
+    pub revenue: Option<f64>,
+    pub market_cap: Option<f64>,
+    /// Date of the report the estimates describe; two dates when it is an unconfirmed window.
+    #[serde(default)]
+    pub report_dates: Vec<NaiveDate>,
+}
+
+impl EarningsEstimate {
+    /// Whether the estimated report falls on `day`. A day either side is allowed, since the
+    /// calendar and Yahoo date the same evening report in different time zones.
+    pub fn is_for(&self, day: NaiveDate) -> bool {
+        let (Some(first), Some(last)) = (self.report_dates.first(), self.report_dates.last())
+        else {
+            return false;
+        };
+        (first.pred_opt().unwrap_or(*first)..=last.succ_opt().unwrap_or(*last)).contains(&day)
+    }
+}
+
+/// What the bot saw around one report, kept for the weekly summary: the implied move from the
+/// daily report and the actuals and price reaction from the post-earnings report. Each report
+/// fills in its own fields; saving merges with what is already stored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EarningsRecord {
+    pub symbol: String,
+    pub day: NaiveDate,
+    /// `BMO`, `AMC` or `TBA`.
+    pub session: String,
+    /// Options-implied move in percent, e.g. `6.4` for ±6.4%.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implied_move: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eps_estimate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eps_actual: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revenue_actual: Option<f64>,
+    /// Extended-hours move after the report in percent, signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_move: Option<f64>,
+}
+
+impl EarningsRecord {
+    pub fn new(symbol: impl Into<String>, day: NaiveDate, session: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            day,
+            session: session.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Take every value `newer` has, keeping ours where it has none.
+    pub fn merge(&mut self, newer: &EarningsRecord) {
+        if newer.session != "TBA" {
+            self.session = newer.session.clone();
+        }
+        self.implied_move = newer.implied_move.or(self.implied_move);
+        self.eps_estimate = newer.eps_estimate.or(self.eps_estimate);
+        self.eps_actual = newer.eps_actual.or(self.eps_actual);
+        self.revenue_actual = newer.revenue_actual.or(self.revenue_actual);
+        self.price_move = newer.price_move.or(self.price_move);
+    }
+
+    /// EPS surprise in percent of the estimate's size; `None` without both numbers or when the
+    /// estimate is zero.
+    pub fn eps_surprise(&self) -> Option<f64> {
+        let (actual, estimate) = (self.eps_actual?, self.eps_estimate?);
+        (estimate != 0.0).then(|| (actual - estimate) / estimate.abs() * 100.0)
+    }
+}
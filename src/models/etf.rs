@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use super::quote_summary::QuoteSummary;
+
+/// Bot-facing profile of an ETF or mutual fund (used by `/etf`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtfProfile {
+    pub symbol: String,
+    pub name: Option<String>,
+    /// Fund family, e.g. `Vanguard`.
+    pub family: Option<String>,
+    /// Morningstar category, e.g. `Large Blend`.
+    pub category: Option<String>,
+    /// Annual expense ratio as a fraction of assets.
+    pub expense_ratio: Option<f64>,
+    /// Assets under management, in `currency`.
+    pub net_assets: Option<f64>,
+    pub currency: Option<String>,
+    /// Largest positions, biggest first.
+    pub holdings: Vec<EtfHolding>,
+    /// Sector weights, biggest first.
+    pub sectors: Vec<SectorWeight>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtfHolding {
+    pub symbol: Option<String>,
+    pub name: String,
+    /// Fraction of the fund.
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorWeight {
+    pub sector: String,
+    /// Fraction of the fund.
+    pub weight: f64,
+}
+
+impl EtfProfile {
+    /// Build a profile from the `price`, `summaryDetail`, `topHoldings` and `fundProfile`
+    /// modules. `None` when the symbol is not a fund.
+    pub fn from_summary(symbol: &str, summary: QuoteSummary) -> Option<Self> {
+        let price = summary.price.unwrap_or_default();
+        let is_fund = matches!(price.quote_type.as_deref(), Some("ETF" | "MUTUALFUND"));
+        if !is_fund && summary.top_holdings.is_none() && summary.fund_profile.is_none() {
+            return None;
+        }
+        let top = summary.top_holdings.unwrap_or_default();
+        let profile = summary.fund_profile.unwrap_or_default();
+
+        let holdings = top
+            .holdings
+            .into_iter()
+            .filter_map(|h| {
+                let name = h.holding_name.or_else(|| h.symbol.clone())?;
+                Some(EtfHolding {
+                    symbol: h.symbol.filter(|s| !s.is_empty()),
+                    name,
+                    weight: h.holding_percent?,
+                })
+            })
+            .collect();
+        let mut sectors: Vec<SectorWeight> = top
+            .sector_weightings
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0.0)
+            .map(|(key, weight)| SectorWeight {
+                sector: sector_label(&key),
+                weight,
+            })
+            .collect();
+        sectors.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+        Some(EtfProfile {
+            symbol: symbol.to_uppercase(),
+            name: price.long_name.or(price.short_name),
+            family: profile.family,
+            category: profile.category_name,
+            expense_ratio: profile
+                .fees_expenses_investment
+                .and_then(|fees| fees.annual_report_expense_ratio),
+            net_assets: summary.summary_detail.and_then(|d| d.total_assets),
+            currency: price.currency,
+            holdings,
+            sectors,
+        })
+    }
+}
+
+/// Display name for a Yahoo sector key: `consumer_cyclical` → `Consumer Cyclical`.
+pub fn sector_label(key: &str) -> String {
+    match key {
+        "realestate" => "Real Estate".to_string(),
+        _ => key
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => format!("{}{}", first.to_ascii_uppercase(), chars.as_str()),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
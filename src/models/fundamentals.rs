@@ -31,6 +31,10 @@ pub enum Frequency {
     Annual,
     #[serde(rename = "quarterly")]
     Quarterly,
+    /// Trailing twelve months: the sum of the last four quarters, derived from quarterly
+    /// statements rather than fetched.
+    #[serde(rename = "ttm")]
+    Ttm,
 }
 
 impl Frequency {
@@ -38,6 +42,15 @@ impl Frequency {
         match self {
             Frequency::Annual => "annual",
             Frequency::Quarterly => "quarterly",
+            Frequency::Ttm => "ttm",
+        }
+    }
+
+    /// The frequency whose statements are fetched from Yahoo to answer this one.
+    pub fn fetched(&self) -> Frequency {
+        match self {
+            Frequency::Annual => Frequency::Annual,
+            Frequency::Quarterly | Frequency::Ttm => Frequency::Quarterly,
         }
     }
 }
@@ -1,14 +1,20 @@
 pub mod earnings;
+pub mod etf;
 pub mod fundamentals;
 pub mod holders;
 pub mod news;
+pub mod quote_summary;
 pub mod quotes;
+pub mod search;
 
-pub use earnings::EarningsEvent;
+pub use earnings::{EarningsEstimate, EarningsEvent, EarningsRange, EarningsRecord};
+pub use etf::{EtfHolding, EtfProfile, SectorWeight};
 pub use fundamentals::{FinancialStatement, FinancialSummary, Frequency, StatementType};
 pub use holders::{
     HolderType, HoldersOverview, InsiderPurchase, InsiderRosterMember, InsiderTransaction,
     InstitutionalHolder, MajorHoldersBreakdown, MutualFundHolder,
 };
-pub use news::NewsItem;
+pub use news::{NewsItem, SourceType};
+pub use quote_summary::QuoteSummary;
 pub use quotes::PriceQuote;
+pub use search::SymbolMatch;
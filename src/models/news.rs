@@ -11,4 +11,32 @@ pub struct NewsItem {
     pub published_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thumbnail: Option<String>,
+    /// Company release or reporting, from the publisher.
+    #[serde(default)]
+    pub source_type: SourceType,
+}
+
+/// Who wrote a news item: the company itself, through a wire service, or the press.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceType {
+    PressRelease,
+    #[default]
+    Media,
+}
+
+impl SourceType {
+    /// Value of the `/news source_type:` option.
+    pub fn name(self) -> &'static str {
+        match self {
+            SourceType::PressRelease => "press",
+            SourceType::Media => "media",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [SourceType::PressRelease, SourceType::Media]
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(name))
+    }
 }
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Typed view of the `quoteSummary` modules the bot reads.
+///
+/// Request only the modules you need; the rest stay `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSummary {
+    pub price: Option<PriceModule>,
+    pub summary_detail: Option<SummaryDetail>,
+    pub default_key_statistics: Option<DefaultKeyStatistics>,
+    pub financial_data: Option<FinancialData>,
+    pub earnings: Option<EarningsModule>,
+    pub calendar_events: Option<CalendarEvents>,
+    pub top_holdings: Option<TopHoldings>,
+    pub fund_profile: Option<FundProfile>,
+    pub asset_profile: Option<AssetProfile>,
+}
+
+impl QuoteSummary {
+    /// Parse the first result of a raw `quoteSummary` response. `Ok(None)` when Yahoo returned
+    /// no result for the symbol.
+    pub fn from_response(value: &Value) -> Result<Option<Self>, serde_json::Error> {
+        let response = Response::deserialize(value)?;
+        Ok(response
+            .quote_summary
+            .result
+            .and_then(|results| results.into_iter().next()))
+    }
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(rename = "quoteSummary")]
+    quote_summary: Envelope,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    result: Option<Vec<QuoteSummary>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceModule {
+    pub symbol: Option<String>,
+    pub short_name: Option<String>,
+    pub long_name: Option<String>,
+    /// `EQUITY`, `ETF`, `MUTUALFUND`, `INDEX`, ...
+    pub quote_type: Option<String>,
+    pub currency: Option<String>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub regular_market_price: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub market_cap: Option<f64>,
+}
+
+/// Company details; the bot reads only the website.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetProfile {
+    /// Company homepage, e.g. `https://www.apple.com`.
+    pub website: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryDetail {
+    #[serde(rename = "trailingPE", default, deserialize_with = "raw_f64")]
+    pub trailing_pe: Option<f64>,
+    #[serde(rename = "forwardPE", default, deserialize_with = "raw_f64")]
+    pub forward_pe: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub market_cap: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub dividend_yield: Option<f64>,
+    /// Forward annual dividend per share.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub dividend_rate: Option<f64>,
+    #[serde(default, deserialize_with = "raw_date")]
+    pub ex_dividend_date: Option<NaiveDate>,
+    /// Net assets of a fund, in its currency.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub total_assets: Option<f64>,
+    /// Three-month average daily volume, in shares.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub average_volume: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultKeyStatistics {
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub trailing_eps: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub forward_eps: Option<f64>,
+    #[serde(rename = "forwardPE", default, deserialize_with = "raw_f64")]
+    pub forward_pe: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub shares_outstanding: Option<f64>,
+    /// New shares to old, e.g. `4:1`, or `1:10` for a reverse split.
+    pub last_split_factor: Option<String>,
+    #[serde(default, deserialize_with = "raw_date")]
+    pub last_split_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinancialData {
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub current_price: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub total_revenue: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub revenue_growth: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub profit_margins: Option<f64>,
+    /// Mean of the analysts' 12-month price targets.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub target_mean_price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EarningsModule {
+    pub earnings_chart: Option<EarningsChart>,
+    pub financials_chart: Option<FinancialsChart>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EarningsChart {
+    /// Oldest first.
+    #[serde(default)]
+    pub quarterly: Vec<EarningsQuarter>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EarningsQuarter {
+    /// Fiscal quarter label, e.g. `2Q2024`.
+    pub date: Option<String>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub actual: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub estimate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FinancialsChart {
+    /// Oldest first.
+    #[serde(default)]
+    pub quarterly: Vec<FinancialsPeriod>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FinancialsPeriod {
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub revenue: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub earnings: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalendarEvents {
+    /// The next report's consensus estimates.
+    pub earnings: Option<CalendarEarnings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEarnings {
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub earnings_average: Option<f64>,
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub revenue_average: Option<f64>,
+    /// The report date, or the first and last day of the window when it is unconfirmed.
+    #[serde(default, deserialize_with = "raw_dates")]
+    pub earnings_date: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopHoldings {
+    /// Largest positions, biggest first.
+    #[serde(default)]
+    pub holdings: Vec<FundHolding>,
+    /// Sector keys (`technology`, `consumer_cyclical`, ...) and their fraction of the fund.
+    /// Yahoo sends a list of single-entry objects.
+    #[serde(default, deserialize_with = "raw_weights")]
+    pub sector_weightings: Vec<(String, f64)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundHolding {
+    pub symbol: Option<String>,
+    pub holding_name: Option<String>,
+    /// Fraction of the fund.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub holding_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundProfile {
+    pub family: Option<String>,
+    pub category_name: Option<String>,
+    pub fees_expenses_investment: Option<FundFees>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundFees {
+    /// Fraction of assets per year.
+    #[serde(default, deserialize_with = "raw_f64")]
+    pub annual_report_expense_ratio: Option<f64>,
+}
+
+/// Yahoo wraps numbers as `{"raw": 1.5, "fmt": "1.50"}`, sends `{}` when missing, and
+/// occasionally a bare number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNumber {
+    Bare(f64),
+    Wrapped { raw: Option<f64> },
+    Other(IgnoredAny),
+}
+
+fn raw_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<RawNumber>::deserialize(deserializer)? {
+        Some(RawNumber::Bare(value)) | Some(RawNumber::Wrapped { raw: Some(value) }) => Some(value),
+        _ => None,
+    })
+}
+
+/// A wrapped unix timestamp, as a date (UTC).
+fn raw_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<RawNumber>::deserialize(deserializer)? {
+        Some(RawNumber::Bare(secs)) | Some(RawNumber::Wrapped { raw: Some(secs) }) => {
+            DateTime::from_timestamp(secs as i64, 0).map(|dt| dt.date_naive())
+        }
+        _ => None,
+    })
+}
+
+/// A list of wrapped unix timestamps, as dates (UTC).
+fn raw_dates<'de, D>(deserializer: D) -> Result<Vec<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Option::<Vec<RawNumber>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .filter_map(|value| match value {
+            RawNumber::Bare(secs) | RawNumber::Wrapped { raw: Some(secs) } => {
+                DateTime::from_timestamp(secs as i64, 0).map(|dt| dt.date_naive())
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// A list of single-entry `{"key": wrapped number}` objects, as pairs in order.
+fn raw_weights<'de, D>(deserializer: D) -> Result<Vec<(String, f64)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries =
+        Option::<Vec<HashMap<String, RawNumber>>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(entries
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| match value {
+            RawNumber::Bare(weight) | RawNumber::Wrapped { raw: Some(weight) } => {
+                Some((key, weight))
+            }
+            _ => None,
+        })
+        .collect())
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A symbol returned by the Yahoo search API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolMatch {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub exchange: Option<String>,
+    /// `EQUITY`, `ETF`, `INDEX`, ...
+    pub quote_type: Option<String>,
+}
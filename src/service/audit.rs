@@ -0,0 +1,136 @@
+//! Per-guild audit channel.
+//!
+//! Admin commands, setting changes, price alert registrations and automation failures are
+//! posted to the channel a guild picks with `/config audit-channel`. Every event is also
+//! logged under the `audit` tracing target, so nothing is lost when no channel is set.
+
+use serenity::all::{
+    ChannelId, CommandDataOption, CommandDataOptionValue, CommandInteraction,
+    CreateAllowedMentions, CreateMessage, GuildId, Http, UserId,
+};
+use tracing::{info, warn};
+
+use crate::service::caching::collections::price_alerts::PriceAlert;
+use crate::service::routing::{self, Route};
+use crate::service::store::StateStore;
+
+/// Something worth telling a guild's admins about.
+#[derive(Debug, Clone)]
+pub enum AuditEvent<'a> {
+    /// An admin or settings command ran. `outcome` is the first line of the reply, or the
+    /// error shown to the user.
+    Command {
+        user: UserId,
+        invocation: String,
+        outcome: Result<&'a str, String>,
+    },
+    AlertRegistered {
+        user: UserId,
+        alert: &'a PriceAlert,
+    },
+    /// Every level fired, so the alert was removed.
+    AlertCompleted {
+        alert: &'a PriceAlert,
+    },
+    AutomationFailed {
+        job: &'a str,
+        error: &'a str,
+    },
+}
+
+impl AuditEvent<'_> {
+    fn render(&self) -> String {
+        match self {
+            AuditEvent::Command {
+                user,
+                invocation,
+                outcome,
+            } => match outcome {
+                Ok(reply) => format!("🛠️ <@{user}> ran `{invocation}` — {reply}"),
+                Err(error) => format!("🛠️ <@{user}> ran `{invocation}` — failed: {error}"),
+            },
+            AuditEvent::AlertRegistered { user, alert } => format!(
+                "🔔 <@{user}> registered a {} price alert ({} levels) → <#{}>",
+                alert.symbol,
+                alert.levels.len(),
+                alert.target_channel_id
+            ),
+            AuditEvent::AlertCompleted { alert } => format!(
+                "🔕 {} price alert in <#{}> removed; every level fired",
+                alert.symbol, alert.target_channel_id
+            ),
+            AuditEvent::AutomationFailed { job, error } => {
+                format!("⚠️ Automation `{job}` failed: {error}")
+            }
+        }
+    }
+}
+
+/// Log `event` and post it to the guild's audit channel, if one is set. Automation failures go
+/// to the guild's `errors` route instead when it has one. Failures to post are logged and
+/// otherwise ignored so auditing never breaks the action being audited.
+pub async fn record(
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    guild_id: GuildId,
+    event: AuditEvent<'_>,
+) {
+    let text = event.render();
+    info!(target: "audit", guild_id = guild_id.get(), "{text}");
+
+    let Some(store) = store else {
+        return;
+    };
+    if matches!(event, AuditEvent::AutomationFailed { .. }) {
+        if let Some(channel_id) = routing::guild_route(Some(store), guild_id, Route::Errors).await {
+            post(http, guild_id, channel_id, text).await;
+            return;
+        }
+    }
+    let channel_id = match store.audit_channel(guild_id.get()).await {
+        Ok(Some(id)) => ChannelId::new(id),
+        Ok(None) => return,
+        Err(e) => {
+            warn!("failed to load audit channel for guild {guild_id}: {e}");
+            return;
+        }
+    };
+    post(http, guild_id, channel_id, text).await;
+}
+
+async fn post(http: &Http, guild_id: GuildId, channel_id: ChannelId, text: String) {
+    let message = CreateMessage::new()
+        .content(text)
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Err(e) = channel_id.send_message(http, message).await {
+        warn!("failed to post to audit channel {channel_id} in guild {guild_id}: {e}");
+    }
+}
+
+/// The command as typed, e.g. `/admin jobs pause job:weekly_earnings`.
+pub fn describe_invocation(interaction: &CommandInteraction) -> String {
+    let mut parts = vec![format!("/{}", interaction.data.name)];
+    describe_options(&interaction.data.options, &mut parts);
+    parts.join(" ")
+}
+
+fn describe_options(options: &[CommandDataOption], parts: &mut Vec<String>) {
+    for option in options {
+        match &option.value {
+            CommandDataOptionValue::SubCommand(args)
+            | CommandDataOptionValue::SubCommandGroup(args) => {
+                parts.push(option.name.clone());
+                describe_options(args, parts);
+            }
+            CommandDataOptionValue::String(s) => parts.push(format!("{}:{s}", option.name)),
+            CommandDataOptionValue::Integer(n) => parts.push(format!("{}:{n}", option.name)),
+            CommandDataOptionValue::Number(n) => parts.push(format!("{}:{n}", option.name)),
+            CommandDataOptionValue::Boolean(b) => parts.push(format!("{}:{b}", option.name)),
+            CommandDataOptionValue::Role(id) => parts.push(format!("{}:{id}", option.name)),
+            CommandDataOptionValue::Channel(id) => parts.push(format!("{}:{id}", option.name)),
+            CommandDataOptionValue::User(id) => parts.push(format!("{}:{id}", option.name)),
+            CommandDataOptionValue::Attachment(_) => parts.push(format!("{}:<file>", option.name)),
+            _ => parts.push(option.name.clone()),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Index levels in a channel topic: each guild can pick a channel with `/config topic-channel`
+//! whose topic the bot keeps set to the major US indexes and the VIX.
+//!
+//! Discord allows only two topic edits per channel every ten minutes, so a channel is edited
+//! at most once per [`MIN_EDIT_INTERVAL_MINUTES`], and only when the text changed; overnight
+//! and on weekends the levels stand still and nothing is edited.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use serenity::all::{ChannelId, EditChannel, Http};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::PriceQuote;
+use crate::service::automation::leader;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+
+/// Shortest gap between two edits of the same channel's topic.
+pub const MIN_EDIT_INTERVAL_MINUTES: i64 = 10;
+
+/// Yahoo symbol and the label shown in the topic, in topic order.
+pub const TOPIC_INDEXES: [(&str, &str); 4] = [
+    ("^GSPC", "S&P 500"),
+    ("^IXIC", "Nasdaq"),
+    ("^DJI", "Dow"),
+    ("^VIX", "VIX"),
+];
+
+/// What was last written to a channel, and when.
+struct LastEdit {
+    at: DateTime<Utc>,
+    topic: String,
+}
+
+/// Spawn the topic updater. Channels come from the state store on every pass, so
+/// `/config topic-channel` applies without a restart; without a store there is nothing to do.
+pub fn spawn_topic_updater(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    store: Option<Arc<dyn StateStore>>,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting channel topic updater");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let mut edits: HashMap<u64, LastEdit> = HashMap::new();
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Channel topic updater stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            let Some(store) = store.as_deref() else {
+                continue;
+            };
+            if !leader::is_leader() {
+                continue;
+            }
+            let now = Utc::now();
+            let channels: Vec<u64> = match store.topic_channels().await {
+                Ok(channels) => channels
+                    .into_values()
+                    .filter(|id| edit_due(edits.get(id).map(|e| e.at), now))
+                    .collect(),
+                Err(e) => {
+                    warn!("failed to load topic channels: {e}");
+                    continue;
+                }
+            };
+            if channels.is_empty() {
+                continue;
+            }
+
+            let topic = topic_text(&fetch_indexes(&finance).await);
+            if topic.is_empty() {
+                continue;
+            }
+            for channel_id in channels {
+                if edits.get(&channel_id).is_some_and(|e| e.topic == topic) {
+                    continue;
+                }
+                let edit = EditChannel::new().topic(&topic);
+                match ChannelId::new(channel_id).edit(&http, edit).await {
+                    Ok(_) => {
+                        METRICS.record_automation_success("channel_topic");
+                        edits.insert(
+                            channel_id,
+                            LastEdit {
+                                at: now,
+                                topic: topic.clone(),
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        METRICS.record_automation_failure("channel_topic");
+                        warn!("failed to update the topic of channel {channel_id}: {e}");
+                        // Wait out the interval before retrying, like a successful edit.
+                        edits.insert(
+                            channel_id,
+                            LastEdit {
+                                at: now,
+                                topic: String::new(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Whether a channel last edited at `last` may be edited again at `now`.
+pub fn edit_due(last: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last.is_none_or(|at| now - at >= chrono::Duration::minutes(MIN_EDIT_INTERVAL_MINUTES))
+}
+
+/// Quotes for [`TOPIC_INDEXES`] in order; `None` where one failed to load.
+async fn fetch_indexes(finance: &FinanceService) -> Vec<(&'static str, Option<PriceQuote>)> {
+    let quotes = join_all(TOPIC_INDEXES.iter().map(|(s, _)| finance.get_price(s))).await;
+    TOPIC_INDEXES
+        .iter()
+        .zip(quotes)
+        .map(|((symbol, label), quote)| {
+            let quote = quote
+                .inspect_err(|e| warn!("channel topic: no quote for {symbol}: {e}"))
+                .ok();
+            (*label, quote)
+        })
+        .collect()
+}
+
+/// `S&P 500 5,812.34 (+0.45%) · Nasdaq 18,400.12 (-0.30%) · Dow … · VIX 16.20 (-2.10%)`.
+/// Indexes without a price are left out.
+pub fn topic_text(quotes: &[(&str, Option<PriceQuote>)]) -> String {
+    quotes
+        .iter()
+        .filter_map(|(label, quote)| {
+            let quote = quote.as_ref()?;
+            let level = format::level(quote.price?);
+            let change = quote
+                .percent_change
+                .map(|c| format!(" ({c:+.2}%)"))
+                .unwrap_or_default();
+            Some(format!("{label} {level}{change}"))
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
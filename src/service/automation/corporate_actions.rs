@@ -0,0 +1,264 @@
+//! Daily split and dividend alerts: every weekday at 8:00 AM ET, compare each symbol on the
+//! `/config symbols` list of `channels.corporate_actions`' guild with the state kept in the
+//! state store on the previous run, and post dividend increases, cuts and initiations and new
+//! splits.
+//!
+//! Nothing is posted on a day without changes. A symbol's first run only records its state.
+
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDate, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
+use serenity::all::{CreateMessage, Http};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
+use crate::service::config::ConfigHandle;
+use crate::service::finance::corporate_actions::{changes, split_label, ActionChange};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+
+/// Concurrent lookups; an index-sized watchlist takes a minute or two.
+const LOOKUPS: usize = 8;
+
+static LAST_CHECK_DATE: Lazy<Mutex<Option<NaiveDate>>> = Lazy::new(|| Mutex::new(None));
+
+/// Changes found for one symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolActions {
+    pub symbol: String,
+    pub changes: Vec<ActionChange>,
+}
+
+/// Spawn the daily split and dividend check (weekdays at 8:00 AM ET).
+pub fn spawn_corporate_actions_watcher(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting split and dividend watcher");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Split and dividend watcher stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            if !should_check_now().await {
+                continue;
+            }
+            let targets = jobs::claim_targets(
+                Job::CorporateActions,
+                &http,
+                store.as_deref(),
+                &config.current(),
+            )
+            .await;
+            for channel_id in targets {
+                let result = post_once(&http, &finance, store.as_deref(), channel_id, false).await;
+                jobs::record_run(
+                    Job::CorporateActions,
+                    &http,
+                    store.as_deref(),
+                    channel_id,
+                    &result,
+                )
+                .await;
+                match result {
+                    Ok(()) => METRICS.record_automation_success("corporate_actions"),
+                    Err(e) => {
+                        METRICS.record_automation_failure("corporate_actions");
+                        warn!("split and dividend check failed in {channel_id}: {e}");
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn should_check_now() -> bool {
+    let now_et = Utc::now().with_timezone(&New_York);
+    if matches!(now_et.weekday(), Weekday::Sat | Weekday::Sun)
+        || !(now_et.hour() == 8 && now_et.minute() < 5)
+    {
+        return false;
+    }
+    let today = now_et.date_naive();
+    let mut last = LAST_CHECK_DATE.lock().await;
+    if *last == Some(today) {
+        return false;
+    }
+    *last = Some(today);
+    true
+}
+
+/// Check the watchlist of the channel's guild and post what changed. `announce_empty` posts a
+/// short note when nothing did, for on-demand runs.
+pub async fn post_once(
+    http: &Http,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+    announce_empty: bool,
+) -> Result<(), String> {
+    let store = store.ok_or("split and dividend alerts need a state store to compare runs")?;
+    let filter = jobs::channel_symbol_filter(
+        Job::CorporateActions,
+        http,
+        finance,
+        Some(store),
+        channel_id,
+    )
+    .await;
+    let symbols = filter
+        .watchlist()
+        .filter(|symbols| !symbols.is_empty())
+        .ok_or("the channel's server has no watchlist; set one with `/config symbols`")?;
+
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let (found, failed) = check(finance, store, &symbols, today).await;
+    if failed == symbols.len() {
+        return Err(format!(
+            "dividend and split data failed to load for all {} symbols",
+            symbols.len()
+        ));
+    }
+    if found.is_empty() && !announce_empty {
+        info!(
+            "No split or dividend changes across {} symbols",
+            symbols.len()
+        );
+        return Ok(());
+    }
+
+    let is_split = |c: &ActionChange| matches!(c, ActionChange::Split { .. });
+    let all: Vec<&ActionChange> = found.iter().flat_map(|s| &s.changes).collect();
+    let tags: Vec<&'static str> = [
+        ("Splits", all.iter().any(|c| is_split(c))),
+        ("Dividends", all.iter().any(|c| !is_split(c))),
+    ]
+    .into_iter()
+    .filter_map(|(tag, present)| present.then_some(tag))
+    .collect();
+    let post = ReportPost::new(
+        format!("Splits and dividends — {}", today.format("%a %b %-d")),
+        &tags,
+    );
+    let content = format_alerts(&found, symbols.len(), failed);
+    posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
+        .await
+        .map_err(|e| format!("failed to post split and dividend alerts: {e}"))?;
+    info!(
+        "Posted split and dividend alerts for {} symbols",
+        found.len()
+    );
+    Ok(())
+}
+
+/// Compare each symbol with its stored state and store the new one. Returns the symbols with
+/// changes, in symbol order, and how many lookups failed.
+async fn check(
+    finance: &FinanceService,
+    store: &dyn StateStore,
+    symbols: &[String],
+    today: NaiveDate,
+) -> (Vec<SymbolActions>, usize) {
+    let results: Vec<Result<Option<SymbolActions>, ()>> = stream::iter(symbols.to_vec())
+        .map(|symbol| async move {
+            let current = finance
+                .corporate_actions(&symbol)
+                .await
+                .map_err(|e| warn!("corporate actions: {symbol} failed: {e}"))?;
+            let previous = store
+                .corporate_actions_state(&symbol)
+                .await
+                .map_err(|e| warn!("corporate actions: {symbol} state unreadable: {e}"))?;
+            if let Err(e) = store.save_corporate_actions_state(&symbol, &current).await {
+                warn!("corporate actions: failed to store {symbol} state: {e}");
+            }
+            let Some(previous) = previous else {
+                return Ok(None);
+            };
+            let changes = changes(&previous, &current, today);
+            Ok((!changes.is_empty()).then_some(SymbolActions { symbol, changes }))
+        })
+        .buffer_unordered(LOOKUPS)
+        .collect()
+        .await;
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    let mut found: Vec<SymbolActions> = results.into_iter().flatten().flatten().collect();
+    found.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    (found, failed)
+}
+
+/// The post: a line per change, then how many symbols were checked.
+pub fn format_alerts(found: &[SymbolActions], checked: usize, failed: usize) -> String {
+    let mut lines = vec!["📣 **Splits and dividends**".to_string()];
+    if found.is_empty() {
+        lines.push("No split or dividend changes.".to_string());
+    }
+    for entry in found {
+        for change in &entry.changes {
+            lines.push(alert_line(&entry.symbol, change));
+        }
+    }
+    let mut note = format!("Checked {checked} watchlist symbols");
+    if failed > 0 {
+        note.push_str(&format!(" · {failed} failed to load"));
+    }
+    lines.push(String::new());
+    lines.push(format!("*{note}*"));
+    lines.join("\n")
+}
+
+fn alert_line(symbol: &str, change: &ActionChange) -> String {
+    let ex = |date: &Option<NaiveDate>| {
+        date.map(|d| format!(" · ex-div {}", d.format("%b %-d")))
+            .unwrap_or_default()
+    };
+    match change {
+        ActionChange::Split { factor, date } => format!(
+            "✂️ **{symbol}** {} split · {}",
+            split_label(factor),
+            date.format("%b %-d, %Y")
+        ),
+        ActionChange::DividendIncrease { from, to, ex_date } => format!(
+            "📈 **{symbol}** dividend raised {} → {} a year ({:+.1}%){}",
+            format::per_share(*from),
+            format::per_share(*to),
+            (to - from) / from * 100.0,
+            ex(ex_date)
+        ),
+        ActionChange::DividendCut { to, .. } if *to == 0.0 => {
+            format!("🛑 **{symbol}** dividend suspended")
+        }
+        ActionChange::DividendCut { from, to, ex_date } => format!(
+            "📉 **{symbol}** dividend cut {} → {} a year ({:+.1}%){}",
+            format::per_share(*from),
+            format::per_share(*to),
+            (to - from) / from * 100.0,
+            ex(ex_date)
+        ),
+        ActionChange::DividendInitiated { rate, ex_date } => format!(
+            "🆕 **{symbol}** initiates a dividend of {} a year{}",
+            format::per_share(*rate),
+            ex(ex_date)
+        ),
+    }
+}
@@ -1,4 +1,3 @@
-use std::env;
 use std::sync::Arc;
 
 use chrono::{Datelike, Duration, Timelike, Utc, Weekday};
@@ -11,10 +10,21 @@ use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
+use super::{day_threads, weekly_summary};
+use crate::models::EarningsRecord;
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
+use crate::service::config::{Config, ConfigHandle};
 use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+use crate::service::timefmt::{self, TimestampStyle};
 
 #[derive(Debug, Clone)]
 struct EarningsActuals {
+    eps_estimate: Option<f64>,
     eps_actual: Option<f64>,
     revenue_actual: Option<f64>,
 }
@@ -30,52 +40,29 @@ static LAST_AFTER_BMO_POST_DATE: Lazy<Mutex<Option<chrono::NaiveDate>>> =
 static LAST_AFTER_AMC_POST_DATE: Lazy<Mutex<Option<chrono::NaiveDate>>> =
     Lazy::new(|| Mutex::new(None));
 
-fn resolve_channel_id(var_names: &[&str], feature_label: &str) -> Option<ChannelId> {
-    for name in var_names {
-        if let Ok(value) = env::var(name) {
-            match value.parse::<u64>() {
-                Ok(id) => return Some(ChannelId::new(id)),
-                Err(_) => warn!("{feature_label}: {name} is set but not a valid u64 channel id"),
-            }
-        }
-    }
-
-    info!(
-        "{feature_label} not started; set one of these env vars: {:?}",
-        var_names
-    );
-    None
-}
-
 /// Spawn post-earnings snapshots twice daily:
 /// - BMO: 8:45 AM ET
 /// - AMC: 5:50 PM ET
 pub fn spawn_after_daily_poster(
     http: Arc<Http>,
     finance: Arc<FinanceService>,
-) -> Option<JoinHandle<()>> {
-    if env::var("ENABLE_EARNINGS_PINGER")
-        .map(|v| v == "0")
-        .unwrap_or(false)
-    {
-        info!("After-daily poster disabled via ENABLE_EARNINGS_PINGER=0");
-        return None;
-    }
-
-    let channel_id = resolve_channel_id(
-        &["EARNINGS_AFTER_CHANNEL_ID", "EARNINGS_CHANNEL_ID"],
-        "after-daily earnings poster",
-    )?;
-
-    info!(
-        "Starting after-daily earnings poster to channel {}",
-        channel_id
-    );
-
-    Some(tokio::spawn(async move {
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting after-daily earnings poster");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("After-daily earnings poster stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
             let now_et = Utc::now().with_timezone(&America::New_York);
             let weekday = now_et.weekday();
 
@@ -85,18 +72,53 @@ pub fn spawn_after_daily_poster(
             }
 
             if should_post_bmo(&now_et).await {
-                if let Err(e) = send_after_daily_report(&http, &finance, channel_id).await {
-                    warn!("after-daily BMO iteration failed: {e}");
-                }
+                post_session(
+                    &http,
+                    &finance,
+                    store.as_deref(),
+                    &config.current(),
+                    "after_daily_bmo",
+                )
+                .await;
             }
-
             if should_post_amc(&now_et).await {
-                if let Err(e) = send_after_daily_report(&http, &finance, channel_id).await {
-                    warn!("after-daily AMC iteration failed: {e}");
-                }
+                post_session(
+                    &http,
+                    &finance,
+                    store.as_deref(),
+                    &config.current(),
+                    "after_daily_amc",
+                )
+                .await;
             }
         }
-    }))
+    })
+}
+
+/// One scheduled post per target channel, recorded under `task` (`after_daily_bmo` /
+/// `after_daily_amc`). The channels come from the current config and routes so a change
+/// applies to the next session.
+async fn post_session(
+    http: &Http,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+    task: &'static str,
+) {
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    for channel_id in jobs::claim_targets(Job::AfterDaily, http, store, config).await {
+        let target =
+            day_threads::channel_for_day(http, &config.earnings, today, channel_id, store).await;
+        let result = send_after_daily_report(http, finance, store, target).await;
+        jobs::record_run(Job::AfterDaily, http, store, channel_id, &result).await;
+        match result {
+            Ok(()) => METRICS.record_automation_success(task),
+            Err(e) => {
+                METRICS.record_automation_failure(task);
+                warn!("{task} failed in {channel_id}: {e}");
+            }
+        }
+    }
 }
 
 async fn should_post_bmo(now_et: &chrono::DateTime<chrono_tz::Tz>) -> bool {
@@ -135,9 +157,13 @@ async fn should_post_amc(now_et: &chrono::DateTime<chrono_tz::Tz>) -> bool {
 /// - Before 4:00 PM ET: show BMO results.
 /// - 6:00 PM ET or later: show AMC results.
 /// - Between 4:00–6:00 PM ET: post a waiting message.
+///
+/// With a `store`, each report's actuals and extended-hours price reaction are recorded for the
+/// weekly summary.
 pub async fn send_after_daily_report(
     http: &Http,
     finance: &FinanceService,
+    store: Option<&dyn StateStore>,
     channel_id: ChannelId,
 ) -> Result<(), String> {
     let now_et = Utc::now().with_timezone(&New_York);
@@ -167,13 +193,24 @@ pub async fn send_after_daily_report(
         SessionTarget::Waiting
     };
 
+    let post = ReportPost::new(
+        format!(
+            "Earnings results — {} ({})",
+            today.format("%a %b %-d"),
+            match session_target {
+                SessionTarget::Bmo => "BMO",
+                SessionTarget::Amc | SessionTarget::Waiting => "AMC",
+            }
+        ),
+        &["Earnings", "Results"],
+    );
+
     if let SessionTarget::Waiting = session_target {
         let msg = format!(
-            "⏱️ It's {} ET. BMO results are done; AMC results will be posted after 6:00 PM ET.",
-            now_et.format("%-I:%M %p")
+            "⏱️ It's {}. BMO results are done; AMC results will be posted after 6:00 PM ET.",
+            timefmt::discord(&now_et, TimestampStyle::ShortTime)
         );
-        channel_id
-            .send_message(http, CreateMessage::new().content(msg))
+        posting::send_report(http, channel_id, &post, CreateMessage::new().content(msg))
             .await
             .map_err(|e| format!("failed to post waiting message: {e}"))?;
         return Ok(());
@@ -182,18 +219,25 @@ pub async fn send_after_daily_report(
     let start = *target_dates.iter().min().unwrap();
     let end = *target_dates.iter().max().unwrap();
 
-    let events = finance
+    let range = finance
         .get_earnings_range(start, end)
         .await
         .map_err(|e| format!("fetch error: {e}"))?;
+    let missing_note = range.missing_note();
+    let mut events = range.events;
+    jobs::channel_symbol_filter(Job::AfterDaily, http, finance, store, channel_id)
+        .await
+        .retain_events(&mut events);
 
     if events.is_empty() {
-        let msg = format!(
+        let mut msg = format!(
             "No earnings events scheduled for target dates ({})",
             date_labels
         );
-        channel_id
-            .send_message(http, CreateMessage::new().content(msg))
+        if let Some(note) = &missing_note {
+            msg.push_str(&format!("\n{note}"));
+        }
+        posting::send_report(http, channel_id, &post, CreateMessage::new().content(msg))
             .await
             .map_err(|e| format!("failed to post empty after-daily earnings: {e}"))?;
         return Ok(());
@@ -207,11 +251,14 @@ pub async fn send_after_daily_report(
 
     let mut lines = Vec::new();
     lines.push(format!(
-        "📈 Post-earnings results ({}) — {} as of {} ET",
+        "📈 Post-earnings results ({}) — {} as of {}",
         date_labels,
         session_label,
-        now_et.format("%-I:%M %p")
+        timefmt::discord(&now_et, TimestampStyle::ShortTime)
     ));
+    if let Some(note) = missing_note {
+        lines.push(note);
+    }
     lines.push(String::new());
 
     let mut shown = 0usize;
@@ -235,8 +282,20 @@ pub async fn send_after_daily_report(
         }
 
         let actuals = fetch_latest_actuals(finance, &ev.symbol).await;
-        let eps_text = format_eps(actuals.as_ref().and_then(|a| a.eps_actual));
-        let rev_text = format_revenue(actuals.as_ref().and_then(|a| a.revenue_actual));
+        let mut record = EarningsRecord::new(&ev.symbol, ev_date, session);
+        record.eps_estimate = actuals
+            .as_ref()
+            .and_then(|a| a.eps_estimate)
+            .or(ev.eps_estimate);
+        record.eps_actual = actuals.as_ref().and_then(|a| a.eps_actual);
+        record.revenue_actual = actuals.as_ref().and_then(|a| a.revenue_actual);
+        let eps_text = format_eps(&record);
+        let rev_text = format_revenue(record.revenue_actual);
+
+        if let Some(store) = store {
+            record.price_move = price_reaction(finance, &ev.symbol, session).await;
+            weekly_summary::save_record(store, &record).await;
+        }
 
         let date_str = ev_date.format("%Y-%m-%d").to_string();
 
@@ -261,8 +320,7 @@ pub async fn send_after_daily_report(
         session_label
     );
 
-    channel_id
-        .send_message(http, CreateMessage::new().content(content))
+    posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
         .await
         .map_err(|e| format!("failed to post after-daily earnings report: {e}"))?;
 
@@ -270,41 +328,22 @@ pub async fn send_after_daily_report(
 }
 
 async fn fetch_latest_actuals(finance: &FinanceService, symbol: &str) -> Option<EarningsActuals> {
-    let resp = match finance
-        .client()
-        .get_quote_summary(symbol, &["earnings"])
-        .await
-    {
-        Ok(v) => v,
+    let earnings = match finance.get_quote_summary(symbol, &["earnings"]).await {
+        Ok(summary) => summary.earnings?,
         Err(e) => {
             warn!("earnings fetch failed for {}: {}", symbol, e);
             return None;
         }
     };
 
-    let root = resp
-        .get("quoteSummary")
-        .and_then(|q| q.get("result"))
-        .and_then(|r| r.as_array())
-        .and_then(|arr| arr.first())?;
-
-    let earnings = root.get("earnings")?;
-
-    let eps_actual = earnings
-        .get("earningsChart")
-        .and_then(|c| c.get("quarterly"))
-        .and_then(|arr| arr.as_array())
-        .and_then(|arr| arr.last())
-        .and_then(|entry| entry.get("actual"))
-        .and_then(|v| v.get("raw").and_then(|r| r.as_f64()).or_else(|| v.as_f64()));
-
+    let latest = earnings
+        .earnings_chart
+        .and_then(|c| c.quarterly.last().cloned());
+    let eps_actual = latest.as_ref().and_then(|q| q.actual);
+    let eps_estimate = latest.as_ref().and_then(|q| q.estimate);
     let revenue_actual = earnings
-        .get("financialsChart")
-        .and_then(|c| c.get("quarterly"))
-        .and_then(|arr| arr.as_array())
-        .and_then(|arr| arr.last())
-        .and_then(|entry| entry.get("revenue"))
-        .and_then(|v| v.get("raw").and_then(|r| r.as_f64()).or_else(|| v.as_f64()));
+        .financials_chart
+        .and_then(|c| c.quarterly.last().and_then(|q| q.revenue));
 
     if eps_actual.is_none() && revenue_actual.is_none() {
         warn!("no actuals available yet for {}", symbol);
@@ -312,32 +351,66 @@ async fn fetch_latest_actuals(finance: &FinanceService, symbol: &str) -> Option<
     }
 
     Some(EarningsActuals {
+        eps_estimate,
         eps_actual,
         revenue_actual,
     })
 }
 
-fn format_eps(eps: Option<f64>) -> String {
-    eps.map(|v| format!("{:.2}", v))
-        .unwrap_or_else(|| "N/A".to_string())
+/// Extended-hours move in percent from the last regular-session price: pre-market for BMO
+/// reports, after-hours for AMC ones.
+async fn price_reaction(finance: &FinanceService, symbol: &str, session: &str) -> Option<f64> {
+    let quote = match finance.get_price(symbol).await {
+        Ok(quote) => quote,
+        Err(e) => {
+            warn!("quote fetch failed for {}: {}", symbol, e);
+            return None;
+        }
+    };
+    let close = quote.price.filter(|p| *p > 0.0)?;
+    let extended = match session {
+        "BMO" => quote.pre_market_price,
+        "AMC" => quote.after_hours_price,
+        _ => None,
+    }?;
+    Some((extended - close) / close * 100.0)
 }
 
-fn format_revenue(revenue: Option<f64>) -> String {
-    match revenue {
-        Some(v) => {
-            let abs = v.abs();
-            if abs >= 1_000_000_000.0 {
-                format!("{:.1}B", v / 1_000_000_000.0)
-            } else if abs >= 1_000_000.0 {
-                format!("{:.1}M", v / 1_000_000.0)
-            } else {
-                format!("{:.0}", v)
-            }
-        }
-        None => "N/A".to_string(),
+/// `$2.10 vs $1.95 est (+7.7%) ✅`; just the actual when there is no estimate to compare.
+fn format_eps(record: &EarningsRecord) -> String {
+    let Some(actual) = record.eps_actual else {
+        return "N/A".to_string();
+    };
+    let actual_text = format::per_share(actual);
+    match (record.eps_estimate, record.eps_surprise()) {
+        (Some(estimate), Some(surprise)) => format!(
+            "{} vs {} est ({:+.1}%) {}",
+            actual_text,
+            format::per_share(estimate),
+            surprise,
+            surprise_marker(surprise)
+        ),
+        (Some(estimate), None) => format!("{} vs {} est", actual_text, format::per_share(estimate)),
+        _ => actual_text,
+    }
+}
+
+fn surprise_marker(surprise: f64) -> &'static str {
+    if surprise > 0.0 {
+        "✅"
+    } else if surprise < 0.0 {
+        "❌"
+    } else {
+        "➖"
     }
 }
 
+fn format_revenue(revenue: Option<f64>) -> String {
+    revenue
+        .map(format::currency)
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
 fn classify_session(time: Option<&str>) -> &'static str {
     let Some(raw) = time else {
         return "TBA";
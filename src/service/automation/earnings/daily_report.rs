@@ -1,4 +1,3 @@
-use std::env;
 use std::sync::Arc;
 
 use chrono::{Datelike, Duration, Timelike, Utc, Weekday};
@@ -11,8 +10,16 @@ use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
+use super::{day_threads, weekly_report, weekly_summary};
+use crate::models::{EarningsEvent, EarningsRecord};
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
+use crate::service::config::{ConfigHandle, ImageConfig};
+use crate::service::finance::exchange::Exchange;
 use crate::service::finance::FinanceService;
-use super::weekly_report;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -28,54 +35,68 @@ struct IvSnapshot {
 static LAST_DAILY_POST_DATE: Lazy<Mutex<Option<chrono::NaiveDate>>> =
     Lazy::new(|| Mutex::new(None));
 
-fn resolve_channel_id(var_names: &[&str], feature_label: &str) -> Option<ChannelId> {
-    for name in var_names {
-        if let Ok(value) = env::var(name) {
-            match value.parse::<u64>() {
-                Ok(id) => return Some(ChannelId::new(id)),
-                Err(_) => warn!("{feature_label}: {name} is set but not a valid u64 channel id"),
-            }
-        }
-    }
-
-    info!(
-        "{feature_label} not started; set one of these env vars: {:?}",
-        var_names
-    );
-    None
-}
-
 /// Spawn a daily earnings poster (Mon–Fri at 6:00 PM ET).
 pub fn spawn_daily_report_poster(
     http: Arc<Http>,
     finance: Arc<FinanceService>,
-) -> Option<JoinHandle<()>> {
-    if env::var("ENABLE_EARNINGS_PINGER")
-        .map(|v| v == "0")
-        .unwrap_or(false)
-    {
-        info!("Daily earnings poster disabled via ENABLE_EARNINGS_PINGER=0");
-        return None;
-    }
-
-    let channel_id = resolve_channel_id(
-        &["EARNINGS_DAILY_CHANNEL_ID", "EARNINGS_CHANNEL_ID"],
-        "daily earnings poster",
-    )?;
-
-    info!("Starting daily earnings poster to channel {}", channel_id);
-
-    Some(tokio::spawn(async move {
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting daily earnings poster");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Daily earnings poster stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
             if should_post_now().await {
-                if let Err(e) = send_daily_report(&http, &finance, channel_id).await {
-                    warn!("daily earnings poster iteration failed: {e}");
+                let config = config.current();
+                let targets =
+                    jobs::claim_targets(Job::DailyEarnings, &http, store.as_deref(), &config).await;
+                let today = Utc::now().with_timezone(&New_York).date_naive();
+                for channel_id in targets {
+                    let target = day_threads::channel_for_day(
+                        &http,
+                        &config.earnings,
+                        today,
+                        channel_id,
+                        store.as_deref(),
+                    )
+                    .await;
+                    let result = send_daily_report(
+                        &http,
+                        &finance,
+                        store.as_deref(),
+                        &config.images,
+                        target,
+                    )
+                    .await;
+                    jobs::record_run(
+                        Job::DailyEarnings,
+                        &http,
+                        store.as_deref(),
+                        channel_id,
+                        &result,
+                    )
+                    .await;
+                    match result {
+                        Ok(()) => METRICS.record_automation_success("daily_earnings"),
+                        Err(e) => {
+                            METRICS.record_automation_failure("daily_earnings");
+                            warn!("daily earnings poster failed in {channel_id}: {e}");
+                        }
+                    }
                 }
             }
         }
-    }))
+    })
 }
 
 async fn should_post_now() -> bool {
@@ -103,10 +124,13 @@ async fn should_post_now() -> bool {
 }
 
 /// Send a daily earnings report for the current day (Mon–Fri).
-/// If weekend, posts a no-data message.
+/// If weekend, posts a no-data message. With a `store`, each implied move is recorded for the
+/// weekly summary.
 pub async fn send_daily_report(
     http: &Http,
     finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    images: &ImageConfig,
     channel_id: ChannelId,
 ) -> Result<(), String> {
     let now_et = Utc::now().with_timezone(&New_York);
@@ -136,15 +160,23 @@ pub async fn send_daily_report(
     let start = target_date;
     let end = start; // same day
 
-    let events = finance
+    let mut events = finance
         .get_earnings_range(start, end)
         .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+        .map_err(|e| format!("fetch error: {e}"))?
+        .events;
+    jobs::channel_symbol_filter(Job::DailyEarnings, http, finance, store, channel_id)
+        .await
+        .retain_events(&mut events);
+
+    let post = ReportPost::new(
+        format!("Earnings — {}", target_date.format("%a %b %-d")),
+        &["Earnings", "Daily"],
+    );
 
     if events.is_empty() {
         let msg = format!("No companies reporting earnings for ({})", date_label);
-        channel_id
-            .say(http, msg)
+        posting::send_report(http, channel_id, &post, CreateMessage::new().content(msg))
             .await
             .map_err(|e| format!("failed to post empty daily earnings: {e}"))?;
         return Ok(());
@@ -155,14 +187,26 @@ pub async fn send_daily_report(
         date_label
     );
     let mut lines = Vec::new();
-    lines.push(heading.clone());
-    lines.push(String::new());
-
     for ev in &events {
         let session = classify_session(ev.time_of_day.as_deref());
+        let exchange = Exchange::for_symbol(&ev.symbol);
+        if !exchange.has_options {
+            lines.push(format!(
+                "{} [{}] — IV/IM unavailable (no options coverage on {})",
+                ev.symbol, session, exchange.name
+            ));
+            continue;
+        }
         let iv_snapshot =
             fetch_iv_snapshot(finance, &ev.symbol, ev.date.date_naive(), session).await;
 
+        if let (Some(store), Some(iv)) = (store, iv_snapshot.as_ref()) {
+            let mut record = EarningsRecord::new(&ev.symbol, ev.date.date_naive(), session);
+            record.implied_move = Some(iv.implied_move_pct);
+            record.eps_estimate = ev.eps_estimate;
+            weekly_summary::save_record(store, &record).await;
+        }
+
         match iv_snapshot {
             Some(iv) => lines.push(format!(
                 "{} [{}] — IV C {:.1}% | IM ±{:.1}%",
@@ -179,28 +223,28 @@ pub async fn send_daily_report(
     }
 
     // Try to render image (reuse weekly renderer); fall back to text
-    match weekly_report::render_calendar_image(&events).await {
-        Ok(bytes) => {
-            let attachment = CreateAttachment::bytes(bytes, "earnings-daily.png");
+    match weekly_report::render_calendar_image(finance, &events, images).await {
+        Ok(rendered) => {
+            let name = rendered.image.file_name("earnings-daily");
+            let attachment =
+                CreateAttachment::bytes(rendered.image.bytes, name).description(rendered.alt_text);
             info!(
-                "Posting daily earnings report (image) with {} lines of backup text",
+                "Posting daily earnings report (image) with {} lines of text summary",
                 lines.len()
             );
-            channel_id
-                .send_files(
-                    http,
-                    vec![attachment],
-                    CreateMessage::new().content(heading),
-                )
+            let mut message = CreateMessage::new().content(heading).add_file(attachment);
+            if let Some(list) = rendered.full_list {
+                message = message.add_file(CreateAttachment::bytes(list, "earnings-daily.csv"));
+            }
+            posting::send_with_summary(http, channel_id, &post, message, &lines.join("\n"))
                 .await
                 .map_err(|e| format!("failed to post daily earnings image: {e}"))?;
         }
         Err(err) => {
             warn!("Daily earnings image render failed, falling back to text: {err}");
-            let content = lines.join("\n");
+            let content = format!("{heading}\n\n{}", lines.join("\n"));
             info!("Posting daily earnings report (text) with {} lines", lines.len());
-            channel_id
-                .send_message(http, CreateMessage::new().content(content))
+            posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
                 .await
                 .map_err(|e| format!("failed to post daily earnings report: {e}"))?;
         }
@@ -209,7 +253,7 @@ pub async fn send_daily_report(
     Ok(())
 }
 
-fn classify_session(time: Option<&str>) -> &'static str {
+pub(crate) fn classify_session(time: Option<&str>) -> &'static str {
     let Some(raw) = time else {
         return "TBA";
     };
@@ -269,6 +313,18 @@ fn parse_hour_prefix(s: &str) -> Option<u32> {
     digits.parse::<u32>().ok()
 }
 
+/// Implied move in percent from the first option expiry after `ev`'s report, or `None` when
+/// the symbol has no options data.
+pub(crate) async fn implied_move(finance: &FinanceService, ev: &EarningsEvent) -> Option<f64> {
+    if !Exchange::for_symbol(&ev.symbol).has_options {
+        return None;
+    }
+    let session = classify_session(ev.time_of_day.as_deref());
+    fetch_iv_snapshot(finance, &ev.symbol, ev.date.date_naive(), session)
+        .await
+        .map(|iv| iv.implied_move_pct)
+}
+
 async fn fetch_iv_snapshot(
     finance: &FinanceService,
     symbol: &str,
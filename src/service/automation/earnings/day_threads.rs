@@ -0,0 +1,111 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use once_cell::sync::Lazy;
+use serenity::all::{
+    AutoArchiveDuration, ChannelType, CreateMessage, CreateThread, Http, Message,
+};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::models::EarningsEvent;
+use crate::service::config::EarningsConfig;
+use crate::service::store::StateStore;
+
+/// Threads created this process, used when no state store is configured.
+static DAY_THREADS: Lazy<Mutex<HashMap<NaiveDate, ChannelId>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Thread title for a trading day, e.g. "Earnings — Tue Mar 4".
+pub fn thread_name(day: NaiveDate) -> String {
+    format!("Earnings — {}", day.format("%a %b %-d"))
+}
+
+/// Create one public thread per trading day in the calendar's channel and remember it so the
+/// daily and post-earnings reports for that day land there instead of the main channel.
+pub async fn create_day_threads(
+    http: &Http,
+    calendar: &Message,
+    events: &[EarningsEvent],
+    store: Option<&dyn StateStore>,
+) {
+    let days: BTreeSet<NaiveDate> = events
+        .iter()
+        .map(|ev| ev.date.date_naive())
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+        .collect();
+
+    for day in days {
+        let builder = CreateThread::new(thread_name(day))
+            .kind(ChannelType::PublicThread)
+            .auto_archive_duration(AutoArchiveDuration::OneWeek);
+        let thread = match calendar.channel_id.create_thread(http, builder).await {
+            Ok(t) => t,
+            Err(e) => {
+                warn!("failed to create earnings thread for {day}: {e}");
+                continue;
+            }
+        };
+
+        let intro = format!(
+            "Daily and post-earnings reports for {} will be posted here. Calendar: {}",
+            day.format("%A, %b %-d"),
+            calendar.link()
+        );
+        if let Err(e) = thread
+            .id
+            .send_message(http, CreateMessage::new().content(intro))
+            .await
+        {
+            warn!("failed to post intro in earnings thread {}: {e}", thread.id);
+        }
+
+        DAY_THREADS.lock().await.insert(day, thread.id);
+        if let Some(store) = store {
+            if let Err(e) = store.save_earnings_thread(day, thread.id.get()).await {
+                warn!("failed to persist earnings thread for {day}: {e}");
+            }
+        }
+        info!("Created earnings thread {} for {day}", thread.id);
+    }
+}
+
+/// Channel a scheduled report for `day` should go to: the day's thread when one was created
+/// under `fallback`, otherwise `fallback`. Per-day threads are opt-in via `earnings.day_threads`.
+///
+/// One thread is remembered per day, the last calendar's; when several channels get the
+/// calendar (`/config routes`), the others post their reports in the channel itself.
+pub async fn channel_for_day(
+    http: &Http,
+    settings: &EarningsConfig,
+    day: NaiveDate,
+    fallback: ChannelId,
+    store: Option<&dyn StateStore>,
+) -> ChannelId {
+    if !settings.day_threads {
+        return fallback;
+    }
+    let mut thread = DAY_THREADS.lock().await.get(&day).copied();
+    if thread.is_none() {
+        if let Some(store) = store {
+            match store.earnings_thread(day).await {
+                Ok(id) => thread = id.map(ChannelId::new),
+                Err(e) => warn!("failed to load earnings thread for {day}: {e}"),
+            }
+        }
+    }
+    let Some(thread) = thread else {
+        return fallback;
+    };
+    match thread.to_channel(http).await {
+        Ok(channel) => match channel.guild().and_then(|c| c.parent_id) {
+            Some(parent) if parent == fallback => thread,
+            _ => fallback,
+        },
+        Err(e) => {
+            warn!("failed to resolve earnings thread {thread} for {day}: {e}");
+            fallback
+        }
+    }
+}
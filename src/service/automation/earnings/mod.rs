@@ -1,8 +1,12 @@
 pub mod after_daily_report;
 pub mod daily_report;
+pub mod day_threads;
+pub mod scheduled_events;
 pub mod weekly_report;
+pub mod weekly_summary;
 
 // Re-export for convenient access
 pub use after_daily_report::*;
 pub use daily_report::*;
 pub use weekly_report::*;
+pub use weekly_summary::*;
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use serenity::all::{CreateScheduledEvent, Http, ScheduledEventType};
+use serenity::model::prelude::ChannelId;
+use tracing::{info, warn};
+
+use super::daily_report::classify_session;
+use crate::models::EarningsEvent;
+use crate::service::format;
+
+/// Event title, e.g. "NVDA earnings (AMC)".
+pub fn event_name(ev: &EarningsEvent) -> String {
+    format!(
+        "{} earnings ({})",
+        ev.symbol,
+        classify_session(ev.time_of_day.as_deref())
+    )
+}
+
+/// When the report is expected: 8:00 AM ET for BMO, otherwise 4:00 PM ET.
+pub fn event_start(ev: &EarningsEvent) -> Option<chrono::DateTime<Utc>> {
+    let time = match classify_session(ev.time_of_day.as_deref()) {
+        "BMO" => NaiveTime::from_hms_opt(8, 0, 0)?,
+        _ => NaiveTime::from_hms_opt(16, 0, 0)?,
+    };
+    let local = ev.date.date_naive().and_time(time);
+    New_York
+        .from_local_datetime(&local)
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Company name and the EPS and revenue estimates, one per line.
+pub fn event_description(ev: &EarningsEvent) -> String {
+    let mut lines = vec![ev
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} quarterly earnings", ev.symbol))];
+    if let Some(eps) = ev.eps_estimate {
+        lines.push(format!("EPS estimate: {}", format::per_share(eps)));
+    }
+    if let Some(rev) = ev.revenue_estimate {
+        lines.push(format!("Revenue estimate: {}", format::currency(rev)));
+    }
+    lines.join("\n")
+}
+
+/// Create guild scheduled events for the earnings in the weekly calendar with at least
+/// `min_importance`. Events that already exist in the guild (same name and start) are skipped.
+pub async fn create_earnings_events(
+    http: &Http,
+    channel_id: ChannelId,
+    events: &[EarningsEvent],
+    min_importance: i64,
+) {
+    let guild_id = match channel_id.to_channel(http).await.map(|c| c.guild()) {
+        Ok(Some(channel)) => channel.guild_id,
+        Ok(None) => {
+            warn!("earnings scheduled events: channel {channel_id} is not in a guild");
+            return;
+        }
+        Err(e) => {
+            warn!("earnings scheduled events: failed to resolve channel {channel_id}: {e}");
+            return;
+        }
+    };
+
+    let existing: HashSet<(String, i64)> = match guild_id.scheduled_events(http, false).await {
+        Ok(list) => list
+            .into_iter()
+            .map(|e| (e.name, e.start_time.unix_timestamp()))
+            .collect(),
+        Err(e) => {
+            warn!("earnings scheduled events: failed to list events for {guild_id}: {e}");
+            HashSet::new()
+        }
+    };
+
+    let now = Utc::now();
+    let mut created = 0usize;
+
+    for ev in events
+        .iter()
+        .filter(|ev| ev.importance.unwrap_or(0) >= min_importance)
+    {
+        let Some(start) = event_start(ev) else {
+            continue;
+        };
+        if start <= now {
+            continue;
+        }
+        let name = event_name(ev);
+        if existing.contains(&(name.clone(), start.timestamp())) {
+            continue;
+        }
+
+        let builder = CreateScheduledEvent::new(ScheduledEventType::External, name, start)
+            .end_time(start + Duration::hours(1))
+            .location("Earnings release")
+            .description(event_description(ev));
+        match guild_id.create_scheduled_event(http, builder).await {
+            Ok(_) => created += 1,
+            Err(e) => warn!("failed to create scheduled event for {}: {e}", ev.symbol),
+        }
+    }
+
+    info!("Created {created} earnings scheduled event(s) in guild {guild_id}");
+}
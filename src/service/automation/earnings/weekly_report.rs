@@ -1,6 +1,4 @@
-use std::collections::BTreeMap;
-use std::env;
-use std::io::Cursor;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 use ab_glyph::{FontArc, PxScale};
@@ -10,7 +8,7 @@ use font_kit::family_name::FamilyName;
 use font_kit::properties::{Properties, Weight};
 use font_kit::source::SystemSource;
 use image::imageops::{self, FilterType};
-use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbaImage};
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
 use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
 use imageproc::rect::Rect;
 use once_cell::sync::Lazy;
@@ -20,103 +18,147 @@ use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
+use super::{day_threads, scheduled_events};
 use crate::models::EarningsEvent;
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
 use crate::service::command::earnings::format_output;
+use crate::service::config::{Config, ConfigHandle, ImageConfig};
+use crate::service::csv;
+use crate::service::finance::logos::LogoRequest;
 use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::render::{self, EncodedImage};
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
 
-static LAST_POST_DATE: Lazy<Mutex<Option<chrono::NaiveDate>>> = Lazy::new(|| Mutex::new(None));
-
-fn resolve_channel_id(var_names: &[&str], feature_label: &str) -> Option<ChannelId> {
-    for name in var_names {
-        if let Ok(value) = env::var(name) {
-            match value.parse::<u64>() {
-                Ok(id) => return Some(ChannelId::new(id)),
-                Err(_) => warn!("{feature_label}: {name} is set but not a valid u64 channel id"),
-            }
-        }
-    }
+/// Attachment name for the full event list sent when the calendar image is truncated.
+pub const FULL_LIST_FILENAME: &str = "earnings-week.csv";
 
-    info!(
-        "{feature_label} not started; set one of these env vars: {:?}",
-        var_names
-    );
-    None
-}
+static LAST_POST_DATE: Lazy<Mutex<Option<chrono::NaiveDate>>> = Lazy::new(|| Mutex::new(None));
 
 /// Spawn a weekly earnings poster (Sunday at 5pm ET).
 pub fn spawn_earnings_poster(
     http: Arc<Http>,
     finance: Arc<FinanceService>,
-) -> Option<JoinHandle<()>> {
-    if env::var("ENABLE_EARNINGS_PINGER")
-        .map(|v| v == "0")
-        .unwrap_or(false)
-    {
-        info!("Earnings poster disabled via ENABLE_EARNINGS_PINGER=0");
-        return None;
-    }
-
-    let channel_id = resolve_channel_id(
-        &["EARNINGS_WEEKLY_CHANNEL_ID", "EARNINGS_CHANNEL_ID"],
-        "earnings poster",
-    )?;
-
-    info!("Starting weekly earnings poster to channel {}", channel_id);
-
-    Some(tokio::spawn(async move {
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting weekly earnings poster");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Weekly earnings poster stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
             if should_post_now().await {
-                if let Err(e) = post_once(&http, &finance, channel_id).await {
-                    error!("earnings poster iteration failed: {e}");
+                let config = config.current();
+                let targets =
+                    jobs::claim_targets(Job::WeeklyEarnings, &http, store.as_deref(), &config)
+                        .await;
+                for channel_id in targets {
+                    let result =
+                        post_once(&http, &finance, store.as_deref(), &config, channel_id).await;
+                    jobs::record_run(
+                        Job::WeeklyEarnings,
+                        &http,
+                        store.as_deref(),
+                        channel_id,
+                        &result,
+                    )
+                    .await;
+                    match result {
+                        Ok(()) => METRICS.record_automation_success("weekly_earnings"),
+                        Err(e) => {
+                            METRICS.record_automation_failure("weekly_earnings");
+                            error!("earnings poster iteration failed in {channel_id}: {e}");
+                        }
+                    }
                 }
             }
         }
-    }))
+    })
 }
 
-async fn post_once(
+pub(crate) async fn post_once(
     http: &Http,
     finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    config: &Config,
     channel_id: ChannelId,
 ) -> Result<(), String> {
     let now_et = Utc::now().with_timezone(&New_York);
     let start = now_et.date_naive();
     let end = start + chrono::Duration::days(5); // Sunday through Friday
     let week_monday = start + chrono::Duration::days(1);
-    let heading = format!("@everyone 📊 Earnings Calendar — Week of {}", week_monday);
+    let mut heading = format!("@everyone 📊 Earnings Calendar — Week of {}", week_monday);
 
-    let events = finance
+    let range = finance
         .get_earnings_range(start, end)
         .await
         .map_err(|e| format!("fetch error: {e}"))?;
+    if let Some(note) = range.missing_note() {
+        heading.push_str(&format!("\n{note}"));
+    }
+    let mut events = range.events;
+    jobs::channel_symbol_filter(Job::WeeklyEarnings, http, finance, store, channel_id)
+        .await
+        .retain_events(&mut events);
 
     if events.is_empty() {
         info!("No earnings in next 7 days; skipping post");
         return Ok(());
     }
 
-    match render_calendar_image(&events).await {
-        Ok(bytes) => {
-            let attachment = CreateAttachment::bytes(bytes, "earnings-calendar.png");
-            channel_id
-                .send_files(
-                    http,
-                    vec![attachment],
-                    CreateMessage::new().content(heading.clone()),
-                )
+    let post = ReportPost::new(
+        format!("Earnings — Week of {}", week_monday.format("%b %-d")),
+        &["Earnings", "Weekly"],
+    );
+    let calendar = match render_calendar_image(finance, &events, &config.images).await {
+        Ok(rendered) => {
+            let name = rendered.image.file_name("earnings-calendar");
+            let attachment =
+                CreateAttachment::bytes(rendered.image.bytes, name).description(rendered.alt_text);
+            let mut message = CreateMessage::new()
+                .content(heading.clone())
+                .add_file(attachment);
+            if let Some(list) = rendered.full_list {
+                message = message.add_file(CreateAttachment::bytes(list, FULL_LIST_FILENAME));
+            }
+            let summary = format_output(&events);
+            posting::send_with_summary(http, channel_id, &post, message, &summary)
                 .await
-                .map_err(|e| format!("failed to post earnings calendar image: {e}"))?;
+                .map_err(|e| format!("failed to post earnings calendar image: {e}"))?
         }
         Err(render_err) => {
             warn!("Falling back to text earnings calendar: {}", render_err);
             let content = format!("{}\n\n{}", heading, format_output(&events));
-            channel_id
-                .say(http, content)
+            posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
                 .await
-                .map_err(|e| format!("failed to post fallback earnings calendar: {e}"))?;
+                .map_err(|e| format!("failed to post fallback earnings calendar: {e}"))?
         }
+    };
+
+    // Forum posts are already one thread per report, so day threads only apply to text channels.
+    if config.earnings.day_threads && calendar.channel_id == channel_id {
+        day_threads::create_day_threads(http, &calendar, &events, store).await;
+    }
+    if config.earnings.scheduled_events {
+        scheduled_events::create_earnings_events(
+            http,
+            channel_id,
+            &events,
+            config.earnings.event_min_importance,
+        )
+        .await;
     }
 
     Ok(())
@@ -148,16 +190,29 @@ async fn should_post_now() -> bool {
 #[derive(Clone)]
 struct DayColumn {
     date: NaiveDate,
+    /// Top-importance names drawn as wide cards above the sessions, whichever session they're in.
+    featured: Vec<EarningsEvent>,
     before: Vec<EarningsEvent>,
     after: Vec<EarningsEvent>,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum Session {
     Before,
     After,
     Tba,
 }
 
+impl Session {
+    fn code(self) -> &'static str {
+        match self {
+            Session::Before => "BMO",
+            Session::After => "AMC",
+            Session::Tba => "TBA",
+        }
+    }
+}
+
 const HALF_WIDTH: u32 = 180;
 const DAY_WIDTH: u32 = HALF_WIDTH * 2;
 const ENTRY_HEIGHT: u32 = 85;
@@ -175,107 +230,289 @@ const DIVIDER_COLOR: Rgba<u8> = Rgba([220, 220, 220, 255]);
 const LOGO_W: u32 = 120;
 const LOGO_H: u32 = 50;
 const MAX_PER_COLUMN: usize = 12;
+/// Importance score that earns a card in the featured row.
+const FEATURED_IMPORTANCE: i64 = 5;
+/// Featured cards per day; further importance-5 names head their session's list instead.
+const MAX_FEATURED: usize = 2;
+/// Space between the date label and the featured row, and below it for the session labels.
+const FEATURED_TOP_PAD: u32 = 8;
+const SESSION_LABEL_HEIGHT: u32 = 20;
+const METRIC_LINE_HEIGHT: u32 = 16;
+/// Names listed in the image's alt text; keeps it well under Discord's 1024 characters.
+const ALT_TEXT_NAMES: usize = 10;
+
+/// Which metric lines the entry cards carry. A line only appears when some shown entry has a
+/// value for it, so calendars without estimates keep the compact cards.
+#[derive(Clone, Copy)]
+struct CardLayout {
+    estimates: bool,
+    market_cap: bool,
+}
 
-pub async fn render_calendar_image(events: &[EarningsEvent]) -> Result<Vec<u8>, String> {
-    let columns = build_columns(events);
-    if columns.is_empty() {
-        return Err("no events to render".into());
+impl CardLayout {
+    fn for_columns(columns: &[DayColumn]) -> Self {
+        let mut layout = CardLayout {
+            estimates: false,
+            market_cap: false,
+        };
+        for ev in columns.iter().flat_map(DayColumn::shown) {
+            layout.estimates |= ev.eps_estimate.is_some() || ev.revenue_estimate.is_some();
+            layout.market_cap |= ev.market_cap.is_some();
+        }
+        layout
     }
 
-    let font = load_font()?;
-
-    // Fetch logos from URLs provided by API
-    let logos = fetch_logos_from_urls(events).await;
+    fn entry_height(self) -> u32 {
+        ENTRY_HEIGHT + self.metrics_height()
+    }
 
-    let image = DynamicImage::ImageRgba8(draw_canvas(&columns, &font, &logos));
-    let mut buffer = Vec::new();
-    image
-        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
-        .map_err(|e| format!("failed to encode png: {e}"))?;
+    /// Height of the metric lines at the bottom of a card.
+    fn metrics_height(self) -> u32 {
+        (self.estimates as u32 + self.market_cap as u32) * METRIC_LINE_HEIGHT
+    }
 
-    Ok(buffer)
-}
+    /// Height of a card above its metric lines, where the ticker and logo go.
+    fn top_height(self) -> u32 {
+        self.entry_height() - self.metrics_height()
+    }
 
-/// Fetch logos from URLs (replaces base64 decoding)
-async fn fetch_logos_from_urls(
-    events: &[EarningsEvent],
-) -> std::collections::HashMap<String, RgbaImage> {
-    use std::collections::HashMap;
-
-    let mut logos = HashMap::new();
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-        .unwrap_or_default();
-
-    // Collect unique logo URLs
-    let mut logo_urls: HashMap<String, String> = HashMap::new();
-    for event in events {
-        if let Some(ref logo_url) = event.logo {
-            // Only fetch if it's a valid URL (not base64 data)
-            if logo_url.starts_with("http://") || logo_url.starts_with("https://") {
-                logo_urls.insert(event.symbol.clone(), logo_url.clone());
-            }
+    /// Height of a featured row holding `rows` cards, including the gap for the session labels
+    /// below it; zero when no day has a featured name.
+    fn featured_height(self, rows: usize) -> u32 {
+        if rows == 0 {
+            return 0;
         }
+        FEATURED_TOP_PAD + rows as u32 * self.entry_height() + SESSION_LABEL_HEIGHT
     }
 
-    info!("Fetching {} logos from URLs...", logo_urls.len());
-
-    // Fetch logos in parallel with limited concurrency
-    let mut tasks = Vec::new();
-    for (symbol, url) in logo_urls {
-        let client = client.clone();
-        let symbol = symbol.clone();
-        let url = url.clone();
-        
-        tasks.push(tokio::spawn(async move {
-            match fetch_single_logo(&client, &url).await {
-                Ok(logo) => Some((symbol, logo)),
-                Err(e) => {
-                    warn!("Failed to fetch logo for {} from {}: {}", symbol, url, e);
-                    None
-                }
-            }
-        }));
+    /// One line per enabled row; rows `ev` has no value for are left blank so cards line up.
+    fn lines(self, ev: &EarningsEvent) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.estimates {
+            let eps = ev
+                .eps_estimate
+                .map(|v| format!("EPS {}", format::per_share(v)));
+            let revenue = ev
+                .revenue_estimate
+                .map(|v| format!("Rev {}", format::currency(v)));
+            lines.push(
+                eps.into_iter()
+                    .chain(revenue)
+                    .collect::<Vec<_>>()
+                    .join(" · "),
+            );
+        }
+        if self.market_cap {
+            lines.push(
+                ev.market_cap
+                    .map(|v| format!("Mkt cap {}", format::currency(v)))
+                    .unwrap_or_default(),
+            );
+        }
+        lines
     }
+}
 
-    // Wait for all tasks and collect results
-    for task in tasks {
-        if let Ok(Some((symbol, logo))) = task.await {
-            logos.insert(symbol, logo);
-        }
+/// A rendered calendar, plus the full list when the image couldn't fit every event.
+pub struct CalendarImage {
+    pub image: EncodedImage,
+    /// CSV of every event (date, session, importance, estimates, market cap), present only when
+    /// the per-day caps or the five-day limit left some events off the image.
+    pub full_list: Option<String>,
+    /// Events drawn on the image.
+    pub shown: usize,
+    /// Description for the attachment, read out by screen readers.
+    pub alt_text: String,
+}
+
+pub async fn render_calendar_image(
+    finance: &FinanceService,
+    events: &[EarningsEvent],
+    images: &ImageConfig,
+) -> Result<CalendarImage, String> {
+    let mut columns = build_columns(events);
+    if columns.is_empty() {
+        return Err("no events to render".into());
     }
 
-    info!("Successfully loaded {} logos from URLs", logos.len());
-    logos
+    // Logos for the cards on the image, and estimates the API left out
+    let requests = logo_requests(&columns);
+    let (logos, ()) = tokio::join!(
+        fetch_logos(finance, &requests),
+        fill_estimates(finance, &mut columns)
+    );
+
+    let shown = columns.iter().map(|c| c.shown().count()).sum::<usize>();
+    let full_list = (shown < events.len()).then(|| full_list_csv(events, &columns));
+
+    let images = images.clone();
+    let image = render::blocking("calendar", move || {
+        let font = load_font()?;
+        let layout = CardLayout::for_columns(&columns);
+        let image = DynamicImage::ImageRgba8(draw_canvas(&columns, &font, &logos, layout));
+        render::encode(&image, &images)
+    })
+    .await?;
+
+    Ok(CalendarImage {
+        image,
+        full_list,
+        shown,
+        alt_text: alt_text(events, shown),
+    })
 }
 
-async fn fetch_single_logo(
-    client: &reqwest::Client,
-    url: &str,
-) -> Result<RgbaImage, String> {
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("request failed: {}", e))?;
+/// Alt text for a calendar of `events` with `shown` of them drawn: the dates, how many
+/// companies report, and the most important names with their day and session.
+pub fn alt_text(events: &[EarningsEvent], shown: usize) -> String {
+    let days: BTreeSet<NaiveDate> = events.iter().map(|ev| ev.date.date_naive()).collect();
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        return "Earnings calendar with no companies reporting".to_string();
+    };
+    let range = if first == last {
+        first.format("%a %b %-d").to_string()
+    } else {
+        format!(
+            "{} to {}",
+            first.format("%a %b %-d"),
+            last.format("%a %b %-d")
+        )
+    };
+    let mut text = format!(
+        "Earnings calendar for {range}: {} companies reporting",
+        events.len()
+    );
+    if shown < events.len() {
+        text.push_str(&format!(", {shown} shown"));
+    }
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP {}", response.status()));
+    // Stable, so equal scores keep the calendar API's order.
+    let mut ranked: Vec<&EarningsEvent> = events.iter().collect();
+    ranked.sort_by_key(|ev| std::cmp::Reverse(ev.importance.unwrap_or(0)));
+    let names: Vec<String> = ranked
+        .iter()
+        .take(ALT_TEXT_NAMES)
+        .map(|ev| {
+            format!(
+                "{} ({} {})",
+                ev.symbol,
+                ev.date.format("%a"),
+                classify_session(ev.time_of_day.as_deref()).code()
+            )
+        })
+        .collect();
+    text.push_str(&format!(". Most important: {}.", names.join(", ")));
+    text
+}
+
+/// Every event as CSV, by date and session with the most important first. Estimates looked up
+/// for the image are included; events left off it carry only what the calendar API sent.
+fn full_list_csv(events: &[EarningsEvent], columns: &[DayColumn]) -> String {
+    let filled: HashMap<&str, &EarningsEvent> = columns
+        .iter()
+        .flat_map(DayColumn::shown)
+        .map(|ev| (ev.symbol.as_str(), ev))
+        .collect();
+    let mut rows: Vec<(&EarningsEvent, Session)> = events
+        .iter()
+        .map(|ev| {
+            let session = classify_session(ev.time_of_day.as_deref());
+            (*filled.get(ev.symbol.as_str()).unwrap_or(&ev), session)
+        })
+        .collect();
+    rows.sort_by(|(a, a_session), (b, b_session)| {
+        a.date
+            .date_naive()
+            .cmp(&b.date.date_naive())
+            .then_with(|| a_session.cmp(b_session))
+            .then_with(|| importance(b).cmp(&importance(a)))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+
+    let mut out = csv::row(&[
+        "date",
+        "session",
+        "symbol",
+        "company",
+        "importance",
+        "eps_estimate",
+        "revenue_estimate",
+        "market_cap",
+    ]);
+    for (ev, session) in rows {
+        out.push_str(&csv::row(&[
+            ev.date.date_naive().to_string(),
+            session.code().to_string(),
+            ev.symbol.clone(),
+            ev.title.clone().unwrap_or_default(),
+            ev.importance.map(|i| i.to_string()).unwrap_or_default(),
+            csv::number(ev.eps_estimate),
+            csv::number(ev.revenue_estimate),
+            csv::number(ev.market_cap),
+        ]));
     }
+    out
+}
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("failed to read bytes: {}", e))?;
+/// One logo request per symbol drawn on the image, with the calendar API's URL if it sent one.
+fn logo_requests(columns: &[DayColumn]) -> Vec<LogoRequest> {
+    let mut seen = BTreeSet::new();
+    columns
+        .iter()
+        .flat_map(DayColumn::shown)
+        .filter(|ev| seen.insert(ev.symbol.clone()))
+        .map(|ev| LogoRequest {
+            symbol: ev.symbol.clone(),
+            calendar_url: ev.logo.clone(),
+        })
+        .collect()
+}
 
-    let img = image::load_from_memory(&bytes)
-        .map_err(|e| format!("failed to decode image: {}", e))?;
+/// Logos from the provider chain, fitted to the card's logo slot. Cards without one get the
+/// symbol's initials when drawn.
+async fn fetch_logos(
+    finance: &FinanceService,
+    requests: &[LogoRequest],
+) -> HashMap<String, RgbaImage> {
+    finance
+        .company_logos(requests)
+        .await
+        .into_iter()
+        .map(|(symbol, logo)| (symbol, fit_logo(&logo)))
+        .collect()
+}
 
-    Ok(fit_logo(&img))
+/// Look up EPS and revenue estimates and market caps the calendar API did not send, for the
+/// entries that make it onto the image. Failed lookups leave the entry's columns empty.
+async fn fill_estimates(finance: &FinanceService, columns: &mut [DayColumn]) {
+    let symbols: Vec<String> = columns
+        .iter()
+        .flat_map(DayColumn::shown)
+        .filter(|ev| ev.needs_estimates())
+        .map(|ev| ev.symbol.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if symbols.is_empty() {
+        return;
+    }
+    let estimates = finance.get_earnings_estimates(&symbols).await;
+
+    for column in columns.iter_mut() {
+        let shown = column
+            .featured
+            .iter_mut()
+            .chain(column.before.iter_mut().take(MAX_PER_COLUMN))
+            .chain(column.after.iter_mut().take(MAX_PER_COLUMN));
+        for ev in shown {
+            if let Some(estimate) = estimates.get(&ev.symbol) {
+                ev.fill_estimates(estimate);
+            }
+        }
+    }
 }
 
-fn load_font() -> Result<FontArc, String> {
+pub(crate) fn load_font() -> Result<FontArc, String> {
     let source = SystemSource::new();
 
     let handle = source
@@ -323,6 +560,7 @@ fn build_columns(events: &[EarningsEvent]) -> Vec<DayColumn> {
         let date = ev.date.date_naive();
         let entry = grouped.entry(date).or_insert_with(|| DayColumn {
             date,
+            featured: Vec::new(),
             before: Vec::new(),
             after: Vec::new(),
         });
@@ -333,31 +571,98 @@ fn build_columns(events: &[EarningsEvent]) -> Vec<DayColumn> {
         }
     }
 
-    grouped.into_values().take(5).collect()
+    let mut columns: Vec<DayColumn> = grouped.into_values().take(5).collect();
+    for column in &mut columns {
+        column.rank();
+    }
+    columns
+}
+
+fn importance(ev: &EarningsEvent) -> i64 {
+    ev.importance.unwrap_or(0)
+}
+
+impl DayColumn {
+    /// Order each session by importance, keeping the API's order among equals, so the least
+    /// important names are the ones cut into the `+N` overflow. Then lift the top names out into
+    /// the featured row.
+    fn rank(&mut self) {
+        self.before
+            .sort_by_key(|ev| std::cmp::Reverse(importance(ev)));
+        self.after
+            .sort_by_key(|ev| std::cmp::Reverse(importance(ev)));
+
+        let featured_before = self
+            .before
+            .iter()
+            .take_while(|ev| importance(ev) >= FEATURED_IMPORTANCE)
+            .count()
+            .min(MAX_FEATURED);
+        let featured_after = self
+            .after
+            .iter()
+            .take_while(|ev| importance(ev) >= FEATURED_IMPORTANCE)
+            .count()
+            .min(MAX_FEATURED - featured_before);
+        self.featured = self
+            .before
+            .drain(..featured_before)
+            .chain(self.after.drain(..featured_after))
+            .collect();
+    }
+
+    /// Entries drawn on the image; the rest only count towards the `+N` overflow.
+    fn shown(&self) -> impl Iterator<Item = &EarningsEvent> {
+        self.featured.iter().chain(
+            self.before
+                .iter()
+                .take(MAX_PER_COLUMN)
+                .chain(self.after.iter().take(MAX_PER_COLUMN)),
+        )
+    }
+
+    fn session_rows(&self) -> usize {
+        self.before.len().max(self.after.len()).min(MAX_PER_COLUMN)
+    }
 }
 
 fn draw_canvas(
     columns: &[DayColumn],
     font: &FontArc,
     logos: &std::collections::HashMap<String, RgbaImage>,
+    layout: CardLayout,
 ) -> RgbaImage {
     let count = columns.len() as u32;
     let width = count * (DAY_WIDTH + DIVIDER_WIDTH) + 2 * MARGIN - DIVIDER_WIDTH;
 
     let max_entries = columns
         .iter()
-        .map(|c| c.before.len().max(c.after.len()))
+        .map(DayColumn::session_rows)
         .max()
-        .unwrap_or(0)
-        .min(MAX_PER_COLUMN);
+        .unwrap_or(0);
+    let featured_rows = columns.iter().map(|c| c.featured.len()).max().unwrap_or(0);
+    let featured_height = layout.featured_height(featured_rows);
 
-    let height = MARGIN + HEADER_HEIGHT + (max_entries as u32 * ENTRY_HEIGHT) + MARGIN;
+    let height = MARGIN
+        + HEADER_HEIGHT
+        + featured_height
+        + (max_entries as u32 * layout.entry_height())
+        + MARGIN;
 
     let mut img = RgbaImage::from_pixel(width, height, CANVAS_BG);
 
     for (idx, column) in columns.iter().enumerate() {
         let x = MARGIN + idx as u32 * (DAY_WIDTH + DIVIDER_WIDTH);
-        draw_day_column(&mut img, x, MARGIN, column, font, logos);
+        draw_day_column(
+            &mut img,
+            x,
+            MARGIN,
+            column,
+            featured_height,
+            font,
+            logos,
+            layout,
+        );
 
         if idx < columns.len() - 1 {
             let divider_x = x + DAY_WIDTH;
@@ -370,20 +675,21 @@ fn draw_canvas(
     img
 }
 
+/// `featured_height` is the featured row's height on the whole image, so the sessions below it
+/// line up across days.
+#[allow(clippy::too_many_arguments)]
 fn draw_day_column(
     img: &mut RgbaImage,
     x: u32,
     y: u32,
     column: &DayColumn,
+    featured_height: u32,
     font: &FontArc,
     logos: &std::collections::HashMap<String, RgbaImage>,
+    layout: CardLayout,
 ) {
-    let max_entries = column
-        .before
-        .len()
-        .max(column.after.len())
-        .min(MAX_PER_COLUMN);
-    let col_height = HEADER_HEIGHT + (max_entries as u32 * ENTRY_HEIGHT);
+    let max_entries = column.session_rows();
+    let col_height = HEADER_HEIGHT + featured_height + (max_entries as u32 * layout.entry_height());
     let bg_rect = Rect::at(x as i32, y as i32).of_size(DAY_WIDTH, col_height);
     draw_filled_rect_mut(img, bg_rect, COLUMN_BG);
 
@@ -411,7 +717,13 @@ fn draw_day_column(
         HEADER_COLOR,
     );
 
-    let header_y = y + HEADER_HEIGHT - 20;
+    let featured_y = y + HEADER_HEIGHT + FEATURED_TOP_PAD;
+    for (idx, ev) in column.featured.iter().enumerate() {
+        let card_y = featured_y + idx as u32 * layout.entry_height();
+        draw_featured_card(img, font, x, card_y, ev, logos, layout);
+    }
+
+    let header_y = y + HEADER_HEIGHT + featured_height - 20;
     draw_centered_text(
         img,
         font,
@@ -433,8 +745,8 @@ fn draw_day_column(
         HEADER_COLOR,
     );
 
-    let entry_start_y = y + HEADER_HEIGHT;
-    draw_half_column(img, font, x, entry_start_y, &column.before, logos);
+    let entry_start_y = y + HEADER_HEIGHT + featured_height;
+    draw_half_column(img, font, x, entry_start_y, &column.before, logos, layout);
     draw_half_column(
         img,
         font,
@@ -442,9 +754,74 @@ fn draw_day_column(
         entry_start_y,
         &column.after,
         logos,
+        layout,
     );
 }
 
+/// A day-wide card for a featured name: a larger ticker, its session, and the usual logo and
+/// metric lines.
+fn draw_featured_card(
+    img: &mut RgbaImage,
+    font: &FontArc,
+    x: u32,
+    y: u32,
+    ev: &EarningsEvent,
+    logos: &std::collections::HashMap<String, RgbaImage>,
+    layout: CardLayout,
+) {
+    let entry_height = layout.entry_height();
+    let card_rect =
+        Rect::at((x + 4) as i32, (y + 2) as i32).of_size(DAY_WIDTH - 8, entry_height - 4);
+    draw_filled_rect_mut(img, card_rect, ENTRY_BG);
+
+    let text_x = x + 12;
+    draw_text_mut(
+        img,
+        TITLE_COLOR,
+        text_x as i32,
+        (y + 10) as i32,
+        PxScale::from(24.0),
+        font,
+        &ev.symbol,
+    );
+    let session = match classify_session(ev.time_of_day.as_deref()) {
+        Session::Before => "Before Open",
+        Session::After => "After Close",
+        Session::Tba => "Time TBA",
+    };
+    draw_text_mut(
+        img,
+        HEADER_COLOR,
+        text_x as i32,
+        (y + 40) as i32,
+        PxScale::from(12.0),
+        font,
+        session,
+    );
+
+    let logo_x = x + DAY_WIDTH - LOGO_W - 8;
+    let logo_y = y + (layout.top_height().saturating_sub(LOGO_H)) / 2;
+    if let Some(logo) = logos.get(&ev.symbol) {
+        imageops::overlay(img, logo, logo_x as i64, logo_y as i64);
+    } else {
+        let placeholder = placeholder_logo(&ev.symbol, font);
+        imageops::overlay(img, &placeholder, logo_x as i64, logo_y as i64);
+    }
+
+    for (line, text) in layout.lines(ev).iter().enumerate() {
+        let line_y = y + layout.top_height() - 8 + line as u32 * METRIC_LINE_HEIGHT;
+        draw_text_mut(
+            img,
+            HEADER_COLOR,
+            text_x as i32,
+            line_y as i32,
+            PxScale::from(11.0),
+            font,
+            text,
+        );
+    }
+}
+
 fn draw_half_column(
     img: &mut RgbaImage,
     font: &FontArc,
@@ -452,12 +829,14 @@ fn draw_half_column(
     y: u32,
     events: &[EarningsEvent],
     logos: &std::collections::HashMap<String, RgbaImage>,
+    layout: CardLayout,
 ) {
+    let entry_height = layout.entry_height();
     for (idx, ev) in events.iter().take(MAX_PER_COLUMN).enumerate() {
-        let entry_y = y + (idx as u32 * ENTRY_HEIGHT);
+        let entry_y = y + (idx as u32 * entry_height);
 
         let entry_rect = Rect::at((x + 4) as i32, (entry_y + 2) as i32)
-            .of_size(HALF_WIDTH - 8, ENTRY_HEIGHT - 4);
+            .of_size(HALF_WIDTH - 8, entry_height - 4);
         draw_filled_rect_mut(img, entry_rect, ENTRY_BG);
 
         // Place ticker on the left and logo on the right so they do not overlap
@@ -474,18 +853,31 @@ fn draw_half_column(
         );
 
         let logo_x = x + HALF_WIDTH - LOGO_W - 8;
-        let logo_y = entry_y + (ENTRY_HEIGHT.saturating_sub(LOGO_H)) / 2;
+        let logo_y = entry_y + (layout.top_height().saturating_sub(LOGO_H)) / 2;
         if let Some(logo) = logos.get(&ev.symbol) {
             imageops::overlay(img, logo, logo_x as i64, logo_y as i64);
         } else {
             let placeholder = placeholder_logo(&ev.symbol, font);
             imageops::overlay(img, &placeholder, logo_x as i64, logo_y as i64);
         }
+
+        for (line, text) in layout.lines(ev).iter().enumerate() {
+            let line_y = entry_y + layout.top_height() - 8 + line as u32 * METRIC_LINE_HEIGHT;
+            draw_text_mut(
+                img,
+                HEADER_COLOR,
+                text_x as i32,
+                line_y as i32,
+                PxScale::from(11.0),
+                font,
+                text,
+            );
+        }
     }
 
     let overflow = events.len().saturating_sub(MAX_PER_COLUMN);
     if overflow > 0 {
-        let overflow_y = y + (MAX_PER_COLUMN as u32 * ENTRY_HEIGHT) - 15;
+        let overflow_y = y + (MAX_PER_COLUMN as u32 * entry_height) - 15;
         let notice = format!("+{}", overflow);
         draw_centered_text(
             img,
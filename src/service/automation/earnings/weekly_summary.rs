@@ -0,0 +1,235 @@
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, NaiveDate, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
+use once_cell::sync::Lazy;
+use serenity::all::{CreateMessage, Http};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::EarningsRecord;
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
+use crate::service::config::ConfigHandle;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+
+const TOP_SURPRISES: usize = 3;
+const TOP_MOVES: usize = 5;
+
+static LAST_SUMMARY_POST_DATE: Lazy<Mutex<Option<NaiveDate>>> = Lazy::new(|| Mutex::new(None));
+
+/// Remember what a report showed for the Friday summary. Failures are logged; the report that
+/// observed the numbers has already been posted.
+pub(crate) async fn save_record(store: &dyn StateStore, record: &EarningsRecord) {
+    if let Err(e) = store.save_earnings_record(record).await {
+        warn!(
+            "failed to save earnings record for {}: {}",
+            record.symbol, e
+        );
+    }
+}
+
+/// Spawn the weekly earnings summary poster (Fridays at 7:00 PM ET).
+pub fn spawn_weekly_summary_poster(
+    http: Arc<Http>,
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting weekly earnings summary poster");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Weekly earnings summary poster stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            if should_post_now().await {
+                let config = config.current();
+                let targets =
+                    jobs::claim_targets(Job::EarningsSummary, &http, store.as_deref(), &config)
+                        .await;
+                for channel_id in targets {
+                    let result = post_summary(&http, store.as_deref(), channel_id).await;
+                    jobs::record_run(
+                        Job::EarningsSummary,
+                        &http,
+                        store.as_deref(),
+                        channel_id,
+                        &result,
+                    )
+                    .await;
+                    match result {
+                        Ok(()) => METRICS.record_automation_success("earnings_summary"),
+                        Err(e) => {
+                            METRICS.record_automation_failure("earnings_summary");
+                            warn!("weekly earnings summary failed in {channel_id}: {e}");
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Post the summary of this week's (Mon–Fri, ET) reports from the records the daily and
+/// post-earnings reports saved.
+pub async fn post_summary(
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+) -> Result<(), String> {
+    let store =
+        store.ok_or("the weekly summary needs a state store (REDIS_URL or DATABASE_URL)")?;
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let records = store
+        .earnings_records(monday, monday + Duration::days(4))
+        .await
+        .map_err(|e| format!("failed to load earnings records: {e}"))?;
+
+    let post = ReportPost::new(
+        format!("Earnings recap — Week of {}", monday.format("%b %-d")),
+        &["Earnings", "Weekly"],
+    );
+    let content = format_summary(monday, &records);
+    posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
+        .await
+        .map_err(|e| format!("failed to post weekly earnings summary: {e}"))?;
+    Ok(())
+}
+
+/// The summary text: counts, biggest surprises both ways, biggest price reactions, and how the
+/// realized moves compared with the options-implied ones.
+pub fn format_summary(monday: NaiveDate, records: &[EarningsRecord]) -> String {
+    let mut lines = vec![format!(
+        "📋 Earnings week in review — Week of {}",
+        monday.format("%b %-d")
+    )];
+    let reported: Vec<&EarningsRecord> = records
+        .iter()
+        .filter(|r| r.eps_actual.is_some() || r.price_move.is_some())
+        .collect();
+    if reported.is_empty() {
+        lines.push("No earnings results were recorded this week.".to_string());
+        return lines.join("\n");
+    }
+
+    let mut surprises: Vec<(&EarningsRecord, f64)> = reported
+        .iter()
+        .filter_map(|r| r.eps_surprise().map(|s| (*r, s)))
+        .collect();
+    surprises.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let beats = surprises.iter().filter(|(_, s)| *s > 0.0).count();
+    let misses = surprises.iter().filter(|(_, s)| *s < 0.0).count();
+    lines.push(format!(
+        "{} reports · {} beats · {} misses · {} in line",
+        reported.len(),
+        beats,
+        misses,
+        surprises.len() - beats - misses
+    ));
+
+    let top_beats: Vec<String> = surprises
+        .iter()
+        .filter(|(_, s)| *s > 0.0)
+        .take(TOP_SURPRISES)
+        .map(|(r, s)| surprise_line(r, *s))
+        .collect();
+    let top_misses: Vec<String> = surprises
+        .iter()
+        .rev()
+        .filter(|(_, s)| *s < 0.0)
+        .take(TOP_SURPRISES)
+        .map(|(r, s)| surprise_line(r, *s))
+        .collect();
+    push_section(&mut lines, "✅ Biggest beats", top_beats);
+    push_section(&mut lines, "❌ Biggest misses", top_misses);
+
+    let mut moves: Vec<(&EarningsRecord, f64)> = reported
+        .iter()
+        .filter_map(|r| r.price_move.map(|m| (*r, m)))
+        .collect();
+    moves.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    let top_moves: Vec<String> = moves
+        .iter()
+        .take(TOP_MOVES)
+        .map(|(r, m)| match r.implied_move {
+            Some(implied) => format!("• {} {:+.1}% (implied ±{:.1}%)", r.symbol, m, implied),
+            None => format!("• {} {:+.1}%", r.symbol, m),
+        })
+        .collect();
+    push_section(&mut lines, "📈 Biggest moves", top_moves);
+
+    let compared: Vec<(f64, f64)> = moves
+        .iter()
+        .filter_map(|(r, m)| r.implied_move.map(|implied| (m.abs(), implied)))
+        .collect();
+    if !compared.is_empty() {
+        let n = compared.len() as f64;
+        let actual = compared.iter().map(|(a, _)| a).sum::<f64>() / n;
+        let implied = compared.iter().map(|(_, i)| i).sum::<f64>() / n;
+        let exceeded = compared.iter().filter(|(a, i)| a > i).count();
+        push_section(
+            &mut lines,
+            "🎯 Implied vs actual",
+            vec![format!(
+                "Average move {:.1}% vs ±{:.1}% implied · {}/{} moved more than implied",
+                actual,
+                implied,
+                exceeded,
+                compared.len()
+            )],
+        );
+    }
+
+    lines.join("\n")
+}
+
+fn surprise_line(record: &EarningsRecord, surprise: f64) -> String {
+    format!(
+        "• {} {:+.1}% (EPS {:.2} vs {:.2} est)",
+        record.symbol,
+        surprise,
+        record.eps_actual.unwrap_or_default(),
+        record.eps_estimate.unwrap_or_default()
+    )
+}
+
+fn push_section(lines: &mut Vec<String>, title: &str, entries: Vec<String>) {
+    if entries.is_empty() {
+        return;
+    }
+    lines.push(String::new());
+    lines.push(format!("**{}**", title));
+    lines.extend(entries);
+}
+
+async fn should_post_now() -> bool {
+    let now_et = Utc::now().with_timezone(&New_York);
+
+    // Only on Fridays at 7:00 PM ET, after the last post-earnings report of the week
+    if now_et.weekday() != Weekday::Fri {
+        return false;
+    }
+    if !(now_et.hour() == 19 && now_et.minute() < 5) {
+        return false;
+    }
+
+    let today = now_et.date_naive();
+    let mut last = LAST_SUMMARY_POST_DATE.lock().await;
+    if *last == Some(today) {
+        return false;
+    }
+    *last = Some(today);
+    true
+}
@@ -0,0 +1,264 @@
+//! Daily event alert check: every weekday at 8:10 AM ET, fetch the earnings date and analyst
+//! target of each symbol with an `/event-alert`, compare with the snapshot each alert last
+//! saw and post what changed in the alert's channel, mentioning whoever set it.
+//!
+//! Alerts stay until removed. Their definitions live in the state store; the snapshot each one
+//! compares against is saved back to Redis after every check, or kept in memory without Redis.
+//! An alert without a snapshot, e.g. after a restart without Redis, takes the values of its
+//! next check as the baseline.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDate, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
+use serenity::all::{CreateAllowedMentions, CreateMessage, Http, UserId};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::service::automation::leader;
+use crate::service::caching::collections::event_alerts::{self, EventAlert};
+use crate::service::caching::RedisCache;
+use crate::service::finance::fundamental_events::{evaluate, EventChange, EventSnapshot};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+
+/// Concurrent lookups.
+const LOOKUPS: usize = 8;
+const JOB_NAME: &str = "event_alerts";
+
+/// A symbol's alerts with updated snapshots, and the ones that fired.
+type Checked = (Vec<EventAlert>, Vec<Fired>);
+
+static LAST_CHECK_DATE: Lazy<Mutex<Option<NaiveDate>>> = Lazy::new(|| Mutex::new(None));
+/// Snapshots by alert id when Redis is not configured.
+static SNAPSHOTS: Lazy<Mutex<HashMap<String, EventSnapshot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Spawn the daily event alert check (weekdays at 8:10 AM ET). Needs a state store, where the
+/// alerts live; without one the task only logs once and exits.
+pub fn spawn_event_alert_watcher(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    cache: Option<Arc<RedisCache>>,
+    store: Option<Arc<dyn StateStore>>,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting event alert watcher");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let Some(store) = store else {
+            info!("Event alerts need a state store; watcher not started");
+            return;
+        };
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Event alert watcher stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            if !leader::is_leader() {
+                continue;
+            }
+            let Some(slot) = due_slot().await else {
+                continue;
+            };
+            if !leader::claim_run(JOB_NAME, slot).await {
+                continue;
+            }
+            match check_once(&http, &finance, store.as_ref(), cache.as_deref()).await {
+                Ok(()) => METRICS.record_automation_success(JOB_NAME),
+                Err(e) => {
+                    METRICS.record_automation_failure(JOB_NAME);
+                    warn!("event alert check failed: {e}");
+                }
+            }
+        }
+    })
+}
+
+/// Today's 8:10 AM ET slot, the first time it is seen on a weekday.
+async fn due_slot() -> Option<chrono::DateTime<chrono_tz::Tz>> {
+    let now_et = Utc::now().with_timezone(&New_York);
+    if matches!(now_et.weekday(), Weekday::Sat | Weekday::Sun)
+        || !(now_et.hour() == 8 && (10..15).contains(&now_et.minute()))
+    {
+        return None;
+    }
+    let today = now_et.date_naive();
+    let mut last = LAST_CHECK_DATE.lock().await;
+    if *last == Some(today) {
+        return None;
+    }
+    *last = Some(today);
+    now_et.with_minute(10)?.with_second(0)?.with_nanosecond(0)
+}
+
+/// The snapshot each alert last saw, by alert id.
+pub async fn load_snapshots(
+    cache: Option<&RedisCache>,
+) -> Result<HashMap<String, EventSnapshot>, String> {
+    let Some(cache) = cache else {
+        return Ok(SNAPSHOTS.lock().await.clone());
+    };
+    event_alerts::load_snapshots(cache)
+        .await
+        .map_err(|e| format!("failed to load event alert snapshots: {e}"))
+}
+
+/// Store the snapshot a new alert starts from.
+pub async fn save_snapshot(
+    cache: Option<&RedisCache>,
+    id: &str,
+    snapshot: &EventSnapshot,
+) -> Result<(), String> {
+    let Some(cache) = cache else {
+        SNAPSHOTS
+            .lock()
+            .await
+            .insert(id.to_string(), snapshot.clone());
+        return Ok(());
+    };
+    event_alerts::save_snapshot(cache, id, snapshot)
+        .await
+        .map_err(|e| format!("failed to save event alert snapshot: {e}"))
+}
+
+async fn replace_snapshots(
+    cache: Option<&RedisCache>,
+    snapshots: HashMap<String, EventSnapshot>,
+) -> Result<(), String> {
+    let Some(cache) = cache else {
+        *SNAPSHOTS.lock().await = snapshots;
+        return Ok(());
+    };
+    event_alerts::replace_snapshots(cache, &snapshots)
+        .await
+        .map_err(|e| format!("failed to save event alert snapshots: {e}"))
+}
+
+/// Check every alert, post the changes and save the snapshots the alerts move to.
+pub async fn check_once(
+    http: &Http,
+    finance: &FinanceService,
+    store: &dyn StateStore,
+    cache: Option<&RedisCache>,
+) -> Result<(), String> {
+    let all = store
+        .event_alerts()
+        .await
+        .map_err(|e| format!("failed to load event alerts: {e}"))?;
+    if all.is_empty() {
+        return Ok(());
+    }
+    let mut snapshots = load_snapshots(cache).await?;
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let symbols = all.len();
+    let ids: HashSet<String> = all.values().flatten().map(|a| a.id.clone()).collect();
+    let stored = &snapshots;
+
+    let results: Vec<Result<Checked, ()>> = stream::iter(all)
+        .map(|(symbol, alerts)| async move {
+            let current = finance
+                .event_snapshot(&symbol)
+                .await
+                .map_err(|e| warn!("event alerts: {symbol} failed: {e}"))?;
+            let mut fired = Vec::new();
+            let alerts = alerts
+                .into_iter()
+                .map(|mut alert| {
+                    let seen = stored.get(&alert.id).unwrap_or(&current);
+                    let (change, seen) =
+                        evaluate(alert.kind, alert.threshold, seen, &current, today);
+                    alert.seen = seen;
+                    if let Some(change) = change {
+                        fired.push(Fired {
+                            alert: alert.clone(),
+                            change,
+                        });
+                    }
+                    alert
+                })
+                .collect();
+            Ok((alerts, fired))
+        })
+        .buffer_unordered(LOOKUPS)
+        .collect()
+        .await;
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    if failed == symbols {
+        return Err(format!("data failed to load for all {symbols} symbols"));
+    }
+
+    // Symbols that failed to load keep their previous snapshots.
+    snapshots.retain(|id, _| ids.contains(id));
+    let mut by_channel: HashMap<u64, Vec<Fired>> = HashMap::new();
+    for (alerts, fired) in results.into_iter().flatten() {
+        for alert in alerts {
+            snapshots.insert(alert.id, alert.seen);
+        }
+        for f in fired {
+            by_channel.entry(f.alert.channel_id).or_default().push(f);
+        }
+    }
+    if let Err(e) = replace_snapshots(cache, snapshots).await {
+        warn!("event alerts: {e}");
+    }
+
+    for (channel_id, mut fired) in by_channel {
+        fired.sort_by(|a, b| a.alert.symbol.cmp(&b.alert.symbol));
+        let users: Vec<UserId> = fired.iter().map(|f| UserId::new(f.alert.user_id)).collect();
+        let lines: Vec<String> = fired.iter().map(Fired::line).collect();
+        let message = CreateMessage::new()
+            .content(lines.join("\n"))
+            .allowed_mentions(CreateAllowedMentions::new().users(users));
+        if let Err(e) = ChannelId::new(channel_id).send_message(http, message).await {
+            warn!("failed to post event alerts to channel {channel_id}: {e}");
+        }
+    }
+    info!("Checked event alerts across {symbols} symbols ({failed} failed)");
+    Ok(())
+}
+
+/// An alert and the change that fired it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fired {
+    pub alert: EventAlert,
+    pub change: EventChange,
+}
+
+impl Fired {
+    /// `🔔 <@123> **NVDA** earnings moved Aug 20 → Aug 27, 2025`
+    pub fn line(&self) -> String {
+        let symbol = &self.alert.symbol;
+        let day = |d: &NaiveDate| d.format("%b %-d, %Y").to_string();
+        let text = match &self.change {
+            EventChange::EarningsScheduled { date } => {
+                format!("**{symbol}** next earnings scheduled for {}", day(date))
+            }
+            EventChange::EarningsMoved { from, to } => format!(
+                "**{symbol}** earnings moved {} → {}",
+                from.format("%b %-d"),
+                day(to)
+            ),
+            EventChange::TargetMoved { from, to } => format!(
+                "**{symbol}** analyst mean target {} → {} ({:+.1}%)",
+                format::per_share(*from),
+                format::per_share(*to),
+                (to / from - 1.0) * 100.0
+            ),
+        };
+        format!("🔔 <@{}> {text}", self.alert.user_id)
+    }
+}
@@ -0,0 +1,121 @@
+//! Consecutive-failure tracking for scheduled jobs: exponential backoff between attempts and a
+//! one-time operator alert once a streak gets long.
+
+use chrono::{DateTime, Duration, Utc};
+use serenity::all::{ChannelId, Http};
+use tracing::{info, warn};
+
+/// Backoff never waits longer than this between attempts.
+const MAX_BACKOFF_MINUTES: i64 = 120;
+
+/// What changed after recording a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreakEvent {
+    /// The streak just reached the alert threshold.
+    Alert { failures: u32, last_error: String },
+    /// A run succeeded after an alerted streak of `failures`.
+    Recovered { failures: u32 },
+}
+
+#[derive(Debug, Clone)]
+pub struct FailureStreak {
+    /// The job's normal interval; backoff doubles it per failure.
+    base: Duration,
+    failures: u32,
+    retry_at: Option<DateTime<Utc>>,
+    alerted: bool,
+}
+
+impl FailureStreak {
+    pub fn new(base: Duration) -> Self {
+        Self {
+            base,
+            failures: 0,
+            retry_at: None,
+            alerted: false,
+        }
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// True while backing off after a failure. Runs due within a minute of the retry time go
+    /// ahead, so a job on fixed marks isn't pushed a whole interval later.
+    pub fn backing_off(&self, now: DateTime<Utc>) -> bool {
+        self.retry_at
+            .is_some_and(|at| now + Duration::minutes(1) < at)
+    }
+
+    /// Wait before the next attempt after `failures` in a row: the base interval, doubled for
+    /// each failure after the first, capped at two hours.
+    pub fn backoff(&self) -> Duration {
+        let doublings = self.failures.saturating_sub(1).min(16);
+        let delay = self.base * 2i32.pow(doublings);
+        delay.min(Duration::minutes(MAX_BACKOFF_MINUTES))
+    }
+
+    /// Record a run. `alert_after` failures in a row raise one alert per streak; a success
+    /// after an alert reports the recovery.
+    pub fn record(
+        &mut self,
+        result: &Result<(), String>,
+        now: DateTime<Utc>,
+        alert_after: u32,
+    ) -> Option<StreakEvent> {
+        match result {
+            Ok(()) => {
+                let event = self.alerted.then_some(StreakEvent::Recovered {
+                    failures: self.failures,
+                });
+                *self = Self::new(self.base);
+                event
+            }
+            Err(error) => {
+                self.failures += 1;
+                self.retry_at = Some(now + self.backoff());
+                if self.alerted || self.failures < alert_after.max(1) {
+                    return None;
+                }
+                self.alerted = true;
+                Some(StreakEvent::Alert {
+                    failures: self.failures,
+                    last_error: error.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// Post a streak alert or recovery for `job` to `channels`: the `errors` routes of the guilds it
+/// posts to, else the operator channel. Without any the event is only logged.
+pub async fn notify(
+    http: &Http,
+    channels: &[ChannelId],
+    job: &str,
+    event: &StreakEvent,
+    backoff: Duration,
+) {
+    let message = match event {
+        StreakEvent::Alert {
+            failures,
+            last_error,
+        } => format!(
+            "🚨 **{job}** failed {failures} times in a row; backing off, next attempt in {} min.\nLast error: `{last_error}`",
+            backoff.num_minutes()
+        ),
+        StreakEvent::Recovered { failures } => {
+            format!("✅ **{job}** recovered after {failures} failed runs.")
+        }
+    };
+    if channels.is_empty() {
+        warn!("{message} (no errors route or operator channel configured)");
+        return;
+    }
+    for channel_id in channels {
+        match channel_id.say(http, &message).await {
+            Ok(_) => info!("Sent {job} streak notice to channel {channel_id}"),
+            Err(err) => warn!("failed to send {job} streak notice to {channel_id}: {err}"),
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Weekly insider net-buying scan: every Saturday at 10:00 AM ET, rank the members of
+//! `insider.universe` by net insider buying over the trailing month and post the top names to
+//! `channels.insider`.
+//!
+//! Net buying comes from each name's Form 4 transactions, summarized like `/holders
+//! type:insider_purchases` but limited to the window. Share counts don't compare across
+//! companies, so names are ranked by net purchase transactions first and net shares second;
+//! only names with more shares bought than sold are listed.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, NaiveDate, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
+use serenity::all::{CreateMessage, Http};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::{HolderType, InsiderPurchase};
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
+use crate::service::config::{Config, ConfigHandle};
+use crate::service::finance::constituents::Index;
+use crate::service::finance::holders::insider_purchases_since;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+
+/// Names listed in the post.
+pub const TOP_NAMES: usize = 10;
+/// Trailing window of insider transactions counted.
+pub const LOOKBACK_DAYS: i64 = 30;
+/// Concurrent insider transaction lookups; a scan of the S&P 500 takes a minute or two.
+const LOOKUPS: usize = 8;
+
+static LAST_SCAN_DATE: Lazy<Mutex<Option<NaiveDate>>> = Lazy::new(|| Mutex::new(None));
+
+/// One name's net insider activity over the window.
+#[derive(Debug, Clone)]
+pub struct InsiderActivity {
+    pub symbol: String,
+    pub summary: InsiderPurchase,
+}
+
+/// A scan's results by symbol, shared by every channel the run posts to.
+#[derive(Debug, Default)]
+struct ScanResults {
+    activity: HashMap<String, InsiderActivity>,
+    failed: HashSet<String>,
+}
+
+/// Spawn the weekly insider scan (Saturdays at 10:00 AM ET).
+pub fn spawn_insider_scanner(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting insider buying scanner");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Insider buying scanner stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            if !should_post_now().await {
+                continue;
+            }
+            let config = config.current();
+            let targets =
+                jobs::claim_targets(Job::InsiderBuying, &http, store.as_deref(), &config).await;
+            let mut channels = Vec::with_capacity(targets.len());
+            for channel_id in targets {
+                let symbols =
+                    channel_symbols(&http, &finance, store.as_deref(), &config, channel_id).await;
+                channels.push((channel_id, symbols));
+            }
+            // One scan of every symbol any channel allows, instead of one per channel.
+            let union: BTreeSet<&String> = channels
+                .iter()
+                .filter_map(|(_, symbols)| symbols.as_ref().ok())
+                .flat_map(|(_, symbols)| symbols)
+                .collect();
+            let union: Vec<String> = union.into_iter().cloned().collect();
+            let results = scan(&finance, &union).await;
+            for (channel_id, symbols) in channels {
+                let result = match symbols {
+                    Ok((index, symbols)) => {
+                        post_results(&http, index, &symbols, &results, channel_id).await
+                    }
+                    Err(e) => Err(e),
+                };
+                jobs::record_run(
+                    Job::InsiderBuying,
+                    &http,
+                    store.as_deref(),
+                    channel_id,
+                    &result,
+                )
+                .await;
+                match result {
+                    Ok(()) => METRICS.record_automation_success("insider_buying"),
+                    Err(e) => {
+                        METRICS.record_automation_failure("insider_buying");
+                        warn!("insider buying scan failed in {channel_id}: {e}");
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn should_post_now() -> bool {
+    let now_et = Utc::now().with_timezone(&New_York);
+    if now_et.weekday() != Weekday::Sat || !(now_et.hour() == 10 && now_et.minute() < 5) {
+        return false;
+    }
+    let today = now_et.date_naive();
+    let mut last = LAST_SCAN_DATE.lock().await;
+    if *last == Some(today) {
+        return false;
+    }
+    *last = Some(today);
+    true
+}
+
+/// Scan the configured universe, limited to what the channel's guild allows with
+/// `/config symbols`, and post the ranking.
+pub async fn post_once(
+    http: &Http,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+    channel_id: ChannelId,
+) -> Result<(), String> {
+    let (index, symbols) = channel_symbols(http, finance, store, config, channel_id).await?;
+    let results = scan(finance, &symbols).await;
+    post_results(http, index, &symbols, &results, channel_id).await
+}
+
+/// The configured universe, limited to what the channel's guild allows, sorted.
+async fn channel_symbols(
+    http: &Http,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+    channel_id: ChannelId,
+) -> Result<(Index, Vec<String>), String> {
+    let index = config
+        .insider
+        .index()
+        .ok_or_else(|| format!("unknown insider.universe {:?}", config.insider.universe))?;
+    let members = finance
+        .constituents(index)
+        .await
+        .map_err(|e| format!("failed to load {} members: {e}", index.label()))?;
+    let filter =
+        jobs::channel_symbol_filter(Job::InsiderBuying, http, finance, store, channel_id).await;
+    let mut symbols: Vec<String> = members
+        .iter()
+        .filter(|s| filter.allows(s, None))
+        .cloned()
+        .collect();
+    symbols.sort();
+    Ok((index, symbols))
+}
+
+/// Post the ranking of the channel's `symbols` from a scan that covered them.
+async fn post_results(
+    http: &Http,
+    index: Index,
+    symbols: &[String],
+    results: &ScanResults,
+    channel_id: ChannelId,
+) -> Result<(), String> {
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let failed = symbols
+        .iter()
+        .filter(|s| results.failed.contains(*s))
+        .count();
+    let activity: Vec<InsiderActivity> = symbols
+        .iter()
+        .filter_map(|s| results.activity.get(s).cloned())
+        .collect();
+    if !symbols.is_empty() && failed == symbols.len() {
+        return Err(format!(
+            "insider transactions failed to load for all {} names",
+            symbols.len()
+        ));
+    }
+
+    let post = ReportPost::new(
+        format!(
+            "Insider buying — Week of {}",
+            week_of(today).format("%b %-d")
+        ),
+        &["Insider", "Weekly"],
+    );
+    let content = format_post(index.label(), today, &rank(activity), symbols.len(), failed);
+    posting::send_chunked(http, channel_id, &post, &content, CreateMessage::new())
+        .await
+        .map_err(|e| format!("failed to post insider buying scan: {e}"))?;
+    info!("Posted insider buying scan of {} names", symbols.len());
+    Ok(())
+}
+
+/// Net activity of every symbol with insider transactions in the window, and the symbols
+/// whose lookup failed.
+async fn scan(finance: &FinanceService, symbols: &[String]) -> ScanResults {
+    let since = Utc::now() - Duration::days(LOOKBACK_DAYS);
+    let period = format!("{LOOKBACK_DAYS}d");
+    let results: Vec<Result<Option<InsiderActivity>, String>> = stream::iter(symbols.to_vec())
+        .map(|symbol| {
+            let period = period.as_str();
+            async move {
+                let data = match finance
+                    .get_holders(&symbol, HolderType::InsiderTransactions)
+                    .await
+                {
+                    Ok(data) => data,
+                    Err(e) => {
+                        warn!("insider scan: {symbol} failed: {e}");
+                        return Err(symbol);
+                    }
+                };
+                let txs = data.insider_transactions.unwrap_or_default();
+                let summary = insider_purchases_since(&txs, since, period);
+                Ok(summary.map(|summary| InsiderActivity { symbol, summary }))
+            }
+        })
+        .buffer_unordered(LOOKUPS)
+        .collect()
+        .await;
+    let mut out = ScanResults::default();
+    for result in results {
+        match result {
+            Ok(Some(activity)) => {
+                out.activity.insert(activity.symbol.clone(), activity);
+            }
+            Ok(None) => {}
+            Err(symbol) => {
+                out.failed.insert(symbol);
+            }
+        }
+    }
+    out
+}
+
+/// Names with positive net shares, most net purchase transactions first, then most net
+/// shares, at most [`TOP_NAMES`].
+pub fn rank(mut activity: Vec<InsiderActivity>) -> Vec<InsiderActivity> {
+    activity.retain(|a| a.summary.net_shares.unwrap_or(0) > 0);
+    activity.sort_by(|a, b| {
+        let key = |x: &InsiderActivity| {
+            (
+                x.summary.net_transactions.unwrap_or(0),
+                x.summary.net_shares.unwrap_or(0),
+            )
+        };
+        key(b).cmp(&key(a)).then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    activity.truncate(TOP_NAMES);
+    activity
+}
+
+/// The post: a numbered line per ranked name, then how many names were scanned.
+pub fn format_post(
+    universe: &str,
+    today: NaiveDate,
+    ranked: &[InsiderActivity],
+    scanned: usize,
+    failed: usize,
+) -> String {
+    let mut lines = vec![format!(
+        "🕵️ **Insider net buying — {universe}, {LOOKBACK_DAYS} days to {}**",
+        today.format("%b %-d")
+    )];
+    if ranked.is_empty() {
+        lines.push(format!(
+            "No {universe} name had more insider buying than selling."
+        ));
+    }
+    for (i, entry) in ranked.iter().enumerate() {
+        let s = &entry.summary;
+        let buys = s.purchases_transactions.unwrap_or(0);
+        let sales = s.sales_transactions.unwrap_or(0);
+        lines.push(format!(
+            "{}. **{}** — net +{} shares · {} {} / {} {}",
+            i + 1,
+            entry.symbol,
+            format::shares(s.net_shares.unwrap_or(0) as f64),
+            buys,
+            if buys == 1 { "buy" } else { "buys" },
+            sales,
+            if sales == 1 { "sale" } else { "sales" }
+        ));
+    }
+    let mut note = format!("Scanned {scanned} names");
+    if failed > 0 {
+        note.push_str(&format!(" · {failed} failed to load"));
+    }
+    lines.push(String::new());
+    lines.push(format!("*{note}*"));
+    lines.join("\n")
+}
+
+/// Monday of the week holding `day`.
+fn week_of(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
@@ -0,0 +1,493 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use serenity::all::Http;
+use serenity::model::prelude::{ChannelId, GuildId};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use super::corporate_actions;
+use super::earnings::{self, day_threads};
+use super::insider_buying;
+use super::leader;
+use super::options_data::spy_data;
+use crate::service::audit::{self, AuditEvent};
+use crate::service::caching::RedisCache;
+use crate::service::config::Config;
+use crate::service::error_reporting;
+use crate::service::finance::FinanceService;
+use crate::service::routing::{self, Route};
+use crate::service::store::StateStore;
+use crate::service::symbol_filter::{self, ResolvedFilter};
+use crate::service::timefmt;
+
+/// Outcome of a job's most recent run in this process.
+#[derive(Debug, Clone)]
+pub struct JobRun {
+    pub at: DateTime<Utc>,
+    pub result: Result<(), String>,
+}
+
+static LAST_RUNS: Lazy<Mutex<HashMap<Job, JobRun>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Pause state used when no state store is configured; lost on restart.
+static PAUSED: Lazy<Mutex<HashSet<(GuildId, Job)>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Every scheduled automation. Spawners resolve their channel here before each run, so a
+/// reloaded config applies without a restart, and `/admin run` uses the same entries to post
+/// on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Job {
+    WeeklyEarnings,
+    DailyEarnings,
+    AfterDaily,
+    EarningsSummary,
+    OptionsPinger,
+    InsiderBuying,
+    CorporateActions,
+}
+
+impl Job {
+    pub const ALL: [Job; 7] = [
+        Job::WeeklyEarnings,
+        Job::DailyEarnings,
+        Job::AfterDaily,
+        Job::EarningsSummary,
+        Job::OptionsPinger,
+        Job::InsiderBuying,
+        Job::CorporateActions,
+    ];
+
+    /// Stable identifier used in commands and Redis keys.
+    pub fn name(self) -> &'static str {
+        match self {
+            Job::WeeklyEarnings => "weekly_earnings",
+            Job::DailyEarnings => "daily_earnings",
+            Job::AfterDaily => "after_daily",
+            Job::EarningsSummary => "earnings_summary",
+            Job::OptionsPinger => "options_pinger",
+            Job::InsiderBuying => "insider_buying",
+            Job::CorporateActions => "corporate_actions",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|job| job.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Job::WeeklyEarnings => "Weekly earnings calendar",
+            Job::DailyEarnings => "Daily earnings (IV/IM)",
+            Job::AfterDaily => "Post-earnings results",
+            Job::EarningsSummary => "Weekly earnings summary",
+            Job::OptionsPinger => "SPY options pinger",
+            Job::InsiderBuying => "Insider net-buying scan",
+            Job::CorporateActions => "Split and dividend alerts",
+        }
+    }
+
+    /// The `/config routes` category the job posts under.
+    pub fn route(self) -> Route {
+        match self {
+            Job::WeeklyEarnings | Job::DailyEarnings | Job::AfterDaily | Job::EarningsSummary => {
+                Route::Earnings
+            }
+            Job::OptionsPinger => Route::Options,
+            Job::InsiderBuying | Job::CorporateActions => Route::Alerts,
+        }
+    }
+
+    /// Scheduled post times (ET) on `weekday`.
+    fn slots(self, weekday: Weekday) -> Vec<NaiveTime> {
+        let weekend = matches!(weekday, Weekday::Sat | Weekday::Sun);
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).into_iter();
+        match self {
+            Job::WeeklyEarnings if weekday == Weekday::Sun => at(17, 0).collect(),
+            Job::DailyEarnings if !weekend => at(18, 0).collect(),
+            Job::AfterDaily if !weekend => at(8, 45).chain(at(17, 50)).collect(),
+            Job::EarningsSummary if weekday == Weekday::Fri => at(19, 0).collect(),
+            // Every 15 minutes from 9:30 until the 15:45 post.
+            Job::OptionsPinger if !weekend => (38..64)
+                .flat_map(|quarter| at(quarter / 4, (quarter % 4) * 15))
+                .collect(),
+            Job::InsiderBuying if weekday == Weekday::Sat => at(10, 0).collect(),
+            Job::CorporateActions if !weekend => at(8, 0).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Next scheduled post strictly after `after`.
+    pub fn next_run(self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        (0..=7).find_map(|offset| {
+            let day = after.date_naive() + Duration::days(offset);
+            self.slots(day.weekday())
+                .into_iter()
+                .filter_map(|t| New_York.from_local_datetime(&day.and_time(t)).earliest())
+                .find(|dt| *dt > after)
+        })
+    }
+
+    /// The scheduled slot a run at `now` belongs to: the latest slot today at or before `now`,
+    /// with a minute's slack for clock skew between replicas. Outside any slot, `now` to the
+    /// minute. Replicas firing the same run agree on it, which keys [`claim_scheduled_run`].
+    pub fn run_slot(self, now: DateTime<Tz>) -> DateTime<Tz> {
+        let day = now.date_naive();
+        let cutoff = now + Duration::minutes(1);
+        self.slots(day.weekday())
+            .into_iter()
+            .filter_map(|t| New_York.from_local_datetime(&day.and_time(t)).earliest())
+            .filter(|slot| *slot <= cutoff)
+            .max()
+            .unwrap_or_else(|| {
+                let minute = now.timestamp() - now.timestamp().rem_euclid(60);
+                now.timezone()
+                    .timestamp_opt(minute, 0)
+                    .single()
+                    .unwrap_or(now)
+            })
+    }
+
+    /// Config keys checked for the job's channel, first set wins.
+    fn channel_keys(self) -> &'static [&'static str] {
+        match self {
+            Job::WeeklyEarnings => &["channels.earnings_weekly", "channels.earnings"],
+            Job::DailyEarnings => &["channels.earnings_daily", "channels.earnings"],
+            Job::AfterDaily => &["channels.earnings_after", "channels.earnings"],
+            Job::EarningsSummary => &["channels.earnings_summary", "channels.earnings"],
+            Job::OptionsPinger => &["channels.options"],
+            Job::InsiderBuying => &["channels.insider"],
+            Job::CorporateActions => &["channels.corporate_actions"],
+        }
+    }
+
+    /// The channel the bot's config gives the job, or `None` when it is not configured. Guilds
+    /// that route the job's category post to their own channel instead; see [`targets`].
+    pub fn channel(self, config: &Config) -> Option<ChannelId> {
+        let channels = &config.channels;
+        let id = match self {
+            Job::WeeklyEarnings => channels.earnings_weekly.or(channels.earnings),
+            Job::DailyEarnings => channels.earnings_daily.or(channels.earnings),
+            Job::AfterDaily => channels.earnings_after.or(channels.earnings),
+            Job::EarningsSummary => channels.earnings_summary.or(channels.earnings),
+            Job::OptionsPinger => channels.options,
+            Job::InsiderBuying => channels.insider,
+            Job::CorporateActions => channels.corporate_actions,
+        };
+        id.map(ChannelId::new)
+    }
+}
+
+/// Claim the current scheduled run of `job` across processes, so replicas that both fire the
+/// same slot (e.g. during a rolling deploy) post it once. Call right before posting.
+pub async fn claim_scheduled_run(job: Job) -> bool {
+    let slot = job.run_slot(Utc::now().with_timezone(&New_York));
+    leader::claim_run(job.name(), slot).await
+}
+
+/// Channels `job` posts to: every guild's `/config routes` channel for its category, and the
+/// configured channel unless its guild routes the category elsewhere.
+pub async fn targets(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+) -> Vec<ChannelId> {
+    let routed = routing::route_channels(store, job.route()).await;
+    let fallback = match job.channel(config) {
+        Some(channel_id) if routed.is_empty() => Some((channel_id, None)),
+        Some(channel_id) => Some((channel_id, guild_of(job, http, channel_id).await)),
+        None => None,
+    };
+    routing::targets(&routed, fallback)
+}
+
+/// The channels a scheduled run of `job` posts to, leaving out guilds that paused it, once
+/// the run is claimed across processes. Empty, with the reason logged, when another process
+/// runs automations or claimed the run, or nothing is routed or configured.
+pub async fn claim_targets(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+) -> Vec<ChannelId> {
+    if !leader::is_leader() {
+        debug!("{} skipped; another process runs automations", job.name());
+        return Vec::new();
+    }
+    let targets = targets(job, http, store, config).await;
+    if targets.is_empty() {
+        info!(
+            "{} skipped; route `{}` with /config routes or set one of {:?} in the config",
+            job.name(),
+            job.route().name(),
+            job.channel_keys()
+        );
+        return Vec::new();
+    }
+    let mut active = Vec::with_capacity(targets.len());
+    for channel_id in targets {
+        if is_paused(job, http, store, channel_id).await {
+            info!("{} paused for channel {channel_id}; skipping", job.name());
+        } else {
+            active.push(channel_id);
+        }
+    }
+    if active.is_empty() || !claim_scheduled_run(job).await {
+        return Vec::new();
+    }
+    active
+}
+
+/// Where `/admin run` posts `job` for `guild_id`: the guild's route, else the configured
+/// channel when it is in `guild_id`. Other servers' admins can't post into the operator's
+/// channels.
+pub async fn run_channel(
+    job: Job,
+    guild_id: Option<GuildId>,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+) -> Option<ChannelId> {
+    let guild_id = guild_id?;
+    if let Some(routed) = routing::guild_route(store, guild_id, job.route()).await {
+        return Some(routed);
+    }
+    let configured = job.channel(config)?;
+    if guild_of(job, http, configured).await == Some(guild_id) {
+        Some(configured)
+    } else {
+        None
+    }
+}
+
+/// Where streak alerts about `job` go: the `errors` route of each guild among `targets`, else
+/// `operator_channel` (`channels.operator`).
+pub async fn error_channels(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    targets: &[ChannelId],
+    operator_channel: Option<u64>,
+) -> Vec<ChannelId> {
+    let mut channels = Vec::new();
+    for channel_id in targets {
+        let Some(guild_id) = guild_of(job, http, *channel_id).await else {
+            continue;
+        };
+        if let Some(errors) = routing::guild_route(store, guild_id, Route::Errors).await {
+            if !channels.contains(&errors) {
+                channels.push(errors);
+            }
+        }
+    }
+    if channels.is_empty() {
+        channels.extend(operator_channel.map(ChannelId::new));
+    }
+    channels
+}
+
+/// Post the job's report right away, outside its schedule, to [`run_channel`] for `guild_id`.
+/// Returns the channel posted to.
+pub async fn run_now(
+    job: Job,
+    guild_id: Option<GuildId>,
+    http: &Http,
+    finance: &FinanceService,
+    cache: Option<&RedisCache>,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+) -> Result<ChannelId, String> {
+    let channel_id = run_channel(job, guild_id, http, store, config)
+        .await
+        .ok_or_else(|| format!("{} has no channel routed or configured", job.name()))?;
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+
+    let target = match job {
+        Job::DailyEarnings | Job::AfterDaily => {
+            day_threads::channel_for_day(http, &config.earnings, today, channel_id, store).await
+        }
+        Job::WeeklyEarnings
+        | Job::EarningsSummary
+        | Job::OptionsPinger
+        | Job::InsiderBuying
+        | Job::CorporateActions => channel_id,
+    };
+    let result = match job {
+        Job::WeeklyEarnings => {
+            earnings::weekly_report::post_once(http, finance, store, config, target).await
+        }
+        Job::DailyEarnings => {
+            earnings::send_daily_report(http, finance, store, &config.images, target).await
+        }
+        Job::AfterDaily => earnings::send_after_daily_report(http, finance, store, target).await,
+        Job::EarningsSummary => earnings::post_summary(http, store, target).await,
+        Job::OptionsPinger => {
+            let timezone = channel_timezone(job, http, store, target).await;
+            let rate = config.options.risk_free_rate;
+            spy_data::post_once(http, finance, cache, target, timezone, rate).await
+        }
+        Job::InsiderBuying => insider_buying::post_once(http, finance, store, config, target).await,
+        Job::CorporateActions => {
+            corporate_actions::post_once(http, finance, store, target, true).await
+        }
+    };
+    record_run(job, http, store, channel_id, &result).await;
+    result?;
+    info!("Ran {} on demand in channel {}", job.name(), target);
+    Ok(target)
+}
+
+/// Remember a run's outcome for `/admin jobs list`. Failures are also sent to error reporting
+/// and the audit channel of the guild owning `channel_id`.
+pub async fn record_run(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+    result: &Result<(), String>,
+) {
+    LAST_RUNS.lock().await.insert(
+        job,
+        JobRun {
+            at: Utc::now(),
+            result: result.clone(),
+        },
+    );
+    if let Err(error) = result {
+        error_reporting::report(&format!("automation:{}", job.name()), error);
+        if let Some(guild_id) = guild_of(job, http, channel_id).await {
+            let event = AuditEvent::AutomationFailed {
+                job: job.name(),
+                error,
+            };
+            audit::record(http, store, guild_id, event).await;
+        }
+    }
+}
+
+pub async fn last_run(job: Job) -> Option<JobRun> {
+    LAST_RUNS.lock().await.get(&job).cloned()
+}
+
+/// Jobs paused in `guild_id`; empty when they can't be loaded.
+pub async fn paused_jobs(store: Option<&dyn StateStore>, guild_id: GuildId) -> HashSet<Job> {
+    load_paused(store, guild_id).await.unwrap_or_else(|e| {
+        warn!("{e}");
+        HashSet::new()
+    })
+}
+
+async fn load_paused(
+    store: Option<&dyn StateStore>,
+    guild_id: GuildId,
+) -> Result<HashSet<Job>, String> {
+    let Some(store) = store else {
+        return Ok(PAUSED
+            .lock()
+            .await
+            .iter()
+            .filter(|(g, _)| *g == guild_id)
+            .map(|(_, job)| *job)
+            .collect());
+    };
+    match store.paused_jobs(guild_id.get()).await {
+        Ok(names) => Ok(names.iter().filter_map(|n| Job::from_name(n)).collect()),
+        Err(e) => Err(format!(
+            "failed to load paused jobs for guild {guild_id}: {e}"
+        )),
+    }
+}
+
+pub async fn set_paused(
+    store: Option<&dyn StateStore>,
+    guild_id: GuildId,
+    job: Job,
+    paused: bool,
+) -> Result<(), String> {
+    let Some(store) = store else {
+        let mut state = PAUSED.lock().await;
+        if paused {
+            state.insert((guild_id, job));
+        } else {
+            state.remove(&(guild_id, job));
+        }
+        return Ok(());
+    };
+    store
+        .set_job_paused(guild_id.get(), job.name(), paused)
+        .await
+        .map_err(|e| format!("failed to save pause state: {e}"))
+}
+
+/// True when the guild owning `channel_id` has paused `job`. Scheduled posters check this
+/// before each post; manual `/admin run` ignores it.
+///
+/// Pauses belong to the channel's guild, not to the guilds reading it: the configured
+/// channel (`channels.*`), which serves every guild without a route, is paused only by the
+/// guild it is in. When the pauses can't be loaded the post is skipped rather than sent to a
+/// guild that may have paused it.
+pub async fn is_paused(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+) -> bool {
+    let Some(guild_id) = guild_of(job, http, channel_id).await else {
+        return false;
+    };
+    match load_paused(store, guild_id).await {
+        Ok(paused) => paused.contains(&job),
+        Err(e) => {
+            warn!("{e}; treating {} as paused in {channel_id}", job.name());
+            true
+        }
+    }
+}
+
+/// Timezone for plain-text times in `channel_id`'s posts, such as forum titles: the owning
+/// guild's `/config timezone`, else US Eastern.
+pub async fn channel_timezone(
+    job: Job,
+    http: &Http,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+) -> Tz {
+    let guild_timezone = match guild_of(job, http, channel_id).await {
+        Some(guild_id) => timefmt::guild_timezone(store, guild_id).await,
+        None => None,
+    };
+    guild_timezone.unwrap_or(timefmt::DEFAULT_TIMEZONE)
+}
+
+/// The owning guild's `/config symbols` filter for `channel_id`'s posts; allows everything
+/// without a store or a guild.
+pub async fn channel_symbol_filter(
+    job: Job,
+    http: &Http,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    channel_id: ChannelId,
+) -> ResolvedFilter {
+    if store.is_none() {
+        return ResolvedFilter::default();
+    }
+    let guild_id = guild_of(job, http, channel_id).await.map(|g| g.get());
+    symbol_filter::guild_filter(store, finance, guild_id).await
+}
+
+async fn guild_of(job: Job, http: &Http, channel_id: ChannelId) -> Option<GuildId> {
+    match channel_id.to_channel(http).await {
+        Ok(channel) => channel.guild().map(|c| c.guild_id),
+        Err(e) => {
+            warn!(
+                "{}: failed to resolve guild of channel {channel_id}: {e}",
+                job.name()
+            );
+            None
+        }
+    }
+}
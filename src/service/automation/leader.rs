@@ -0,0 +1,173 @@
+//! Picks the one process that runs scheduled automations.
+//!
+//! Shards can be split across processes (`discord.shard_range`); every process starts the
+//! automation loops, but only the holder of a Redis lease posts. The lease expires if its
+//! holder stops renewing, so another process takes over within [`LEASE`]. Without Redis there
+//! is nothing to coordinate with and the process always leads.
+//!
+//! Leadership is only checked every [`RENEW_EVERY`], so during a handover (a rolling deploy)
+//! two processes can both believe they lead for a few seconds. Each scheduled run therefore
+//! also claims a per-run lock with [`claim_run`]; whichever process claims it first posts.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone};
+use once_cell::sync::{Lazy, OnceCell};
+use tracing::{info, warn};
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::shutdown::Shutdown;
+
+const LEADER_KEY: &str = "automation:leader";
+const LEASE: Duration = Duration::from_secs(30);
+const RENEW_EVERY: Duration = Duration::from_secs(10);
+/// Run locks only need to outlive the window in which a replica could still fire the same
+/// scheduled run; a day leaves room for clock skew and slow posts.
+const RUN_LOCK_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Extends the lease only while `ARGV[1]` still holds it.
+const RENEW_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// Deletes the lease only while `ARGV[1]` still holds it.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+static IS_LEADER: AtomicBool = AtomicBool::new(false);
+static INSTANCE_ID: Lazy<String> = Lazy::new(instance_id);
+/// Set once election starts with Redis; run locks are claimed here.
+static RUN_LOCKS: OnceCell<Arc<RedisCache>> = OnceCell::new();
+
+/// Whether this process should run scheduled automations right now.
+pub fn is_leader() -> bool {
+    IS_LEADER.load(Ordering::Relaxed)
+}
+
+/// Start competing for the automation lease. The lease is released on shutdown so a standby
+/// process can take over without waiting for it to expire.
+pub fn spawn_leader_election(cache: Option<Arc<RedisCache>>, shutdown: &Shutdown) {
+    let Some(cache) = cache else {
+        IS_LEADER.store(true, Ordering::Relaxed);
+        info!("No Redis configured; this process runs all automations");
+        return;
+    };
+    let _ = RUN_LOCKS.set(cache.clone());
+
+    let id = INSTANCE_ID.clone();
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(RENEW_EVERY);
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            // A Redis outage drops leadership: skipping posts beats posting them twice.
+            let leading = match hold_lease(&cache, &id).await {
+                Ok(leading) => leading,
+                Err(e) => {
+                    warn!("automation leader election failed: {e}");
+                    false
+                }
+            };
+            if IS_LEADER.swap(leading, Ordering::Relaxed) != leading {
+                if leading {
+                    info!("Acquired the automation lease as {id}; running automations");
+                } else {
+                    info!("Lost the automation lease; automations paused in this process");
+                }
+            }
+        }
+
+        IS_LEADER.store(false, Ordering::Relaxed);
+        if let Err(e) = release_lease(&cache, &id).await {
+            warn!("failed to release the automation lease: {e}");
+        }
+    });
+}
+
+/// Claim the scheduled run of `job` at `slot` for this process. `false` means another process
+/// already claimed it and this one should not post. Always `true` without Redis.
+pub async fn claim_run<Tz: TimeZone>(job: &str, slot: DateTime<Tz>) -> bool {
+    let Some(cache) = RUN_LOCKS.get() else {
+        return true;
+    };
+    let key = run_lock_key(job, slot.timestamp());
+    let mut conn = cache.connection();
+    let claimed: Result<Option<String>, _> = redis::cmd("SET")
+        .arg(&key)
+        .arg(INSTANCE_ID.as_str())
+        .arg("NX")
+        .arg("PX")
+        .arg(RUN_LOCK_TTL.as_millis() as u64)
+        .query_async(&mut conn)
+        .await;
+    match claimed {
+        Ok(Some(_)) => true,
+        Ok(None) => {
+            info!("{job} run at {key} already claimed by another process; skipping");
+            false
+        }
+        // As with the lease, a Redis outage skips the run rather than risk a double post.
+        Err(e) => {
+            warn!("failed to claim the {job} run lock: {e}");
+            false
+        }
+    }
+}
+
+/// `automation:run:{job}:{unix seconds of the scheduled slot}`
+pub fn run_lock_key(job: &str, slot_timestamp: i64) -> String {
+    format!("automation:run:{job}:{slot_timestamp}")
+}
+
+/// Take the lease if it is free, or renew it if we already hold it.
+async fn hold_lease(cache: &RedisCache, id: &str) -> Result<bool, CacheError> {
+    let mut conn = cache.connection();
+    let lease_ms = LEASE.as_millis() as u64;
+    let acquired: Option<String> = redis::cmd("SET")
+        .arg(LEADER_KEY)
+        .arg(id)
+        .arg("NX")
+        .arg("PX")
+        .arg(lease_ms)
+        .query_async(&mut conn)
+        .await?;
+    if acquired.is_some() {
+        return Ok(true);
+    }
+    let renewed: i64 = redis::Script::new(RENEW_SCRIPT)
+        .key(LEADER_KEY)
+        .arg(id)
+        .arg(lease_ms)
+        .invoke_async(&mut conn)
+        .await?;
+    Ok(renewed == 1)
+}
+
+async fn release_lease(cache: &RedisCache, id: &str) -> Result<(), CacheError> {
+    let mut conn = cache.connection();
+    let _: i64 = redis::Script::new(RELEASE_SCRIPT)
+        .key(LEADER_KEY)
+        .arg(id)
+        .invoke_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+/// Unique per process, and readable in logs: host name, pid and start time.
+fn instance_id() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "bot".to_string());
+    let started = chrono::Utc::now().timestamp_millis();
+    format!("{host}:{}:{started}", std::process::id())
+}
@@ -1,2 +1,13 @@
+pub mod channel_topic;
+pub mod corporate_actions;
 pub mod earnings;
+pub mod event_alerts;
+pub mod failure_streak;
+pub mod insider_buying;
+pub mod jobs;
+pub mod leader;
+pub mod morning_brief;
 pub mod options_data;
+pub mod posting;
+pub mod presence;
+pub mod quote_board;
@@ -0,0 +1,329 @@
+//! Morning brief DMs: each user who opted in with `/brief set` gets a message at their chosen
+//! local time on weekdays with quotes for their tickers, which of them report earnings that day,
+//! ex-dividend dates in the coming week and each ticker's latest headline.
+//!
+//! Briefs live in the state store; a user gets at most one a day, even across restarts and
+//! replicas.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use futures_util::stream::{self, StreamExt};
+use serenity::all::{CreateMessage, Http, UserId};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::{EarningsEvent, NewsItem, PriceQuote};
+use crate::service::automation::earnings::daily_report::classify_session;
+use crate::service::automation::leader;
+use crate::service::caching::collections::morning_briefs::MorningBrief;
+use crate::service::chunks;
+use crate::service::command::news_multi::{roundup_text, Headline};
+use crate::service::finance::FinanceService;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+use crate::service::timefmt;
+
+const JOB_NAME: &str = "morning_brief";
+/// Concurrent briefs being assembled; each looks up its tickers concurrently too.
+const BRIEFS: usize = 4;
+const LOOKUPS: usize = 4;
+/// A brief missed at its minute (a restart, a lease handover) still goes out within this window.
+const SEND_WINDOW_MINUTES: i64 = 30;
+/// Ex-dividend dates this many days ahead are listed.
+pub const EX_DIVIDEND_DAYS: i64 = 7;
+/// Older headlines are left out rather than repeated every morning.
+const HEADLINE_MAX_AGE_HOURS: i64 = 48;
+
+/// What one brief shows.
+#[derive(Debug, Clone, Default)]
+pub struct BriefData {
+    /// The user's local date.
+    pub date: NaiveDate,
+    /// Quotes in the order the tickers were set; `None` when one failed to load.
+    pub quotes: Vec<(String, Option<PriceQuote>)>,
+    /// The user's tickers reporting on `date`.
+    pub reporting: Vec<EarningsEvent>,
+    /// Upcoming ex-dividend dates, soonest first.
+    pub ex_dividends: Vec<(String, NaiveDate)>,
+    pub headlines: Vec<Headline>,
+}
+
+/// Spawn the brief sender, which checks every minute for briefs that are due. Needs a state
+/// store, where the briefs live; without one the task only logs once and exits.
+pub fn spawn_morning_brief_sender(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    store: Option<Arc<dyn StateStore>>,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting morning brief sender");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let Some(store) = store else {
+            info!("Morning briefs need a state store; sender not started");
+            return;
+        };
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Morning brief sender stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            if !leader::is_leader() {
+                continue;
+            }
+            if let Err(e) = send_due(&http, &finance, store.as_ref(), Utc::now()).await {
+                METRICS.record_automation_failure(JOB_NAME);
+                warn!("morning briefs failed: {e}");
+            }
+        }
+    })
+}
+
+/// The timezone a brief's time is in; US Eastern if the stored name no longer parses.
+pub fn brief_timezone(brief: &MorningBrief) -> Tz {
+    timefmt::parse_timezone(&brief.timezone).unwrap_or(New_York)
+}
+
+/// The user's local date when their brief is due at `now`: a weekday, within the send window
+/// after their time, and not already sent that day.
+pub fn due_date(brief: &MorningBrief, now: DateTime<Utc>) -> Option<NaiveDate> {
+    let local = now.with_timezone(&brief_timezone(brief));
+    let today = local.date_naive();
+    if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) || brief.last_sent == Some(today) {
+        return None;
+    }
+    let since = local.time() - brief.time;
+    (since >= Duration::zero() && since < Duration::minutes(SEND_WINDOW_MINUTES)).then_some(today)
+}
+
+/// Send every brief due at `now`.
+async fn send_due(
+    http: &Http,
+    finance: &FinanceService,
+    store: &dyn StateStore,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    let briefs = store
+        .morning_briefs()
+        .await
+        .map_err(|e| format!("failed to load morning briefs: {e}"))?;
+    let due: Vec<(MorningBrief, NaiveDate)> = briefs
+        .into_values()
+        .filter_map(|brief| due_date(&brief, now).map(|date| (brief, date)))
+        .collect();
+
+    stream::iter(due)
+        .map(|(brief, date)| send_once(http, finance, store, brief, date))
+        .buffer_unordered(BRIEFS)
+        .collect::<Vec<()>>()
+        .await;
+    Ok(())
+}
+
+/// Claim today's brief for the user, record it as sent and send it.
+async fn send_once(
+    http: &Http,
+    finance: &FinanceService,
+    store: &dyn StateStore,
+    mut brief: MorningBrief,
+    date: NaiveDate,
+) {
+    let slot = date.and_time(NaiveTime::MIN).and_utc();
+    if !leader::claim_run(&format!("{JOB_NAME}:{}", brief.user_id), slot).await {
+        return;
+    }
+    // One attempt a day: a user who blocked DMs isn't retried every minute.
+    brief.last_sent = Some(date);
+    if let Err(e) = store.save_morning_brief(&brief).await {
+        warn!(
+            "failed to save morning brief of user {}: {e}",
+            brief.user_id
+        );
+    }
+    match send_brief(http, finance, &brief, date).await {
+        Ok(()) => METRICS.record_automation_success(JOB_NAME),
+        Err(e) => {
+            METRICS.record_automation_failure(JOB_NAME);
+            warn!("morning brief to user {} failed: {e}", brief.user_id);
+        }
+    }
+}
+
+async fn send_brief(
+    http: &Http,
+    finance: &FinanceService,
+    brief: &MorningBrief,
+    date: NaiveDate,
+) -> Result<(), String> {
+    let text = assemble(finance, &brief.symbols, date).await?;
+    let dm = UserId::new(brief.user_id)
+        .create_dm_channel(http)
+        .await
+        .map_err(|e| format!("DM channel error: {e}"))?;
+    for part in chunks::split(&text, chunks::MESSAGE_LIMIT) {
+        dm.send_message(http, CreateMessage::new().content(part))
+            .await
+            .map_err(|e| format!("send error: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Look up everything a brief for `symbols` on `date` shows and render it. Fails when no quote
+/// loads, which means Yahoo is down rather than a quiet morning.
+pub async fn assemble(
+    finance: &FinanceService,
+    symbols: &[String],
+    date: NaiveDate,
+) -> Result<String, String> {
+    let now = Utc::now();
+    let per_symbol: Vec<SymbolData> = stream::iter(symbols.iter().cloned())
+        .map(|symbol| symbol_data(finance, symbol, now))
+        .buffered(LOOKUPS)
+        .collect()
+        .await;
+    if per_symbol.iter().all(|(_, quote, ..)| quote.is_none()) {
+        return Err(format!("no quotes loaded for {} symbols", symbols.len()));
+    }
+
+    let reporting = match finance.get_earnings_range(date, date).await {
+        Ok(range) => range
+            .events
+            .into_iter()
+            .filter(|ev| symbols.contains(&ev.symbol))
+            .collect(),
+        Err(e) => {
+            warn!("morning brief: earnings for {date} failed: {e}");
+            Vec::new()
+        }
+    };
+
+    let mut data = BriefData {
+        date,
+        reporting,
+        ..BriefData::default()
+    };
+    for (symbol, quote, ex_dividend, headline) in per_symbol {
+        if let Some(ex) =
+            ex_dividend.filter(|ex| (date..=date + Duration::days(EX_DIVIDEND_DAYS)).contains(ex))
+        {
+            data.ex_dividends.push((symbol.clone(), ex));
+        }
+        data.quotes.push((symbol.clone(), quote));
+        data.headlines.push((symbol, headline));
+    }
+    data.ex_dividends.sort_by_key(|(_, ex)| *ex);
+    Ok(render(&data))
+}
+
+/// A symbol's quote, next ex-dividend date and latest headline.
+type SymbolData = (
+    String,
+    Option<PriceQuote>,
+    Option<NaiveDate>,
+    Result<Option<NewsItem>, String>,
+);
+
+async fn symbol_data(finance: &FinanceService, symbol: String, now: DateTime<Utc>) -> SymbolData {
+    let (quote, actions, news) = tokio::join!(
+        finance.get_price(&symbol),
+        finance.corporate_actions(&symbol),
+        finance.get_news(&symbol, 1)
+    );
+    let quote = quote
+        .inspect_err(|e| warn!("morning brief: no quote for {symbol}: {e}"))
+        .ok();
+    let ex_dividend = actions
+        .inspect_err(|e| warn!("morning brief: no dividend data for {symbol}: {e}"))
+        .ok()
+        .and_then(|a| a.ex_dividend_date);
+    let headline = news
+        .map(|news| {
+            news.into_iter().next().filter(|item| {
+                item.published_at
+                    .is_none_or(|at| now - at < Duration::hours(HEADLINE_MAX_AGE_HOURS))
+            })
+        })
+        .map_err(|e| e.to_string());
+    (symbol, quote, ex_dividend, headline)
+}
+
+/// The brief text: a quote line per ticker, then earnings, ex-dividend dates and headlines.
+pub fn render(data: &BriefData) -> String {
+    let mut lines = vec![format!(
+        "☀️ **Morning brief** · {}",
+        data.date.format("%A, %b %-d")
+    )];
+    for (symbol, quote) in &data.quotes {
+        lines.push(quote_line(symbol, quote.as_ref()));
+    }
+
+    if !data.reporting.is_empty() {
+        let reports: Vec<String> = data
+            .reporting
+            .iter()
+            .map(|ev| match classify_session(ev.time_of_day.as_deref()) {
+                "BMO" => format!("{} (before open)", ev.symbol),
+                "AMC" => format!("{} (after close)", ev.symbol),
+                _ => ev.symbol.clone(),
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push(format!("📅 **Reporting today:** {}", reports.join(", ")));
+    }
+    if !data.ex_dividends.is_empty() {
+        let dates: Vec<String> = data
+            .ex_dividends
+            .iter()
+            .map(|(symbol, ex)| {
+                if *ex == data.date {
+                    format!("{symbol} today")
+                } else {
+                    format!("{symbol} {}", ex.format("%a %b %-d"))
+                }
+            })
+            .collect();
+        if data.reporting.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("💵 **Ex-dividend:** {}", dates.join(", ")));
+    }
+
+    if !data.headlines.is_empty() {
+        lines.push(String::new());
+        lines.push(roundup_text(&data.headlines));
+    }
+    lines.join("\n")
+}
+
+/// `🟢 **NVDA** 181.23 (+1.17%) · pre 183.10`
+fn quote_line(symbol: &str, quote: Option<&PriceQuote>) -> String {
+    let Some(quote) = quote else {
+        return format!("⚪ **{symbol}** n/a");
+    };
+    let Some(price) = quote.price else {
+        return format!("⚪ **{symbol}** n/a");
+    };
+    let marker = match quote.percent_change {
+        Some(c) if c > 0.0 => "🟢",
+        Some(c) if c < 0.0 => "🔴",
+        _ => "⚪",
+    };
+    let change = quote
+        .percent_change
+        .map(|c| format!(" ({c:+.2}%)"))
+        .unwrap_or_default();
+    let pre = quote
+        .pre_market_price
+        .map(|p| format!(" · pre {p:.2}"))
+        .unwrap_or_default();
+    format!("{marker} **{symbol}** {price:.2}{change}{pre}")
+}
@@ -1,18 +1,27 @@
-use std::{collections::HashMap, env, sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use chrono::{Datelike, Timelike, Utc, Weekday};
 use chrono_tz::America::New_York;
-use finance_query_core::OptionContract;
-use serenity::all::{CreateAttachment, Http};
+use chrono_tz::Tz;
+use serenity::all::{CreateAttachment, CreateMessage, Http};
 use serenity::model::prelude::ChannelId;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
+use crate::service::automation::failure_streak::{self, FailureStreak};
+use crate::service::automation::jobs::{self, Job};
+use crate::service::automation::posting::{self, ReportPost};
 use crate::service::caching::collections::spy_data as cache_spy;
 use crate::service::caching::RedisCache;
-use crate::service::finance::options::OptionSlice;
+use crate::service::config::ConfigHandle;
+use crate::service::finance::options::{OptionKind, OptionSlice};
 use crate::service::finance::FinanceService;
+use crate::service::metrics::METRICS;
+use crate::service::quickchart;
+use crate::service::shutdown::Shutdown;
+use crate::service::store::StateStore;
+use crate::service::timefmt::{self, TimestampStyle};
 
 static LAST_RUN: once_cell::sync::Lazy<Mutex<Option<chrono::DateTime<Utc>>>> =
     once_cell::sync::Lazy::new(|| Mutex::new(None));
@@ -22,53 +31,148 @@ pub fn spawn_options_pinger(
     http: Arc<Http>,
     finance: Arc<FinanceService>,
     cache: Option<Arc<RedisCache>>,
-) -> Option<JoinHandle<()>> {
-    if env::var("ENABLE_OPTIONS_PINGER")
-        .map(|v| v == "0")
-        .unwrap_or(false)
-    {
-        info!("Options pinger disabled via ENABLE_OPTIONS_PINGER=0");
-        return None;
-    }
-
-    let channel_id = match env::var("OPTIONS_CHANNEL_ID")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-    {
-        Some(id) => ChannelId::new(id),
-        None => {
-            info!("OPTIONS_CHANNEL_ID not set; options pinger not started");
-            return None;
-        }
-    };
-
-    info!("Starting options pinger for SPY to channel {}", channel_id);
+    store: Option<Arc<dyn StateStore>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting options pinger for SPY");
     let cache = cache.clone();
 
-    Some(tokio::spawn(async move {
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let mut streak = FailureStreak::new(chrono::Duration::minutes(15));
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Options pinger stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
             if should_run_now().await {
-                if let Err(e) = post_once(&http, &finance, cache.as_deref(), channel_id).await {
-                    error!("options pinger iteration failed: {e}");
+                if streak.backing_off(Utc::now()) {
+                    info!(
+                        "Options pinger backing off after {} failures",
+                        streak.failures()
+                    );
+                    continue;
+                }
+                let cfg = config.current();
+                let mut targets = Vec::new();
+                for channel_id in
+                    jobs::claim_targets(Job::OptionsPinger, &http, store.as_deref(), &cfg).await
+                {
+                    let filter = jobs::channel_symbol_filter(
+                        Job::OptionsPinger,
+                        &http,
+                        &finance,
+                        store.as_deref(),
+                        channel_id,
+                    )
+                    .await;
+                    if filter.allows("SPY", None) {
+                        targets.push(channel_id);
+                    }
+                }
+                if targets.is_empty() {
+                    continue;
+                }
+                // Fetched once per run: the slice history must get one point per run, however
+                // many channels the post goes to.
+                let run = fetch_run(&finance, cache.as_deref()).await;
+                let mut posted = Vec::new();
+                let mut failures = Vec::new();
+                for channel_id in targets {
+                    let timezone = jobs::channel_timezone(
+                        Job::OptionsPinger,
+                        &http,
+                        store.as_deref(),
+                        channel_id,
+                    )
+                    .await;
+                    let result = match &run {
+                        Ok(run) => {
+                            post_run(&http, run, channel_id, timezone, cfg.options.risk_free_rate)
+                                .await
+                        }
+                        Err(e) => Err(e.clone()),
+                    };
+                    jobs::record_run(
+                        Job::OptionsPinger,
+                        &http,
+                        store.as_deref(),
+                        channel_id,
+                        &result,
+                    )
+                    .await;
+                    match result {
+                        Ok(()) => METRICS.record_automation_success("options_pinger"),
+                        Err(e) => {
+                            METRICS.record_automation_failure("options_pinger");
+                            error!("options pinger failed in {channel_id}: {e}");
+                            failures.push(e);
+                        }
+                    }
+                    posted.push(channel_id);
+                }
+                if posted.is_empty() {
+                    continue;
+                }
+                // One streak per run: it failed only when every channel did.
+                let result = if failures.len() == posted.len() {
+                    Err(failures.pop().unwrap_or_default())
+                } else {
+                    Ok(())
+                };
+                let event = streak.record(&result, Utc::now(), cfg.options.alert_after_failures);
+                if let Some(event) = event {
+                    let channels = jobs::error_channels(
+                        Job::OptionsPinger,
+                        &http,
+                        store.as_deref(),
+                        &posted,
+                        cfg.channels.operator,
+                    )
+                    .await;
+                    failure_streak::notify(
+                        &http,
+                        &channels,
+                        "SPY options pinger",
+                        &event,
+                        streak.backoff(),
+                    )
+                    .await;
                 }
             }
         }
-    }))
+    })
 }
 
-async fn post_once(
-    http: &Http,
+/// One run's slice and chart, fetched once and posted to every target channel.
+pub(crate) struct SpyRun {
+    slice: OptionSlice,
+    chart: Result<Vec<u8>, String>,
+}
+
+/// Fetch the slice, record it in the Redis history and render the chart.
+pub(crate) async fn fetch_run(
     finance: &FinanceService,
     cache: Option<&RedisCache>,
-    channel_id: ChannelId,
-) -> Result<(), String> {
+) -> Result<SpyRun, String> {
     let slice = finance
         .get_option_slice_today("SPY", 5)
         .await
         .map_err(|e| e.to_string())?;
 
+    // The first run of the day records SPY's open interest for `/oi-change`; later runs
+    // find the snapshot already stored.
+    if cache.is_some() {
+        if let Err(err) = finance.snapshot_open_interest("SPY").await {
+            warn!("failed to snapshot SPY open interest: {err}");
+        }
+    }
+
     let history = if let Some(cache) = cache {
         if let Err(err) = cache_spy::append_slice(cache, &slice).await {
             warn!("failed to append slice to redis history: {err}");
@@ -93,23 +197,34 @@ async fn post_once(
     };
 
     let history = history.unwrap_or_else(|| cache_spy::history_from_slice(&slice));
+    let chart = build_chart_bytes(&slice, &history).await;
+    Ok(SpyRun { slice, chart })
+}
 
-    let summary = format_slice(&slice);
-    match build_chart_bytes(&slice, &history).await {
+/// Post a fetched run to one channel, in the channel's timezone.
+pub(crate) async fn post_run(
+    http: &Http,
+    run: &SpyRun,
+    channel_id: ChannelId,
+    timezone: Tz,
+    risk_free_rate: f64,
+) -> Result<(), String> {
+    let summary = format_slice(&run.slice, risk_free_rate);
+    let post = ReportPost::new(
+        format!("SPY options — {}", timefmt::plain(&Utc::now(), timezone)),
+        &["Options", "SPY"],
+    );
+    match &run.chart {
         Ok(bytes) => {
-            let attachment = CreateAttachment::bytes(bytes, "spy_options.png");
-            let builder = serenity::builder::CreateMessage::new()
-                .content(summary)
-                .add_file(attachment);
-            channel_id
-                .send_message(http, builder)
+            let attachment = CreateAttachment::bytes(bytes.clone(), "spy_options.png");
+            let builder = CreateMessage::new().add_file(attachment);
+            posting::send_chunked(http, channel_id, &post, &summary, builder)
                 .await
                 .map_err(|e| format!("failed to post options chart: {e}"))?;
         }
         Err(err) => {
             let msg = format!("{summary}\n\n(chart generation failed: {err})");
-            channel_id
-                .say(http, msg)
+            posting::send_chunked(http, channel_id, &post, &msg, CreateMessage::new())
                 .await
                 .map_err(|e| format!("failed to post options text fallback: {e}"))?;
         }
@@ -118,39 +233,67 @@ async fn post_once(
     Ok(())
 }
 
-fn format_slice(slice: &OptionSlice) -> String {
+/// Fetch and post a single run, for `/admin run`.
+pub(crate) async fn post_once(
+    http: &Http,
+    finance: &FinanceService,
+    cache: Option<&RedisCache>,
+    channel_id: ChannelId,
+    timezone: Tz,
+    risk_free_rate: f64,
+) -> Result<(), String> {
+    let run = fetch_run(finance, cache).await?;
+    post_run(http, &run, channel_id, timezone, risk_free_rate).await
+}
+
+fn format_slice(slice: &OptionSlice, risk_free_rate: f64) -> String {
     let mut out = Vec::new();
     out.push(format!(
         "SPY options (exp {}) | spot {:.2} | fetched {}",
         slice.expiration,
         slice.spot,
-        Utc::now().format("%H:%M UTC")
+        timefmt::discord(&Utc::now(), TimestampStyle::ShortTime)
     ));
     out.push(format!(
         "Calls (top 5 above spot):\n{}",
-        fmt_side(&slice.calls)
+        fmt_side(slice, OptionKind::Call, risk_free_rate)
     ));
     out.push(format!(
         "Puts (top 5 below spot):\n{}",
-        fmt_side(&slice.puts)
+        fmt_side(slice, OptionKind::Put, risk_free_rate)
     ));
     out.join("\n\n")
 }
 
-fn fmt_side(contracts: &[OptionContract]) -> String {
+fn fmt_side(slice: &OptionSlice, kind: OptionKind, risk_free_rate: f64) -> String {
+    let contracts = match kind {
+        OptionKind::Call => &slice.calls,
+        OptionKind::Put => &slice.puts,
+    };
     if contracts.is_empty() {
         return "none".to_string();
     }
 
+    let now = Utc::now();
     let mut lines = Vec::new();
     for c in contracts {
+        let greeks = slice
+            .greeks(c, kind, risk_free_rate, now)
+            .map(|g| {
+                format!(
+                    " | Δ {:>5.2} Γ {:.3} Θ {:>6.2} V {:.2}",
+                    g.delta, g.gamma, g.theta, g.vega
+                )
+            })
+            .unwrap_or_default();
         lines.push(format!(
-            "K {:>7.2} | LTP {:>6.2} | B/A {:>6.2}/{:>6.2} | IV {:>5.1}% | OI {:>7} | Vol {:>7}{}",
+            "K {:>7.2} | LTP {:>6.2} | B/A {:>6.2}/{:>6.2} | IV {:>5.1}%{} | OI {:>7} | Vol {:>7}{}",
             c.strike,
             c.last_price,
             c.bid,
             c.ask,
             c.implied_volatility * 100.0,
+            greeks,
             c.open_interest.unwrap_or(0),
             c.volume.unwrap_or(0),
             if c.in_the_money { " | ITM" } else { "" },
@@ -225,33 +368,7 @@ async fn build_chart_bytes(
         }
     });
 
-    let body = serde_json::json!({
-        "chart": chart,
-        "width": 800,
-        "height": 400,
-        "backgroundColor": "white",
-        "plugins": ["chartjs-adapter-date-fns"],
-        "version": "4.4.0"
-    });
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .post("https://quickchart.io/chart")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| format!("chart request failed: {e}"))?;
-
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        return Err(format!("chart service status {status}: {text}"));
-    }
-
-    resp.bytes()
-        .await
-        .map(|b| b.to_vec())
-        .map_err(|e| format!("chart bytes error: {e}"))
+    quickchart::render(chart, 800, 400).await
 }
 
 async fn should_run_now() -> bool {
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serenity::all::{
+    ChannelType, CreateForumPost, CreateMessage, ForumTagId, GuildChannel, Http, Message,
+    MessageId,
+};
+use serenity::model::prelude::ChannelId;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::service::chunks;
+
+/// How an automated report is labelled when its channel is a forum.
+#[derive(Debug, Clone)]
+pub struct ReportPost {
+    /// Forum post title, e.g. "Earnings — Week of 2025-03-03".
+    pub title: String,
+    /// Tag names applied when the forum defines them (matched case-insensitively).
+    pub tags: Vec<&'static str>,
+}
+
+impl ReportPost {
+    pub fn new(title: impl Into<String>, tags: &[&'static str]) -> Self {
+        Self {
+            title: title.into(),
+            tags: tags.to_vec(),
+        }
+    }
+}
+
+/// How long a resolved channel is reused before Discord is asked again, so renamed forum tags
+/// or a channel switched to a forum are picked up.
+const CHANNEL_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// When a channel was resolved, and the forum channel or `None` for a text channel.
+type Resolved = (Instant, Option<GuildChannel>);
+
+/// Report channels resolved recently. A run posting many reports to one channel looks it up
+/// once.
+static CHANNELS: Lazy<Mutex<HashMap<ChannelId, Resolved>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+async fn forum_channel(http: &Http, channel_id: ChannelId) -> Option<GuildChannel> {
+    if let Some((resolved_at, forum)) = CHANNELS.lock().await.get(&channel_id) {
+        if resolved_at.elapsed() < CHANNEL_TTL {
+            return forum.clone();
+        }
+    }
+
+    match channel_id.to_channel(http).await {
+        Ok(channel) => {
+            let forum = channel.guild().filter(|c| c.kind == ChannelType::Forum);
+            CHANNELS
+                .lock()
+                .await
+                .insert(channel_id, (Instant::now(), forum.clone()));
+            forum
+        }
+        // Not cached, so the next post retries the lookup.
+        Err(e) => {
+            warn!("failed to resolve channel {channel_id}: {e}");
+            None
+        }
+    }
+}
+
+fn matching_tags(forum: &GuildChannel, names: &[&str]) -> Vec<ForumTagId> {
+    forum
+        .available_tags
+        .iter()
+        .filter(|tag| names.iter().any(|n| tag.name.eq_ignore_ascii_case(n)))
+        .map(|tag| tag.id)
+        .collect()
+}
+
+/// Send an automated report. Text channels get a plain message; forum channels get a new post
+/// titled and tagged from `post`, and the returned message is the post's starter message.
+pub async fn send_report(
+    http: &Http,
+    channel_id: ChannelId,
+    post: &ReportPost,
+    message: CreateMessage,
+) -> serenity::Result<Message> {
+    let Some(forum) = forum_channel(http, channel_id).await else {
+        return channel_id.send_message(http, message).await;
+    };
+
+    let builder = CreateForumPost::new(post.title.clone(), message)
+        .set_applied_tags(matching_tags(&forum, &post.tags));
+    let thread = channel_id.create_forum_post(http, builder).await?;
+    // A forum post's starter message shares the thread's id.
+    thread
+        .id
+        .message(http, MessageId::new(thread.id.get()))
+        .await
+}
+
+/// Send `content` as a report like [`send_report`], split on line boundaries into as many
+/// messages as Discord's length limit needs. `first` carries anything else for the opening
+/// message (files, components); the rest follow in the same channel, or in the forum post.
+/// Returns the opening message.
+pub async fn send_chunked(
+    http: &Http,
+    channel_id: ChannelId,
+    post: &ReportPost,
+    content: &str,
+    first: CreateMessage,
+) -> serenity::Result<Message> {
+    let mut parts = chunks::split(content, chunks::MESSAGE_LIMIT).into_iter();
+    let first = match parts.next() {
+        Some(part) => first.content(part),
+        None => first,
+    };
+    let opening = send_report(http, channel_id, post, first).await?;
+    for part in parts {
+        opening
+            .channel_id
+            .send_message(http, CreateMessage::new().content(part))
+            .await?;
+    }
+    Ok(opening)
+}
+
+/// Send an image report like [`send_report`], then `summary` as spoilered follow-ups in the
+/// same channel or forum post: a text version for screen readers and data-saver clients that
+/// stays collapsed for everyone else. The report counts as sent once the image is up; a failed
+/// follow-up is only logged.
+pub async fn send_with_summary(
+    http: &Http,
+    channel_id: ChannelId,
+    post: &ReportPost,
+    message: CreateMessage,
+    summary: &str,
+) -> serenity::Result<Message> {
+    let opening = send_report(http, channel_id, post, message).await?;
+    for part in chunks::spoilers(summary, chunks::MESSAGE_LIMIT) {
+        if let Err(e) = opening
+            .channel_id
+            .send_message(http, CreateMessage::new().content(part))
+            .await
+        {
+            warn!("failed to post text summary in {}: {e}", opening.channel_id);
+            break;
+        }
+    }
+    Ok(opening)
+}
@@ -0,0 +1,100 @@
+//! Market clock in the bot's Discord presence: `presence.symbol`'s price and day change while
+//! US markets are open, "Market closed" otherwise, refreshed every `presence.interval_minutes`.
+//!
+//! Presence belongs to each gateway connection, so every process sets it on the shards it
+//! runs; there is nothing to coordinate through the automation lease.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serenity::all::{ActivityData, ShardManager};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::PriceQuote;
+use crate::service::config::ConfigHandle;
+use crate::service::finance::exchange;
+use crate::service::finance::FinanceService;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+
+pub const MARKET_CLOSED: &str = "Market closed";
+
+/// Spawn the presence updater. It checks every minute and refreshes once
+/// `presence.interval_minutes` have passed, or right away when the market opens or closes.
+pub fn spawn_market_presence(
+    shards: Arc<ShardManager>,
+    finance: Arc<FinanceService>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting market presence");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let mut last: Option<(DateTime<Utc>, bool)> = None;
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Market presence stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            let config = config.current();
+            let presence = &config.presence;
+            if !presence.enabled {
+                continue;
+            }
+            let now = Utc::now();
+            let open = exchange::US.is_open(now);
+            // A few seconds of slack so minute ticks don't slip a whole interval.
+            let every = chrono::Duration::minutes(presence.interval_minutes as i64)
+                - chrono::Duration::seconds(5);
+            if last.is_some_and(|(at, was_open)| was_open == open && now - at < every) {
+                continue;
+            }
+
+            let text = if open {
+                match finance.get_price(&presence.symbol).await {
+                    Ok(quote) => status_text(&presence.symbol, Some(&quote), true),
+                    Err(e) => {
+                        METRICS.record_automation_failure("market_presence");
+                        warn!("market presence: no quote for {}: {e}", presence.symbol);
+                        continue;
+                    }
+                }
+            } else {
+                status_text(&presence.symbol, None, false)
+            };
+            last = Some((now, open));
+
+            let runners = shards.runners.lock().await;
+            for runner in runners.values() {
+                runner
+                    .runner_tx
+                    .set_activity(Some(ActivityData::custom(text.clone())));
+            }
+            METRICS.record_automation_success("market_presence");
+        }
+    })
+}
+
+/// `SPY 581.23 (+0.45%)` while open, [`MARKET_CLOSED`] otherwise. A quote without a price
+/// shows the symbol alone.
+pub fn status_text(symbol: &str, quote: Option<&PriceQuote>, open: bool) -> String {
+    if !open {
+        return MARKET_CLOSED.to_string();
+    }
+    let price = quote
+        .and_then(|q| q.price)
+        .map(|p| format!(" {p:.2}"))
+        .unwrap_or_default();
+    let change = quote
+        .and_then(|q| q.percent_change)
+        .map(|c| format!(" ({c:+.2}%)"))
+        .unwrap_or_default();
+    format!("{symbol}{price}{change}")
+}
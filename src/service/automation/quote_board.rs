@@ -0,0 +1,204 @@
+//! Live quote board: one message per `quote_board.channels` entry listing
+//! `quote_board.symbols`, edited in place every `quote_board.interval_minutes` during market
+//! hours instead of posting new messages.
+//!
+//! Board message ids are kept in Redis so a restart or another process keeps editing the same
+//! messages; without Redis they live in memory and a restart posts fresh boards. A board whose
+//! message was deleted is posted again.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use futures_util::future::join_all;
+use once_cell::sync::Lazy;
+use serenity::all::{ChannelId, CreateMessage, EditMessage, Http, HttpError, MessageId};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::models::PriceQuote;
+use crate::service::automation::leader;
+use crate::service::caching::collections::quote_board as cache_board;
+use crate::service::caching::RedisCache;
+use crate::service::config::ConfigHandle;
+use crate::service::finance::FinanceService;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+use crate::service::timefmt::{self, TimestampStyle};
+
+/// Board message per channel when Redis is not configured.
+static BOARD_MESSAGES: Lazy<Mutex<HashMap<u64, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Spawn the quote board updater. It checks every minute and edits the boards once
+/// `quote_board.interval_minutes` have passed since the last edit.
+pub fn spawn_quote_board(
+    http: Arc<Http>,
+    finance: Arc<FinanceService>,
+    cache: Option<Arc<RedisCache>>,
+    config: ConfigHandle,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    info!("Starting quote board");
+
+    let stop = shutdown.clone();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let mut last_update: Option<DateTime<Utc>> = None;
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    info!("Quote board stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            let config = config.current();
+            let board = &config.quote_board;
+            if board.channels.is_empty() || !leader::is_leader() {
+                continue;
+            }
+            let now = Utc::now();
+            if !in_session(now.with_timezone(&New_York)) {
+                continue;
+            }
+            // A few seconds of slack so minute ticks don't slip a whole interval.
+            let every = chrono::Duration::minutes(board.interval_minutes as i64)
+                - chrono::Duration::seconds(5);
+            if last_update.is_some_and(|at| now - at < every) {
+                continue;
+            }
+            last_update = Some(now);
+
+            let content = render(&fetch_quotes(&finance, &board.symbols).await, now);
+            for &channel_id in &board.channels {
+                match update_board(&http, cache.as_deref(), channel_id, &content).await {
+                    Ok(()) => METRICS.record_automation_success("quote_board"),
+                    Err(e) => {
+                        METRICS.record_automation_failure("quote_board");
+                        warn!("quote board update failed in channel {channel_id}: {e}");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Weekdays from the 9:30 ET open until 16:05, so the last edit shows closing prices.
+pub fn in_session(now_et: DateTime<Tz>) -> bool {
+    if matches!(now_et.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    let time = now_et.time();
+    let open = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+    let close = NaiveTime::from_hms_opt(16, 5, 0).unwrap();
+    (open..close).contains(&time)
+}
+
+/// Quotes in board order; `None` where a symbol failed to load.
+async fn fetch_quotes(
+    finance: &FinanceService,
+    symbols: &[String],
+) -> Vec<(String, Option<PriceQuote>)> {
+    let quotes = join_all(symbols.iter().map(|s| finance.get_price(s))).await;
+    symbols
+        .iter()
+        .zip(quotes)
+        .map(|(symbol, quote)| {
+            let quote = quote
+                .inspect_err(|e| warn!("quote board: no quote for {symbol}: {e}"))
+                .ok();
+            (symbol.clone(), quote)
+        })
+        .collect()
+}
+
+/// The board text: a line per symbol with price and day change, and when it was updated.
+pub fn render(quotes: &[(String, Option<PriceQuote>)], updated: DateTime<Utc>) -> String {
+    let mut lines = vec![format!(
+        "📋 **Quote board** · updated {}",
+        timefmt::discord(&updated, TimestampStyle::Relative)
+    )];
+    for (symbol, quote) in quotes {
+        let line = match quote
+            .as_ref()
+            .and_then(|q| Some((q.price?, q.percent_change)))
+        {
+            Some((price, change)) => {
+                let marker = match change {
+                    Some(c) if c > 0.0 => "🟢",
+                    Some(c) if c < 0.0 => "🔴",
+                    _ => "⚪",
+                };
+                let change = change.map(|c| format!(" ({c:+.2}%)")).unwrap_or_default();
+                format!("{marker} **{symbol}** {price:.2}{change}")
+            }
+            None => format!("⚪ **{symbol}** n/a"),
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Edit the channel's board, or post one when there is none yet or it was deleted.
+async fn update_board(
+    http: &Http,
+    cache: Option<&RedisCache>,
+    channel_id: u64,
+    content: &str,
+) -> Result<(), String> {
+    let channel = ChannelId::new(channel_id);
+    if let Some(message_id) = load_message(cache, channel_id).await? {
+        let edit = EditMessage::new().content(content);
+        match channel
+            .edit_message(http, MessageId::new(message_id), edit)
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(e) if is_missing(&e) => {
+                info!("Quote board message in channel {channel_id} is gone; posting a new one");
+            }
+            Err(e) => return Err(format!("edit error: {e}")),
+        }
+    }
+
+    let message = channel
+        .send_message(http, CreateMessage::new().content(content))
+        .await
+        .map_err(|e| format!("send error: {e}"))?;
+    save_message(cache, channel_id, message.id.get()).await;
+    info!("Posted quote board in channel {channel_id}");
+    Ok(())
+}
+
+/// The message or its channel no longer exists.
+fn is_missing(err: &serenity::Error) -> bool {
+    matches!(
+        err,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 404
+    )
+}
+
+/// An error skips the update: posting a second board would leave the first one stale.
+async fn load_message(cache: Option<&RedisCache>, channel_id: u64) -> Result<Option<u64>, String> {
+    let Some(cache) = cache else {
+        return Ok(BOARD_MESSAGES.lock().await.get(&channel_id).copied());
+    };
+    cache_board::load_message(cache, channel_id)
+        .await
+        .map_err(|e| format!("board lookup error: {e}"))
+}
+
+async fn save_message(cache: Option<&RedisCache>, channel_id: u64, message_id: u64) {
+    let Some(cache) = cache else {
+        BOARD_MESSAGES.lock().await.insert(channel_id, message_id);
+        return;
+    };
+    if let Err(e) = cache_board::save_message(cache, channel_id, message_id).await {
+        warn!("failed to save quote board message for channel {channel_id}: {e}");
+    }
+}
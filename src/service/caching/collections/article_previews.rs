@@ -0,0 +1,48 @@
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::finance::article_preview::ArticlePreview;
+
+/// Articles rarely change their preview once published; a day covers a story's news cycle.
+const PREVIEW_TTL_SECS: u64 = 24 * 60 * 60;
+
+fn preview_key(canonical_link: &str) -> String {
+    format!("news:preview:{canonical_link}")
+}
+
+#[derive(Debug, Error)]
+pub enum ArticlePreviewCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Save an article's preview for a day, keyed by its canonical link.
+pub async fn save_preview(
+    cache: &RedisCache,
+    canonical_link: &str,
+    preview: &ArticlePreview,
+) -> Result<(), ArticlePreviewCacheError> {
+    let mut conn = cache.connection();
+    conn.set_ex::<_, _, ()>(
+        preview_key(canonical_link),
+        serde_json::to_string(preview)?,
+        PREVIEW_TTL_SECS,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The cached preview of an article, or `None` once it has expired.
+pub async fn load_preview(
+    cache: &RedisCache,
+    canonical_link: &str,
+) -> Result<Option<ArticlePreview>, ArticlePreviewCacheError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(preview_key(canonical_link)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
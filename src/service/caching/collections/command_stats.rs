@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Daily buckets are kept a little longer than the longest summary window (7 days).
+const STATS_TTL_SECS: i64 = 8 * 24 * 60 * 60;
+
+/// Per-day command counts, error counts, latency sums and ticker scores.
+type DayBuckets = (
+    HashMap<String, u64>,
+    HashMap<String, u64>,
+    HashMap<String, u64>,
+    Vec<(String, f64)>,
+);
+
+/// Buckets are per guild so each server's `/stats` only counts its own usage; commands run in
+/// DMs or as user-installed apps share the `dm` scope.
+pub fn day_key(guild_id: Option<u64>, day: NaiveDate, kind: &str) -> String {
+    let scope = guild_id.map_or_else(|| "dm".to_string(), |id| id.to_string());
+    format!("stats:{scope}:{}:{kind}", day.format("%Y-%m-%d"))
+}
+
+#[derive(Debug, Error)]
+pub enum CommandStatsError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// A single handled command invocation.
+#[derive(Debug, Clone)]
+pub struct CommandUse<'a> {
+    pub guild_id: Option<u64>,
+    pub day: NaiveDate,
+    pub command: &'a str,
+    pub latency_ms: u64,
+    pub ok: bool,
+    pub ticker: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandUsage {
+    pub command: String,
+    pub count: u64,
+    pub errors: u64,
+    pub total_latency_ms: u64,
+}
+
+impl CommandUsage {
+    pub fn error_rate(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.count as f64
+        }
+    }
+
+    pub fn avg_latency_ms(&self) -> u64 {
+        self.total_latency_ms.checked_div(self.count).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsSummary {
+    /// Sorted by count, busiest first.
+    pub commands: Vec<CommandUsage>,
+    /// Sorted by count, most requested first.
+    pub tickers: Vec<(String, u64)>,
+}
+
+pub async fn record_command(
+    cache: &RedisCache,
+    usage: &CommandUse<'_>,
+) -> Result<(), CommandStatsError> {
+    let mut conn = cache.connection();
+    let counts = day_key(usage.guild_id, usage.day, "commands");
+    let errors = day_key(usage.guild_id, usage.day, "errors");
+    let latency = day_key(usage.guild_id, usage.day, "latency_ms");
+
+    let mut pipe = redis::pipe();
+    pipe.hincr(&counts, usage.command, 1)
+        .expire(&counts, STATS_TTL_SECS)
+        .hincr(&latency, usage.command, usage.latency_ms)
+        .expire(&latency, STATS_TTL_SECS);
+    if !usage.ok {
+        pipe.hincr(&errors, usage.command, 1)
+            .expire(&errors, STATS_TTL_SECS);
+    }
+    if let Some(ticker) = usage.ticker {
+        let tickers = day_key(usage.guild_id, usage.day, "tickers");
+        pipe.zincr(&tickers, ticker.to_uppercase(), 1)
+            .expire(&tickers, STATS_TTL_SECS);
+    }
+
+    let _: () = pipe.query_async(&mut conn).await?;
+    Ok(())
+}
+
+/// Aggregate a guild's `days` daily buckets ending at `today` (inclusive).
+pub async fn load_summary(
+    cache: &RedisCache,
+    guild_id: u64,
+    today: NaiveDate,
+    days: i64,
+) -> Result<StatsSummary, CommandStatsError> {
+    let mut conn = cache.connection();
+    let mut commands: HashMap<String, CommandUsage> = HashMap::new();
+    let mut tickers: HashMap<String, u64> = HashMap::new();
+
+    for offset in 0..days.max(1) {
+        let day = today - Duration::days(offset);
+        let (counts, errors, latency, day_tickers): DayBuckets = redis::pipe()
+            .hgetall(day_key(Some(guild_id), day, "commands"))
+            .hgetall(day_key(Some(guild_id), day, "errors"))
+            .hgetall(day_key(Some(guild_id), day, "latency_ms"))
+            .zrange_withscores(day_key(Some(guild_id), day, "tickers"), 0, -1)
+            .query_async(&mut conn)
+            .await?;
+
+        for (name, count) in counts {
+            let entry = commands
+                .entry(name.clone())
+                .or_insert_with(|| CommandUsage {
+                    command: name.clone(),
+                    ..Default::default()
+                });
+            entry.count += count;
+            entry.errors += errors.get(&name).copied().unwrap_or(0);
+            entry.total_latency_ms += latency.get(&name).copied().unwrap_or(0);
+        }
+        for (ticker, score) in day_tickers {
+            *tickers.entry(ticker).or_default() += score as u64;
+        }
+    }
+
+    let mut commands: Vec<CommandUsage> = commands.into_values().collect();
+    commands.sort_by(|a, b| b.count.cmp(&a.count).then(a.command.cmp(&b.command)));
+    let mut tickers: Vec<(String, u64)> = tickers.into_iter().collect();
+    tickers.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    Ok(StatsSummary { commands, tickers })
+}
@@ -0,0 +1,50 @@
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::finance::corporate_actions::CorporateActions;
+
+/// The state each run compares against. It only matters until the next run, but a symbol
+/// dropped from the watchlist and added back months later should start from a fresh baseline,
+/// so it expires after a quarter.
+const STATE_TTL_SECS: u64 = 90 * 24 * 60 * 60;
+
+fn state_key(symbol: &str) -> String {
+    format!("corporate_actions:{}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum CorporateActionsCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Store the state seen for `symbol` on this run.
+pub async fn save_state(
+    cache: &RedisCache,
+    symbol: &str,
+    state: &CorporateActions,
+) -> Result<(), CorporateActionsCacheError> {
+    let mut conn = cache.connection();
+    conn.set_ex::<_, _, ()>(
+        state_key(symbol),
+        serde_json::to_string(state)?,
+        STATE_TTL_SECS,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The state stored on the previous run, or `None` for a symbol not seen before.
+pub async fn load_state(
+    cache: &RedisCache,
+    symbol: &str,
+) -> Result<Option<CorporateActions>, CorporateActionsCacheError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(state_key(symbol)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
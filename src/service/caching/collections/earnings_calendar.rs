@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::models::EarningsEvent;
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Calendars change through the day as companies confirm dates, so keep them briefly.
+const CALENDAR_TTL_SECS: u64 = 15 * 60;
+
+fn day_key(day: NaiveDate) -> String {
+    format!("earnings:calendar:{}", day.format("%Y-%m-%d"))
+}
+
+fn days(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (0..=(to - from).num_days()).map(move |offset| from + Duration::days(offset))
+}
+
+#[derive(Debug, Error)]
+pub enum EarningsCalendarCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Cache a fetched range one day per key, so a later request for any part of it is served
+/// from here. Days without reports are stored as empty lists; events dated outside the range
+/// are left out since their day was not fetched in full.
+pub async fn save_range(
+    cache: &RedisCache,
+    from: NaiveDate,
+    to: NaiveDate,
+    events: &[EarningsEvent],
+) -> Result<(), EarningsCalendarCacheError> {
+    let mut by_day: BTreeMap<NaiveDate, Vec<&EarningsEvent>> =
+        days(from, to).map(|day| (day, Vec::new())).collect();
+    for event in events {
+        if let Some(list) = by_day.get_mut(&event.date.date_naive()) {
+            list.push(event);
+        }
+    }
+
+    let mut pipe = redis::pipe();
+    for (day, list) in &by_day {
+        pipe.set_ex(
+            day_key(*day),
+            serde_json::to_string(list)?,
+            CALENDAR_TTL_SECS,
+        )
+        .ignore();
+    }
+    let mut conn = cache.connection();
+    pipe.query_async::<()>(&mut conn).await?;
+    Ok(())
+}
+
+/// Events from `from` through `to`, or `None` unless every day in the range is cached.
+pub async fn load_range(
+    cache: &RedisCache,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Option<Vec<EarningsEvent>>, EarningsCalendarCacheError> {
+    let keys: Vec<String> = days(from, to).map(day_key).collect();
+    if keys.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+    let mut conn = cache.connection();
+    // An explicit MGET: the typed helper sends GET for a single key, which decodes differently.
+    let stored: Vec<Option<String>> = redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+
+    let mut events = Vec::new();
+    for json in stored {
+        let Some(json) = json else {
+            return Ok(None);
+        };
+        events.extend(serde_json::from_str::<Vec<EarningsEvent>>(&json)?);
+    }
+    Ok(Some(events))
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::models::EarningsEstimate;
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Consensus figures move at most daily; keys carry the day, so this only bounds their lifetime.
+const ESTIMATE_TTL_SECS: u64 = 24 * 60 * 60;
+
+fn estimate_key(symbol: &str, day: NaiveDate) -> String {
+    format!(
+        "earnings:estimate:{}:{}",
+        symbol.to_uppercase(),
+        day.format("%Y-%m-%d")
+    )
+}
+
+#[derive(Debug, Error)]
+pub enum EarningsEstimateCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Save estimates looked up on `day`.
+pub async fn save_estimates(
+    cache: &RedisCache,
+    day: NaiveDate,
+    estimates: &HashMap<String, EarningsEstimate>,
+) -> Result<(), EarningsEstimateCacheError> {
+    if estimates.is_empty() {
+        return Ok(());
+    }
+    let mut pipe = redis::pipe();
+    for (symbol, estimate) in estimates {
+        pipe.set_ex(
+            estimate_key(symbol, day),
+            serde_json::to_string(estimate)?,
+            ESTIMATE_TTL_SECS,
+        )
+        .ignore();
+    }
+    let mut conn = cache.connection();
+    pipe.query_async::<()>(&mut conn).await?;
+    Ok(())
+}
+
+/// The estimates of `symbols` saved on `day`, keyed by symbol as given; symbols without one
+/// are left out.
+pub async fn load_estimates(
+    cache: &RedisCache,
+    day: NaiveDate,
+    symbols: &[String],
+) -> Result<HashMap<String, EarningsEstimate>, EarningsEstimateCacheError> {
+    if symbols.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let keys: Vec<String> = symbols.iter().map(|s| estimate_key(s, day)).collect();
+    let mut conn = cache.connection();
+    // An explicit MGET: the typed helper sends GET for a single key, which decodes differently.
+    let stored: Vec<Option<String>> = redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+    let mut estimates = HashMap::new();
+    for (symbol, json) in symbols.iter().zip(stored) {
+        if let Some(json) = json {
+            estimates.insert(symbol.clone(), serde_json::from_str(&json)?);
+        }
+    }
+    Ok(estimates)
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Market cap and volume tiers move slowly; a week keeps a season's lookups to one per symbol.
+const SCORE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+fn score_key(symbol: &str) -> String {
+    format!("earnings:importance:{}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum EarningsImportanceCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Save importance scores computed for symbols the calendar API sent without one.
+pub async fn save_scores(
+    cache: &RedisCache,
+    scores: &HashMap<String, i64>,
+) -> Result<(), EarningsImportanceCacheError> {
+    if scores.is_empty() {
+        return Ok(());
+    }
+    let mut pipe = redis::pipe();
+    for (symbol, score) in scores {
+        pipe.set_ex(score_key(symbol), *score, SCORE_TTL_SECS)
+            .ignore();
+    }
+    let mut conn = cache.connection();
+    pipe.query_async::<()>(&mut conn).await?;
+    Ok(())
+}
+
+/// The cached scores of `symbols`, keyed by symbol as given; symbols without one are left out.
+pub async fn load_scores(
+    cache: &RedisCache,
+    symbols: &[String],
+) -> Result<HashMap<String, i64>, EarningsImportanceCacheError> {
+    if symbols.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let keys: Vec<String> = symbols.iter().map(|s| score_key(s)).collect();
+    let mut conn = cache.connection();
+    // An explicit MGET: the typed helper sends GET for a single key, which decodes differently.
+    let stored: Vec<Option<i64>> = redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+    Ok(symbols
+        .iter()
+        .zip(stored)
+        .filter_map(|(symbol, score)| Some((symbol.clone(), score?)))
+        .collect())
+}
@@ -0,0 +1,66 @@
+use chrono::{Duration, NaiveDate};
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::models::EarningsRecord;
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Records feed the Friday summary, so two weeks covers it with room for a late `/admin run`.
+const RECORD_TTL_SECS: i64 = 15 * 24 * 60 * 60;
+
+fn records_key(day: NaiveDate) -> String {
+    format!("earnings:records:{}", day.format("%Y-%m-%d"))
+}
+
+#[derive(Debug, Error)]
+pub enum EarningsRecordStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Merge `record` into what is stored for its day and symbol.
+pub async fn save_record(
+    cache: &RedisCache,
+    record: &EarningsRecord,
+) -> Result<(), EarningsRecordStoreError> {
+    let mut conn = cache.connection();
+    let key = records_key(record.day);
+    let stored: Option<String> = conn.hget(&key, &record.symbol).await?;
+    let merged = match stored {
+        Some(json) => {
+            let mut existing: EarningsRecord = serde_json::from_str(&json)?;
+            existing.merge(record);
+            existing
+        }
+        None => record.clone(),
+    };
+    let _: () = redis::pipe()
+        .hset(&key, &record.symbol, serde_json::to_string(&merged)?)
+        .expire(&key, RECORD_TTL_SECS)
+        .query_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+/// Every record from `from` through `to`, by day.
+pub async fn load_range(
+    cache: &RedisCache,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<EarningsRecord>, EarningsRecordStoreError> {
+    let mut conn = cache.connection();
+    let mut out = Vec::new();
+    let mut day = from;
+    while day <= to {
+        let stored: Vec<(String, String)> = conn.hgetall(records_key(day)).await?;
+        for (_, json) in stored {
+            out.push(serde_json::from_str(&json)?);
+        }
+        day += Duration::days(1);
+    }
+    Ok(out)
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Threads are only posted into during the week they were created for.
+const THREAD_TTL_SECS: u64 = 8 * 24 * 60 * 60;
+
+fn thread_key(day: NaiveDate) -> String {
+    format!("earnings:threads:{}", day.format("%Y-%m-%d"))
+}
+
+#[derive(Debug, Error)]
+pub enum EarningsThreadStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Remember the thread created for a trading day's earnings.
+pub async fn save_thread(
+    cache: &RedisCache,
+    day: NaiveDate,
+    thread_id: u64,
+) -> Result<(), EarningsThreadStoreError> {
+    let mut conn = cache.connection();
+    conn.set_ex::<_, _, ()>(thread_key(day), thread_id, THREAD_TTL_SECS)
+        .await?;
+    Ok(())
+}
+
+pub async fn load_thread(
+    cache: &RedisCache,
+    day: NaiveDate,
+) -> Result<Option<u64>, EarningsThreadStoreError> {
+    let mut conn = cache.connection();
+    let thread_id: Option<u64> = conn.get(thread_key(day)).await?;
+    Ok(thread_id)
+}
@@ -0,0 +1,48 @@
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::models::EtfProfile;
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Funds publish holdings monthly or quarterly; a day keeps `/etf` and `/held-by` from
+/// refetching the whole universe on every lookup.
+const PROFILE_TTL_SECS: u64 = 24 * 60 * 60;
+
+fn profile_key(symbol: &str) -> String {
+    format!("etf:{}:profile", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum EtfHoldingsCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Save a fund's profile for a day.
+pub async fn save_profile(
+    cache: &RedisCache,
+    profile: &EtfProfile,
+) -> Result<(), EtfHoldingsCacheError> {
+    let mut conn = cache.connection();
+    conn.set_ex::<_, _, ()>(
+        profile_key(&profile.symbol),
+        serde_json::to_string(profile)?,
+        PROFILE_TTL_SECS,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The cached profile of `symbol`, or `None` once it has expired.
+pub async fn load_profile(
+    cache: &RedisCache,
+    symbol: &str,
+) -> Result<Option<EtfProfile>, EtfHoldingsCacheError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(profile_key(symbol)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
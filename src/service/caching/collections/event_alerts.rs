@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use redis::{AsyncCommands, RedisError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::finance::fundamental_events::{EventKind, EventSnapshot};
+
+const SYMBOL_SET_KEY: &str = "event_alerts:symbols";
+/// Hash of the snapshot each alert last saw, a field per alert id. It is kept apart from the
+/// definitions, which live in the state store.
+const SNAPSHOTS_KEY: &str = "event_alerts:seen";
+
+fn alerts_key(symbol: &str) -> String {
+    format!("event_alerts:{symbol}")
+}
+
+/// A standing alert on a symbol's earnings date or analyst target, posted into the channel
+/// it was set in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventAlert {
+    pub id: String,
+    pub symbol: String,
+    pub kind: EventKind,
+    /// Fractional move that fires a target alert; unused for earnings dates.
+    pub threshold: f64,
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub user_id: u64,
+    pub created_at: DateTime<Utc>,
+    /// The snapshot the next check compares against. Not part of the stored definition; it
+    /// is kept in the cache, see [`load_snapshots`].
+    #[serde(skip)]
+    pub seen: EventSnapshot,
+}
+
+#[derive(Debug, Error)]
+pub enum EventAlertStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Every alert definition, by symbol.
+pub async fn load_all(
+    cache: &RedisCache,
+) -> Result<HashMap<String, Vec<EventAlert>>, EventAlertStoreError> {
+    let mut conn = cache.connection();
+    let symbols: Vec<String> = conn.smembers(SYMBOL_SET_KEY).await?;
+    let mut out = HashMap::new();
+    for symbol in symbols {
+        let stored: Option<String> = conn.get(alerts_key(&symbol)).await?;
+        match stored {
+            Some(json) => {
+                let alerts: Vec<EventAlert> = serde_json::from_str(&json)?;
+                if !alerts.is_empty() {
+                    out.insert(symbol, alerts);
+                }
+            }
+            None => {
+                let _: () = conn.srem(SYMBOL_SET_KEY, &symbol).await?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Replace the alerts on `symbol`; an empty list removes the symbol.
+pub async fn save_symbol_alerts(
+    cache: &RedisCache,
+    symbol: &str,
+    alerts: &[EventAlert],
+) -> Result<(), EventAlertStoreError> {
+    let mut conn = cache.connection();
+    if alerts.is_empty() {
+        let _: () = redis::pipe()
+            .del(alerts_key(symbol))
+            .srem(SYMBOL_SET_KEY, symbol)
+            .query_async(&mut conn)
+            .await?;
+        return Ok(());
+    }
+    let _: () = redis::pipe()
+        .set(alerts_key(symbol), serde_json::to_string(alerts)?)
+        .sadd(SYMBOL_SET_KEY, symbol)
+        .query_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+/// The snapshot each alert last saw, by alert id.
+pub async fn load_snapshots(
+    cache: &RedisCache,
+) -> Result<HashMap<String, EventSnapshot>, EventAlertStoreError> {
+    let mut conn = cache.connection();
+    let fields: Vec<(String, String)> = conn.hgetall(SNAPSHOTS_KEY).await?;
+    Ok(fields
+        .into_iter()
+        .map(|(id, json)| Ok((id, serde_json::from_str(&json)?)))
+        .collect::<Result<_, serde_json::Error>>()?)
+}
+
+/// Store one alert's snapshot, e.g. the baseline of a new alert.
+pub async fn save_snapshot(
+    cache: &RedisCache,
+    id: &str,
+    snapshot: &EventSnapshot,
+) -> Result<(), EventAlertStoreError> {
+    let mut conn = cache.connection();
+    conn.hset::<_, _, _, ()>(SNAPSHOTS_KEY, id, serde_json::to_string(snapshot)?)
+        .await?;
+    Ok(())
+}
+
+/// Replace every snapshot, dropping those of alerts that were removed.
+pub async fn replace_snapshots(
+    cache: &RedisCache,
+    snapshots: &HashMap<String, EventSnapshot>,
+) -> Result<(), EventAlertStoreError> {
+    let fields = snapshots
+        .iter()
+        .map(|(id, snapshot)| Ok((id.as_str(), serde_json::to_string(snapshot)?)))
+        .collect::<Result<Vec<_>, serde_json::Error>>()?;
+    let mut conn = cache.connection();
+    let mut pipe = redis::pipe();
+    pipe.atomic().del(SNAPSHOTS_KEY);
+    if !fields.is_empty() {
+        pipe.hset_multiple(SNAPSHOTS_KEY, &fields);
+    }
+    let _: () = pipe.query_async(&mut conn).await?;
+    Ok(())
+}
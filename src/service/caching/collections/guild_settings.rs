@@ -0,0 +1,345 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::symbol_filter::SymbolFilter;
+
+fn command_roles_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:command_roles")
+}
+
+#[derive(Debug, Error)]
+pub enum GuildSettingsError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Roles allowed to run `command` in a guild. Empty means everyone may run it.
+pub async fn load_command_roles(
+    cache: &RedisCache,
+    guild_id: u64,
+    command: &str,
+) -> Result<Vec<u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.hget(command_roles_key(guild_id), command).await?;
+    match raw {
+        Some(raw) => Ok(serde_json::from_str(&raw)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Every restricted command in a guild with its allowed roles.
+pub async fn load_all_command_roles(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<BTreeMap<String, Vec<u64>>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let raw: BTreeMap<String, String> = conn.hgetall(command_roles_key(guild_id)).await?;
+    let mut out = BTreeMap::new();
+    for (command, roles) in raw {
+        out.insert(command, serde_json::from_str(&roles)?);
+    }
+    Ok(out)
+}
+
+/// Replace the allowed roles for `command`; an empty list lifts the restriction.
+pub async fn set_command_roles(
+    cache: &RedisCache,
+    guild_id: u64,
+    command: &str,
+    roles: &[u64],
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    let key = command_roles_key(guild_id);
+    if roles.is_empty() {
+        conn.hdel::<_, _, ()>(key, command).await?;
+    } else {
+        conn.hset::<_, _, _, ()>(key, command, serde_json::to_string(roles)?)
+            .await?;
+    }
+    Ok(())
+}
+
+fn audit_channel_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:audit_channel")
+}
+
+/// Channel the guild's audit log posts to, if one is set.
+pub async fn load_audit_channel(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<Option<u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let channel: Option<u64> = conn.get(audit_channel_key(guild_id)).await?;
+    Ok(channel)
+}
+
+pub async fn set_audit_channel(
+    cache: &RedisCache,
+    guild_id: u64,
+    channel_id: Option<u64>,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    let key = audit_channel_key(guild_id);
+    match channel_id {
+        Some(id) => conn.set::<_, _, ()>(key, id).await?,
+        None => conn.del::<_, ()>(key).await?,
+    }
+    Ok(())
+}
+
+/// One hash for every guild, so the topic updater can list them in a single read.
+const TOPIC_CHANNELS_KEY: &str = "guilds:topic_channels";
+
+/// Channel whose topic shows index levels (`/config topic-channel`), if one is set.
+pub async fn load_topic_channel(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<Option<u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let channel: Option<u64> = conn.hget(TOPIC_CHANNELS_KEY, guild_id).await?;
+    Ok(channel)
+}
+
+/// Topic channels of every guild that set one, by guild id.
+pub async fn load_topic_channels(
+    cache: &RedisCache,
+) -> Result<HashMap<u64, u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let channels: HashMap<u64, u64> = conn.hgetall(TOPIC_CHANNELS_KEY).await?;
+    Ok(channels)
+}
+
+pub async fn set_topic_channel(
+    cache: &RedisCache,
+    guild_id: u64,
+    channel_id: Option<u64>,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    match channel_id {
+        Some(id) => {
+            conn.hset::<_, _, _, ()>(TOPIC_CHANNELS_KEY, guild_id, id)
+                .await?
+        }
+        None => conn.hdel::<_, _, ()>(TOPIC_CHANNELS_KEY, guild_id).await?,
+    }
+    Ok(())
+}
+
+/// A guild's routes, by route name (`/config routes`).
+fn guild_routes_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:routes")
+}
+
+/// Every guild's channel for one route, by guild id, so posters list them in a single read.
+fn route_channels_key(route: &str) -> String {
+    format!("guilds:routes:{route}")
+}
+
+pub async fn load_guild_routes(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<BTreeMap<String, u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let routes: BTreeMap<String, u64> = conn.hgetall(guild_routes_key(guild_id)).await?;
+    Ok(routes)
+}
+
+pub async fn load_route_channels(
+    cache: &RedisCache,
+    route: &str,
+) -> Result<HashMap<u64, u64>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let channels: HashMap<u64, u64> = conn.hgetall(route_channels_key(route)).await?;
+    Ok(channels)
+}
+
+/// Write both views of the route together.
+pub async fn set_guild_route(
+    cache: &RedisCache,
+    guild_id: u64,
+    route: &str,
+    channel_id: Option<u64>,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    match channel_id {
+        Some(id) => pipe.hset(guild_routes_key(guild_id), route, id).hset(
+            route_channels_key(route),
+            guild_id,
+            id,
+        ),
+        None => pipe
+            .hdel(guild_routes_key(guild_id), route)
+            .hdel(route_channels_key(route), guild_id),
+    };
+    let _: () = pipe.query_async(&mut conn).await?;
+    Ok(())
+}
+
+fn locale_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:locale")
+}
+
+/// Language code the guild picked with `/config language`, if any.
+pub async fn load_locale(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<Option<String>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let locale: Option<String> = conn.get(locale_key(guild_id)).await?;
+    Ok(locale)
+}
+
+pub async fn set_locale(
+    cache: &RedisCache,
+    guild_id: u64,
+    locale: Option<&str>,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    let key = locale_key(guild_id);
+    match locale {
+        Some(code) => conn.set::<_, _, ()>(key, code).await?,
+        None => conn.del::<_, ()>(key).await?,
+    }
+    Ok(())
+}
+
+fn timezone_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:timezone")
+}
+
+fn user_timezone_key(user_id: u64) -> String {
+    format!("user:{user_id}:timezone")
+}
+
+/// IANA timezone the guild picked with `/config timezone`, if any.
+pub async fn load_timezone(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<Option<String>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let timezone: Option<String> = conn.get(timezone_key(guild_id)).await?;
+    Ok(timezone)
+}
+
+pub async fn set_timezone(
+    cache: &RedisCache,
+    guild_id: u64,
+    timezone: Option<&str>,
+) -> Result<(), GuildSettingsError> {
+    set_or_clear(cache, timezone_key(guild_id), timezone).await
+}
+
+/// IANA timezone a user picked with `/timezone`, if any. Applies in every guild and in DMs.
+pub async fn load_user_timezone(
+    cache: &RedisCache,
+    user_id: u64,
+) -> Result<Option<String>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let timezone: Option<String> = conn.get(user_timezone_key(user_id)).await?;
+    Ok(timezone)
+}
+
+pub async fn set_user_timezone(
+    cache: &RedisCache,
+    user_id: u64,
+    timezone: Option<&str>,
+) -> Result<(), GuildSettingsError> {
+    set_or_clear(cache, user_timezone_key(user_id), timezone).await
+}
+
+fn channel_ticker_key(channel_id: u64) -> String {
+    format!("channel:{channel_id}:ticker")
+}
+
+/// Symbol bound to a channel with `/bind-ticker`, if any.
+pub async fn load_channel_ticker(
+    cache: &RedisCache,
+    channel_id: u64,
+) -> Result<Option<String>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let ticker: Option<String> = conn.get(channel_ticker_key(channel_id)).await?;
+    Ok(ticker)
+}
+
+pub async fn set_channel_ticker(
+    cache: &RedisCache,
+    channel_id: u64,
+    ticker: Option<&str>,
+) -> Result<(), GuildSettingsError> {
+    set_or_clear(cache, channel_ticker_key(channel_id), ticker).await
+}
+
+async fn set_or_clear(
+    cache: &RedisCache,
+    key: String,
+    value: Option<&str>,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    match value {
+        Some(value) => conn.set::<_, _, ()>(key, value).await?,
+        None => conn.del::<_, ()>(key).await?,
+    }
+    Ok(())
+}
+
+fn symbol_filter_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:symbol_filter")
+}
+
+/// Symbol allow/deny lists the guild set with `/config symbols`, if any.
+pub async fn load_symbol_filter(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<Option<SymbolFilter>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(symbol_filter_key(guild_id)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
+
+pub async fn set_symbol_filter(
+    cache: &RedisCache,
+    guild_id: u64,
+    filter: Option<&SymbolFilter>,
+) -> Result<(), GuildSettingsError> {
+    let raw = filter.map(serde_json::to_string).transpose()?;
+    set_or_clear(cache, symbol_filter_key(guild_id), raw.as_deref()).await
+}
+
+fn paused_jobs_key(guild_id: u64) -> String {
+    format!("guild:{guild_id}:paused_jobs")
+}
+
+/// Automations paused in a guild, by job name.
+pub async fn load_paused_jobs(
+    cache: &RedisCache,
+    guild_id: u64,
+) -> Result<HashSet<String>, GuildSettingsError> {
+    let mut conn = cache.connection();
+    let jobs: HashSet<String> = conn.smembers(paused_jobs_key(guild_id)).await?;
+    Ok(jobs)
+}
+
+pub async fn set_job_paused(
+    cache: &RedisCache,
+    guild_id: u64,
+    job: &str,
+    paused: bool,
+) -> Result<(), GuildSettingsError> {
+    let mut conn = cache.connection();
+    let key = paused_jobs_key(guild_id);
+    if paused {
+        conn.sadd::<_, _, ()>(key, job).await?;
+    } else {
+        conn.srem::<_, _, ()>(key, job).await?;
+    }
+    Ok(())
+}
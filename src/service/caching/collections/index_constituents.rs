@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Index membership changes a few times a quarter; a day keeps restarts from refetching it.
+const MEMBERS_TTL_SECS: u64 = 24 * 60 * 60;
+
+fn members_key(index: &str) -> String {
+    format!("index:{index}:members")
+}
+
+/// Same list without an expiry, used when a later fetch fails or looks wrong.
+fn last_good_key(index: &str) -> String {
+    format!("index:{index}:members:last_good")
+}
+
+#[derive(Debug, Error)]
+pub enum IndexConstituentsCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Save `index`'s members (by its settings name, e.g. `sp500`) for a day, and as the last good
+/// list until the next save.
+pub async fn save_members(
+    cache: &RedisCache,
+    index: &str,
+    members: &HashSet<String>,
+) -> Result<(), IndexConstituentsCacheError> {
+    let mut sorted: Vec<&String> = members.iter().collect();
+    sorted.sort();
+    let json = serde_json::to_string(&sorted)?;
+    let mut conn = cache.connection();
+    let _: () = redis::pipe()
+        .set_ex(members_key(index), &json, MEMBERS_TTL_SECS)
+        .set(last_good_key(index), &json)
+        .query_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+/// The cached members of `index`, or `None` once they have expired.
+pub async fn load_members(
+    cache: &RedisCache,
+    index: &str,
+) -> Result<Option<HashSet<String>>, IndexConstituentsCacheError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(members_key(index)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
+
+/// The members saved by the last [`save_members`], however old.
+pub async fn load_last_good(
+    cache: &RedisCache,
+    index: &str,
+) -> Result<Option<HashSet<String>>, IndexConstituentsCacheError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.get(last_good_key(index)).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
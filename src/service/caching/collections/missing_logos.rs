@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Providers add logos rarely; a week keeps dead lookups out of daily renders but lets a
+/// newly listed company pick one up.
+const MISSING_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+fn missing_key(provider: &str, symbol: &str) -> String {
+    format!("logo:missing:{provider}:{}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum MissingLogosCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Remember that `provider` has no logo for `symbols`.
+pub async fn mark_missing(
+    cache: &RedisCache,
+    provider: &str,
+    symbols: &[String],
+) -> Result<(), MissingLogosCacheError> {
+    if symbols.is_empty() {
+        return Ok(());
+    }
+    let mut pipe = redis::pipe();
+    for symbol in symbols {
+        pipe.set_ex(missing_key(provider, symbol), 1, MISSING_TTL_SECS)
+            .ignore();
+    }
+    let mut conn = cache.connection();
+    pipe.query_async::<()>(&mut conn).await?;
+    Ok(())
+}
+
+/// Those of `symbols` that `provider` is known to have no logo for.
+pub async fn load_missing(
+    cache: &RedisCache,
+    provider: &str,
+    symbols: &[String],
+) -> Result<HashSet<String>, MissingLogosCacheError> {
+    if symbols.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let keys: Vec<String> = symbols.iter().map(|s| missing_key(provider, s)).collect();
+    let mut conn = cache.connection();
+    // An explicit MGET: the typed helper sends GET for a single key, which decodes differently.
+    let stored: Vec<Option<i64>> = redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+    Ok(symbols
+        .iter()
+        .zip(stored)
+        .filter(|(_, flag)| flag.is_some())
+        .map(|(symbol, _)| symbol.clone())
+        .collect())
+}
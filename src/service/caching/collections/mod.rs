@@ -1,2 +1,21 @@
+pub mod article_previews;
+pub mod command_stats;
+pub mod corporate_actions;
+pub mod earnings_calendar;
+pub mod earnings_estimates;
+pub mod earnings_importance;
+pub mod earnings_records;
+pub mod earnings_threads;
+pub mod etf_holdings;
+pub mod event_alerts;
+pub mod guild_settings;
+pub mod index_constituents;
+pub mod missing_logos;
+pub mod morning_briefs;
+pub mod news_seen;
+pub mod ownership;
 pub mod price_alerts;
+pub mod quote_board;
+pub mod relay_messages;
+pub mod relay_pairs;
 pub mod spy_data;
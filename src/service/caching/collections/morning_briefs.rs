@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveTime};
+use redis::{AsyncCommands, RedisError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Hash of user id → brief.
+const BRIEFS_KEY: &str = "morning_briefs";
+
+/// A user's opted-in morning brief (`/brief set`), sent by DM on weekdays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MorningBrief {
+    pub user_id: u64,
+    pub symbols: Vec<String>,
+    /// Local time of day the brief is sent at, in `timezone`.
+    pub time: NaiveTime,
+    /// IANA name the time is in.
+    pub timezone: String,
+    /// Local date of the last brief sent, so each day gets one.
+    pub last_sent: Option<NaiveDate>,
+}
+
+#[derive(Debug, Error)]
+pub enum MorningBriefStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Every brief, by user id.
+pub async fn load_all(
+    cache: &RedisCache,
+) -> Result<HashMap<u64, MorningBrief>, MorningBriefStoreError> {
+    let mut conn = cache.connection();
+    let raw: HashMap<u64, String> = conn.hgetall(BRIEFS_KEY).await?;
+    let mut out = HashMap::with_capacity(raw.len());
+    for (user_id, json) in raw {
+        out.insert(user_id, serde_json::from_str(&json)?);
+    }
+    Ok(out)
+}
+
+pub async fn load(
+    cache: &RedisCache,
+    user_id: u64,
+) -> Result<Option<MorningBrief>, MorningBriefStoreError> {
+    let mut conn = cache.connection();
+    let raw: Option<String> = conn.hget(BRIEFS_KEY, user_id).await?;
+    Ok(raw.map(|raw| serde_json::from_str(&raw)).transpose()?)
+}
+
+pub async fn save(cache: &RedisCache, brief: &MorningBrief) -> Result<(), MorningBriefStoreError> {
+    let mut conn = cache.connection();
+    conn.hset::<_, _, _, ()>(BRIEFS_KEY, brief.user_id, serde_json::to_string(brief)?)
+        .await?;
+    Ok(())
+}
+
+/// Remove the user's brief; `false` when there was none.
+pub async fn delete(cache: &RedisCache, user_id: u64) -> Result<bool, MorningBriefStoreError> {
+    let mut conn = cache.connection();
+    let removed: u64 = conn.hdel(BRIEFS_KEY, user_id).await?;
+    Ok(removed > 0)
+}
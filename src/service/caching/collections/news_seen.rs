@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use redis::RedisError;
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// How long a posted link suppresses reposts. Stories older than a few days no longer show up
+/// in Yahoo's feed, so they can't come back after it.
+const SEEN_TTL_SECS: i64 = 3 * 24 * 60 * 60;
+
+/// One sorted set per scope (e.g. a channel), scored by when each canonical link was claimed,
+/// so links age out individually while the key itself lives as long as it is used.
+fn seen_key(scope: &str) -> String {
+    format!("news:seen:{scope}")
+}
+
+#[derive(Debug, Error)]
+pub enum NewsSeenError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Claim `links` (canonical URLs) for `scope` at `now`. Returns, per link, whether it was new:
+/// `false` means it was already claimed in the last [`SEEN_TTL_SECS`], by this process or
+/// another, and should not be posted again.
+pub async fn claim_links(
+    cache: &RedisCache,
+    scope: &str,
+    links: &[String],
+    now: DateTime<Utc>,
+) -> Result<Vec<bool>, NewsSeenError> {
+    if links.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut conn = cache.connection();
+    let key = seen_key(scope);
+    let now = now.timestamp();
+
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .zrembyscore(&key, "-inf", now - SEEN_TTL_SECS)
+        .ignore();
+    for link in links {
+        // ZADD NX replies 1 for a new member and 0 for one already in the set.
+        pipe.cmd("ZADD").arg(&key).arg("NX").arg(now).arg(link);
+    }
+    pipe.expire(&key, SEEN_TTL_SECS).ignore();
+    let added: Vec<i64> = pipe.query_async(&mut conn).await?;
+    Ok(added.into_iter().map(|n| n == 1).collect())
+}
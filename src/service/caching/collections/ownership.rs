@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+use crate::service::finance::ownership::{OwnershipSnapshot, KEEP_QUARTERS};
+
+/// One hash per symbol, a field per quarter end. Snapshots are only useful across quarters,
+/// so the key doesn't expire; old quarters are dropped on save instead.
+fn snapshots_key(symbol: &str) -> String {
+    format!("holders:ownership:{}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum OwnershipCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Store the quarter's snapshot, replacing an earlier one for the same quarter since late
+/// filings keep updating it, and drop all but the latest [`KEEP_QUARTERS`].
+pub async fn save_snapshot(
+    cache: &RedisCache,
+    symbol: &str,
+    snapshot: &OwnershipSnapshot,
+) -> Result<(), OwnershipCacheError> {
+    let mut conn = cache.connection();
+    let key = snapshots_key(symbol);
+    conn.hset::<_, _, _, ()>(
+        &key,
+        snapshot.quarter.to_string(),
+        serde_json::to_string(snapshot)?,
+    )
+    .await?;
+
+    let mut quarters: Vec<String> = conn.hkeys(&key).await?;
+    if quarters.len() > KEEP_QUARTERS {
+        // ISO dates sort chronologically.
+        quarters.sort();
+        let stale = &quarters[..quarters.len() - KEEP_QUARTERS];
+        conn.hdel::<_, _, ()>(&key, stale).await?;
+    }
+    Ok(())
+}
+
+/// Every stored snapshot for `symbol`, oldest quarter first.
+pub async fn load_snapshots(
+    cache: &RedisCache,
+    symbol: &str,
+) -> Result<Vec<OwnershipSnapshot>, OwnershipCacheError> {
+    let mut conn = cache.connection();
+    let fields: Vec<(String, String)> = conn.hgetall(snapshots_key(symbol)).await?;
+    let mut snapshots = fields
+        .into_iter()
+        .filter(|(quarter, _)| NaiveDate::parse_from_str(quarter, "%Y-%m-%d").is_ok())
+        .map(|(_, json)| serde_json::from_str(&json))
+        .collect::<Result<Vec<OwnershipSnapshot>, _>>()?;
+    snapshots.sort_by_key(|s| s.quarter);
+    Ok(snapshots)
+}
@@ -0,0 +1,38 @@
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Boards are edited for as long as they are configured, so the ids don't expire.
+fn message_key(channel_id: u64) -> String {
+    format!("quote_board:message:{channel_id}")
+}
+
+#[derive(Debug, Error)]
+pub enum QuoteBoardStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Remember the board message posted in a channel.
+pub async fn save_message(
+    cache: &RedisCache,
+    channel_id: u64,
+    message_id: u64,
+) -> Result<(), QuoteBoardStoreError> {
+    let mut conn = cache.connection();
+    conn.set::<_, _, ()>(message_key(channel_id), message_id)
+        .await?;
+    Ok(())
+}
+
+pub async fn load_message(
+    cache: &RedisCache,
+    channel_id: u64,
+) -> Result<Option<u64>, QuoteBoardStoreError> {
+    let mut conn = cache.connection();
+    let message_id: Option<u64> = conn.get(message_key(channel_id)).await?;
+    Ok(message_id)
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisError};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+/// Mappings are only needed while a source message can still be edited or deleted in a
+/// meaningful way, so they expire after a week.
+const MIRROR_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Gateway events can be replayed on resume or delivered to several replicas; a short window
+/// is enough to make processing idempotent.
+const PROCESSED_TTL_SECS: u64 = 10 * 60;
+
+fn processed_key(source_message_id: u64) -> String {
+    format!("relay:processed:{source_message_id}")
+}
+
+fn mirror_key(source_message_id: u64) -> String {
+    format!("relay:mirror:{source_message_id}")
+}
+
+/// A relayed copy of a source message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MirroredMessage {
+    pub channel_id: u64,
+    pub message_id: u64,
+}
+
+#[derive(Debug, Error)]
+pub enum RelayMessageStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+/// Mark a source message as handled. Returns false when it was already claimed.
+pub async fn claim_message(
+    cache: &RedisCache,
+    source_message_id: u64,
+) -> Result<bool, RelayMessageStoreError> {
+    let mut conn = cache.connection();
+    let reply: Option<String> = conn
+        .set_options(
+            processed_key(source_message_id),
+            1,
+            redis::SetOptions::default()
+                .conditional_set(redis::ExistenceCheck::NX)
+                .with_expiration(redis::SetExpiry::EX(PROCESSED_TTL_SECS)),
+        )
+        .await?;
+    // SET NX replies OK when the key was set and nil when it already existed.
+    Ok(reply.is_some())
+}
+
+pub async fn record_mirror(
+    cache: &RedisCache,
+    source_message_id: u64,
+    mirror: MirroredMessage,
+) -> Result<(), RelayMessageStoreError> {
+    let mut conn = cache.connection();
+    let key = mirror_key(source_message_id);
+    let _: () = redis::pipe()
+        .hset(&key, mirror.channel_id, mirror.message_id)
+        .expire(&key, MIRROR_TTL_SECS)
+        .query_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+pub async fn load_mirrors(
+    cache: &RedisCache,
+    source_message_id: u64,
+) -> Result<Vec<MirroredMessage>, RelayMessageStoreError> {
+    let mut conn = cache.connection();
+    let stored: HashMap<u64, u64> = conn.hgetall(mirror_key(source_message_id)).await?;
+    let mut mirrors: Vec<MirroredMessage> = stored
+        .into_iter()
+        .map(|(channel_id, message_id)| MirroredMessage {
+            channel_id,
+            message_id,
+        })
+        .collect();
+    mirrors.sort_by_key(|m| m.channel_id);
+    Ok(mirrors)
+}
+
+/// Drop the mapping and return what was stored, so callers can clean up the copies.
+pub async fn take_mirrors(
+    cache: &RedisCache,
+    source_message_id: u64,
+) -> Result<Vec<MirroredMessage>, RelayMessageStoreError> {
+    let mirrors = load_mirrors(cache, source_message_id).await?;
+    let mut conn = cache.connection();
+    let _: () = conn.del(mirror_key(source_message_id)).await?;
+    Ok(mirrors)
+}
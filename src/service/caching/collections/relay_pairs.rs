@@ -0,0 +1,61 @@
+use redis::{AsyncCommands, RedisError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::service::caching::{CacheError, RedisCache};
+
+const PAIRS_KEY: &str = "relay:pairs";
+
+/// A single source → target relay mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RelayPair {
+    pub source_channel_id: u64,
+    pub target_channel_id: u64,
+}
+
+impl RelayPair {
+    fn member(&self) -> String {
+        format!("{}:{}", self.source_channel_id, self.target_channel_id)
+    }
+
+    fn from_member(raw: &str) -> Option<Self> {
+        let (source, target) = raw.split_once(':')?;
+        Some(Self {
+            source_channel_id: source.parse().ok()?,
+            target_channel_id: target.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RelayPairStoreError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
+pub async fn load_pairs(cache: &RedisCache) -> Result<Vec<RelayPair>, RelayPairStoreError> {
+    let mut conn = cache.connection();
+    let members: Vec<String> = conn.smembers(PAIRS_KEY).await?;
+    let mut pairs: Vec<RelayPair> = members
+        .iter()
+        .filter_map(|m| RelayPair::from_member(m))
+        .collect();
+    pairs.sort_by_key(|p| (p.source_channel_id, p.target_channel_id));
+    Ok(pairs)
+}
+
+/// Returns true when the pair was newly added.
+pub async fn add_pair(cache: &RedisCache, pair: RelayPair) -> Result<bool, RelayPairStoreError> {
+    let mut conn = cache.connection();
+    let added: i64 = conn.sadd(PAIRS_KEY, pair.member()).await?;
+    Ok(added > 0)
+}
+
+/// Returns true when the pair existed and was removed.
+pub async fn remove_pair(cache: &RedisCache, pair: RelayPair) -> Result<bool, RelayPairStoreError> {
+    let mut conn = cache.connection();
+    let removed: i64 = conn.srem(PAIRS_KEY, pair.member()).await?;
+    Ok(removed > 0)
+}
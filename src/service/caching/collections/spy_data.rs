@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 
-use chrono::{DateTime, Utc};
-use redis::AsyncCommands;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use redis::{AsyncCommands, RedisError};
 use serde::Deserialize;
+use thiserror::Error;
 
 use crate::service::caching::{CacheError, RedisCache};
+use crate::service::finance::open_interest::ContractOpenInterest;
 use crate::service::finance::options::OptionSlice;
 
 const HISTORY_LIMIT: isize = 200;
 const HISTORY_TTL_SECS: i64 = 60 * 60 * 24 * 7; // 7 days
+/// Long enough to compare across a holiday weekend.
+const OI_SNAPSHOT_TTL_SECS: u64 = 60 * 60 * 24 * 10;
+/// Days searched back for the previous snapshot.
+const OI_LOOKBACK_DAYS: i64 = 7;
 
 #[derive(Debug, Deserialize)]
 struct Point {
@@ -24,6 +30,20 @@ fn strike_key(expiration: &str, strike: &str) -> String {
     format!("spy:history:{expiration}:{strike}")
 }
 
+fn oi_key(symbol: &str, day: NaiveDate) -> String {
+    format!("options:oi:{}:{day}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Error)]
+pub enum OpenInterestCacheError {
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+}
+
 /// Append the latest slice prices to Redis and keep the history bounded.
 pub async fn append_slice(cache: &RedisCache, slice: &OptionSlice) -> Result<(), CacheError> {
     let mut conn = cache.connection();
@@ -95,3 +115,48 @@ pub fn history_from_slice(slice: &OptionSlice) -> HashMap<String, Vec<(DateTime<
 
 pub const DEFAULT_HISTORY_POINTS: usize = HISTORY_LIMIT as usize;
 
+/// Store the day's open interest for `symbol` unless that day already has a snapshot, so
+/// there is one per day. Returns false when one existed.
+pub async fn save_oi_snapshot(
+    cache: &RedisCache,
+    symbol: &str,
+    day: NaiveDate,
+    contracts: &[ContractOpenInterest],
+) -> Result<bool, OpenInterestCacheError> {
+    let mut conn = cache.connection();
+    let reply: Option<String> = conn
+        .set_options(
+            oi_key(symbol, day),
+            serde_json::to_string(contracts)?,
+            redis::SetOptions::default()
+                .conditional_set(redis::ExistenceCheck::NX)
+                .with_expiration(redis::SetExpiry::EX(OI_SNAPSHOT_TTL_SECS)),
+        )
+        .await?;
+    Ok(reply.is_some())
+}
+
+pub async fn load_oi_snapshot(
+    cache: &RedisCache,
+    symbol: &str,
+    day: NaiveDate,
+) -> Result<Option<Vec<ContractOpenInterest>>, OpenInterestCacheError> {
+    let mut conn = cache.connection();
+    let json: Option<String> = conn.get(oi_key(symbol, day)).await?;
+    Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+}
+
+/// The most recent snapshot before `day`, within the last week, with its date.
+pub async fn previous_oi_snapshot(
+    cache: &RedisCache,
+    symbol: &str,
+    day: NaiveDate,
+) -> Result<Option<(NaiveDate, Vec<ContractOpenInterest>)>, OpenInterestCacheError> {
+    for back in 1..=OI_LOOKBACK_DAYS {
+        let earlier = day - Duration::days(back);
+        if let Some(snapshot) = load_oi_snapshot(cache, symbol, earlier).await? {
+            return Ok(Some((earlier, snapshot)));
+        }
+    }
+    Ok(None)
+}
@@ -1,11 +1,11 @@
-use std::env;
-
 use redis::{aio::ConnectionManager, Client};
 use thiserror::Error;
 
+use crate::service::config::RedisConfig;
+
 #[derive(Debug, Error)]
 pub enum CacheError {
-    #[error("redis url not set (REDIS_URL)")]
+    #[error("redis url not set (redis.url / REDIS_URL)")]
     MissingUrl,
     #[error("redis error: {0}")]
     Redis(#[from] redis::RedisError),
@@ -23,13 +23,21 @@ impl RedisCache {
         Ok(Self { manager })
     }
 
-    pub async fn from_env() -> Result<Self, CacheError> {
-        let url = env::var("REDIS_URL").map_err(|_| CacheError::MissingUrl)?;
-        Self::new(&url).await
+    /// Connect to `redis.url`; `MissingUrl` when it is unset.
+    pub async fn from_config(config: &RedisConfig) -> Result<Self, CacheError> {
+        let url = config.url.as_deref().ok_or(CacheError::MissingUrl)?;
+        Self::new(url).await
     }
 
     pub fn connection(&self) -> ConnectionManager {
         self.manager.clone()
     }
+
+    /// Round-trip a PING; used by the health check.
+    pub async fn ping(&self) -> Result<(), CacheError> {
+        let mut conn = self.connection();
+        let _: String = redis::cmd("PING").query_async(&mut conn).await?;
+        Ok(())
+    }
 }
 
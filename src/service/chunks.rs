@@ -0,0 +1,58 @@
+//! Splitting text over Discord's message length limit into several messages.
+
+/// Discord rejects message content longer than this many characters.
+pub const MESSAGE_LIMIT: usize = 2000;
+
+/// Split `content` into pieces of at most `limit` characters, breaking between lines. A line
+/// longer than `limit` on its own is cut mid-line. Content within the limit is returned as is.
+pub fn split(content: &str, limit: usize) -> Vec<String> {
+    if content.chars().count() <= limit {
+        return vec![content.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    for line in content.split('\n') {
+        let line_len = line.chars().count();
+        if current_len == 0 && line_len <= limit {
+            current.push_str(line);
+            current_len = line_len;
+            continue;
+        }
+        if current_len + 1 + line_len <= limit {
+            current.push('\n');
+            current.push_str(line);
+            current_len += 1 + line_len;
+            continue;
+        }
+
+        if current_len > 0 {
+            chunks.push(std::mem::take(&mut current));
+        }
+        let mut rest = line;
+        while rest.chars().count() > limit {
+            let cut = rest
+                .char_indices()
+                .nth(limit)
+                .map_or(rest.len(), |(i, _)| i);
+            chunks.push(rest[..cut].to_string());
+            rest = &rest[cut..];
+        }
+        current = rest.to_string();
+        current_len = rest.chars().count();
+    }
+    chunks.push(current);
+    // Discord also rejects blank messages, which a run of empty lines at a break would make.
+    chunks.retain(|chunk| !chunk.trim().is_empty());
+    chunks
+}
+
+/// [`split`] with every piece wrapped in a spoiler (`||…||`), so long text stays collapsed
+/// until clicked. Each piece, markers included, is at most `limit` characters.
+pub fn spoilers(content: &str, limit: usize) -> Vec<String> {
+    split(content, limit - 4)
+        .into_iter()
+        .map(|chunk| format!("||{chunk}||"))
+        .collect()
+}
@@ -0,0 +1,490 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use chrono_tz::America::New_York;
+use serenity::all::{
+    Attachment, ChannelId, CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand,
+    CreateCommandOption, GuildId, Http, Permissions, ResolvedOption, ResolvedValue,
+};
+use serenity::async_trait;
+use tracing::{info, warn};
+
+use crate::service::automation::jobs::{self, Job};
+use crate::service::caching::RedisCache;
+use crate::service::config::{Config, ConfigChange, ConfigHandle};
+use crate::service::finance::FinanceService;
+use crate::service::i18n::Locale;
+use crate::service::store::export::{self, GuildExport, ImportSummary, EXPORT_VERSION};
+use crate::service::store::StateStore;
+use crate::service::timefmt::{self, TimestampStyle};
+
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+
+pub const COMMAND_NAME: &str = "admin";
+
+/// Exports are a few KB; anything much larger is not one of ours.
+const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+
+fn job_option() -> CreateCommandOption {
+    Job::ALL.into_iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "job", "Scheduled automation")
+            .required(true),
+        |opt, job| opt.add_string_choice(job.label(), job.name()),
+    )
+}
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("Operate the bot's automations (admins only)")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .dm_permission(false)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "run",
+                "Post an automation's report now instead of waiting for its schedule",
+            )
+            .add_sub_option(job_option()),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "reload-config",
+            "Re-read the config file and apply changed channels and settings",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "export",
+            "Download this server's settings, paused jobs and price alerts as JSON",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "import",
+                "Restore this server's data from an /admin export file",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Attachment,
+                    "file",
+                    "JSON file from /admin export",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommandGroup,
+                "jobs",
+                "Scheduled automations in this server",
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "list",
+                "Show each automation's next run and last result",
+            ))
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "pause",
+                    "Stop an automation from posting until resumed",
+                )
+                .add_sub_option(job_option()),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "resume",
+                    "Let a paused automation post again",
+                )
+                .add_sub_option(job_option()),
+            ),
+        )
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    http: &Http,
+    finance: &FinanceService,
+    cache: Option<&RedisCache>,
+    store: Option<&dyn StateStore>,
+    config: &ConfigHandle,
+) -> CommandResult {
+    let options = command.data.options();
+    let (subcommand, args) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(args),
+            ..
+        }) => (*name, args),
+        Some(ResolvedOption {
+            name: "jobs",
+            value: ResolvedValue::SubCommandGroup(group),
+            ..
+        }) => {
+            let guild_id = command
+                .guild_id
+                .ok_or_else(|| BotError::bad_input("/admin jobs only works in a server"))?;
+            return handle_jobs(group, guild_id, store, &config.current())
+                .await
+                .map(CommandResponse::text);
+        }
+        _ => return Err(BotError::bad_input("choose an /admin subcommand")),
+    };
+
+    match subcommand {
+        "run" => {
+            let job = get_job(args)?;
+            let config = config.current();
+            if jobs::run_channel(job, command.guild_id, http, store, &config)
+                .await
+                .is_none()
+            {
+                return Err(BotError::bad_input(format!(
+                    "{} has no channel; route `{}` with `/config routes`",
+                    job.label(),
+                    job.route().name()
+                )));
+            }
+            let channel_id =
+                jobs::run_now(job, command.guild_id, http, finance, cache, store, &config)
+                    .await
+                    .map_err(|e| BotError::internal(format!("{} failed: {e}", job.name())))?;
+            Ok(CommandResponse::text(format!(
+                "✅ {} posted to <#{channel_id}>.",
+                job.label()
+            )))
+        }
+        "reload-config" => {
+            if !may_reload(command, http, &config.current()).await {
+                return Err(BotError::Forbidden(
+                    "only the bot's owners or the operator server can reload the config"
+                        .to_string(),
+                ));
+            }
+            let changes = config
+                .reload()
+                .map_err(|e| BotError::bad_input(format!("config not reloaded: {e}")))?;
+            info!("Config reloaded by {}: {changes:?}", command.user.name);
+            Ok(CommandResponse::text(format_changes(&changes)))
+        }
+        "export" => {
+            let (guild_id, store) = guild_and_store(command, store)?;
+            let data = export::export_guild(store, guild_id.get())
+                .await
+                .map_err(|e| BotError::internal(format!("export failed: {e}")))?;
+            let json = serde_json::to_vec_pretty(&data)
+                .map_err(|e| BotError::internal(format!("export failed: {e}")))?;
+            info!("Guild {guild_id} exported by {}", command.user.name);
+            Ok(CommandResponse::text(format!(
+                "📦 Export of {} restricted command(s), {} paused job(s) and {} price alert(s).",
+                data.command_roles.len(),
+                data.paused_jobs.len(),
+                data.price_alerts.len()
+            ))
+            .with_attachment(CreateAttachment::bytes(
+                json,
+                format!("guild-{guild_id}-export.json"),
+            )))
+        }
+        "import" => {
+            let (guild_id, store) = guild_and_store(command, store)?;
+            let data = read_export(args, guild_id).await?;
+            check_channels(http, guild_id, &data).await?;
+            let summary = export::import_guild(store, guild_id.get(), &data)
+                .await
+                .map_err(|e| BotError::internal(format!("import failed: {e}")))?;
+            info!(
+                "Guild {guild_id} imported by {} from export of {}: {summary:?}",
+                command.user.name, data.exported_at
+            );
+            Ok(CommandResponse::text(format_import(&summary)))
+        }
+        other => Err(BotError::bad_input(format!("unknown subcommand {other}"))),
+    }
+}
+
+async fn handle_jobs(
+    group: &[ResolvedOption<'_>],
+    guild_id: GuildId,
+    store: Option<&dyn StateStore>,
+    config: &Config,
+) -> BotResult<String> {
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(args),
+        ..
+    }) = group.first()
+    else {
+        return Err(BotError::bad_input("choose list, pause or resume"));
+    };
+
+    match *name {
+        "list" => Ok(format_jobs(guild_id, store, config).await),
+        "pause" | "resume" => {
+            let job = get_job(args)?;
+            let pause = *name == "pause";
+            jobs::set_paused(store, guild_id, job, pause)
+                .await
+                .map_err(BotError::internal)?;
+            Ok(if pause {
+                format!("⏸️ {} paused in this server.", job.label())
+            } else {
+                format!("▶️ {} resumed in this server.", job.label())
+            })
+        }
+        other => Err(BotError::bad_input(format!("unknown subcommand {other}"))),
+    }
+}
+
+async fn format_jobs(guild_id: GuildId, store: Option<&dyn StateStore>, config: &Config) -> String {
+    let paused = jobs::paused_jobs(store, guild_id).await;
+    let now = Utc::now().with_timezone(&New_York);
+
+    let mut lines = vec!["Automations".to_string()];
+    for job in Job::ALL {
+        let state = if job.channel(config).is_none() {
+            "⚪ no channel configured".to_string()
+        } else if paused.contains(&job) {
+            "⏸️ paused".to_string()
+        } else {
+            match job.next_run(now) {
+                Some(next) => format!(
+                    "▶️ next {}",
+                    timefmt::discord(&next, TimestampStyle::ShortDateTime)
+                ),
+                None => "▶️ active".to_string(),
+            }
+        };
+        let last = match jobs::last_run(job).await {
+            Some(run) => match run.result {
+                Ok(()) => format!(
+                    "last ✅ {}",
+                    timefmt::discord(&run.at, TimestampStyle::Relative)
+                ),
+                Err(e) => format!(
+                    "last ❌ {}: {e}",
+                    timefmt::discord(&run.at, TimestampStyle::Relative)
+                ),
+            },
+            None => "not run since restart".to_string(),
+        };
+        lines.push(format!(
+            "• {} (`{}`) — {state} | {last}",
+            job.label(),
+            job.name()
+        ));
+    }
+    if store.is_none() {
+        lines.push(String::new());
+        lines.push(
+            "No state store is configured, so pauses reset when the bot restarts.".to_string(),
+        );
+    }
+    lines.join("\n")
+}
+
+fn guild_and_store<'a>(
+    command: &CommandInteraction,
+    store: Option<&'a dyn StateStore>,
+) -> BotResult<(GuildId, &'a dyn StateStore)> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| BotError::bad_input("export and import only work in a server"))?;
+    let store = store.ok_or_else(|| {
+        BotError::bad_input("no state store is configured, so there is nothing to export or import")
+    })?;
+    Ok((guild_id, store))
+}
+
+/// Download and check an uploaded export before anything is written.
+/// Reject an export naming channels in another server: an edited file could otherwise point
+/// routes, the audit channel or price alerts at any channel the bot can see.
+async fn check_channels(http: &Http, guild_id: GuildId, data: &GuildExport) -> BotResult<()> {
+    let mut channel_guilds = BTreeMap::new();
+    for channel_id in data.channel_ids().into_iter().filter(|id| *id != 0) {
+        match ChannelId::new(channel_id).to_channel(http).await {
+            Ok(channel) => {
+                if let Some(channel) = channel.guild() {
+                    channel_guilds.insert(channel_id, channel.guild_id.get());
+                }
+            }
+            Err(e) => {
+                warn!("import for guild {guild_id}: failed to resolve channel {channel_id}: {e}")
+            }
+        }
+    }
+    let foreign = data.foreign_channels(guild_id.get(), &channel_guilds);
+    if foreign.is_empty() {
+        return Ok(());
+    }
+    let channels: Vec<String> = foreign.iter().map(|id| format!("<#{id}>")).collect();
+    Err(BotError::bad_input(format!(
+        "{} {} not in this server; nothing was imported",
+        channels.join(", "),
+        if foreign.len() == 1 { "is" } else { "are" }
+    )))
+}
+
+async fn read_export(args: &[ResolvedOption<'_>], guild_id: GuildId) -> BotResult<GuildExport> {
+    let file: &Attachment = args
+        .iter()
+        .find_map(|o| match o.value {
+            ResolvedValue::Attachment(file) if o.name == "file" => Some(file),
+            _ => None,
+        })
+        .ok_or_else(|| BotError::bad_input("attach the JSON file from /admin export"))?;
+    if file.size > MAX_IMPORT_BYTES {
+        return Err(BotError::bad_input(format!(
+            "{} is too large to be an export",
+            file.filename
+        )));
+    }
+    let bytes = file
+        .download()
+        .await
+        .map_err(|e| BotError::internal(format!("downloading {} failed: {e}", file.filename)))?;
+    let data: GuildExport = serde_json::from_slice(&bytes).map_err(|e| {
+        BotError::bad_input(format!("{} is not an export file: {e}", file.filename))
+    })?;
+
+    if data.version != EXPORT_VERSION {
+        return Err(BotError::bad_input(format!(
+            "export version {} is not supported (expected {EXPORT_VERSION})",
+            data.version
+        )));
+    }
+    // Role and channel ids only mean something in the server they came from.
+    if data.guild_id != guild_id.get() {
+        return Err(BotError::bad_input(format!(
+            "this export belongs to server {}, not this one",
+            data.guild_id
+        )));
+    }
+    let unknown: Vec<&str> = data
+        .paused_jobs
+        .iter()
+        .filter(|job| Job::from_name(job).is_none())
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(BotError::bad_input(format!(
+            "unknown job(s) in export: {}",
+            unknown.join(", ")
+        )));
+    }
+    if let Some(code) = data.locale.as_deref() {
+        if Locale::from_code(code).is_none() {
+            return Err(BotError::bad_input(format!(
+                "unknown language in export: {code}"
+            )));
+        }
+    }
+    if let Some(name) = data.timezone.as_deref() {
+        if timefmt::parse_timezone(name).is_none() {
+            return Err(BotError::bad_input(format!(
+                "unknown timezone in export: {name}"
+            )));
+        }
+    }
+    Ok(data)
+}
+
+fn format_import(summary: &ImportSummary) -> String {
+    format!(
+        "📥 Imported {} restricted command(s), {} paused job(s) and {} price alert(s); \
+         anything not in the file was cleared. Price alerts take effect when the alerts bot \
+         next restarts.",
+        summary.command_roles, summary.paused_jobs, summary.price_alerts
+    )
+}
+
+/// The config is process-wide, so a reload is limited to the application's owners (or team
+/// members) and to the server holding `channels.operator`.
+async fn may_reload(command: &CommandInteraction, http: &Http, config: &Config) -> bool {
+    if let (Some(guild_id), Some(operator)) = (command.guild_id, config.channels.operator) {
+        match ChannelId::new(operator).to_channel(http).await {
+            Ok(channel) => {
+                if channel.guild().is_some_and(|c| c.guild_id == guild_id) {
+                    return true;
+                }
+            }
+            Err(e) => warn!("failed to resolve operator channel {operator}: {e}"),
+        }
+    }
+    match http.get_current_application_info().await {
+        Ok(app) => {
+            let user_id = command.user.id;
+            app.owner.is_some_and(|owner| owner.id == user_id)
+                || app
+                    .team
+                    .is_some_and(|team| team.members.iter().any(|m| m.user.id == user_id))
+        }
+        Err(e) => {
+            warn!("failed to load application owners: {e}");
+            false
+        }
+    }
+}
+
+/// Changed keys only; values stay in the log so operator settings aren't echoed to Discord.
+pub fn format_changes(changes: &[ConfigChange]) -> String {
+    if changes.is_empty() {
+        return "🔄 Config reloaded; nothing changed.".to_string();
+    }
+    let mut lines = vec!["🔄 Config reloaded".to_string()];
+    for change in changes {
+        let restart = if change.needs_restart {
+            " (applies after restart)"
+        } else {
+            ""
+        };
+        lines.push(format!("• `{}` changed{restart}", change.key));
+    }
+    lines.join("\n")
+}
+
+fn get_job(args: &[ResolvedOption<'_>]) -> BotResult<Job> {
+    let name = args
+        .iter()
+        .find(|o| o.name == "job")
+        .and_then(|o| match o.value {
+            ResolvedValue::String(s) => Some(s),
+            _ => None,
+        })
+        .ok_or_else(|| BotError::bad_input("job is required"))?;
+    Job::from_name(name).ok_or_else(|| BotError::bad_input(format!("unknown job {name}")))
+}
+
+pub struct AdminCommand;
+
+#[async_trait]
+impl Command for AdminCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    fn audited(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(
+            ctx.interaction,
+            &ctx.ctx.http,
+            ctx.finance,
+            ctx.cache,
+            ctx.store,
+            ctx.config,
+        )
+        .await
+    }
+}
@@ -0,0 +1,374 @@
+//! Argument parsing for mention commands, and lookups of slash command options.
+//!
+//! Mention commands reuse the slash command definitions: a [`CommandSpec`] is read from the
+//! same `CreateCommand` builder, so positional order, required args, choices and integer
+//! bounds stay in sync with `/commands` and the help text is generated from them.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serenity::all::{
+    CommandDataOptionValue, CommandInteraction, CommandOption, CommandOptionType, CreateCommand,
+    ResolvedOption, ResolvedValue,
+};
+
+use super::error::{BotError, BotResult};
+use super::export::EXPORT_OPTION;
+use super::framework::EPHEMERAL_OPTION;
+
+/// A top-level string option of a slash command, by name.
+pub fn get_str_opt<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match &o.value {
+            CommandDataOptionValue::String(s) => Some(s.as_str()),
+            _ => None,
+        })
+}
+
+/// A top-level number option of a slash command, by name; integer options are widened.
+pub fn get_f64_opt(command: &CommandInteraction, name: &str) -> Option<f64> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            CommandDataOptionValue::Number(n) => Some(n),
+            CommandDataOptionValue::Integer(n) => Some(n as f64),
+            _ => None,
+        })
+}
+
+/// A top-level integer option of a slash command, by name.
+pub fn get_i64_opt(command: &CommandInteraction, name: &str) -> Option<i64> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            CommandDataOptionValue::Integer(n) => Some(n),
+            _ => None,
+        })
+}
+
+/// A top-level boolean option of a slash command, by name.
+pub fn get_bool_opt(command: &CommandInteraction, name: &str) -> Option<bool> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            CommandDataOptionValue::Boolean(b) => Some(b),
+            _ => None,
+        })
+}
+
+/// A string option among a subcommand's resolved options, by name.
+pub fn get_str<'a>(args: &'a [ResolvedOption<'a>], name: &str) -> Option<&'a str> {
+    args.iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            ResolvedValue::String(s) => Some(s),
+            _ => None,
+        })
+}
+
+/// Short names accepted for `key=value` flags.
+const ARG_ALIASES: [(&str, &str); 7] = [
+    ("n", "limit"),
+    ("y", "year"),
+    ("q", "quarter"),
+    ("f", "freq"),
+    ("m", "metric"),
+    ("cur", "currency"),
+    ("ccy", "currency"),
+];
+
+/// One argument of a mention command.
+#[derive(Debug, Clone)]
+pub struct ArgSpec {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+    pub kind: CommandOptionType,
+    /// `(label, value)` pairs; empty when any value is accepted.
+    pub choices: Vec<(String, String)>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+/// A mention command built from a slash command definition.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub name: String,
+    pub description: String,
+    pub aliases: &'static [&'static str],
+    pub args: Vec<ArgSpec>,
+}
+
+impl CommandSpec {
+    /// Read name, description and options from a slash command builder. Options listed in
+    /// `skip` are left out, as are the `ephemeral` flag, which means nothing in a channel
+    /// message, and `format`, since mention replies stay text.
+    pub fn from_slash(
+        command: CreateCommand,
+        aliases: &'static [&'static str],
+        skip: &[&str],
+    ) -> Self {
+        let json = serde_json::to_value(command).unwrap_or_default();
+        let name = json["name"].as_str().unwrap_or_default().to_string();
+        let description = json["description"].as_str().unwrap_or_default().to_string();
+        let options: Vec<CommandOption> =
+            serde_json::from_value(json["options"].clone()).unwrap_or_default();
+
+        let args = options
+            .into_iter()
+            .filter(|opt| {
+                opt.name != EPHEMERAL_OPTION
+                    && opt.name != EXPORT_OPTION
+                    && !skip.contains(&opt.name.as_str())
+            })
+            .map(|opt| ArgSpec {
+                choices: opt
+                    .choices
+                    .iter()
+                    .map(|c| {
+                        let value = match &c.value {
+                            serde_json::Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        (c.name.clone(), value)
+                    })
+                    .collect(),
+                min: opt.min_value.as_ref().and_then(|n| n.as_i64()),
+                max: opt.max_value.as_ref().and_then(|n| n.as_i64()),
+                name: opt.name,
+                description: opt.description,
+                required: opt.required,
+                kind: opt.kind,
+            })
+            .collect();
+
+        Self {
+            name,
+            description,
+            aliases,
+            args,
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+
+    /// `quote TICKER [CURRENCY]`
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for arg in &self.args {
+            let placeholder = if arg.choices.is_empty() {
+                arg.name.to_uppercase()
+            } else {
+                arg.choices
+                    .iter()
+                    .map(|(_, value)| value.as_str())
+                    .collect::<Vec<_>>()
+                    .join("|")
+            };
+            if arg.required {
+                usage.push_str(&format!(" {placeholder}"));
+            } else {
+                usage.push_str(&format!(" [{placeholder}]"));
+            }
+        }
+        usage
+    }
+
+    /// Fill arguments from `tokens`: `key=value` flags by name, everything else by position
+    /// in slash-option order, skipping args already set by a flag. A token is only a flag
+    /// when `key` names one of this command's args, so symbols like `EURUSD=X` or `GC=F`
+    /// stay positional.
+    pub fn parse(&self, tokens: &[String]) -> BotResult<ParsedArgs> {
+        let mut values = HashMap::new();
+        let mut positional = Vec::new();
+
+        for token in tokens {
+            let flag = token
+                .split_once('=')
+                .and_then(|(key, value)| Some((self.arg_for_flag(key)?, value)));
+            match flag {
+                Some((arg, value)) => {
+                    values.insert(arg.name.clone(), value.to_string());
+                }
+                None => positional.push(token.clone()),
+            }
+        }
+
+        let flagged: Vec<String> = values.keys().cloned().collect();
+        let mut free = self.args.iter().filter(|arg| !flagged.contains(&arg.name));
+        for token in positional {
+            let arg = free.next().ok_or_else(|| {
+                BotError::bad_input(format!(
+                    "unexpected argument `{token}`; usage: {}",
+                    self.usage()
+                ))
+            })?;
+            values.insert(arg.name.clone(), token);
+        }
+
+        for arg in &self.args {
+            match values.get(&arg.name) {
+                Some(raw) => {
+                    let checked = self.check(arg, raw)?;
+                    values.insert(arg.name.clone(), checked);
+                }
+                None if arg.required => {
+                    return Err(BotError::bad_input(format!(
+                        "{} is required; usage: {}",
+                        arg.name,
+                        self.usage()
+                    )));
+                }
+                None => {}
+            }
+        }
+
+        Ok(ParsedArgs(values))
+    }
+
+    fn arg_for_flag(&self, key: &str) -> Option<&ArgSpec> {
+        let key = key.to_ascii_lowercase();
+        let name = ARG_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == key)
+            .map(|(_, name)| *name)
+            .unwrap_or(key.as_str());
+        self.args.iter().find(|arg| arg.name == name)
+    }
+
+    /// Map choices to their canonical value and enforce integer bounds.
+    fn check(&self, arg: &ArgSpec, raw: &str) -> BotResult<String> {
+        if !arg.choices.is_empty() {
+            return arg
+                .choices
+                .iter()
+                .find(|(label, value)| {
+                    value.eq_ignore_ascii_case(raw) || label.eq_ignore_ascii_case(raw)
+                })
+                .map(|(_, value)| value.clone())
+                .ok_or_else(|| {
+                    let values: Vec<&str> = arg.choices.iter().map(|(_, v)| v.as_str()).collect();
+                    BotError::bad_input(format!(
+                        "{} must be one of {}",
+                        arg.name,
+                        values.join(" | ")
+                    ))
+                });
+        }
+
+        if arg.kind == CommandOptionType::Integer {
+            let value: i64 = raw.parse().map_err(|_| {
+                BotError::bad_input(format!("{} must be a whole number, got `{raw}`", arg.name))
+            })?;
+            let below = arg.min.is_some_and(|min| value < min);
+            let above = arg.max.is_some_and(|max| value > max);
+            if below || above {
+                let bounds = match (arg.min, arg.max) {
+                    (Some(min), Some(max)) => format!("between {min} and {max}"),
+                    (Some(min), None) => format!("at least {min}"),
+                    (None, Some(max)) => format!("at most {max}"),
+                    (None, None) => unreachable!(),
+                };
+                return Err(BotError::bad_input(format!(
+                    "{} must be {bounds}",
+                    arg.name
+                )));
+            }
+        }
+
+        Ok(raw.to_string())
+    }
+}
+
+/// Parsed, validated mention arguments keyed by slash option name.
+#[derive(Debug, Default)]
+pub struct ParsedArgs(HashMap<String, String>);
+
+impl ParsedArgs {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Fill in an argument the user left out.
+    pub fn insert(&mut self, name: &str, value: String) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    /// Value of an argument the spec marks as required.
+    pub fn required(&self, name: &str) -> BotResult<&str> {
+        self.get(name)
+            .ok_or_else(|| BotError::bad_input(format!("{name} is required")))
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> BotResult<Option<T>> {
+        self.get(name)
+            .map(|raw| {
+                raw.parse::<T>()
+                    .map_err(|_| BotError::bad_input(format!("invalid {name}: `{raw}`")))
+            })
+            .transpose()
+    }
+}
+
+/// Split on whitespace, keeping "quoted text" (straight or curly quotes) together. Quotes
+/// only open at the start of a token or right after `key=`, so `O'Neil` stays one word.
+pub fn tokenize(input: &str) -> BotResult<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    for c in input.chars() {
+        match quote {
+            Some(close) if c == close => quote = None,
+            Some(_) => current.push(c),
+            None => match c {
+                '"' | '\'' if opens_quote(&current, in_token) => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                '“' if opens_quote(&current, in_token) => {
+                    quote = Some('”');
+                    in_token = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_token = true;
+                }
+            },
+        }
+    }
+
+    if quote.is_some() {
+        return Err(BotError::bad_input("unterminated quote"));
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn opens_quote(current: &str, in_token: bool) -> bool {
+    !in_token || current.ends_with('=')
+}
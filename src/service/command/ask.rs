@@ -0,0 +1,58 @@
+use serenity::all::{CommandType, CreateCommand, ResolvedTarget};
+use serenity::async_trait;
+
+use crate::service::timefmt;
+
+use super::error::BotError;
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Invoker};
+use super::mention;
+
+/// Message context-menu command ("Apps → Ask Stacks-bot") that runs a message's text as a
+/// mention command. Context-menu interactions always carry the message content, so this works
+/// without the Message Content intent and on messages that don't mention the bot.
+pub struct AskCommand;
+
+pub const COMMAND_NAME: &str = "Ask Stacks-bot";
+
+#[async_trait]
+impl Command for AskCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(COMMAND_NAME).kind(CommandType::Message)
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let Some(ResolvedTarget::Message(message)) = ctx.interaction.data.target() else {
+            return Err(BotError::bad_input("run this on a message"));
+        };
+        let bot_id = ctx.ctx.cache.current_user().id;
+        let content = message.content.trim();
+        let text = mention::strip_mention(content, bot_id).unwrap_or(content);
+        if text.is_empty() {
+            return Ok(CommandResponse::text(mention::help_text()));
+        }
+
+        let interaction = ctx.interaction;
+        let timezone =
+            timefmt::preferred_timezone(ctx.store, interaction.guild_id, interaction.user.id).await;
+        let response = mention::handle(
+            text,
+            &ctx.ctx.http,
+            interaction.channel_id,
+            ctx.finance,
+            ctx.store,
+            Invoker::from_member(interaction.guild_id, interaction.member.as_deref()).as_ref(),
+            timezone,
+            &ctx.config.current().images,
+        )
+        .await?;
+        let mut reply = CommandResponse::text(response.content);
+        for attachment in response.attachments {
+            reply = reply.with_attachment(attachment);
+        }
+        Ok(reply)
+    }
+}
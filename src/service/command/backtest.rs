@@ -0,0 +1,233 @@
+//! `/backtest ticker strategy [period]`: a simple strategy run over past daily closes, with its
+//! return, drawdown and win rate against buy and hold and an equity-curve chart.
+
+use chrono::Duration;
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::backtest::{self, Backtest, Period, Strategy};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::quickchart;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub const COMMAND_NAME: &str = "backtest";
+/// Most recent trades listed under the summary.
+const RECENT_TRADES: usize = 5;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("Test a simple strategy on past prices against buy and hold")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "ticker",
+                "Ticker symbol, e.g., AAPL",
+            )
+            .required(true),
+        )
+        .add_option(
+            Strategy::ALL.into_iter().fold(
+                CreateCommandOption::new(CommandOptionType::String, "strategy", "Strategy to test")
+                    .required(true),
+                |opt, s| opt.add_string_choice(s.label(), s.name()),
+            ),
+        )
+        .add_option(Period::ALL.into_iter().fold(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "How far back to test (default 1 year)",
+            ),
+            |opt, p| opt.add_string_choice(p.label(), p.name()),
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let strategy = get_str_opt(command, "strategy")
+        .and_then(Strategy::from_name)
+        .ok_or_else(|| BotError::bad_input("choose a strategy from the list"))?;
+    let period = match get_str_opt(command, "period") {
+        Some(name) => Period::from_name(name)
+            .ok_or_else(|| BotError::bad_input(format!("unknown period {name}")))?,
+        None => Period::OneYear,
+    };
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let history = finance
+        .daily_closes(&symbol, period.history_range())
+        .await?;
+    let last = history
+        .last()
+        .ok_or_else(|| BotError::not_found(format!("no price history for {symbol}")))?;
+    let start = last.date - Duration::days(period.days());
+    let result = backtest::run(strategy, &history, start).ok_or_else(|| {
+        BotError::not_found(format!("not enough price history to backtest {symbol}"))
+    })?;
+
+    let mut response = CommandResponse::text(backtest_text(&symbol, &result));
+    match quickchart::render(equity_chart(&symbol, &result), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "backtest.png"))
+        }
+        Err(err) => {
+            warn!("backtest chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Return, drawdown and win rate against buy and hold, then the latest trades.
+pub fn backtest_text(symbol: &str, result: &Backtest) -> String {
+    let percent = |v: f64| format!("{:+.2}%", v * 100.0);
+    let mut lines = vec![
+        format!(
+            "🧪 **{symbol} backtest** · {} · {} – {}",
+            result.strategy.label(),
+            result.start().format("%b %-d, %Y"),
+            result.end().format("%b %-d, %Y")
+        ),
+        format!(
+            "Total return **{}** (buy and hold {})",
+            percent(result.total_return()),
+            percent(result.benchmark_return())
+        ),
+        format!(
+            "Max drawdown **{}** (buy and hold {})",
+            percent(result.max_drawdown()),
+            percent(result.benchmark_drawdown())
+        ),
+    ];
+    let wins = result
+        .trades
+        .iter()
+        .filter(|t| t.return_fraction() > 0.0)
+        .count();
+    lines.push(match result.win_rate() {
+        Some(rate) => format!(
+            "Win rate **{:.0}%** ({wins} of {} trades) · in the market {:.0}% of sessions",
+            rate * 100.0,
+            result.trades.len(),
+            result.exposure() * 100.0
+        ),
+        None => "No trades: the strategy never signalled a buy in this period.".to_string(),
+    });
+
+    if !result.trades.is_empty() {
+        lines.push(String::new());
+        lines.push("**Recent trades**".to_string());
+        let skip = result.trades.len().saturating_sub(RECENT_TRADES);
+        for trade in &result.trades[skip..] {
+            let exit = if trade.open {
+                "open".to_string()
+            } else {
+                trade.exit.format("%b %-d, %Y").to_string()
+            };
+            lines.push(format!(
+                "• {} → {exit}: {} → {} ({})",
+                trade.entry.format("%b %-d, %Y"),
+                format::per_share(trade.entry_price),
+                format::per_share(trade.exit_price),
+                percent(trade.return_fraction())
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(
+        "_Trades at the signal's close, long only, without costs or dividends. Past results \
+         don't predict future ones._"
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+/// Growth of 100 for the strategy and for buy and hold.
+fn equity_chart(symbol: &str, result: &Backtest) -> Value {
+    let points = |curve: &[(chrono::NaiveDate, f64)]| -> Vec<Value> {
+        curve
+            .iter()
+            .map(|(date, v)| {
+                json!({
+                    "x": date.format("%Y-%m-%d").to_string(),
+                    "y": (v * 10_000.0).round() / 100.0
+                })
+            })
+            .collect()
+    };
+    let unit = if result.end() - result.start() > Duration::days(800) {
+        "quarter"
+    } else {
+        "month"
+    };
+
+    json!({
+        "type": "line",
+        "data": {
+            "datasets": [
+                {
+                    "label": result.strategy.label(),
+                    "data": points(&result.equity),
+                    "borderColor": "#2196f3",
+                    "pointRadius": 0,
+                    "fill": false
+                },
+                {
+                    "label": "Buy and hold",
+                    "data": points(&result.benchmark),
+                    "borderColor": "#9e9e9e",
+                    "borderDash": [6, 4],
+                    "pointRadius": 0,
+                    "fill": false
+                }
+            ]
+        },
+        "options": {
+            "plugins": {
+                "legend": { "position": "bottom" },
+                "title": {
+                    "display": true,
+                    "text": format!("{symbol} · {} (growth of 100)", result.strategy.label())
+                }
+            },
+            "scales": {
+                "x": { "type": "time", "time": { "unit": unit } },
+                "y": { "title": { "display": true, "text": "Value" } }
+            }
+        }
+    })
+}
+
+pub struct BacktestCommand;
+
+#[async_trait]
+impl Command for BacktestCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
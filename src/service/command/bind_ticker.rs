@@ -0,0 +1,88 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, Permissions,
+    ResolvedValue,
+};
+use serenity::async_trait;
+
+use crate::service::finance::FinanceService;
+use crate::service::i18n::{Locale, Text};
+use crate::service::store::StateStore;
+
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+use super::symbol;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("bind-ticker")
+        .description("Default symbol for commands in this channel that leave out the ticker")
+        .default_member_permissions(Permissions::MANAGE_CHANNELS)
+        .dm_permission(false)
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., SPY; leave out to unbind the channel",
+        ))
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    locale: Locale,
+) -> BotResult<String> {
+    let store = store.ok_or_else(|| {
+        BotError::not_found("channel tickers are unavailable; no state store is configured")
+    })?;
+    let options = command.data.options();
+    let input = options
+        .iter()
+        .find(|o| o.name == "ticker")
+        .and_then(|o| match o.value {
+            ResolvedValue::String(s) => Some(s),
+            _ => None,
+        });
+    let ticker = match input {
+        Some(input) => Some(symbol::resolve(finance, input).await?),
+        None => None,
+    };
+
+    store
+        .set_channel_ticker(command.channel_id.get(), ticker.as_deref())
+        .await
+        .map_err(|e| BotError::internal(format!("failed to save channel ticker: {e}")))?;
+    Ok(match ticker {
+        Some(ticker) => format!(
+            "{} **{ticker}** (<#{}>).",
+            Text::ChannelTickerSet.get(locale),
+            command.channel_id
+        ),
+        None => Text::ChannelTickerCleared.get(locale).to_string(),
+    })
+}
+
+pub struct BindTickerCommand;
+
+#[async_trait]
+impl Command for BindTickerCommand {
+    fn name(&self) -> &str {
+        "bind-ticker"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    fn audited(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store, ctx.locale)
+            .await
+            .map(CommandResponse::text)
+    }
+}
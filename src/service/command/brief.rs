@@ -0,0 +1,212 @@
+//! `/brief set|show|off`: opt in to a morning brief of your tickers, sent by DM at a chosen
+//! local time on weekdays by `automation::morning_brief`.
+
+use chrono::{NaiveTime, Utc};
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, ResolvedOption,
+    ResolvedValue,
+};
+use serenity::async_trait;
+
+use crate::service::automation::morning_brief::{self, brief_timezone};
+use crate::service::caching::collections::morning_briefs::MorningBrief;
+use crate::service::finance::FinanceService;
+use crate::service::store::StateStore;
+use crate::service::timefmt;
+
+use super::args::get_str;
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+use super::news_multi::parse_tickers;
+use super::symbol;
+
+pub const COMMAND_NAME: &str = "brief";
+/// Concurrent ticker lookups when setting a brief.
+const LOOKUPS: usize = 4;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("A morning brief of your tickers, sent by DM on weekdays")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "set",
+                "Start or change your brief",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "tickers",
+                    "Up to 10 tickers separated by commas or spaces, e.g., AAPL, MSFT, NVDA",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "time",
+                    "Local time to send it, e.g., 07:30 or 7:30am",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "show",
+            "Your brief settings and what it shows right now",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "off",
+            "Stop your brief",
+        ))
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+) -> BotResult<String> {
+    let store = store.ok_or_else(|| {
+        BotError::not_found("morning briefs are unavailable; no state store is configured")
+    })?;
+    let user_id = command.user.id.get();
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(args),
+        ..
+    }) = options.first()
+    else {
+        return Err(BotError::bad_input("choose a /brief subcommand"));
+    };
+
+    match *name {
+        "set" => {
+            let input = get_str(args, "tickers")
+                .ok_or_else(|| BotError::bad_input("tickers is required"))?;
+            let time =
+                get_str(args, "time").ok_or_else(|| BotError::bad_input("time is required"))?;
+            let time = parse_time(time).ok_or_else(|| {
+                BotError::bad_input(format!("time must look like 07:30 or 7:30am, got '{time}'"))
+            })?;
+            let symbols: Vec<String> = stream::iter(parse_tickers(input)?)
+                .map(|ticker| async move { symbol::resolve(finance, &ticker).await })
+                .buffered(LOOKUPS)
+                .try_collect()
+                .await?;
+            let timezone =
+                timefmt::preferred_timezone(Some(store), command.guild_id, command.user.id)
+                    .await
+                    .unwrap_or(New_York);
+
+            // Set after today's time, the first brief goes out on the next weekday.
+            let now = Utc::now().with_timezone(&timezone);
+            let brief = MorningBrief {
+                user_id,
+                symbols,
+                time,
+                timezone: timezone.name().to_string(),
+                last_sent: (now.time() >= time).then(|| now.date_naive()),
+            };
+            store
+                .save_morning_brief(&brief)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save morning brief: {e}")))?;
+            Ok(format!(
+                "☀️ {}\nThe brief can't reach you if your DMs are closed to the bot. Times follow \
+                 your `/timezone`; run `/brief set` again after changing it.",
+                settings_line(&brief)
+            ))
+        }
+        "show" => {
+            let brief = store
+                .morning_brief(user_id)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to load morning brief: {e}")))?
+                .ok_or_else(|| {
+                    BotError::not_found("you have no morning brief; start one with `/brief set`")
+                })?;
+            let today = Utc::now()
+                .with_timezone(&brief_timezone(&brief))
+                .date_naive();
+            let preview = morning_brief::assemble(finance, &brief.symbols, today)
+                .await
+                .map_err(BotError::internal)?;
+            Ok(format!("{}\n\n{preview}", settings_line(&brief)))
+        }
+        "off" => {
+            let removed = store
+                .delete_morning_brief(user_id)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to remove morning brief: {e}")))?;
+            if !removed {
+                return Err(BotError::not_found("you have no morning brief"));
+            }
+            Ok("🔕 Morning brief stopped.".to_string())
+        }
+        other => Err(BotError::bad_input(format!("unknown subcommand {other}"))),
+    }
+}
+
+/// `07:30`, `7:30`, `7:30am`, `7 AM` or `19:05`.
+pub fn parse_time(input: &str) -> Option<NaiveTime> {
+    let compact: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    if let Ok(time) = NaiveTime::parse_from_str(&compact, "%H:%M") {
+        return Some(time);
+    }
+    let (clock, pm) = match compact.strip_suffix("am") {
+        Some(clock) => (clock, false),
+        None => (compact.strip_suffix("pm")?, true),
+    };
+    let (hour, minute) = clock.split_once(':').unwrap_or((clock, "0"));
+    let hour: u32 = hour.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    NaiveTime::from_hms_opt(hour % 12 + if pm { 12 } else { 0 }, minute, 0)
+}
+
+/// `Morning brief: AAPL, MSFT at 07:30 (America/New_York) on weekdays, by DM.`
+pub fn settings_line(brief: &MorningBrief) -> String {
+    format!(
+        "**Morning brief:** {} at {} ({}) on weekdays, by DM.",
+        brief.symbols.join(", "),
+        brief.time.format("%H:%M"),
+        brief.timezone
+    )
+}
+
+pub struct BriefCommand;
+
+#[async_trait]
+impl Command for BriefCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    fn user_installable(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store)
+            .await
+            .map(CommandResponse::text)
+    }
+}
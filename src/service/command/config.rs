@@ -0,0 +1,456 @@
+use chrono::Utc;
+use serenity::all::{
+    ChannelType, CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption,
+    Permissions, ResolvedOption, ResolvedValue,
+};
+use serenity::async_trait;
+
+use crate::service::i18n::{Locale, Text};
+use crate::service::routing::Route;
+use crate::service::store::StateStore;
+use crate::service::symbol_filter::SymbolFilter;
+use crate::service::timefmt;
+
+use super::args::get_str;
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+
+pub const COMMAND_NAME: &str = "config";
+
+/// Discord allows at most 25 choices per option.
+const MAX_CHOICES: usize = 25;
+
+pub fn register_command(commands: &[String]) -> CreateCommand {
+    let command_option = || {
+        let option = CreateCommandOption::new(
+            CommandOptionType::String,
+            "command",
+            "Slash command to restrict",
+        )
+        .required(true);
+        commands.iter().take(MAX_CHOICES).fold(option, |opt, name| {
+            opt.add_string_choice(format!("/{name}"), name)
+        })
+    };
+
+    CreateCommand::new(COMMAND_NAME)
+        .description("Server settings for the bot (admins only)")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .dm_permission(false)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "restrict",
+                "Only let members with this role run a command",
+            )
+            .add_sub_option(command_option())
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Role, "role", "Role to allow")
+                    .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "unrestrict",
+                "Remove a role from a command's allowed roles, or all roles if none is given",
+            )
+            .add_sub_option(command_option())
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::Role,
+                "role",
+                "Role to remove",
+            )),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "permissions",
+            "List restricted commands",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "audit-channel",
+                "Log admin actions, alerts and automation failures to a channel",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "Channel for the audit log; leave out to turn it off",
+                )
+                .channel_types(vec![ChannelType::Text]),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "topic-channel",
+                "Keep a channel's topic set to index levels and the VIX",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "Channel whose topic to update; leave out to stop",
+                )
+                .channel_types(vec![ChannelType::Text]),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "language",
+                "Language the bot answers in on this server",
+            )
+            .add_sub_option(
+                Locale::ALL.into_iter().fold(
+                    CreateCommandOption::new(CommandOptionType::String, "language", "Language")
+                        .required(true),
+                    |opt, locale| opt.add_string_choice(locale.label(), locale.code()),
+                ),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "timezone",
+                "Timezone for times shown as plain text, such as forum post titles",
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "timezone",
+                "IANA name, e.g. Europe/London; leave out for US Eastern",
+            )),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "symbols",
+                "Limit which symbols earnings and options posts show; leave all out to show all",
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "allow",
+                "Only show these tickers or indexes, e.g. sp500, MSFT",
+            ))
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "deny",
+                "Never show these tickers or indexes",
+            ))
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Number,
+                    "min-market-cap",
+                    "Hide companies below this market cap, in billions of USD",
+                )
+                .min_number_value(0.0),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "routes",
+                "Send a category of automation posts to a channel; leave all out to list routes",
+            )
+            .add_sub_option(Route::ALL.into_iter().fold(
+                CreateCommandOption::new(CommandOptionType::String, "route", "Category of posts"),
+                |opt, route| opt.add_string_choice(route.label(), route.name()),
+            ))
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "Channel for the category; leave out to clear its route",
+                )
+                .channel_types(vec![ChannelType::Text]),
+            ),
+        )
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    store: Option<&dyn StateStore>,
+    locale: Locale,
+) -> BotResult<String> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| BotError::bad_input("/config only works in a server"))?
+        .get();
+    let store = store.ok_or_else(|| {
+        BotError::not_found("server settings are unavailable; no state store is configured")
+    })?;
+
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name: subcommand,
+        value: ResolvedValue::SubCommand(args),
+        ..
+    }) = options.first()
+    else {
+        return Err(BotError::bad_input(
+            "choose restrict, unrestrict, permissions, audit-channel, topic-channel, language, timezone, symbols or routes",
+        ));
+    };
+
+    match *subcommand {
+        "restrict" => {
+            let target = get_str(args, "command")
+                .ok_or_else(|| BotError::bad_input("command is required"))?;
+            let role =
+                get_role(args, "role").ok_or_else(|| BotError::bad_input("role is required"))?;
+            let mut roles = load_roles(store, guild_id, target).await?;
+            if !roles.contains(&role) {
+                roles.push(role);
+            }
+            save_roles(store, guild_id, target, &roles).await?;
+            Ok(format!(
+                "🔒 /{target} {} {}",
+                Text::LimitedTo.get(locale),
+                format_roles(&roles)
+            ))
+        }
+        "unrestrict" => {
+            let target = get_str(args, "command")
+                .ok_or_else(|| BotError::bad_input("command is required"))?;
+            let mut roles = load_roles(store, guild_id, target).await?;
+            match get_role(args, "role") {
+                Some(role) => roles.retain(|r| *r != role),
+                None => roles.clear(),
+            }
+            save_roles(store, guild_id, target, &roles).await?;
+            if roles.is_empty() {
+                Ok(format!("🔓 /{target} {}", Text::OpenToEveryone.get(locale)))
+            } else {
+                Ok(format!(
+                    "🔒 /{target} {} {}",
+                    Text::LimitedTo.get(locale),
+                    format_roles(&roles)
+                ))
+            }
+        }
+        "permissions" => {
+            let all = store
+                .all_command_roles(guild_id)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to load command roles: {e}")))?;
+            if all.is_empty() {
+                return Ok(Text::NoRestrictions.get(locale).to_string());
+            }
+            let mut lines = vec![Text::RestrictionsHeader.get(locale).to_string()];
+            for (name, roles) in all {
+                lines.push(format!("• /{name} — {}", format_roles(&roles)));
+            }
+            Ok(lines.join("\n"))
+        }
+        "audit-channel" => {
+            let channel = get_channel(args, "channel");
+            store
+                .set_audit_channel(guild_id, channel)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save audit channel: {e}")))?;
+            Ok(match channel {
+                Some(id) => format!("{} <#{id}>.", Text::AuditChannelSet.get(locale)),
+                None => Text::AuditChannelOff.get(locale).to_string(),
+            })
+        }
+        "topic-channel" => {
+            let channel = get_channel(args, "channel");
+            store
+                .set_topic_channel(guild_id, channel)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save topic channel: {e}")))?;
+            Ok(match channel {
+                Some(id) => format!("{} <#{id}>.", Text::TopicChannelSet.get(locale)),
+                None => Text::TopicChannelOff.get(locale).to_string(),
+            })
+        }
+        "language" => {
+            let chosen = get_str(args, "language")
+                .and_then(Locale::from_code)
+                .ok_or_else(|| BotError::bad_input("choose a language from the list"))?;
+            // English is the default, so choosing it clears the setting.
+            let code = (chosen != Locale::default()).then(|| chosen.code());
+            store
+                .set_guild_locale(guild_id, code)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save language: {e}")))?;
+            Ok(format!(
+                "{} {}.",
+                Text::LanguageSet.get(chosen),
+                chosen.label()
+            ))
+        }
+        "timezone" => {
+            let timezone = get_str(args, "timezone")
+                .map(|name| {
+                    timefmt::parse_timezone(name).ok_or_else(|| {
+                        BotError::bad_input(format!(
+                            "unknown timezone {name}; use an IANA name such as Europe/London"
+                        ))
+                    })
+                })
+                .transpose()?;
+            store
+                .set_guild_timezone(guild_id, timezone.map(|tz| tz.name()))
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save timezone: {e}")))?;
+            let tz = timezone.unwrap_or(timefmt::DEFAULT_TIMEZONE);
+            Ok(format!(
+                "{} {} ({}).",
+                Text::GuildTimezoneSet.get(locale),
+                tz.name(),
+                timefmt::plain(&Utc::now(), tz)
+            ))
+        }
+        "symbols" => {
+            let list = |name| {
+                get_str(args, name)
+                    .map(SymbolFilter::parse_list)
+                    .transpose()
+                    .map_err(BotError::bad_input)
+                    .map(Option::unwrap_or_default)
+            };
+            let filter = SymbolFilter {
+                allow: list("allow")?,
+                deny: list("deny")?,
+                min_market_cap: get_number(args, "min-market-cap").map(|b| b * 1e9),
+            };
+            let filter = (!filter.is_empty()).then_some(filter);
+            store
+                .set_guild_symbol_filter(guild_id, filter.as_ref())
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save symbol filter: {e}")))?;
+            Ok(match filter {
+                Some(filter) => format!(
+                    "{} {}.",
+                    Text::SymbolFilterSet.get(locale),
+                    filter.describe()
+                ),
+                None => Text::SymbolFilterCleared.get(locale).to_string(),
+            })
+        }
+        "routes" => {
+            let channel = get_channel(args, "channel");
+            let Some(name) = get_str(args, "route") else {
+                if channel.is_some() {
+                    return Err(BotError::bad_input("choose which route the channel is for"));
+                }
+                let routes = store
+                    .guild_routes(guild_id)
+                    .await
+                    .map_err(|e| BotError::internal(format!("failed to load routes: {e}")))?;
+                if routes.is_empty() {
+                    return Ok(Text::NoRoutes.get(locale).to_string());
+                }
+                let mut lines = vec![Text::RoutesHeader.get(locale).to_string()];
+                for (route, channel) in routes {
+                    lines.push(format!("• `{route}` — <#{channel}>"));
+                }
+                return Ok(lines.join("\n"));
+            };
+            let route = Route::from_name(name)
+                .ok_or_else(|| BotError::bad_input(format!("unknown route {name}")))?;
+            store
+                .set_guild_route(guild_id, route.name(), channel)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save route: {e}")))?;
+            Ok(match channel {
+                Some(id) => format!("📬 `{name}` {} <#{id}>.", Text::RouteSet.get(locale)),
+                None => format!("📬 `{name}` {}", Text::RouteCleared.get(locale)),
+            })
+        }
+        other => Err(BotError::bad_input(format!("unknown subcommand {other}"))),
+    }
+}
+
+async fn load_roles(store: &dyn StateStore, guild_id: u64, command: &str) -> BotResult<Vec<u64>> {
+    store
+        .command_roles(guild_id, command)
+        .await
+        .map_err(|e| BotError::internal(format!("failed to load roles for /{command}: {e}")))
+}
+
+async fn save_roles(
+    store: &dyn StateStore,
+    guild_id: u64,
+    command: &str,
+    roles: &[u64],
+) -> BotResult<()> {
+    store
+        .set_command_roles(guild_id, command, roles)
+        .await
+        .map_err(|e| BotError::internal(format!("failed to save roles for /{command}: {e}")))
+}
+
+fn format_roles(roles: &[u64]) -> String {
+    roles
+        .iter()
+        .map(|id| format!("<@&{id}>"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn get_role(args: &[ResolvedOption<'_>], name: &str) -> Option<u64> {
+    args.iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match &o.value {
+            ResolvedValue::Role(role) => Some(role.id.get()),
+            _ => None,
+        })
+}
+
+fn get_channel(args: &[ResolvedOption<'_>], name: &str) -> Option<u64> {
+    args.iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match &o.value {
+            ResolvedValue::Channel(channel) => Some(channel.id.get()),
+            _ => None,
+        })
+}
+
+fn get_number(args: &[ResolvedOption<'_>], name: &str) -> Option<f64> {
+    args.iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            ResolvedValue::Number(n) => Some(n),
+            _ => None,
+        })
+}
+
+/// `/config`; holds the command names offered as `command:` choices.
+pub struct ConfigCommand {
+    commands: Vec<String>,
+}
+
+impl ConfigCommand {
+    pub fn new(commands: Vec<String>) -> Self {
+        Self { commands }
+    }
+}
+
+#[async_trait]
+impl Command for ConfigCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command(&self.commands)
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    fn audited(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.store, ctx.locale)
+            .await
+            .map(CommandResponse::text)
+    }
+}
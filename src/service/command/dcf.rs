@@ -0,0 +1,248 @@
+//! `/dcf ticker`: a simple discounted cash flow estimate of intrinsic value per share, from
+//! trailing free cash flow grown for a few years and then at a terminal rate.
+
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::models::{Frequency, StatementType};
+use crate::service::finance::dcf::{
+    default_growth, fcf_history, value_range, DcfInputs, DcfRange, DEFAULT_DISCOUNT,
+    DEFAULT_GROWTH, DEFAULT_TERMINAL_GROWTH, DEFAULT_YEARS, DISCOUNT_SPREAD, GROWTH_SPREAD,
+};
+use crate::service::finance::exchange::Exchange;
+use crate::service::finance::fundamentals::reshape_timeseries_to_financial_statements;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::growth;
+
+use super::args::{get_f64_opt, get_i64_opt, get_str_opt};
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Enough annual periods for a 3-year FCF CAGR.
+const ANNUAL_YEARS: i64 = 5;
+const TTM_YEARS: i64 = 2;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("dcf")
+        .description("Discounted cash flow estimate of intrinsic value per share")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Number,
+                "growth",
+                "FCF growth per year in %, default the 3-year FCF CAGR (0-15%) or 5%",
+            )
+            .min_number_value(-50.0)
+            .max_number_value(100.0),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Number,
+                "discount",
+                "Discount rate in %, default 10",
+            )
+            .min_number_value(1.0)
+            .max_number_value(50.0),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Number,
+                "terminal",
+                "Growth after the projection in %, default 2.5",
+            )
+            .min_number_value(-5.0)
+            .max_number_value(10.0),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "years",
+                "Years of projected growth, default 5",
+            )
+            .min_int_value(1)
+            .max_int_value(20),
+        )
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let discount = get_f64_opt(command, "discount").map_or(DEFAULT_DISCOUNT, |v| v / 100.0);
+    let terminal_growth =
+        get_f64_opt(command, "terminal").map_or(DEFAULT_TERMINAL_GROWTH, |v| v / 100.0);
+    if discount <= terminal_growth {
+        return Err(BotError::bad_input(
+            "discount must be above terminal growth, or the terminal value is unbounded",
+        ));
+    }
+    let years = get_i64_opt(command, "years").map_or(DEFAULT_YEARS, |v| v.clamp(1, 20) as u32);
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let (ttm, annual, summary) = tokio::join!(
+        finance.get_fundamentals_raw(&symbol, StatementType::CashFlow, Frequency::Ttm, TTM_YEARS),
+        finance.get_fundamentals_raw(
+            &symbol,
+            StatementType::CashFlow,
+            Frequency::Annual,
+            ANNUAL_YEARS
+        ),
+        finance.get_quote_summary(&symbol, &["price", "defaultKeyStatistics"]),
+    );
+    let summary = summary?;
+    // Without a TTM series the latest fiscal year is the base, as for companies that do not
+    // report one.
+    let ttm = match ttm {
+        Ok(raw) => {
+            fcf_history(
+                &reshape_timeseries_to_financial_statements(&raw),
+                Frequency::Ttm,
+            )
+            .0
+        }
+        Err(e) => {
+            warn!("dcf: TTM free cash flow for {symbol} failed: {e}");
+            Vec::new()
+        }
+    };
+    let (annual, currency) = fcf_history(
+        &reshape_timeseries_to_financial_statements(&annual?),
+        Frequency::Annual,
+    );
+
+    // The trailing twelve months are more current; the latest fiscal year stands in for
+    // companies without four consecutive quarters reported.
+    let (base_label, base_date, base_fcf) = match (ttm.last(), annual.last()) {
+        (Some(&(date, fcf)), _) => ("TTM", date, fcf),
+        (None, Some(&(date, fcf))) => ("FY", date, fcf),
+        (None, None) => {
+            return Err(BotError::not_found(format!(
+                "no free cash flow data for {symbol}"
+            )))
+        }
+    };
+    let reported = currency.unwrap_or_else(|| Exchange::for_symbol(&symbol).currency.to_string());
+    if base_fcf <= 0.0 {
+        return Err(BotError::not_found(format!(
+            "a DCF needs positive free cash flow; {symbol} reported {} ({base_label} {})",
+            format::currency_in(base_fcf, &reported),
+            base_date.format("%b '%y")
+        )));
+    }
+
+    let price = summary.price.unwrap_or_default();
+    let stats = summary.default_key_statistics.unwrap_or_default();
+    let spot = price
+        .regular_market_price
+        .ok_or_else(|| BotError::not_found(format!("no price for {symbol}")))?;
+    let shares = stats
+        .shares_outstanding
+        .filter(|s| *s > 0.0)
+        .ok_or_else(|| BotError::not_found(format!("no share count for {symbol}")))?;
+    let quote_currency = price.currency.unwrap_or_else(|| reported.clone());
+    // ADRs report in the home currency and trade in dollars.
+    let fx = finance.conversion(&reported, &quote_currency).await?;
+
+    let growth = match get_f64_opt(command, "growth") {
+        Some(percent) => percent / 100.0,
+        None => annual
+            .last()
+            .map_or(DEFAULT_GROWTH, |(at, _)| default_growth(&annual, *at)),
+    };
+    let inputs = DcfInputs {
+        fcf: fx.apply(base_fcf),
+        growth,
+        discount,
+        terminal_growth,
+        years,
+    };
+    let range = value_range(&inputs, shares)
+        .ok_or_else(|| BotError::bad_input("those rates give no finite value"))?;
+
+    let mut text = dcf_text(
+        &symbol,
+        &inputs,
+        &range,
+        spot,
+        &quote_currency,
+        &format!("{base_label} {}", base_date.format("%b '%y")),
+    );
+    if let Some(note) = fx.note() {
+        text.push_str(&format!("\n_FCF {note}_"));
+    }
+    Ok(CommandResponse::text(text))
+}
+
+/// The range against the price, then the inputs that produced it.
+pub fn dcf_text(
+    symbol: &str,
+    inputs: &DcfInputs,
+    range: &DcfRange,
+    price: f64,
+    currency: &str,
+    base_period: &str,
+) -> String {
+    let per_share = |v: f64| format::per_share_in(v, currency);
+    let percent = |v: f64| format!("{:.1}%", v * 100.0);
+    let mut lines = vec![format!("🧮 **{symbol} DCF**")];
+    lines.push(format!(
+        "Intrinsic value **{} – {}** per share (base {}) vs price {}",
+        per_share(range.low),
+        per_share(range.high),
+        per_share(range.base),
+        per_share(price)
+    ));
+    if let Some(upside) = growth::change(price, range.base) {
+        let verdict = if upside >= 0.0 { "upside" } else { "downside" };
+        lines.push(format!("Base case {:+.1}% {verdict}", upside * 100.0));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "• FCF base ({base_period}): {}",
+        format::currency_in(inputs.fcf, currency)
+    ));
+    lines.push(format!(
+        "• Growth {} a year for {} years, then {} terminal",
+        percent(inputs.growth),
+        inputs.years,
+        percent(inputs.terminal_growth)
+    ));
+    lines.push(format!("• Discount rate {}", percent(inputs.discount)));
+    lines.push(format!(
+        "• Terminal value is {:.0}% of the base case",
+        range.terminal_share * 100.0
+    ));
+    lines.push(format!(
+        "Range: growth ±{:.0} pts with discount ∓{:.0} pt",
+        GROWTH_SPREAD * 100.0,
+        DISCOUNT_SPREAD * 100.0
+    ));
+    lines.join("\n")
+}
+
+pub struct DcfCommand;
+
+#[async_trait]
+impl Command for DcfCommand {
+    fn name(&self) -> &str {
+        "dcf"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -1,13 +1,65 @@
-use chrono::{Datelike, Duration, Utc, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
 use chrono_tz::America::New_York;
-use serenity::all::{ChannelId, CommandInteraction, CreateCommand, Http};
-use std::time::Duration as StdDuration;
+use futures_util::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
+use serenity::all::{
+    ChannelId, CommandInteraction, CommandOptionType, ComponentInteractionDataKind,
+    CreateActionRow, CreateAttachment, CreateCommand, CreateCommandOption, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption, Http,
+};
+use serenity::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use tokio::sync::Mutex;
 use tokio::time::timeout;
 use tracing::{error, info, warn};
 
-use crate::models::EarningsEvent;
+use crate::models::{EarningsEvent, EarningsRange};
 use crate::service::automation::earnings;
+use crate::service::config::ImageConfig;
+use crate::service::finance::constituents::Index;
 use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::render::EncodedImage;
+use crate::service::store::StateStore;
+use crate::service::symbol_filter::{self, ResolvedFilter};
+use crate::service::timefmt::{self, TimestampStyle};
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, ComponentContext};
+use super::universe;
+
+/// `custom_id` prefix of the `/weekly-earnings` day picker; the week's Monday follows it, then
+/// `:<universe>` when one was picked.
+const DAY_PICKER_PREFIX: &str = "weekly-earnings:day:";
+/// Picker value that switches back to the calendar image.
+const WHOLE_WEEK: &str = "week";
+/// Names listed in a day view, to stay within Discord's 2000 character message limit.
+const MAX_DAY_ENTRIES: usize = 20;
+/// Concurrent option chain lookups for a day view.
+const DAY_LOOKUPS: usize = 6;
+/// The range request (15 s), the day-by-day retry after it times out (another 15 s) and the
+/// importance lookups for names the calendar sent unscored.
+const WEEK_FETCH_TIMEOUT: StdDuration = StdDuration::from_secs(60);
+/// How long a fetched week and the implied moves shown for it are reused, so moving through
+/// the day picker doesn't refetch them.
+const PICKER_TTL: StdDuration = StdDuration::from_secs(10 * 60);
+
+/// When a week was fetched, and its events before any filter.
+type FetchedWeek = (Instant, Arc<EarningsRange>);
+
+/// Weeks fetched recently, by first and last day.
+static WEEKS: Lazy<Mutex<HashMap<(NaiveDate, NaiveDate), FetchedWeek>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// A symbol and its report day.
+type Report = (String, NaiveDate);
+
+/// Implied moves looked up for day views, with when they were looked up. Failed lookups are not
+/// kept, so the next pick retries them.
+static IMPLIED_MOVES: Lazy<Mutex<HashMap<Report, (Instant, f64)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 fn week_range_mon_fri(
     weekday: Weekday,
@@ -24,14 +76,100 @@ fn week_range_mon_fri(
     (monday, friday)
 }
 
+/// The Monday–Friday range `/weekly-earnings` shows: the week holding `date:` if given,
+/// otherwise the current week (see [`week_range_mon_fri`]) moved by `week:` (`last`, `this`
+/// or `next`).
+pub fn requested_week(
+    week: Option<&str>,
+    date: Option<&str>,
+    today: NaiveDate,
+) -> BotResult<(NaiveDate, NaiveDate)> {
+    match (week, date) {
+        (Some(_), Some(_)) => Err(BotError::bad_input(
+            "pick either `week` or `date`, not both",
+        )),
+        (_, Some(raw)) => {
+            let day = NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+                .map_err(|_| BotError::bad_input(format!("{raw} is not a date like 2026-10-20")))?;
+            Ok(week_range_mon_fri(day.weekday(), day))
+        }
+        (week, None) => {
+            let offset = match week.unwrap_or("this") {
+                "last" => -1,
+                "this" => 0,
+                "next" => 1,
+                other => return Err(BotError::bad_input(format!("unknown week `{other}`"))),
+            };
+            let (monday, friday) = week_range_mon_fri(today.weekday(), today);
+            let shift = Duration::weeks(offset);
+            Ok((monday + shift, friday + shift))
+        }
+    }
+}
+
+/// `Last week`, `Next week`, `In 3 weeks` or `2 weeks ago` for the week starting `monday`,
+/// relative to the current week on `today`; `None` for the current week itself.
+pub fn week_label(monday: NaiveDate, today: NaiveDate) -> Option<String> {
+    let (current, _) = week_range_mon_fri(today.weekday(), today);
+    match (monday - current).num_days().div_euclid(7) {
+        0 => None,
+        -1 => Some("Last week".to_string()),
+        1 => Some("Next week".to_string()),
+        n if n < 0 => Some(format!("{} weeks ago", -n)),
+        n => Some(format!("In {n} weeks")),
+    }
+}
+
 /// Response payload for the /earnings command.
 pub struct EarningsResponse {
     pub content: String,
-    pub image: Option<Vec<u8>>,
+    pub image: Option<EncodedImage>,
+    /// Every event as CSV, when the image had to leave some out.
+    pub full_list: Option<String>,
+    /// Day picker for the slash command; mention replies can't take components.
+    pub picker: Option<CreateActionRow>,
+}
+
+impl EarningsResponse {
+    pub fn attachments(&self) -> Vec<CreateAttachment> {
+        let image = self.image.as_ref().map(|image| {
+            CreateAttachment::bytes(image.bytes.as_slice(), image.file_name("earnings-calendar"))
+        });
+        let list = self
+            .full_list
+            .as_ref()
+            .map(|list| CreateAttachment::bytes(list.as_str(), earnings::FULL_LIST_FILENAME));
+        image.into_iter().chain(list).collect()
+    }
+
+    fn into_command_response(self) -> CommandResponse {
+        let attachments = self.attachments();
+        let mut out = CommandResponse::text(self.content);
+        for attachment in attachments {
+            out = out.with_attachment(attachment);
+        }
+        if let Some(picker) = self.picker {
+            out = out.with_components(picker);
+        }
+        out
+    }
 }
 
 pub fn register_weekly_command() -> CreateCommand {
-    CreateCommand::new("weekly-earnings").description("Weekly earnings calendar")
+    CreateCommand::new("weekly-earnings")
+        .description("Weekly earnings calendar")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "week", "Which week to show")
+                .add_string_choice("Last week", "last")
+                .add_string_choice("This week", "this")
+                .add_string_choice("Next week", "next"),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "date",
+            "Show the week of this day, YYYY-MM-DD",
+        ))
+        .add_option(universe::universe_option())
 }
 
 pub fn register_daily_command() -> CreateCommand {
@@ -44,34 +182,68 @@ pub fn register_after_daily_command() -> CreateCommand {
 }
 
 /// Mention helpers (text entrypoints)
-pub async fn handle_weekly_mention(finance: &FinanceService) -> Result<EarningsResponse, String> {
-    handle_weekly_plain(finance).await
+pub async fn handle_weekly_mention(
+    finance: &FinanceService,
+    images: &ImageConfig,
+) -> BotResult<EarningsResponse> {
+    handle_weekly_plain(finance, &ResolvedFilter::default(), None, images).await
 }
 
 pub async fn handle_daily_mention(
     finance: &FinanceService,
     http: &Http,
     channel_id: ChannelId,
-) -> Result<String, String> {
-    handle_daily_for_channel(finance, http, channel_id).await
+    images: &ImageConfig,
+) -> BotResult<String> {
+    handle_daily_for_channel(finance, http, channel_id, images).await
 }
 
 pub async fn handle_after_daily_mention(
     finance: &FinanceService,
     http: &Http,
     channel_id: ChannelId,
-) -> Result<String, String> {
+) -> BotResult<String> {
     handle_after_daily_for_channel(finance, http, channel_id).await
 }
 
+/// The calendar of the week picked with `week:` or `date:` (the current one by default),
+/// limited to the symbols the server's `/config symbols` allows and to the `universe:` index
+/// if one was picked.
 pub async fn handle_weekly(
-    _command: &CommandInteraction,
+    command: &CommandInteraction,
     finance: &FinanceService,
-) -> Result<EarningsResponse, String> {
-    handle_weekly_plain(finance).await
+    store: Option<&dyn StateStore>,
+    images: &ImageConfig,
+) -> BotResult<EarningsResponse> {
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let (start, end) = requested_week(
+        get_str_opt(command, "week"),
+        get_str_opt(command, "date"),
+        today,
+    )?;
+    let guild_id = command.guild_id.map(|g| g.get());
+    let index = universe::requested(command);
+    let filter = symbol_filter::guild_filter(store, finance, guild_id).await;
+    let filter = universe::restrict(finance, filter, index).await?;
+    let label = week_label(start, today);
+    week_response(
+        finance,
+        &filter,
+        index,
+        start,
+        end,
+        label.as_deref(),
+        images,
+    )
+    .await
 }
 
-pub async fn handle_weekly_plain(finance: &FinanceService) -> Result<EarningsResponse, String> {
+pub async fn handle_weekly_plain(
+    finance: &FinanceService,
+    filter: &ResolvedFilter,
+    index: Option<Index>,
+    images: &ImageConfig,
+) -> BotResult<EarningsResponse> {
     info!("Starting earnings command handler");
 
     // Compute the Monday–Friday range for the relevant week:
@@ -80,91 +252,323 @@ pub async fn handle_weekly_plain(finance: &FinanceService) -> Result<EarningsRes
     // - Sun: next week's Mon..Fri
     let now_et = Utc::now().with_timezone(&New_York);
     let (start, end) = week_range_mon_fri(now_et.weekday(), now_et.date_naive());
+    week_response(finance, filter, index, start, end, None, images).await
+}
 
-    info!("Fetching earnings from {} to {}", start, end);
-
-    // Wrap the entire fetch in a timeout
-    let events = match timeout(
-        StdDuration::from_secs(200), // 20 second total timeout
-        finance.get_earnings_range(start, end),
-    )
-    .await
-    {
-        Ok(Ok(events)) => {
-            info!("Successfully fetched {} earnings events", events.len());
-            events
-        }
-        Ok(Err(e)) => {
-            error!("Failed to fetch earnings: {}", e);
-            return Err(format!("Failed to fetch earnings: {}", e));
-        }
-        Err(_) => {
-            error!("Earnings fetch timed out after 20 seconds");
-            return Err("Request timed out. The earnings API is taking too long to respond. Please try again later.".to_string());
-        }
-    };
-
-    if events.is_empty() {
-        info!("No earnings found in the next 7 days");
+async fn week_response(
+    finance: &FinanceService,
+    filter: &ResolvedFilter,
+    index: Option<Index>,
+    start: NaiveDate,
+    end: NaiveDate,
+    label: Option<&str>,
+    images: &ImageConfig,
+) -> BotResult<EarningsResponse> {
+    let range = fetch_week(finance, filter, start, end).await?;
+    let missing_note = range
+        .missing_note()
+        .map(|note| format!("\n{note}"))
+        .unwrap_or_default();
+    if range.events.is_empty() {
+        info!("No earnings found from {start} to {end}");
         return Ok(EarningsResponse {
-            content: "No earnings within the next 7 days.".to_string(),
+            content: format!(
+                "No earnings from {} to {}{}.{missing_note}",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d"),
+                label.map(|l| format!(" ({l})")).unwrap_or_default()
+            ),
             image: None,
+            full_list: None,
+            picker: None,
         });
     }
+    let picker = Some(day_picker(start, end, index, &range, None));
 
+    let events = range.events;
     info!("Formatting output for {} events", events.len());
     let output = format_output(&events);
     let summary = format!(
-        "📊 Earnings Calendar ({} to {}) — {} events",
+        "📊 Earnings Calendar ({} to {}){}{} — {} events",
         start.format("%Y-%m-%d"),
         end.format("%Y-%m-%d"),
+        label.map(|l| format!(" · {l}")).unwrap_or_default(),
+        index
+            .map(|i| format!(" · {}", i.label()))
+            .unwrap_or_default(),
         events.len()
     );
 
-    match earnings::render_calendar_image(&events).await {
-        Ok(bytes) => Ok(EarningsResponse {
-            content: summary,
-            image: Some(bytes),
+    match earnings::render_calendar_image(finance, &events, images).await {
+        Ok(rendered) => Ok(EarningsResponse {
+            content: if rendered.full_list.is_some() {
+                format!(
+                    "{summary}; {} shown, full list in {}{missing_note}",
+                    rendered.shown,
+                    earnings::FULL_LIST_FILENAME
+                )
+            } else {
+                format!("{summary}{missing_note}")
+            },
+            image: Some(rendered.image),
+            full_list: rendered.full_list,
+            picker,
         }),
         Err(err) => {
             warn!("Falling back to text earnings calendar: {}", err);
 
-            // Discord has a 2000 character limit
-            let content = if output.len() > 1900 {
-                warn!(
-                    "Output is {} characters, truncating to fit Discord limit",
-                    output.len()
-                );
-                format!(
-                    "{}\n\n⚠️ *Message truncated - showing first {} of {} events. Use filters to see more.*\n⚠️ Image render unavailable: {}",
-                    &output[..1800],
-                    output.matches("📈").count().min(30),
-                    events.len(),
-                    err
-                )
-            } else {
-                info!(
-                    "Output is {} characters, within Discord limit",
-                    output.len()
-                );
-                format!("{}\n\n⚠️ Image render unavailable: {}", output, err)
-            };
+            // Replies over Discord's limit are split into follow-ups by the framework.
+            let content = format!(
+                "{}\n\n⚠️ Image render unavailable: {}{missing_note}",
+                output, err
+            );
 
             Ok(EarningsResponse {
                 content,
                 image: None,
+                full_list: None,
+                picker,
             })
         }
     }
 }
 
+/// The week's earnings that `filter` allows, giving up after the earnings API's time budget.
+/// A week fetched in the last [`PICKER_TTL`] is reused, along with the days it was missing.
+async fn fetch_week(
+    finance: &FinanceService,
+    filter: &ResolvedFilter,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> BotResult<EarningsRange> {
+    let fetched = match WEEKS.lock().await.get(&(start, end)) {
+        Some((fetched_at, events)) if fetched_at.elapsed() < PICKER_TTL => Some(events.clone()),
+        _ => None,
+    };
+    let week = match fetched {
+        Some(events) => events,
+        None => {
+            info!("Fetching earnings from {} to {}", start, end);
+            let events =
+                match timeout(WEEK_FETCH_TIMEOUT, finance.get_earnings_range(start, end)).await {
+                    Ok(Ok(range)) => {
+                        info!(
+                            "Successfully fetched {} earnings events",
+                            range.events.len()
+                        );
+                        Arc::new(range)
+                    }
+                    Ok(Err(e)) => {
+                        error!("Failed to fetch earnings: {}", e);
+                        return Err(e.into());
+                    }
+                    Err(_) => {
+                        error!(
+                            "Earnings fetch timed out after {} seconds",
+                            WEEK_FETCH_TIMEOUT.as_secs()
+                        );
+                        return Err(BotError::UpstreamTimeout("earnings API".to_string()));
+                    }
+                };
+            let mut weeks = WEEKS.lock().await;
+            weeks.retain(|_, (fetched_at, _)| fetched_at.elapsed() < PICKER_TTL);
+            weeks.insert((start, end), (Instant::now(), events.clone()));
+            events
+        }
+    };
+    let mut range = week.as_ref().clone();
+    filter.retain_events(&mut range.events);
+
+    Ok(range)
+}
+
+/// [`earnings::implied_move`], reused for [`PICKER_TTL`].
+async fn implied_move(finance: &FinanceService, ev: &EarningsEvent) -> Option<f64> {
+    let key = (ev.symbol.clone(), ev.date.date_naive());
+    if let Some((looked_up_at, implied_move)) = IMPLIED_MOVES.lock().await.get(&key) {
+        if looked_up_at.elapsed() < PICKER_TTL {
+            return Some(*implied_move);
+        }
+    }
+    let implied_move = earnings::implied_move(finance, ev).await?;
+    let mut moves = IMPLIED_MOVES.lock().await;
+    moves.retain(|_, (looked_up_at, _)| looked_up_at.elapsed() < PICKER_TTL);
+    moves.insert(key, (Instant::now(), implied_move));
+    Some(implied_move)
+}
+
+/// Select menu offering each trading day of the week starting `start`, plus the whole week.
+fn day_picker(
+    start: NaiveDate,
+    end: NaiveDate,
+    index: Option<Index>,
+    fetched: &EarningsRange,
+    selected: Option<NaiveDate>,
+) -> CreateActionRow {
+    let week = CreateSelectMenuOption::new("Whole week", WHOLE_WEEK)
+        .description("Calendar image")
+        .default_selection(selected.is_none());
+    let days = start.iter_days().take_while(|day| *day <= end).map(|day| {
+        let description = if fetched.missing_days.contains(&day) {
+            "Could not be loaded".to_string()
+        } else {
+            let count = fetched
+                .events
+                .iter()
+                .filter(|e| e.date.date_naive() == day)
+                .count();
+            format!("{count} reporting")
+        };
+        CreateSelectMenuOption::new(
+            day.format("%A, %b %-d").to_string(),
+            day.format("%Y-%m-%d").to_string(),
+        )
+        .description(description)
+        .default_selection(selected == Some(day))
+    });
+    let options = std::iter::once(week).chain(days).collect();
+    let universe = index.map(|i| format!(":{}", i.name())).unwrap_or_default();
+    let menu = CreateSelectMenu::new(
+        format!("{DAY_PICKER_PREFIX}{}{universe}", start.format("%Y-%m-%d")),
+        CreateSelectMenuKind::String { options },
+    )
+    .placeholder("Pick a day for estimates and implied moves");
+    CreateActionRow::SelectMenu(menu)
+}
+
+/// One day of the week in detail: the most important names first, with estimates and the
+/// implied move from options.
+async fn day_response(
+    finance: &FinanceService,
+    filter: &ResolvedFilter,
+    index: Option<Index>,
+    start: NaiveDate,
+    day: NaiveDate,
+) -> BotResult<EarningsResponse> {
+    let end = start + Duration::days(4);
+    let week = fetch_week(finance, filter, start, end).await?;
+    let picker = Some(day_picker(start, end, index, &week, Some(day)));
+    let label = day.format("%A, %b %-d");
+    if week.missing_days.contains(&day) {
+        return Ok(EarningsResponse {
+            content: format!(
+                "⚠️ Earnings for {label} could not be loaded; try again in a few minutes."
+            ),
+            image: None,
+            full_list: None,
+            picker,
+        });
+    }
+
+    let mut day_events: Vec<EarningsEvent> = week
+        .events
+        .iter()
+        .filter(|e| e.date.date_naive() == day)
+        .cloned()
+        .collect();
+    if day_events.is_empty() {
+        return Ok(EarningsResponse {
+            content: format!("📅 No earnings on {label}."),
+            image: None,
+            full_list: None,
+            picker,
+        });
+    }
+    day_events.sort_by(|a, b| {
+        b.importance
+            .unwrap_or(0)
+            .cmp(&a.importance.unwrap_or(0))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    let total = day_events.len();
+    day_events.truncate(MAX_DAY_ENTRIES);
+
+    let missing: Vec<String> = day_events
+        .iter()
+        .filter(|ev| ev.needs_estimates())
+        .map(|ev| ev.symbol.clone())
+        .collect();
+    let estimates = finance.get_earnings_estimates(&missing).await;
+    for ev in day_events.iter_mut() {
+        if let Some(estimate) = estimates.get(&ev.symbol) {
+            ev.fill_estimates(estimate);
+        }
+    }
+
+    let details: Vec<(EarningsEvent, Option<f64>)> = stream::iter(day_events)
+        .map(|ev| async move {
+            let implied_move = implied_move(finance, &ev).await;
+            (ev, implied_move)
+        })
+        .buffered(DAY_LOOKUPS)
+        .collect()
+        .await;
+
+    let mut lines = vec![format!("📅 **Earnings — {label}** ({total} reporting)")];
+    for (session, heading) in [
+        ("BMO", "Before Open"),
+        ("AMC", "After Close"),
+        ("TBA", "Time TBA"),
+    ] {
+        let entries: Vec<String> = details
+            .iter()
+            .filter(|(ev, _)| earnings::classify_session(ev.time_of_day.as_deref()) == session)
+            .map(|(ev, implied_move)| day_line(ev, *implied_move))
+            .collect();
+        if !entries.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("**{heading}**"));
+            lines.extend(entries);
+        }
+    }
+    if total > MAX_DAY_ENTRIES {
+        lines.push(String::new());
+        lines.push(format!("*+{} more*", total - MAX_DAY_ENTRIES));
+    }
+
+    Ok(EarningsResponse {
+        content: lines.join("\n"),
+        image: None,
+        full_list: None,
+        picker,
+    })
+}
+
+/// `🔥 **NVDA** — EPS $1.39 · Rev $89.50B · Mkt cap $3.45T · IM ±7.2%`, leaving out what
+/// isn't known.
+fn day_line(ev: &EarningsEvent, implied_move: Option<f64>) -> String {
+    let marker = match ev.importance {
+        Some(5) => "🔥",
+        Some(4) => "⭐",
+        _ => "•",
+    };
+    let details = [
+        ev.eps_estimate
+            .map(|v| format!("EPS {}", format::per_share(v))),
+        ev.revenue_estimate
+            .map(|v| format!("Rev {}", format::currency(v))),
+        ev.market_cap
+            .map(|v| format!("Mkt cap {}", format::currency(v))),
+        implied_move.map(|v| format!("IM ±{v:.1}%")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if details.is_empty() {
+        format!("{marker} **{}**", ev.symbol)
+    } else {
+        format!("{marker} **{}** — {}", ev.symbol, details.join(" · "))
+    }
+}
+
 /// Post today's earnings summary to the current channel using the daily automation helper.
 pub async fn handle_daily(
     command: &CommandInteraction,
     finance: &FinanceService,
     http: &Http,
-) -> Result<String, String> {
-    handle_daily_for_channel(finance, http, command.channel_id).await
+    images: &ImageConfig,
+) -> BotResult<String> {
+    handle_daily_for_channel(finance, http, command.channel_id, images).await
 }
 
 /// Manually trigger the post-earnings report for today.
@@ -175,7 +579,7 @@ pub async fn handle_after_daily(
     command: &CommandInteraction,
     finance: &FinanceService,
     http: &Http,
-) -> Result<String, String> {
+) -> BotResult<String> {
     handle_after_daily_for_channel(finance, http, command.channel_id).await
 }
 
@@ -183,8 +587,9 @@ pub async fn handle_daily_for_channel(
     finance: &FinanceService,
     http: &Http,
     channel_id: ChannelId,
-) -> Result<String, String> {
-    earnings::send_daily_report(http, finance, channel_id).await?;
+    images: &ImageConfig,
+) -> BotResult<String> {
+    earnings::send_daily_report(http, finance, None, images, channel_id).await?;
     Ok("Posted today's earnings report to this channel.".to_string())
 }
 
@@ -192,8 +597,8 @@ pub async fn handle_after_daily_for_channel(
     finance: &FinanceService,
     http: &Http,
     channel_id: ChannelId,
-) -> Result<String, String> {
-    earnings::send_after_daily_report(http, finance, channel_id).await?;
+) -> BotResult<String> {
+    earnings::send_after_daily_report(http, finance, None, channel_id).await?;
     Ok("Posted today's post-earnings report to this channel.".to_string())
 }
 
@@ -201,7 +606,7 @@ pub fn format_output(events: &[EarningsEvent]) -> String {
     let mut lines = Vec::new();
     lines.push(format!(
         "📊 **Earnings Calendar (Next 7 Days)**\nFetched: {} | Total: {}",
-        Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        timefmt::discord(&Utc::now(), TimestampStyle::ShortDateTime),
         events.len()
     ));
     lines.push(String::new()); // Empty line for spacing
@@ -246,3 +651,105 @@ pub fn format_output(events: &[EarningsEvent]) -> String {
 
     lines.join("\n")
 }
+
+pub struct WeeklyEarningsCommand;
+
+#[async_trait]
+impl Command for WeeklyEarningsCommand {
+    fn name(&self) -> &str {
+        "weekly-earnings"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_weekly_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let config = ctx.config.current();
+        let response =
+            handle_weekly(ctx.interaction, ctx.finance, ctx.store, &config.images).await?;
+        Ok(response.into_command_response())
+    }
+
+    async fn component(&self, ctx: &ComponentContext<'_>) -> CommandResult {
+        let (monday, universe) = ctx
+            .interaction
+            .data
+            .custom_id
+            .strip_prefix(DAY_PICKER_PREFIX)
+            .map(|rest| rest.split_once(':').unwrap_or((rest, "")))
+            .ok_or_else(|| BotError::bad_input("this calendar no longer takes input"))?;
+        let start = NaiveDate::parse_from_str(monday, "%Y-%m-%d")
+            .map_err(|_| BotError::bad_input("this calendar no longer takes input"))?;
+        let index = Index::from_name(universe);
+        let choice = match &ctx.interaction.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => values.first(),
+            _ => None,
+        }
+        .ok_or_else(|| BotError::bad_input("pick a day"))?;
+
+        let guild_id = ctx.interaction.guild_id.map(|g| g.get());
+        let filter = symbol_filter::guild_filter(ctx.store, ctx.finance, guild_id).await;
+        let filter = universe::restrict(ctx.finance, filter, index).await?;
+        let config = ctx.config.current();
+        let response = if choice == WHOLE_WEEK {
+            let end = start + Duration::days(4);
+            let today = Utc::now().with_timezone(&New_York).date_naive();
+            let label = week_label(start, today);
+            week_response(
+                ctx.finance,
+                &filter,
+                index,
+                start,
+                end,
+                label.as_deref(),
+                &config.images,
+            )
+            .await?
+        } else {
+            let day = NaiveDate::parse_from_str(choice, "%Y-%m-%d")
+                .map_err(|_| BotError::bad_input(format!("unknown day {choice}")))?;
+            day_response(ctx.finance, &filter, index, start, day).await?
+        };
+        Ok(response.into_command_response())
+    }
+}
+
+pub struct DailyEarningsCommand;
+
+#[async_trait]
+impl Command for DailyEarningsCommand {
+    fn name(&self) -> &str {
+        "daily-earnings"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_daily_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let config = ctx.config.current();
+        handle_daily(ctx.interaction, ctx.finance, &ctx.ctx.http, &config.images)
+            .await
+            .map(CommandResponse::text)
+    }
+}
+
+pub struct AfterDailyEarningsCommand;
+
+#[async_trait]
+impl Command for AfterDailyEarningsCommand {
+    fn name(&self) -> &str {
+        "er-reports"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_after_daily_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle_after_daily(ctx.interaction, ctx.finance, &ctx.ctx.http)
+            .await
+            .map(CommandResponse::text)
+    }
+}
@@ -0,0 +1,160 @@
+//! `/earnings-ics`: the week's earnings reports as an iCalendar file, optionally limited to
+//! the server's watchlist or an index.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::America::New_York;
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+
+use crate::models::EarningsEvent;
+use crate::service::automation::earnings::scheduled_events;
+use crate::service::finance::FinanceService;
+use crate::service::ics;
+use crate::service::store::StateStore;
+use crate::service::symbol_filter::{self, ResolvedFilter};
+
+use super::args::get_bool_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::universe;
+
+/// Length of each calendar entry; reports have no end time, this just keeps them visible.
+const EVENT_MINUTES: u32 = 60;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("earnings-ics")
+        .description("Download the week's earnings as a calendar file (.ics)")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "watchlist",
+            "Only the symbols this server follows (/config symbols)",
+        ))
+        .add_option(universe::universe_option())
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+) -> BotResult<CommandResponse> {
+    let now = Utc::now();
+    let (start, end) = week_of(now.with_timezone(&New_York).date_naive());
+    let index = universe::requested(command);
+    let filter = if get_bool_opt(command, "watchlist").unwrap_or(false) {
+        watchlist_filter(command, finance, store).await?
+    } else {
+        ResolvedFilter::default()
+    };
+    let filter = universe::restrict(finance, filter, index).await?;
+
+    let range = finance.get_earnings_range(start, end).await?;
+    let missing_note = range
+        .missing_note()
+        .map(|note| format!("\n{note}"))
+        .unwrap_or_default();
+    let mut events = range.events;
+    events.retain(|ev| (start..=end).contains(&ev.date.date_naive()));
+    filter.retain_events(&mut events);
+
+    let label = format!(
+        "week of {}{}",
+        start.format("%b %-d"),
+        index
+            .map(|i| format!(" · {}", i.label()))
+            .unwrap_or_default()
+    );
+    if events.is_empty() {
+        return Ok(CommandResponse::text(format!(
+            "📅 No earnings reporters for the {label}.{missing_note}"
+        )));
+    }
+
+    let body = calendar(&format!("Earnings — {label}"), &events, now);
+    let filename = format!("earnings-{}.ics", start.format("%Y-%m-%d"));
+    Ok(CommandResponse::text(format!(
+        "📅 {} earnings reports for the {label}. Open the file or import it into Google or Apple Calendar.{missing_note}",
+        events.len()
+    ))
+    .with_attachment(CreateAttachment::bytes(body.into_bytes(), filename)))
+}
+
+/// Monday through Friday of this week, or of the coming week on a weekend.
+pub fn week_of(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = match today.weekday() {
+        Weekday::Sat => today + Duration::days(2),
+        Weekday::Sun => today + Duration::days(1),
+        day => today - Duration::days(day.num_days_from_monday() as i64),
+    };
+    (monday, monday + Duration::days(4))
+}
+
+/// An iCalendar file with one entry per report, starting when the report is expected
+/// (8:00 AM ET before the open, 4:00 PM ET otherwise), in date and symbol order.
+pub fn calendar(name: &str, events: &[EarningsEvent], stamp: DateTime<Utc>) -> String {
+    let mut entries: Vec<ics::Event> = events
+        .iter()
+        .filter_map(|ev| {
+            Some(ics::Event {
+                uid: format!(
+                    "{}-{}@stacks-bot",
+                    ev.symbol,
+                    ev.date.date_naive().format("%Y%m%d")
+                ),
+                start: scheduled_events::event_start(ev)?,
+                duration_minutes: EVENT_MINUTES,
+                summary: scheduled_events::event_name(ev),
+                description: scheduled_events::event_description(ev),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.uid.cmp(&b.uid)));
+    ics::calendar(name, &entries, stamp)
+}
+
+/// The server's `/config symbols` filter; asking for it where none is set is an error rather
+/// than a silently unfiltered file.
+async fn watchlist_filter(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+) -> BotResult<ResolvedFilter> {
+    let Some(guild_id) = command.guild_id.map(|g| g.get()) else {
+        return Err(BotError::bad_input(
+            "`watchlist` uses the server's symbol list and only works in a server",
+        ));
+    };
+    let configured = match store {
+        Some(store) => store
+            .guild_symbol_filter(guild_id)
+            .await
+            .map_err(|e| BotError::internal(format!("failed to load symbol list: {e}")))?
+            .is_some_and(|f| !f.is_empty()),
+        None => false,
+    };
+    if !configured {
+        return Err(BotError::bad_input(
+            "This server has no symbol list; an admin can set one with `/config symbols`",
+        ));
+    }
+    Ok(symbol_filter::guild_filter(store, finance, Some(guild_id)).await)
+}
+
+pub struct EarningsIcsCommand;
+
+#[async_trait]
+impl Command for EarningsIcsCommand {
+    fn name(&self) -> &str {
+        "earnings-ics"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store).await
+    }
+}
@@ -0,0 +1,144 @@
+use finance_query_core::YahooError;
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use crate::service::error_reporting;
+use crate::service::finance::FinanceServiceError;
+use crate::service::i18n::{Locale, Text};
+
+/// Why a command failed.
+///
+/// The `Display` output is the detailed form meant for logs; users only ever see
+/// [`BotError::user_message`].
+#[derive(Debug, Error)]
+pub enum BotError {
+    /// Nothing matched. Holds a user-safe description of what was missing.
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error("rate limited by upstream API")]
+    RateLimited,
+    /// Holds the name of the service that timed out.
+    #[error("{0} timed out")]
+    UpstreamTimeout(String),
+    /// A data source is paused after repeated failures. Holds the endpoint name.
+    #[error("{0} unavailable")]
+    Unavailable(String),
+    /// Holds a user-safe explanation of what was wrong with the input.
+    #[error("bad input: {0}")]
+    BadInput(String),
+    /// The user is not allowed to run the command. Holds a user-safe explanation.
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+pub type BotResult<T> = Result<T, BotError>;
+
+impl BotError {
+    pub fn not_found(what: impl Into<String>) -> Self {
+        Self::NotFound(what.into())
+    }
+
+    pub fn bad_input(message: impl Into<String>) -> Self {
+        Self::BadInput(message.into())
+    }
+
+    pub fn internal(detail: impl Into<String>) -> Self {
+        Self::Internal(detail.into())
+    }
+
+    /// Message shown in Discord.
+    pub fn user_message(&self) -> String {
+        self.localized_message(Locale::En)
+    }
+
+    /// [`BotError::user_message`] in `locale`. Details carried by the error (what was not
+    /// found, what was wrong with the input) are not translated.
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match self {
+            Self::NotFound(what) => format!("🔍 {}", capitalize(what)),
+            Self::RateLimited => Text::RateLimited.get(locale).to_string(),
+            Self::UpstreamTimeout(_) => Text::UpstreamTimeout.get(locale).to_string(),
+            Self::Unavailable(_) => Text::Unavailable.get(locale).to_string(),
+            Self::BadInput(message) => format!("⚠️ {}", capitalize(message)),
+            Self::Forbidden(message) => format!("🔒 {}", capitalize(message)),
+            Self::Internal(_) => Text::Internal.get(locale).to_string(),
+        }
+    }
+
+    /// Log the failure with full detail; user errors are logged quieter than upstream or
+    /// internal ones. Internal errors are also sent to error reporting.
+    pub fn log(&self, command: &str) {
+        match self {
+            Self::NotFound(_) | Self::BadInput(_) | Self::Forbidden(_) => info!(command, "command rejected: {self}"),
+            Self::RateLimited | Self::UpstreamTimeout(_) | Self::Unavailable(_) => {
+                warn!(command, "command failed upstream: {self}")
+            }
+            Self::Internal(_) => {
+                error!(command, "command failed: {self}");
+                error_reporting::report(&format!("command:{command}"), &self.to_string());
+            }
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl From<FinanceServiceError> for BotError {
+    fn from(err: FinanceServiceError) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl From<&FinanceServiceError> for BotError {
+    fn from(err: &FinanceServiceError) -> Self {
+        match err {
+            FinanceServiceError::Yahoo(err) => err.into(),
+            FinanceServiceError::NotFound(symbol) => {
+                Self::NotFound(format!("no quote data for {symbol}"))
+            }
+            FinanceServiceError::Unavailable(endpoint) => Self::Unavailable(endpoint.to_string()),
+            FinanceServiceError::NoCoverage { .. } => Self::NotFound(err.to_string()),
+            FinanceServiceError::Http(detail) => Self::Internal(format!("earnings API: {detail}")),
+            FinanceServiceError::Timeout(_) => Self::UpstreamTimeout("earnings API".to_string()),
+            FinanceServiceError::Constituents(_) => {
+                Self::Unavailable("index constituents".to_string())
+            }
+            FinanceServiceError::Shared(err) => err.as_ref().into(),
+        }
+    }
+}
+
+impl From<&YahooError> for BotError {
+    fn from(err: &YahooError) -> Self {
+        match err {
+            YahooError::NotFound(_) => Self::NotFound("no data found for that symbol".to_string()),
+            YahooError::RateLimited | YahooError::HttpError(429, _) => Self::RateLimited,
+            YahooError::NetworkError(e) if e.is_timeout() => {
+                Self::UpstreamTimeout("Yahoo Finance".to_string())
+            }
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<tokio::time::error::Elapsed> for BotError {
+    fn from(_: tokio::time::error::Elapsed) -> Self {
+        Self::UpstreamTimeout("request".to_string())
+    }
+}
+
+/// Lower-level helpers (rendering, automation reports) still report plain strings; those
+/// are treated as internal failures.
+impl From<String> for BotError {
+    fn from(detail: String) -> Self {
+        Self::Internal(detail)
+    }
+}
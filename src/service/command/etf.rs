@@ -0,0 +1,134 @@
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+
+use crate::models::EtfProfile;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Holdings listed; Yahoo reports at most ten.
+const TOP_HOLDINGS: usize = 10;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("etf")
+        .description("Top holdings, sector weights, expense ratio and assets of an ETF")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "ETF or fund symbol, e.g., SPY; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let profile = finance.get_etf_profile(&symbol).await?.ok_or_else(|| {
+        BotError::bad_input(format!(
+            "{symbol} is not an ETF or fund; try `/quote` or `/holders`"
+        ))
+    })?;
+    Ok(CommandResponse::text(profile_text(&profile)))
+}
+
+/// Name and category, costs and size, then the largest holdings and the sector split.
+pub fn profile_text(profile: &EtfProfile) -> String {
+    let title = match &profile.name {
+        Some(name) => format!("📦 **{} — {name}**", profile.symbol),
+        None => format!("📦 **{}**", profile.symbol),
+    };
+    let mut lines = vec![title];
+    let about = [profile.family.as_deref(), profile.category.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    if !about.is_empty() {
+        lines.push(about.join(" · "));
+    }
+    let currency = profile.currency.as_deref().unwrap_or("USD");
+    let facts = [
+        profile
+            .expense_ratio
+            .map(|r| format!("Expense ratio {}", format::fraction_percent(r))),
+        profile
+            .net_assets
+            .map(|a| format!("AUM {}", format::currency_in(a, currency))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !facts.is_empty() {
+        lines.push(facts.join(" · "));
+    }
+
+    if !profile.holdings.is_empty() {
+        lines.push(String::new());
+        lines.push("**Top holdings**".to_string());
+        for (i, holding) in profile.holdings.iter().take(TOP_HOLDINGS).enumerate() {
+            let label = match &holding.symbol {
+                Some(symbol) if *symbol != holding.name => format!("**{symbol}** {}", holding.name),
+                _ => format!("**{}**", holding.name),
+            };
+            lines.push(format!(
+                "{}. {label} — {}",
+                i + 1,
+                format::fraction_percent(holding.weight)
+            ));
+        }
+        let total: f64 = profile
+            .holdings
+            .iter()
+            .take(TOP_HOLDINGS)
+            .map(|h| h.weight)
+            .sum();
+        lines.push(format!(
+            "*Top {} together: {}*",
+            profile.holdings.len().min(TOP_HOLDINGS),
+            format::fraction_percent(total)
+        ));
+    }
+
+    if !profile.sectors.is_empty() {
+        lines.push(String::new());
+        lines.push("**Sectors**".to_string());
+        for sector in &profile.sectors {
+            lines.push(format!(
+                "• {} {}",
+                sector.sector,
+                format::fraction_percent(sector.weight)
+            ));
+        }
+    }
+
+    if profile.holdings.is_empty() && profile.sectors.is_empty() {
+        lines.push(String::new());
+        lines.push("No holdings breakdown is published for this fund.".to_string());
+    }
+    lines.join("\n")
+}
+
+pub struct EtfCommand;
+
+#[async_trait]
+impl Command for EtfCommand {
+    fn name(&self) -> &str {
+        "etf"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,301 @@
+//! `/event-alert add|list|remove`: standing alerts on a symbol's next earnings date or mean
+//! analyst target, checked daily by `automation::event_alerts` and posted in the channel they
+//! were set in.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, GuildId,
+    ResolvedOption, ResolvedValue,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::automation::event_alerts::{load_snapshots, save_snapshot};
+use crate::service::caching::collections::event_alerts::EventAlert;
+use crate::service::caching::RedisCache;
+use crate::service::finance::fundamental_events::{
+    EventKind, EventSnapshot, DEFAULT_TARGET_THRESHOLD,
+};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::store::StateStore;
+
+use super::args::get_str;
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub const COMMAND_NAME: &str = "event-alert";
+/// Alerts one server may hold, across symbols.
+pub const MAX_GUILD_ALERTS: usize = 25;
+
+pub fn register_command() -> CreateCommand {
+    let event = EventKind::ALL.into_iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "event", "What to watch")
+            .required(true),
+        |option, kind| option.add_string_choice(kind.label(), kind.name()),
+    );
+    CreateCommand::new(COMMAND_NAME)
+        .description("Alerts when a ticker's earnings date or analyst target changes")
+        .dm_permission(false)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "add",
+                "Post here when the data changes",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "ticker",
+                    "Ticker symbol, e.g., NVDA",
+                )
+                .required(true),
+            )
+            .add_sub_option(event)
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Number,
+                    "threshold",
+                    "Target move in % that fires the alert, default 5",
+                )
+                .min_number_value(1.0)
+                .max_number_value(100.0),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "Event alerts in this server",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "remove",
+                "Stop an event alert",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "id",
+                    "Alert ID from /event-alert list",
+                )
+                .required(true),
+            ),
+        )
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    cache: Option<&RedisCache>,
+) -> BotResult<String> {
+    let store = store.ok_or_else(|| {
+        BotError::not_found("event alerts are unavailable; no state store is configured")
+    })?;
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| BotError::bad_input("event alerts only work in a server"))?;
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(args),
+        ..
+    }) = options.first()
+    else {
+        return Err(BotError::bad_input("choose an /event-alert subcommand"));
+    };
+
+    let mut all = store
+        .event_alerts()
+        .await
+        .map_err(|e| BotError::internal(format!("failed to load event alerts: {e}")))?;
+    let snapshots = load_snapshots(cache).await.map_err(BotError::internal)?;
+    for alert in all.values_mut().flatten() {
+        if let Some(seen) = snapshots.get(&alert.id) {
+            alert.seen = seen.clone();
+        }
+    }
+    let mut in_guild: Vec<&EventAlert> = all
+        .values()
+        .flatten()
+        .filter(|a| a.guild_id == guild_id.get())
+        .collect();
+    in_guild.sort_by(|a, b| (&a.symbol, a.created_at).cmp(&(&b.symbol, b.created_at)));
+
+    match *name {
+        "add" => {
+            let ticker =
+                get_str(args, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+            let kind = get_str(args, "event")
+                .and_then(EventKind::from_name)
+                .ok_or_else(|| BotError::bad_input("event must be earnings_date | target"))?;
+            let threshold =
+                get_number(args, "threshold").map_or(DEFAULT_TARGET_THRESHOLD, |v| v / 100.0);
+            if in_guild.len() >= MAX_GUILD_ALERTS {
+                return Err(BotError::bad_input(format!(
+                    "this server already has {MAX_GUILD_ALERTS} event alerts; remove one first"
+                )));
+            }
+            let symbol = symbol::resolve(finance, ticker).await?;
+            let channel_id = command.channel_id.get();
+            if in_guild
+                .iter()
+                .any(|a| a.symbol == symbol && a.kind == kind && a.channel_id == channel_id)
+            {
+                return Err(BotError::bad_input(format!(
+                    "{symbol} {} is already watched in this channel",
+                    kind.label().to_lowercase()
+                )));
+            }
+
+            let seen = finance.event_snapshot(&symbol).await?;
+            let alert = EventAlert {
+                id: alert_id(Utc::now()),
+                symbol: symbol.clone(),
+                kind,
+                threshold,
+                guild_id: guild_id.get(),
+                channel_id,
+                user_id: command.user.id.get(),
+                created_at: Utc::now(),
+                seen,
+            };
+            let alerts = all.entry(symbol.clone()).or_default();
+            alerts.push(alert.clone());
+            store
+                .save_event_alerts(&symbol, alerts)
+                .await
+                .map_err(|e| BotError::internal(format!("failed to save event alert: {e}")))?;
+            if let Err(e) = save_snapshot(cache, &alert.id, &alert.seen).await {
+                // The next check takes its values as the baseline instead.
+                warn!("{e}");
+            }
+            Ok(format!(
+                "🔔 Watching {}. Checked weekdays at 8:10 AM ET; posts go to <#{channel_id}>.",
+                alert_line(&alert)
+            ))
+        }
+        "list" => Ok(list_text(&in_guild)),
+        "remove" => {
+            let id = get_str(args, "id").ok_or_else(|| BotError::bad_input("id is required"))?;
+            remove(store, all, guild_id, id.trim_matches('`')).await
+        }
+        other => Err(BotError::bad_input(format!("unknown subcommand {other}"))),
+    }
+}
+
+async fn remove(
+    store: &dyn StateStore,
+    mut all: HashMap<String, Vec<EventAlert>>,
+    guild_id: GuildId,
+    id: &str,
+) -> BotResult<String> {
+    let found = all.iter_mut().find_map(|(symbol, alerts)| {
+        let index = alerts
+            .iter()
+            .position(|a| a.id == id && a.guild_id == guild_id.get())?;
+        Some((symbol.clone(), alerts.remove(index), alerts.clone()))
+    });
+    let Some((symbol, removed, rest)) = found else {
+        return Err(BotError::not_found(format!(
+            "no event alert `{id}` in this server"
+        )));
+    };
+    store
+        .save_event_alerts(&symbol, &rest)
+        .await
+        .map_err(|e| BotError::internal(format!("failed to save event alerts: {e}")))?;
+    Ok(format!("🔕 Stopped watching {}.", alert_line(&removed)))
+}
+
+/// Short, sortable ID from the creation time, e.g. `199a3c5e2f1`.
+pub fn alert_id(now: DateTime<Utc>) -> String {
+    format!("{:x}", now.timestamp_millis())
+}
+
+/// `**NVDA** next earnings date (now Aug 27, 2025) · ID `199a3c5e2f1``
+pub fn alert_line(alert: &EventAlert) -> String {
+    let detail = match alert.kind {
+        EventKind::EarningsDate => current_value(alert.kind, &alert.seen),
+        EventKind::TargetPrice => format!(
+            "±{:.0}%, {}",
+            alert.threshold * 100.0,
+            current_value(alert.kind, &alert.seen)
+        ),
+    };
+    format!(
+        "**{}** {} ({detail}) · ID `{}`",
+        alert.symbol,
+        alert.kind.label().to_lowercase(),
+        alert.id
+    )
+}
+
+fn current_value(kind: EventKind, seen: &EventSnapshot) -> String {
+    match kind {
+        EventKind::EarningsDate => seen
+            .earnings_date
+            .map_or("none scheduled yet".to_string(), |d| {
+                format!("now {}", d.format("%b %-d, %Y"))
+            }),
+        EventKind::TargetPrice => seen.target_mean.map_or("no target yet".to_string(), |t| {
+            format!("from {}", format::per_share(t))
+        }),
+    }
+}
+
+/// A line per alert with its channel and owner.
+pub fn list_text(alerts: &[&EventAlert]) -> String {
+    if alerts.is_empty() {
+        return "No event alerts in this server. Add one with `/event-alert add`.".to_string();
+    }
+    let mut lines = vec![format!(
+        "🔔 **Event alerts** ({}/{MAX_GUILD_ALERTS})",
+        alerts.len()
+    )];
+    for alert in alerts {
+        lines.push(format!(
+            "• {} · <#{}> by <@{}>",
+            alert_line(alert),
+            alert.channel_id,
+            alert.user_id
+        ));
+    }
+    lines.join("\n")
+}
+
+fn get_number(args: &[ResolvedOption<'_>], name: &str) -> Option<f64> {
+    args.iter()
+        .find(|o| o.name == name)
+        .and_then(|o| match o.value {
+            ResolvedValue::Number(n) => Some(n),
+            _ => None,
+        })
+}
+
+pub struct EventAlertCommand;
+
+#[async_trait]
+impl Command for EventAlertCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn audited(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store, ctx.cache)
+            .await
+            .map(CommandResponse::text)
+    }
+}
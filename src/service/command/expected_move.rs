@@ -0,0 +1,163 @@
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::history::DailyClose;
+use crate::service::finance::options::ExpectedMove;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::quickchart;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Price history drawn behind the cone.
+const HISTORY_RANGE: &str = "3mo";
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("expected-move")
+        .description("Options-implied ±1σ move cone over recent prices")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let (moves, history) = tokio::join!(
+        finance.expected_moves(&symbol),
+        finance.daily_closes(&symbol, HISTORY_RANGE)
+    );
+    let moves = moves?;
+    // The cone is still worth showing without the history behind it.
+    let history = history.unwrap_or_else(|e| {
+        warn!("expected move: no price history for {symbol}: {e}");
+        Vec::new()
+    });
+
+    let spot = moves[0].spot;
+    let mut lines = vec![format!(
+        "📐 **{symbol} expected move** — ±1σ from ATM straddles, spot {}",
+        format::per_share(spot)
+    )];
+    for m in &moves {
+        lines.push(format!(
+            "• {} ({}d) ±{} (±{:.1}%) → {} – {}",
+            m.expiration.format("%b %-d"),
+            m.days,
+            format::per_share(m.amount),
+            m.percent(),
+            format::per_share(m.spot - m.amount),
+            format::per_share(m.spot + m.amount)
+        ));
+    }
+
+    let mut response = CommandResponse::text(lines.join("\n"));
+    match quickchart::render(cone_chart(&symbol, &moves, &history), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "expected-move.png"))
+        }
+        Err(err) => {
+            warn!("expected move chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Closing prices, then the upper and lower bounds fanning out from the latest close to each
+/// expiration, with the area between them shaded.
+fn cone_chart(symbol: &str, moves: &[ExpectedMove], history: &[DailyClose]) -> Value {
+    let closes: Vec<Value> = history
+        .iter()
+        .map(|p| json!({"x": p.date.format("%Y-%m-%d").to_string(), "y": p.close}))
+        .collect();
+    // The cone starts at today's spot, which is also the last point of the history.
+    let start = history
+        .last()
+        .map(|p| p.date)
+        .unwrap_or_else(|| moves[0].expiration - chrono::Duration::days(moves[0].days));
+    let bound = |sign: f64| -> Vec<Value> {
+        std::iter::once(json!({"x": start.format("%Y-%m-%d").to_string(), "y": moves[0].spot}))
+            .chain(moves.iter().map(|m| {
+                json!({
+                    "x": m.expiration.format("%Y-%m-%d").to_string(),
+                    "y": m.spot + sign * m.amount
+                })
+            }))
+            .collect()
+    };
+
+    json!({
+        "type": "line",
+        "data": {
+            "datasets": [
+                {
+                    "label": "Close",
+                    "data": closes,
+                    "borderColor": "#37474f",
+                    "pointRadius": 0,
+                    "fill": false
+                },
+                {
+                    "label": "+1σ",
+                    "data": bound(1.0),
+                    "borderColor": "#4caf50",
+                    "borderDash": [6, 4],
+                    "fill": false
+                },
+                {
+                    "label": "−1σ",
+                    "data": bound(-1.0),
+                    "borderColor": "#f44336",
+                    "borderDash": [6, 4],
+                    "backgroundColor": "#2196f322",
+                    "fill": "-1"
+                }
+            ]
+        },
+        "options": {
+            "plugins": {
+                "legend": { "position": "bottom" },
+                "title": { "display": true, "text": format!("{symbol} expected move (±1σ)") }
+            },
+            "scales": {
+                "x": { "type": "time", "time": { "unit": "week" } },
+                "y": { "title": { "display": true, "text": "Price" } }
+            }
+        }
+    })
+}
+
+pub struct ExpectedMoveCommand;
+
+#[async_trait]
+impl Command for ExpectedMoveCommand {
+    fn name(&self) -> &str {
+        "expected-move"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,139 @@
+//! `format:csv|json` for data commands: the full dataset as a file attachment instead of the
+//! truncated text reply.
+
+use serde_json::{Map, Value};
+use serenity::all::{
+    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateAttachment,
+    CreateCommandOption,
+};
+
+use crate::service::csv;
+
+use super::error::{BotError, BotResult};
+
+pub const EXPORT_OPTION: &str = "format";
+
+/// Add to a data command's options to offer the full dataset as a file.
+pub fn export_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::String,
+        EXPORT_OPTION,
+        "Send the full data as a file instead of a summary",
+    )
+    .add_string_choice("CSV", "csv")
+    .add_string_choice("JSON", "json")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// The format picked with [`export_option`], or `None` for the usual text reply.
+    pub fn requested(command: &CommandInteraction) -> Option<Self> {
+        command
+            .data
+            .options
+            .iter()
+            .find(|o| o.name == EXPORT_OPTION)
+            .and_then(|o| match &o.value {
+                CommandDataOptionValue::String(s) => match s.as_str() {
+                    "csv" => Some(ExportFormat::Csv),
+                    "json" => Some(ExportFormat::Json),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Rows of named cells, written as CSV with a header line or as a JSON array of objects.
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new<S: Into<String>>(columns: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row; cells line up with the columns, `Value::Null` for missing values.
+    pub fn push(&mut self, row: Vec<Value>) {
+        debug_assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Header line, then one line per row; missing values are empty cells.
+    pub fn to_csv(&self) -> String {
+        let mut out = csv::row(&self.columns);
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(cell).collect();
+            out.push_str(&csv::row(&cells));
+        }
+        out
+    }
+
+    /// An array with one object per row, keyed by column.
+    pub fn to_json(&self) -> BotResult<String> {
+        let objects: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object: Map<String, Value> = self
+                    .columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect();
+                Value::Object(object)
+            })
+            .collect();
+        serde_json::to_string_pretty(&objects)
+            .map_err(|e| BotError::internal(format!("failed to encode export: {e}")))
+    }
+
+    /// The table as `<stem>.csv` or `<stem>.json`.
+    pub fn attachment(&self, format: ExportFormat, stem: &str) -> BotResult<CreateAttachment> {
+        let body = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json()?,
+        };
+        Ok(CreateAttachment::bytes(
+            body,
+            format!("{stem}.{}", format.extension()),
+        ))
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,737 @@
+use serenity::all::{
+    CommandDataOption, CommandDataOptionValue, CommandInteraction, CommandOptionType,
+    ComponentInteraction, Context, CreateActionRow, CreateAttachment, CreateCommand,
+    CreateCommandOption, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage, EditAttachments, EditInteractionResponse, Guild, GuildId,
+    Http, InstallationContext, InteractionContext, Member, Message, MessageFlags, RoleId, UserId,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::models::StatementType;
+use crate::service::audit::{self, AuditEvent};
+use crate::service::caching::RedisCache;
+use crate::service::chunks;
+use crate::service::config::{Config, ConfigHandle};
+use crate::service::finance::FinanceService;
+use crate::service::i18n::{self, Locale, Text};
+use crate::service::store::StateStore;
+
+use super::error::{BotError, BotResult};
+use super::registration::{self, Scope, SyncReport};
+use super::{
+    admin, ask, backtest, bind_ticker, brief, config, dcf, earnings, earnings_ics, etf,
+    event_alert, expected_move, fundamentals, fundamentals_chart, held_by, holders, holders_trend,
+    implied_moves, news, news_multi, oi_change, ping, quality, quotes, risk, rs, seasonality,
+    stats, strategy, timezone, vol_surface,
+};
+
+/// What a command sends back: text plus optional embeds, files and message components.
+#[derive(Debug, Clone, Default)]
+pub struct CommandResponse {
+    pub content: String,
+    pub embeds: Vec<CreateEmbed>,
+    pub attachments: Vec<CreateAttachment>,
+    /// Select menus and buttons; their `custom_id` must start with `"<command name>:"` so
+    /// interactions with them reach [`Command::component`].
+    pub components: Vec<CreateActionRow>,
+    /// From a component handler: send as a new message under the one the component is on
+    /// instead of replacing it.
+    pub followup: bool,
+}
+
+impl CommandResponse {
+    pub fn text(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_embed(mut self, embed: CreateEmbed) -> Self {
+        self.embeds.push(embed);
+        self
+    }
+
+    pub fn with_attachment(mut self, attachment: CreateAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    pub fn with_components(mut self, row: CreateActionRow) -> Self {
+        self.components.push(row);
+        self
+    }
+
+    pub fn as_followup(mut self) -> Self {
+        self.followup = true;
+        self
+    }
+}
+
+pub type CommandResult = BotResult<CommandResponse>;
+
+/// Boolean option that lets a user ask for a response only they can see.
+pub const EPHEMERAL_OPTION: &str = "ephemeral";
+
+/// Add to a command's options to let users pick the response visibility. Overrides the
+/// guild default from `responses.ephemeral_guild_ids`.
+pub fn ephemeral_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::Boolean,
+        EPHEMERAL_OPTION,
+        "Only show the response to you",
+    )
+}
+
+/// Option that `/bind-ticker` fills in when a command leaves it out; commands opt in by not
+/// making it required.
+pub const TICKER_OPTION: &str = "ticker";
+
+/// A `ticker` option as Discord would have sent it.
+pub fn ticker_option(ticker: &str) -> Option<CommandDataOption> {
+    serde_json::from_value(serde_json::json!({
+        "name": TICKER_OPTION,
+        "type": CommandOptionType::String,
+        "value": ticker,
+    }))
+    .ok()
+}
+
+/// Whether `definition` has a `ticker` option users may leave out.
+fn takes_bound_ticker(definition: &CreateCommand) -> bool {
+    let Ok(definition) = serde_json::to_value(definition) else {
+        return false;
+    };
+    definition["options"].as_array().is_some_and(|options| {
+        options.iter().any(|option| {
+            option["name"] == TICKER_OPTION && option["required"] != serde_json::Value::Bool(true)
+        })
+    })
+}
+
+/// `interaction` with the channel's bound ticker filled in, when `command` takes one and the
+/// user left it out.
+async fn with_bound_ticker(
+    command: &dyn Command,
+    interaction: &CommandInteraction,
+    store: Option<&dyn StateStore>,
+) -> Option<CommandInteraction> {
+    let store = store?;
+    if interaction
+        .data
+        .options
+        .iter()
+        .any(|o| o.name == TICKER_OPTION)
+        || !takes_bound_ticker(&command.register())
+    {
+        return None;
+    }
+    let ticker = match store.channel_ticker(interaction.channel_id.get()).await {
+        Ok(ticker) => ticker?,
+        Err(e) => {
+            warn!(
+                "failed to load the bound ticker of {}: {e}",
+                interaction.channel_id
+            );
+            return None;
+        }
+    };
+    let mut filled = interaction.clone();
+    filled.data.options.insert(0, ticker_option(&ticker)?);
+    Some(filled)
+}
+
+/// How the initial interaction response is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deferral {
+    /// Reply directly; only for commands that answer well within Discord's 3 second limit.
+    None,
+    /// Show "thinking…" publicly, then edit in the response.
+    Public,
+    /// Like `Public`, but only the invoking user sees the response.
+    Ephemeral,
+}
+
+/// Everything a command needs to run.
+pub struct CommandContext<'a> {
+    pub ctx: &'a Context,
+    pub interaction: &'a CommandInteraction,
+    pub finance: &'a FinanceService,
+    pub cache: Option<&'a RedisCache>,
+    pub store: Option<&'a dyn StateStore>,
+    pub config: &'a ConfigHandle,
+    /// The guild's `/config language`, English by default.
+    pub locale: Locale,
+}
+
+/// Everything a command needs to handle a component on one of its messages.
+pub struct ComponentContext<'a> {
+    pub ctx: &'a Context,
+    pub interaction: &'a ComponentInteraction,
+    pub finance: &'a FinanceService,
+    pub cache: Option<&'a RedisCache>,
+    pub store: Option<&'a dyn StateStore>,
+    pub config: &'a ConfigHandle,
+    pub locale: Locale,
+}
+
+#[async_trait]
+pub trait Command: Send + Sync {
+    /// Slash command name; must match the name used in `register`.
+    fn name(&self) -> &str;
+
+    fn register(&self) -> CreateCommand;
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Public
+    }
+
+    /// Whether uses are posted to the guild's audit channel. Set for commands that change
+    /// settings or operate the bot.
+    fn audited(&self) -> bool {
+        false
+    }
+
+    /// Whether users can add the app to their account and run this command in any server,
+    /// DM or group DM. Only global registration sets it; guild commands can't be user-installed.
+    fn user_installable(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult;
+
+    /// Handle a select menu or button from one of this command's responses. The response
+    /// replaces the message the component is on, unless it is marked as a follow-up.
+    async fn component(&self, _ctx: &ComponentContext<'_>) -> CommandResult {
+        Err(BotError::bad_input("this message no longer takes input"))
+    }
+}
+
+/// The set of slash commands the bot serves. Handles registration, deferral, error
+/// formatting and response editing so individual commands only produce a `CommandResponse`.
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Box<dyn Command>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every built-in slash command.
+    pub fn standard() -> Self {
+        let registry = Self::new()
+            .with(ping::PingCommand)
+            .with(fundamentals::FundamentalsCommand(
+                StatementType::IncomeStatement,
+            ))
+            .with(fundamentals::FundamentalsCommand(
+                StatementType::BalanceSheet,
+            ))
+            .with(fundamentals::FundamentalsCommand(StatementType::CashFlow))
+            .with(fundamentals::StatementCommand)
+            .with(fundamentals_chart::FundamentalsChartCommand)
+            .with(dcf::DcfCommand)
+            .with(quality::QualityCommand)
+            .with(backtest::BacktestCommand)
+            .with(seasonality::SeasonalityCommand)
+            .with(risk::RiskCommand)
+            .with(rs::RsCommand)
+            .with(quotes::QuoteCommand)
+            .with(holders::HoldersCommand)
+            .with(holders_trend::HoldersTrendCommand)
+            .with(etf::EtfCommand)
+            .with(held_by::HeldByCommand)
+            .with(news::NewsCommand)
+            .with(news_multi::NewsMultiCommand)
+            .with(stats::StatsCommand)
+            .with(earnings::WeeklyEarningsCommand)
+            .with(earnings::DailyEarningsCommand)
+            .with(earnings::AfterDailyEarningsCommand)
+            .with(earnings_ics::EarningsIcsCommand)
+            .with(implied_moves::ImpliedMovesCommand)
+            .with(expected_move::ExpectedMoveCommand)
+            .with(oi_change::OiChangeCommand)
+            .with(vol_surface::VolSurfaceCommand)
+            .with(strategy::StrategyCommand)
+            .with(admin::AdminCommand)
+            .with(timezone::TimezoneCommand)
+            .with(brief::BriefCommand)
+            .with(bind_ticker::BindTickerCommand)
+            .with(event_alert::EventAlertCommand)
+            .with(ask::AskCommand);
+        let mut restrictable: Vec<String> = registry
+            .commands
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        restrictable.push(config::COMMAND_NAME.to_string());
+        registry.with(config::ConfigCommand::new(restrictable))
+    }
+
+    pub fn with(mut self, command: impl Command + 'static) -> Self {
+        self.commands.push(Box::new(command));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Command> {
+        self.commands
+            .iter()
+            .find(|c| c.name() == name)
+            .map(|c| c.as_ref())
+    }
+
+    fn definitions(&self, scope: Scope) -> Vec<CreateCommand> {
+        self.commands
+            .iter()
+            .map(|c| (c, i18n::localize_command(c.register(), c.name())))
+            .map(|(c, definition)| match scope {
+                Scope::Global if c.user_installable() => definition
+                    .integration_types(vec![InstallationContext::Guild, InstallationContext::User])
+                    .contexts(vec![
+                        InteractionContext::Guild,
+                        InteractionContext::BotDm,
+                        InteractionContext::PrivateChannel,
+                    ]),
+                _ => definition,
+            })
+            .collect()
+    }
+
+    /// Create, update or delete guild commands so they match this registry.
+    pub async fn register_guild(&self, http: &Http, guild_id: GuildId) -> SyncReport {
+        let scope = Scope::Guild(guild_id);
+        registration::sync(http, scope, self.definitions(scope)).await
+    }
+
+    /// Create, update or delete global commands so they match this registry.
+    pub async fn register_global(&self, http: &Http) -> SyncReport {
+        registration::sync(http, Scope::Global, self.definitions(Scope::Global)).await
+    }
+
+    /// Run the matching command and send its response. Returns false when the command is
+    /// unknown or returned an error; errors are logged and shown to the user ephemerally.
+    pub async fn dispatch(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+        finance: &FinanceService,
+        cache: Option<&RedisCache>,
+        store: Option<&dyn StateStore>,
+        config: &ConfigHandle,
+    ) -> bool {
+        let locale = i18n::guild_locale(store, interaction.guild_id).await;
+        let Some(command) = self.get(&interaction.data.name) else {
+            let _ = interaction
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(Text::CommandNotImplemented.get(locale)),
+                    ),
+                )
+                .await;
+            return false;
+        };
+
+        let invoker = Invoker::from_member(interaction.guild_id, interaction.member.as_deref());
+        if let Err(err) = check_roles(command.name(), invoker.as_ref(), store, locale).await {
+            err.log(command.name());
+            if let Err(send_err) = send_error(
+                ctx,
+                interaction,
+                Deferral::None,
+                err.localized_message(locale),
+            )
+            .await
+            {
+                warn!("failed to send /{} denial: {send_err}", command.name());
+            }
+            return false;
+        }
+
+        let deferral = self.deferral_for(command, interaction, &config.current());
+        if deferral != Deferral::None {
+            // Defer immediately to avoid the 3-second timeout.
+            let defer =
+                CreateInteractionResponseMessage::new().ephemeral(deferral == Deferral::Ephemeral);
+            let _ = interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Defer(defer))
+                .await;
+        }
+
+        let bound = with_bound_ticker(command, interaction, store).await;
+        let command_ctx = CommandContext {
+            ctx,
+            interaction: bound.as_ref().unwrap_or(interaction),
+            finance,
+            cache,
+            store,
+            config,
+            locale,
+        };
+        let response = match command.execute(&command_ctx).await {
+            Ok(response) => response,
+            Err(err) => {
+                err.log(command.name());
+                if let Err(send_err) =
+                    send_error(ctx, interaction, deferral, err.localized_message(locale)).await
+                {
+                    warn!("failed to send /{} error: {send_err}", command.name());
+                }
+                audit_use(command, ctx, interaction, store, Err(err.user_message())).await;
+                return false;
+            }
+        };
+        let summary = response
+            .content
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        // Content over Discord's limit continues in follow-ups after the first message.
+        let mut parts = chunks::split(&response.content, chunks::MESSAGE_LIMIT).into_iter();
+        let first = parts.next().unwrap_or_default();
+        let sent = if deferral == Deferral::None {
+            let message = CreateInteractionResponseMessage::new()
+                .content(first)
+                .embeds(response.embeds)
+                .add_files(response.attachments)
+                .components(response.components);
+            interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+                .await
+        } else {
+            let mut edit = EditInteractionResponse::new()
+                .content(first)
+                .embeds(response.embeds)
+                .components(response.components);
+            if !response.attachments.is_empty() {
+                let attachments = response
+                    .attachments
+                    .into_iter()
+                    .fold(EditAttachments::new(), |acc, a| acc.add(a));
+                edit = edit.attachments(attachments);
+            }
+            interaction.edit_response(&ctx.http, edit).await.map(|_| ())
+        };
+
+        match sent {
+            Ok(()) => {
+                for part in parts {
+                    let followup = CreateInteractionResponseFollowup::new()
+                        .content(part)
+                        .ephemeral(deferral == Deferral::Ephemeral);
+                    if let Err(err) = interaction.create_followup(&ctx.http, followup).await {
+                        warn!("failed to send /{} continuation: {err}", command.name());
+                        break;
+                    }
+                }
+            }
+            Err(err) => warn!("failed to send /{} response: {err}", command.name()),
+        }
+        audit_use(command, ctx, interaction, store, Ok(&summary)).await;
+        true
+    }
+
+    /// Run the component handler of the command named by the `custom_id` prefix and replace
+    /// the component's message with the result. Errors are shown only to the user who used
+    /// the component. The command's `/config restrict` roles apply to the user of the
+    /// component, who need not be the one who ran the command.
+    pub async fn dispatch_component(
+        &self,
+        ctx: &Context,
+        interaction: &ComponentInteraction,
+        finance: &FinanceService,
+        cache: Option<&RedisCache>,
+        store: Option<&dyn StateStore>,
+        config: &ConfigHandle,
+    ) -> bool {
+        let name = interaction
+            .data
+            .custom_id
+            .split_once(':')
+            .map_or(interaction.data.custom_id.as_str(), |(name, _)| name);
+        let Some(command) = self.get(name) else {
+            warn!("component {} has no command", interaction.data.custom_id);
+            let _ = interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+                .await;
+            return false;
+        };
+
+        // Acknowledge right away; the response edits the message in place.
+        let _ = interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+            .await;
+
+        let locale = i18n::guild_locale(store, interaction.guild_id).await;
+        let invoker = Invoker::from_member(interaction.guild_id, interaction.member.as_ref());
+        if let Err(err) = check_roles(command.name(), invoker.as_ref(), store, locale).await {
+            err.log(command.name());
+            let followup = CreateInteractionResponseFollowup::new()
+                .content(err.localized_message(locale))
+                .ephemeral(true);
+            if let Err(send_err) = interaction.create_followup(&ctx.http, followup).await {
+                warn!("failed to send /{} denial: {send_err}", command.name());
+            }
+            return false;
+        }
+
+        let component_ctx = ComponentContext {
+            ctx,
+            interaction,
+            finance,
+            cache,
+            store,
+            config,
+            locale,
+        };
+        let response = match command.component(&component_ctx).await {
+            Ok(response) => response,
+            Err(err) => {
+                err.log(command.name());
+                let followup = CreateInteractionResponseFollowup::new()
+                    .content(err.localized_message(locale))
+                    .ephemeral(true);
+                if let Err(send_err) = interaction.create_followup(&ctx.http, followup).await {
+                    warn!(
+                        "failed to send /{} component error: {send_err}",
+                        command.name()
+                    );
+                }
+                return false;
+            }
+        };
+
+        if response.followup {
+            // Follow-ups to an ephemeral message stay visible only to the same user.
+            let ephemeral = interaction
+                .message
+                .flags
+                .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL));
+            let followup = CreateInteractionResponseFollowup::new()
+                .content(response.content)
+                .embeds(response.embeds)
+                .add_files(response.attachments)
+                .ephemeral(ephemeral);
+            if let Err(err) = interaction.create_followup(&ctx.http, followup).await {
+                warn!("failed to send /{} follow-up: {err}", command.name());
+            }
+            return true;
+        }
+
+        // Files not in the response are removed, so a text view replaces an image.
+        let attachments = response
+            .attachments
+            .into_iter()
+            .fold(EditAttachments::new(), |acc, a| acc.add(a));
+        let edit = EditInteractionResponse::new()
+            .content(response.content)
+            .embeds(response.embeds)
+            .attachments(attachments)
+            .components(response.components);
+        if let Err(err) = interaction.edit_response(&ctx.http, edit).await {
+            warn!("failed to update /{} message: {err}", command.name());
+        }
+        true
+    }
+
+    /// Publicly deferred commands become ephemeral when the user asks for it, or by default
+    /// in guilds listed in `responses.ephemeral_guild_ids`.
+    fn deferral_for(
+        &self,
+        command: &dyn Command,
+        interaction: &CommandInteraction,
+        config: &Config,
+    ) -> Deferral {
+        let deferral = command.deferral();
+        if deferral != Deferral::Public {
+            return deferral;
+        }
+
+        let requested = interaction
+            .data
+            .options
+            .iter()
+            .find(|o| o.name == EPHEMERAL_OPTION)
+            .and_then(|o| match o.value {
+                CommandDataOptionValue::Boolean(b) => Some(b),
+                _ => None,
+            });
+        let guild_default = interaction
+            .guild_id
+            .is_some_and(|id| config.is_ephemeral_guild(id));
+
+        if requested.unwrap_or(guild_default) {
+            Deferral::Ephemeral
+        } else {
+            Deferral::Public
+        }
+    }
+}
+
+async fn audit_use(
+    command: &dyn Command,
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    store: Option<&dyn StateStore>,
+    outcome: Result<&str, String>,
+) {
+    let Some(guild_id) = interaction.guild_id.filter(|_| command.audited()) else {
+        return;
+    };
+    let event = AuditEvent::Command {
+        user: interaction.user.id,
+        invocation: audit::describe_invocation(interaction),
+        outcome,
+    };
+    audit::record(&ctx.http, store, guild_id, event).await;
+}
+
+/// Who is running a command in a guild, as far as `/config restrict` cares.
+#[derive(Debug, Clone)]
+pub struct Invoker {
+    pub guild_id: GuildId,
+    pub roles: Vec<RoleId>,
+    /// Has Manage Server or Administrator, which no restriction applies to.
+    pub manager: bool,
+}
+
+impl Invoker {
+    /// From an interaction's member, whose permissions Discord resolves. `None` outside a
+    /// guild.
+    pub fn from_member(guild_id: Option<GuildId>, member: Option<&Member>) -> Option<Self> {
+        let (guild_id, member) = (guild_id?, member?);
+        Some(Self {
+            guild_id,
+            roles: member.roles.clone(),
+            manager: member
+                .permissions
+                .is_some_and(|p| p.administrator() || p.manage_guild()),
+        })
+    }
+
+    /// The author of a guild message, for mention commands. Messages carry no permissions, so
+    /// Manage Server is worked out from the cached guild's roles; without it the author is
+    /// treated as a regular member.
+    pub async fn from_message(ctx: &Context, msg: &Message) -> Option<Self> {
+        let guild_id = msg.guild_id?;
+        let roles = match msg.member.as_deref() {
+            Some(member) => member.roles.clone(),
+            None => match guild_id.member(&ctx.http, msg.author.id).await {
+                Ok(member) => member.roles,
+                Err(e) => {
+                    warn!("failed to load member {} of {guild_id}: {e}", msg.author.id);
+                    Vec::new()
+                }
+            },
+        };
+        let manager = ctx
+            .cache
+            .guild(guild_id)
+            .is_some_and(|guild| guild_manager(&guild, msg.author.id, &roles));
+        Some(Self {
+            guild_id,
+            roles,
+            manager,
+        })
+    }
+
+    /// Whether a command limited to `allowed` roles (none means everyone) may run.
+    pub fn allowed(&self, allowed: &[u64]) -> bool {
+        self.manager || allowed.is_empty() || self.roles.iter().any(|r| allowed.contains(&r.get()))
+    }
+}
+
+/// Guild-wide Manage Server or Administrator from the owner and role permissions; channel
+/// overwrites can't grant either.
+fn guild_manager(guild: &Guild, user_id: UserId, roles: &[RoleId]) -> bool {
+    if guild.owner_id == user_id {
+        return true;
+    }
+    // The @everyone role shares the guild's id.
+    let everyone = RoleId::new(guild.id.get());
+    roles
+        .iter()
+        .chain(std::iter::once(&everyone))
+        .filter_map(|id| guild.roles.get(id))
+        .any(|role| role.permissions.administrator() || role.permissions.manage_guild())
+}
+
+/// Enforce the guild's `/config restrict` roles for `command`. Managers always pass so a
+/// guild can't lock its admins out; without a state store or outside a guild nothing is
+/// restricted. When the roles can't be loaded the command is denied rather than run
+/// unrestricted.
+pub async fn check_roles(
+    command: &str,
+    invoker: Option<&Invoker>,
+    store: Option<&dyn StateStore>,
+    locale: Locale,
+) -> BotResult<()> {
+    let (Some(store), Some(invoker)) = (store, invoker) else {
+        return Ok(());
+    };
+    if invoker.manager {
+        return Ok(());
+    }
+
+    let allowed = store
+        .command_roles(invoker.guild_id.get(), command)
+        .await
+        .map_err(|e| {
+            BotError::internal(format!(
+                "failed to load role restrictions for /{command}: {e}"
+            ))
+        })?;
+    if invoker.allowed(&allowed) {
+        return Ok(());
+    }
+
+    let roles = allowed
+        .iter()
+        .map(|id| format!("<@&{id}>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(BotError::Forbidden(format!(
+        "/{command} {} {roles}",
+        Text::RestrictedToRoles.get(locale)
+    )))
+}
+
+/// Errors are only shown to the invoking user. A public "thinking…" placeholder can't be
+/// made ephemeral after the fact, so it is removed and the error sent as a follow-up.
+async fn send_error(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    deferral: Deferral,
+    message: String,
+) -> serenity::Result<()> {
+    match deferral {
+        Deferral::None => {
+            let message = CreateInteractionResponseMessage::new()
+                .content(message)
+                .ephemeral(true);
+            interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+                .await
+        }
+        Deferral::Ephemeral => interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(message))
+            .await
+            .map(|_| ()),
+        Deferral::Public => {
+            interaction.delete_response(&ctx.http).await?;
+            let followup = CreateInteractionResponseFollowup::new()
+                .content(message)
+                .ephemeral(true);
+            interaction
+                .create_followup(&ctx.http, followup)
+                .await
+                .map(|_| ())
+        }
+    }
+}
@@ -1,22 +1,33 @@
 use chrono::{Datelike, NaiveDate, Utc};
-use serenity::all::{
-    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateCommand,
-    CreateCommandOption,
-};
+use serde_json::Value;
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
 
 use ab_glyph::{FontArc, PxScale};
 use font_kit::family_name::FamilyName;
 use font_kit::properties::{Properties, Weight};
 use font_kit::source::SystemSource;
-use image::{ImageFormat, Rgba, RgbaImage};
+use image::{DynamicImage, Rgba, RgbaImage};
 use imageproc::drawing::draw_text_mut;
-use std::io::Cursor;
+use std::collections::BTreeSet;
 
 use crate::models::{Frequency, StatementType};
+use crate::service::config::ImageConfig;
 use crate::service::finance::{
+    exchange::Exchange,
     fundamentals::{reshape_timeseries_to_financial_statements, FETCH_YEARS_DEFAULT},
+    fx::Conversion,
     FinanceService,
 };
+use crate::service::format;
+use crate::service::growth::{self, Growth};
+use crate::service::render::{self, EncodedImage};
+
+use super::args::{get_i64_opt, get_str_opt};
+use super::error::{BotError, BotResult};
+use super::export::{export_option, ExportFormat, Table};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
 
 #[derive(Debug, Clone)]
 struct MetricSpec {
@@ -124,10 +135,39 @@ fn normalize_freq(raw: &str) -> (Frequency, bool) {
     match norm.as_str() {
         "annual" => (Frequency::Annual, false),
         "quarterly" => (Frequency::Quarterly, false),
+        "ttm" => (Frequency::Ttm, false),
         _ => (Frequency::Annual, true), // default to annual if unrecognized
     }
 }
 
+/// Balance sheets are point-in-time, so they have no trailing-twelve-month figures.
+fn check_frequency(statement_type: StatementType, freq: Frequency) -> BotResult<()> {
+    if freq == Frequency::Ttm && statement_type == StatementType::BalanceSheet {
+        return Err(BotError::bad_input(
+            "ttm only applies to income and cash flow statements",
+        ));
+    }
+    Ok(())
+}
+
+/// The `freq` option; TTM is offered unless the statement is a balance sheet.
+fn freq_option(ttm: bool) -> CreateCommandOption {
+    let description = if ttm {
+        "annual, quarterly or ttm (last four quarters)"
+    } else {
+        "annual or quarterly"
+    };
+    let option = CreateCommandOption::new(CommandOptionType::String, "freq", description)
+        .required(true)
+        .add_string_choice("Annual", "annual")
+        .add_string_choice("Quarterly", "quarterly");
+    if ttm {
+        option.add_string_choice("Trailing twelve months", "ttm")
+    } else {
+        option
+    }
+}
+
 pub fn register_command(statement_type: StatementType) -> CreateCommand {
     let (cmd_name, description) = match statement_type {
         StatementType::IncomeStatement => (
@@ -166,12 +206,7 @@ pub fn register_command(statement_type: StatementType) -> CreateCommand {
             }
             opt
         })
-        .add_option(
-            CreateCommandOption::new(CommandOptionType::String, "freq", "annual or quarterly")
-                .required(true)
-                .add_string_choice("Annual", "annual")
-                .add_string_choice("Quarterly", "quarterly"),
-        )
+        .add_option(freq_option(statement_type != StatementType::BalanceSheet))
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::Integer,
@@ -184,32 +219,37 @@ pub fn register_command(statement_type: StatementType) -> CreateCommand {
             CreateCommandOption::new(
                 CommandOptionType::String,
                 "quarter",
-                "Quarter (Q1-Q4, only with quarterly)",
+                "Quarter (Q1-Q4, only with quarterly or ttm)",
             )
             .add_string_choice("Q1", "Q1")
             .add_string_choice("Q2", "Q2")
             .add_string_choice("Q3", "Q3")
             .add_string_choice("Q4", "Q4")
         })
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "currency",
+            "Convert amounts to this currency, e.g., USD",
+        ))
+        .add_option(export_option())
+        .add_option(ephemeral_option())
 }
 
 pub async fn handle(
     command: &CommandInteraction,
     finance: &FinanceService,
-) -> Result<String, String> {
-    let ticker = get_str_opt(command, "ticker").ok_or("ticker is required")?;
-    let metric_val = get_str_opt(command, "metric").ok_or("metric is required")?;
-    let freq_val = get_str_opt(command, "freq").ok_or("freq is required")?;
+) -> BotResult<String> {
+    let ticker = get_str_opt(command, "ticker")
+        .ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let metric_val = get_str_opt(command, "metric")
+        .ok_or_else(|| BotError::bad_input("metric is required"))?;
+    let freq_val = get_str_opt(command, "freq")
+        .ok_or_else(|| BotError::bad_input("freq is required"))?;
     let year = get_i64_opt(command, "year").map(|v| v as i32);
     let quarter = get_str_opt(command, "quarter");
+    let currency = get_str_opt(command, "currency");
 
-    // Determine statement type from command name
-    let statement_type = match command.data.name.as_str() {
-        "income" => StatementType::IncomeStatement,
-        "balance" => StatementType::BalanceSheet,
-        "cashflow" => StatementType::CashFlow,
-        _ => return Err("unknown command".into()),
-    };
+    let statement_type = statement_type_for(command)?;
 
     handle_text(
         finance,
@@ -219,10 +259,166 @@ pub async fn handle(
         freq_val,
         year,
         quarter,
+        currency,
     )
     .await
 }
 
+/// Determine statement type from command name
+fn statement_type_for(command: &CommandInteraction) -> BotResult<StatementType> {
+    match command.data.name.as_str() {
+        "income" => Ok(StatementType::IncomeStatement),
+        "balance" => Ok(StatementType::BalanceSheet),
+        "cashflow" => Ok(StatementType::CashFlow),
+        _ => Err(BotError::internal("unknown fundamentals command")),
+    }
+}
+
+/// Every metric of the statement for every fetched period as a
+/// `<SYMBOL>-<statement>-<freq>.csv|json` file, one row per period (newest first) and one
+/// column per metric. `metric` is ignored; `year` and `quarter` narrow the periods.
+pub async fn handle_export(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    format: ExportFormat,
+) -> BotResult<CommandResponse> {
+    let statement_type = statement_type_for(command)?;
+    let ticker = get_str_opt(command, "ticker")
+        .ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let (freq, _) = normalize_freq(get_str_opt(command, "freq").unwrap_or("annual"));
+    let year = get_i64_opt(command, "year").map(|v| v as i32);
+    let quarter_num = match freq {
+        Frequency::Annual => None,
+        Frequency::Quarterly | Frequency::Ttm => get_str_opt(command, "quarter").and_then(|q| match q {
+            "Q1" => Some(1),
+            "Q2" => Some(2),
+            "Q3" => Some(3),
+            "Q4" => Some(4),
+            _ => None,
+        }),
+    };
+    let currency = get_str_opt(command, "currency");
+    export_statement(
+        finance,
+        statement_type,
+        ticker,
+        freq,
+        year,
+        quarter_num,
+        currency,
+        format,
+    )
+    .await
+}
+
+/// Fetch, reshape and convert a statement, then attach it as a table in `format`.
+#[allow(clippy::too_many_arguments)]
+async fn export_statement(
+    finance: &FinanceService,
+    statement_type: StatementType,
+    ticker: &str,
+    freq: Frequency,
+    year: Option<i32>,
+    quarter_num: Option<u32>,
+    currency: Option<&str>,
+    format: ExportFormat,
+) -> BotResult<CommandResponse> {
+    check_frequency(statement_type, freq)?;
+    let target = currency.map(symbol::parse_currency).transpose()?;
+    let ticker = &symbol::resolve(finance, ticker).await?;
+
+    let years_back = year
+        .map(|y| {
+            let current_year = Utc::now().year();
+            (current_year - y + 1).max(FETCH_YEARS_DEFAULT as i32) as i64
+        })
+        .unwrap_or(FETCH_YEARS_DEFAULT);
+    let raw = finance
+        .get_fundamentals_raw(ticker, statement_type, freq, years_back)
+        .await?;
+    let statements = reshape_timeseries_to_financial_statements(&raw);
+    let fx = conversion_for(finance, &statements, ticker, target.as_deref()).await?;
+
+    let table = statement_table(&statements, statement_type, freq, year, quarter_num, &fx)
+        .ok_or_else(|| BotError::not_found("no matching data for the requested filters"))?;
+    let stem = format!(
+        "{}-{}-{}",
+        ticker.to_uppercase(),
+        statement_type.as_str(),
+        freq.as_str()
+    );
+    let attachment = table.attachment(format, &stem)?;
+
+    let summary = format!(
+        "{} {} periods of {} data for {} in {}",
+        table.len(),
+        freq.as_str(),
+        statement_type.as_str(),
+        ticker.to_uppercase(),
+        fx.to
+    );
+    let summary = match fx.note() {
+        Some(note) => format!("{summary} ({note})"),
+        None => summary,
+    };
+    Ok(CommandResponse::text(summary).with_attachment(attachment))
+}
+
+/// One row per period of the statement, newest first, with a column per metric.
+pub fn statement_table(
+    statements: &[crate::models::FinancialStatement],
+    statement_type: StatementType,
+    frequency: Frequency,
+    year: Option<i32>,
+    quarter: Option<u32>,
+    fx: &Conversion,
+) -> Option<Table> {
+    let stmt = statements.iter().find(|s| {
+        s.statement_type == statement_type.as_str() && s.frequency == frequency.as_str()
+    })?;
+
+    let dates: BTreeSet<NaiveDate> = stmt
+        .statement
+        .values()
+        .flat_map(|series| series.keys())
+        .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .filter(|d| year.is_none_or(|y| d.year() == y))
+        .filter(|d| quarter.is_none_or(|q| (d.month() - 1) / 3 + 1 == q))
+        .collect();
+    if dates.is_empty() {
+        return None;
+    }
+
+    let mut metrics: Vec<&String> = stmt.statement.keys().collect();
+    metrics.sort();
+
+    let mut table = Table::new(
+        ["date".to_string(), "currency".to_string()]
+            .into_iter()
+            .chain(metrics.iter().map(|m| to_snake(m))),
+    );
+    for date in dates.iter().rev() {
+        let key = date.format("%Y-%m-%d").to_string();
+        let mut row = vec![Value::from(key.clone()), Value::from(fx.to.clone())];
+        for metric in &metrics {
+            let value = stmt.statement[*metric]
+                .get(&key)
+                .and_then(raw_value)
+                .map(|raw| {
+                    if is_amount(metric) {
+                        fx.apply(raw)
+                    } else {
+                        raw
+                    }
+                });
+            row.push(value.map(Value::from).unwrap_or(Value::Null));
+        }
+        table.push(row);
+    }
+    Some(table)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_text(
     finance: &FinanceService,
     statement_type: StatementType,
@@ -231,15 +427,18 @@ pub async fn handle_text(
     freq_val: &str,
     year: Option<i32>,
     quarter: Option<&str>,
-) -> Result<String, String> {
+    currency: Option<&str>,
+) -> BotResult<String> {
+    let target = currency.map(symbol::parse_currency).transpose()?;
+    let ticker = &symbol::resolve(finance, ticker).await?;
     let mut corrections = Vec::new();
 
     let (metric, metric_corrected) =
         normalize_metric_value(statement_type, metric_val).map_err(|_| {
-            format!(
+            BotError::bad_input(format!(
                 "unknown metric '{}'; try one of the slash choices for this statement",
                 metric_val
-            )
+            ))
         })?;
     if metric_corrected {
         corrections.push(format!("metric→{}", metric.slash_value));
@@ -247,8 +446,9 @@ pub async fn handle_text(
 
     let (freq, freq_corrected) = normalize_freq(freq_val);
     if freq_corrected {
-        corrections.push(format!("freq→{}", match freq { Frequency::Annual => "annual", Frequency::Quarterly => "quarterly", }));
+        corrections.push(format!("freq→{}", freq.as_str()));
     }
+    check_frequency(statement_type, freq)?;
 
     let quarter_num = quarter.and_then(|q| match q {
         "Q1" => Some(1),
@@ -264,22 +464,23 @@ pub async fn handle_text(
             }
             None
         }
-        Frequency::Quarterly => quarter_num,
+        Frequency::Quarterly | Frequency::Ttm => quarter_num,
     };
 
+    // Reach three years before a requested year too, for its growth rates.
     let years_back = year
         .map(|y| {
             let current_year = Utc::now().year();
-            (current_year - y + 1).max(FETCH_YEARS_DEFAULT as i32) as i64
+            (current_year - y + 4).max(FETCH_YEARS_DEFAULT as i32) as i64
         })
         .unwrap_or(FETCH_YEARS_DEFAULT);
 
     let raw = finance
         .get_fundamentals_raw(ticker, statement_type, freq, years_back)
-        .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+        .await?;
 
     let statements = reshape_timeseries_to_financial_statements(&raw);
+    let fx = conversion_for(finance, &statements, ticker, target.as_deref()).await?;
     let selected = select_metric(
         &statements,
         statement_type,
@@ -287,26 +488,30 @@ pub async fn handle_text(
         &metric.field_key,
         year,
         quarter_num,
+        &fx,
     )
-    .ok_or_else(|| "no matching data for the requested filters".to_string())?;
+    .ok_or_else(|| BotError::not_found("no matching data for the requested filters"))?;
 
-    let (date, display) = selected;
     let quarter_text = quarter.map(|q| format!("{q} ")).unwrap_or_default();
 
-    let freq_label = match freq {
-        Frequency::Annual => "annual",
-        Frequency::Quarterly => "quarterly",
-    };
-
     let response = format!(
         "{} ({}) for {} {}on {}: {}",
         metric.label,
-        freq_label,
+        freq.as_str(),
         ticker.to_uppercase(),
         quarter_text,
-        date,
-        display
+        selected.date,
+        selected.display
     );
+    let response = match selected.growth.summary() {
+        Some(growth) => format!("{response} · {growth}"),
+        None => response,
+    };
+
+    let response = match fx.note() {
+        Some(note) => format!("{response} ({note})"),
+        None => response,
+    };
 
     if corrections.is_empty() {
         Ok(response)
@@ -315,6 +520,7 @@ pub async fn handle_text(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn render_statement_image(
     finance: &FinanceService,
     statement_type: StatementType,
@@ -322,8 +528,13 @@ pub async fn render_statement_image(
     freq_val: &str,
     year: Option<i32>,
     quarter: Option<&str>,
-) -> Result<(String, Vec<u8>), String> {
+    currency: Option<&str>,
+    images: &ImageConfig,
+) -> BotResult<(String, EncodedImage)> {
+    let target = currency.map(symbol::parse_currency).transpose()?;
+    let ticker = &symbol::resolve(finance, ticker).await?;
     let (freq, _) = normalize_freq(freq_val);
+    check_frequency(statement_type, freq)?;
 
     let quarter_num = quarter.and_then(|q| match q {
         "Q1" => Some(1),
@@ -334,7 +545,7 @@ pub async fn render_statement_image(
     });
     let quarter_num = match freq {
         Frequency::Annual => None,
-        Frequency::Quarterly => quarter_num,
+        Frequency::Quarterly | Frequency::Ttm => quarter_num,
     };
 
     let years_back = year
@@ -346,23 +557,19 @@ pub async fn render_statement_image(
 
     let raw = finance
         .get_fundamentals_raw(ticker, statement_type, freq, years_back)
-        .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+        .await?;
 
     let statements = reshape_timeseries_to_financial_statements(&raw);
+    let fx = conversion_for(finance, &statements, ticker, target.as_deref()).await?;
     let (date, rows) = select_statement_rows(
         &statements,
         statement_type,
         freq,
         year,
         quarter_num,
+        &fx,
     )
-    .ok_or_else(|| "no matching data for the requested filters".to_string())?;
-
-    let freq_label = match freq {
-        Frequency::Annual => "annual",
-        Frequency::Quarterly => "quarterly",
-    };
+    .ok_or_else(|| BotError::not_found("no matching data for the requested filters"))?;
 
     let title = format!(
         "{} ({}) for {} on {}",
@@ -371,30 +578,58 @@ pub async fn render_statement_image(
             StatementType::BalanceSheet => "Balance Sheet",
             StatementType::CashFlow => "Cash Flow",
         },
-        freq_label,
+        freq.as_str(),
         ticker.to_uppercase(),
         date
     );
 
-    let image = render_rows_image(&title, &rows)?;
+    let image = {
+        let title = title.clone();
+        let images = images.clone();
+        render::blocking("statement", move || {
+            render::encode(&render_rows_image(&title, &rows)?, &images)
+        })
+        .await?
+    };
+    let title = match fx.note() {
+        Some(note) => format!("{title} ({note})"),
+        None => title,
+    };
     Ok((title, image))
 }
 
+/// Conversion from the statement's reporting currency to `target`, or the identity when no
+/// target was requested. Statements without a currency code fall back to the listing's.
+async fn conversion_for(
+    finance: &FinanceService,
+    statements: &[crate::models::FinancialStatement],
+    ticker: &str,
+    target: Option<&str>,
+) -> BotResult<Conversion> {
+    let reported = statements
+        .iter()
+        .flat_map(|s| s.statement.values())
+        .flat_map(|series| series.values())
+        .find_map(|val| val.get("currencyCode").and_then(|c| c.as_str()))
+        .unwrap_or(Exchange::for_symbol(ticker).currency);
+
+    match target {
+        Some(target) => Ok(finance.conversion(reported, target).await?),
+        None => Ok(Conversion::identity(reported)),
+    }
+}
+
 fn select_statement_rows(
     statements: &[crate::models::FinancialStatement],
     statement_type: StatementType,
     frequency: Frequency,
     year: Option<i32>,
     quarter: Option<u32>,
+    fx: &Conversion,
 ) -> Option<(String, Vec<(String, String)>)> {
-    let freq_str = match frequency {
-        Frequency::Annual => "annual",
-        Frequency::Quarterly => "quarterly",
-    };
-
-    let stmt = statements
-        .iter()
-        .find(|s| s.statement_type == statement_type.as_str() && s.frequency == freq_str)?;
+    let stmt = statements.iter().find(|s| {
+        s.statement_type == statement_type.as_str() && s.frequency == frequency.as_str()
+    })?;
 
     // Choose the best date entry based on year/quarter filters
     let mut best: Option<(NaiveDate, String)> = None;
@@ -425,7 +660,7 @@ fn select_statement_rows(
     let mut rows = Vec::new();
     for (metric, series) in stmt.statement.iter() {
         if let Some(val) = series.get(&best_date) {
-            let display = extract_display(val);
+            let display = extract_display(metric, val, fx);
             rows.push((metric.clone(), display));
         }
     }
@@ -437,7 +672,7 @@ fn select_statement_rows(
     Some((best_date, rows))
 }
 
-fn render_rows_image(title: &str, rows: &[(String, String)]) -> Result<Vec<u8>, String> {
+fn render_rows_image(title: &str, rows: &[(String, String)]) -> Result<DynamicImage, String> {
     let font = load_font()?;
     let header_scale = PxScale::from(28.0);
     let row_scale = PxScale::from(20.0);
@@ -474,12 +709,7 @@ fn render_rows_image(title: &str, rows: &[(String, String)]) -> Result<Vec<u8>,
         y += line_h;
     }
 
-    let mut buffer = Vec::new();
-    image::DynamicImage::ImageRgba8(img)
-        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
-        .map_err(|e| format!("failed to encode png: {e}"))?;
-
-    Ok(buffer)
+    Ok(DynamicImage::ImageRgba8(img))
 }
 
 fn load_font() -> Result<FontArc, String> {
@@ -504,6 +734,14 @@ fn load_font() -> Result<FontArc, String> {
     FontArc::try_from_vec(font_data).map_err(|_| "Failed to create FontArc from system font".into())
 }
 
+/// The latest value of a metric that matches the year and quarter filters.
+struct SelectedMetric {
+    date: String,
+    display: String,
+    /// Against the same period one and three years earlier, when those were fetched.
+    growth: Growth,
+}
+
 fn select_metric(
     statements: &[crate::models::FinancialStatement],
     statement_type: StatementType,
@@ -511,15 +749,11 @@ fn select_metric(
     metric: &str,
     year: Option<i32>,
     quarter: Option<u32>,
-) -> Option<(String, String)> {
-    let freq_str = match frequency {
-        Frequency::Annual => "annual",
-        Frequency::Quarterly => "quarterly",
-    };
-
-    let stmt = statements
-        .iter()
-        .find(|s| s.statement_type == statement_type.as_str() && s.frequency == freq_str)?;
+    fx: &Conversion,
+) -> Option<SelectedMetric> {
+    let stmt = statements.iter().find(|s| {
+        s.statement_type == statement_type.as_str() && s.frequency == frequency.as_str()
+    })?;
 
     let metric_map = stmt.statement.get(metric)?;
 
@@ -540,7 +774,7 @@ fn select_metric(
                 }
             }
 
-            let display = extract_display(val);
+            let display = extract_display(metric, val, fx);
 
             match &best {
                 Some((best_date, _, _)) if nd <= *best_date => {}
@@ -549,17 +783,53 @@ fn select_metric(
         }
     }
 
-    best.map(|(_, display, date)| (date, display))
+    let (end, display, date) = best?;
+    // A rate's percentage change reads as noise; conversion scales every period alike.
+    let growth = if metric.contains("TaxRate") {
+        Growth::default()
+    } else {
+        let points: Vec<(NaiveDate, f64)> = metric_map
+            .iter()
+            .filter_map(|(date, val)| {
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                Some((date, raw_value(val)?))
+            })
+            .collect();
+        growth::growth_at(&points, end)
+    };
+    Some(SelectedMetric {
+        date,
+        display,
+        growth,
+    })
 }
 
-fn extract_display(val: &serde_json::Value) -> String {
-    if let Some(raw) = val
-        .get("reportedValue")
+fn raw_value(val: &serde_json::Value) -> Option<f64> {
+    val.get("reportedValue")
         .and_then(|rv| rv.get("raw"))
         .and_then(|r| r.as_f64())
         .or_else(|| val.get("raw").and_then(|r| r.as_f64()))
-    {
-        return format!("{:.2}B", raw / 1_000_000_000.0);
+}
+
+/// Whether `metric` is a money amount (including per-share ones) that currency conversion
+/// applies to, rather than a share count or a rate.
+fn is_amount(metric: &str) -> bool {
+    !(metric.contains("Shares") || metric.contains("ShareIssued") || metric.contains("TaxRate"))
+}
+
+/// Format a statement value according to what the metric measures, converting monetary
+/// amounts with `fx`.
+fn extract_display(metric: &str, val: &serde_json::Value, fx: &Conversion) -> String {
+    if let Some(raw) = raw_value(val) {
+        return if metric.ends_with("EPS") {
+            format::per_share_in(fx.apply(raw), &fx.to)
+        } else if metric.contains("Shares") || metric.contains("ShareIssued") {
+            format::shares(raw)
+        } else if metric.contains("TaxRate") {
+            format::fraction_percent(raw)
+        } else {
+            format::currency_in(fx.apply(raw), &fx.to)
+        };
     }
 
     val.get("reportedValue")
@@ -569,26 +839,107 @@ fn extract_display(val: &serde_json::Value) -> String {
         .unwrap_or_else(|| "n/a".to_string())
 }
 
-fn get_str_opt<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        })
+pub fn register_statement_command() -> CreateCommand {
+    CreateCommand::new("statement")
+        .description("Download a full financial statement (all metrics, all periods)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "ticker",
+                "Ticker symbol, e.g., AAPL",
+            )
+            .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "type", "Which statement")
+                .required(true)
+                .add_string_choice("Income statement", "income")
+                .add_string_choice("Balance sheet", "balance")
+                .add_string_choice("Cash flow", "cashflow"),
+        )
+        .add_option(freq_option(true))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "currency",
+            "Convert amounts to this currency, e.g., USD",
+        ))
+        .add_option(export_option())
+        .add_option(ephemeral_option())
 }
 
-fn get_i64_opt(command: &CommandInteraction, name: &str) -> Option<i64> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::Integer(v) => Some(v),
-            _ => None,
-        })
+/// `/statement`: the whole reshaped statement as a file, CSV unless `format:json` is picked.
+pub async fn handle_statement(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker = get_str_opt(command, "ticker")
+        .ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let statement_type = match get_str_opt(command, "type") {
+        Some("income") => StatementType::IncomeStatement,
+        Some("balance") => StatementType::BalanceSheet,
+        Some("cashflow") => StatementType::CashFlow,
+        _ => {
+            return Err(BotError::bad_input(
+                "type must be income | balance | cashflow",
+            ))
+        }
+    };
+    let (freq, _) = normalize_freq(get_str_opt(command, "freq").unwrap_or("annual"));
+    let currency = get_str_opt(command, "currency");
+    let format = ExportFormat::requested(command).unwrap_or(ExportFormat::Csv);
+    export_statement(
+        finance,
+        statement_type,
+        ticker,
+        freq,
+        None,
+        None,
+        currency,
+        format,
+    )
+    .await
+}
+
+pub struct StatementCommand;
+
+#[async_trait]
+impl Command for StatementCommand {
+    fn name(&self) -> &str {
+        "statement"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_statement_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle_statement(ctx.interaction, ctx.finance).await
+    }
+}
+
+/// `/income`, `/balance` and `/cashflow` share one implementation.
+pub struct FundamentalsCommand(pub StatementType);
+
+#[async_trait]
+impl Command for FundamentalsCommand {
+    fn name(&self) -> &str {
+        match self.0 {
+            StatementType::IncomeStatement => "income",
+            StatementType::BalanceSheet => "balance",
+            StatementType::CashFlow => "cashflow",
+        }
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command(self.0)
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        if let Some(format) = ExportFormat::requested(ctx.interaction) {
+            return handle_export(ctx.interaction, ctx.finance, format).await;
+        }
+        handle(ctx.interaction, ctx.finance)
+            .await
+            .map(CommandResponse::text)
+    }
 }
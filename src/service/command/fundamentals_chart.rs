@@ -0,0 +1,415 @@
+//! `/fundamentals-chart ticker metric`: one headline statement metric across the most recent
+//! periods as a bar chart, from the same fundamentals timeseries as `/income` and `/cashflow`.
+
+use ab_glyph::PxScale;
+use chrono::NaiveDate;
+use image::{DynamicImage, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::models::{FinancialStatement, Frequency, StatementType};
+use crate::service::automation::earnings::weekly_report;
+use crate::service::config::ImageConfig;
+use crate::service::finance::exchange::Exchange;
+use crate::service::finance::fundamentals::reshape_timeseries_to_financial_statements;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::growth;
+use crate::service::render;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Bars drawn, newest last.
+pub const MAX_PERIODS: usize = 12;
+/// Lookback per frequency, enough for `MAX_PERIODS` when Yahoo has them.
+const QUARTERLY_YEARS: i64 = 3;
+/// Each TTM bar sums four quarters, so it reaches three quarters further back.
+const TTM_YEARS: i64 = 4;
+const ANNUAL_YEARS: i64 = 12;
+
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 450;
+/// Keeps a short history from turning into a few slabs.
+const MAX_BAR_WIDTH: u32 = 80;
+const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const TEXT: Rgba<u8> = Rgba([40, 40, 40, 255]);
+const AXIS: Rgba<u8> = Rgba([200, 200, 200, 255]);
+const POSITIVE: Rgba<u8> = Rgba([33, 150, 243, 255]);
+const NEGATIVE: Rgba<u8> = Rgba([218, 54, 51, 255]);
+
+/// A metric the chart can plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartMetric {
+    Revenue,
+    NetIncome,
+    FreeCashFlow,
+}
+
+impl ChartMetric {
+    pub const ALL: [ChartMetric; 3] = [
+        ChartMetric::Revenue,
+        ChartMetric::NetIncome,
+        ChartMetric::FreeCashFlow,
+    ];
+
+    /// Option value, as picked in Discord.
+    pub fn name(self) -> &'static str {
+        match self {
+            ChartMetric::Revenue => "revenue",
+            ChartMetric::NetIncome => "net_income",
+            ChartMetric::FreeCashFlow => "fcf",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartMetric::Revenue => "Revenue",
+            ChartMetric::NetIncome => "Net income",
+            ChartMetric::FreeCashFlow => "Free cash flow",
+        }
+    }
+
+    /// Field in the reshaped statement.
+    pub fn field(self) -> &'static str {
+        match self {
+            ChartMetric::Revenue => "TotalRevenue",
+            ChartMetric::NetIncome => "NetIncome",
+            ChartMetric::FreeCashFlow => "FreeCashFlow",
+        }
+    }
+
+    pub fn statement(self) -> StatementType {
+        match self {
+            ChartMetric::Revenue | ChartMetric::NetIncome => StatementType::IncomeStatement,
+            ChartMetric::FreeCashFlow => StatementType::CashFlow,
+        }
+    }
+}
+
+/// One bar: the period's end date and the reported value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Period {
+    pub end: NaiveDate,
+    pub value: f64,
+}
+
+/// A metric's values, oldest first, and the currency they were reported in when Yahoo says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    pub periods: Vec<Period>,
+    pub currency: Option<String>,
+}
+
+pub fn register_command() -> CreateCommand {
+    let metric = ChartMetric::ALL.into_iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "metric", "What to chart")
+            .required(true),
+        |option, metric| option.add_string_choice(metric.label(), metric.name()),
+    );
+    CreateCommand::new("fundamentals-chart")
+        .description("Chart revenue, net income or free cash flow across recent periods")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "ticker",
+                "Ticker symbol, e.g., AAPL",
+            )
+            .required(true),
+        )
+        .add_option(metric)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "freq",
+                "quarterly (default), annual or ttm (last four quarters)",
+            )
+            .add_string_choice("Quarterly", "quarterly")
+            .add_string_choice("Annual", "annual")
+            .add_string_choice("Trailing twelve months", "ttm"),
+        )
+        .add_option(ephemeral_option())
+}
+
+/// The last [`MAX_PERIODS`] values of `metric` at `freq` in `statements`, oldest first.
+/// Periods without a reported value are skipped.
+pub fn metric_series(
+    statements: &[FinancialStatement],
+    metric: ChartMetric,
+    freq: Frequency,
+) -> MetricSeries {
+    let values = statements
+        .iter()
+        .filter(|s| s.statement_type == metric.statement().as_str())
+        .filter(|s| s.frequency == freq.as_str())
+        .filter_map(|s| s.statement.get(metric.field()))
+        .flat_map(|series| series.iter());
+
+    let mut currency = None;
+    let mut periods: Vec<Period> = Vec::new();
+    for (date, value) in values {
+        let Ok(end) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+            continue;
+        };
+        let Some(raw) = value
+            .get("reportedValue")
+            .and_then(|rv| rv.get("raw"))
+            .and_then(|r| r.as_f64())
+        else {
+            continue;
+        };
+        if currency.is_none() {
+            currency = value
+                .get("currencyCode")
+                .and_then(|c| c.as_str())
+                .map(str::to_string);
+        }
+        periods.push(Period { end, value: raw });
+    }
+    periods.sort_by_key(|p| p.end);
+    periods.dedup_by_key(|p| p.end);
+    let skip = periods.len().saturating_sub(MAX_PERIODS);
+    periods.drain(..skip);
+    MetricSeries { periods, currency }
+}
+
+/// Axis label for a period: the year for annual figures, the end month for quarters and
+/// trailing twelve months (fiscal quarters don't line up with calendar ones).
+pub fn period_label(end: NaiveDate, freq: Frequency) -> String {
+    match freq {
+        Frequency::Annual => end.format("%Y").to_string(),
+        Frequency::Quarterly | Frequency::Ttm => end.format("%b '%y").to_string(),
+    }
+}
+
+/// A line per period, oldest first, then the latest period against the same one a year
+/// earlier when that was positive.
+pub fn chart_text(
+    symbol: &str,
+    metric: ChartMetric,
+    freq: Frequency,
+    series: &MetricSeries,
+    currency: &str,
+) -> String {
+    let mut lines = vec![format!(
+        "📊 **{symbol} {} ({})**",
+        metric.label().to_lowercase(),
+        freq.as_str()
+    )];
+    for period in &series.periods {
+        lines.push(format!(
+            "• **{}** · {}",
+            period_label(period.end, freq),
+            format::currency_in(period.value, currency)
+        ));
+    }
+
+    let year_back = match freq {
+        Frequency::Annual => 1,
+        Frequency::Quarterly | Frequency::Ttm => 4,
+    };
+    let periods = &series.periods;
+    if let (Some(latest), Some(earlier)) = (
+        periods.last(),
+        periods
+            .len()
+            .checked_sub(year_back + 1)
+            .map(|i| &periods[i]),
+    ) {
+        if let Some(change) = growth::change(earlier.value, latest.value) {
+            lines.push(String::new());
+            lines.push(format!(
+                "{} vs {}: {:+.1}%",
+                period_label(latest.end, freq),
+                period_label(earlier.end, freq),
+                change * 100.0
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    images: &ImageConfig,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let metric = get_str_opt(command, "metric")
+        .and_then(ChartMetric::from_name)
+        .ok_or_else(|| BotError::bad_input("metric must be revenue | net_income | fcf"))?;
+    let freq = match get_str_opt(command, "freq") {
+        Some("annual") => Frequency::Annual,
+        Some("ttm") => Frequency::Ttm,
+        _ => Frequency::Quarterly,
+    };
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let years_back = match freq {
+        Frequency::Annual => ANNUAL_YEARS,
+        Frequency::Quarterly => QUARTERLY_YEARS,
+        Frequency::Ttm => TTM_YEARS,
+    };
+    let raw = finance
+        .get_fundamentals_raw(&symbol, metric.statement(), freq, years_back)
+        .await?;
+    let statements = reshape_timeseries_to_financial_statements(&raw);
+    let series = metric_series(&statements, metric, freq);
+    if series.periods.is_empty() {
+        return Err(BotError::not_found(format!(
+            "no {} {} data for {symbol}",
+            freq.as_str(),
+            metric.label().to_lowercase()
+        )));
+    }
+    let currency = series
+        .currency
+        .clone()
+        .unwrap_or_else(|| Exchange::for_symbol(&symbol).currency.to_string());
+
+    let mut response = CommandResponse::text(chart_text(&symbol, metric, freq, &series, &currency));
+    let title = format!(
+        "{symbol} {} ({}, {currency})",
+        metric.label().to_lowercase(),
+        freq.as_str()
+    );
+    let images = images.clone();
+    let chart = render::blocking("fundamentals-chart", move || {
+        render::encode(&render_chart(&title, &series, freq, &currency)?, &images)
+    });
+    match chart.await {
+        Ok(chart) => {
+            let name = chart.file_name("fundamentals-chart");
+            response = response.with_attachment(CreateAttachment::bytes(chart.bytes, name))
+        }
+        Err(err) => {
+            warn!("fundamentals chart render failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Vertical bars from a zero line, blue above it and red below, each labelled with its value
+/// and period.
+fn render_chart(
+    title: &str,
+    series: &MetricSeries,
+    freq: Frequency,
+    currency: &str,
+) -> Result<DynamicImage, String> {
+    let font = weekly_report::load_font()?;
+    let periods = &series.periods;
+
+    let margin = 24u32;
+    let header_h = 48u32;
+    let value_h = 22u32;
+    let label_h = 28u32;
+    let mut img = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    draw_text_mut(
+        &mut img,
+        TEXT,
+        margin as i32,
+        margin as i32,
+        PxScale::from(26.0),
+        &font,
+        title,
+    );
+
+    // Room above and below the plot for the value labels of the tallest and deepest bars.
+    let top = (margin + header_h + value_h) as f64;
+    let bottom = (HEIGHT - margin - label_h - value_h) as f64;
+    let max = periods.iter().map(|p| p.value).fold(0.0, f64::max);
+    let min = periods.iter().map(|p| p.value).fold(0.0, f64::min);
+    let span = (max - min).max(f64::EPSILON);
+    let y_of = |value: f64| top + (max - value) / span * (bottom - top);
+    let zero = y_of(0.0);
+
+    let slot = (WIDTH - margin * 2) / periods.len().max(1) as u32;
+    let bar_w = (slot * 3 / 5).clamp(1, MAX_BAR_WIDTH);
+    let scale = PxScale::from(15.0);
+    draw_filled_rect_mut(
+        &mut img,
+        Rect::at(margin as i32, zero.round() as i32).of_size(WIDTH - margin * 2, 1),
+        AXIS,
+    );
+    for (i, period) in periods.iter().enumerate() {
+        let center = margin + i as u32 * slot + slot / 2;
+        let x = (center - bar_w / 2) as i32;
+        let end = y_of(period.value);
+        let (bar_top, color) = if period.value >= 0.0 {
+            (end, POSITIVE)
+        } else {
+            (zero, NEGATIVE)
+        };
+        let bar_h = (end - zero).abs().round().max(1.0) as u32;
+        draw_filled_rect_mut(
+            &mut img,
+            Rect::at(x, bar_top.round() as i32).of_size(bar_w, bar_h),
+            color,
+        );
+
+        let value = format::currency_in(period.value, currency);
+        let (value_w, _) = text_size(scale, &font, &value);
+        let value_y = if period.value >= 0.0 {
+            end - value_h as f64
+        } else {
+            end + 4.0
+        };
+        draw_text_mut(
+            &mut img,
+            TEXT,
+            center as i32 - value_w as i32 / 2,
+            value_y.round() as i32,
+            scale,
+            &font,
+            &value,
+        );
+
+        let label = period_label(period.end, freq);
+        let (label_w, _) = text_size(scale, &font, &label);
+        draw_text_mut(
+            &mut img,
+            TEXT,
+            center as i32 - label_w as i32 / 2,
+            (HEIGHT - margin - label_h + 8) as i32,
+            scale,
+            &font,
+            &label,
+        );
+    }
+
+    Ok(DynamicImage::ImageRgba8(img))
+}
+
+pub struct FundamentalsChartCommand;
+
+#[async_trait]
+impl Command for FundamentalsChartCommand {
+    fn name(&self) -> &str {
+        "fundamentals-chart"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let config = ctx.config.current();
+        handle(ctx.interaction, ctx.finance, &config.images).await
+    }
+}
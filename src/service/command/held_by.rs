@@ -0,0 +1,96 @@
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+
+use crate::service::config::ConfigHandle;
+use crate::service::finance::etf::HeldBy;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("held-by")
+        .description("Which tracked ETFs hold a stock among their top ten, and at what weight")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., NVDA; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    config: &ConfigHandle,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let universe = config.current().etf.universe.clone();
+    let held = finance.held_by(&symbol, &universe).await;
+    if held.failed.len() == held.searched {
+        return Err(BotError::internal(format!(
+            "holdings failed to load for all {} tracked funds",
+            held.searched
+        )));
+    }
+    Ok(CommandResponse::text(held_by_text(&symbol, &held)))
+}
+
+/// A line per fund holding `symbol`, largest weight first, then what was searched.
+pub fn held_by_text(symbol: &str, held: &HeldBy) -> String {
+    let mut lines = vec![format!("🧺 **Funds holding {symbol}**")];
+    if held.positions.is_empty() {
+        lines.push(format!(
+            "{symbol} is not a top-ten holding of any tracked fund."
+        ));
+    }
+    for (i, position) in held.positions.iter().enumerate() {
+        let name = position
+            .fund_name
+            .as_deref()
+            .map(|name| format!(" {name}"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}. **{}**{name} — {} · #{} holding",
+            i + 1,
+            position.fund,
+            format::fraction_percent(position.weight),
+            position.rank
+        ));
+    }
+
+    let mut note = format!("Top ten holdings of {} tracked funds", held.searched);
+    if !held.failed.is_empty() {
+        note.push_str(&format!(
+            " · {} failed to load ({})",
+            held.failed.len(),
+            held.failed.join(", ")
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!("*{note}*"));
+    lines.join("\n")
+}
+
+pub struct HeldByCommand;
+
+#[async_trait]
+impl Command for HeldByCommand {
+    fn name(&self) -> &str {
+        "held-by"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.config).await
+    }
+}
@@ -1,13 +1,19 @@
-use serenity::all::{
-    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateCommand,
-    CreateCommandOption,
-};
+use serde_json::{json, Value};
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
 
 use crate::models::{
     HolderType, InsiderPurchase, InsiderRosterMember, InsiderTransaction, InstitutionalHolder,
     MutualFundHolder,
 };
 use crate::service::finance::FinanceService;
+use crate::service::format;
+
+use super::args::{get_i64_opt, get_str_opt};
+use super::error::{BotError, BotResult};
+use super::export::{export_option, ExportFormat, Table};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
 
 pub fn register_command() -> CreateCommand {
     CreateCommand::new("holders")
@@ -39,15 +45,19 @@ pub fn register_command() -> CreateCommand {
             .min_int_value(1)
             .max_int_value(10),
         )
+        .add_option(export_option())
+        .add_option(ephemeral_option())
 }
 
 pub async fn handle(
     command: &CommandInteraction,
     finance: &FinanceService,
-) -> Result<String, String> {
-    let ticker = get_str_opt(command, "ticker").ok_or("ticker is required")?;
-    let holder_type_raw = get_str_opt(command, "type").ok_or("type is required")?;
-    let limit = get_int_opt(command, "limit").map(|v| v as usize);
+) -> BotResult<String> {
+    let ticker = get_str_opt(command, "ticker")
+        .ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let holder_type_raw = get_str_opt(command, "type")
+        .ok_or_else(|| BotError::bad_input("type is required"))?;
+    let limit = get_i64_opt(command, "limit").map(|v| v as usize);
     handle_text(finance, ticker, holder_type_raw, limit).await
 }
 
@@ -56,30 +66,17 @@ pub async fn handle_text(
     ticker: &str,
     holder_type_raw: &str,
     limit: Option<usize>,
-) -> Result<String, String> {
-    let holder_type = match holder_type_raw {
-        "major" => HolderType::Major,
-        "institutional" => HolderType::Institutional,
-        "mutualfund" => HolderType::MutualFund,
-        "insider_transactions" => HolderType::InsiderTransactions,
-        "insider_purchases" => HolderType::InsiderPurchases,
-        "insider_roster" => HolderType::InsiderRoster,
-        _ => {
-            return Err(
-                "type must be major | institutional | mutualfund | insider_transactions | insider_purchases | insider_roster"
-                    .into(),
-            )
-        }
-    };
-    let limit = limit.unwrap_or(5).clamp(1, 10) as usize;
+) -> BotResult<String> {
+    let holder_type = parse_holder_type(holder_type_raw)?;
+    let limit = limit.unwrap_or(5).clamp(1, 10);
+    let ticker = &symbol::resolve(finance, ticker).await?;
 
-    let data = finance
-        .get_holders(ticker, holder_type)
-        .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+    let data = finance.get_holders(ticker, holder_type).await?;
 
     match holder_type {
-        HolderType::Major => format_major(&data).ok_or_else(|| "no major holders found".into()),
+        HolderType::Major => {
+            format_major(&data).ok_or_else(|| BotError::not_found("no major holders found"))
+        }
         HolderType::Institutional => format_table(
             &data.institutional_holders.unwrap_or_default(),
             limit,
@@ -108,6 +105,166 @@ pub async fn handle_text(
     }
 }
 
+/// Every row of the chosen holder category as a `<SYMBOL>-<type>.csv|json` file, ignoring
+/// `limit`.
+pub async fn handle_export(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    format: ExportFormat,
+) -> BotResult<CommandResponse> {
+    let ticker = get_str_opt(command, "ticker")
+        .ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let holder_type_raw = get_str_opt(command, "type")
+        .ok_or_else(|| BotError::bad_input("type is required"))?;
+    let holder_type = parse_holder_type(holder_type_raw)?;
+    let ticker = &symbol::resolve(finance, ticker).await?;
+
+    let data = finance.get_holders(ticker, holder_type).await?;
+    let table = export_table(&data, holder_type);
+    if table.is_empty() {
+        return Err(BotError::not_found(format!(
+            "no {} data for {}",
+            holder_type_raw.replace('_', " "),
+            data.symbol
+        )));
+    }
+
+    let attachment = table.attachment(format, &format!("{}-{}", data.symbol, holder_type_raw))?;
+    Ok(CommandResponse::text(format!(
+        "{} {} rows for {}",
+        table.len(),
+        holder_type_raw.replace('_', " "),
+        data.symbol
+    ))
+    .with_attachment(attachment))
+}
+
+/// The rows `format:csv|json` exports for `holder_type`.
+pub fn export_table(data: &crate::models::HoldersOverview, holder_type: HolderType) -> Table {
+    let date = |d: chrono::DateTime<chrono::Utc>| Value::from(d.date_naive().to_string());
+    match holder_type {
+        HolderType::Major => {
+            let mut table = Table::new(["metric", "value"]);
+            if let Some(breakdown) = &data.major_breakdown {
+                let mut entries: Vec<_> = breakdown.breakdown_data.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                for (k, v) in entries {
+                    let value = v.get("raw").unwrap_or(v).clone();
+                    table.push(vec![json!(k), value]);
+                }
+            }
+            table
+        }
+        HolderType::Institutional | HolderType::MutualFund => {
+            let mut table =
+                Table::new(["holder", "shares", "percent_out", "value", "date_reported"]);
+            let institutional = data
+                .institutional_holders
+                .iter()
+                .flatten()
+                .map(|h| (&h.holder, h.shares, h.percent_out, h.value, h.date_reported));
+            let funds = data
+                .mutualfund_holders
+                .iter()
+                .flatten()
+                .map(|h| (&h.holder, h.shares, h.percent_out, h.value, h.date_reported));
+            for (holder, shares, percent_out, value, reported) in institutional.chain(funds) {
+                table.push(vec![
+                    json!(holder),
+                    json!(shares),
+                    json!(percent_out),
+                    json!(value),
+                    date(reported),
+                ]);
+            }
+            table
+        }
+        HolderType::InsiderTransactions => {
+            let mut table = Table::new([
+                "date",
+                "insider",
+                "position",
+                "transaction",
+                "shares",
+                "value",
+                "ownership",
+            ]);
+            for tx in data.insider_transactions.iter().flatten() {
+                table.push(vec![
+                    date(tx.start_date),
+                    json!(tx.insider),
+                    json!(tx.position),
+                    json!(tx.transaction),
+                    json!(tx.shares),
+                    json!(tx.value),
+                    json!(tx.ownership),
+                ]);
+            }
+            table
+        }
+        HolderType::InsiderPurchases => {
+            let mut table = Table::new([
+                "period",
+                "purchases_shares",
+                "purchases_transactions",
+                "sales_shares",
+                "sales_transactions",
+                "net_shares",
+                "net_transactions",
+                "total_insider_shares",
+            ]);
+            if let Some(p) = &data.insider_purchases {
+                table.push(vec![
+                    json!(p.period),
+                    json!(p.purchases_shares),
+                    json!(p.purchases_transactions),
+                    json!(p.sales_shares),
+                    json!(p.sales_transactions),
+                    json!(p.net_shares),
+                    json!(p.net_transactions),
+                    json!(p.total_insider_shares),
+                ]);
+            }
+            table
+        }
+        HolderType::InsiderRoster => {
+            let mut table = Table::new([
+                "name",
+                "position",
+                "most_recent_transaction",
+                "latest_transaction_date",
+                "shares_owned_directly",
+                "shares_owned_indirectly",
+            ]);
+            for row in data.insider_roster.iter().flatten() {
+                table.push(vec![
+                    json!(row.name),
+                    json!(row.position),
+                    json!(row.most_recent_transaction),
+                    row.latest_transaction_date.map(date).unwrap_or(Value::Null),
+                    json!(row.shares_owned_directly),
+                    json!(row.shares_owned_indirectly),
+                ]);
+            }
+            table
+        }
+    }
+}
+
+fn parse_holder_type(raw: &str) -> BotResult<HolderType> {
+    match raw {
+        "major" => Ok(HolderType::Major),
+        "institutional" => Ok(HolderType::Institutional),
+        "mutualfund" => Ok(HolderType::MutualFund),
+        "insider_transactions" => Ok(HolderType::InsiderTransactions),
+        "insider_purchases" => Ok(HolderType::InsiderPurchases),
+        "insider_roster" => Ok(HolderType::InsiderRoster),
+        _ => Err(BotError::bad_input(
+            "type must be major | institutional | mutualfund | insider_transactions | insider_purchases | insider_roster",
+        )),
+    }
+}
+
 fn format_major(data: &crate::models::HoldersOverview) -> Option<String> {
     let breakdown = data.major_breakdown.as_ref()?;
     let mut parts = Vec::new();
@@ -135,12 +292,15 @@ fn format_major(data: &crate::models::HoldersOverview) -> Option<String> {
     }
 }
 
-fn format_table<T>(rows: &[T], limit: usize, heading: &str, symbol: &str) -> Result<String, String>
+fn format_table<T>(rows: &[T], limit: usize, heading: &str, symbol: &str) -> BotResult<String>
 where
     T: HolderRow,
 {
     if rows.is_empty() {
-        return Err(format!("no {} found", heading.to_lowercase()));
+        return Err(BotError::not_found(format!(
+            "no {} found",
+            heading.to_lowercase()
+        )));
     }
 
     let mut rows_sorted: Vec<&T> = rows.iter().collect();
@@ -200,67 +360,30 @@ impl HolderRow for MutualFundHolder {
     }
 }
 
-fn get_str_opt<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        })
-}
-
-fn get_int_opt(command: &CommandInteraction, name: &str) -> Option<i64> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::Integer(i) => Some(i),
-            _ => None,
-        })
-}
-
+/// Breakdown values arrive as fractions (0.61) or, for some fields, already in percent.
 fn format_percent(value: f64) -> String {
-    let pct = if value.abs() <= 1.0 {
-        value * 100.0
+    if value.abs() <= 1.0 {
+        format::fraction_percent(value)
     } else {
-        value
-    };
-    format!("{:.2}%", pct)
+        format::percent(value)
+    }
 }
 
 fn format_shares(shares: i64) -> String {
-    if shares.abs() >= 1_000_000_000 {
-        format!("{:.2}B", shares as f64 / 1_000_000_000.0)
-    } else if shares.abs() >= 1_000_000 {
-        format!("{:.2}M", shares as f64 / 1_000_000.0)
-    } else {
-        shares.to_string()
-    }
+    format::shares(shares as f64)
 }
 
 fn format_currency(value: i64) -> String {
-    let abs = value.abs() as f64;
-    if abs >= 1_000_000_000.0 {
-        format!("${:.2}B", value as f64 / 1_000_000_000.0)
-    } else if abs >= 1_000_000.0 {
-        format!("${:.2}M", value as f64 / 1_000_000.0)
-    } else {
-        format!("${}", value)
-    }
+    format::currency(value as f64)
 }
 
 fn format_transactions(
     txs: &[InsiderTransaction],
     limit: usize,
     symbol: &str,
-) -> Result<String, String> {
+) -> BotResult<String> {
     if txs.is_empty() {
-        return Err("no insider transactions found".into());
+        return Err(BotError::not_found("no insider transactions found"));
     }
     let mut lines = Vec::new();
     lines.push(format!("Insider transactions for {}", symbol));
@@ -282,8 +405,8 @@ fn format_transactions(
     Ok(lines.join("\n"))
 }
 
-fn format_purchases(p: Option<&InsiderPurchase>, symbol: &str) -> Result<String, String> {
-    let p = p.ok_or("no insider purchase summary found")?;
+fn format_purchases(p: Option<&InsiderPurchase>, symbol: &str) -> BotResult<String> {
+    let p = p.ok_or_else(|| BotError::not_found("no insider purchase summary found"))?;
     Ok(format!(
         "Insider purchases (recent) for {}\nBuys: {} shares in {} transactions\nSells: {} shares in {} transactions\nNet shares: {}",
         symbol,
@@ -299,9 +422,9 @@ fn format_roster(
     rows: &[InsiderRosterMember],
     limit: usize,
     symbol: &str,
-) -> Result<String, String> {
+) -> BotResult<String> {
     if rows.is_empty() {
-        return Err("no insider roster found".into());
+        return Err(BotError::not_found("no insider roster found"));
     }
     let mut lines = Vec::new();
     lines.push(format!("Insider roster for {}", symbol));
@@ -326,3 +449,25 @@ fn format_roster(
     }
     Ok(lines.join("\n"))
 }
+
+pub struct HoldersCommand;
+
+#[async_trait]
+impl Command for HoldersCommand {
+    fn name(&self) -> &str {
+        "holders"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        if let Some(format) = ExportFormat::requested(ctx.interaction) {
+            return handle_export(ctx.interaction, ctx.finance, format).await;
+        }
+        handle(ctx.interaction, ctx.finance)
+            .await
+            .map(CommandResponse::text)
+    }
+}
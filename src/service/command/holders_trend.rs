@@ -0,0 +1,205 @@
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::ownership::{quarter_label, OwnershipSnapshot};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::quickchart;
+use crate::service::store::StateStore;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("holders-trend")
+        .description("Institutional ownership across the last four quarters")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+) -> BotResult<CommandResponse> {
+    let store = store.ok_or_else(|| {
+        BotError::not_found("ownership history is unavailable; no state store is configured")
+    })?;
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let snapshots = finance.ownership_trend(&symbol, Some(store)).await?;
+    let mut response = CommandResponse::text(trend_text(&symbol, &snapshots));
+    if snapshots.len() < 2 {
+        return Ok(response);
+    }
+    match quickchart::render(trend_chart(&symbol, &snapshots), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "holders-trend.png"))
+        }
+        Err(err) => {
+            warn!("holders trend chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// A line per quarter, oldest first, then the change from the first quarter to the latest.
+/// A single quarter says that earlier ones appear as they are recorded.
+pub fn trend_text(symbol: &str, snapshots: &[OwnershipSnapshot]) -> String {
+    let mut lines = vec![format!("🏦 **{symbol} institutional ownership**")];
+    for snapshot in snapshots {
+        let percent = |v: Option<f64>| v.map(format::fraction_percent);
+        let parts = [
+            percent(snapshot.institutions_percent).map(|v| format!("Institutions {v}")),
+            percent(snapshot.top10_percent).map(|v| format!("Top 10 {v}")),
+            snapshot
+                .institutions_count
+                .map(|c| format!("{} holders", format::count(c))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        lines.push(format!(
+            "• **{}** · {}",
+            quarter_label(snapshot.quarter),
+            parts.join(" · ")
+        ));
+    }
+
+    match snapshots {
+        [] => {}
+        [only] => {
+            lines.push(String::new());
+            lines.push(format!(
+                "📸 Saved {}. Earlier quarters show here once they have been recorded.",
+                quarter_label(only.quarter)
+            ));
+        }
+        [first, .., last] => {
+            let points =
+                |a: Option<f64>, b: Option<f64>| Some(format!("{:+.2} pts", (b? - a?) * 100.0));
+            let changes = [
+                points(first.institutions_percent, last.institutions_percent)
+                    .map(|v| format!("institutions {v}")),
+                points(first.top10_percent, last.top10_percent).map(|v| format!("top 10 {v}")),
+                first
+                    .institutions_count
+                    .zip(last.institutions_count)
+                    .map(|(a, b)| {
+                        let sign = if b >= a { "+" } else { "" };
+                        format!("holders {sign}{}", format::count(b - a))
+                    }),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                lines.push(String::new());
+                lines.push(format!(
+                    "Since {}: {}",
+                    quarter_label(first.quarter),
+                    changes.join(" · ")
+                ));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Institutional and top-10 percentages on the left axis, holder count on the right.
+fn trend_chart(symbol: &str, snapshots: &[OwnershipSnapshot]) -> Value {
+    let labels: Vec<String> = snapshots.iter().map(|s| quarter_label(s.quarter)).collect();
+    let percent = |pick: fn(&OwnershipSnapshot) -> Option<f64>| -> Vec<Value> {
+        snapshots
+            .iter()
+            .map(|s| match pick(s) {
+                Some(v) => json!((v * 1000.0).round() / 10.0),
+                None => Value::Null,
+            })
+            .collect()
+    };
+    let counts: Vec<Value> = snapshots
+        .iter()
+        .map(|s| s.institutions_count.map_or(Value::Null, Value::from))
+        .collect();
+
+    json!({
+        "type": "line",
+        "data": {
+            "labels": labels,
+            "datasets": [
+                {
+                    "label": "Institutions %",
+                    "data": percent(|s| s.institutions_percent),
+                    "borderColor": "#2196f3",
+                    "backgroundColor": "#2196f3",
+                    "yAxisID": "y",
+                    "fill": false
+                },
+                {
+                    "label": "Top 10 %",
+                    "data": percent(|s| s.top10_percent),
+                    "borderColor": "#ff9800",
+                    "backgroundColor": "#ff9800",
+                    "yAxisID": "y",
+                    "fill": false
+                },
+                {
+                    "label": "Holders",
+                    "data": counts,
+                    "borderColor": "#9e9e9e",
+                    "backgroundColor": "#9e9e9e",
+                    "borderDash": [6, 4],
+                    "yAxisID": "count",
+                    "fill": false
+                }
+            ]
+        },
+        "options": {
+            "plugins": {
+                "legend": { "position": "bottom" },
+                "title": { "display": true, "text": format!("{symbol} institutional ownership") }
+            },
+            "scales": {
+                "y": { "title": { "display": true, "text": "% of shares outstanding" } },
+                "count": {
+                    "position": "right",
+                    "grid": { "drawOnChartArea": false },
+                    "title": { "display": true, "text": "Institutional holders" }
+                }
+            }
+        }
+    })
+}
+
+pub struct HoldersTrendCommand;
+
+#[async_trait]
+impl Command for HoldersTrendCommand {
+    fn name(&self) -> &str {
+        "holders-trend"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store).await
+    }
+}
@@ -0,0 +1,291 @@
+use ab_glyph::PxScale;
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt};
+use image::{DynamicImage, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut};
+use imageproc::rect::Rect;
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::models::EarningsEvent;
+use crate::service::automation::earnings::{self, weekly_report};
+use crate::service::config::ImageConfig;
+use crate::service::finance::FinanceService;
+use crate::service::render;
+use crate::service::store::StateStore;
+use crate::service::symbol_filter;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult};
+use super::universe;
+
+/// Reporters checked for an implied move, most important first; each costs two option
+/// chain requests.
+const MAX_LOOKUPS: usize = 40;
+/// Concurrent option chain lookups.
+const LOOKUPS: usize = 6;
+/// Bars drawn on the chart; the text lists every ranked name.
+const CHART_ROWS: usize = 20;
+
+const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const TEXT: Rgba<u8> = Rgba([40, 40, 40, 255]);
+const BMO_COLOR: Rgba<u8> = Rgba([255, 152, 0, 255]);
+const AMC_COLOR: Rgba<u8> = Rgba([33, 150, 243, 255]);
+const TBA_COLOR: Rgba<u8> = Rgba([158, 158, 158, 255]);
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("implied-moves")
+        .description("Rank a day's earnings reporters by options-implied move")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "date",
+            "Day to rank, YYYY-MM-DD (default today, or Monday on weekends)",
+        ))
+        .add_option(universe::universe_option())
+}
+
+/// One reporter with its implied move in percent.
+#[derive(Debug, Clone)]
+struct RankedMove {
+    symbol: String,
+    session: &'static str,
+    implied_move: f64,
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    images: &ImageConfig,
+) -> BotResult<CommandResponse> {
+    let day = match get_str_opt(command, "date") {
+        Some(raw) => NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+            .map_err(|_| BotError::bad_input(format!("{raw} is not a date like 2026-10-20")))?,
+        None => default_day(Utc::now().with_timezone(&New_York).date_naive()),
+    };
+    let index = universe::requested(command);
+    let guild_id = command.guild_id.map(|g| g.get());
+    let filter = symbol_filter::guild_filter(store, finance, guild_id).await;
+    let filter = universe::restrict(finance, filter, index).await?;
+
+    let mut events = finance.get_earnings_range(day, day).await?.events;
+    events.retain(|ev| ev.date.date_naive() == day);
+    filter.retain_events(&mut events);
+
+    let label = format!(
+        "{}{}",
+        day.format("%a, %b %-d"),
+        index
+            .map(|i| format!(" · {}", i.label()))
+            .unwrap_or_default()
+    );
+    if events.is_empty() {
+        return Ok(CommandResponse::text(format!(
+            "📅 No earnings reporters on {label}."
+        )));
+    }
+
+    let total = events.len();
+    let (ranked, unpriced) = rank(finance, events).await;
+    if ranked.is_empty() {
+        return Ok(CommandResponse::text(format!(
+            "📅 None of the {total} reporters on {label} have options data for an implied move."
+        )));
+    }
+
+    let mut lines = vec![format!("📊 **Implied moves — {label}**")];
+    for (i, entry) in ranked.iter().enumerate() {
+        lines.push(format!(
+            "{}. **{}** [{}] ±{:.1}%",
+            i + 1,
+            entry.symbol,
+            entry.session,
+            entry.implied_move
+        ));
+    }
+    let mut notes = Vec::new();
+    if unpriced > 0 {
+        notes.push(format!("{unpriced} without options data"));
+    }
+    if total > MAX_LOOKUPS {
+        notes.push(format!("{} smaller names not checked", total - MAX_LOOKUPS));
+    }
+    if !notes.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("*{}*", notes.join(" · ")));
+    }
+
+    let title = format!("Implied moves — {label}");
+    let mut response = CommandResponse::text(lines.join("\n"));
+    let images = images.clone();
+    let chart = render::blocking("implied-moves", move || {
+        render::encode(&render_chart(&title, &ranked)?, &images)
+    });
+    match chart.await {
+        Ok(chart) => {
+            let name = chart.file_name("implied-moves");
+            response = response.with_attachment(CreateAttachment::bytes(chart.bytes, name))
+        }
+        Err(err) => {
+            warn!("implied move chart render failed: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Today, or the coming Monday on a weekend.
+fn default_day(today: NaiveDate) -> NaiveDate {
+    match today.weekday() {
+        Weekday::Sat => today + Duration::days(2),
+        Weekday::Sun => today + Duration::days(1),
+        _ => today,
+    }
+}
+
+/// Implied moves of the most important `MAX_LOOKUPS` reporters, largest first, and how many
+/// of those had no options data.
+async fn rank(
+    finance: &FinanceService,
+    mut events: Vec<EarningsEvent>,
+) -> (Vec<RankedMove>, usize) {
+    events.sort_by(|a, b| {
+        b.importance
+            .unwrap_or(0)
+            .cmp(&a.importance.unwrap_or(0))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    events.truncate(MAX_LOOKUPS);
+    let checked = events.len();
+
+    let mut ranked: Vec<RankedMove> = stream::iter(events)
+        .map(|ev| async move {
+            let implied_move = earnings::implied_move(finance, &ev).await?;
+            Some(RankedMove {
+                session: earnings::classify_session(ev.time_of_day.as_deref()),
+                symbol: ev.symbol,
+                implied_move,
+            })
+        })
+        .buffered(LOOKUPS)
+        .filter_map(|entry| async move { entry })
+        .collect()
+        .await;
+    ranked.sort_by(|a, b| b.implied_move.total_cmp(&a.implied_move));
+    let unpriced = checked - ranked.len();
+    (ranked, unpriced)
+}
+
+/// Horizontal bars, one per reporter, colored by session.
+fn render_chart(title: &str, ranked: &[RankedMove]) -> Result<DynamicImage, String> {
+    let font = weekly_report::load_font()?;
+    let rows = &ranked[..ranked.len().min(CHART_ROWS)];
+
+    let margin = 24u32;
+    let header_h = 64u32;
+    let row_h = 30u32;
+    let label_w = 110u32;
+    let value_w = 90u32;
+    let width = 900u32;
+    let height = margin * 2 + header_h + rows.len() as u32 * row_h;
+    let mut img = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+    draw_text_mut(
+        &mut img,
+        TEXT,
+        margin as i32,
+        margin as i32,
+        PxScale::from(26.0),
+        &font,
+        title,
+    );
+    let mut legend_x = margin as i32;
+    for (label, color) in [("BMO", BMO_COLOR), ("AMC", AMC_COLOR), ("TBA", TBA_COLOR)] {
+        let legend_y = (margin + 36) as i32;
+        draw_filled_rect_mut(
+            &mut img,
+            Rect::at(legend_x, legend_y).of_size(14, 14),
+            color,
+        );
+        draw_text_mut(
+            &mut img,
+            TEXT,
+            legend_x + 20,
+            legend_y - 2,
+            PxScale::from(16.0),
+            &font,
+            label,
+        );
+        legend_x += 80;
+    }
+
+    let max = rows
+        .iter()
+        .map(|r| r.implied_move)
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    let bar_x = margin + label_w;
+    let bar_max_w = width - bar_x - value_w - margin;
+    let row_scale = PxScale::from(20.0);
+    for (i, entry) in rows.iter().enumerate() {
+        let y = margin + header_h + i as u32 * row_h;
+        draw_text_mut(
+            &mut img,
+            TEXT,
+            margin as i32,
+            y as i32 + 4,
+            row_scale,
+            &font,
+            &entry.symbol,
+        );
+        let bar_w = ((entry.implied_move / max) * bar_max_w as f64)
+            .round()
+            .max(1.0) as u32;
+        let color = match entry.session {
+            "BMO" => BMO_COLOR,
+            "AMC" => AMC_COLOR,
+            _ => TBA_COLOR,
+        };
+        draw_filled_rect_mut(
+            &mut img,
+            Rect::at(bar_x as i32, y as i32 + 4).of_size(bar_w, row_h - 8),
+            color,
+        );
+        draw_text_mut(
+            &mut img,
+            TEXT,
+            (bar_x + bar_w + 8) as i32,
+            y as i32 + 4,
+            row_scale,
+            &font,
+            &format!("±{:.1}%", entry.implied_move),
+        );
+    }
+
+    Ok(DynamicImage::ImageRgba8(img))
+}
+
+pub struct ImpliedMovesCommand;
+
+#[async_trait]
+impl Command for ImpliedMovesCommand {
+    fn name(&self) -> &str {
+        "implied-moves"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let config = ctx.config.current();
+        handle(ctx.interaction, ctx.finance, ctx.store, &config.images).await
+    }
+}
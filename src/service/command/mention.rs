@@ -1,144 +1,430 @@
-use serenity::all::{ChannelId, CreateAttachment, Http};
+use chrono_tz::Tz;
+use futures_util::future::join_all;
+use once_cell::sync::Lazy;
+use serenity::all::{
+    ChannelId, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption, Http,
+    UserId,
+};
+use tracing::warn;
 
-use crate::models::StatementType;
-use crate::service::finance::FinanceService;
+use crate::models::{SourceType, StatementType};
 use crate::service::command::fundamentals::render_statement_image;
+use crate::service::config::ImageConfig;
+use crate::service::finance::FinanceService;
+use crate::service::i18n;
+use crate::service::store::StateStore;
 
-use super::{earnings, holders, news, quotes};
+use super::args::{tokenize, CommandSpec};
+use super::error::{BotError, BotResult};
+use super::framework::{check_roles, Invoker, TICKER_OPTION};
+use super::{earnings, fundamentals, holders, news, quotes};
 
 pub struct MentionResponse {
     pub content: String,
-    pub attachment: Option<CreateAttachment>,
+    pub attachments: Vec<CreateAttachment>,
+}
+
+impl MentionResponse {
+    fn text(content: String) -> Self {
+        Self {
+            content,
+            attachments: Vec::new(),
+        }
+    }
 }
 
+/// Most cashtags answered from one message.
+const MAX_CASHTAGS: usize = 5;
+
+/// Most `;`-separated commands answered from one message.
+const MAX_BATCH: usize = 5;
+
+/// Mention commands that reply with the statement image.
+const STATEMENT_COMMANDS: [&str; 3] = ["income", "balance", "cashflow"];
+
+/// Mention commands, in help order. Arguments come from the slash definitions; the
+/// fundamentals image shows every metric, so it takes no `metric`, and replies are text, so
+/// `news` takes no `preview`.
+static SPECS: Lazy<Vec<CommandSpec>> = Lazy::new(|| {
+    vec![
+        CommandSpec::from_slash(quotes::register_command(), &["q", "price"], &[]),
+        CommandSpec::from_slash(holders::register_command(), &["h"], &[]),
+        CommandSpec::from_slash(news::register_command(), &[], &["preview"]),
+        CommandSpec::from_slash(
+            fundamentals::register_command(StatementType::IncomeStatement),
+            &["is"],
+            &["metric"],
+        ),
+        CommandSpec::from_slash(
+            fundamentals::register_command(StatementType::BalanceSheet),
+            &["bs"],
+            &["metric"],
+        ),
+        CommandSpec::from_slash(
+            fundamentals::register_command(StatementType::CashFlow),
+            &["cf"],
+            &["metric"],
+        ),
+        CommandSpec::from_slash(register_earnings(), &["er"], &[]),
+    ]
+});
+
+/// `earnings MODE` bundles the three earnings slash commands.
+fn register_earnings() -> CreateCommand {
+    CreateCommand::new("earnings")
+        .description("Earnings calendars and post-earnings reports")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "mode", "Which report")
+                .add_string_choice("Weekly", "weekly")
+                .add_string_choice("Daily", "daily")
+                .add_string_choice("Reports", "reports")
+                .required(true),
+        )
+}
+
+/// `content` after a leading `<@bot>` or `<@!bot>` mention, or `None` when it doesn't start
+/// with one.
+pub fn strip_mention(content: &str, bot_id: UserId) -> Option<&str> {
+    [format!("<@{bot_id}>"), format!("<@!{bot_id}>")]
+        .iter()
+        .find_map(|prefix| content.strip_prefix(prefix.as_str()))
+        .map(str::trim)
+}
+
+/// Answer a mention. Several commands separated by `;` (`quote SPY; news NVDA 2`) get one
+/// consolidated reply. Each command is held to the `/config restrict` roles of the slash
+/// command it stands for.
+#[allow(clippy::too_many_arguments)]
 pub async fn handle(
     text: &str,
     http: &Http,
     channel_id: ChannelId,
     finance: &FinanceService,
-) -> Result<MentionResponse, String> {
-    let mut parts = text.split_whitespace();
-    let cmd = parts
+    store: Option<&dyn StateStore>,
+    invoker: Option<&Invoker>,
+    timezone: Option<Tz>,
+    images: &ImageConfig,
+) -> BotResult<MentionResponse> {
+    let commands = split_commands(text);
+    if commands.len() <= 1 {
+        let text = commands.first().copied().unwrap_or(text);
+        return handle_one(
+            text, http, channel_id, finance, store, invoker, timezone, images,
+        )
+        .await;
+    }
+    if commands.len() > MAX_BATCH {
+        return Err(BotError::bad_input(format!(
+            "at most {MAX_BATCH} commands per message, got {}",
+            commands.len()
+        )));
+    }
+
+    let replies = join_all(commands.iter().map(|command| {
+        handle_one(
+            command, http, channel_id, finance, store, invoker, timezone, images,
+        )
+    }))
+    .await;
+    let mut sections = Vec::new();
+    let mut attachments = Vec::new();
+    for (command, reply) in commands.iter().zip(replies) {
+        match reply {
+            Ok(reply) => {
+                sections.push(reply.content);
+                attachments.extend(reply.attachments);
+            }
+            Err(err) => {
+                err.log("mention");
+                sections.push(format!("`{command}`: {}", err.user_message()));
+            }
+        }
+    }
+    Ok(MentionResponse {
+        content: sections.join("\n\n"),
+        attachments,
+    })
+}
+
+/// The channel's `/bind-ticker` symbol, if any.
+async fn bound_ticker(store: Option<&dyn StateStore>, channel_id: ChannelId) -> Option<String> {
+    match store?.channel_ticker(channel_id.get()).await {
+        Ok(ticker) => ticker,
+        Err(e) => {
+            warn!("failed to load the bound ticker of {channel_id}: {e}");
+            None
+        }
+    }
+}
+
+/// The non-empty commands of a batch, trimmed. A `;` separates commands, even inside quotes,
+/// when every part starts with a command name; other text (`$NVDA; $AMD`) stays whole.
+pub fn split_commands(text: &str) -> Vec<&str> {
+    let parts: Vec<&str> = text
+        .split(';')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    let all_commands = parts.iter().all(|part| {
+        let first = part
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        first == "help" || SPECS.iter().any(|spec| spec.matches(&first))
+    });
+    if all_commands {
+        parts
+    } else {
+        vec![text.trim()]
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_one(
+    text: &str,
+    http: &Http,
+    channel_id: ChannelId,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+    invoker: Option<&Invoker>,
+    timezone: Option<Tz>,
+    images: &ImageConfig,
+) -> BotResult<MentionResponse> {
+    let first = text
+        .split_whitespace()
         .next()
-        .ok_or_else(|| "No command provided. Try: ".to_string() + help_text())?
+        .unwrap_or_default()
         .to_ascii_lowercase();
+    let is_command = first == "help" || SPECS.iter().any(|spec| spec.matches(&first));
+    if !is_command {
+        let tags = cashtags(text);
+        if !tags.is_empty() {
+            check_access(store, invoker, "quote").await?;
+            return Ok(MentionResponse::text(cashtag_quotes(finance, &tags).await));
+        }
+    }
+
+    let tokens = tokenize(text)?;
+    let (cmd, rest) = tokens
+        .split_first()
+        .ok_or_else(|| BotError::bad_input(format!("no command provided. {}", help_text())))?;
+    let cmd = cmd.to_ascii_lowercase();
 
-    match cmd.as_str() {
+    if cmd == "help" {
+        return Ok(MentionResponse::text(help_text()));
+    }
+
+    let spec = SPECS
+        .iter()
+        .find(|spec| spec.matches(&cmd))
+        .ok_or_else(|| BotError::bad_input(format!("unknown command: {}. {}", cmd, help_text())))?;
+    let mut args = if STATEMENT_COMMANDS.contains(&spec.name.as_str()) {
+        spec.parse(&without_image_keyword(rest))?
+    } else {
+        spec.parse(rest)?
+    };
+    check_access(store, invoker, slash_name(&spec.name, args.get("mode"))).await?;
+
+    // Only commands whose ticker is optional get here without one (see `/bind-ticker`).
+    if args.get(TICKER_OPTION).is_none() && spec.args.iter().any(|a| a.name == TICKER_OPTION) {
+        if let Some(ticker) = bound_ticker(store, channel_id).await {
+            args.insert(TICKER_OPTION, ticker);
+        }
+    }
+
+    match spec.name.as_str() {
         "quote" => {
-            let ticker = parts.next().ok_or("ticker required, e.g., quote AAPL")?;
-            let content = quotes::handle_text(finance, ticker).await?;
-            Ok(MentionResponse {
-                content,
-                attachment: None,
-            })
+            let content = quotes::handle_text(
+                finance,
+                args.required("ticker")?,
+                args.get("currency"),
+                timezone,
+            )
+            .await?;
+            Ok(MentionResponse::text(content))
         }
         "holders" => {
-            let ticker = parts.next().ok_or("ticker required, e.g., holders AAPL major")?;
-            let holder_type = parts
-                .next()
-                .ok_or("type required: major|institutional|mutualfund|insider_transactions|insider_purchases|insider_roster")?
-                .to_ascii_lowercase();
-            let limit = parts
-                .next()
-                .map(parse_usize)
-                .transpose()
-                .map_err(|e| format!("invalid limit: {e}"))?;
-            let content = holders::handle_text(finance, ticker, &holder_type, limit).await?;
-            Ok(MentionResponse {
-                content,
-                attachment: None,
-            })
+            let content = holders::handle_text(
+                finance,
+                args.required("ticker")?,
+                args.required("type")?,
+                args.parse("limit")?,
+            )
+            .await?;
+            Ok(MentionResponse::text(content))
         }
         "news" => {
-            let ticker = parts.next().ok_or("ticker required, e.g., news AAPL 3")?;
-            let limit = parts
-                .next()
-                .map(parse_usize)
-                .transpose()
-                .map_err(|e| format!("invalid limit: {e}"))?
-                .unwrap_or(1)
-                .clamp(1, 10);
-            let content = news::handle_text(finance, ticker, limit).await?;
-            Ok(MentionResponse {
-                content,
-                attachment: None,
-            })
+            let limit = args.parse("limit")?.unwrap_or(1);
+            let source_type = args.get("source_type").and_then(SourceType::from_name);
+            let content =
+                news::handle_text(finance, args.required("ticker")?, limit, source_type).await?;
+            Ok(MentionResponse::text(content))
         }
         "income" | "balance" | "cashflow" => {
-            let ticker = parts.next().ok_or("ticker required, e.g., income AAPL annual")?;
-            let freq = parts.next().ok_or("freq required: annual|quarterly")?;
-            let year = parts
-                .next()
-                .map(parse_i32)
-                .transpose()
-                .map_err(|e| format!("invalid year: {e}"))?;
-            let quarter = parts.next();
-
-            let statement_type = match cmd.as_str() {
+            let statement_type = match spec.name.as_str() {
                 "income" => StatementType::IncomeStatement,
                 "balance" => StatementType::BalanceSheet,
-                "cashflow" => StatementType::CashFlow,
-                _ => unreachable!(),
+                _ => StatementType::CashFlow,
             };
 
-            let (content, image) =
-                render_statement_image(finance, statement_type, ticker, freq, year, quarter).await?;
+            let (content, image) = render_statement_image(
+                finance,
+                statement_type,
+                args.required("ticker")?,
+                args.required("freq")?,
+                args.parse("year")?,
+                args.get("quarter"),
+                args.get("currency"),
+                images,
+            )
+            .await?;
 
-            let attachment = CreateAttachment::bytes(image, "fundamentals.png");
+            let name = image.file_name("fundamentals");
+            let attachment = CreateAttachment::bytes(image.bytes, name);
 
             Ok(MentionResponse {
                 content,
-                attachment: Some(attachment),
+                attachments: vec![attachment],
             })
         }
-        "earnings" => {
-            let mode = parts
-                .next()
-                .ok_or("earnings mode required: weekly|daily|reports")?
-                .to_ascii_lowercase();
-            match mode.as_str() {
-                "weekly" => {
-                    let resp = earnings::handle_weekly_plain(finance).await?;
-                    let attachment = resp
-                        .image
-                        .map(|bytes| CreateAttachment::bytes(bytes, "earnings-calendar.png"));
-                    Ok(MentionResponse {
-                        content: resp.content,
-                        attachment,
-                    })
-                }
-                "daily" => {
-                    let content =
-                        earnings::handle_daily_for_channel(finance, http, channel_id).await?;
-                    Ok(MentionResponse {
-                        content,
-                        attachment: None,
-                    })
-                }
-                "reports" => {
-                    let content =
-                        earnings::handle_after_daily_for_channel(finance, http, channel_id).await?;
-                    Ok(MentionResponse {
-                        content,
-                        attachment: None,
-                    })
-                }
-                _ => Err("earnings mode must be weekly | daily | reports".into()),
+        "earnings" => match args.required("mode")? {
+            "weekly" => {
+                let resp = earnings::handle_weekly_mention(finance, images).await?;
+                Ok(MentionResponse {
+                    attachments: resp.attachments(),
+                    content: resp.content,
+                })
             }
-        }
-        _ => Err(format!("Unknown command: {}. {}", cmd, help_text())),
+            "daily" => {
+                let content =
+                    earnings::handle_daily_for_channel(finance, http, channel_id, images).await?;
+                Ok(MentionResponse::text(content))
+            }
+            _ => {
+                let content =
+                    earnings::handle_after_daily_for_channel(finance, http, channel_id).await?;
+                Ok(MentionResponse::text(content))
+            }
+        },
+        other => Err(BotError::internal(format!(
+            "mention command {other} has no handler"
+        ))),
     }
 }
 
-pub fn help_text() -> &'static str {
-    "Usage: @Bot quote TICKER | holders TICKER TYPE [LIMIT] | news TICKER [LIMIT] | income|balance|cashflow TICKER FREQ [YEAR] [QUARTER] | earnings weekly|daily|reports"
+/// The slash command a mention command stands for; `earnings MODE` covers three.
+pub fn slash_name<'a>(command: &'a str, mode: Option<&str>) -> &'a str {
+    match (command, mode) {
+        ("earnings", Some("weekly")) => "weekly-earnings",
+        ("earnings", Some("daily")) => "daily-earnings",
+        ("earnings", _) => "er-reports",
+        _ => command,
+    }
+}
+
+async fn check_access(
+    store: Option<&dyn StateStore>,
+    invoker: Option<&Invoker>,
+    command: &str,
+) -> BotResult<()> {
+    let locale = i18n::guild_locale(store, invoker.map(|i| i.guild_id)).await;
+    check_roles(command, invoker, store, locale).await
+}
+
+/// Statement mentions always reply with the image; an `image` word anywhere in the arguments
+/// (`income AAPL image annual`) asks for what they do anyway, so it is dropped rather than
+/// read as a positional argument.
+fn without_image_keyword(tokens: &[String]) -> Vec<String> {
+    tokens
+        .iter()
+        .filter(|token| !token.eq_ignore_ascii_case("image"))
+        .cloned()
+        .collect()
 }
 
-fn parse_usize(raw: &str) -> Result<usize, std::num::ParseIntError> {
-    raw.parse::<usize>()
+/// `$TICKER` cashtags in free text, uppercased and deduplicated in order of appearance.
+/// A `$` must start a word and be followed by a letter, so `$5` and `US$5` are ignored.
+fn cashtags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        let starts_word = prev.is_none_or(|p| !p.is_alphanumeric());
+        prev = Some(c);
+        if c != '$' || !starts_word {
+            continue;
+        }
+        if !chars
+            .peek()
+            .is_some_and(|(_, next)| next.is_ascii_alphabetic())
+        {
+            continue;
+        }
+
+        let rest = &text[idx + 1..];
+        let end = rest
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '-'))
+            .unwrap_or(rest.len());
+        let tag = rest[..end].trim_end_matches(['.', '-']).to_uppercase();
+        if tag.len() <= 12 && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
 }
 
-fn parse_i32(raw: &str) -> Result<i32, std::num::ParseIntError> {
-    raw.parse::<i32>()
+/// A compact quote line per cashtag; a failed symbol gets its error on its own line
+/// instead of failing the whole reply.
+async fn cashtag_quotes(finance: &FinanceService, tags: &[String]) -> String {
+    let shown = &tags[..tags.len().min(MAX_CASHTAGS)];
+    let lines = join_all(shown.iter().map(|tag| async move {
+        match quotes::compact_line(finance, tag).await {
+            Ok(line) => line,
+            Err(err) => {
+                err.log("cashtag");
+                format!("{tag}: {}", err.user_message())
+            }
+        }
+    }))
+    .await;
+
+    let mut content = lines.join("\n");
+    if tags.len() > MAX_CASHTAGS {
+        content.push_str(&format!(
+            "\n(showing the first {MAX_CASHTAGS} of {} symbols)",
+            tags.len()
+        ));
+    }
+    content
 }
 
+/// Usage for every mention command, generated from the same definitions as the slash
+/// commands.
+pub fn help_text() -> String {
+    let mut lines = vec![
+        "Usage: @Bot COMMAND [ARGS] — quote multi-word values, or pass `name=value`:".to_string(),
+    ];
+    for spec in SPECS.iter() {
+        let aliases = if spec.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" (alias: {})", spec.aliases.join(", "))
+        };
+        lines.push(format!(
+            "• `{}`{} — {}",
+            spec.usage(),
+            aliases,
+            spec.description
+        ));
+    }
+    lines.push(format!(
+        "• `$TICKER` anywhere in a message — one-line quote for up to {MAX_CASHTAGS} symbols"
+    ));
+    lines.push(format!(
+        "• `quote SPY; news NVDA 2` — up to {MAX_BATCH} commands separated by `;`, in one reply"
+    ));
+    lines.join("\n")
+}
@@ -1,6 +1,40 @@
+pub mod admin;
+pub mod ask;
+pub mod args;
+pub mod backtest;
+pub mod bind_ticker;
+pub mod brief;
+pub mod config;
+pub mod dcf;
 pub mod earnings;
+pub mod earnings_ics;
+pub mod error;
+pub mod etf;
+pub mod event_alert;
+pub mod expected_move;
+pub mod export;
+pub mod framework;
 pub mod fundamentals;
+pub mod fundamentals_chart;
+pub mod held_by;
 pub mod holders;
+pub mod holders_trend;
+pub mod implied_moves;
 pub mod news;
+pub mod news_multi;
+pub mod oi_change;
+pub mod ping;
+pub mod quality;
+pub mod quick_actions;
 pub mod quotes;
+pub mod registration;
+pub mod risk;
+pub mod rs;
+pub mod seasonality;
+pub mod stats;
+pub mod strategy;
+pub mod symbol;
+pub mod timezone;
+pub mod universe;
+pub mod vol_surface;
 pub mod mention;
@@ -1,22 +1,32 @@
-use chrono::Utc;
 use serenity::all::{
-    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateCommand,
-    CreateCommandOption,
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+    CreateEmbed, CreateEmbedFooter,
 };
+use serenity::async_trait;
+use tracing::warn;
 
+use crate::models::{NewsItem, SourceType};
+use crate::service::finance::article_preview::ArticlePreview;
 use crate::service::finance::FinanceService;
+use crate::service::sparkline;
+use crate::service::timefmt::{self, TimestampStyle};
+
+use super::args::{get_bool_opt, get_i64_opt, get_str_opt};
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Headlines searched when filtering by source type, the most `get_news` returns.
+const MAX_SEARCH: usize = 20;
 
 pub fn register_command() -> CreateCommand {
     CreateCommand::new("news")
         .description("Latest headlines for a ticker")
-        .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "ticker",
-                "Ticker symbol, e.g., AAPL",
-            )
-            .required(true),
-        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::Integer,
@@ -26,38 +36,114 @@ pub fn register_command() -> CreateCommand {
             .min_int_value(1)
             .max_int_value(10),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "source_type",
+                "Only company press releases or only media coverage (default both)",
+            )
+            .add_string_choice("Press releases", SourceType::PressRelease.name())
+            .add_string_choice("Media", SourceType::Media.name()),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "preview",
+            "Show the top article's summary and image (default false)",
+        ))
+        .add_option(ephemeral_option())
 }
 
+/// Headlines with a sparkline of the last week's closes, when the history loads, for price
+/// context. `source_type:` keeps only press releases or only media coverage; `preview:true` adds an embed with the top article's Open Graph summary and image.
 pub async fn handle(
     command: &CommandInteraction,
     finance: &FinanceService,
-) -> Result<String, String> {
-    let ticker = get_str_opt(command, "ticker").ok_or("ticker is required")?;
-    let limit = get_int_opt(command, "limit").unwrap_or(1).clamp(1, 10) as usize;
-    handle_text(finance, ticker, limit).await
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let limit = get_i64_opt(command, "limit").unwrap_or(1).clamp(1, 10) as usize;
+    let source_type = get_str_opt(command, "source_type").and_then(SourceType::from_name);
+    let preview = get_bool_opt(command, "preview").unwrap_or(false);
+    let symbol = symbol::resolve(finance, ticker).await?;
+    let (news, spark) = tokio::join!(
+        latest(finance, &symbol, limit, source_type),
+        sparkline::for_symbol(finance, &symbol)
+    );
+    let news = news?;
+    let mut response = CommandResponse::text(format_headlines(&symbol, &news, source_type));
+    if preview {
+        let top = &news[0];
+        if let Some(article) = finance.article_preview(&top.link).await {
+            response = response.with_embed(preview_embed(top, &article));
+        }
+    }
+    Ok(match spark {
+        Ok(png) => response.with_attachment(CreateAttachment::bytes(png, "sparkline.png")),
+        Err(err) => {
+            warn!("news sparkline failed for {symbol}: {err}");
+            response
+        }
+    })
 }
 
 pub async fn handle_text(
     finance: &FinanceService,
     ticker: &str,
     limit: usize,
-) -> Result<String, String> {
-    let news = finance
-        .get_news(ticker, limit)
-        .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+    source_type: Option<SourceType>,
+) -> BotResult<String> {
+    let ticker = symbol::resolve(finance, ticker).await?;
+    let news = latest(finance, &ticker, limit, source_type).await?;
+    Ok(format_headlines(&ticker, &news, source_type))
+}
 
+/// At least one headline, newest first. With a source type, the widest search is filtered so a
+/// ticker with mostly media coverage still turns up its releases.
+async fn latest(
+    finance: &FinanceService,
+    ticker: &str,
+    limit: usize,
+    source_type: Option<SourceType>,
+) -> BotResult<Vec<NewsItem>> {
+    let mut news = match source_type {
+        Some(wanted) => {
+            let mut news = finance.get_news(ticker, MAX_SEARCH).await?;
+            news.retain(|item| item.source_type == wanted);
+            news
+        }
+        None => finance.get_news(ticker, limit).await?,
+    };
+    if news.is_empty() {
+        return Err(BotError::not_found(format!(
+            "no {} found for {}",
+            kind_label(source_type),
+            ticker.to_uppercase()
+        )));
+    }
+    news.truncate(limit);
+    Ok(news)
+}
+
+fn kind_label(source_type: Option<SourceType>) -> &'static str {
+    match source_type {
+        Some(SourceType::PressRelease) => "press releases",
+        Some(SourceType::Media) => "media coverage",
+        None => "news",
+    }
+}
+
+fn format_headlines(ticker: &str, news: &[NewsItem], source_type: Option<SourceType>) -> String {
     let mut lines = Vec::new();
-    lines.push(format!("Latest news for {}", ticker.to_uppercase()));
+    lines.push(format!(
+        "Latest {} for {}",
+        kind_label(source_type),
+        ticker.to_uppercase()
+    ));
     for item in news {
         let source = item.source.clone().unwrap_or_else(|| "Unknown".to_string());
         let time_str = item
             .published_at
-            .map(|t| {
-                t.with_timezone(&Utc)
-                    .format("%Y-%m-%d %H:%M UTC")
-                    .to_string()
-            })
+            .map(|t| timefmt::discord(&t, TimestampStyle::ShortDateTime))
             .unwrap_or_else(|| "time n/a".to_string());
         lines.push(format!(
             "• [{}]({}) — {} ({})",
@@ -65,29 +151,45 @@ pub async fn handle_text(
         ));
     }
 
-    Ok(lines.join("\n"))
+    lines.join("\n")
 }
 
-fn get_str_opt<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        })
+/// The top article as an embed: headline linking to it, the page's summary and image (Yahoo's
+/// thumbnail when the page has none) and the publisher.
+pub fn preview_embed(item: &NewsItem, preview: &ArticlePreview) -> CreateEmbed {
+    let mut embed = CreateEmbed::new().title(&item.title).url(&item.link);
+    if let Some(description) = &preview.description {
+        embed = embed.description(description);
+    }
+    if let Some(image) = preview.image.as_ref().or(item.thumbnail.as_ref()) {
+        embed = embed.image(image);
+    }
+    if let Some(source) = item.source.as_ref().or(preview.site_name.as_ref()) {
+        embed = embed.footer(CreateEmbedFooter::new(source));
+    }
+    if let Some(published) = item.published_at {
+        embed = embed.timestamp(published);
+    }
+    embed
 }
 
-fn get_int_opt(command: &CommandInteraction, name: &str) -> Option<i64> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::Integer(i) => Some(i),
-            _ => None,
-        })
+pub struct NewsCommand;
+
+#[async_trait]
+impl Command for NewsCommand {
+    fn name(&self) -> &str {
+        "news"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn user_installable(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
 }
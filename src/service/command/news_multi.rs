@@ -0,0 +1,166 @@
+use futures_util::stream::{self, StreamExt};
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::models::NewsItem;
+use crate::service::finance::exchange;
+use crate::service::finance::news::canonical_url;
+use crate::service::finance::FinanceService;
+use crate::service::timefmt::{self, TimestampStyle};
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+
+/// Most tickers in one roundup.
+pub const MAX_TICKERS: usize = 10;
+/// Concurrent headline lookups.
+const LOOKUPS: usize = 4;
+
+/// The latest headline of one ticker, `None` when it has none, `Err` when it failed to load.
+pub type Headline = (String, Result<Option<NewsItem>, String>);
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("news-multi")
+        .description("Latest headline for up to 10 tickers at once")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "tickers",
+                "Tickers separated by commas or spaces, e.g., AAPL, MSFT, NVDA",
+            )
+            .required(true),
+        )
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let input = get_str_opt(command, "tickers")
+        .ok_or_else(|| BotError::bad_input("tickers is required"))?;
+    let tickers = parse_tickers(input)?;
+
+    let headlines: Vec<Headline> = stream::iter(tickers)
+        .map(|ticker| async move {
+            let item = finance
+                .get_news(&ticker, 1)
+                .await
+                .map(|news| news.into_iter().next())
+                .map_err(|e| {
+                    warn!("news-multi: {ticker} headlines failed: {e}");
+                    e.to_string()
+                });
+            (ticker, item)
+        })
+        .buffered(LOOKUPS)
+        .collect()
+        .await;
+    if headlines.iter().all(|(_, item)| item.is_err()) {
+        return Err(BotError::internal(format!(
+            "headlines failed to load for all {} tickers",
+            headlines.len()
+        )));
+    }
+    Ok(CommandResponse::text(roundup_text(&headlines)))
+}
+
+/// Split a comma- or space-separated list into distinct tickers, in the order given.
+pub fn parse_tickers(input: &str) -> BotResult<Vec<String>> {
+    let mut tickers: Vec<String> = Vec::new();
+    for raw in input.split([',', ' ']).filter(|s| !s.is_empty()) {
+        let ticker = exchange::normalize_symbol(raw.trim_start_matches('$')).to_uppercase();
+        let valid = !ticker.is_empty()
+            && ticker
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '^' | '='));
+        if !valid {
+            return Err(BotError::bad_input(format!("`{raw}` is not a ticker")));
+        }
+        if !tickers.contains(&ticker) {
+            tickers.push(ticker);
+        }
+    }
+    if tickers.is_empty() {
+        return Err(BotError::bad_input("give at least one ticker"));
+    }
+    if tickers.len() > MAX_TICKERS {
+        return Err(BotError::bad_input(format!(
+            "at most {MAX_TICKERS} tickers at once, got {}",
+            tickers.len()
+        )));
+    }
+    Ok(tickers)
+}
+
+/// A line per story, in the order the tickers were asked. Tickers whose latest headline is the
+/// same story share a line; links are wrapped in `<>` so ten of them don't unfurl.
+pub fn roundup_text(headlines: &[Headline]) -> String {
+    let mut stories: Vec<(Vec<&str>, &NewsItem)> = Vec::new();
+    let mut quiet = Vec::new();
+    let mut failed = Vec::new();
+    for (ticker, item) in headlines {
+        match item {
+            Ok(Some(item)) => {
+                let link = canonical_url(&item.link);
+                match stories
+                    .iter_mut()
+                    .find(|(_, story)| canonical_url(&story.link) == link)
+                {
+                    Some((tickers, _)) => tickers.push(ticker),
+                    None => stories.push((vec![ticker.as_str()], item)),
+                }
+            }
+            Ok(None) => quiet.push(ticker.as_str()),
+            Err(_) => failed.push(ticker.as_str()),
+        }
+    }
+
+    let mut lines = vec!["🗞️ **News roundup**".to_string()];
+    for (tickers, item) in &stories {
+        let mut details = Vec::new();
+        if let Some(source) = &item.source {
+            details.push(source.clone());
+        }
+        if let Some(published) = item.published_at {
+            details.push(timefmt::discord(&published, TimestampStyle::Relative));
+        }
+        let details = if details.is_empty() {
+            String::new()
+        } else {
+            format!(" — {}", details.join(", "))
+        };
+        lines.push(format!(
+            "**{}** · [{}](<{}>){details}",
+            tickers.join(", "),
+            item.title,
+            item.link
+        ));
+    }
+    if !quiet.is_empty() {
+        lines.push(format!("No recent headlines: {}", quiet.join(", ")));
+    }
+    if !failed.is_empty() {
+        lines.push(format!("*Failed to load: {}*", failed.join(", ")));
+    }
+    lines.join("\n")
+}
+
+pub struct NewsMultiCommand;
+
+#[async_trait]
+impl Command for NewsMultiCommand {
+    fn name(&self) -> &str {
+        "news-multi"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+
+use crate::service::caching::RedisCache;
+use crate::service::finance::open_interest::OpenInterestChange;
+use crate::service::finance::options::OptionKind;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Contracts listed under each of builds and drops.
+const TOP_CONTRACTS: usize = 8;
+/// Strikes listed as where positioning is accumulating.
+const TOP_STRIKES: usize = 5;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("oi-change")
+        .description("Largest day-over-day open-interest builds and drops by strike")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., SPY; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    cache: Option<&RedisCache>,
+) -> BotResult<CommandResponse> {
+    if cache.is_none() {
+        return Err(BotError::not_found(
+            "open-interest history is unavailable; Redis is not configured",
+        ));
+    }
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let comparison = finance.open_interest_comparison(&symbol).await?;
+    let Some((since, _)) = &comparison.previous else {
+        return Ok(CommandResponse::text(format!(
+            "📸 Saved today's open interest for **{symbol}**. Day-over-day changes show from the next trading day."
+        )));
+    };
+    let since = since.format("%a, %b %-d");
+    let changes = comparison.changes();
+    if changes.is_empty() {
+        return Ok(CommandResponse::text(format!(
+            "No open-interest changes for **{symbol}** since {since}."
+        )));
+    }
+
+    let net = |kind: OptionKind| -> i64 {
+        changes
+            .iter()
+            .filter(|c| c.kind == kind)
+            .map(OpenInterestChange::change)
+            .sum()
+    };
+    let mut lines = vec![
+        format!("📊 **{symbol} open interest — changes since {since}**"),
+        format!(
+            "Calls {} · Puts {} (nearest expirations)",
+            signed(net(OptionKind::Call)),
+            signed(net(OptionKind::Put))
+        ),
+    ];
+
+    let builds: Vec<&OpenInterestChange> = changes
+        .iter()
+        .filter(|c| c.change() > 0)
+        .take(TOP_CONTRACTS)
+        .collect();
+    if !builds.is_empty() {
+        lines.push(String::new());
+        lines.push("**Largest builds**".to_string());
+        lines.extend(builds.into_iter().map(contract_line));
+    }
+    let drops: Vec<&OpenInterestChange> = changes
+        .iter()
+        .rev()
+        .filter(|c| c.change() < 0)
+        .take(TOP_CONTRACTS)
+        .collect();
+    if !drops.is_empty() {
+        lines.push(String::new());
+        lines.push("**Largest drops**".to_string());
+        lines.extend(drops.into_iter().map(contract_line));
+    }
+
+    let accumulating = accumulating_strikes(&changes);
+    if !accumulating.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "🧲 Building at: {}",
+            accumulating
+                .iter()
+                .map(|(strike, change)| format!(
+                    "{} ({})",
+                    format::strike(*strike),
+                    signed(*change)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    Ok(CommandResponse::text(lines.join("\n")))
+}
+
+/// `• Oct 24 580C +12.35K → 45.20K`
+fn contract_line(change: &OpenInterestChange) -> String {
+    format!(
+        "• {} {}{} {} → {}",
+        change.expiration.format("%b %-d"),
+        format::strike(change.strike),
+        change.kind.letter(),
+        signed(change.change()),
+        format::shares(change.current as f64)
+    )
+}
+
+/// Strikes with the largest net open-interest added across expirations and both sides.
+fn accumulating_strikes(changes: &[OpenInterestChange]) -> Vec<(f64, i64)> {
+    let mut by_strike: BTreeMap<i64, (f64, i64)> = BTreeMap::new();
+    for change in changes {
+        let entry = by_strike
+            .entry((change.strike * 100.0).round() as i64)
+            .or_insert((change.strike, 0));
+        entry.1 += change.change();
+    }
+    let mut strikes: Vec<(f64, i64)> = by_strike
+        .into_values()
+        .filter(|(_, change)| *change > 0)
+        .collect();
+    strikes.sort_by_key(|(_, change)| std::cmp::Reverse(*change));
+    strikes.truncate(TOP_STRIKES);
+    strikes
+}
+
+fn signed(value: i64) -> String {
+    let sign = if value > 0 { "+" } else { "" };
+    format!("{sign}{}", format::shares(value as f64))
+}
+
+pub struct OiChangeCommand;
+
+#[async_trait]
+impl Command for OiChangeCommand {
+    fn name(&self) -> &str {
+        "oi-change"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.cache).await
+    }
+}
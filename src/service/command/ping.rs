@@ -0,0 +1,25 @@
+use serenity::all::CreateCommand;
+use serenity::async_trait;
+
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+
+pub struct PingCommand;
+
+#[async_trait]
+impl Command for PingCommand {
+    fn name(&self) -> &str {
+        "ping"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("ping").description("Simple ping command")
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::None
+    }
+
+    async fn execute(&self, _ctx: &CommandContext<'_>) -> CommandResult {
+        Ok(CommandResponse::text("Pong!"))
+    }
+}
@@ -0,0 +1,109 @@
+//! `/quality ticker`: Piotroski F-score and Altman Z-score from the latest annual statements,
+//! with the tests behind the F-score.
+
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+
+use crate::service::finance::quality::{QualityReport, Z_DISTRESS, Z_SAFE};
+use crate::service::finance::FinanceService;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("quality")
+        .description("Piotroski F-score and Altman Z-score from the annual statements")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let report = finance.quality(&symbol).await?;
+    if report.f_score.is_none() && report.z_score.is_none() {
+        return Err(BotError::not_found(format!(
+            "not enough annual statement data to score {symbol}"
+        )));
+    }
+    Ok(CommandResponse::text(quality_text(&symbol, &report)))
+}
+
+/// The F-score with a line per test, then the Z-score with its zone and ratios.
+pub fn quality_text(symbol: &str, report: &QualityReport) -> String {
+    let mut lines = vec![format!(
+        "🩺 **{symbol} quality** (fiscal year ended {})",
+        report.fiscal_year.format("%b %-d, %Y")
+    )];
+
+    lines.push(String::new());
+    match &report.f_score {
+        Some(f) => {
+            let missing = f.criteria.len() - f.evaluated();
+            let note = if missing > 0 {
+                format!(" ({missing} not reported)")
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "**Piotroski F-score {}/{}**{note}",
+                f.score(),
+                f.criteria.len()
+            ));
+            for criterion in &f.criteria {
+                let mark = match criterion.passed {
+                    Some(true) => "✅",
+                    Some(false) => "❌",
+                    None => "➖",
+                };
+                lines.push(format!("{mark} {}", criterion.name));
+            }
+        }
+        None => lines.push("**Piotroski F-score** unavailable: no prior year to compare".into()),
+    }
+
+    lines.push(String::new());
+    match &report.z_score {
+        Some(z) => {
+            lines.push(format!(
+                "**Altman Z-score {:.2}** · {} (safe above {Z_SAFE}, distress below {Z_DISTRESS})",
+                z.z,
+                z.zone().label()
+            ));
+            lines.push(format!(
+                "WC/TA {:.2} · RE/TA {:.2} · EBIT/TA {:.2} · MVE/TL {:.2} · Sales/TA {:.2}",
+                z.working_capital, z.retained_earnings, z.ebit, z.market_value, z.sales
+            ));
+        }
+        None => lines.push("**Altman Z-score** unavailable: a figure it needs is missing".into()),
+    }
+    lines.join("\n")
+}
+
+pub struct QualityCommand;
+
+#[async_trait]
+impl Command for QualityCommand {
+    fn name(&self) -> &str {
+        "quality"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,231 @@
+//! Buttons under a `/quote` reply: 📰 latest headlines, 📊 the income statement image and 🔔 a
+//! default price alert. Each answers in a new message, leaving the quote in place.
+//!
+//! Alerts are watched by Lambda-bot, which reads the stored alerts when it starts; one made
+//! here is stored the same way `/admin import` stores them.
+
+use chrono::{DateTime, Utc};
+use serenity::all::{
+    ButtonStyle, CreateActionRow, CreateAttachment, CreateButton, GuildId, Permissions, UserId,
+};
+
+use crate::models::StatementType;
+use crate::service::audit::{self, AuditEvent};
+use crate::service::caching::collections::price_alerts::{
+    PriceAlert, PriceAlertLevel, PriceDirection,
+};
+
+use super::error::{BotError, BotResult};
+use super::framework::{CommandResponse, ComponentContext};
+use super::fundamentals::render_statement_image;
+use super::news;
+
+/// `custom_id` of a quick action: `quote:<action>:<SYMBOL>`.
+const PREFIX: &str = "quote:";
+/// Headlines the 📰 button shows.
+const NEWS_LIMIT: usize = 3;
+/// The 🔔 alert fires on a move of this fraction either way from the price when it was set.
+pub const DEFAULT_ALERT_MOVE: f64 = 0.05;
+
+/// What a quick action button does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAction {
+    News,
+    Fundamentals,
+    Alert,
+}
+
+impl QuickAction {
+    const ALL: [QuickAction; 3] = [
+        QuickAction::News,
+        QuickAction::Fundamentals,
+        QuickAction::Alert,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            QuickAction::News => "news",
+            QuickAction::Fundamentals => "fundamentals",
+            QuickAction::Alert => "alert",
+        }
+    }
+
+    fn emoji(self) -> char {
+        match self {
+            QuickAction::News => '📰',
+            QuickAction::Fundamentals => '📊',
+            QuickAction::Alert => '🔔',
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            QuickAction::News => "News",
+            QuickAction::Fundamentals => "Fundamentals",
+            QuickAction::Alert => "Alert ±5%",
+        }
+    }
+
+    pub fn custom_id(self, symbol: &str) -> String {
+        format!("{PREFIX}{}:{symbol}", self.name())
+    }
+
+    /// The action and symbol of a button's `custom_id`.
+    pub fn parse(custom_id: &str) -> Option<(Self, &str)> {
+        let (action, symbol) = custom_id.strip_prefix(PREFIX)?.split_once(':')?;
+        let action = Self::ALL.into_iter().find(|a| a.name() == action)?;
+        (!symbol.is_empty()).then_some((action, symbol))
+    }
+}
+
+/// The row of quick action buttons for `symbol`.
+pub fn buttons(symbol: &str) -> CreateActionRow {
+    CreateActionRow::Buttons(
+        QuickAction::ALL
+            .into_iter()
+            .map(|action| {
+                CreateButton::new(action.custom_id(symbol))
+                    .label(action.label())
+                    .emoji(action.emoji())
+                    .style(ButtonStyle::Secondary)
+            })
+            .collect(),
+    )
+}
+
+/// Run the quick action of the pressed button.
+pub async fn handle(ctx: &ComponentContext<'_>) -> BotResult<CommandResponse> {
+    let (action, symbol) = QuickAction::parse(&ctx.interaction.data.custom_id)
+        .ok_or_else(|| BotError::bad_input("this quote no longer takes input"))?;
+    let response = match action {
+        QuickAction::News => {
+            let content = news::handle_text(ctx.finance, symbol, NEWS_LIMIT, None).await?;
+            CommandResponse::text(content)
+        }
+        QuickAction::Fundamentals => {
+            let (content, image) = render_statement_image(
+                ctx.finance,
+                StatementType::IncomeStatement,
+                symbol,
+                "annual",
+                None,
+                None,
+                None,
+                &ctx.config.current().images,
+            )
+            .await?;
+            let name = image.file_name("fundamentals");
+            CommandResponse::text(content)
+                .with_attachment(CreateAttachment::bytes(image.bytes, name))
+        }
+        QuickAction::Alert => CommandResponse::text(set_alert(ctx, symbol).await?),
+    };
+    Ok(response.as_followup())
+}
+
+async fn set_alert(ctx: &ComponentContext<'_>, symbol: &str) -> BotResult<String> {
+    let interaction = ctx.interaction;
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| BotError::bad_input("price alerts can only be set in a server"))?;
+    let allowed = interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|p| p.contains(Permissions::MANAGE_MESSAGES));
+    if !allowed {
+        return Err(BotError::Forbidden(
+            "setting a price alert needs the Manage Messages permission".to_string(),
+        ));
+    }
+    let store = ctx
+        .store
+        .ok_or_else(|| BotError::internal("price alerts need a state store"))?;
+
+    let quote = ctx.finance.get_price(symbol).await?;
+    let price = quote
+        .price
+        .ok_or_else(|| BotError::not_found(format!("no current price for {symbol}")))?;
+    let alert = default_alert(
+        &quote.symbol,
+        price,
+        guild_id,
+        interaction.channel_id.get(),
+        Utc::now(),
+    );
+
+    let mut alerts = store
+        .price_alerts()
+        .await
+        .map_err(|e| BotError::internal(format!("failed to load price alerts: {e}")))?
+        .remove(&alert.symbol)
+        .unwrap_or_default();
+    alerts.push(alert.clone());
+    store
+        .save_price_alerts(&alert.symbol, &alerts)
+        .await
+        .map_err(|e| BotError::internal(format!("failed to save price alert: {e}")))?;
+    audit::record(
+        &ctx.ctx.http,
+        Some(store),
+        guild_id,
+        AuditEvent::AlertRegistered {
+            user: interaction.user.id,
+            alert: &alert,
+        },
+    )
+    .await;
+    Ok(alert_confirmation(&alert, interaction.user.id))
+}
+
+/// A two-level alert, [`DEFAULT_ALERT_MOVE`] above and below `price`, posting into
+/// `channel_id`.
+pub fn default_alert(
+    symbol: &str,
+    price: f64,
+    guild_id: GuildId,
+    channel_id: u64,
+    now: DateTime<Utc>,
+) -> PriceAlert {
+    let level = |label: &str, target: f64, direction| PriceAlertLevel {
+        label: label.to_string(),
+        target: (target * 100.0).round() / 100.0,
+        direction,
+        fired: false,
+    };
+    PriceAlert {
+        id: format!("{symbol}-{}", now.timestamp_nanos_opt().unwrap_or_default()),
+        symbol: symbol.to_string(),
+        created_at: now,
+        created_price: price,
+        target_guild_id: guild_id.get(),
+        target_channel_id: channel_id,
+        levels: vec![
+            level(
+                "Up 5%",
+                price * (1.0 + DEFAULT_ALERT_MOVE),
+                PriceDirection::AtOrAbove,
+            ),
+            level(
+                "Down 5%",
+                price * (1.0 - DEFAULT_ALERT_MOVE),
+                PriceDirection::AtOrBelow,
+            ),
+        ],
+    }
+}
+
+fn alert_confirmation(alert: &PriceAlert, user: UserId) -> String {
+    let targets: Vec<String> = alert
+        .levels
+        .iter()
+        .map(|level| format!("{} {:.2}", level.label, level.target))
+        .collect();
+    format!(
+        "🔔 <@{user}> set a {} price alert from {:.2}: {}. Lambda-bot starts watching it on its \
+         next restart.",
+        alert.symbol,
+        alert.created_price,
+        targets.join(" · ")
+    )
+}
@@ -1,72 +1,176 @@
+use chrono::Utc;
+use chrono_tz::Tz;
 use serenity::all::{
-    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateCommand,
-    CreateCommandOption,
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
 };
+use serenity::async_trait;
+use tracing::warn;
 
+use crate::service::finance::exchange::Exchange;
+use crate::service::finance::fx::Conversion;
 use crate::service::finance::FinanceService;
+use crate::service::sparkline;
+use crate::service::timefmt;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{
+    ephemeral_option, Command, CommandContext, CommandResponse, CommandResult, ComponentContext,
+};
+use super::{quick_actions, symbol};
 
 pub fn register_command() -> CreateCommand {
     CreateCommand::new("quote")
         .description("Get a simple quote for a ticker")
-        .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "ticker",
-                "Ticker symbol, e.g., AAPL",
-            )
-            .required(true),
-        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "currency",
+            "Convert prices to this currency, e.g., USD",
+        ))
+        .add_option(ephemeral_option())
 }
 
+/// `timezone` is the reader's preference for the session times; `None` shows them in the
+/// exchange's own timezone. The reply carries a sparkline of the last week's closes when the
+/// history loads, and news, fundamentals and alert buttons.
 pub async fn handle(
     command: &CommandInteraction,
     finance: &FinanceService,
-) -> Result<String, String> {
-    let ticker = get_str_opt(command, "ticker").ok_or("ticker is required")?;
-    build_response(finance, ticker).await
+    timezone: Option<Tz>,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let currency = get_str_opt(command, "currency");
+    let target = currency.map(symbol::parse_currency).transpose()?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+    let (text, spark) = tokio::join!(
+        quote_text(finance, &symbol, target.as_deref(), timezone),
+        sparkline::for_symbol(finance, &symbol)
+    );
+    let response = CommandResponse::text(text?).with_components(quick_actions::buttons(&symbol));
+    Ok(match spark {
+        Ok(png) => response.with_attachment(CreateAttachment::bytes(png, "sparkline.png")),
+        Err(err) => {
+            warn!("quote sparkline failed for {symbol}: {err}");
+            response
+        }
+    })
 }
 
-pub async fn handle_text(finance: &FinanceService, ticker: &str) -> Result<String, String> {
-    build_response(finance, ticker).await
+pub async fn handle_text(
+    finance: &FinanceService,
+    ticker: &str,
+    currency: Option<&str>,
+    timezone: Option<Tz>,
+) -> BotResult<String> {
+    build_response(finance, ticker, currency, timezone).await
 }
 
-async fn build_response(finance: &FinanceService, ticker: &str) -> Result<String, String> {
-    let quote = finance
-        .get_price(ticker)
-        .await
-        .map_err(|e| format!("fetch error: {e}"))?;
+/// One-line quote for cashtag replies: `NVDA 181.23 USD (+1.17%)`.
+pub async fn compact_line(finance: &FinanceService, ticker: &str) -> BotResult<String> {
+    let ticker = &symbol::resolve(finance, ticker).await?;
+    let quote = finance.get_price(ticker).await?;
+    let currency = quote
+        .currency
+        .as_deref()
+        .unwrap_or(Exchange::for_symbol(&quote.symbol).currency);
+
+    let price = quote
+        .price
+        .map(|p| format!("{p:.2} {currency}"))
+        .unwrap_or_else(|| "n/a".to_string());
+    let pct = quote
+        .percent_change
+        .map(|p| format!(" ({p:+.2}%)"))
+        .unwrap_or_default();
+    Ok(format!("{} {}{}", quote.symbol, price, pct))
+}
+
+async fn build_response(
+    finance: &FinanceService,
+    ticker: &str,
+    currency: Option<&str>,
+    timezone: Option<Tz>,
+) -> BotResult<String> {
+    let target = currency.map(symbol::parse_currency).transpose()?;
+    let ticker = symbol::resolve(finance, ticker).await?;
+    quote_text(finance, &ticker, target.as_deref(), timezone).await
+}
+
+/// The quote line for a resolved symbol, converted to `target` when given.
+async fn quote_text(
+    finance: &FinanceService,
+    ticker: &str,
+    target: Option<&str>,
+    timezone: Option<Tz>,
+) -> BotResult<String> {
+    let quote = finance.get_price(ticker).await?;
+    let exchange = Exchange::for_symbol(&quote.symbol);
+    let reported = quote.currency.as_deref().unwrap_or(exchange.currency);
+    let fx = match target {
+        Some(target) => finance.conversion(reported, target).await?,
+        None => Conversion::identity(reported),
+    };
 
     let mut parts = Vec::new();
     parts.push(format!("{} ({})", quote.name, quote.symbol));
     if let Some(price) = quote.price {
-        let currency = quote.currency.as_deref().unwrap_or("");
-        parts.push(format!("Price: {:.2} {}", price, currency));
+        parts.push(format!("Price: {:.2} {}", fx.apply(price), fx.to));
     }
     if let Some(ch) = quote.change {
         let pct = quote
             .percent_change
             .map(|p| format!("{:+.2}%", p))
             .unwrap_or_default();
-        parts.push(format!("Change: {:+.2} {}", ch, pct));
+        parts.push(format!("Change: {:+.2} {}", fx.apply(ch), pct));
     }
     if let Some(pm) = quote.pre_market_price {
-        parts.push(format!("Pre-market: {:.2}", pm));
+        parts.push(format!("Pre-market: {:.2}", fx.apply(pm)));
     }
     if let Some(ah) = quote.after_hours_price {
-        parts.push(format!("After-hours: {:.2}", ah));
+        parts.push(format!("After-hours: {:.2}", fx.apply(ah)));
+    }
+    if let Some(note) = fx.note() {
+        parts.push(note);
     }
+    parts.push(format!(
+        "{}: {}",
+        exchange.name,
+        exchange.session_status(Utc::now(), timezone)
+    ));
 
     Ok(parts.join(" | "))
 }
 
-fn get_str_opt<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
-    command
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == name)
-        .and_then(|o| match o.value {
-            CommandDataOptionValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        })
+pub struct QuoteCommand;
+
+#[async_trait]
+impl Command for QuoteCommand {
+    fn name(&self) -> &str {
+        "quote"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn user_installable(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let interaction = ctx.interaction;
+        let timezone =
+            timefmt::preferred_timezone(ctx.store, interaction.guild_id, interaction.user.id).await;
+        handle(interaction, ctx.finance, timezone).await
+    }
+
+    async fn component(&self, ctx: &ComponentContext<'_>) -> CommandResult {
+        quick_actions::handle(ctx).await
+    }
 }
@@ -0,0 +1,190 @@
+//! Startup command registration.
+//!
+//! Discord keeps registered commands between restarts, so instead of re-creating every
+//! command on each `ready` the desired definitions are compared with what Discord already
+//! has: only new commands are created, changed ones edited and stale ones deleted.
+
+use serde_json::{Map, Value};
+use serenity::all::{Command as DiscordCommand, CommandId, CreateCommand, GuildId, Http};
+use tracing::{error, info};
+
+/// Where a set of commands is registered.
+#[derive(Debug, Clone, Copy)]
+pub enum Scope {
+    Global,
+    Guild(GuildId),
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::Global => write!(f, "global"),
+            Scope::Guild(id) => write!(f, "guild {id}"),
+        }
+    }
+}
+
+/// What a sync changed; failures are counted and logged, never swallowed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    pub created: usize,
+    pub edited: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+}
+
+/// Bring the commands registered in `scope` in line with `desired`.
+pub async fn sync(http: &Http, scope: Scope, desired: Vec<CreateCommand>) -> SyncReport {
+    let mut report = SyncReport::default();
+
+    let existing = match scope {
+        Scope::Global => DiscordCommand::get_global_commands_with_localizations(http).await,
+        Scope::Guild(guild_id) => guild_id.get_commands_with_localizations(http).await,
+    };
+    let existing = match existing {
+        Ok(commands) => commands,
+        Err(err) => {
+            error!("failed to list {scope} commands; registration skipped: {err}");
+            report.failed = desired.len();
+            return report;
+        }
+    };
+
+    let mut matched: Vec<CommandId> = Vec::new();
+    for builder in desired {
+        let wanted = comparable(&serde_json::to_value(&builder).unwrap_or_default(), scope);
+        let name = wanted["name"].as_str().unwrap_or_default().to_string();
+
+        let current = existing.iter().find(|c| c.name == name);
+        let result = match current {
+            Some(cmd) => {
+                matched.push(cmd.id);
+                let have = comparable(&serde_json::to_value(cmd).unwrap_or_default(), scope);
+                if have == wanted {
+                    report.unchanged += 1;
+                    continue;
+                }
+                info!("/{name} changed in {scope}; updating");
+                let edited = match scope {
+                    Scope::Global => DiscordCommand::edit_global_command(http, cmd.id, builder)
+                        .await
+                        .map(|_| ()),
+                    Scope::Guild(guild_id) => guild_id
+                        .edit_command(http, cmd.id, builder)
+                        .await
+                        .map(|_| ()),
+                };
+                edited.map(|()| report.edited += 1)
+            }
+            None => {
+                let created = match scope {
+                    Scope::Global => DiscordCommand::create_global_command(http, builder)
+                        .await
+                        .map(|_| ()),
+                    Scope::Guild(guild_id) => {
+                        guild_id.create_command(http, builder).await.map(|_| ())
+                    }
+                };
+                created.map(|()| report.created += 1)
+            }
+        };
+        if let Err(err) = result {
+            error!("failed to register /{name} in {scope}: {err}");
+            report.failed += 1;
+        }
+    }
+
+    for stale in existing.iter().filter(|c| !matched.contains(&c.id)) {
+        let deleted = match scope {
+            Scope::Global => DiscordCommand::delete_global_command(http, stale.id).await,
+            Scope::Guild(guild_id) => guild_id.delete_command(http, stale.id).await,
+        };
+        match deleted {
+            Ok(()) => {
+                info!("removed stale /{} from {scope}", stale.name);
+                report.deleted += 1;
+            }
+            Err(err) => {
+                error!("failed to remove stale /{} from {scope}: {err}", stale.name);
+                report.failed += 1;
+            }
+        }
+    }
+
+    if report.failed > 0 {
+        error!("{scope} command registration finished with errors: {report:?}");
+    } else {
+        info!("{scope} command registration: {report:?}");
+    }
+    report
+}
+
+/// The parts of a command definition the bot controls, with Discord's defaults stripped so a
+/// builder and the registered command compare equal when nothing changed.
+pub fn comparable(command: &Value, scope: Scope) -> Value {
+    let mut out = Map::new();
+    for key in [
+        "name",
+        "name_localizations",
+        "description",
+        "description_localizations",
+        "options",
+        "default_member_permissions",
+    ] {
+        if let Some(value) = command.get(key).map(strip_defaults) {
+            if !is_default(&value) {
+                out.insert(key.to_string(), value);
+            }
+        }
+    }
+    // Builders leave the type unset for slash commands; Discord reports 1 (chat input).
+    let kind = command.get("type").and_then(Value::as_u64).unwrap_or(1);
+    out.insert("type".to_string(), Value::from(kind));
+    // Only meaningful for global commands; Discord reports `true` when it was never set.
+    if let Scope::Global = scope {
+        let dm = command
+            .get("dm_permission")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        out.insert("dm_permission".to_string(), Value::Bool(dm));
+
+        // Unset means guild install only, usable wherever `dm_permission` allows.
+        let integration_types = match command.get("integration_types") {
+            Some(Value::Array(types)) if !types.is_empty() => Value::Array(types.clone()),
+            _ => Value::from(vec![0]),
+        };
+        out.insert("integration_types".to_string(), integration_types);
+        let contexts = match command.get("contexts") {
+            Some(Value::Array(contexts)) if !contexts.is_empty() => Value::Array(contexts.clone()),
+            _ if dm => Value::from(vec![0, 1, 2]),
+            _ => Value::from(vec![0]),
+        };
+        out.insert("contexts".to_string(), contexts);
+    }
+    Value::Object(out)
+}
+
+fn strip_defaults(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), strip_defaults(v)))
+                .filter(|(_, v)| !is_default(v))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(strip_defaults).collect()),
+        other => other.clone(),
+    }
+}
+
+fn is_default(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        // Context-menu commands have no description; Discord reports an empty one.
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
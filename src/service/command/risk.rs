@@ -0,0 +1,185 @@
+//! `/risk ticker [period]`: annualized volatility, max drawdown, Sharpe ratio and beta next to
+//! SPY's, and how far the stock sits below its all-time closing high.
+
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::history::DailyClose;
+use crate::service::finance::risk::{self, Period, RiskStats, BENCHMARK};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub const COMMAND_NAME: &str = "risk";
+/// The whole history, for the all-time high.
+const FULL_RANGE: &str = "max";
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description(
+            "Volatility, drawdown and Sharpe ratio against SPY, and distance from the high",
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(Period::ALL.into_iter().fold(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "Window for the statistics (default 1 year)",
+            ),
+            |opt, p| opt.add_string_choice(p.label(), p.name()),
+        ))
+        .add_option(ephemeral_option())
+}
+
+/// What `/risk` shows for one symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskReport {
+    pub period: Period,
+    pub stats: RiskStats,
+    /// SPY over the same window; `None` when it failed to load.
+    pub benchmark: Option<RiskStats>,
+    pub beta: Option<f64>,
+    pub high: DailyClose,
+    pub last: DailyClose,
+    pub risk_free: f64,
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    risk_free: f64,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let period = match get_str_opt(command, "period") {
+        Some(name) => Period::from_name(name)
+            .ok_or_else(|| BotError::bad_input(format!("unknown period {name}")))?,
+        None => Period::OneYear,
+    };
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let (history, spy) = tokio::join!(
+        finance.daily_closes(&symbol, FULL_RANGE),
+        finance.daily_closes(BENCHMARK, period.name())
+    );
+    let history = history?;
+    // The stock's own figures still stand without the comparison.
+    let spy = spy
+        .inspect_err(|e| warn!("risk: no {BENCHMARK} history: {e}"))
+        .unwrap_or_default();
+
+    let window = risk::trailing(&history, period.years());
+    let not_enough = || BotError::not_found(format!("not enough price history for {symbol}"));
+    let stats = risk::risk_stats(window, risk_free).ok_or_else(not_enough)?;
+    let report = RiskReport {
+        period,
+        stats,
+        benchmark: risk::risk_stats(risk::trailing(&spy, period.years()), risk_free),
+        beta: risk::beta(window, &spy),
+        high: risk::all_time_high(&history).ok_or_else(not_enough)?,
+        last: *history.last().ok_or_else(not_enough)?,
+        risk_free,
+    };
+    Ok(CommandResponse::text(risk_text(&symbol, &report)))
+}
+
+/// A line per statistic with SPY's in brackets, then the all-time high.
+pub fn risk_text(symbol: &str, report: &RiskReport) -> String {
+    let stats = &report.stats;
+    let spy = report.benchmark.as_ref();
+    let versus = |value: Option<String>| {
+        value
+            .map(|v| format!(" ({BENCHMARK} {v})"))
+            .unwrap_or_default()
+    };
+    let percent = |v: f64| format!("{:.1}%", v * 100.0);
+    let signed = |v: f64| format!("{:+.1}%", v * 100.0);
+    let sharpe = |s: &RiskStats| s.sharpe.map(|v| format!("{v:.2}"));
+
+    let mut lines = vec![
+        format!(
+            "⚖️ **{symbol} risk** · {} ({} – {})",
+            report.period.label(),
+            stats.start.format("%b %-d, %Y"),
+            stats.end.format("%b %-d, %Y")
+        ),
+        format!(
+            "Volatility **{}** annualized{}",
+            percent(stats.volatility),
+            versus(spy.map(|s| percent(s.volatility)))
+        ),
+    ];
+    let drawdown = |s: &RiskStats| s.max_drawdown.map_or(0.0, |d| d.change());
+    lines.push(match stats.max_drawdown {
+        Some(d) => format!(
+            "Max drawdown **{}** ({} – {}){}",
+            signed(d.change()),
+            d.peak.date.format("%b %-d, %Y"),
+            d.trough.date.format("%b %-d, %Y"),
+            versus(spy.map(|s| signed(drawdown(s))))
+        ),
+        None => format!(
+            "Max drawdown **none**{}",
+            versus(spy.map(|s| signed(drawdown(s))))
+        ),
+    });
+    lines.push(format!(
+        "Sharpe ratio **{}**{} · risk-free {}",
+        sharpe(stats).unwrap_or_else(|| "n/a".to_string()),
+        versus(spy.and_then(sharpe)),
+        format::fraction_percent(report.risk_free)
+    ));
+    let beta = report
+        .beta
+        .map(|b| format!(" · beta {b:.2}"))
+        .unwrap_or_default();
+    lines.push(format!(
+        "Return **{}** a year{}{beta}",
+        signed(stats.annual_return),
+        versus(spy.map(|s| signed(s.annual_return)))
+    ));
+
+    lines.push(String::new());
+    let below = report.last.close / report.high.close - 1.0;
+    lines.push(if below >= 0.0 {
+        format!(
+            "🏔️ At its all-time closing high of {}",
+            format::per_share(report.high.close)
+        )
+    } else {
+        format!(
+            "🏔️ **{}** from its all-time closing high of {} ({})",
+            signed(below),
+            format::per_share(report.high.close),
+            report.high.date.format("%b %-d, %Y")
+        )
+    });
+    lines.join("\n")
+}
+
+pub struct RiskCommand;
+
+#[async_trait]
+impl Command for RiskCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        let risk_free = ctx.config.current().options.risk_free_rate;
+        handle(ctx.interaction, ctx.finance, risk_free).await
+    }
+}
@@ -0,0 +1,162 @@
+//! `/rs [by]`: ranks the server's watchlist by relative strength against SPY over one and three
+//! months, with the leaders and laggards.
+
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::async_trait;
+
+use crate::service::finance::relative_strength::{
+    Ranking, RelativeStrength, SymbolStrength, BENCHMARK,
+};
+use crate::service::finance::FinanceService;
+use crate::service::store::StateStore;
+use crate::service::symbol_filter;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+
+pub const COMMAND_NAME: &str = "rs";
+/// Symbols shown at each end of the ranking.
+const SHOWN: usize = 5;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("Rank the server's watchlist by 1- and 3-month relative strength against SPY")
+        .add_option(Ranking::ALL.into_iter().fold(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "by",
+                "What to rank by (default the average of 1 and 3 months)",
+            ),
+            |opt, r| opt.add_string_choice(r.label(), r.name()),
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+    store: Option<&dyn StateStore>,
+) -> BotResult<CommandResponse> {
+    let ranking = match get_str_opt(command, "by") {
+        Some(name) => Ranking::from_name(name)
+            .ok_or_else(|| BotError::bad_input(format!("unknown ranking {name}")))?,
+        None => Ranking::Blend,
+    };
+    let Some(guild_id) = command.guild_id.map(|g| g.get()) else {
+        return Err(BotError::bad_input(
+            "`/rs` ranks the server's symbol list and only works in a server",
+        ));
+    };
+    let symbols = symbol_filter::guild_filter(store, finance, Some(guild_id))
+        .await
+        .watchlist()
+        .filter(|symbols| !symbols.is_empty())
+        .ok_or_else(|| {
+            BotError::bad_input(
+                "This server has no symbol list; an admin can set one with `/config symbols`",
+            )
+        })?;
+
+    let strength = finance
+        .relative_strength(&symbols)
+        .await
+        .map_err(|e| BotError::internal(format!("{BENCHMARK} history failed to load: {e}")))?;
+    if strength.failed.len() == symbols.len() {
+        return Err(BotError::internal(format!(
+            "price history failed to load for all {} symbols",
+            symbols.len()
+        )));
+    }
+    Ok(CommandResponse::text(rs_text(&strength, ranking)))
+}
+
+/// SPY's own returns, the leaders from the top and the laggards from the bottom of the ranking,
+/// then what couldn't be ranked.
+pub fn rs_text(strength: &RelativeStrength, ranking: Ranking) -> String {
+    let ranked = strength.ranked(ranking);
+    let benchmark = strength.benchmark;
+    let mut lines = vec![
+        format!(
+            "💪 **Relative strength vs {BENCHMARK}** · {} symbols by {}",
+            ranked.len(),
+            ranking.label()
+        ),
+        format!(
+            "{BENCHMARK} 1M {} · 3M {}",
+            percent(benchmark.one_month),
+            percent(benchmark.three_months)
+        ),
+    ];
+
+    if ranked.is_empty() {
+        lines.push(String::new());
+        lines.push("No symbol has enough history to rank.".to_string());
+    } else {
+        let leaders = SHOWN.min(ranked.len().div_ceil(2));
+        let laggards = SHOWN.min(ranked.len() - leaders);
+        lines.push(String::new());
+        lines.push("🟢 **Leaders**".to_string());
+        for (i, s) in ranked.iter().enumerate().take(leaders) {
+            lines.push(rank_line(i + 1, s));
+        }
+        if laggards > 0 {
+            lines.push(String::new());
+            lines.push("🔴 **Laggards**".to_string());
+            let start = ranked.len() - laggards;
+            for (i, s) in ranked.iter().enumerate().skip(start) {
+                lines.push(rank_line(i + 1, s));
+            }
+        }
+    }
+
+    let mut notes = vec![format!(
+        "Return over {BENCHMARK}'s, as the change of the price ratio"
+    )];
+    let unranked = strength.symbols.len() - ranked.len();
+    if unranked > 0 {
+        notes.push(format!("{unranked} without enough history"));
+    }
+    if !strength.failed.is_empty() {
+        notes.push(format!(
+            "{} failed to load ({})",
+            strength.failed.len(),
+            strength.failed.join(", ")
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!("*{}*", notes.join(" · ")));
+    lines.join("\n")
+}
+
+/// `3. **NVDA** 1M +4.20% · 3M +12.00%`
+fn rank_line(rank: usize, s: &SymbolStrength) -> String {
+    format!(
+        "{rank}. **{}** 1M {} · 3M {}",
+        s.symbol,
+        percent(s.relative.one_month),
+        percent(s.relative.three_months)
+    )
+}
+
+/// `+1.23%`, or `n/a` without enough history.
+fn percent(change: Option<f64>) -> String {
+    change.map_or_else(|| "n/a".to_string(), |c| format!("{:+.2}%", c * 100.0))
+}
+
+pub struct RsCommand;
+
+#[async_trait]
+impl Command for RsCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance, ctx.store).await
+    }
+}
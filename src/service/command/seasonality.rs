@@ -0,0 +1,202 @@
+//! `/seasonality ticker`: average return of each calendar month over the past 10 years as a
+//! bar chart, and how the stock has done in the current month year by year.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::seasonality::{self, Seasonality, HISTORY_RANGE};
+use crate::service::finance::FinanceService;
+use crate::service::quickchart;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub const COMMAND_NAME: &str = "seasonality";
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("Average return by calendar month over the past 10 years")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let closes = finance.daily_closes(&symbol, HISTORY_RANGE).await?;
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let stats = seasonality::seasonality(&closes, today);
+    if stats.years() == 0 {
+        return Err(BotError::not_found(format!(
+            "not enough price history for {symbol}'s seasonality"
+        )));
+    }
+
+    let mut response = CommandResponse::text(seasonality_text(&symbol, &stats, today));
+    match quickchart::render(seasonality_chart(&symbol, &stats, today.month()), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "seasonality.png"))
+        }
+        Err(err) => {
+            warn!("seasonality chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// The strongest and weakest months, then the current month: its average, hit rate, best and
+/// worst years, this year so far and each past year's return.
+pub fn seasonality_text(symbol: &str, stats: &Seasonality, today: NaiveDate) -> String {
+    let month_name = |month: u32| month_label(month, "%B");
+    let mut lines = vec![format!(
+        "🗓️ **{symbol} seasonality** · average monthly returns over {} years",
+        stats.years()
+    )];
+
+    let mut ranked: Vec<(u32, f64)> = (1..=12)
+        .filter_map(|m| Some((m, stats.month(m).average()?)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    if let (Some(best), Some(worst)) = (ranked.first(), ranked.last()) {
+        lines.push(format!(
+            "Strongest **{}** ({}) · weakest **{}** ({})",
+            month_name(best.0),
+            percent(best.1),
+            month_name(worst.0),
+            percent(worst.1)
+        ));
+    }
+
+    let month = stats.month(today.month());
+    lines.push(String::new());
+    lines.push(format!("**{}**", month_name(today.month())));
+    match month.average() {
+        Some(average) => lines.push(format!(
+            "Average {} · up in {} of {} years",
+            percent(average),
+            month.positive(),
+            month.returns.len()
+        )),
+        None => lines.push("No full month on record yet.".to_string()),
+    }
+    if let (Some(best), Some(worst)) = (month.best(), month.worst()) {
+        lines.push(format!(
+            "Best {} ({}) · worst {} ({})",
+            best.year,
+            percent(best.change),
+            worst.year,
+            percent(worst.change)
+        ));
+    }
+    if let Some(current) = stats.current {
+        lines.push(format!(
+            "{} so far: {}",
+            current.year,
+            percent(current.change)
+        ));
+    }
+    if !month.returns.is_empty() {
+        let years: Vec<String> = month
+            .returns
+            .iter()
+            .rev()
+            .map(|r| format!("{} {}", r.year, percent(r.change)))
+            .collect();
+        lines.push(format!("By year: {}", years.join(" · ")));
+    }
+    lines.join("\n")
+}
+
+/// Average return per month, green up and red down, the current month drawn darker.
+fn seasonality_chart(symbol: &str, stats: &Seasonality, current_month: u32) -> Value {
+    let labels: Vec<String> = (1..=12).map(|m| month_label(m, "%b")).collect();
+    let averages: Vec<Option<f64>> = (1..=12).map(|m| stats.month(m).average()).collect();
+    let values: Vec<Value> = averages
+        .iter()
+        .map(|avg| avg.map_or(Value::Null, |v| json!((v * 10_000.0).round() / 100.0)))
+        .collect();
+    let colors: Vec<&str> = averages
+        .iter()
+        .zip(1..=12)
+        .map(
+            |(avg, m)| match (avg.unwrap_or(0.0) >= 0.0, m == current_month) {
+                (true, false) => "#4caf5099",
+                (true, true) => "#2e7d32",
+                (false, false) => "#f4433699",
+                (false, true) => "#c62828",
+            },
+        )
+        .collect();
+
+    json!({
+        "type": "bar",
+        "data": {
+            "labels": labels,
+            "datasets": [{
+                "label": "Average return %",
+                "data": values,
+                "backgroundColor": colors
+            }]
+        },
+        "options": {
+            "plugins": {
+                "legend": { "display": false },
+                "title": {
+                    "display": true,
+                    "text": format!("{symbol} average monthly return, {} years", stats.years())
+                }
+            },
+            "scales": {
+                "y": { "title": { "display": true, "text": "%" } }
+            }
+        }
+    })
+}
+
+/// `+1.23%`
+fn percent(change: f64) -> String {
+    format!("{:+.2}%", change * 100.0)
+}
+
+fn month_label(month: u32, format: &str) -> String {
+    NaiveDate::from_ymd_opt(2000, month, 1)
+        .map(|d| d.format(format).to_string())
+        .unwrap_or_default()
+}
+
+pub struct SeasonalityCommand;
+
+#[async_trait]
+impl Command for SeasonalityCommand {
+    fn name(&self) -> &str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serenity::all::{CommandInteraction, CreateCommand, Permissions};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::caching::collections::command_stats::{self, CommandUse, StatsSummary};
+use crate::service::caching::RedisCache;
+
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+
+/// Days covered by `/stats`.
+const WINDOW_DAYS: i64 = 7;
+const TOP_N: usize = 10;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("stats")
+        .description("Command usage over the last 7 days (admins only)")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+}
+
+pub async fn handle(command: &CommandInteraction, cache: Option<&RedisCache>) -> BotResult<String> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| BotError::bad_input("usage stats are kept per server; run /stats in one"))?;
+    let cache = cache
+        .ok_or_else(|| BotError::not_found("no usage stats available; Redis is not configured"))?;
+    let summary =
+        command_stats::load_summary(cache, guild_id.get(), Utc::now().date_naive(), WINDOW_DAYS)
+            .await
+            .map_err(|e| BotError::internal(format!("failed to load usage stats: {e}")))?;
+    Ok(format_summary(&summary))
+}
+
+/// Record a handled slash command. Failures are logged and otherwise ignored so analytics
+/// never affect command handling.
+pub async fn record(cache: &RedisCache, command: &CommandInteraction, elapsed: Duration, ok: bool) {
+    let ticker = command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "ticker")
+        .and_then(|o| o.value.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+
+    let usage = CommandUse {
+        guild_id: command.guild_id.map(|g| g.get()),
+        day: Utc::now().date_naive(),
+        command: &command.data.name,
+        latency_ms: elapsed.as_millis() as u64,
+        ok,
+        ticker,
+    };
+    if let Err(err) = command_stats::record_command(cache, &usage).await {
+        warn!("failed to record command usage: {err}");
+    }
+}
+
+/// Totals, then the busiest commands and most requested tickers.
+pub fn format_summary(summary: &StatsSummary) -> String {
+    if summary.commands.is_empty() {
+        return format!("No commands recorded in the last {WINDOW_DAYS} days.");
+    }
+
+    let total: u64 = summary.commands.iter().map(|c| c.count).sum();
+    let errors: u64 = summary.commands.iter().map(|c| c.errors).sum();
+
+    let mut lines = vec![format!(
+        "📈 Usage — last {WINDOW_DAYS} days: {total} commands, {errors} failed ({:.1}%)",
+        percent(errors, total)
+    )];
+
+    lines.push(String::new());
+    lines.push("Busiest commands".to_string());
+    for usage in summary.commands.iter().take(TOP_N) {
+        lines.push(format!(
+            "• /{} — {} uses, {:.1}% failed, avg {} ms",
+            usage.command,
+            usage.count,
+            usage.error_rate() * 100.0,
+            usage.avg_latency_ms()
+        ));
+    }
+
+    if !summary.tickers.is_empty() {
+        lines.push(String::new());
+        lines.push("Top tickers".to_string());
+        for (ticker, count) in summary.tickers.iter().take(TOP_N) {
+            lines.push(format!("• {ticker} — {count}"));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+pub struct StatsCommand;
+
+#[async_trait]
+impl Command for StatsCommand {
+    fn name(&self) -> &str {
+        "stats"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.cache)
+            .await
+            .map(CommandResponse::text)
+    }
+}
@@ -0,0 +1,299 @@
+use chrono::{NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::options::{self, OptionKind, OptionSlice};
+use crate::service::finance::strategy::{self, Payoff, PricedLeg};
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::quickchart;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+/// Most legs one strategy may have.
+const MAX_LEGS: usize = 8;
+/// Points sampled across the payoff chart, besides the strikes.
+const CHART_POINTS: usize = 120;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("strategy")
+        .description("Payoff at expiration of an options strategy at live prices")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "ticker",
+                "Ticker symbol, e.g., SPY",
+            )
+            .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "legs",
+                "Legs, e.g., buy 1 450C, sell 1 455C",
+            )
+            .required(true),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "expiration",
+            "Expiration, YYYY-MM-DD (default the nearest)",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let legs = strategy::parse_legs(get_str_opt(command, "legs").unwrap_or_default())
+        .map_err(BotError::bad_input)?;
+    if legs.len() > MAX_LEGS {
+        return Err(BotError::bad_input(format!(
+            "a strategy can have at most {MAX_LEGS} legs"
+        )));
+    }
+    let requested = get_str_opt(command, "expiration")
+        .map(|raw| {
+            NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+                .map_err(|_| BotError::bad_input(format!("{raw} is not a date like 2026-10-23")))
+        })
+        .transpose()?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let expirations = finance.get_option_expirations(&symbol).await?;
+    let expiration = pick_expiration(&expirations, requested)?;
+    let chain = finance.get_option_chain(&symbol, expiration).await?;
+
+    let mut priced = Vec::new();
+    for leg in legs {
+        let contract = chain
+            .contract(leg.kind, leg.strike)
+            .ok_or_else(|| missing_strike(&chain, leg.kind, leg.strike, expiration))?;
+        priced.push(PricedLeg {
+            leg,
+            premium: options::premium(contract),
+        });
+    }
+    let payoff = Payoff::new(priced);
+
+    let mut lines = vec![format!(
+        "🧮 **{symbol} {} strategy** — spot {}",
+        expiration.format("%b %-d"),
+        format::per_share(chain.spot)
+    )];
+    for priced in payoff.legs() {
+        lines.push(format!(
+            "• {} @ {}",
+            priced.leg.describe(),
+            format::per_share(priced.premium)
+        ));
+    }
+    let debit = payoff.net_debit();
+    lines.push(String::new());
+    lines.push(if debit >= 0.0 {
+        format!("Net debit {}", format::per_share(debit))
+    } else {
+        format!("Net credit {}", format::per_share(-debit))
+    });
+    lines.push(format!(
+        "Max profit {} · Max loss {}",
+        payoff
+            .max_profit()
+            .map(format::per_share)
+            .unwrap_or_else(|| "unlimited".to_string()),
+        payoff
+            .max_loss()
+            .map(format::per_share)
+            .unwrap_or_else(|| "unlimited".to_string())
+    ));
+    let breakevens = payoff.breakevens();
+    lines.push(if breakevens.is_empty() {
+        "No breakeven at expiration".to_string()
+    } else {
+        format!(
+            "Breakeven{} {}",
+            if breakevens.len() > 1 { "s" } else { "" },
+            breakevens
+                .iter()
+                .map(|b| format::per_share(*b))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    });
+    lines.push("*Per the whole position (100 shares a contract), mid prices where quoted.*".into());
+
+    let title = format!(
+        "{symbol} {} payoff at expiration",
+        expiration.format("%b %-d")
+    );
+    let mut response = CommandResponse::text(lines.join("\n"));
+    match quickchart::render(payoff_chart(&title, &payoff, chain.spot), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "strategy.png"))
+        }
+        Err(err) => {
+            warn!("strategy chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// The requested expiration when listed, else the nearest one from today (ET).
+fn pick_expiration(
+    expirations: &[NaiveDate],
+    requested: Option<NaiveDate>,
+) -> BotResult<NaiveDate> {
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let mut upcoming: Vec<NaiveDate> = expirations
+        .iter()
+        .copied()
+        .filter(|e| *e >= today)
+        .collect();
+    upcoming.sort();
+    match requested {
+        Some(day) if upcoming.contains(&day) => Ok(day),
+        Some(day) => Err(BotError::bad_input(format!(
+            "{day} is not a listed expiration; nearest are {}",
+            upcoming
+                .iter()
+                .take(4)
+                .map(|e| e.format("%Y-%m-%d").to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+        None => upcoming
+            .first()
+            .copied()
+            .ok_or_else(|| BotError::not_found("no upcoming option expirations")),
+    }
+}
+
+/// The leg's strike is not in the chain: name the listed strikes around it.
+fn missing_strike(
+    chain: &OptionSlice,
+    kind: OptionKind,
+    strike: f64,
+    expiration: NaiveDate,
+) -> BotError {
+    let side = match kind {
+        OptionKind::Call => &chain.calls,
+        OptionKind::Put => &chain.puts,
+    };
+    let mut nearby: Vec<f64> = side.iter().map(|c| c.strike).collect();
+    nearby.sort_by(|a, b| (a - strike).abs().total_cmp(&(b - strike).abs()));
+    nearby.truncate(3);
+    nearby.sort_by(f64::total_cmp);
+    BotError::bad_input(format!(
+        "no {}{} listed for {}; nearby strikes: {}",
+        format::strike(strike),
+        kind.letter(),
+        expiration.format("%b %-d"),
+        nearby
+            .iter()
+            .map(|s| format::strike(*s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// P/L across prices around the strikes and spot, shaded green above zero and red below, with
+/// a dashed line at spot.
+fn payoff_chart(title: &str, payoff: &Payoff, spot: f64) -> Value {
+    let (low_strike, high_strike) = payoff.strike_range();
+    let low = (low_strike.min(spot) * 0.85).max(0.0);
+    let high = high_strike.max(spot) * 1.15;
+
+    let mut prices: Vec<f64> = (0..=CHART_POINTS)
+        .map(|i| low + (high - low) * i as f64 / CHART_POINTS as f64)
+        .chain(payoff.legs().iter().map(|l| l.leg.strike))
+        .collect();
+    prices.sort_by(f64::total_cmp);
+    prices.dedup();
+    let data: Vec<Value> = prices
+        .iter()
+        .map(|p| json!({"x": round2(*p), "y": round2(payoff.profit_at(*p))}))
+        .collect();
+
+    let (min, max) = prices
+        .iter()
+        .map(|p| payoff.profit_at(*p))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+    let pad = ((max - min) * 0.1).max(1.0);
+
+    json!({
+        "type": "line",
+        "data": {
+            "datasets": [
+                {
+                    "label": "P/L at expiration",
+                    "data": data,
+                    "borderColor": "#37474f",
+                    "pointRadius": 0,
+                    "fill": {
+                        "target": "origin",
+                        "above": "rgba(76, 175, 80, 0.25)",
+                        "below": "rgba(244, 67, 54, 0.25)"
+                    }
+                },
+                {
+                    "label": "Spot",
+                    "data": [
+                        {"x": round2(spot), "y": round2(min - pad)},
+                        {"x": round2(spot), "y": round2(max + pad)}
+                    ],
+                    "borderColor": "#2196f3",
+                    "borderDash": [6, 4],
+                    "pointRadius": 0,
+                    "fill": false
+                }
+            ]
+        },
+        "options": {
+            "plugins": {
+                "legend": { "position": "bottom" },
+                "title": { "display": true, "text": title }
+            },
+            "scales": {
+                "x": { "type": "linear", "title": { "display": true, "text": "Price at expiration" } },
+                "y": { "title": { "display": true, "text": "Profit / loss ($)" } }
+            }
+        }
+    })
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub struct StrategyCommand;
+
+#[async_trait]
+impl Command for StrategyCommand {
+    fn name(&self) -> &str {
+        "strategy"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
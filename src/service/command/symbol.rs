@@ -0,0 +1,90 @@
+use tracing::warn;
+
+use crate::models::SymbolMatch;
+use crate::service::finance::{exchange, fx, FinanceService};
+
+use super::error::{BotError, BotResult};
+
+const SUGGESTIONS: usize = 3;
+
+/// Resolve user input to a ticker symbol.
+///
+/// Accepts a ticker in any case (`nvda`, `$NVDA`), an exchange-qualified ticker (`RY.TO`,
+/// `TSX:RY`, `sap.xetra`) or a company name (`apple`). Input that quotes as a ticker is used
+/// directly; only when Yahoo has no quote for it is the symbol search consulted, and unknown
+/// input fails with "Did you mean …" suggestions. If Yahoo is down the input is used as
+/// typed, so lookups are never blocked on validation.
+pub async fn resolve(finance: &FinanceService, input: &str) -> BotResult<String> {
+    let query = exchange::normalize_symbol(input.trim().trim_start_matches('$'));
+    let query = query.as_str();
+    if query.is_empty() {
+        return Err(BotError::bad_input("ticker is required"));
+    }
+
+    if !query.contains(char::is_whitespace) {
+        let ticker = query.to_uppercase();
+        match finance.get_price(&ticker).await {
+            Ok(quote) if !quote.symbol.is_empty() => return Ok(quote.symbol),
+            Ok(_) => return Ok(ticker),
+            Err(err) if err.is_not_found() => {}
+            Err(err) => {
+                warn!("quote lookup failed for '{query}', using it as typed: {err}");
+                return Ok(ticker);
+            }
+        }
+    }
+
+    let matches = match finance.search_symbols(query, 5).await {
+        Ok(matches) => matches,
+        Err(err) => {
+            warn!("symbol search failed for '{query}', using it as typed: {err}");
+            return Ok(query.to_uppercase());
+        }
+    };
+
+    if let Some(exact) = matches
+        .iter()
+        .find(|m| m.symbol.eq_ignore_ascii_case(query))
+    {
+        return Ok(exact.symbol.clone());
+    }
+
+    let lowered = query.to_lowercase();
+    if let Some(top) = matches.first() {
+        let name_matches = top
+            .name
+            .as_deref()
+            .is_some_and(|name| name.to_lowercase().contains(&lowered));
+        if name_matches {
+            return Ok(top.symbol.clone());
+        }
+    }
+
+    Err(BotError::not_found(unknown_symbol_message(query, &matches)))
+}
+
+/// Validate a `currency:` option (`usd` → `USD`).
+pub fn parse_currency(input: &str) -> BotResult<String> {
+    fx::parse_currency(input).ok_or_else(|| {
+        BotError::bad_input(format!(
+            "currency must be a 3-letter code like USD or EUR, got '{input}'"
+        ))
+    })
+}
+
+fn unknown_symbol_message(query: &str, matches: &[SymbolMatch]) -> String {
+    let base = format!("no symbol matches `{}`", query.to_uppercase());
+    if matches.is_empty() {
+        return base;
+    }
+
+    let suggestions: Vec<String> = matches
+        .iter()
+        .take(SUGGESTIONS)
+        .map(|m| match &m.name {
+            Some(name) => format!("{} ({name})", m.symbol),
+            None => m.symbol.clone(),
+        })
+        .collect();
+    format!("{base}. Did you mean {}?", suggestions.join(", "))
+}
@@ -0,0 +1,90 @@
+use chrono::Utc;
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, ResolvedValue,
+};
+use serenity::async_trait;
+
+use crate::service::i18n::{Locale, Text};
+use crate::service::store::StateStore;
+use crate::service::timefmt;
+
+use super::error::{BotError, BotResult};
+use super::framework::{Command, CommandContext, CommandResponse, CommandResult, Deferral};
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("timezone")
+        .description("Your timezone for times shown as plain text")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "timezone",
+            "IANA name, e.g. Europe/London; leave out to use the server's",
+        ))
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    store: Option<&dyn StateStore>,
+    locale: Locale,
+) -> BotResult<String> {
+    let store = store.ok_or_else(|| {
+        BotError::not_found("timezone preferences are unavailable; no state store is configured")
+    })?;
+    let options = command.data.options();
+    let name = options
+        .iter()
+        .find(|o| o.name == "timezone")
+        .and_then(|o| match o.value {
+            ResolvedValue::String(s) => Some(s),
+            _ => None,
+        });
+    let timezone = name
+        .map(|name| {
+            timefmt::parse_timezone(name).ok_or_else(|| {
+                BotError::bad_input(format!(
+                    "unknown timezone {name}; use an IANA name such as Europe/London"
+                ))
+            })
+        })
+        .transpose()?;
+
+    store
+        .set_user_timezone(command.user.id.get(), timezone.map(|tz| tz.name()))
+        .await
+        .map_err(|e| BotError::internal(format!("failed to save timezone: {e}")))?;
+    Ok(match timezone {
+        Some(tz) => format!(
+            "{} {} ({}).",
+            Text::UserTimezoneSet.get(locale),
+            tz.name(),
+            timefmt::plain(&Utc::now(), tz)
+        ),
+        None => Text::UserTimezoneCleared.get(locale).to_string(),
+    })
+}
+
+pub struct TimezoneCommand;
+
+#[async_trait]
+impl Command for TimezoneCommand {
+    fn name(&self) -> &str {
+        "timezone"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    fn deferral(&self) -> Deferral {
+        Deferral::Ephemeral
+    }
+
+    fn user_installable(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.store, ctx.locale)
+            .await
+            .map(CommandResponse::text)
+    }
+}
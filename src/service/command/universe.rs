@@ -0,0 +1,53 @@
+//! `universe:sp500|nasdaq100|dow30` for commands that list many symbols: only the index's
+//! members are shown.
+
+use serenity::all::{
+    CommandDataOptionValue, CommandInteraction, CommandOptionType, CreateCommandOption,
+};
+
+use crate::service::finance::constituents::Index;
+use crate::service::finance::FinanceService;
+use crate::service::symbol_filter::ResolvedFilter;
+
+use super::error::BotResult;
+
+pub const UNIVERSE_OPTION: &str = "universe";
+
+/// Add to a command's options to let callers narrow it to an index.
+pub fn universe_option() -> CreateCommandOption {
+    Index::ALL.into_iter().fold(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            UNIVERSE_OPTION,
+            "Only show members of this index",
+        ),
+        |opt, index| opt.add_string_choice(index.label(), index.name()),
+    )
+}
+
+/// The index picked with [`universe_option`], if any.
+pub fn requested(command: &CommandInteraction) -> Option<Index> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == UNIVERSE_OPTION)
+        .and_then(|o| match &o.value {
+            CommandDataOptionValue::String(s) => Index::from_name(s),
+            _ => None,
+        })
+}
+
+/// Narrow `filter` to `index`'s members. Unlike a server's `/config symbols`, a universe the
+/// caller asked for fails the command when its list can't be loaded.
+pub async fn restrict(
+    finance: &FinanceService,
+    filter: ResolvedFilter,
+    index: Option<Index>,
+) -> BotResult<ResolvedFilter> {
+    let Some(index) = index else {
+        return Ok(filter);
+    };
+    let members = finance.constituents(index).await?;
+    Ok(filter.restrict_to(&members))
+}
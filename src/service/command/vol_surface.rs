@@ -0,0 +1,218 @@
+use chrono::{NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use serde_json::{json, Value};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, CreateAttachment, CreateCommand, CreateCommandOption,
+};
+use serenity::async_trait;
+use tracing::warn;
+
+use crate::service::finance::options::{EarningsKink, TermPoint, TermShape};
+use crate::service::finance::FinanceService;
+use crate::service::quickchart;
+
+use super::args::get_str_opt;
+use super::error::{BotError, BotResult};
+use super::framework::{ephemeral_option, Command, CommandContext, CommandResponse, CommandResult};
+use super::symbol;
+
+pub fn register_command() -> CreateCommand {
+    CreateCommand::new("vol-surface")
+        .description("ATM implied volatility term structure across expirations")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ticker",
+            "Ticker symbol, e.g., AAPL; defaults to the channel's bound ticker",
+        ))
+        .add_option(ephemeral_option())
+}
+
+pub async fn handle(
+    command: &CommandInteraction,
+    finance: &FinanceService,
+) -> BotResult<CommandResponse> {
+    let ticker =
+        get_str_opt(command, "ticker").ok_or_else(|| BotError::bad_input("ticker is required"))?;
+    let symbol = symbol::resolve(finance, ticker).await?;
+
+    let (points, earnings) = tokio::join!(
+        finance.iv_term_structure(&symbol),
+        finance.next_earnings_date(&symbol)
+    );
+    let points = points?;
+    // Funds and indexes have no earnings; the curve stands on its own. Yahoo keeps showing
+    // the last report until the next one is scheduled, so past dates are dropped.
+    let today = Utc::now().with_timezone(&New_York).date_naive();
+    let earnings = earnings
+        .unwrap_or_else(|e| {
+            warn!("vol surface: no earnings date for {symbol}: {e}");
+            None
+        })
+        .filter(|day| *day >= today);
+    let kink = earnings.and_then(|day| EarningsKink::find(&points, day));
+
+    let front = points[0];
+    let back = points[points.len() - 1];
+    let mut lines = vec![format!(
+        "📈 **{symbol} IV term structure** — ATM implied volatility"
+    )];
+    if points.len() > 1 {
+        lines.push(format!(
+            "{} → {}: **{}** ({})",
+            point_label(&front),
+            point_label(&back),
+            TermShape::between(front.atm_iv, back.atm_iv).label(),
+            vol_points(back.atm_iv - front.atm_iv)
+        ));
+    }
+    match (&kink, earnings) {
+        (Some(kink), _) => lines.push(describe_kink(kink)),
+        (None, Some(day)) => lines.push(format!(
+            "📅 Next earnings ({}) come after the last sampled expiration.",
+            day.format("%b %-d")
+        )),
+        (None, None) => {}
+    }
+
+    lines.push(String::new());
+    for point in &points {
+        let marker = match &kink {
+            Some(kink) if kink.event.expiration == point.expiration => " ⚡ earnings",
+            _ => "",
+        };
+        lines.push(format!("• {}{marker}", point_label(point)));
+    }
+
+    let mut response = CommandResponse::text(lines.join("\n"));
+    match quickchart::render(term_chart(&symbol, &points, earnings), 900, 450).await {
+        Ok(png) => {
+            response = response.with_attachment(CreateAttachment::bytes(png, "vol-surface.png"))
+        }
+        Err(err) => {
+            warn!("vol surface chart failed for {symbol}: {err}");
+            response
+                .content
+                .push_str(&format!("\n\n⚠️ Chart unavailable: {err}"));
+        }
+    }
+    Ok(response)
+}
+
+/// `📅 Earnings Oct 29 · Oct 31 48.2% prices the report · +9.1 pts vs Oct 24 (contango into
+/// the report) · -6.0 pts to Nov 7 (backwardation after it)`
+fn describe_kink(kink: &EarningsKink) -> String {
+    let mut parts = vec![format!(
+        "📅 Earnings {} · {} {:.1}% prices the report",
+        kink.earnings.format("%b %-d"),
+        kink.event.expiration.format("%b %-d"),
+        kink.event.atm_iv * 100.0
+    )];
+    if let (Some(before), Some(shape)) = (kink.before, kink.entering_event()) {
+        parts.push(format!(
+            "{} vs {} ({} into the report)",
+            vol_points(kink.event.atm_iv - before.atm_iv),
+            before.expiration.format("%b %-d"),
+            shape.label()
+        ));
+    }
+    if let (Some(after), Some(shape)) = (kink.after, kink.leaving_event()) {
+        parts.push(format!(
+            "{} to {} ({} after it)",
+            vol_points(after.atm_iv - kink.event.atm_iv),
+            after.expiration.format("%b %-d"),
+            shape.label()
+        ));
+    }
+    parts.join(" · ")
+}
+
+/// `Oct 24 (7d) 32.1%`
+fn point_label(point: &TermPoint) -> String {
+    format!(
+        "{} ({}d) {:.1}%",
+        point.expiration.format("%b %-d"),
+        point.days,
+        point.atm_iv * 100.0
+    )
+}
+
+/// Difference of two IVs in vol points: `+3.2 pts`.
+fn vol_points(diff: f64) -> String {
+    format!("{:+.1} pts", diff * 100.0)
+}
+
+/// ATM IV by expiration date, with a dashed vertical line at the earnings date.
+fn term_chart(symbol: &str, points: &[TermPoint], earnings: Option<NaiveDate>) -> Value {
+    let data: Vec<Value> = points
+        .iter()
+        .map(|p| {
+            json!({
+                "x": p.expiration.format("%Y-%m-%d").to_string(),
+                "y": (p.atm_iv * 1000.0).round() / 10.0
+            })
+        })
+        .collect();
+    let mut datasets = vec![json!({
+        "label": "ATM IV %",
+        "data": data,
+        "borderColor": "#2196f3",
+        "backgroundColor": "#2196f3",
+        "tension": 0.2,
+        "fill": false
+    })];
+
+    let last = points.last().map(|p| p.expiration);
+    if let (Some(day), Some(last)) = (earnings, last) {
+        if day <= last {
+            let low = points
+                .iter()
+                .map(|p| p.atm_iv)
+                .fold(f64::INFINITY, f64::min);
+            let high = points.iter().map(|p| p.atm_iv).fold(0.0, f64::max);
+            let x = day.format("%Y-%m-%d").to_string();
+            datasets.push(json!({
+                "label": "Earnings",
+                "data": [
+                    {"x": x, "y": (low * 90.0).round()},
+                    {"x": x, "y": (high * 110.0).round()}
+                ],
+                "borderColor": "#ff9800",
+                "borderDash": [6, 4],
+                "pointRadius": 0,
+                "fill": false
+            }));
+        }
+    }
+
+    json!({
+        "type": "line",
+        "data": { "datasets": datasets },
+        "options": {
+            "plugins": {
+                "legend": { "position": "bottom" },
+                "title": { "display": true, "text": format!("{symbol} ATM IV term structure") }
+            },
+            "scales": {
+                "x": { "type": "time", "time": { "unit": "month" } },
+                "y": { "title": { "display": true, "text": "Implied volatility (%)" } }
+            }
+        }
+    })
+}
+
+pub struct VolSurfaceCommand;
+
+#[async_trait]
+impl Command for VolSurfaceCommand {
+    fn name(&self) -> &str {
+        "vol-surface"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_command()
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>) -> CommandResult {
+        handle(ctx.interaction, ctx.finance).await
+    }
+}
@@ -0,0 +1,1036 @@
+//! Typed bot configuration.
+//!
+//! Settings are read from a TOML file (`CONFIG_PATH`, or `config.toml` in the working
+//! directory when present), then any of the environment variables listed in
+//! `config.example.toml` override the matching field. The result is validated at startup
+//! and shared through a [`ConfigHandle`]; modules don't read env vars themselves.
+//!
+//! `/admin reload-config` re-reads the file at runtime. Readers take a fresh snapshot each
+//! time they act, so channel and earnings settings apply on the next scheduled run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::{env, fs, io};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serenity::model::prelude::GuildId;
+use thiserror::Error;
+
+use crate::service::finance::constituents::Index;
+
+const DEFAULT_PATH: &str = "config.toml";
+const DEFAULT_MIN_IMPORTANCE: i64 = 4;
+const DEFAULT_RISK_FREE_RATE: f64 = 0.04;
+const DEFAULT_ALERT_AFTER_FAILURES: u32 = 3;
+const DEFAULT_BOARD_SYMBOLS: &[&str] = &["SPY", "QQQ", "IWM", "DIA"];
+const DEFAULT_BOARD_INTERVAL_MINUTES: u64 = 5;
+/// Keeps a board well within one Discord message.
+const MAX_BOARD_SYMBOLS: usize = 25;
+const DEFAULT_INSIDER_UNIVERSE: &str = "sp500";
+const DEFAULT_SP500_URL: &str =
+    "https://raw.githubusercontent.com/datasets/s-and-p-500-companies/main/data/constituents.csv";
+const DEFAULT_NASDAQ100_URL: &str = "https://api.nasdaq.com/api/quote/list-type/nasdaq100";
+const DEFAULT_DOW30_URL: &str =
+    "https://en.wikipedia.org/w/index.php?title=Dow_Jones_Industrial_Average&action=raw";
+/// Broad-market funds, the sector SPDRs and a few popular thematic funds.
+const DEFAULT_ETF_UNIVERSE: &[&str] = &[
+    "SPY", "QQQ", "DIA", "IWM", "VTI", "XLK", "XLF", "XLE", "XLV", "XLY", "XLP", "XLI", "XLU",
+    "XLB", "XLRE", "XLC", "SMH", "ARKK",
+];
+const MAX_ETF_UNIVERSE: usize = 60;
+const DEFAULT_PRESENCE_SYMBOL: &str = "SPY";
+const DEFAULT_PRESENCE_INTERVAL_MINUTES: u64 = 5;
+const DEFAULT_SQLITE_URL: &str = "sqlite://stacks-bot.db?mode=rwc";
+const DEFAULT_SHUTDOWN_NOTICE: &str = "🔄 Bot restarting — back shortly.";
+const DEFAULT_JPEG_QUALITY: u8 = 85;
+/// Under Discord's 10 MiB upload limit for servers without boosts, with room for the message.
+const DEFAULT_IMAGE_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Sections read once at startup; changing them needs a restart.
+const RESTART_SECTIONS: &[&str] = &[
+    "discord",
+    "constituents",
+    "redis",
+    "storage",
+    "health",
+    "errors",
+];
+/// Keys whose values are never shown in a diff.
+const SECRET_KEYS: &[&str] = &[
+    "discord.token",
+    "redis.url",
+    "storage.database_url",
+    "errors.sentry_dsn",
+    "errors.webhook_url",
+];
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("invalid config file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: Box<toml::de::Error>,
+    },
+    #[error("{var} must be {expected}, got {value:?}")]
+    Env {
+        var: &'static str,
+        expected: &'static str,
+        value: String,
+    },
+    #[error("invalid config: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub discord: DiscordConfig,
+    pub channels: ChannelConfig,
+    pub earnings: EarningsConfig,
+    pub options: OptionsConfig,
+    pub insider: InsiderConfig,
+    pub constituents: ConstituentsConfig,
+    pub etf: EtfConfig,
+    pub quote_board: QuoteBoardConfig,
+    pub presence: PresenceConfig,
+    pub images: ImageConfig,
+    pub responses: ResponseConfig,
+    pub redis: RedisConfig,
+    pub storage: StorageConfig,
+    pub health: HealthConfig,
+    pub shutdown: ShutdownConfig,
+    pub errors: ErrorReportingConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordConfig {
+    pub token: String,
+    pub application_id: u64,
+    /// Dev builds register commands in these guilds instead of globally.
+    pub guild_ids: Vec<u64>,
+    /// Total gateway shards. Unset uses the count Discord recommends.
+    pub shard_count: Option<u32>,
+    /// First and last shard this process runs, to split shards across processes. Needs
+    /// `shard_count`; unset runs every shard.
+    pub shard_range: Option<[u32; 2]>,
+    /// Connect without the privileged Message Content intent, e.g. once the bot is in more
+    /// than 100 guilds and unverified for it.
+    pub disable_message_content: bool,
+}
+
+/// Channels the scheduled automations post to. The per-report channels fall back to
+/// `earnings`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChannelConfig {
+    pub earnings: Option<u64>,
+    pub earnings_weekly: Option<u64>,
+    pub earnings_daily: Option<u64>,
+    pub earnings_after: Option<u64>,
+    pub earnings_summary: Option<u64>,
+    pub options: Option<u64>,
+    /// Weekly insider net-buying scan.
+    pub insider: Option<u64>,
+    /// Split and dividend change alerts for the guild's `/config symbols` list.
+    pub corporate_actions: Option<u64>,
+    /// Operator alerts, such as an automation failing several runs in a row.
+    pub operator: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EarningsConfig {
+    pub day_threads: bool,
+    pub scheduled_events: bool,
+    /// Minimum importance (1-5) for an earnings event to get a guild scheduled event.
+    pub event_min_importance: i64,
+}
+
+impl Default for EarningsConfig {
+    fn default() -> Self {
+        Self {
+            day_threads: false,
+            scheduled_events: false,
+            event_min_importance: DEFAULT_MIN_IMPORTANCE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OptionsConfig {
+    /// Annual risk-free rate for Black-Scholes greeks, as a fraction (0.04 = 4%).
+    pub risk_free_rate: f64,
+    /// Consecutive options pinger failures before `channels.operator` is alerted.
+    pub alert_after_failures: u32,
+}
+
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        Self {
+            risk_free_rate: DEFAULT_RISK_FREE_RATE,
+            alert_after_failures: DEFAULT_ALERT_AFTER_FAILURES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct InsiderConfig {
+    /// Index scanned for insider buying: `sp500`, `nasdaq100` or `dow30`.
+    pub universe: String,
+}
+
+impl Default for InsiderConfig {
+    fn default() -> Self {
+        Self {
+            universe: DEFAULT_INSIDER_UNIVERSE.to_string(),
+        }
+    }
+}
+
+impl InsiderConfig {
+    /// The configured index; [`Config::validate`] rejects names that don't parse.
+    pub fn index(&self) -> Option<Index> {
+        Index::from_name(&self.universe)
+    }
+}
+
+/// Where index member lists are downloaded from. Point a source at a fixed revision (a
+/// commit's raw file, a Wikipedia `oldid`) to pin it; a list whose size looks wrong is rejected
+/// either way.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConstituentsConfig {
+    /// CSV with the Yahoo-style symbol in its first column.
+    pub sp500_url: String,
+    /// JSON in the Nasdaq list API's shape, `data.data.rows[].symbol`.
+    pub nasdaq100_url: String,
+    /// Raw wikitext of the article whose "Components" table lists the Dow's members.
+    pub dow30_url: String,
+}
+
+impl Default for ConstituentsConfig {
+    fn default() -> Self {
+        Self {
+            sp500_url: DEFAULT_SP500_URL.to_string(),
+            nasdaq100_url: DEFAULT_NASDAQ100_URL.to_string(),
+            dow30_url: DEFAULT_DOW30_URL.to_string(),
+        }
+    }
+}
+
+impl ConstituentsConfig {
+    fn apply_env(&mut self) {
+        if let Some(url) = env_value("SP500_CONSTITUENTS_URL") {
+            self.sp500_url = url;
+        }
+        if let Some(url) = env_value("NASDAQ100_CONSTITUENTS_URL") {
+            self.nasdaq100_url = url;
+        }
+        if let Some(url) = env_value("DOW30_CONSTITUENTS_URL") {
+            self.dow30_url = url;
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let urls = [
+            ("sp500_url", "SP500_CONSTITUENTS_URL", &self.sp500_url),
+            (
+                "nasdaq100_url",
+                "NASDAQ100_CONSTITUENTS_URL",
+                &self.nasdaq100_url,
+            ),
+            ("dow30_url", "DOW30_CONSTITUENTS_URL", &self.dow30_url),
+        ];
+        for (field, var, url) in urls {
+            if !url.starts_with("https://") {
+                return Err(format!("constituents.{field} ({var}) must be an https URL"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EtfConfig {
+    /// Funds whose holdings `/held-by` searches.
+    pub universe: Vec<String>,
+}
+
+impl Default for EtfConfig {
+    fn default() -> Self {
+        Self {
+            universe: DEFAULT_ETF_UNIVERSE.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuoteBoardConfig {
+    /// Channels that each get one board message, edited in place; the board is off when empty.
+    pub channels: Vec<u64>,
+    pub symbols: Vec<String>,
+    /// Minutes between edits during market hours.
+    pub interval_minutes: u64,
+}
+
+impl Default for QuoteBoardConfig {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            symbols: DEFAULT_BOARD_SYMBOLS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            interval_minutes: DEFAULT_BOARD_INTERVAL_MINUTES,
+        }
+    }
+}
+
+/// The bot's presence text as a market clock.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresenceConfig {
+    pub enabled: bool,
+    pub symbol: String,
+    /// Minutes between refreshes while the market is open.
+    pub interval_minutes: u64,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            symbol: DEFAULT_PRESENCE_SYMBOL.to_string(),
+            interval_minutes: DEFAULT_PRESENCE_INTERVAL_MINUTES,
+        }
+    }
+}
+
+/// File format of rendered images (calendars, statements, bot-drawn charts).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageEncoding {
+    #[default]
+    Png,
+    /// Lossless, and usually well under the PNG size for the flat-colour cards.
+    Webp,
+    /// Lossy at `images.jpeg_quality`; the smallest files, with soft edges on text.
+    Jpeg,
+}
+
+impl ImageEncoding {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Webp => "webp",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+impl FromStr for ImageEncoding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "webp" => Ok(Self::Webp),
+            "jpeg" | "jpg" => Ok(Self::Jpeg),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImageConfig {
+    pub format: ImageEncoding,
+    /// 1-100; only used for JPEG.
+    pub jpeg_quality: u8,
+    /// Encoded images larger than this are scaled down until they fit.
+    pub max_bytes: u64,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            format: ImageEncoding::default(),
+            jpeg_quality: DEFAULT_JPEG_QUALITY,
+            max_bytes: DEFAULT_IMAGE_MAX_BYTES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseConfig {
+    /// Guilds where command responses are ephemeral by default.
+    pub ephemeral_guild_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedisConfig {
+    pub url: Option<String>,
+}
+
+impl RedisConfig {
+    /// Only the env vars, for binaries without a config file.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.apply_env();
+        config
+    }
+
+    fn apply_env(&mut self) {
+        if let Some(url) = env_value("REDIS_URL") {
+            self.url = Some(url);
+        }
+    }
+}
+
+/// Where guild settings, paused jobs, earnings threads and price alerts are persisted.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// Use `redis.url`; state is in memory only when Redis is not configured.
+    #[default]
+    Redis,
+    Sqlite,
+    Postgres,
+}
+
+impl FromStr for StorageBackend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "redis" => Ok(Self::Redis),
+            "sqlite" => Ok(Self::Sqlite),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// sqlx connection URL for the SQL backends; SQLite defaults to `stacks-bot.db`.
+    pub database_url: Option<String>,
+}
+
+impl StorageConfig {
+    /// Only the env vars, for binaries without a config file.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_env()?;
+        config.validate().map_err(ConfigError::Invalid)?;
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Some(backend) = env_parse("STORAGE_BACKEND", "redis, sqlite or postgres")? {
+            self.backend = backend;
+        }
+        if let Some(url) = env_value("DATABASE_URL") {
+            self.database_url = Some(url);
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let url = self.database_url.as_deref();
+        match self.backend {
+            StorageBackend::Redis => Ok(()),
+            StorageBackend::Sqlite if url.is_none_or(|u| u.starts_with("sqlite:")) => Ok(()),
+            StorageBackend::Postgres if url.is_some_and(|u| u.starts_with("postgres")) => Ok(()),
+            StorageBackend::Sqlite => {
+                Err("storage.database_url must be a sqlite: URL for the sqlite backend".into())
+            }
+            StorageBackend::Postgres => Err(
+                "storage.database_url (DATABASE_URL) must be a postgres:// URL for the postgres backend"
+                    .into(),
+            ),
+        }
+    }
+
+    /// Connection URL for the SQL backends.
+    pub fn sql_url(&self) -> String {
+        self.database_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SQLITE_URL.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Address for `/healthz` and `/metrics`; the server is off when unset.
+    pub addr: Option<SocketAddr>,
+}
+
+impl HealthConfig {
+    /// Only the env vars, for binaries without a config file.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Some(addr) = env_parse("HEALTH_ADDR", "a socket address like 0.0.0.0:8080")? {
+            self.addr = Some(addr);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    pub notice_channel: Option<u64>,
+    pub notice: String,
+}
+
+impl ShutdownConfig {
+    /// Only the env vars, for binaries without a config file.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        set(&mut self.notice_channel, "SHUTDOWN_NOTICE_CHANNEL_ID")?;
+        if let Some(notice) = env_value("SHUTDOWN_NOTICE") {
+            self.notice = notice;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            notice_channel: None,
+            notice: DEFAULT_SHUTDOWN_NOTICE.to_string(),
+        }
+    }
+}
+
+/// Where upstream, internal and automation errors are reported; both are optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorReportingConfig {
+    pub sentry_dsn: Option<String>,
+    /// Tag sent with Sentry events, e.g. "production".
+    pub environment: Option<String>,
+    /// Receives a JSON POST per error; Discord webhook URLs work as-is.
+    pub webhook_url: Option<String>,
+}
+
+impl ErrorReportingConfig {
+    /// Only the env vars, for binaries without a config file.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_env();
+        config.validate().map_err(ConfigError::Invalid)?;
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) {
+        if let Some(dsn) = env_value("SENTRY_DSN") {
+            self.sentry_dsn = Some(dsn);
+        }
+        if let Some(environment) = env_value("SENTRY_ENVIRONMENT") {
+            self.environment = Some(environment);
+        }
+        if let Some(url) = env_value("ERROR_WEBHOOK_URL") {
+            self.webhook_url = Some(url);
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(dsn) = &self.sentry_dsn {
+            if dsn.parse::<sentry::types::Dsn>().is_err() {
+                return Err("errors.sentry_dsn (SENTRY_DSN) is not a valid Sentry DSN".into());
+            }
+        }
+        if let Some(url) = &self.webhook_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err("errors.webhook_url (ERROR_WEBHOOK_URL) must be an http(s) URL".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Load the config file (if any), apply env overrides and validate.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match env::var("CONFIG_PATH") {
+            Ok(path) if !path.trim().is_empty() => Self::from_file(Path::new(path.trim()))?,
+            _ if Path::new(DEFAULT_PATH).exists() => Self::from_file(Path::new(DEFAULT_PATH))?,
+            _ => Self::default(),
+        };
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let raw = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&raw).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })
+    }
+
+    /// Override file values with any env vars that are set and non-empty.
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Some(token) = env_value("DISCORD_TOKEN") {
+            self.discord.token = token;
+        }
+        if let Some(id) = env_parse("APPLICATION_ID", "a numeric id")? {
+            self.discord.application_id = id;
+        }
+        match env_list("GUILD_IDS")? {
+            Some(ids) => self.discord.guild_ids = ids,
+            None => {
+                if let Some(id) = env_parse::<u64>("GUILD_ID", "a guild id")? {
+                    self.discord.guild_ids = vec![id];
+                }
+            }
+        }
+
+        if let Some(count) = env_parse("SHARD_COUNT", "a number of shards")? {
+            self.discord.shard_count = Some(count);
+        }
+        if let Some(value) = env_value("SHARD_RANGE") {
+            let range = value.split_once('-').and_then(|(first, last)| {
+                Some([first.trim().parse().ok()?, last.trim().parse().ok()?])
+            });
+            self.discord.shard_range = Some(range.ok_or(ConfigError::Env {
+                var: "SHARD_RANGE",
+                expected: "a shard range like 0-3",
+                value,
+            })?);
+        }
+
+        if let Some(v) = env_bool("DISABLE_MESSAGE_CONTENT")? {
+            self.discord.disable_message_content = v;
+        }
+
+        set(&mut self.channels.earnings, "EARNINGS_CHANNEL_ID")?;
+        set(
+            &mut self.channels.earnings_weekly,
+            "EARNINGS_WEEKLY_CHANNEL_ID",
+        )?;
+        set(
+            &mut self.channels.earnings_daily,
+            "EARNINGS_DAILY_CHANNEL_ID",
+        )?;
+        set(
+            &mut self.channels.earnings_after,
+            "EARNINGS_AFTER_CHANNEL_ID",
+        )?;
+        set(
+            &mut self.channels.earnings_summary,
+            "EARNINGS_SUMMARY_CHANNEL_ID",
+        )?;
+        set(&mut self.channels.options, "OPTIONS_CHANNEL_ID")?;
+        set(&mut self.channels.insider, "INSIDER_CHANNEL_ID")?;
+        set(
+            &mut self.channels.corporate_actions,
+            "CORPORATE_ACTIONS_CHANNEL_ID",
+        )?;
+        set(&mut self.channels.operator, "OPERATOR_CHANNEL_ID")?;
+
+        if let Some(v) = env_bool("EARNINGS_DAY_THREADS")? {
+            self.earnings.day_threads = v;
+        }
+        if let Some(v) = env_bool("EARNINGS_SCHEDULED_EVENTS")? {
+            self.earnings.scheduled_events = v;
+        }
+        if let Some(v) = env_parse("EARNINGS_EVENT_MIN_IMPORTANCE", "a number from 1 to 5")? {
+            self.earnings.event_min_importance = v;
+        }
+        if let Some(rate) = env_parse("RISK_FREE_RATE", "a fraction like 0.04")? {
+            self.options.risk_free_rate = rate;
+        }
+        if let Some(n) = env_parse("OPTIONS_ALERT_AFTER_FAILURES", "a number of runs")? {
+            self.options.alert_after_failures = n;
+        }
+
+        if let Some(universe) = env_value("INSIDER_UNIVERSE") {
+            self.insider.universe = universe;
+        }
+        if let Some(symbols) = env_value("ETF_UNIVERSE") {
+            self.etf.universe = symbols
+                .split(',')
+                .map(|s| s.trim().to_ascii_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+
+        if let Some(ids) = env_list("QUOTE_BOARD_CHANNEL_IDS")? {
+            self.quote_board.channels = ids;
+        }
+        if let Some(symbols) = env_value("QUOTE_BOARD_SYMBOLS") {
+            self.quote_board.symbols = symbols
+                .split(',')
+                .map(|s| s.trim().to_ascii_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if let Some(minutes) = env_parse("QUOTE_BOARD_INTERVAL_MINUTES", "a number of minutes")? {
+            self.quote_board.interval_minutes = minutes;
+        }
+
+        if let Some(v) = env_bool("PRESENCE_ENABLED")? {
+            self.presence.enabled = v;
+        }
+        if let Some(symbol) = env_value("PRESENCE_SYMBOL") {
+            self.presence.symbol = symbol.to_ascii_uppercase();
+        }
+        if let Some(minutes) = env_parse("PRESENCE_INTERVAL_MINUTES", "a number of minutes")? {
+            self.presence.interval_minutes = minutes;
+        }
+
+        if let Some(format) = env_parse("IMAGE_FORMAT", "png, webp or jpeg")? {
+            self.images.format = format;
+        }
+        if let Some(quality) = env_parse("IMAGE_JPEG_QUALITY", "a number from 1 to 100")? {
+            self.images.jpeg_quality = quality;
+        }
+        if let Some(bytes) = env_parse("IMAGE_MAX_BYTES", "a number of bytes")? {
+            self.images.max_bytes = bytes;
+        }
+
+        if let Some(ids) = env_list("EPHEMERAL_GUILD_IDS")? {
+            self.responses.ephemeral_guild_ids = ids;
+        }
+        self.constituents.apply_env();
+        self.redis.apply_env();
+        self.storage.apply_env()?;
+        self.health.apply_env()?;
+        self.shutdown.apply_env()?;
+        self.errors.apply_env();
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+        if self.discord.token.is_empty() {
+            problems.push("discord.token (DISCORD_TOKEN) is required".to_string());
+        }
+        if self.discord.application_id == 0 {
+            problems.push("discord.application_id (APPLICATION_ID) is required".to_string());
+        }
+
+        let ids = [
+            ("channels.earnings", self.channels.earnings),
+            ("channels.earnings_weekly", self.channels.earnings_weekly),
+            ("channels.earnings_daily", self.channels.earnings_daily),
+            ("channels.earnings_after", self.channels.earnings_after),
+            ("channels.earnings_summary", self.channels.earnings_summary),
+            ("channels.options", self.channels.options),
+            ("channels.insider", self.channels.insider),
+            (
+                "channels.corporate_actions",
+                self.channels.corporate_actions,
+            ),
+            ("channels.operator", self.channels.operator),
+            ("shutdown.notice_channel", self.shutdown.notice_channel),
+        ];
+        for (field, id) in ids {
+            if id == Some(0) {
+                problems.push(format!("{field} must not be 0"));
+            }
+        }
+        let lists = [
+            ("discord.guild_ids", &self.discord.guild_ids),
+            ("quote_board.channels", &self.quote_board.channels),
+            (
+                "responses.ephemeral_guild_ids",
+                &self.responses.ephemeral_guild_ids,
+            ),
+        ];
+        for (field, list) in lists {
+            if list.contains(&0) {
+                problems.push(format!("{field} must not contain 0"));
+            }
+        }
+
+        match (self.discord.shard_count, self.discord.shard_range) {
+            (Some(0), _) => problems.push("discord.shard_count must not be 0".to_string()),
+            (None, Some(_)) => problems.push(
+                "discord.shard_range (SHARD_RANGE) needs discord.shard_count (SHARD_COUNT)"
+                    .to_string(),
+            ),
+            (Some(count), Some([first, last])) if first > last || last >= count => {
+                problems.push(format!(
+                    "discord.shard_range {first}-{last} must be within 0-{}",
+                    count - 1
+                ))
+            }
+            _ => {}
+        }
+        if let Err(problem) = self.storage.validate() {
+            problems.push(problem);
+        }
+        if let Err(problem) = self.errors.validate() {
+            problems.push(problem);
+        }
+        if let Err(problem) = self.constituents.validate() {
+            problems.push(problem);
+        }
+        if !(1..=5).contains(&self.earnings.event_min_importance) {
+            problems.push(format!(
+                "earnings.event_min_importance must be between 1 and 5, got {}",
+                self.earnings.event_min_importance
+            ));
+        }
+        if !(0.0..=0.25).contains(&self.options.risk_free_rate) {
+            problems.push(format!(
+                "options.risk_free_rate must be a fraction between 0 and 0.25, got {}",
+                self.options.risk_free_rate
+            ));
+        }
+
+        if self.insider.index().is_none() {
+            problems.push(format!(
+                "insider.universe must be sp500, nasdaq100 or dow30, got {:?}",
+                self.insider.universe
+            ));
+        }
+        if self.etf.universe.is_empty() {
+            problems.push("etf.universe must list at least one fund".to_string());
+        }
+        if self.etf.universe.len() > MAX_ETF_UNIVERSE {
+            problems.push(format!(
+                "etf.universe can list at most {MAX_ETF_UNIVERSE} funds"
+            ));
+        }
+
+        if !(1..=60).contains(&self.quote_board.interval_minutes) {
+            problems.push(format!(
+                "quote_board.interval_minutes must be between 1 and 60, got {}",
+                self.quote_board.interval_minutes
+            ));
+        }
+        if !self.quote_board.channels.is_empty() && self.quote_board.symbols.is_empty() {
+            problems.push("quote_board.symbols must list at least one symbol".to_string());
+        }
+        if self.quote_board.symbols.len() > MAX_BOARD_SYMBOLS {
+            problems.push(format!(
+                "quote_board.symbols can list at most {MAX_BOARD_SYMBOLS} symbols"
+            ));
+        }
+        if !(1..=60).contains(&self.presence.interval_minutes) {
+            problems.push(format!(
+                "presence.interval_minutes must be between 1 and 60, got {}",
+                self.presence.interval_minutes
+            ));
+        }
+        if self.presence.enabled && self.presence.symbol.trim().is_empty() {
+            problems.push("presence.symbol must be set when presence is enabled".to_string());
+        }
+        if !(1..=100).contains(&self.images.jpeg_quality) {
+            problems.push(format!(
+                "images.jpeg_quality must be between 1 and 100, got {}",
+                self.images.jpeg_quality
+            ));
+        }
+        if self.images.max_bytes == 0 {
+            problems.push("images.max_bytes must be at least 1".to_string());
+        }
+        if self.options.alert_after_failures == 0 {
+            problems.push("options.alert_after_failures must be at least 1".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems.join("; ")))
+        }
+    }
+
+    pub fn guild_ids(&self) -> Vec<GuildId> {
+        self.discord
+            .guild_ids
+            .iter()
+            .map(|id| GuildId::new(*id))
+            .collect()
+    }
+
+    pub fn is_ephemeral_guild(&self, guild_id: GuildId) -> bool {
+        self.responses.ephemeral_guild_ids.contains(&guild_id.get())
+    }
+
+    /// Settings that differ between `self` and `new`, by dotted key.
+    pub fn diff(&self, new: &Config) -> Vec<ConfigChange> {
+        let (old, new) = (flatten(self), flatten(new));
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        keys.into_iter()
+            .filter(|key| old.get(*key) != new.get(*key))
+            .map(|key| {
+                let show = |value: Option<&String>| {
+                    if SECRET_KEYS.contains(&key.as_str()) {
+                        "(hidden)".to_string()
+                    } else {
+                        value.cloned().unwrap_or_else(|| "unset".to_string())
+                    }
+                };
+                let section = key.split('.').next().unwrap_or_default();
+                ConfigChange {
+                    key: key.clone(),
+                    old: show(old.get(key)),
+                    new: show(new.get(key)),
+                    needs_restart: RESTART_SECTIONS.contains(&section),
+                }
+            })
+            .collect()
+    }
+}
+
+/// One setting changed by a reload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old: String,
+    pub new: String,
+    /// Only read at startup, so the new value applies after a restart.
+    pub needs_restart: bool,
+}
+
+/// The live config, shared by the handler, commands and automations.
+#[derive(Debug, Clone)]
+pub struct ConfigHandle {
+    current: Arc<RwLock<Arc<Config>>>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: Config) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(config))),
+        }
+    }
+
+    /// Snapshot of the config as of now; take a new one for each run rather than holding it.
+    pub fn current(&self) -> Arc<Config> {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Load the config again and swap it in when it is valid. The running config is kept on
+    /// error. Returns what changed.
+    pub fn reload(&self) -> Result<Vec<ConfigChange>, ConfigError> {
+        let new = Config::load()?;
+        let mut current = self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let changes = current.diff(&new);
+        *current = Arc::new(new);
+        Ok(changes)
+    }
+}
+
+/// `section.field` → JSON-rendered value; unset options are left out.
+fn flatten(config: &Config) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let Ok(Value::Object(sections)) = serde_json::to_value(config) else {
+        return out;
+    };
+    for (section, fields) in sections {
+        let Value::Object(fields) = fields else {
+            continue;
+        };
+        for (field, value) in fields.into_iter().filter(|(_, v)| !v.is_null()) {
+            out.insert(format!("{section}.{field}"), value.to_string());
+        }
+    }
+    out
+}
+
+/// An env var's trimmed value; unset and blank are treated the same.
+fn env_value(var: &str) -> Option<String> {
+    env::var(var)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn env_parse<T: FromStr>(
+    var: &'static str,
+    expected: &'static str,
+) -> Result<Option<T>, ConfigError> {
+    env_value(var)
+        .map(|value| {
+            value.parse().map_err(|_| ConfigError::Env {
+                var,
+                expected,
+                value,
+            })
+        })
+        .transpose()
+}
+
+fn env_bool(var: &'static str) -> Result<Option<bool>, ConfigError> {
+    env_value(var)
+        .map(|value| match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Ok(true),
+            "0" | "false" | "no" | "off" => Ok(false),
+            _ => Err(ConfigError::Env {
+                var,
+                expected: "true or false",
+                value,
+            }),
+        })
+        .transpose()
+}
+
+/// Comma-separated ids, e.g. `GUILD_IDS=123,456`.
+fn env_list(var: &'static str) -> Result<Option<Vec<u64>>, ConfigError> {
+    env_value(var)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(|id| id.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ConfigError::Env {
+                    var,
+                    expected: "a comma-separated list of ids",
+                    value,
+                })
+        })
+        .transpose()
+}
+
+fn set(field: &mut Option<u64>, var: &'static str) -> Result<(), ConfigError> {
+    if let Some(id) = env_parse(var, "a numeric id")? {
+        *field = Some(id);
+    }
+    Ok(())
+}
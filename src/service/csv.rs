@@ -0,0 +1,38 @@
+//! Minimal CSV writing for file attachments (RFC 4180 quoting, `\n` line endings).
+
+/// Leading characters that make spreadsheets read a cell as a formula.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Quote `field` when it contains a comma, quote or line break, doubling inner quotes. Text
+/// starting like a formula gets a leading `'` so spreadsheets show it instead of running it;
+/// numbers such as `-1.5` are left alone.
+pub fn field(field: &str) -> String {
+    let escaped;
+    let field = if field.starts_with(FORMULA_PREFIXES) && field.parse::<f64>().is_err() {
+        escaped = format!("'{field}");
+        escaped.as_str()
+    } else {
+        field
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// One CSV line, including the trailing newline.
+pub fn row<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// Optional number as a CSV cell; missing values are left empty.
+pub fn number(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,133 @@
+//! Optional error reporting to Sentry and/or a webhook.
+//!
+//! Internal command errors (`BotError::log`) and automation failures (`jobs::record_run`) are
+//! reported here so recurring upstream problems reach the operator without reading container
+//! logs. Webhook posts are throttled per source: a failure that keeps recurring is posted once
+//! per cooldown, with a count of the repeats in between.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::service::config::ErrorReportingConfig;
+
+const WEBHOOK_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+/// Discord rejects message content over 2000 characters.
+const MAX_WEBHOOK_MESSAGE: usize = 1800;
+
+static REPORTER: OnceCell<Reporter> = OnceCell::new();
+
+struct Reporter {
+    webhook_url: Option<String>,
+    client: reqwest::Client,
+    /// Per source: when the webhook last posted and how many reports were held back since.
+    throttle: Mutex<HashMap<String, (Instant, u64)>>,
+}
+
+/// Start reporting with `config`. Keep the returned guard alive until exit; dropping it
+/// flushes queued Sentry events. Panics are reported too once Sentry is set up.
+pub fn init(config: &ErrorReportingConfig) -> Option<sentry::ClientInitGuard> {
+    let guard = config.sentry_dsn.as_deref().map(|dsn| {
+        sentry::init((
+            dsn,
+            sentry::ClientOptions {
+                release: sentry::release_name!(),
+                environment: config.environment.clone().map(Into::into),
+                ..Default::default()
+            },
+        ))
+    });
+    let reporter = Reporter {
+        webhook_url: config.webhook_url.clone(),
+        client: reqwest::Client::new(),
+        throttle: Mutex::new(HashMap::new()),
+    };
+    match (guard.is_some(), reporter.webhook_url.is_some()) {
+        (false, false) => {
+            info!("Error reporting disabled; set errors.sentry_dsn or errors.webhook_url")
+        }
+        (sentry, webhook) => info!("Error reporting: sentry={sentry} webhook={webhook}"),
+    }
+    let _ = REPORTER.set(reporter);
+    guard
+}
+
+/// Report an error. `source` names where it happened, e.g. `command:quote` or
+/// `automation:daily_earnings`, and is sent as a tag.
+pub fn report(source: &str, message: &str) {
+    let Some(reporter) = REPORTER.get() else {
+        return;
+    };
+
+    sentry::with_scope(
+        |scope| scope.set_tag("source", source),
+        || sentry::capture_message(message, sentry::Level::Error),
+    );
+
+    let Some(url) = reporter.webhook_url.clone() else {
+        return;
+    };
+    let Some(repeats) = reporter.take_slot(source) else {
+        return;
+    };
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+
+    let mut content = format!("🚨 `{source}`: {message}");
+    if repeats > 0 {
+        content.push_str(&format!(" (plus {repeats} more since the last report)"));
+    }
+    if content.len() > MAX_WEBHOOK_MESSAGE {
+        let cut = (0..=MAX_WEBHOOK_MESSAGE)
+            .rev()
+            .find(|i| content.is_char_boundary(*i))
+            .unwrap_or_default();
+        content.truncate(cut);
+        content.push('…');
+    }
+    let body = json!({
+        "content": content,
+        "source": source,
+        "message": message,
+        "repeats": repeats,
+    });
+    let client = reporter.client.clone();
+    runtime.spawn(async move {
+        let sent = client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+        if let Err(e) = sent {
+            warn!("failed to post error report: {e}");
+        }
+    });
+}
+
+impl Reporter {
+    /// `Some(held back count)` when `source` may post now, `None` while it is cooling down.
+    fn take_slot(&self, source: &str) -> Option<u64> {
+        let mut throttle = self.throttle.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        match throttle.get_mut(source) {
+            Some((last, held)) if now.duration_since(*last) < WEBHOOK_COOLDOWN => {
+                *held += 1;
+                None
+            }
+            Some((last, held)) => {
+                *last = now;
+                Some(std::mem::take(held))
+            }
+            None => {
+                throttle.insert(source.to_string(), (now, 0));
+                Some(0)
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Open Graph previews of news articles for `/news preview:true`: the description and image a
+//! page advertises to link unfurlers, cached in Redis for a day.
+//!
+//! Publishers are slow and sometimes block bots, so the fetch is short and a failure only
+//! costs the embed; the headlines are sent either way.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::news::canonical_url;
+use super::FinanceService;
+use crate::service::caching::collections::article_previews;
+
+/// Whole request, connect to last byte; the reply is already deferred but shouldn't stall.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+/// Open Graph tags live in `<head>`; stop reading a page after this much.
+const MAX_HEAD_BYTES: usize = 256 * 1024;
+/// Discord cuts embed descriptions at 4096 characters; a preview needs far fewer.
+const MAX_DESCRIPTION_CHARS: usize = 300;
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; StacksBot/1.0; +https://discord.com)";
+
+static META_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<meta\s[^>]*>").unwrap());
+static ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?is)([a-z_:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+/// What a page says about itself for link previews.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticlePreview {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Absolute image URL.
+    pub image: Option<String>,
+    pub site_name: Option<String>,
+}
+
+impl ArticlePreview {
+    /// Whether there is anything to show beyond the headline itself.
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.image.is_none()
+    }
+}
+
+/// Read `og:*` tags (falling back to `twitter:*` and the plain `description` meta) from `html`.
+/// Relative image URLs are resolved against `page`.
+pub fn parse_open_graph(html: &str, page: &Url) -> ArticlePreview {
+    let mut og = ArticlePreview::default();
+    let mut fallback = ArticlePreview::default();
+    for tag in META_TAG.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attr in ATTRIBUTE.captures_iter(tag.as_str()) {
+            let value = attr.get(2).or_else(|| attr.get(3)).map(|m| m.as_str());
+            match attr[1].to_ascii_lowercase().as_str() {
+                "property" | "name" => key = value.map(str::to_ascii_lowercase),
+                "content" => content = value.map(decode_entities),
+                _ => {}
+            }
+        }
+        let (Some(key), Some(content)) = (key, content) else {
+            continue;
+        };
+        let content = content.trim().to_string();
+        if content.is_empty() {
+            continue;
+        }
+        let slot = match key.as_str() {
+            "og:title" => &mut og.title,
+            "og:description" => &mut og.description,
+            "og:image" | "og:image:url" | "og:image:secure_url" => &mut og.image,
+            "og:site_name" => &mut og.site_name,
+            "twitter:title" => &mut fallback.title,
+            "twitter:description" | "description" => &mut fallback.description,
+            "twitter:image" | "twitter:image:src" => &mut fallback.image,
+            _ => continue,
+        };
+        slot.get_or_insert(content);
+    }
+
+    let image = og
+        .image
+        .or(fallback.image)
+        .and_then(|image| page.join(&image).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(String::from);
+    ArticlePreview {
+        title: og.title.or(fallback.title),
+        description: og.description.or(fallback.description).map(|d| shorten(&d)),
+        image,
+        site_name: og.site_name,
+    }
+}
+
+fn shorten(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= MAX_DESCRIPTION_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_DESCRIPTION_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+impl FinanceService {
+    /// Open Graph preview of the article at `link`, or `None` when the page can't be fetched in
+    /// time or advertises nothing beyond its title.
+    pub async fn article_preview(&self, link: &str) -> Option<ArticlePreview> {
+        let key = canonical_url(link);
+        if let Some(cache) = &self.cache {
+            match article_previews::load_preview(cache, &key).await {
+                Ok(Some(preview)) => return Some(preview),
+                Ok(None) => {}
+                Err(e) => warn!("failed to read cached preview of {link}: {e}"),
+            }
+        }
+        let preview = match fetch(link).await {
+            Ok(preview) => preview,
+            Err(e) => {
+                warn!("article preview of {link} failed: {e}");
+                return None;
+            }
+        };
+        if preview.is_empty() {
+            return None;
+        }
+        if let Some(cache) = &self.cache {
+            if let Err(e) = article_previews::save_preview(cache, &key, &preview).await {
+                warn!("failed to cache preview of {link}: {e}");
+            }
+        }
+        Some(preview)
+    }
+}
+
+async fn fetch(link: &str) -> Result<ArticlePreview, String> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| format!("failed to build client: {e}"))?;
+    let mut resp = client
+        .get(link)
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| e.to_string())?;
+    // Redirects (consent pages, AMP) change the base for relative image URLs.
+    let page = resp.url().clone();
+
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await.map_err(|e| e.to_string())? {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_HEAD_BYTES || contains(&body, b"</head>") {
+            break;
+        }
+    }
+    Ok(parse_open_graph(&String::from_utf8_lossy(&body), &page))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
@@ -0,0 +1,274 @@
+//! Long-only backtests of simple strategies over daily closes for `/backtest`.
+//!
+//! Signals are read at each close and traded at that same close, all in or all out, without
+//! costs, slippage or dividends. Indicators warm up on history before the tested period, so a
+//! moving-average cross can hold a position from its first day.
+
+use chrono::NaiveDate;
+
+use super::history::DailyClose;
+use super::technicals;
+
+/// Fast and slow averages of the moving-average cross.
+pub const SMA_FAST: usize = 50;
+pub const SMA_SLOW: usize = 200;
+pub const RSI_PERIOD: usize = 14;
+/// RSI strategy: buy at or below `RSI_BUY`, sell at or above `RSI_SELL`.
+pub const RSI_BUY: f64 = 30.0;
+pub const RSI_SELL: f64 = 70.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    SmaCross,
+    Rsi,
+    BuyHold,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::SmaCross, Strategy::Rsi, Strategy::BuyHold];
+
+    /// Option value, as picked in Discord.
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::SmaCross => "sma_cross",
+            Strategy::Rsi => "rsi",
+            Strategy::BuyHold => "buy_hold",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Strategy::SmaCross => "SMA 50/200 cross",
+            Strategy::Rsi => "RSI(14) 30/70",
+            Strategy::BuyHold => "Buy and hold",
+        }
+    }
+
+    /// Per close: `Some(true)` to be in the market, `Some(false)` to be out, `None` to keep the
+    /// current position.
+    fn signals(self, closes: &[f64]) -> Vec<Option<bool>> {
+        match self {
+            Strategy::SmaCross => {
+                let fast = technicals::sma(closes, SMA_FAST);
+                let slow = technicals::sma(closes, SMA_SLOW);
+                fast.iter()
+                    .zip(&slow)
+                    .map(|(f, s)| Some(f.as_ref()? > s.as_ref()?))
+                    .collect()
+            }
+            Strategy::Rsi => technicals::rsi(closes, RSI_PERIOD)
+                .into_iter()
+                .map(|rsi| match rsi? {
+                    r if r <= RSI_BUY => Some(true),
+                    r if r >= RSI_SELL => Some(false),
+                    _ => None,
+                })
+                .collect(),
+            Strategy::BuyHold => vec![Some(true); closes.len()],
+        }
+    }
+}
+
+/// How far back `/backtest` tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    SixMonths,
+    OneYear,
+    TwoYears,
+    FiveYears,
+}
+
+impl Period {
+    pub const ALL: [Period; 4] = [
+        Period::SixMonths,
+        Period::OneYear,
+        Period::TwoYears,
+        Period::FiveYears,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Period::SixMonths => "6mo",
+            Period::OneYear => "1y",
+            Period::TwoYears => "2y",
+            Period::FiveYears => "5y",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Period::SixMonths => "6 months",
+            Period::OneYear => "1 year",
+            Period::TwoYears => "2 years",
+            Period::FiveYears => "5 years",
+        }
+    }
+
+    pub fn days(self) -> i64 {
+        match self {
+            Period::SixMonths => 182,
+            Period::OneYear => 365,
+            Period::TwoYears => 730,
+            Period::FiveYears => 1826,
+        }
+    }
+
+    /// Yahoo chart range fetched: the period plus at least the 200 sessions the slow average
+    /// needs before its first day.
+    pub fn history_range(self) -> &'static str {
+        match self {
+            Period::SixMonths | Period::OneYear => "2y",
+            Period::TwoYears => "5y",
+            Period::FiveYears => "10y",
+        }
+    }
+}
+
+/// One round trip. A trade still open at the end is valued at the last close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub entry: NaiveDate,
+    pub entry_price: f64,
+    pub exit: NaiveDate,
+    pub exit_price: f64,
+    pub open: bool,
+}
+
+impl Trade {
+    /// As a fraction: `0.05` for +5%.
+    pub fn return_fraction(&self) -> f64 {
+        self.exit_price / self.entry_price - 1.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backtest {
+    pub strategy: Strategy,
+    /// Strategy value per close, starting at 1.0.
+    pub equity: Vec<(NaiveDate, f64)>,
+    /// Buy and hold over the same closes, starting at 1.0.
+    pub benchmark: Vec<(NaiveDate, f64)>,
+    pub trades: Vec<Trade>,
+    /// Closes spent in the market, excluding the first.
+    pub days_in_market: usize,
+}
+
+impl Backtest {
+    pub fn start(&self) -> NaiveDate {
+        self.equity[0].0
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.equity[self.equity.len() - 1].0
+    }
+
+    pub fn total_return(&self) -> f64 {
+        final_value(&self.equity) - 1.0
+    }
+
+    pub fn benchmark_return(&self) -> f64 {
+        final_value(&self.benchmark) - 1.0
+    }
+
+    pub fn max_drawdown(&self) -> f64 {
+        technicals::max_drawdown(&values(&self.equity))
+    }
+
+    pub fn benchmark_drawdown(&self) -> f64 {
+        technicals::max_drawdown(&values(&self.benchmark))
+    }
+
+    /// Share of trades, the open one included, that gained; `None` without trades.
+    pub fn win_rate(&self) -> Option<f64> {
+        if self.trades.is_empty() {
+            return None;
+        }
+        let wins = self
+            .trades
+            .iter()
+            .filter(|t| t.return_fraction() > 0.0)
+            .count();
+        Some(wins as f64 / self.trades.len() as f64)
+    }
+
+    /// Share of the period's sessions spent holding.
+    pub fn exposure(&self) -> f64 {
+        let sessions = self.equity.len().saturating_sub(1).max(1);
+        self.days_in_market as f64 / sessions as f64
+    }
+}
+
+fn final_value(curve: &[(NaiveDate, f64)]) -> f64 {
+    curve.last().map_or(1.0, |(_, v)| *v)
+}
+
+fn values(curve: &[(NaiveDate, f64)]) -> Vec<f64> {
+    curve.iter().map(|(_, v)| *v).collect()
+}
+
+/// Run `strategy` on the closes from `start` on, with earlier closes used only to warm up its
+/// indicators. `None` with fewer than two closes in the period.
+pub fn run(strategy: Strategy, history: &[DailyClose], start: NaiveDate) -> Option<Backtest> {
+    let first = history.iter().position(|c| c.date >= start)?;
+    if history.len() - first < 2 {
+        return None;
+    }
+    let closes: Vec<f64> = history.iter().map(|c| c.close).collect();
+    let signals = strategy.signals(&closes);
+    let last = history.len() - 1;
+    let base = closes[first];
+
+    let mut equity = Vec::with_capacity(history.len() - first);
+    let mut benchmark = Vec::with_capacity(history.len() - first);
+    let mut trades = Vec::new();
+    let mut entry: Option<usize> = None;
+    let mut value = 1.0;
+    let mut days_in_market = 0;
+    for i in first..=last {
+        if i > first && entry.is_some() {
+            value *= closes[i] / closes[i - 1];
+            days_in_market += 1;
+        }
+        equity.push((history[i].date, value));
+        benchmark.push((history[i].date, closes[i] / base));
+
+        match (signals[i], entry) {
+            // Buying the last close would only open an empty trade.
+            (Some(true), None) if i < last => entry = Some(i),
+            (Some(false), Some(at)) => {
+                trades.push(trade(history, at, i, false));
+                entry = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(at) = entry {
+        trades.push(trade(history, at, last, true));
+    }
+
+    Some(Backtest {
+        strategy,
+        equity,
+        benchmark,
+        trades,
+        days_in_market,
+    })
+}
+
+fn trade(history: &[DailyClose], entry: usize, exit: usize, open: bool) -> Trade {
+    Trade {
+        entry: history[entry].date,
+        entry_price: history[entry].close,
+        exit: history[exit].date,
+        exit_price: history[exit].close,
+        open,
+    }
+}
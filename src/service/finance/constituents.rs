@@ -0,0 +1,268 @@
+//! Index membership (S&P 500, Nasdaq-100, Dow 30) for filtering symbols to a universe.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::{FinanceService, FinanceServiceError};
+use crate::service::caching::collections::index_constituents;
+use crate::service::config::ConstituentsConfig;
+
+/// Membership changes a few times a quarter; refetch once a day.
+const REFRESH_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long the last good list is kept after a failed or implausible fetch before retrying.
+const RETRY_AFTER: Duration = Duration::from_secs(60 * 60);
+/// Whole request; the lists are small, and the scheduled posts that need them shouldn't stall.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(concat!("stacks-bot/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap_or_default()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Index {
+    Sp500,
+    Nasdaq100,
+    Dow30,
+}
+
+impl Index {
+    pub const ALL: [Index; 3] = [Index::Sp500, Index::Nasdaq100, Index::Dow30];
+
+    /// Name used in settings and command options, e.g. `sp500`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Index::Sp500 => "sp500",
+            Index::Nasdaq100 => "nasdaq100",
+            Index::Dow30 => "dow30",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Index::Sp500 => "S&P 500",
+            Index::Nasdaq100 => "Nasdaq-100",
+            Index::Dow30 => "Dow 30",
+        }
+    }
+
+    /// Member counts a fetched list must fall in to replace the last good one; a source that
+    /// changed shape tends to parse to a handful of rows or to every row of the page.
+    pub fn expected_members(self) -> RangeInclusive<usize> {
+        match self {
+            // Around 503 share classes for about 500 companies.
+            Index::Sp500 => 480..=520,
+            // 100 companies, a few of them listed with two share classes.
+            Index::Nasdaq100 => 95..=110,
+            Index::Dow30 => 30..=30,
+        }
+    }
+
+    /// Parse a name or common alias (`spx`, `ndx`, `djia`), case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "sp500" | "s&p500" | "spx" => Some(Index::Sp500),
+            "nasdaq100" | "ndx" => Some(Index::Nasdaq100),
+            "dow30" | "dow" | "djia" => Some(Index::Dow30),
+            _ => None,
+        }
+    }
+}
+
+/// Members cached per index with when to refetch them.
+pub(crate) type ConstituentCache = Mutex<HashMap<Index, (Instant, Arc<HashSet<String>>)>>;
+
+impl FinanceService {
+    /// Yahoo symbols in `index`, fetched at most once a day. With a Redis cache the list is
+    /// shared across restarts and instances. When a fetch fails or returns an implausible
+    /// list, the last good one is used and the fetch retried an hour later.
+    pub async fn constituents(
+        &self,
+        index: Index,
+    ) -> Result<Arc<HashSet<String>>, FinanceServiceError> {
+        let previous = {
+            let cache = self.constituents.lock().unwrap_or_else(|e| e.into_inner());
+            match cache.get(&index) {
+                Some((refresh_at, members)) if Instant::now() < *refresh_at => {
+                    return Ok(members.clone())
+                }
+                entry => entry.map(|(_, members)| members.clone()),
+            }
+        };
+        let (members, refresh_after) = match self.load_constituents(index).await {
+            Ok(members) => (Arc::new(members), REFRESH_AFTER),
+            Err(e) => {
+                let last_good = match previous {
+                    Some(members) => Some(members),
+                    None => self.last_good_constituents(index).await.map(Arc::new),
+                };
+                let Some(members) = last_good else {
+                    return Err(e);
+                };
+                warn!(
+                    "keeping the last good {} list ({} members): {e}",
+                    index.label(),
+                    members.len()
+                );
+                (members, RETRY_AFTER)
+            }
+        };
+        self.constituents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(index, (Instant::now() + refresh_after, members.clone()));
+        Ok(members)
+    }
+
+    /// The list saved after the last fetch that passed the checks, from before a restart.
+    async fn last_good_constituents(&self, index: Index) -> Option<HashSet<String>> {
+        let cache = self.cache.as_ref()?;
+        match index_constituents::load_last_good(cache, index.name()).await {
+            Ok(members) => members,
+            Err(e) => {
+                warn!("failed to read the last good {} list: {e}", index.label());
+                None
+            }
+        }
+    }
+
+    async fn load_constituents(
+        &self,
+        index: Index,
+    ) -> Result<HashSet<String>, FinanceServiceError> {
+        if let Some(cache) = &self.cache {
+            match index_constituents::load_members(cache, index.name()).await {
+                Ok(Some(members)) => return Ok(members),
+                Ok(None) => {}
+                Err(e) => warn!("failed to read cached {} members: {e}", index.label()),
+            }
+        }
+        let members = self
+            .resilience
+            .call("constituents", || fetch(index, &self.constituent_sources))
+            .await?;
+        info!("Loaded {} {} members", members.len(), index.label());
+        if let Some(cache) = &self.cache {
+            if let Err(e) = index_constituents::save_members(cache, index.name(), &members).await {
+                warn!("failed to cache {} members: {e}", index.label());
+            }
+        }
+        Ok(members)
+    }
+}
+
+async fn fetch(
+    index: Index,
+    sources: &ConstituentsConfig,
+) -> Result<HashSet<String>, FinanceServiceError> {
+    let url = match index {
+        Index::Sp500 => sources.sp500_url.as_str(),
+        Index::Nasdaq100 => sources.nasdaq100_url.as_str(),
+        Index::Dow30 => sources.dow30_url.as_str(),
+    };
+    let failed = |e: reqwest::Error| {
+        FinanceServiceError::Constituents(format!("{} request failed: {e}", index.label()))
+    };
+    let body = CLIENT
+        .get(url)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(failed)?
+        .text()
+        .await
+        .map_err(failed)?;
+
+    let members = match index {
+        Index::Sp500 => parse_symbol_column(&body),
+        Index::Nasdaq100 => parse_nasdaq_rows(&body),
+        Index::Dow30 => parse_wiki_components(&body),
+    };
+    let expected = index.expected_members();
+    if !expected.contains(&members.len()) {
+        return Err(FinanceServiceError::Constituents(format!(
+            "{} list had {} symbols, expected {}-{}",
+            index.label(),
+            members.len(),
+            expected.start(),
+            expected.end()
+        )));
+    }
+    Ok(members)
+}
+
+/// Nasdaq and Yahoo write share classes differently (`BRK.B`, `BRK/B` vs `BRK-B`).
+fn yahoo_symbol(symbol: &str) -> String {
+    symbol
+        .trim()
+        .trim_matches('"')
+        .replace(['.', '/'], "-")
+        .to_uppercase()
+}
+
+/// First column of a CSV with a header row.
+pub fn parse_symbol_column(csv: &str) -> HashSet<String> {
+    csv.lines()
+        .skip(1)
+        .filter_map(|line| line.split(',').next())
+        .map(yahoo_symbol)
+        .filter(|symbol| !symbol.is_empty())
+        .collect()
+}
+
+/// `data.data.rows[].symbol` of the Nasdaq list API.
+pub fn parse_nasdaq_rows(json: &str) -> HashSet<String> {
+    let Ok(value) = serde_json::from_str::<Value>(json) else {
+        return HashSet::new();
+    };
+    value["data"]["data"]["rows"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|row| row["symbol"].as_str())
+        .map(yahoo_symbol)
+        .filter(|symbol| !symbol.is_empty())
+        .collect()
+}
+
+/// Ticker templates (`{{NYSE|MMM}}`, `{{NASDAQ|AAPL}}`) in the article's "Components"
+/// section, which holds the current members' table.
+pub fn parse_wiki_components(wikitext: &str) -> HashSet<String> {
+    let section = wikitext
+        .split("\n==")
+        .find(|part| {
+            part.trim_start_matches('=')
+                .trim_start()
+                .starts_with("Components")
+        })
+        .unwrap_or_default();
+
+    let mut symbols = HashSet::new();
+    let mut rest = section;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+        let mut parts = rest[..end].split('|');
+        let exchange = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        if matches!(exchange.as_str(), "nyse" | "nasdaq" | "nasdaqselect") {
+            if let Some(symbol) = parts.next().map(yahoo_symbol) {
+                if !symbol.is_empty() {
+                    symbols.insert(symbol);
+                }
+            }
+        }
+        rest = &rest[end + 2..];
+    }
+    symbols
+}
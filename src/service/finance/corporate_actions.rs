@@ -0,0 +1,130 @@
+//! Dividend and split state per symbol, compared run to run to spot dividend increases, cuts
+//! and initiations and newly recorded splits.
+//!
+//! Yahoo has no feed of announcements, so a change is detected when the forward dividend rate
+//! or the last split moves from what was stored on the previous run. Splits show up once Yahoo
+//! records them, usually around the effective date.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::{FinanceService, FinanceServiceError};
+use crate::models::QuoteSummary;
+
+/// Relative dividend rate change reported; smaller moves are rounding in Yahoo's forward rate.
+pub const MIN_DIVIDEND_CHANGE: f64 = 0.01;
+/// Splits recorded longer ago than this are old news filling in a missing field.
+pub const SPLIT_RECENCY_DAYS: i64 = 30;
+
+/// What is compared between runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorporateActions {
+    /// Forward annual dividend per share.
+    pub dividend_rate: Option<f64>,
+    pub ex_dividend_date: Option<NaiveDate>,
+    /// Yahoo's `new:old`, e.g. `4:1`.
+    pub split_factor: Option<String>,
+    pub split_date: Option<NaiveDate>,
+}
+
+impl CorporateActions {
+    pub fn from_summary(summary: &QuoteSummary) -> Self {
+        let detail = summary.summary_detail.as_ref();
+        let stats = summary.default_key_statistics.as_ref();
+        CorporateActions {
+            dividend_rate: detail.and_then(|d| d.dividend_rate),
+            ex_dividend_date: detail.and_then(|d| d.ex_dividend_date),
+            split_factor: stats.and_then(|s| s.last_split_factor.clone()),
+            split_date: stats.and_then(|s| s.last_split_date),
+        }
+    }
+}
+
+/// A change worth an alert.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionChange {
+    Split {
+        factor: String,
+        date: NaiveDate,
+    },
+    DividendIncrease {
+        from: f64,
+        to: f64,
+        ex_date: Option<NaiveDate>,
+    },
+    DividendCut {
+        from: f64,
+        to: f64,
+        ex_date: Option<NaiveDate>,
+    },
+    DividendInitiated {
+        rate: f64,
+        ex_date: Option<NaiveDate>,
+    },
+}
+
+/// Changes from `previous` to `current`. A missing dividend rate is treated as unknown rather
+/// than as a suspension, since Yahoo drops the field for payers now and then.
+pub fn changes(
+    previous: &CorporateActions,
+    current: &CorporateActions,
+    today: NaiveDate,
+) -> Vec<ActionChange> {
+    let mut found = Vec::new();
+    if let (Some(date), Some(factor)) = (current.split_date, &current.split_factor) {
+        if previous.split_date != Some(date) && date >= today - Duration::days(SPLIT_RECENCY_DAYS) {
+            found.push(ActionChange::Split {
+                factor: factor.clone(),
+                date,
+            });
+        }
+    }
+
+    let ex_date = current.ex_dividend_date;
+    match (previous.dividend_rate, current.dividend_rate) {
+        (Some(from), Some(to)) if from > 0.0 && to > 0.0 => {
+            let change = (to - from) / from;
+            if change >= MIN_DIVIDEND_CHANGE {
+                found.push(ActionChange::DividendIncrease { from, to, ex_date });
+            } else if change <= -MIN_DIVIDEND_CHANGE {
+                found.push(ActionChange::DividendCut { from, to, ex_date });
+            }
+        }
+        (Some(from), Some(to)) if from > 0.0 && to == 0.0 => {
+            found.push(ActionChange::DividendCut { from, to, ex_date });
+        }
+        (from, Some(rate)) if from.unwrap_or(0.0) <= 0.0 && rate > 0.0 => {
+            found.push(ActionChange::DividendInitiated { rate, ex_date });
+        }
+        _ => {}
+    }
+    found
+}
+
+/// `4:1` → `4-for-1`, `1:10` → `1-for-10 reverse`.
+pub fn split_label(factor: &str) -> String {
+    let parsed = factor.split_once(':').and_then(|(new, old)| {
+        Some((
+            new.trim().parse::<f64>().ok()?,
+            old.trim().parse::<f64>().ok()?,
+        ))
+    });
+    match parsed {
+        Some((new, old)) if new < old => format!("{new}-for-{old} reverse"),
+        Some((new, old)) => format!("{new}-for-{old}"),
+        None => factor.to_string(),
+    }
+}
+
+impl FinanceService {
+    /// Current dividend and split state of `symbol`.
+    pub async fn corporate_actions(
+        &self,
+        symbol: &str,
+    ) -> Result<CorporateActions, FinanceServiceError> {
+        let summary = self
+            .get_quote_summary(symbol, &["summaryDetail", "defaultKeyStatistics"])
+            .await?;
+        Ok(CorporateActions::from_summary(&summary))
+    }
+}
@@ -0,0 +1,147 @@
+//! A simple discounted cash flow model: trailing free cash flow grown for a few years and then
+//! at a terminal rate, discounted back to today.
+
+use chrono::NaiveDate;
+
+use crate::models::{FinancialStatement, Frequency, StatementType};
+use crate::service::growth;
+
+const FCF_FIELD: &str = "FreeCashFlow";
+/// Growth used when neither the user nor the FCF history gives one.
+pub const DEFAULT_GROWTH: f64 = 0.05;
+/// A historical CAGR is clamped to this before it is projected forward.
+const MAX_DEFAULT_GROWTH: f64 = 0.15;
+pub const DEFAULT_DISCOUNT: f64 = 0.10;
+pub const DEFAULT_TERMINAL_GROWTH: f64 = 0.025;
+pub const DEFAULT_YEARS: u32 = 5;
+/// The range moves growth by this much and the discount rate by [`DISCOUNT_SPREAD`], each
+/// against the other, around the base case.
+pub const GROWTH_SPREAD: f64 = 0.02;
+pub const DISCOUNT_SPREAD: f64 = 0.01;
+
+/// What the model is run with; rates are fractions (`0.10` = 10%).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DcfInputs {
+    /// Free cash flow of the base year, in the quote currency.
+    pub fcf: f64,
+    pub growth: f64,
+    pub discount: f64,
+    pub terminal_growth: f64,
+    pub years: u32,
+}
+
+/// Present value of the projected cash flows, and how much of it is the terminal value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DcfValue {
+    pub total: f64,
+    pub terminal: f64,
+}
+
+/// Intrinsic value per share: the base case and the bear and bull cases around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DcfRange {
+    pub low: f64,
+    pub base: f64,
+    pub high: f64,
+    /// Share of the base case's value that comes from the terminal value.
+    pub terminal_share: f64,
+}
+
+/// Discount `years` of cash flows growing at `growth` from `fcf`, plus a Gordon growth
+/// terminal value after the last one. `None` unless the discount rate is above the terminal
+/// growth rate, where the terminal value is finite.
+pub fn present_value(inputs: &DcfInputs) -> Option<DcfValue> {
+    let DcfInputs {
+        fcf,
+        growth,
+        discount,
+        terminal_growth,
+        years,
+    } = *inputs;
+    if discount <= terminal_growth || discount <= -1.0 {
+        return None;
+    }
+    let mut cash_flow = fcf;
+    let mut explicit = 0.0;
+    for year in 1..=years as i32 {
+        cash_flow *= 1.0 + growth;
+        explicit += cash_flow / (1.0 + discount).powi(year);
+    }
+    let terminal_value = cash_flow * (1.0 + terminal_growth) / (discount - terminal_growth);
+    let terminal = terminal_value / (1.0 + discount).powi(years as i32);
+    Some(DcfValue {
+        total: explicit + terminal,
+        terminal,
+    })
+}
+
+/// Per-share values for the base case and for growth and discount moved against each other.
+/// The bull case keeps the base discount rate when lowering it would reach terminal growth.
+pub fn value_range(inputs: &DcfInputs, shares: f64) -> Option<DcfRange> {
+    if shares <= 0.0 {
+        return None;
+    }
+    let base = present_value(inputs)?;
+    let low = present_value(&DcfInputs {
+        growth: inputs.growth - GROWTH_SPREAD,
+        discount: inputs.discount + DISCOUNT_SPREAD,
+        ..*inputs
+    })?;
+    let bull_discount = inputs.discount - DISCOUNT_SPREAD;
+    let high = present_value(&DcfInputs {
+        growth: inputs.growth + GROWTH_SPREAD,
+        discount: if bull_discount > inputs.terminal_growth {
+            bull_discount
+        } else {
+            inputs.discount
+        },
+        ..*inputs
+    })?;
+    Some(DcfRange {
+        low: low.total / shares,
+        base: base.total / shares,
+        high: high.total / shares,
+        terminal_share: base.terminal / base.total,
+    })
+}
+
+/// Growth to project when none is given: the FCF's 3-year CAGR up to `at`, clamped to
+/// 0–15%, or [`DEFAULT_GROWTH`] without that history.
+pub fn default_growth(history: &[(NaiveDate, f64)], at: NaiveDate) -> f64 {
+    growth::growth_at(history, at)
+        .cagr_3y
+        .map(|cagr| cagr.clamp(0.0, MAX_DEFAULT_GROWTH))
+        .unwrap_or(DEFAULT_GROWTH)
+}
+
+/// Dated free cash flow values of `freq` in `statements`, oldest first, and their currency.
+pub fn fcf_history(
+    statements: &[FinancialStatement],
+    freq: Frequency,
+) -> (Vec<(NaiveDate, f64)>, Option<String>) {
+    let mut currency = None;
+    let mut points: Vec<(NaiveDate, f64)> = statements
+        .iter()
+        .filter(|s| s.statement_type == StatementType::CashFlow.as_str())
+        .filter(|s| s.frequency == freq.as_str())
+        .filter_map(|s| s.statement.get(FCF_FIELD))
+        .flat_map(|series| series.iter())
+        .filter_map(|(date, value)| {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            let raw = value
+                .get("reportedValue")
+                .and_then(|rv| rv.get("raw"))
+                .and_then(|r| r.as_f64())?;
+            if currency.is_none() {
+                currency = value
+                    .get("currencyCode")
+                    .and_then(|c| c.as_str())
+                    .map(str::to_string);
+            }
+            Some((date, raw))
+        })
+        .collect();
+    points.sort_by_key(|(date, _)| *date);
+    points.dedup_by_key(|(date, _)| *date);
+    (points, currency)
+}
@@ -50,6 +50,12 @@ struct ApiEarning {
     symbol: String,
     date: String,
     time: Option<String>,
+    #[serde(default, alias = "epsEstimate", deserialize_with = "lenient_f64")]
+    eps_estimate: Option<f64>,
+    #[serde(default, alias = "revenueEstimate", deserialize_with = "lenient_f64")]
+    revenue_estimate: Option<f64>,
+    #[serde(default, alias = "marketCap", deserialize_with = "lenient_f64")]
+    market_cap: Option<f64>,
     title: Option<String>,
     emoji: Option<String>,
     logo: Option<String>, // This is now a URL string, not base64 data
@@ -88,7 +94,7 @@ pub async fn fetch_earnings_range(
         .await
         .map_err(|e| {
             warn!("Earnings API request failed: {}", e);
-            FinanceServiceError::Http(format!("earnings request failed: {e}"))
+            request_error("earnings request failed", e)
         })?;
 
     info!("Received response with status: {}", resp.status());
@@ -108,7 +114,7 @@ pub async fn fetch_earnings_range(
 
     let raw_bytes = resp.bytes().await.map_err(|e| {
         warn!("Failed to read earnings API body: {}", e);
-        FinanceServiceError::Http(format!("earnings body read failed: {e}"))
+        request_error("earnings body read failed", e)
     })?;
 
     let parsed: ApiTopLevel = serde_json::from_slice(&raw_bytes).map_err(|e| {
@@ -168,6 +174,14 @@ pub async fn fetch_earnings_range(
     Ok(events)
 }
 
+fn request_error(context: &str, err: reqwest::Error) -> FinanceServiceError {
+    if err.is_timeout() {
+        FinanceServiceError::Timeout(format!("{context}: {err}"))
+    } else {
+        FinanceServiceError::Http(format!("{context}: {err}"))
+    }
+}
+
 fn push_events(
     events: &mut Vec<EarningsEvent>,
     stocks: &[ApiEarning],
@@ -175,7 +189,7 @@ fn push_events(
     fallback_date: NaiveDate,
 ) {
     let parsed_date =
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap_or_else(|_| fallback_date);
+        NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap_or(fallback_date);
 
     for s in stocks {
         events.push(EarningsEvent {
@@ -188,14 +202,29 @@ fn push_events(
                 })),
             date_end: None,
             time_of_day: s.time.clone(),
-            eps_estimate: None,
+            eps_estimate: s.eps_estimate,
             eps_actual: None,
-            revenue_estimate: None,
+            revenue_estimate: s.revenue_estimate,
             revenue_actual: None,
+            market_cap: s.market_cap,
             importance: s.importance,
             title: s.title.clone(),
             emoji: s.emoji.clone(),
             logo: s.logo.clone(), // Now stores logo URL as a string
         });
     }
-}
\ No newline at end of file
+}
+
+/// Numbers from the calendar API may arrive as JSON numbers, numeric strings or null; anything
+/// else is treated as missing rather than failing the whole payload.
+fn lenient_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_f64(),
+        Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
@@ -0,0 +1,71 @@
+//! Consensus estimates for the earnings calendar's entries, looked up once per symbol a day.
+//! The calendar image is rendered for every post and `/weekly-earnings` call, so lookups are
+//! cached in Redis keyed by symbol and day.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use chrono_tz::America::New_York;
+use futures_util::stream::{self, StreamExt};
+use tracing::{debug, info, warn};
+
+use super::FinanceService;
+use crate::models::EarningsEstimate;
+use crate::service::caching::collections::earnings_estimates;
+
+/// Concurrent quoteSummary lookups.
+const LOOKUPS: usize = 8;
+
+impl FinanceService {
+    /// Estimates for `symbols` from today's cache or a quoteSummary lookup. Symbols whose
+    /// lookup fails are left out.
+    pub async fn get_earnings_estimates(
+        &self,
+        symbols: &[String],
+    ) -> HashMap<String, EarningsEstimate> {
+        if symbols.is_empty() {
+            return HashMap::new();
+        }
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+
+        let mut estimates = match &self.cache {
+            Some(cache) => earnings_estimates::load_estimates(cache, today, symbols)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("failed to read cached earnings estimates: {e}");
+                    HashMap::new()
+                }),
+            None => HashMap::new(),
+        };
+        let uncached: Vec<String> = symbols
+            .iter()
+            .filter(|symbol| !estimates.contains_key(*symbol))
+            .cloned()
+            .collect();
+
+        let fetched: HashMap<String, EarningsEstimate> = stream::iter(uncached)
+            .map(|symbol| async move {
+                match self.get_earnings_estimate(&symbol).await {
+                    Ok(estimate) => Some((symbol, estimate)),
+                    Err(e) => {
+                        debug!("no earnings estimate for {symbol}: {e}");
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(LOOKUPS)
+            .filter_map(|found| async move { found })
+            .collect()
+            .await;
+        if !fetched.is_empty() {
+            info!("Loaded earnings estimates for {} symbols", fetched.len());
+        }
+        if let Some(cache) = &self.cache {
+            if let Err(e) = earnings_estimates::save_estimates(cache, today, &fetched).await {
+                warn!("failed to cache earnings estimates: {e}");
+            }
+        }
+        estimates.extend(fetched);
+        estimates
+    }
+}
@@ -0,0 +1,133 @@
+//! ETF profiles from Yahoo's fund modules, cached in Redis for a day, and the reverse lookup
+//! behind `/held-by`: which funds of the configured universe hold a stock.
+//!
+//! Yahoo lists only a fund's ten largest holdings, so a stock is found in the funds where it
+//! is a top-ten position.
+
+use futures_util::stream::{self, StreamExt};
+use tracing::warn;
+
+use super::{FinanceService, FinanceServiceError};
+use crate::models::EtfProfile;
+use crate::service::caching::collections::etf_holdings;
+
+/// Concurrent profile lookups when the universe isn't cached.
+const LOOKUPS: usize = 6;
+
+/// A fund holding the searched stock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundPosition {
+    pub fund: String,
+    pub fund_name: Option<String>,
+    /// Fraction of the fund.
+    pub weight: f64,
+    /// 1 for the fund's largest holding.
+    pub rank: usize,
+}
+
+/// Funds the reverse lookup found the stock in, and the funds that couldn't be checked.
+#[derive(Debug, Clone, Default)]
+pub struct HeldBy {
+    pub positions: Vec<FundPosition>,
+    pub searched: usize,
+    pub failed: Vec<String>,
+}
+
+impl FinanceService {
+    /// Top holdings, sector weights, expense ratio and net assets of an ETF or mutual fund.
+    /// `None` when the symbol is not a fund.
+    pub async fn get_etf_profile(
+        &self,
+        symbol: &str,
+    ) -> Result<Option<EtfProfile>, FinanceServiceError> {
+        if let Some(cache) = &self.cache {
+            match etf_holdings::load_profile(cache, symbol).await {
+                Ok(Some(profile)) => return Ok(Some(profile)),
+                Ok(None) => {}
+                Err(e) => warn!("failed to read cached {symbol} fund profile: {e}"),
+            }
+        }
+        let summary = self
+            .get_quote_summary(
+                symbol,
+                &["price", "summaryDetail", "topHoldings", "fundProfile"],
+            )
+            .await?;
+        let profile = EtfProfile::from_summary(symbol, summary);
+        if let (Some(cache), Some(profile)) = (&self.cache, &profile) {
+            if let Err(e) = etf_holdings::save_profile(cache, profile).await {
+                warn!("failed to cache {symbol} fund profile: {e}");
+            }
+        }
+        Ok(profile)
+    }
+
+    /// Funds in `universe` whose top holdings include `symbol`, largest weight first.
+    pub async fn held_by(&self, symbol: &str, universe: &[String]) -> HeldBy {
+        let results: Vec<(String, Option<EtfProfile>)> = stream::iter(universe.to_vec())
+            .map(|fund| async move {
+                let profile = match self.get_etf_profile(&fund).await {
+                    Ok(Some(profile)) => Some(profile),
+                    Ok(None) => {
+                        warn!("held-by: {fund} is not a fund");
+                        None
+                    }
+                    Err(e) => {
+                        warn!("held-by: {fund} holdings failed: {e}");
+                        None
+                    }
+                };
+                (fund, profile)
+            })
+            .buffer_unordered(LOOKUPS)
+            .collect()
+            .await;
+
+        let mut failed = Vec::new();
+        let mut profiles = Vec::new();
+        for (fund, profile) in results {
+            match profile {
+                Some(profile) => profiles.push(profile),
+                None => failed.push(fund),
+            }
+        }
+        failed.sort();
+        HeldBy {
+            positions: positions(symbol, &profiles),
+            searched: universe.len(),
+            failed,
+        }
+    }
+}
+
+/// Where `symbol` sits in each fund's holdings, largest weight first. Share classes match
+/// whether written `BRK.B` or `BRK-B`.
+pub fn positions(symbol: &str, profiles: &[EtfProfile]) -> Vec<FundPosition> {
+    let wanted = normalize(symbol);
+    let mut found: Vec<FundPosition> = profiles
+        .iter()
+        .filter_map(|profile| {
+            let (index, holding) = profile
+                .holdings
+                .iter()
+                .enumerate()
+                .find(|(_, h)| h.symbol.as_deref().is_some_and(|s| normalize(s) == wanted))?;
+            Some(FundPosition {
+                fund: profile.symbol.clone(),
+                fund_name: profile.name.clone(),
+                weight: holding.weight,
+                rank: index + 1,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        b.weight
+            .total_cmp(&a.weight)
+            .then_with(|| a.fund.cmp(&b.fund))
+    });
+    found
+}
+
+fn normalize(symbol: &str) -> String {
+    symbol.trim().to_ascii_uppercase().replace('.', "-")
+}
@@ -0,0 +1,235 @@
+//! Exchange metadata keyed by Yahoo ticker suffix (`RY.TO`, `SAP.DE`, `7203.T`).
+//!
+//! Symbols without a known suffix are treated as US listings.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+#[derive(Debug)]
+pub struct Exchange {
+    /// Yahoo suffix without the dot; empty for US listings.
+    pub suffix: &'static str,
+    pub name: &'static str,
+    /// Quote currency as Yahoo reports it (`GBp` is pence).
+    pub currency: &'static str,
+    pub timezone: Tz,
+    /// Regular session in local time as `(hour, minute)`; lunch breaks are ignored.
+    pub open: (u32, u32),
+    pub close: (u32, u32),
+    /// Whether Yahoo serves option chains for this market.
+    pub has_options: bool,
+}
+
+const fn exchange(
+    suffix: &'static str,
+    name: &'static str,
+    currency: &'static str,
+    timezone: Tz,
+    open: (u32, u32),
+    close: (u32, u32),
+) -> Exchange {
+    Exchange {
+        suffix,
+        name,
+        currency,
+        timezone,
+        open,
+        close,
+        has_options: false,
+    }
+}
+
+pub static US: Exchange = Exchange {
+    suffix: "",
+    name: "US markets",
+    currency: "USD",
+    timezone: Tz::America__New_York,
+    open: (9, 30),
+    close: (16, 0),
+    has_options: true,
+};
+
+#[rustfmt::skip]
+static EXCHANGES: [Exchange; 19] = [
+    exchange("TO", "Toronto Stock Exchange", "CAD", Tz::America__Toronto, (9, 30), (16, 0)),
+    exchange("V", "TSX Venture Exchange", "CAD", Tz::America__Toronto, (9, 30), (16, 0)),
+    exchange("L", "London Stock Exchange", "GBp", Tz::Europe__London, (8, 0), (16, 30)),
+    exchange("DE", "XETRA", "EUR", Tz::Europe__Berlin, (9, 0), (17, 30)),
+    exchange("F", "Frankfurt Stock Exchange", "EUR", Tz::Europe__Berlin, (8, 0), (22, 0)),
+    exchange("PA", "Euronext Paris", "EUR", Tz::Europe__Paris, (9, 0), (17, 30)),
+    exchange("AS", "Euronext Amsterdam", "EUR", Tz::Europe__Amsterdam, (9, 0), (17, 30)),
+    exchange("MI", "Borsa Italiana", "EUR", Tz::Europe__Rome, (9, 0), (17, 30)),
+    exchange("MC", "Bolsa de Madrid", "EUR", Tz::Europe__Madrid, (9, 0), (17, 30)),
+    exchange("SW", "SIX Swiss Exchange", "CHF", Tz::Europe__Zurich, (9, 0), (17, 30)),
+    exchange("T", "Tokyo Stock Exchange", "JPY", Tz::Asia__Tokyo, (9, 0), (15, 30)),
+    exchange("HK", "Hong Kong Stock Exchange", "HKD", Tz::Asia__Hong_Kong, (9, 30), (16, 0)),
+    exchange("SS", "Shanghai Stock Exchange", "CNY", Tz::Asia__Shanghai, (9, 30), (15, 0)),
+    exchange("SZ", "Shenzhen Stock Exchange", "CNY", Tz::Asia__Shanghai, (9, 30), (15, 0)),
+    exchange("KS", "Korea Exchange", "KRW", Tz::Asia__Seoul, (9, 0), (15, 30)),
+    exchange("AX", "Australian Securities Exchange", "AUD", Tz::Australia__Sydney, (10, 0), (16, 0)),
+    exchange("NS", "National Stock Exchange of India", "INR", Tz::Asia__Kolkata, (9, 15), (15, 30)),
+    exchange("BO", "BSE India", "INR", Tz::Asia__Kolkata, (9, 15), (15, 30)),
+    exchange("SA", "B3 (Brazil)", "BRL", Tz::America__Sao_Paulo, (10, 0), (17, 0)),
+];
+
+/// Exchange codes people commonly type, mapped to the Yahoo suffix. Used both as
+/// suffixes (`RY.TSX`) and as prefixes (`TSX:RY`).
+const ALIASES: [(&str, &str); 28] = [
+    ("TSX", "TO"),
+    ("TSE", "TO"),
+    ("TSXV", "V"),
+    ("CVE", "V"),
+    ("LSE", "L"),
+    ("LON", "L"),
+    ("LN", "L"),
+    ("XETRA", "DE"),
+    ("ETR", "DE"),
+    ("GR", "DE"),
+    ("FRA", "F"),
+    ("EPA", "PA"),
+    ("FP", "PA"),
+    ("AMS", "AS"),
+    ("NA", "AS"),
+    ("BIT", "MI"),
+    ("IM", "MI"),
+    ("SM", "MC"),
+    ("SWX", "SW"),
+    ("VX", "SW"),
+    ("TYO", "T"),
+    ("JP", "T"),
+    ("HKG", "HK"),
+    ("KRX", "KS"),
+    ("ASX", "AX"),
+    ("AU", "AX"),
+    ("NSE", "NS"),
+    ("BSE", "BO"),
+];
+
+/// Prefixes that name a US venue; `NASDAQ:AAPL` is just `AAPL` to Yahoo.
+const US_PREFIXES: [&str; 5] = ["NASDAQ", "NYSE", "NYSEARCA", "AMEX", "BATS"];
+
+impl Exchange {
+    /// The exchange a Yahoo symbol trades on, defaulting to [`US`].
+    pub fn for_symbol(symbol: &str) -> &'static Exchange {
+        symbol
+            .rsplit_once('.')
+            .and_then(|(_, suffix)| find(suffix))
+            .unwrap_or(&US)
+    }
+
+    /// Whether the regular session is running at `now`. Exchange holidays are not tracked.
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.timezone);
+        let time = local.time();
+        is_weekday(local.weekday()) && time >= self.open_time() && time < self.close_time()
+    }
+
+    /// Short session summary, e.g. `open until 16:00 EDT` or `closed (opens Mon 09:00 JST)`.
+    /// Times are shown in `display`, or in the exchange's own timezone when `None`.
+    pub fn session_status(&self, now: DateTime<Utc>, display: Option<Tz>) -> String {
+        let display = display.unwrap_or(self.timezone);
+        if self.is_open(now) {
+            let close = now
+                .with_timezone(&self.timezone)
+                .date_naive()
+                .and_time(self.close_time());
+            return match self.timezone.from_local_datetime(&close).earliest() {
+                Some(close) => format!(
+                    "open until {}",
+                    close.with_timezone(&display).format("%H:%M %Z")
+                ),
+                None => "open".to_string(),
+            };
+        }
+        match self.next_open(now) {
+            Some(open) => format!(
+                "closed (opens {})",
+                open.with_timezone(&display).format("%a %H:%M %Z")
+            ),
+            None => "closed".to_string(),
+        }
+    }
+
+    fn next_open(&self, now: DateTime<Utc>) -> Option<DateTime<Tz>> {
+        let today = now.with_timezone(&self.timezone).date_naive();
+        (0..8)
+            .map(|offset| today + Duration::days(offset))
+            .filter(|day| is_weekday(day.weekday()))
+            .filter_map(|day| {
+                self.timezone
+                    .from_local_datetime(&day.and_time(self.open_time()))
+                    .earliest()
+            })
+            .find(|open| open.with_timezone(&Utc) > now)
+    }
+
+    fn open_time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.open.0, self.open.1, 0).unwrap_or(NaiveTime::MIN)
+    }
+
+    fn close_time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.close.0, self.close.1, 0).unwrap_or(NaiveTime::MIN)
+    }
+}
+
+/// Rewrite common exchange notations into Yahoo's suffix form.
+///
+/// `TSX:RY`, `RY.TSX` and `ry.to` all become `RY.TO`; `NASDAQ:AAPL` becomes `AAPL`; Hong Kong
+/// codes are zero-padded (`700.HK` → `0700.HK`). Anything that does not look like a
+/// ticker with an exchange code (company names, `BRK.B`) is returned trimmed but otherwise
+/// unchanged.
+pub fn normalize_symbol(input: &str) -> String {
+    let input = input.trim();
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return input.to_string();
+    }
+
+    if let Some((prefix, ticker)) = input.split_once(':') {
+        let prefix = prefix.to_uppercase();
+        if US_PREFIXES.contains(&prefix.as_str()) {
+            return ticker.to_uppercase();
+        }
+        if let Some(suffix) = canonical_suffix(&prefix) {
+            return with_suffix(ticker, suffix);
+        }
+        return input.to_string();
+    }
+
+    match input.rsplit_once('.') {
+        Some((ticker, code)) if !ticker.is_empty() => {
+            match canonical_suffix(&code.to_uppercase()) {
+                Some(suffix) => with_suffix(ticker, suffix),
+                None => input.to_string(),
+            }
+        }
+        _ => input.to_string(),
+    }
+}
+
+fn canonical_suffix(code: &str) -> Option<&'static str> {
+    if let Some(exchange) = find(code) {
+        return Some(exchange.suffix);
+    }
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == code)
+        .map(|(_, suffix)| *suffix)
+}
+
+fn with_suffix(ticker: &str, suffix: &str) -> String {
+    let ticker = ticker.to_uppercase();
+    if suffix == "HK" && !ticker.is_empty() && ticker.chars().all(|c| c.is_ascii_digit()) {
+        return format!("{ticker:0>4}.{suffix}");
+    }
+    format!("{ticker}.{suffix}")
+}
+
+fn find(suffix: &str) -> Option<&'static Exchange> {
+    EXCHANGES
+        .iter()
+        .find(|exchange| exchange.suffix.eq_ignore_ascii_case(suffix))
+}
+
+fn is_weekday(day: Weekday) -> bool {
+    !matches!(day, Weekday::Sat | Weekday::Sun)
+}
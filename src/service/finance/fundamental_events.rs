@@ -0,0 +1,130 @@
+//! Alerts on data rather than price: a symbol's next earnings date being scheduled or moved,
+//! and its mean analyst price target moving by more than a threshold. Each alert keeps the
+//! snapshot it last compared against, and the daily check diffs a fresh one with it.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{FinanceService, FinanceServiceError};
+
+/// Target move that fires a `target` alert when none is given.
+pub const DEFAULT_TARGET_THRESHOLD: f64 = 0.05;
+
+/// What an alert watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// The next earnings date appears or changes.
+    EarningsDate,
+    /// The mean analyst price target moves by at least the alert's threshold.
+    TargetPrice,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 2] = [EventKind::EarningsDate, EventKind::TargetPrice];
+
+    /// Option value, as picked in Discord.
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::EarningsDate => "earnings_date",
+            EventKind::TargetPrice => "target",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventKind::EarningsDate => "Next earnings date",
+            EventKind::TargetPrice => "Analyst mean target",
+        }
+    }
+}
+
+/// The watched values of one symbol at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventSnapshot {
+    /// Next report date; the start of the window while it is unconfirmed.
+    pub earnings_date: Option<NaiveDate>,
+    pub target_mean: Option<f64>,
+}
+
+/// A change worth posting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventChange {
+    /// A date appeared where there was none, or the previous report has passed.
+    EarningsScheduled {
+        date: NaiveDate,
+    },
+    EarningsMoved {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    TargetMoved {
+        from: f64,
+        to: f64,
+    },
+}
+
+/// Compare `current` with the snapshot an alert last saw. Returns the change, if any, and the
+/// snapshot the alert keeps for the next check: earnings dates follow every run, while a
+/// target baseline only moves when it fires, so a slow drift still adds up to the threshold.
+/// Values Yahoo stopped sending keep their last known value.
+pub fn evaluate(
+    kind: EventKind,
+    threshold: f64,
+    seen: &EventSnapshot,
+    current: &EventSnapshot,
+    today: NaiveDate,
+) -> (Option<EventChange>, EventSnapshot) {
+    let mut next = seen.clone();
+    let change = match kind {
+        EventKind::EarningsDate => {
+            let Some(to) = current.earnings_date else {
+                return (None, next);
+            };
+            next.earnings_date = Some(to);
+            match seen.earnings_date {
+                None => Some(EventChange::EarningsScheduled { date: to }),
+                Some(from) if from == to => None,
+                Some(from) if from < today => Some(EventChange::EarningsScheduled { date: to }),
+                Some(from) => Some(EventChange::EarningsMoved { from, to }),
+            }
+        }
+        EventKind::TargetPrice => {
+            let Some(to) = current.target_mean else {
+                return (None, next);
+            };
+            match seen.target_mean.filter(|from| *from > 0.0) {
+                None => {
+                    next.target_mean = Some(to);
+                    None
+                }
+                Some(from) if (to / from - 1.0).abs() >= threshold => {
+                    next.target_mean = Some(to);
+                    Some(EventChange::TargetMoved { from, to })
+                }
+                Some(_) => None,
+            }
+        }
+    };
+    (change, next)
+}
+
+impl FinanceService {
+    /// The next earnings date and mean analyst target from `quoteSummary`.
+    pub async fn event_snapshot(&self, symbol: &str) -> Result<EventSnapshot, FinanceServiceError> {
+        let summary = self
+            .get_quote_summary(symbol, &["calendarEvents", "financialData"])
+            .await?;
+        Ok(EventSnapshot {
+            earnings_date: summary
+                .calendar_events
+                .and_then(|c| c.earnings)
+                .and_then(|e| e.earnings_date.first().copied()),
+            target_mean: summary.financial_data.and_then(|f| f.target_mean_price),
+        })
+    }
+}
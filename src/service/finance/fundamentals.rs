@@ -1,4 +1,4 @@
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use finance_query_core::{
     utils::{
         financials_constants::{BALANCE_SHEET_FIELDS, CASH_FLOW_FIELDS, INCOME_STATEMENT_FIELDS},
@@ -15,6 +15,10 @@ use crate::models::{Frequency, StatementType};
 /// Default lookback for fundamentals queries (in years).
 pub const FETCH_YEARS_DEFAULT: i64 = 5;
 
+/// Longest span from the first to the last of four quarter ends that still counts as four
+/// consecutive quarters (three quarters is about 273 days; a missing one makes it ~365).
+const TTM_MAX_SPAN_DAYS: i64 = 300;
+
 /// Fetch fundamentals timeseries data for a symbol using finance-query-core.
 ///
 /// This builds the correct `type` list from StatementType/Frequency and queries
@@ -29,7 +33,7 @@ pub async fn fetch_fundamentals_timeseries(
     let now = Utc::now().timestamp();
     let start = now - Duration::days(365 * years_back).num_seconds();
 
-    let fields = get_statement_fields(statement_type.as_str(), frequency.as_str());
+    let fields = get_statement_fields(statement_type.as_str(), frequency.fetched().as_str());
     let refs: Vec<&str> = fields.iter().map(String::as_str).collect();
 
     client
@@ -39,7 +43,8 @@ pub async fn fetch_fundamentals_timeseries(
 
 /// Reshape the raw finance-query-core fundamentals timeseries payload into our
 /// `FinancialStatement` model. Groups metrics by statement type and frequency,
-/// and indexes each metric's values by `asOfDate`.
+/// and indexes each metric's values by `asOfDate`. Quarterly income and cash-flow
+/// statements also get a `ttm` counterpart (see [`trailing_twelve_months`]).
 pub fn reshape_timeseries_to_financial_statements(data: &Value) -> Vec<FinancialStatement> {
     let income: HashSet<&'static str> = INCOME_STATEMENT_FIELDS.iter().copied().collect();
     let balance: HashSet<&'static str> = BALANCE_SHEET_FIELDS.iter().copied().collect();
@@ -141,5 +146,77 @@ pub fn reshape_timeseries_to_financial_statements(data: &Value) -> Vec<Financial
         }
     }
 
-    grouped.into_values().collect()
+    let mut statements: Vec<FinancialStatement> = grouped.into_values().collect();
+    let ttm: Vec<FinancialStatement> = statements
+        .iter()
+        .filter_map(trailing_twelve_months)
+        .collect();
+    statements.extend(ttm);
+    statements
+}
+
+/// The trailing-twelve-month statement for a quarterly income or cash-flow statement: each
+/// flow metric summed over every run of four consecutive quarters, dated by the last one.
+/// Share counts and rates don't add up and are left out; balance sheets are point-in-time
+/// and have no TTM form.
+pub fn trailing_twelve_months(quarterly: &FinancialStatement) -> Option<FinancialStatement> {
+    if quarterly.frequency != Frequency::Quarterly.as_str()
+        || quarterly.statement_type == StatementType::BalanceSheet.as_str()
+    {
+        return None;
+    }
+
+    let statement: HashMap<String, HashMap<String, Value>> = quarterly
+        .statement
+        .iter()
+        .filter(|(metric, _)| is_flow(metric))
+        .map(|(metric, series)| (metric.clone(), trailing_sums(series)))
+        .filter(|(_, series)| !series.is_empty())
+        .collect();
+
+    Some(FinancialStatement {
+        symbol: quarterly.symbol.clone(),
+        statement_type: quarterly.statement_type.clone(),
+        frequency: Frequency::Ttm.as_str().to_string(),
+        statement,
+    })
+}
+
+/// Whether a metric accumulates over a period, so four quarters sum to a year.
+fn is_flow(metric: &str) -> bool {
+    !(metric.contains("Shares") || metric.contains("ShareIssued") || metric.contains("TaxRate"))
+}
+
+fn trailing_sums(series: &HashMap<String, Value>) -> HashMap<String, Value> {
+    let mut quarters: Vec<(NaiveDate, &Value, f64)> = series
+        .iter()
+        .filter_map(|(date, item)| {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            let raw = item
+                .get("reportedValue")
+                .and_then(|rv| rv.get("raw"))
+                .and_then(Value::as_f64)?;
+            Some((date, item, raw))
+        })
+        .collect();
+    quarters.sort_by_key(|(date, _, _)| *date);
+
+    quarters
+        .windows(4)
+        .filter(|window| (window[3].0 - window[0].0).num_days() <= TTM_MAX_SPAN_DAYS)
+        .map(|window| {
+            let (end, latest, _) = window[3];
+            let sum: f64 = window.iter().map(|(_, _, raw)| raw).sum();
+            let date = end.format("%Y-%m-%d").to_string();
+            let mut item = serde_json::json!({
+                "asOfDate": date,
+                "periodType": "TTM",
+                "reportedValue": { "raw": sum },
+            });
+            if let Some(currency) = latest.get("currencyCode") {
+                item["currencyCode"] = currency.clone();
+            }
+            (date, item)
+        })
+        .collect()
 }
@@ -0,0 +1,102 @@
+//! Currency conversion using Yahoo's live FX pairs (`JPYUSD=X`).
+
+use super::{FinanceService, FinanceServiceError};
+
+/// A rate for turning amounts in `from` into `to`.
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+}
+
+impl Conversion {
+    /// Leave amounts as reported.
+    pub fn identity(currency: &str) -> Self {
+        Self {
+            from: currency.to_string(),
+            to: currency.to_string(),
+            rate: 1.0,
+        }
+    }
+
+    pub fn apply(&self, amount: f64) -> f64 {
+        amount * self.rate
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Footnote for converted output, e.g. `converted from JPY at 1 JPY = 0.006712 USD`.
+    pub fn note(&self) -> Option<String> {
+        if self.is_identity() {
+            return None;
+        }
+        Some(format!(
+            "converted from {} at 1 {} = {} {}",
+            self.from,
+            self.from,
+            format_rate(self.rate),
+            self.to
+        ))
+    }
+}
+
+/// Validate a user-supplied ISO currency code (`usd` → `USD`).
+pub fn parse_currency(input: &str) -> Option<String> {
+    let code = input.trim();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some(code.to_uppercase())
+    } else {
+        None
+    }
+}
+
+/// Yahoo quotes some markets in minor units (London in pence as `GBp`); FX pairs only
+/// exist for the major currency.
+fn major_unit(currency: &str) -> (&str, f64) {
+    match currency {
+        "GBp" | "GBX" => ("GBP", 0.01),
+        "ZAc" | "ZAC" => ("ZAR", 0.01),
+        "ILA" => ("ILS", 0.01),
+        other => (other, 1.0),
+    }
+}
+
+fn format_rate(rate: f64) -> String {
+    if rate >= 100.0 {
+        format!("{rate:.2}")
+    } else {
+        format!("{rate:.6}")
+    }
+}
+
+impl FinanceService {
+    /// Live conversion rate between two currencies. Same-currency pairs skip the lookup.
+    pub async fn conversion(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Conversion, FinanceServiceError> {
+        let (from_major, from_scale) = major_unit(from);
+        let (to_major, to_scale) = major_unit(to);
+
+        let rate = if from_major == to_major {
+            1.0
+        } else {
+            let pair = format!("{from_major}{to_major}=X");
+            self.get_price(&pair)
+                .await?
+                .price
+                .filter(|price| *price > 0.0)
+                .ok_or(FinanceServiceError::NotFound(pair))?
+        };
+
+        Ok(Conversion {
+            from: from.to_string(),
+            to: to.to_string(),
+            rate: rate * from_scale / to_scale,
+        })
+    }
+}
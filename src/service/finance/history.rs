@@ -0,0 +1,56 @@
+//! Daily closing prices from Yahoo's chart endpoint.
+
+use chrono::{DateTime, NaiveDate};
+use serde_json::Value;
+
+use super::{flight_key, FinanceService, FinanceServiceError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyClose {
+    pub date: NaiveDate,
+    pub close: f64,
+}
+
+impl FinanceService {
+    /// Daily closes over a Yahoo chart range such as `3mo` or `1y`, oldest first.
+    pub async fn daily_closes(
+        &self,
+        symbol: &str,
+        range: &str,
+    ) -> Result<Vec<DailyClose>, FinanceServiceError> {
+        let data = self
+            .json_flights
+            .run(flight_key("chart", symbol, range), || {
+                self.resilience
+                    .call("chart", || self.client.get_chart(symbol, "1d", range))
+            })
+            .await?;
+        let closes = parse_chart(&data);
+        if closes.is_empty() {
+            return Err(FinanceServiceError::NotFound(symbol.to_string()));
+        }
+        Ok(closes)
+    }
+}
+
+/// `chart.result[0]`: `timestamp[i]` pairs with `indicators.quote[0].close[i]`, which is null
+/// for days without a trade.
+fn parse_chart(data: &Value) -> Vec<DailyClose> {
+    let result = &data["chart"]["result"][0];
+    let timestamps = result["timestamp"].as_array().cloned().unwrap_or_default();
+    let closes = result["indicators"]["quote"][0]["close"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    timestamps
+        .iter()
+        .zip(closes.iter())
+        .filter_map(|(ts, close)| {
+            let date = DateTime::from_timestamp(ts.as_i64()?, 0)?.date_naive();
+            Some(DailyClose {
+                date,
+                close: close.as_f64()?,
+            })
+        })
+        .collect()
+}
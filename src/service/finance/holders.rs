@@ -231,7 +231,25 @@ fn parse_insider_transactions(result: &Value) -> Option<Vec<InsiderTransaction>>
 }
 
 fn parse_insider_purchases(txs: Option<Vec<InsiderTransaction>>) -> Option<InsiderPurchase> {
-    let txs = txs?;
+    summarize_purchases(&txs?, "recent")
+}
+
+/// Purchases and sales among `txs` reported on or after `since`, as an [`InsiderPurchase`]
+/// labeled `period`; `None` when there are none in the window.
+pub fn insider_purchases_since(
+    txs: &[InsiderTransaction],
+    since: DateTime<Utc>,
+    period: &str,
+) -> Option<InsiderPurchase> {
+    let recent: Vec<InsiderTransaction> = txs
+        .iter()
+        .filter(|tx| tx.start_date >= since)
+        .cloned()
+        .collect();
+    summarize_purchases(&recent, period)
+}
+
+fn summarize_purchases(txs: &[InsiderTransaction], period: &str) -> Option<InsiderPurchase> {
     if txs.is_empty() {
         return None;
     }
@@ -241,7 +259,7 @@ fn parse_insider_purchases(txs: Option<Vec<InsiderTransaction>>) -> Option<Insid
     let mut sales_shares: i64 = 0;
     let mut sales_tx = 0;
 
-    for tx in &txs {
+    for tx in txs {
         let text = tx.transaction.to_lowercase();
         let shares = tx.shares.unwrap_or(0);
         if text.contains("buy") || text.contains("purchase") {
@@ -254,7 +272,7 @@ fn parse_insider_purchases(txs: Option<Vec<InsiderTransaction>>) -> Option<Insid
     }
 
     Some(InsiderPurchase {
-        period: "recent".to_string(),
+        period: period.to_string(),
         purchases_shares: Some(purchases_shares),
         purchases_transactions: Some(purchases_tx),
         sales_shares: Some(sales_shares),
@@ -0,0 +1,116 @@
+//! Importance (1-5) for earnings events the calendar API sent without one, scored from market
+//! cap and average volume so featured cards and ordering still work. Scores are cached in Redis
+//! for a week.
+
+use std::collections::{BTreeSet, HashMap};
+
+use futures_util::stream::{self, StreamExt};
+use tracing::{debug, info, warn};
+
+use super::FinanceService;
+use crate::models::EarningsEvent;
+use crate::service::caching::collections::earnings_importance;
+
+/// Market cap floors, largest first, and the points each adds to the base score of 1.
+const MARKET_CAP_TIERS: [(f64, i64); 3] = [(200e9, 3), (10e9, 2), (2e9, 1)];
+/// Average daily volume, in shares, that adds a point: the names traders watch most.
+const HEAVY_VOLUME: f64 = 10_000_000.0;
+/// Concurrent quote lookups.
+const LOOKUPS: usize = 8;
+/// Most quote lookups per calendar load; the rest, smallest by the API's market cap, are scored
+/// from that alone.
+const MAX_LOOKUPS: usize = 100;
+
+/// Importance from 1 (small, thinly traded) to 5 (mega cap with heavy volume), or `None` when
+/// neither figure is known.
+pub fn score(market_cap: Option<f64>, average_volume: Option<f64>) -> Option<i64> {
+    if market_cap.is_none() && average_volume.is_none() {
+        return None;
+    }
+    let cap_points = market_cap
+        .and_then(|cap| MARKET_CAP_TIERS.iter().find(|(floor, _)| cap >= *floor))
+        .map_or(0, |(_, points)| *points);
+    let volume_points = i64::from(average_volume.is_some_and(|volume| volume >= HEAVY_VOLUME));
+    Some((1 + cap_points + volume_points).min(5))
+}
+
+impl FinanceService {
+    /// Fill `importance` where the calendar API left it out, from cached scores or a quote
+    /// lookup. Events that can't be scored keep `None`.
+    pub async fn fill_importance(&self, events: &mut [EarningsEvent]) {
+        let missing: BTreeSet<String> = events
+            .iter()
+            .filter(|ev| ev.importance.is_none())
+            .map(|ev| ev.symbol.clone())
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        let missing: Vec<String> = missing.into_iter().collect();
+
+        let mut scores = match &self.cache {
+            Some(cache) => earnings_importance::load_scores(cache, &missing)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("failed to read cached earnings importance: {e}");
+                    HashMap::new()
+                }),
+            None => HashMap::new(),
+        };
+
+        let api_caps: HashMap<&str, f64> = events
+            .iter()
+            .filter_map(|ev| Some((ev.symbol.as_str(), ev.market_cap?)))
+            .collect();
+        let mut uncached: Vec<String> = missing
+            .into_iter()
+            .filter(|symbol| !scores.contains_key(symbol))
+            .collect();
+        uncached.sort_by(|a, b| {
+            let cap = |symbol: &str| api_caps.get(symbol).copied().unwrap_or(0.0);
+            cap(b).total_cmp(&cap(a))
+        });
+        uncached.truncate(MAX_LOOKUPS);
+
+        let computed: HashMap<String, i64> = stream::iter(uncached)
+            .map(|symbol| async move {
+                match self
+                    .get_quote_summary(&symbol, &["price", "summaryDetail"])
+                    .await
+                {
+                    Ok(summary) => {
+                        let detail = summary.summary_detail.unwrap_or_default();
+                        let market_cap = summary
+                            .price
+                            .and_then(|p| p.market_cap)
+                            .or(detail.market_cap);
+                        score(market_cap, detail.average_volume).map(|s| (symbol, s))
+                    }
+                    Err(e) => {
+                        debug!("no quote to score {symbol} earnings importance: {e}");
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(LOOKUPS)
+            .filter_map(|scored| async move { scored })
+            .collect()
+            .await;
+        if !computed.is_empty() {
+            info!("Scored earnings importance for {} symbols", computed.len());
+        }
+        if let Some(cache) = &self.cache {
+            if let Err(e) = earnings_importance::save_scores(cache, &computed).await {
+                warn!("failed to cache earnings importance: {e}");
+            }
+        }
+        scores.extend(computed);
+
+        for ev in events.iter_mut().filter(|ev| ev.importance.is_none()) {
+            ev.importance = scores
+                .get(&ev.symbol)
+                .copied()
+                .or_else(|| score(ev.market_cap, None));
+        }
+    }
+}
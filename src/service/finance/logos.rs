@@ -0,0 +1,217 @@
+//! Company logos for the earnings calendar image, tried provider by provider: the URL the
+//! calendar API sent, Financial Modeling Prep's symbol images, then Clearbit by the company's
+//! website domain from Yahoo `assetProfile`. Names no provider has are drawn with their
+//! initials by the renderer.
+//!
+//! A provider that answers "no such logo" is remembered in Redis for a week, so repeat renders
+//! skip it; timeouts and server errors are retried next time.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures_util::stream::{self, StreamExt};
+use image::DynamicImage;
+use reqwest::{StatusCode, Url};
+use tracing::{debug, info, warn};
+
+use super::FinanceService;
+use crate::service::caching::collections::missing_logos;
+
+/// Per logo request; a slow logo only costs its card the initials.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+/// Concurrent logo requests.
+const LOOKUPS: usize = 8;
+
+/// Where a logo can come from, in the order they are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogoProvider {
+    /// The URL the earnings calendar API sent with the event.
+    Calendar,
+    Fmp,
+    Clearbit,
+}
+
+impl LogoProvider {
+    pub const CHAIN: [LogoProvider; 3] = [
+        LogoProvider::Calendar,
+        LogoProvider::Fmp,
+        LogoProvider::Clearbit,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogoProvider::Calendar => "calendar",
+            LogoProvider::Fmp => "fmp",
+            LogoProvider::Clearbit => "clearbit",
+        }
+    }
+}
+
+/// A company whose logo is wanted, with the calendar API's logo URL if it sent one.
+#[derive(Debug, Clone)]
+pub struct LogoRequest {
+    pub symbol: String,
+    pub calendar_url: Option<String>,
+}
+
+/// Why a provider gave no logo.
+enum Miss {
+    /// The provider has none; remembered so it isn't asked again for a while.
+    Missing,
+    /// Timeout, server error or similar; asked again on the next render.
+    Failed(String),
+}
+
+pub fn fmp_url(symbol: &str) -> String {
+    format!(
+        "https://financialmodelingprep.com/image-stock/{}.png",
+        symbol.to_uppercase()
+    )
+}
+
+pub fn clearbit_url(domain: &str) -> String {
+    format!("https://logo.clearbit.com/{domain}")
+}
+
+/// The bare domain of a company website: `https://www.apple.com/` → `apple.com`.
+pub fn website_domain(website: &str) -> Option<String> {
+    let website = website.trim();
+    let url = if website.contains("://") {
+        Url::parse(website)
+    } else {
+        Url::parse(&format!("https://{website}"))
+    }
+    .ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    host.contains('.').then(|| host.to_string())
+}
+
+impl FinanceService {
+    /// Logos for `requests` by symbol, from the first provider that has one. Symbols missing
+    /// from the result have no logo anywhere, or every provider failed this time.
+    pub async fn company_logos(&self, requests: &[LogoRequest]) -> HashMap<String, DynamicImage> {
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let mut logos = HashMap::new();
+        let mut pending: Vec<&LogoRequest> = requests.iter().collect();
+
+        for provider in LogoProvider::CHAIN {
+            let candidates: Vec<String> = pending
+                .iter()
+                .filter(|request| {
+                    provider != LogoProvider::Calendar || request.calendar_url.is_some()
+                })
+                .map(|request| request.symbol.clone())
+                .collect();
+            let known_missing = match &self.cache {
+                Some(cache) => missing_logos::load_missing(cache, provider.name(), &candidates)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("failed to read missing {} logos: {e}", provider.name());
+                        Default::default()
+                    }),
+                None => Default::default(),
+            };
+            let lookups: Vec<LogoRequest> = pending
+                .iter()
+                .filter(|request| {
+                    candidates.contains(&request.symbol) && !known_missing.contains(&request.symbol)
+                })
+                .map(|request| (*request).clone())
+                .collect();
+
+            let results: Vec<(String, Result<DynamicImage, Miss>)> = stream::iter(lookups)
+                .map(|request| {
+                    let client = client.clone();
+                    async move {
+                        let logo = self.fetch_logo(&client, provider, &request).await;
+                        (request.symbol, logo)
+                    }
+                })
+                .buffer_unordered(LOOKUPS)
+                .collect()
+                .await;
+
+            let mut missing = Vec::new();
+            for (symbol, result) in results {
+                match result {
+                    Ok(logo) => {
+                        logos.insert(symbol, logo);
+                    }
+                    Err(Miss::Missing) => missing.push(symbol),
+                    Err(Miss::Failed(e)) => {
+                        debug!("{} logo for {symbol} failed: {e}", provider.name());
+                    }
+                }
+            }
+            if let Some(cache) = &self.cache {
+                if let Err(e) = missing_logos::mark_missing(cache, provider.name(), &missing).await
+                {
+                    warn!("failed to remember missing {} logos: {e}", provider.name());
+                }
+            }
+            pending.retain(|request| !logos.contains_key(&request.symbol));
+            if pending.is_empty() {
+                break;
+            }
+        }
+
+        info!(
+            "Loaded {} of {} logos; {} left to initials",
+            logos.len(),
+            requests.len(),
+            pending.len()
+        );
+        logos
+    }
+
+    async fn fetch_logo(
+        &self,
+        client: &reqwest::Client,
+        provider: LogoProvider,
+        request: &LogoRequest,
+    ) -> Result<DynamicImage, Miss> {
+        let url = match provider {
+            LogoProvider::Calendar => request.calendar_url.clone().ok_or(Miss::Missing)?,
+            LogoProvider::Fmp => fmp_url(&request.symbol),
+            LogoProvider::Clearbit => {
+                let summary = self
+                    .get_quote_summary(&request.symbol, &["assetProfile"])
+                    .await
+                    .map_err(|e| Miss::Failed(format!("asset profile: {e}")))?;
+                let domain = summary
+                    .asset_profile
+                    .and_then(|profile| profile.website)
+                    .and_then(|website| website_domain(&website))
+                    .ok_or(Miss::Missing)?;
+                clearbit_url(&domain)
+            }
+        };
+        download(client, &url).await
+    }
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<DynamicImage, Miss> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(Miss::Missing);
+    }
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Miss::Failed(format!("request failed: {e}")))?;
+    match resp.status() {
+        status if status.is_success() => {}
+        StatusCode::NOT_FOUND | StatusCode::GONE => return Err(Miss::Missing),
+        status => return Err(Miss::Failed(format!("HTTP {status}"))),
+    }
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| Miss::Failed(format!("failed to read bytes: {e}")))?;
+    // A provider serving something other than an image has nothing usable for the symbol.
+    image::load_from_memory(&bytes).map_err(|_| Miss::Missing)
+}
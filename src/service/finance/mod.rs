@@ -1,27 +1,104 @@
 use std::sync::Arc;
 
+use chrono::Utc;
 use finance_query_core::{FetchClient, YahooAuthManager, YahooError, YahooFinanceClient};
+use futures_util::future::join_all;
 use serde_json::Value;
+use tracing::warn;
 
 use crate::models::{
-    EarningsEvent, FinancialSummary, Frequency, HolderType, HoldersOverview, NewsItem, PriceQuote,
-    StatementType,
+    EarningsEstimate, EarningsEvent, EarningsRange, FinancialSummary, Frequency, HolderType,
+    HoldersOverview, NewsItem, PriceQuote, QuoteSummary, StatementType, SymbolMatch,
 };
-
+use crate::service::caching::collections::{earnings_calendar, news_seen};
+use crate::service::caching::RedisCache;
+use crate::service::config::ConstituentsConfig;
+
+pub mod article_preview;
+pub mod backtest;
+pub mod constituents;
+pub mod corporate_actions;
+pub mod dcf;
 pub mod earnings;
+pub mod estimates;
+pub mod etf;
+pub mod exchange;
+pub mod fundamental_events;
 pub mod fundamentals;
+pub mod fx;
+pub mod history;
 pub mod holders;
+pub mod importance;
+pub mod logos;
 pub mod news;
+pub mod open_interest;
 pub mod options;
+pub mod ownership;
+pub mod quality;
+pub mod relative_strength;
+pub mod resilience;
+pub mod risk;
+pub mod search;
+pub mod seasonality;
+pub mod single_flight;
+pub mod strategy;
+pub mod technicals;
+
+use resilience::Resilience;
+use single_flight::SingleFlight;
 
 #[derive(Debug, thiserror::Error)]
 pub enum FinanceServiceError {
     #[error(transparent)]
-    Yahoo(#[from] YahooError),
+    Yahoo(YahooError),
     #[error("No quote data for symbol {0}")]
     NotFound(String),
     #[error("Earnings API error: {0}")]
     Http(String),
+    /// The earnings API did not answer within the client timeout.
+    #[error("Earnings API timed out: {0}")]
+    Timeout(String),
+    /// An index membership list could not be loaded.
+    #[error("index constituents unavailable: {0}")]
+    Constituents(String),
+    /// The endpoint's circuit breaker is open after repeated failures.
+    #[error("{0} is temporarily unavailable")]
+    Unavailable(&'static str),
+    /// Yahoo has no data of this kind for the symbol's market.
+    #[error("no {feature} coverage for {symbol} ({exchange})")]
+    NoCoverage {
+        symbol: String,
+        feature: &'static str,
+        exchange: &'static str,
+    },
+    /// Error from a request shared with concurrent identical callers.
+    #[error(transparent)]
+    Shared(Arc<FinanceServiceError>),
+}
+
+impl FinanceServiceError {
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout(_) => true,
+            Self::Shared(err) => err.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Whether the upstream answered that the symbol does not exist.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Self::NotFound(_) | Self::Yahoo(YahooError::NotFound(_)) => true,
+            Self::Shared(err) => err.is_not_found(),
+            _ => false,
+        }
+    }
+}
+
+impl From<YahooError> for FinanceServiceError {
+    fn from(err: YahooError) -> Self {
+        Self::Yahoo(err)
+    }
 }
 
 pub struct FinanceService {
@@ -30,6 +107,16 @@ pub struct FinanceService {
     auth: Arc<YahooAuthManager>,
     #[allow(dead_code)]
     fetch: Arc<FetchClient>,
+    resilience: Resilience,
+    json_flights: SingleFlight<Value>,
+    holders_flights: SingleFlight<HoldersOverview>,
+    news_flights: SingleFlight<Vec<NewsItem>>,
+    earnings_flights: SingleFlight<EarningsRange>,
+    search_flights: SingleFlight<Vec<SymbolMatch>>,
+    /// Shared earnings calendar cache; without it every caller fetches from the API.
+    cache: Option<RedisCache>,
+    constituents: constituents::ConstituentCache,
+    constituent_sources: ConstituentsConfig,
 }
 
 impl FinanceService {
@@ -43,9 +130,30 @@ impl FinanceService {
             client,
             auth,
             fetch,
+            resilience: Resilience::default(),
+            json_flights: SingleFlight::default(),
+            holders_flights: SingleFlight::default(),
+            news_flights: SingleFlight::default(),
+            earnings_flights: SingleFlight::default(),
+            search_flights: SingleFlight::default(),
+            cache: None,
+            constituents: Default::default(),
+            constituent_sources: ConstituentsConfig::default(),
         })
     }
 
+    /// Cache earnings calendars in Redis so the commands and automations share one fetch.
+    pub fn with_cache(mut self, cache: RedisCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Download index member lists from `sources` instead of the defaults.
+    pub fn with_constituent_sources(mut self, sources: ConstituentsConfig) -> Self {
+        self.constituent_sources = sources;
+        self
+    }
+
     /// Access the underlying YahooFinanceClient.
     pub fn client(&self) -> &YahooFinanceClient {
         self.client.as_ref()
@@ -53,7 +161,14 @@ impl FinanceService {
 
     /// Fetch a simple price quote for a single symbol.
     pub async fn get_price(&self, symbol: &str) -> Result<PriceQuote, FinanceServiceError> {
-        let data = self.client.get_simple_quotes(&[symbol]).await?;
+        let symbols = [symbol];
+        let data = self
+            .json_flights
+            .run(flight_key("quote", symbol, ""), || {
+                self.resilience
+                    .call("quote", || self.client.get_simple_quotes(&symbols))
+            })
+            .await?;
         let quote = extract_simple_quote(&data)
             .ok_or_else(|| FinanceServiceError::NotFound(symbol.to_string()))?;
 
@@ -74,18 +189,21 @@ impl FinanceService {
         &self,
         symbol: &str,
     ) -> Result<FinancialSummary, FinanceServiceError> {
-        let fundamentals = fundamentals::fetch_fundamentals_timeseries(
-            self.client.as_ref(),
-            symbol,
-            StatementType::IncomeStatement,
-            Frequency::Annual,
-            5,
-        )
-        .await
-        .ok();
+        let fundamentals = self
+            .resilience
+            .call("fundamentals", || {
+                fundamentals::fetch_fundamentals_timeseries(
+                    self.client.as_ref(),
+                    symbol,
+                    StatementType::IncomeStatement,
+                    Frequency::Annual,
+                    5,
+                )
+            })
+            .await
+            .ok();
 
         let summary = self
-            .client
             .get_quote_summary(
                 symbol,
                 &[
@@ -96,33 +214,19 @@ impl FinanceService {
                 ],
             )
             .await?;
-
-        let result = summary
-            .get("quoteSummary")
-            .and_then(|q| q.get("result"))
-            .and_then(|r| r.as_array())
-            .and_then(|arr| arr.first())
-            .ok_or_else(|| FinanceServiceError::NotFound(symbol.to_string()))?;
+        let price = summary.price.unwrap_or_default();
+        let detail = summary.summary_detail.unwrap_or_default();
+        let stats = summary.default_key_statistics.unwrap_or_default();
+        let financial = summary.financial_data.unwrap_or_default();
 
         let revenue = fundamentals
             .as_ref()
             .and_then(|v| extract_timeseries_latest(v, "annualTotalRevenue"))
-            .or_else(|| extract_financial_data_f64(result, "financialData", "totalRevenue"));
-
-        let eps = extract_f64_raw(result, &["defaultKeyStatistics", "trailingEps"])
-            .or_else(|| extract_f64_raw(result, &["defaultKeyStatistics", "forwardEps"]));
-
-        let pe_ratio = extract_f64_raw(result, &["summaryDetail", "trailingPE"])
-            .or_else(|| extract_f64_raw(result, &["defaultKeyStatistics", "forwardPE"]));
-
-        let market_cap = extract_f64_raw(result, &["price", "marketCap"])
-            .or_else(|| extract_f64_raw(result, &["summaryDetail", "marketCap"]));
-
-        let currency = result
-            .get("price")
-            .and_then(|p| p.get("currency"))
-            .and_then(|c| c.as_str())
-            .map(|s| s.to_string());
+            .or(financial.total_revenue);
+        let eps = stats.trailing_eps.or(stats.forward_eps);
+        let pe_ratio = detail.trailing_pe.or(stats.forward_pe);
+        let market_cap = price.market_cap.or(detail.market_cap);
+        let currency = price.currency;
 
         Ok(FinancialSummary {
             symbol: symbol.to_uppercase(),
@@ -134,7 +238,29 @@ impl FinanceService {
         })
     }
 
-    /// Fetch raw fundamentals timeseries for a symbol and frequency.
+    /// Fetch the given `quoteSummary` modules for a symbol; modules not requested are `None`.
+    pub async fn get_quote_summary(
+        &self,
+        symbol: &str,
+        modules: &[&str],
+    ) -> Result<QuoteSummary, FinanceServiceError> {
+        let key = flight_key("quote_summary", symbol, &modules.join(","));
+        let raw = self
+            .json_flights
+            .run(key, || {
+                self.resilience.call("quote_summary", || {
+                    self.client.get_quote_summary(symbol, modules)
+                })
+            })
+            .await?;
+
+        QuoteSummary::from_response(&raw)
+            .map_err(|e| YahooError::ParseError(format!("quoteSummary: {e}")))?
+            .ok_or_else(|| FinanceServiceError::NotFound(symbol.to_string()))
+    }
+
+    /// Fetch raw fundamentals timeseries for a symbol and frequency. TTM shares the quarterly
+    /// fetch; its statements are derived when the payload is reshaped.
     pub async fn get_fundamentals_raw(
         &self,
         symbol: &str,
@@ -142,14 +268,25 @@ impl FinanceService {
         frequency: Frequency,
         years_back: i64,
     ) -> Result<Value, FinanceServiceError> {
-        let data = fundamentals::fetch_fundamentals_timeseries(
-            self.client.as_ref(),
+        let key = flight_key(
+            "fundamentals",
             symbol,
-            statement_type,
-            frequency,
-            years_back,
-        )
-        .await?;
+            &format!("{statement_type:?}:{:?}:{years_back}", frequency.fetched()),
+        );
+        let data = self
+            .json_flights
+            .run(key, || {
+                self.resilience.call("fundamentals", || {
+                    fundamentals::fetch_fundamentals_timeseries(
+                        self.client.as_ref(),
+                        symbol,
+                        statement_type,
+                        frequency,
+                        years_back,
+                    )
+                })
+            })
+            .await?;
 
         Ok(data)
     }
@@ -160,36 +297,217 @@ impl FinanceService {
         symbol: &str,
         holder_type: HolderType,
     ) -> Result<HoldersOverview, FinanceServiceError> {
-        let data = holders::fetch_holders(self.client.as_ref(), symbol, holder_type).await?;
+        let key = flight_key("holders", symbol, &format!("{holder_type:?}"));
+        let data = self
+            .holders_flights
+            .run(key, || {
+                self.resilience.call("holders", || {
+                    holders::fetch_holders(self.client.as_ref(), symbol, holder_type)
+                })
+            })
+            .await?;
         Ok(data)
     }
 
-    /// Fetch news for a symbol (limited number of items).
+    /// Fetch news for a symbol (limited number of items). May be empty.
     pub async fn get_news(
         &self,
         symbol: &str,
         limit: usize,
     ) -> Result<Vec<NewsItem>, FinanceServiceError> {
         let limit = limit.clamp(1, 20);
-        let items = news::fetch_news(self.client.as_ref(), symbol, limit).await?;
-        if items.is_empty() {
-            return Err(FinanceServiceError::NotFound(format!(
-                "no news found for symbol {symbol}"
-            )));
-        }
+        let items = self
+            .news_flights
+            .run(flight_key("news", symbol, &limit.to_string()), || {
+                self.resilience.call("news", || {
+                    news::fetch_news(self.client.as_ref(), symbol, limit)
+                })
+            })
+            .await?;
         Ok(items)
     }
 
-    /// Fetch earnings events for a date range (external API).
+    /// Keep the items whose link hasn't been posted to `scope` (e.g. a channel id) in the last
+    /// few days, and mark them posted, so news surfaced by several features goes out once.
+    /// Without Redis, or when it fails, every item is kept.
+    pub async fn unseen_news(&self, scope: &str, items: Vec<NewsItem>) -> Vec<NewsItem> {
+        let Some(cache) = &self.cache else {
+            return items;
+        };
+        let links: Vec<String> = items
+            .iter()
+            .map(|item| news::canonical_url(&item.link))
+            .collect();
+        match news_seen::claim_links(cache, scope, &links, Utc::now()).await {
+            Ok(fresh) => items
+                .into_iter()
+                .zip(fresh)
+                .filter_map(|(item, fresh)| fresh.then_some(item))
+                .collect(),
+            Err(e) => {
+                warn!("failed to check posted news links for {scope}: {e}");
+                items
+            }
+        }
+    }
+
+    /// Search symbols by ticker or company name, best match first.
+    pub async fn search_symbols(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolMatch>, FinanceServiceError> {
+        let limit = limit.clamp(1, 10);
+        let key = flight_key("search", query, &limit.to_string());
+        self.search_flights
+            .run(key, || {
+                self.resilience.call("search", || {
+                    search::search_symbols(self.client.as_ref(), query, limit)
+                })
+            })
+            .await
+    }
+
+    /// Consensus EPS and revenue estimates for the symbol's next report, and its market cap.
+    pub async fn get_earnings_estimate(
+        &self,
+        symbol: &str,
+    ) -> Result<EarningsEstimate, FinanceServiceError> {
+        let summary = self
+            .get_quote_summary(symbol, &["price", "calendarEvents"])
+            .await?;
+        let earnings = summary
+            .calendar_events
+            .and_then(|c| c.earnings)
+            .unwrap_or_default();
+        Ok(EarningsEstimate {
+            eps: earnings.earnings_average,
+            revenue: earnings.revenue_average,
+            market_cap: summary.price.and_then(|p| p.market_cap),
+            report_dates: earnings.earnings_date,
+        })
+    }
+
+    /// Date of the symbol's next report from Yahoo `calendarEvents`; the start of the window
+    /// when the date is unconfirmed.
+    pub async fn next_earnings_date(
+        &self,
+        symbol: &str,
+    ) -> Result<Option<chrono::NaiveDate>, FinanceServiceError> {
+        let summary = self.get_quote_summary(symbol, &["calendarEvents"]).await?;
+        Ok(summary
+            .calendar_events
+            .and_then(|c| c.earnings)
+            .and_then(|e| e.earnings_date.first().copied()))
+    }
+
+    /// Fetch earnings events for a date range (external API). Events sent without an
+    /// importance get a local score (see [`importance`]). Days that could not be fetched are
+    /// listed in [`EarningsRange::missing_days`].
     pub async fn get_earnings_range(
         &self,
         from: chrono::NaiveDate,
         to: chrono::NaiveDate,
+    ) -> Result<EarningsRange, FinanceServiceError> {
+        self.earnings_flights
+            .run(
+                flight_key("earnings", "", &format!("{from}:{to}")),
+                || async {
+                    let mut range = self.load_earnings(from, to).await?;
+                    self.fill_importance(&mut range.events).await;
+                    Ok(range)
+                },
+            )
+            .await
+    }
+
+    /// The range from the Redis cache when every day in it was fetched in the last 15 minutes,
+    /// by this request or any overlapping one; otherwise from the API. A multi-day range that
+    /// times out is fetched again one day at a time.
+    async fn load_earnings(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> Result<EarningsRange, FinanceServiceError> {
+        if let Some(cache) = &self.cache {
+            match earnings_calendar::load_range(cache, from, to).await {
+                Ok(Some(events)) => return Ok(EarningsRange::complete(events)),
+                Ok(None) => {}
+                Err(e) => warn!("failed to read cached earnings {from}..{to}: {e}"),
+            }
+        }
+        match self.fetch_earnings(from, to).await {
+            Ok(events) => Ok(EarningsRange::complete(events)),
+            Err(err) if err.is_timeout() && from < to => {
+                warn!("earnings {from}..{to} timed out; fetching each day separately");
+                self.fetch_earnings_by_day(from, to, err).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// One API call for the range (with retries), cached on success.
+    async fn fetch_earnings(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
     ) -> Result<Vec<EarningsEvent>, FinanceServiceError> {
-        earnings::fetch_earnings_range(from, to).await
+        let events = self
+            .resilience
+            .call("earnings", || earnings::fetch_earnings_range(from, to))
+            .await?;
+        if let Some(cache) = &self.cache {
+            if let Err(e) = earnings_calendar::save_range(cache, from, to, &events).await {
+                warn!("failed to cache earnings {from}..{to}: {e}");
+            }
+        }
+        Ok(events)
+    }
+
+    /// Every day of the range as its own request, merged. Days that still fail are left out
+    /// and listed as missing, so callers can say their data is partial; `range_err` is returned
+    /// only when no day succeeds.
+    async fn fetch_earnings_by_day(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+        range_err: FinanceServiceError,
+    ) -> Result<EarningsRange, FinanceServiceError> {
+        let days: Vec<chrono::NaiveDate> = from.iter_days().take_while(|d| *d <= to).collect();
+        let results = join_all(days.iter().map(|day| self.fetch_earnings(*day, *day))).await;
+
+        let mut range = EarningsRange::default();
+        for (day, result) in days.iter().zip(results) {
+            match result {
+                Ok(day_events) => range.events.extend(day_events),
+                Err(e) => {
+                    warn!("earnings for {day} unavailable: {e}");
+                    range.missing_days.push(*day);
+                }
+            }
+        }
+        if range.missing_days.len() == days.len() {
+            return Err(range_err);
+        }
+        if !range.missing_days.is_empty() {
+            warn!(
+                "returning partial earnings {from}..{to}: {} of {} days missing",
+                range.missing_days.len(),
+                days.len()
+            );
+        }
+        range
+            .events
+            .sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.symbol.cmp(&b.symbol)));
+        Ok(range)
     }
 }
 
+/// Single-flight key: identical `(endpoint, symbol, params)` requests share one fetch.
+fn flight_key(endpoint: &str, symbol: &str, params: &str) -> String {
+    format!("{endpoint}:{}:{params}", symbol.trim().to_uppercase())
+}
+
 /// Extract the first simple quote from the Yahoo response into our bot-facing struct.
 fn extract_simple_quote(data: &Value) -> Option<PriceQuote> {
     let result = data
@@ -246,22 +564,4 @@ fn extract_timeseries_latest(data: &Value, field: &str) -> Option<f64> {
     None
 }
 
-fn extract_f64_raw(root: &Value, path: &[&str]) -> Option<f64> {
-    let mut current = root;
-    for key in path {
-        current = current.get(*key)?;
-    }
-
-    current
-        .get("raw")
-        .and_then(|v| v.as_f64())
-        .or_else(|| current.as_f64())
-}
-
-fn extract_financial_data_f64(root: &Value, module: &str, field: &str) -> Option<f64> {
-    root.get(module)
-        .and_then(|m| m.get(field))
-        .and_then(|v| v.get("raw").and_then(|r| r.as_f64()).or_else(|| v.as_f64()))
-}
-
 pub use FinanceServiceError as Error;
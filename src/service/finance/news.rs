@@ -1,10 +1,40 @@
 use chrono::{DateTime, Utc};
 use finance_query_core::{YahooError, YahooFinanceClient};
+use reqwest::Url;
 use serde_json::Value;
 
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
-use crate::models::NewsItem;
+use crate::models::{NewsItem, SourceType};
+
+/// Query parameters that only record where a click came from, besides any `utm_*`.
+const TRACKING_PARAMS: &[&str] = &[
+    ".tsrc",
+    "guccounter",
+    "guce_referrer",
+    "guce_referrer_sig",
+    "ncid",
+    "soc_src",
+    "soc_trk",
+    "yptr",
+    "fr",
+    "cmpid",
+    "ref",
+];
+
+/// Wire services companies pay to publish their own announcements, compared lowercase with
+/// spaces removed.
+const PRESS_RELEASE_WIRES: &[&str] = &[
+    "prnewswire",
+    "businesswire",
+    "globenewswire",
+    "accesswire",
+    "newsfile",
+    "eqs-news",
+    "cision",
+    "marketwired",
+];
 
 /// Fetch news items for a symbol via Yahoo search.
 pub async fn fetch_news(
@@ -67,12 +97,14 @@ fn parse_news(data: &Value, limit: usize) -> Vec<NewsItem> {
                     .map(|s| s.to_string())
             });
 
+        let source_type = classify_source(source.as_deref());
         items.push(NewsItem {
             title,
             link,
             source,
             published_at,
             thumbnail,
+            source_type,
         });
     }
 
@@ -84,9 +116,68 @@ fn parse_news(data: &Value, limit: usize) -> Vec<NewsItem> {
         (None, None) => Ordering::Equal,
     });
 
+    // The same story often comes back through several feeds; keep the newest copy.
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(canonical_url(&item.link)));
+
     if items.len() > limit {
         items.truncate(limit);
     }
 
     items
 }
+
+/// Whether `publisher` is a press-release wire (PR Newswire, Business Wire, …) or the press.
+/// Unknown publishers count as press.
+pub fn classify_source(publisher: Option<&str>) -> SourceType {
+    let Some(publisher) = publisher else {
+        return SourceType::Media;
+    };
+    let compact: String = publisher
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    if PRESS_RELEASE_WIRES
+        .iter()
+        .any(|wire| compact.contains(wire))
+    {
+        SourceType::PressRelease
+    } else {
+        SourceType::Media
+    }
+}
+
+/// The form of an article link used to tell whether two links are the same story: https,
+/// host without `www.`, no fragment or trailing slash, tracking parameters dropped and the
+/// rest sorted. Links that don't parse are only trimmed.
+pub fn canonical_url(link: &str) -> String {
+    let link = link.trim();
+    let Ok(mut url) = Url::parse(link) else {
+        return link.to_string();
+    };
+    if url.scheme() == "http" {
+        let _ = url.set_scheme("https");
+    }
+    if let Some(host) = url.host_str().and_then(|h| h.strip_prefix("www.")) {
+        let host = host.to_string();
+        let _ = url.set_host(Some(&host));
+    }
+    url.set_fragment(None);
+
+    let mut params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    params.sort();
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(&params);
+    }
+
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(if path.is_empty() { "/" } else { &path });
+    url.to_string()
+}
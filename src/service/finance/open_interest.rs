@@ -0,0 +1,209 @@
+//! Day-over-day open-interest changes from daily snapshots kept in Redis.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::options::OptionKind;
+use super::{FinanceService, FinanceServiceError};
+use crate::service::caching::collections::spy_data;
+
+/// Expirations covered by an open-interest snapshot, nearest first.
+const OPEN_INTEREST_EXPIRATIONS: usize = 4;
+
+/// Open interest of one contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractOpenInterest {
+    pub expiration: NaiveDate,
+    pub kind: OptionKind,
+    pub strike: f64,
+    pub open_interest: u64,
+}
+
+/// Day-over-day open-interest change of one contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenInterestChange {
+    pub expiration: NaiveDate,
+    pub kind: OptionKind,
+    pub strike: f64,
+    pub previous: u64,
+    pub current: u64,
+}
+
+impl OpenInterestChange {
+    pub fn change(&self) -> i64 {
+        self.current as i64 - self.previous as i64
+    }
+}
+
+/// Contracts whose open interest changed between two snapshots, largest build first. Only
+/// expirations in both are compared, so one entering the window doesn't read as a build; a
+/// contract missing from one snapshot counts as zero there.
+pub fn open_interest_changes(
+    previous: &[ContractOpenInterest],
+    current: &[ContractOpenInterest],
+) -> Vec<OpenInterestChange> {
+    let in_previous: HashSet<NaiveDate> = previous.iter().map(|c| c.expiration).collect();
+    let in_current: HashSet<NaiveDate> = current.iter().map(|c| c.expiration).collect();
+
+    // Keyed by strike in cents so float strikes from the two days line up.
+    let mut merged: BTreeMap<(NaiveDate, OptionKind, i64), (f64, u64, u64)> = BTreeMap::new();
+    for c in previous
+        .iter()
+        .filter(|c| in_current.contains(&c.expiration))
+    {
+        let key = (c.expiration, c.kind, (c.strike * 100.0).round() as i64);
+        merged.entry(key).or_insert((c.strike, 0, 0)).1 += c.open_interest;
+    }
+    for c in current
+        .iter()
+        .filter(|c| in_previous.contains(&c.expiration))
+    {
+        let key = (c.expiration, c.kind, (c.strike * 100.0).round() as i64);
+        merged.entry(key).or_insert((c.strike, 0, 0)).2 += c.open_interest;
+    }
+
+    let mut changes: Vec<OpenInterestChange> = merged
+        .into_iter()
+        .filter(|(_, (_, previous, current))| previous != current)
+        .map(
+            |((expiration, kind, _), (strike, previous, current))| OpenInterestChange {
+                expiration,
+                kind,
+                strike,
+                previous,
+                current,
+            },
+        )
+        .collect();
+    changes.sort_by_key(|c| std::cmp::Reverse(c.change()));
+    changes
+}
+
+/// The day's open interest next to the latest earlier snapshot, when one is stored.
+#[derive(Debug, Clone)]
+pub struct OpenInterestComparison {
+    pub day: NaiveDate,
+    pub current: Vec<ContractOpenInterest>,
+    pub previous: Option<(NaiveDate, Vec<ContractOpenInterest>)>,
+}
+
+impl OpenInterestComparison {
+    pub fn changes(&self) -> Vec<OpenInterestChange> {
+        self.previous
+            .as_ref()
+            .map(|(_, previous)| open_interest_changes(previous, &self.current))
+            .unwrap_or_default()
+    }
+}
+
+impl FinanceService {
+    /// Open interest of every listed contract in the nearest expirations. Yahoo updates it
+    /// once a day from the prior session's clearing data. An expiration whose chain fails to
+    /// load is left out.
+    pub async fn open_interest(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<ContractOpenInterest>, FinanceServiceError> {
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+        let mut expirations = self.get_option_expirations(symbol).await?;
+        expirations.retain(|exp| *exp >= today);
+        expirations.sort();
+        expirations.truncate(OPEN_INTEREST_EXPIRATIONS);
+
+        let chains = join_all(expirations.iter().map(|exp| {
+            let exp = exp.format("%Y-%m-%d").to_string();
+            async move {
+                self.resilience
+                    .call("options", || {
+                        self.client.get_option_chain(symbol, Some(exp.as_str()))
+                    })
+                    .await
+            }
+        }))
+        .await;
+
+        let mut contracts = Vec::new();
+        for (expiration, chain) in expirations.into_iter().zip(chains) {
+            let chain = match chain {
+                Ok(chain) => chain,
+                Err(e) => {
+                    warn!("open interest: {symbol} {expiration} chain failed: {e}");
+                    continue;
+                }
+            };
+            let sides = [
+                (OptionKind::Call, chain.calls),
+                (OptionKind::Put, chain.puts),
+            ];
+            for (kind, side) in sides {
+                contracts.extend(side.into_iter().filter_map(|c| {
+                    Some(ContractOpenInterest {
+                        expiration,
+                        kind,
+                        strike: c.strike,
+                        open_interest: c.open_interest.filter(|oi| *oi > 0)?,
+                    })
+                }));
+            }
+        }
+
+        if contracts.is_empty() {
+            return Err(FinanceServiceError::NotFound(format!(
+                "no open interest for {}",
+                symbol.to_uppercase()
+            )));
+        }
+        Ok(contracts)
+    }
+
+    /// Today's (ET) open interest for `symbol`: the stored snapshot when there is one, else
+    /// fetched and stored as the day's snapshot. Without a Redis cache nothing is kept.
+    pub async fn snapshot_open_interest(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<ContractOpenInterest>, FinanceServiceError> {
+        let day = Utc::now().with_timezone(&New_York).date_naive();
+        if let Some(cache) = &self.cache {
+            match spy_data::load_oi_snapshot(cache, symbol, day).await {
+                Ok(Some(snapshot)) => return Ok(snapshot),
+                Ok(None) => {}
+                Err(e) => warn!("failed to read {symbol} open interest snapshot: {e}"),
+            }
+        }
+        let contracts = self.open_interest(symbol).await?;
+        if let Some(cache) = &self.cache {
+            if let Err(e) = spy_data::save_oi_snapshot(cache, symbol, day, &contracts).await {
+                warn!("failed to store {symbol} open interest snapshot: {e}");
+            }
+        }
+        Ok(contracts)
+    }
+
+    /// Today's open interest and the most recent earlier snapshot to compare it with.
+    pub async fn open_interest_comparison(
+        &self,
+        symbol: &str,
+    ) -> Result<OpenInterestComparison, FinanceServiceError> {
+        let day = Utc::now().with_timezone(&New_York).date_naive();
+        let current = self.snapshot_open_interest(symbol).await?;
+        let previous = match &self.cache {
+            Some(cache) => spy_data::previous_oi_snapshot(cache, symbol, day)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("failed to read earlier {symbol} open interest: {e}");
+                    None
+                }),
+            None => None,
+        };
+        Ok(OpenInterestComparison {
+            day,
+            current,
+            previous,
+        })
+    }
+}
@@ -1,10 +1,18 @@
 use std::cmp::Ordering;
 
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::America::New_York;
 use finance_query_core::OptionContract;
+use futures_util::future::join_all;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
 
+use super::exchange::Exchange;
 use super::{FinanceService, FinanceServiceError};
 
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
 #[derive(Debug, Clone)]
 pub struct OptionSlice {
     pub symbol: String,
@@ -14,13 +22,231 @@ pub struct OptionSlice {
     pub puts: Vec<OptionContract>,
 }
 
+impl OptionSlice {
+    /// The contract of `kind` at `strike`, if this slice has it.
+    pub fn contract(&self, kind: OptionKind, strike: f64) -> Option<&OptionContract> {
+        let side = match kind {
+            OptionKind::Call => &self.calls,
+            OptionKind::Put => &self.puts,
+        };
+        side.iter().find(|c| (c.strike - strike).abs() < 1e-6)
+    }
+
+    /// Black-Scholes greeks of a contract from this slice, as of `now`, using the contract's
+    /// implied volatility. `None` once the expiration has passed or without a usable IV.
+    pub fn greeks(
+        &self,
+        contract: &OptionContract,
+        kind: OptionKind,
+        rate: f64,
+        now: DateTime<Utc>,
+    ) -> Option<Greeks> {
+        let expiration = NaiveDate::parse_from_str(&self.expiration, "%Y-%m-%d").ok()?;
+        Greeks::black_scholes(
+            kind,
+            self.spot,
+            contract.strike,
+            years_to_expiry(expiration, now),
+            contract.implied_volatility,
+            rate,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+impl OptionKind {
+    /// `C` or `P`, as in `580C`.
+    pub fn letter(self) -> char {
+        match self {
+            OptionKind::Call => 'C',
+            OptionKind::Put => 'P',
+        }
+    }
+}
+
+/// Black-Scholes sensitivities of one contract, per share.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    pub delta: f64,
+    /// Change in delta for a $1 move in the underlying.
+    pub gamma: f64,
+    /// Value lost per calendar day.
+    pub theta: f64,
+    /// Value change for a one-point (1%) move in implied volatility.
+    pub vega: f64,
+}
+
+impl Greeks {
+    /// Greeks of a European option on a non-dividend-paying underlying. `years` to expiry,
+    /// `volatility` and `rate` are annual fractions. `None` when any input is degenerate.
+    pub fn black_scholes(
+        kind: OptionKind,
+        spot: f64,
+        strike: f64,
+        years: f64,
+        volatility: f64,
+        rate: f64,
+    ) -> Option<Self> {
+        let valid = |x: f64| x.is_finite() && x > 0.0;
+        if !(valid(spot) && valid(strike) && valid(years) && valid(volatility)) {
+            return None;
+        }
+        let sqrt_t = years.sqrt();
+        let d1 = ((spot / strike).ln() + (rate + volatility * volatility / 2.0) * years)
+            / (volatility * sqrt_t);
+        let d2 = d1 - volatility * sqrt_t;
+        let discount = (-rate * years).exp();
+        let pdf_d1 = normal_pdf(d1);
+
+        let gamma = pdf_d1 / (spot * volatility * sqrt_t);
+        let vega = spot * pdf_d1 * sqrt_t / 100.0;
+        let decay = -spot * pdf_d1 * volatility / (2.0 * sqrt_t);
+        let (delta, annual_theta) = match kind {
+            OptionKind::Call => (
+                normal_cdf(d1),
+                decay - rate * strike * discount * normal_cdf(d2),
+            ),
+            OptionKind::Put => (
+                normal_cdf(d1) - 1.0,
+                decay + rate * strike * discount * normal_cdf(-d2),
+            ),
+        };
+        Some(Self {
+            delta,
+            gamma,
+            theta: annual_theta / 365.0,
+            vega,
+        })
+    }
+}
+
+/// Horizons, in calendar days, the expected-move cone is sampled at: for each, the first
+/// expiration at least that far out.
+const EXPECTED_MOVE_HORIZONS: [i64; 6] = [1, 7, 14, 30, 60, 90];
+/// Half a vol point: IV differences this small read as flat.
+const FLAT_IV_BAND: f64 = 0.005;
+/// Expirations sampled for the IV term structure; weeklies make the front crowded, so this
+/// reaches out about a year for most names.
+const MAX_TERM_EXPIRATIONS: usize = 24;
+/// Concurrent chain requests for the term structure.
+const TERM_LOOKUPS: usize = 6;
+
+/// One expiration's ±1σ expected move, priced from its at-the-money straddle.
+#[derive(Debug, Clone)]
+pub struct ExpectedMove {
+    pub expiration: NaiveDate,
+    /// Calendar days from today (ET) to expiration.
+    pub days: i64,
+    pub spot: f64,
+    /// Nearest call above plus nearest put below spot, at mid where quoted.
+    pub straddle: f64,
+    /// ±1σ move in price. An ATM straddle is worth about √(2/π) ≈ 0.8 of one standard
+    /// deviation, so this is the straddle scaled by √(π/2).
+    pub amount: f64,
+}
+
+/// At-the-money implied volatility of one expiration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TermPoint {
+    pub expiration: NaiveDate,
+    /// Calendar days from today (ET) to expiration.
+    pub days: i64,
+    /// Annualized, as a fraction.
+    pub atm_iv: f64,
+}
+
+/// Whether implied volatility rises (contango) or falls (backwardation) with time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermShape {
+    Contango,
+    Backwardation,
+    Flat,
+}
+
+impl TermShape {
+    /// Shape from a nearer to a later IV; within half a vol point counts as flat.
+    pub fn between(near_iv: f64, far_iv: f64) -> Self {
+        let slope = far_iv - near_iv;
+        if slope > FLAT_IV_BAND {
+            TermShape::Contango
+        } else if slope < -FLAT_IV_BAND {
+            TermShape::Backwardation
+        } else {
+            TermShape::Flat
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TermShape::Contango => "contango",
+            TermShape::Backwardation => "backwardation",
+            TermShape::Flat => "flat",
+        }
+    }
+}
+
+/// The term structure around an earnings date: the first expiration after the report prices
+/// the move, so it usually stands above its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarningsKink {
+    pub earnings: NaiveDate,
+    /// Last expiration on or before the report date.
+    pub before: Option<TermPoint>,
+    /// First expiration after the report date.
+    pub event: TermPoint,
+    /// The expiration after `event`.
+    pub after: Option<TermPoint>,
+}
+
+impl EarningsKink {
+    /// `None` when the report falls after the last sampled expiration. A report on an
+    /// expiration day counts toward the next expiration, since after-close reports miss it.
+    pub fn find(points: &[TermPoint], earnings: NaiveDate) -> Option<Self> {
+        let index = points.iter().position(|p| p.expiration > earnings)?;
+        Some(Self {
+            earnings,
+            before: index.checked_sub(1).map(|i| points[i]),
+            event: points[index],
+            after: points.get(index + 1).copied(),
+        })
+    }
+
+    /// Shape from the last pre-earnings expiration into the event expiration.
+    pub fn entering_event(&self) -> Option<TermShape> {
+        self.before
+            .map(|before| TermShape::between(before.atm_iv, self.event.atm_iv))
+    }
+
+    /// Shape from the event expiration to the one after it.
+    pub fn leaving_event(&self) -> Option<TermShape> {
+        self.after
+            .map(|after| TermShape::between(self.event.atm_iv, after.atm_iv))
+    }
+}
+
+impl ExpectedMove {
+    pub fn percent(&self) -> f64 {
+        self.amount / self.spot * 100.0
+    }
+}
+
 impl FinanceService {
     /// Fetch available option expirations as NaiveDate values.
     pub async fn get_option_expirations(
         &self,
         symbol: &str,
     ) -> Result<Vec<NaiveDate>, FinanceServiceError> {
-        let expirations = self.client.get_option_expirations(symbol).await?;
+        ensure_options_coverage(symbol)?;
+        let expirations = self
+            .resilience
+            .call("options", || self.client.get_option_expirations(symbol))
+            .await?;
         let parsed: Vec<NaiveDate> = expirations
             .expirations
             .iter()
@@ -43,10 +269,14 @@ impl FinanceService {
         expiration: NaiveDate,
         strikes_each_side: usize,
     ) -> Result<OptionSlice, FinanceServiceError> {
+        ensure_options_coverage(symbol)?;
         let expiration_str = expiration.format("%Y-%m-%d").to_string();
         let chain = self
-            .client
-            .get_option_chain(symbol, Some(expiration_str.as_str()))
+            .resilience
+            .call("options", || {
+                self.client
+                    .get_option_chain(symbol, Some(expiration_str.as_str()))
+            })
             .await?;
 
         let spot = chain
@@ -78,13 +308,50 @@ impl FinanceService {
         })
     }
 
+    /// Every strike of one expiration, ascending on both sides.
+    pub async fn get_option_chain(
+        &self,
+        symbol: &str,
+        expiration: NaiveDate,
+    ) -> Result<OptionSlice, FinanceServiceError> {
+        ensure_options_coverage(symbol)?;
+        let expiration_str = expiration.format("%Y-%m-%d").to_string();
+        let chain = self
+            .resilience
+            .call("options", || {
+                self.client
+                    .get_option_chain(symbol, Some(expiration_str.as_str()))
+            })
+            .await?;
+
+        let spot = chain
+            .underlying_price
+            .ok_or_else(|| FinanceServiceError::NotFound("no underlying price".into()))?;
+        let mut calls = chain.calls;
+        calls.sort_by(|a, b| float_cmp(a.strike, b.strike));
+        let mut puts = chain.puts;
+        puts.sort_by(|a, b| float_cmp(a.strike, b.strike));
+
+        Ok(OptionSlice {
+            symbol: symbol.to_uppercase(),
+            expiration: expiration_str,
+            spot,
+            calls,
+            puts,
+        })
+    }
+
     /// Fetch today’s expiration option chain and slice around spot.
     pub async fn get_option_slice_today(
         &self,
         symbol: &str,
         strikes_each_side: usize,
     ) -> Result<OptionSlice, FinanceServiceError> {
-        let expirations = self.client.get_option_expirations(symbol).await?;
+        ensure_options_coverage(symbol)?;
+        let expirations = self
+            .resilience
+            .call("options", || self.client.get_option_expirations(symbol))
+            .await?;
         let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
 
         let expiration = expirations
@@ -133,6 +400,177 @@ impl FinanceService {
     }
 }
 
+impl FinanceService {
+    /// Expected moves for a handful of expirations from a day to three months out, nearest
+    /// first. Expirations whose chain fails to load are left out.
+    pub async fn expected_moves(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<ExpectedMove>, FinanceServiceError> {
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+        let expirations = self.get_option_expirations(symbol).await?;
+        let mut picked: Vec<NaiveDate> = EXPECTED_MOVE_HORIZONS
+            .iter()
+            .filter_map(|horizon| {
+                expirations
+                    .iter()
+                    .copied()
+                    .filter(|exp| (*exp - today).num_days() >= *horizon)
+                    .min()
+            })
+            .collect();
+        picked.sort();
+        picked.dedup();
+
+        let slices = join_all(
+            picked
+                .iter()
+                .map(|exp| self.get_option_slice(symbol, *exp, 1)),
+        )
+        .await;
+        let mut moves = Vec::new();
+        for (expiration, slice) in picked.into_iter().zip(slices) {
+            let slice = match slice {
+                Ok(slice) => slice,
+                Err(e) => {
+                    warn!("expected move: {symbol} {expiration} chain failed: {e}");
+                    continue;
+                }
+            };
+            let (Some(call), Some(put)) = (slice.calls.first(), slice.puts.first()) else {
+                continue;
+            };
+            let straddle = premium(call) + premium(put);
+            if straddle <= 0.0 || slice.spot <= 0.0 {
+                continue;
+            }
+            moves.push(ExpectedMove {
+                expiration,
+                days: (expiration - today).num_days(),
+                spot: slice.spot,
+                straddle,
+                amount: straddle * std::f64::consts::FRAC_PI_2.sqrt(),
+            });
+        }
+
+        if moves.is_empty() {
+            return Err(FinanceServiceError::NotFound(format!(
+                "no priced at-the-money options for {}",
+                symbol.to_uppercase()
+            )));
+        }
+        Ok(moves)
+    }
+}
+
+impl FinanceService {
+    /// ATM implied volatility for each listed expiration up to `MAX_TERM_EXPIRATIONS`, nearest
+    /// first: the mean IV of the nearest call above and put below spot. Expirations without a
+    /// usable quote are left out.
+    pub async fn iv_term_structure(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<TermPoint>, FinanceServiceError> {
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+        let mut expirations = self.get_option_expirations(symbol).await?;
+        expirations.retain(|exp| *exp >= today);
+        expirations.sort();
+        expirations.truncate(MAX_TERM_EXPIRATIONS);
+
+        let slices: Vec<_> = stream::iter(expirations.iter().copied())
+            .map(|exp| self.get_option_slice(symbol, exp, 1))
+            .buffered(TERM_LOOKUPS)
+            .collect()
+            .await;
+        let mut points = Vec::new();
+        for (expiration, slice) in expirations.into_iter().zip(slices) {
+            let slice = match slice {
+                Ok(slice) => slice,
+                Err(e) => {
+                    warn!("term structure: {symbol} {expiration} chain failed: {e}");
+                    continue;
+                }
+            };
+            let ivs: Vec<f64> = slice
+                .calls
+                .first()
+                .into_iter()
+                .chain(slice.puts.first())
+                .map(|c| c.implied_volatility)
+                // Yahoo reports a placeholder IV near zero for contracts it cannot price.
+                .filter(|iv| iv.is_finite() && *iv > 0.01)
+                .collect();
+            if ivs.is_empty() {
+                continue;
+            }
+            points.push(TermPoint {
+                expiration,
+                days: (expiration - today).num_days(),
+                atm_iv: ivs.iter().sum::<f64>() / ivs.len() as f64,
+            });
+        }
+
+        if points.is_empty() {
+            return Err(FinanceServiceError::NotFound(format!(
+                "no at-the-money implied volatility for {}",
+                symbol.to_uppercase()
+            )));
+        }
+        Ok(points)
+    }
+}
+
+/// Mid price when the contract has a two-sided quote, else its last trade.
+pub fn premium(contract: &OptionContract) -> f64 {
+    if contract.bid > 0.0 && contract.ask > 0.0 {
+        (contract.bid + contract.ask) / 2.0
+    } else {
+        contract.last_price
+    }
+}
+
+/// Yahoo only lists option chains for US symbols; fail fast instead of spending a request
+/// (and a circuit-breaker strike) on a market that never has data.
+fn ensure_options_coverage(symbol: &str) -> Result<(), FinanceServiceError> {
+    let exchange = Exchange::for_symbol(symbol);
+    if exchange.has_options {
+        return Ok(());
+    }
+    Err(FinanceServiceError::NoCoverage {
+        symbol: symbol.to_uppercase(),
+        feature: "options",
+        exchange: exchange.name,
+    })
+}
+
 fn float_cmp(a: f64, b: f64) -> Ordering {
     a.partial_cmp(&b).unwrap_or(Ordering::Equal)
 }
+
+/// Time from `now` to the 4:00 PM ET close on `expiration`, in years.
+fn years_to_expiry(expiration: NaiveDate, now: DateTime<Utc>) -> f64 {
+    expiration
+        .and_hms_opt(16, 0, 0)
+        .and_then(|close| close.and_local_timezone(New_York).earliest())
+        .map(|close| (close.with_timezone(&Utc) - now).num_seconds() as f64 / SECONDS_PER_YEAR)
+        .unwrap_or(0.0)
+}
+
+fn normal_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal CDF via the Abramowitz-Stegun erf approximation (error below 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        (1.0 + erf) / 2.0
+    } else {
+        (1.0 - erf) / 2.0
+    }
+}
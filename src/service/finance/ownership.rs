@@ -0,0 +1,156 @@
+//! Quarterly institutional-ownership snapshots kept in the state store, so `/holders-trend` can
+//! show how ownership moved across 13F reporting periods. Yahoo only serves the latest period,
+//! so the history builds up from the lookups made each quarter.
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::{FinanceService, FinanceServiceError};
+use crate::models::holders::value_to_f64;
+use crate::models::{HolderType, HoldersOverview};
+use crate::service::store::StateStore;
+
+/// Largest holders summed for the concentration figure.
+const TOP_HOLDERS: usize = 10;
+/// Quarters shown by the trend, latest last.
+pub const TREND_QUARTERS: usize = 4;
+/// Quarters stored per symbol; the ones beyond [`TREND_QUARTERS`] are slack for a quarter that
+/// was skipped.
+pub const KEEP_QUARTERS: usize = 8;
+
+/// Institutional ownership of one symbol as of a quarter end. Percentages are fractions of
+/// shares outstanding, as Yahoo reports them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipSnapshot {
+    pub quarter: NaiveDate,
+    pub institutions_percent: Option<f64>,
+    /// Share held by the ten largest institutional holders.
+    pub top10_percent: Option<f64>,
+    pub institutions_count: Option<i64>,
+}
+
+impl OwnershipSnapshot {
+    /// Build a snapshot from the major-holders breakdown and the institutional holder list.
+    /// The quarter is the one the holders' latest filings report on, or the last completed
+    /// quarter before `today` when no filing date is known.
+    pub fn from_holders(
+        major: Option<&HoldersOverview>,
+        institutional: Option<&HoldersOverview>,
+        today: NaiveDate,
+    ) -> Self {
+        let breakdown = major.and_then(|m| m.major_breakdown.as_ref());
+        let metric = |key: &str| {
+            breakdown
+                .and_then(|b| b.breakdown_data.get(key))
+                .and_then(value_to_f64)
+        };
+        let mut holders: Vec<_> = institutional
+            .and_then(|i| i.institutional_holders.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .collect();
+        holders.sort_by_key(|h| std::cmp::Reverse(h.shares));
+        let top: Vec<f64> = holders
+            .iter()
+            .take(TOP_HOLDERS)
+            .filter_map(|h| h.percent_out)
+            .collect();
+        let reported = holders
+            .iter()
+            .map(|h| h.date_reported.date_naive())
+            .filter(|d| d.year() > 1970)
+            .max();
+
+        OwnershipSnapshot {
+            quarter: match reported {
+                Some(day) => quarter_end(day),
+                None => previous_quarter_end(today),
+            },
+            institutions_percent: metric("institutionsPercentHeld"),
+            top10_percent: (!top.is_empty()).then(|| top.iter().sum()),
+            institutions_count: metric("institutionsCount").map(|c| c as i64),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.institutions_percent.is_none()
+            && self.top10_percent.is_none()
+            && self.institutions_count.is_none()
+    }
+}
+
+/// Last day of the calendar quarter holding `day`.
+pub fn quarter_end(day: NaiveDate) -> NaiveDate {
+    let last_month = (day.month0() / 3) * 3 + 3;
+    let next = if last_month == 12 {
+        NaiveDate::from_ymd_opt(day.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(day.year(), last_month + 1, 1)
+    };
+    next.expect("first of a month is a valid date") - Duration::days(1)
+}
+
+/// Last day of the quarter before the one holding `day`.
+pub fn previous_quarter_end(day: NaiveDate) -> NaiveDate {
+    let start = NaiveDate::from_ymd_opt(day.year(), (day.month0() / 3) * 3 + 1, 1)
+        .expect("first of a month is a valid date");
+    start - Duration::days(1)
+}
+
+/// `Q3 2026` for the quarter ending `quarter`.
+pub fn quarter_label(quarter: NaiveDate) -> String {
+    format!("Q{} {}", quarter.month0() / 3 + 1, quarter.year())
+}
+
+impl FinanceService {
+    /// The latest reporting period's ownership for `symbol`. The institutional list is only
+    /// needed for concentration and the period, so it failing leaves those out.
+    pub async fn ownership_snapshot(
+        &self,
+        symbol: &str,
+    ) -> Result<OwnershipSnapshot, FinanceServiceError> {
+        let (major, institutional) = tokio::join!(
+            self.get_holders(symbol, HolderType::Major),
+            self.get_holders(symbol, HolderType::Institutional)
+        );
+        let major = major?;
+        let institutional = institutional
+            .inspect_err(|e| warn!("ownership: no institutional holders for {symbol}: {e}"))
+            .ok();
+        let today = Utc::now().date_naive();
+        let snapshot = OwnershipSnapshot::from_holders(Some(&major), institutional.as_ref(), today);
+        if snapshot.is_empty() {
+            return Err(FinanceServiceError::NotFound(format!(
+                "no institutional ownership for {}",
+                symbol.to_uppercase()
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    /// Up to [`TREND_QUARTERS`] quarters of ownership, oldest first, ending with the latest
+    /// period, which is stored for later lookups. Without a state store only the latest period
+    /// is returned.
+    pub async fn ownership_trend(
+        &self,
+        symbol: &str,
+        store: Option<&dyn StateStore>,
+    ) -> Result<Vec<OwnershipSnapshot>, FinanceServiceError> {
+        let current = self.ownership_snapshot(symbol).await?;
+        let Some(store) = store else {
+            return Ok(vec![current]);
+        };
+        if let Err(e) = store.save_ownership_snapshot(symbol, &current).await {
+            warn!("failed to store {symbol} ownership snapshot: {e}");
+        }
+        let mut snapshots = store.ownership_snapshots(symbol).await.unwrap_or_else(|e| {
+            warn!("failed to read {symbol} ownership history: {e}");
+            Vec::new()
+        });
+        snapshots.retain(|s| s.quarter < current.quarter);
+        snapshots.push(current);
+        let skip = snapshots.len().saturating_sub(TREND_QUARTERS);
+        Ok(snapshots.split_off(skip))
+    }
+}
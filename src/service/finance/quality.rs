@@ -0,0 +1,321 @@
+//! Balance-sheet quality scores from the annual statements: the Piotroski F-score (nine
+//! pass/fail tests of profitability, leverage and efficiency against the prior year) and the
+//! Altman Z-score (bankruptcy risk from five ratios to total assets).
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use super::fundamentals::reshape_timeseries_to_financial_statements;
+use super::{FinanceService, FinanceServiceError};
+use crate::models::{FinancialStatement, Frequency, StatementType};
+
+/// Two fiscal years are compared; a third covers a year missing from one statement.
+const ANNUAL_YEARS: i64 = 3;
+/// Altman's cut-offs: above `SAFE` is the safe zone, below `DISTRESS` the distress zone.
+pub const Z_SAFE: f64 = 2.99;
+pub const Z_DISTRESS: f64 = 1.81;
+
+/// Every annual metric reported for one fiscal year end, across the three statements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FiscalYear {
+    pub end: NaiveDate,
+    pub values: HashMap<String, f64>,
+}
+
+impl FiscalYear {
+    pub fn get(&self, field: &str) -> Option<f64> {
+        self.values.get(field).copied()
+    }
+
+    fn ratio(&self, numerator: &str, denominator: &str) -> Option<f64> {
+        let denominator = self.get(denominator).filter(|d| *d != 0.0)?;
+        Some(self.get(numerator)? / denominator)
+    }
+
+    /// Shares outstanding at year end, or the diluted average when the balance sheet lacks it.
+    fn shares(&self) -> Option<f64> {
+        self.get("OrdinarySharesNumber")
+            .or_else(|| self.get("DilutedAverageShares"))
+    }
+}
+
+/// The annual statements merged by fiscal year end, newest first.
+pub fn fiscal_years(statements: &[FinancialStatement]) -> Vec<FiscalYear> {
+    let mut years: HashMap<NaiveDate, FiscalYear> = HashMap::new();
+    for statement in statements
+        .iter()
+        .filter(|s| s.frequency == Frequency::Annual.as_str())
+    {
+        for (metric, series) in &statement.statement {
+            for (date, value) in series {
+                let Ok(end) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+                    continue;
+                };
+                let Some(raw) = value
+                    .get("reportedValue")
+                    .and_then(|rv| rv.get("raw"))
+                    .and_then(|r| r.as_f64())
+                else {
+                    continue;
+                };
+                years
+                    .entry(end)
+                    .or_insert_with(|| FiscalYear {
+                        end,
+                        ..Default::default()
+                    })
+                    .values
+                    .insert(metric.clone(), raw);
+            }
+        }
+    }
+    let mut years: Vec<FiscalYear> = years.into_values().collect();
+    years.sort_by_key(|year| std::cmp::Reverse(year.end));
+    years
+}
+
+/// One Piotroski test; `passed` is `None` when a figure it needs wasn't reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Criterion {
+    pub name: &'static str,
+    pub passed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FScore {
+    pub criteria: Vec<Criterion>,
+}
+
+impl FScore {
+    /// Tests passed.
+    pub fn score(&self) -> usize {
+        self.criteria
+            .iter()
+            .filter(|c| c.passed == Some(true))
+            .count()
+    }
+
+    /// Tests that could be run.
+    pub fn evaluated(&self) -> usize {
+        self.criteria.iter().filter(|c| c.passed.is_some()).count()
+    }
+}
+
+/// The nine Piotroski tests for `current` against `prior`. Return on assets uses year-end
+/// assets, since the year before `prior` is often not in the data.
+pub fn piotroski(current: &FiscalYear, prior: &FiscalYear) -> FScore {
+    let roa = |year: &FiscalYear| year.ratio("NetIncome", "TotalAssets");
+    let leverage = |year: &FiscalYear| year.ratio("LongTermDebt", "TotalAssets");
+    let current_ratio = |year: &FiscalYear| year.ratio("CurrentAssets", "CurrentLiabilities");
+    let gross_margin = |year: &FiscalYear| year.ratio("GrossProfit", "TotalRevenue");
+    let turnover = |year: &FiscalYear| year.ratio("TotalRevenue", "TotalAssets");
+    let improved = |f: &dyn Fn(&FiscalYear) -> Option<f64>| Some(f(current)? > f(prior)?);
+    let cfo = current.get("OperatingCashFlow");
+
+    let criteria = vec![
+        Criterion {
+            name: "Positive return on assets",
+            passed: roa(current).map(|r| r > 0.0),
+        },
+        Criterion {
+            name: "Positive operating cash flow",
+            passed: cfo.map(|c| c > 0.0),
+        },
+        Criterion {
+            name: "Higher return on assets",
+            passed: improved(&roa),
+        },
+        Criterion {
+            name: "Operating cash flow above net income",
+            passed: cfo.zip(current.get("NetIncome")).map(|(c, n)| c > n),
+        },
+        Criterion {
+            name: "Lower long-term debt to assets",
+            // No long-term debt in either year counts as not having added any.
+            passed: match (leverage(current), leverage(prior)) {
+                (Some(now), Some(before)) => Some(now < before || now == 0.0),
+                (None, None) if current.get("TotalAssets").is_some() => Some(true),
+                _ => None,
+            },
+        },
+        Criterion {
+            name: "Higher current ratio",
+            passed: improved(&current_ratio),
+        },
+        Criterion {
+            name: "No new shares issued",
+            passed: current
+                .shares()
+                .zip(prior.shares())
+                .map(|(now, before)| now <= before),
+        },
+        Criterion {
+            name: "Higher gross margin",
+            passed: improved(&gross_margin),
+        },
+        Criterion {
+            name: "Higher asset turnover",
+            passed: improved(&turnover),
+        },
+    ];
+    FScore { criteria }
+}
+
+/// Which side of Altman's cut-offs a Z-score falls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZZone {
+    Safe,
+    Grey,
+    Distress,
+}
+
+impl ZZone {
+    pub fn of(z: f64) -> Self {
+        if z > Z_SAFE {
+            ZZone::Safe
+        } else if z >= Z_DISTRESS {
+            ZZone::Grey
+        } else {
+            ZZone::Distress
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ZZone::Safe => "safe",
+            ZZone::Grey => "grey zone",
+            ZZone::Distress => "distress",
+        }
+    }
+}
+
+/// The five weighted ratios and their sum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZScore {
+    pub working_capital: f64,
+    pub retained_earnings: f64,
+    pub ebit: f64,
+    pub market_value: f64,
+    pub sales: f64,
+    pub z: f64,
+}
+
+impl ZScore {
+    pub fn zone(&self) -> ZZone {
+        ZZone::of(self.z)
+    }
+}
+
+/// Altman's original Z-score, 1.2·WC/TA + 1.4·RE/TA + 3.3·EBIT/TA + 0.6·MVE/TL + 1.0·Sales/TA,
+/// with the market value of equity in the statements' currency. `None` when a figure is
+/// missing.
+pub fn altman(year: &FiscalYear, market_cap: f64) -> Option<ZScore> {
+    let assets = year.get("TotalAssets").filter(|a| *a > 0.0)?;
+    let liabilities = year
+        .get("TotalLiabilitiesNetMinorityInterest")
+        .filter(|l| *l > 0.0)?;
+    let working_capital = year
+        .get("WorkingCapital")
+        .or_else(|| Some(year.get("CurrentAssets")? - year.get("CurrentLiabilities")?))?;
+
+    let working_capital = working_capital / assets;
+    let retained_earnings = year.get("RetainedEarnings")? / assets;
+    let ebit = year.get("EBIT")? / assets;
+    let market_value = market_cap / liabilities;
+    let sales = year.get("TotalRevenue")? / assets;
+    Some(ZScore {
+        working_capital,
+        retained_earnings,
+        ebit,
+        market_value,
+        sales,
+        z: 1.2 * working_capital
+            + 1.4 * retained_earnings
+            + 3.3 * ebit
+            + 0.6 * market_value
+            + 1.0 * sales,
+    })
+}
+
+/// Both scores for a symbol, from its latest fiscal year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    pub fiscal_year: NaiveDate,
+    /// `None` without a prior year to compare with.
+    pub f_score: Option<FScore>,
+    /// `None` when a figure it needs or the market cap is missing.
+    pub z_score: Option<ZScore>,
+    /// Currency the statements report in, when Yahoo says.
+    pub currency: Option<String>,
+}
+
+/// Both scores from reshaped annual statements and the market cap, already in the statements'
+/// currency. `None` without any annual figures.
+pub fn quality_report(
+    statements: &[FinancialStatement],
+    market_cap: Option<f64>,
+) -> Option<QualityReport> {
+    let years = fiscal_years(statements);
+    let current = years.first()?;
+    Some(QualityReport {
+        fiscal_year: current.end,
+        f_score: years.get(1).map(|prior| piotroski(current, prior)),
+        z_score: market_cap.and_then(|cap| altman(current, cap)),
+        currency: reporting_currency(statements),
+    })
+}
+
+fn reporting_currency(statements: &[FinancialStatement]) -> Option<String> {
+    statements
+        .iter()
+        .flat_map(|s| s.statement.values())
+        .flat_map(|series| series.values())
+        .find_map(|v| v.get("currencyCode").and_then(|c| c.as_str()))
+        .map(str::to_string)
+}
+
+impl FinanceService {
+    /// F-score and Z-score for `symbol`. The market cap is converted to the statements'
+    /// currency when the listing trades in another one.
+    pub async fn quality(&self, symbol: &str) -> Result<QualityReport, FinanceServiceError> {
+        let (income, balance, cashflow, summary) = tokio::join!(
+            self.get_fundamentals_raw(
+                symbol,
+                StatementType::IncomeStatement,
+                Frequency::Annual,
+                ANNUAL_YEARS
+            ),
+            self.get_fundamentals_raw(
+                symbol,
+                StatementType::BalanceSheet,
+                Frequency::Annual,
+                ANNUAL_YEARS
+            ),
+            self.get_fundamentals_raw(
+                symbol,
+                StatementType::CashFlow,
+                Frequency::Annual,
+                ANNUAL_YEARS
+            ),
+            self.get_quote_summary(symbol, &["price"]),
+        );
+        let mut statements = reshape_timeseries_to_financial_statements(&income?);
+        statements.extend(reshape_timeseries_to_financial_statements(&balance?));
+        statements.extend(reshape_timeseries_to_financial_statements(&cashflow?));
+
+        let price = summary?.price.unwrap_or_default();
+        let market_cap = match (
+            price.market_cap,
+            price.currency,
+            reporting_currency(&statements),
+        ) {
+            (Some(cap), Some(quoted), Some(reported)) if quoted != reported => {
+                Some(self.conversion(&quoted, &reported).await?.apply(cap))
+            }
+            (cap, ..) => cap,
+        };
+        quality_report(&statements, market_cap)
+            .ok_or_else(|| FinanceServiceError::NotFound(symbol.to_string()))
+    }
+}
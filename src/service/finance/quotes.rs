@@ -37,6 +37,6 @@ pub fn extract_simple_quote(data: &Value) -> Option<crate::models::PriceQuote> {
 }
 
 pub fn map_error(err: YahooError) -> FinanceServiceError {
-    FinanceServiceError::Yahoo(err)
+    err.into()
 }
 
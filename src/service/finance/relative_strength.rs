@@ -0,0 +1,174 @@
+//! Relative strength against SPY for `/rs`: how far each watchlist symbol has out- or
+//! underperformed the benchmark over the last one and three months.
+
+use chrono::Months;
+use futures_util::stream::{self, StreamExt};
+use tracing::warn;
+
+use super::history::DailyClose;
+use super::{FinanceService, FinanceServiceError};
+
+/// Benchmark the symbols are measured against.
+pub const BENCHMARK: &str = "SPY";
+/// Yahoo chart range fetched per symbol; covers the three-month lookback with room to spare.
+pub const HISTORY_RANGE: &str = "6mo";
+/// Concurrent history lookups; an index-sized watchlist takes a minute or so.
+const LOOKUPS: usize = 8;
+
+/// What the ranking sorts by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ranking {
+    /// The average of the one- and three-month figures.
+    Blend,
+    OneMonth,
+    ThreeMonths,
+}
+
+impl Ranking {
+    pub const ALL: [Ranking; 3] = [Ranking::Blend, Ranking::OneMonth, Ranking::ThreeMonths];
+
+    /// Option value.
+    pub fn name(self) -> &'static str {
+        match self {
+            Ranking::Blend => "blend",
+            Ranking::OneMonth => "1m",
+            Ranking::ThreeMonths => "3m",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Ranking::Blend => "1 and 3 months",
+            Ranking::OneMonth => "1 month",
+            Ranking::ThreeMonths => "3 months",
+        }
+    }
+}
+
+/// Returns over the two lookbacks, as fractions; `None` where the history doesn't reach back.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Returns {
+    pub one_month: Option<f64>,
+    pub three_months: Option<f64>,
+}
+
+impl Returns {
+    pub fn from_closes(closes: &[DailyClose]) -> Self {
+        Returns {
+            one_month: trailing_return(closes, 1),
+            three_months: trailing_return(closes, 3),
+        }
+    }
+}
+
+/// One symbol's returns relative to the benchmark's: `(1 + stock) / (1 + SPY) - 1`, the change
+/// of the stock-to-SPY price ratio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolStrength {
+    pub symbol: String,
+    pub relative: Returns,
+}
+
+impl SymbolStrength {
+    pub fn new(symbol: impl Into<String>, returns: Returns, benchmark: Returns) -> Self {
+        let relative = |r: Option<f64>, b: Option<f64>| Some((1.0 + r?) / (1.0 + b?) - 1.0);
+        SymbolStrength {
+            symbol: symbol.into(),
+            relative: Returns {
+                one_month: relative(returns.one_month, benchmark.one_month),
+                three_months: relative(returns.three_months, benchmark.three_months),
+            },
+        }
+    }
+
+    /// The figure ranked on; the blend needs both lookbacks.
+    pub fn score(&self, ranking: Ranking) -> Option<f64> {
+        let Returns {
+            one_month,
+            three_months,
+        } = self.relative;
+        match ranking {
+            Ranking::Blend => Some((one_month? + three_months?) / 2.0),
+            Ranking::OneMonth => one_month,
+            Ranking::ThreeMonths => three_months,
+        }
+    }
+}
+
+/// The watchlist measured against the benchmark.
+#[derive(Debug, Clone, Default)]
+pub struct RelativeStrength {
+    pub benchmark: Returns,
+    /// In watchlist order.
+    pub symbols: Vec<SymbolStrength>,
+    /// Symbols whose history failed to load, sorted.
+    pub failed: Vec<String>,
+}
+
+impl RelativeStrength {
+    /// The symbols with a score, strongest first; ties keep watchlist order.
+    pub fn ranked(&self, ranking: Ranking) -> Vec<&SymbolStrength> {
+        let mut scored: Vec<(&SymbolStrength, f64)> = self
+            .symbols
+            .iter()
+            .filter_map(|s| Some((s, s.score(ranking)?)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().map(|(s, _)| s).collect()
+    }
+}
+
+impl FinanceService {
+    /// Relative strength of each of `symbols` against SPY. Histories are fetched a few at a
+    /// time; a symbol that fails lands in `failed`, while SPY failing fails the whole lookup.
+    pub async fn relative_strength(
+        &self,
+        symbols: &[String],
+    ) -> Result<RelativeStrength, FinanceServiceError> {
+        let lookups = stream::iter(symbols.iter().cloned().enumerate())
+            .map(|(i, symbol)| async move {
+                let closes = self
+                    .daily_closes(&symbol, HISTORY_RANGE)
+                    .await
+                    .inspect_err(|e| warn!("relative strength: {symbol} failed: {e}"));
+                (i, symbol, closes)
+            })
+            .buffer_unordered(LOOKUPS)
+            .collect::<Vec<_>>();
+        let (benchmark, mut results) =
+            tokio::join!(self.daily_closes(BENCHMARK, HISTORY_RANGE), lookups);
+        let benchmark = Returns::from_closes(&benchmark?);
+
+        results.sort_by_key(|(i, _, _)| *i);
+        let mut strength = RelativeStrength {
+            benchmark,
+            ..Default::default()
+        };
+        for (_, symbol, closes) in results {
+            match closes {
+                Ok(closes) => strength.symbols.push(SymbolStrength::new(
+                    symbol,
+                    Returns::from_closes(&closes),
+                    benchmark,
+                )),
+                Err(_) => strength.failed.push(symbol),
+            }
+        }
+        strength.failed.sort();
+        Ok(strength)
+    }
+}
+
+/// Return from the last close on or before `months` before the final close, to the final close.
+/// `None` when the history starts later than that.
+pub fn trailing_return(closes: &[DailyClose], months: u32) -> Option<f64> {
+    let last = closes.last()?;
+    let start = last.date.checked_sub_months(Months::new(months))?;
+    let after = closes.partition_point(|c| c.date <= start);
+    let base = closes[..after].last()?;
+    (base.close > 0.0).then(|| last.close / base.close - 1.0)
+}
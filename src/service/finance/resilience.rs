@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use finance_query_core::YahooError;
+use tracing::warn;
+
+use super::FinanceServiceError;
+use crate::service::metrics::{UpstreamOutcome, METRICS};
+
+/// Consecutive failed calls (after retries) before an endpoint's circuit opens.
+const FAILURE_THRESHOLD: u32 = 5;
+/// How long an open circuit rejects calls before letting a trial call through.
+const OPEN_FOR: Duration = Duration::from_secs(60);
+
+/// Exponential backoff for transient upstream failures.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first call.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying after the given (1-based) failed attempt.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Retries and per-endpoint circuit breakers shared by all `FinanceService` calls.
+#[derive(Debug, Default)]
+pub struct Resilience {
+    policy: RetryPolicy,
+    breakers: Mutex<HashMap<&'static str, Breaker>>,
+}
+
+impl Resilience {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            breakers: Mutex::default(),
+        }
+    }
+
+    /// Run `op`, retrying transient failures with backoff. Fails fast with
+    /// `FinanceServiceError::Unavailable` while `endpoint`'s circuit is open.
+    pub async fn call<T, E, F, Fut>(
+        &self,
+        endpoint: &'static str,
+        mut op: F,
+    ) -> Result<T, FinanceServiceError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<FinanceServiceError>,
+    {
+        if !self.allow(endpoint) {
+            METRICS.record_upstream_rejected(endpoint);
+            return Err(FinanceServiceError::Unavailable(endpoint));
+        }
+
+        let mut attempt = 1;
+        loop {
+            let started = Instant::now();
+            let result = op().await.map_err(Into::into);
+            METRICS.record_upstream_request(endpoint, started.elapsed(), outcome(&result));
+            match result {
+                Ok(value) => {
+                    self.record(endpoint, true);
+                    return Ok(value);
+                }
+                Err(err) if is_transient(&err) && attempt < self.policy.max_attempts => {
+                    let delay = self.policy.delay(attempt);
+                    warn!(
+                        "{endpoint} call failed (attempt {attempt}/{}): {err}; retrying in {delay:?}",
+                        self.policy.max_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => {
+                    // A definitive answer (e.g. not found) means the upstream is healthy.
+                    self.record(endpoint, !is_transient(&err));
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn allow(&self, endpoint: &'static str) -> bool {
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        match breakers.get(endpoint).and_then(|b| b.open_until) {
+            // Once the window passes, calls go through again; the failure count is kept, so
+            // a single failed trial reopens the circuit.
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    fn record(&self, endpoint: &'static str, ok: bool) {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let breaker = breakers.entry(endpoint).or_default();
+        if ok {
+            if breaker.open_until.is_some() {
+                METRICS.set_circuit_open(endpoint, false);
+            }
+            *breaker = Breaker::default();
+            return;
+        }
+
+        breaker.consecutive_failures += 1;
+        if breaker.consecutive_failures >= FAILURE_THRESHOLD {
+            warn!(
+                "{endpoint} failed {} times in a row; pausing calls for {OPEN_FOR:?}",
+                breaker.consecutive_failures
+            );
+            breaker.open_until = Some(Instant::now() + OPEN_FOR);
+            METRICS.set_circuit_open(endpoint, true);
+        }
+    }
+}
+
+fn outcome<T>(result: &Result<T, FinanceServiceError>) -> UpstreamOutcome {
+    match result {
+        Err(err) if is_rate_limit(err) => UpstreamOutcome::RateLimited,
+        Err(err) if is_transient(err) => UpstreamOutcome::Failed,
+        _ => UpstreamOutcome::Ok,
+    }
+}
+
+fn is_rate_limit(err: &FinanceServiceError) -> bool {
+    match err {
+        FinanceServiceError::Yahoo(err) => {
+            matches!(err, YahooError::RateLimited | YahooError::HttpError(429, _))
+        }
+        FinanceServiceError::Http(detail) => detail.contains("429"),
+        FinanceServiceError::Shared(err) => is_rate_limit(err),
+        _ => false,
+    }
+}
+
+fn is_transient(err: &FinanceServiceError) -> bool {
+    match err {
+        FinanceServiceError::Yahoo(err) => matches!(
+            err,
+            YahooError::RateLimited
+                | YahooError::NetworkError(_)
+                | YahooError::HttpError(429 | 500..=599, _)
+        ),
+        FinanceServiceError::Http(_)
+        | FinanceServiceError::Timeout(_)
+        | FinanceServiceError::Constituents(_) => true,
+        FinanceServiceError::Shared(err) => is_transient(err),
+        FinanceServiceError::NotFound(_)
+        | FinanceServiceError::Unavailable(_)
+        | FinanceServiceError::NoCoverage { .. } => false,
+    }
+}
@@ -0,0 +1,189 @@
+//! Risk statistics from daily closes for `/risk`: annualized volatility, max drawdown, Sharpe
+//! ratio, beta against a benchmark and distance from the all-time closing high.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use super::history::DailyClose;
+
+/// Sessions per year used to annualize daily figures.
+pub const TRADING_DAYS: f64 = 252.0;
+/// Benchmark the statistics are compared with.
+pub const BENCHMARK: &str = "SPY";
+
+/// How far back `/risk` measures; the all-time high always uses the whole history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    OneYear,
+    TwoYears,
+    FiveYears,
+    TenYears,
+}
+
+impl Period {
+    pub const ALL: [Period; 4] = [
+        Period::OneYear,
+        Period::TwoYears,
+        Period::FiveYears,
+        Period::TenYears,
+    ];
+
+    /// Option value and Yahoo chart range.
+    pub fn name(self) -> &'static str {
+        match self {
+            Period::OneYear => "1y",
+            Period::TwoYears => "2y",
+            Period::FiveYears => "5y",
+            Period::TenYears => "10y",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Period::OneYear => "1 year",
+            Period::TwoYears => "2 years",
+            Period::FiveYears => "5 years",
+            Period::TenYears => "10 years",
+        }
+    }
+
+    pub fn years(self) -> i32 {
+        match self {
+            Period::OneYear => 1,
+            Period::TwoYears => 2,
+            Period::FiveYears => 5,
+            Period::TenYears => 10,
+        }
+    }
+}
+
+/// Largest peak-to-trough fall between two closes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    pub peak: DailyClose,
+    pub trough: DailyClose,
+}
+
+impl Drawdown {
+    /// As a negative fraction: `-0.25` for a 25% fall.
+    pub fn change(&self) -> f64 {
+        self.trough.close / self.peak.close - 1.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskStats {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Compound annual growth from the first close to the last, as a fraction.
+    pub annual_return: f64,
+    /// Standard deviation of daily returns, annualized.
+    pub volatility: f64,
+    /// `None` when the series never fell.
+    pub max_drawdown: Option<Drawdown>,
+    /// Annualized mean daily return above `risk_free`, over volatility; `None` for a flat series.
+    pub sharpe: Option<f64>,
+}
+
+/// Statistics over `closes`, oldest first. `None` with fewer than three closes.
+pub fn risk_stats(closes: &[DailyClose], risk_free: f64) -> Option<RiskStats> {
+    let (first, last) = (closes.first()?, closes.last()?);
+    let returns: Vec<f64> = daily_returns(closes).into_iter().map(|(_, r)| r).collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance =
+        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let volatility = variance.sqrt() * TRADING_DAYS.sqrt();
+    let years = (last.date - first.date).num_days() as f64 / 365.25;
+    let growth = last.close / first.close;
+    let annual_return = if years > 0.0 {
+        growth.powf(1.0 / years) - 1.0
+    } else {
+        growth - 1.0
+    };
+    Some(RiskStats {
+        start: first.date,
+        end: last.date,
+        annual_return,
+        volatility,
+        max_drawdown: max_drawdown(closes),
+        sharpe: (volatility > 0.0).then(|| (mean * TRADING_DAYS - risk_free) / volatility),
+    })
+}
+
+/// Return from each close to the next, dated by the later close.
+pub fn daily_returns(closes: &[DailyClose]) -> Vec<(NaiveDate, f64)> {
+    closes
+        .windows(2)
+        .filter(|w| w[0].close > 0.0)
+        .map(|w| (w[1].date, w[1].close / w[0].close - 1.0))
+        .collect()
+}
+
+pub fn max_drawdown(closes: &[DailyClose]) -> Option<Drawdown> {
+    let mut peak = *closes.first()?;
+    let mut worst: Option<Drawdown> = None;
+    for close in closes {
+        if close.close > peak.close {
+            peak = *close;
+            continue;
+        }
+        let candidate = Drawdown {
+            peak,
+            trough: *close,
+        };
+        if candidate.change() < worst.map_or(0.0, |w| w.change()) {
+            worst = Some(candidate);
+        }
+    }
+    worst
+}
+
+/// Slope of the asset's daily returns on the benchmark's, over the dates both traded.
+pub fn beta(asset: &[DailyClose], benchmark: &[DailyClose]) -> Option<f64> {
+    let market: HashMap<NaiveDate, f64> = daily_returns(benchmark).into_iter().collect();
+    let pairs: Vec<(f64, f64)> = daily_returns(asset)
+        .into_iter()
+        .filter_map(|(date, r)| Some((r, *market.get(&date)?)))
+        .collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_m = pairs.iter().map(|(_, m)| m).sum::<f64>() / n;
+    let covariance = pairs
+        .iter()
+        .map(|(a, m)| (a - mean_a) * (m - mean_m))
+        .sum::<f64>();
+    let variance = pairs.iter().map(|(_, m)| (m - mean_m).powi(2)).sum::<f64>();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// The highest close on record.
+pub fn all_time_high(closes: &[DailyClose]) -> Option<DailyClose> {
+    closes
+        .iter()
+        .copied()
+        .max_by(|a, b| a.close.total_cmp(&b.close))
+}
+
+/// Closes from `years` before the last one on.
+pub fn trailing(closes: &[DailyClose], years: i32) -> &[DailyClose] {
+    let Some(last) = closes.last() else {
+        return closes;
+    };
+    let start = last
+        .date
+        .checked_sub_months(chrono::Months::new(12 * years as u32))
+        .unwrap_or(NaiveDate::MIN);
+    let first = closes.partition_point(|c| c.date < start);
+    &closes[first..]
+}
@@ -0,0 +1,46 @@
+use finance_query_core::{YahooError, YahooFinanceClient};
+use serde_json::Value;
+
+use crate::models::SymbolMatch;
+
+/// Look up symbols matching a ticker or company name.
+pub async fn search_symbols(
+    client: &YahooFinanceClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SymbolMatch>, YahooError> {
+    let data = client.search(query, limit).await?;
+    Ok(parse_quotes(&data, limit))
+}
+
+fn parse_quotes(data: &Value, limit: usize) -> Vec<SymbolMatch> {
+    let empty = Vec::new();
+    let quotes = data
+        .get("quotes")
+        .and_then(|q| q.as_array())
+        .unwrap_or(&empty);
+
+    quotes
+        .iter()
+        .filter_map(|quote| {
+            let symbol = quote.get("symbol")?.as_str()?.trim();
+            if symbol.is_empty() {
+                return None;
+            }
+            let text = |key: &str| {
+                quote
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+            Some(SymbolMatch {
+                symbol: symbol.to_uppercase(),
+                name: text("longname").or_else(|| text("shortname")),
+                exchange: text("exchDisp"),
+                quote_type: text("quoteType"),
+            })
+        })
+        .take(limit)
+        .collect()
+}
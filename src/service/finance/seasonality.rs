@@ -0,0 +1,121 @@
+//! Calendar-month returns from daily closes for `/seasonality`.
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use super::history::DailyClose;
+
+/// Yahoo chart range the statistics cover.
+pub const HISTORY_RANGE: &str = "10y";
+
+/// Close-to-close return of one calendar month, from the prior month's last close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonthReturn {
+    pub year: i32,
+    /// 1–12.
+    pub month: u32,
+    /// As a fraction: `0.05` for +5%.
+    pub change: f64,
+}
+
+/// One calendar month across the years.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonthStats {
+    /// Oldest first.
+    pub returns: Vec<MonthReturn>,
+}
+
+impl MonthStats {
+    pub fn average(&self) -> Option<f64> {
+        if self.returns.is_empty() {
+            return None;
+        }
+        Some(self.returns.iter().map(|r| r.change).sum::<f64>() / self.returns.len() as f64)
+    }
+
+    pub fn positive(&self) -> usize {
+        self.returns.iter().filter(|r| r.change > 0.0).count()
+    }
+
+    pub fn best(&self) -> Option<MonthReturn> {
+        self.returns
+            .iter()
+            .copied()
+            .max_by(|a, b| a.change.total_cmp(&b.change))
+    }
+
+    pub fn worst(&self) -> Option<MonthReturn> {
+        self.returns
+            .iter()
+            .copied()
+            .min_by(|a, b| a.change.total_cmp(&b.change))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Seasonality {
+    /// January first.
+    pub months: Vec<MonthStats>,
+    /// The month of the last close, which is still under way and left out of `months`.
+    pub current: Option<MonthReturn>,
+}
+
+impl Seasonality {
+    pub fn month(&self, month: u32) -> &MonthStats {
+        &self.months[month as usize - 1]
+    }
+
+    /// Years of history: the most full months on record for any calendar month.
+    pub fn years(&self) -> usize {
+        self.months
+            .iter()
+            .map(|m| m.returns.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Each month's return from the previous month's last close to its own. The first month in
+/// `closes`, and any after a month without closes, has no prior close and is skipped.
+pub fn monthly_returns(closes: &[DailyClose]) -> Vec<MonthReturn> {
+    let mut month_ends: Vec<(NaiveDate, f64)> = Vec::new();
+    for close in closes {
+        match month_ends.last_mut() {
+            Some((date, last)) if same_month(*date, close.date) => {
+                *date = close.date;
+                *last = close.close;
+            }
+            _ => month_ends.push((close.date, close.close)),
+        }
+    }
+    month_ends
+        .windows(2)
+        .filter(|w| {
+            w[0].0
+                .checked_add_months(Months::new(1))
+                .is_some_and(|next| same_month(next, w[1].0))
+        })
+        .map(|w| MonthReturn {
+            year: w[1].0.year(),
+            month: w[1].0.month(),
+            change: w[1].1 / w[0].1 - 1.0,
+        })
+        .collect()
+}
+
+/// Group returns by calendar month, treating the month of `today` as under way.
+pub fn seasonality(closes: &[DailyClose], today: NaiveDate) -> Seasonality {
+    let mut months = vec![MonthStats::default(); 12];
+    let mut current = None;
+    for r in monthly_returns(closes) {
+        if r.year == today.year() && r.month == today.month() {
+            current = Some(r);
+        } else {
+            months[r.month as usize - 1].returns.push(r);
+        }
+    }
+    Seasonality { months, current }
+}
+
+fn same_month(a: NaiveDate, b: NaiveDate) -> bool {
+    a.year() == b.year() && a.month() == b.month()
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use super::FinanceServiceError;
+
+type Shared<T> = Result<T, Arc<FinanceServiceError>>;
+
+/// Collapses concurrent identical requests into one upstream call.
+///
+/// The first caller for a key runs the fetch; callers arriving while it is in flight wait for
+/// and share its result. Nothing is cached once the call completes.
+pub struct SingleFlight<T> {
+    inflight: Mutex<HashMap<String, broadcast::Sender<Shared<T>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub async fn run<F, Fut>(&self, key: String, op: F) -> Result<T, FinanceServiceError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, FinanceServiceError>>,
+    {
+        let waiting = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            match inflight.get(&key) {
+                Some(tx) => Some(tx.subscribe()),
+                None => {
+                    inflight.insert(key.clone(), broadcast::channel(1).0);
+                    None
+                }
+            }
+        };
+
+        if let Some(mut rx) = waiting {
+            // A closed channel means the leading call was cancelled; fetch ourselves instead.
+            if let Ok(result) = rx.recv().await {
+                return result.map_err(FinanceServiceError::Shared);
+            }
+            return op().await;
+        }
+
+        let leader = Leader {
+            flight: self,
+            key,
+            finished: false,
+        };
+        let result = op().await.map_err(Arc::new);
+        if let Some(tx) = leader.finish() {
+            let _ = tx.send(result.clone());
+        }
+        result.map_err(|err| Arc::try_unwrap(err).unwrap_or_else(FinanceServiceError::Shared))
+    }
+}
+
+/// Removes the in-flight entry even if the leading call is dropped mid-fetch, so waiters are
+/// released instead of hanging.
+struct Leader<'a, T> {
+    flight: &'a SingleFlight<T>,
+    key: String,
+    finished: bool,
+}
+
+impl<T> Leader<'_, T> {
+    fn finish(mut self) -> Option<broadcast::Sender<Shared<T>>> {
+        self.finished = true;
+        self.remove()
+    }
+
+    fn remove(&self) -> Option<broadcast::Sender<Shared<T>>> {
+        let mut inflight = self
+            .flight
+            .inflight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        inflight.remove(&self.key)
+    }
+}
+
+impl<T> Drop for Leader<'_, T> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.remove();
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Multi-leg option strategies: leg parsing and payoff at expiration.
+
+use super::options::OptionKind;
+use crate::service::format;
+
+/// Shares per contract.
+pub const CONTRACT_MULTIPLIER: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    fn sign(self) -> f64 {
+        match self {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Side::Buy => "Buy",
+            Side::Sell => "Sell",
+        }
+    }
+}
+
+/// One leg as written, e.g. `sell 2 455C`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Leg {
+    pub side: Side,
+    pub quantity: u32,
+    pub strike: f64,
+    pub kind: OptionKind,
+}
+
+impl Leg {
+    /// `Sell 2 455C`
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {} {}{}",
+            self.side.label(),
+            self.quantity,
+            format::strike(self.strike),
+            self.kind.letter()
+        )
+    }
+
+    /// Value per share at expiration with the underlying at `price`.
+    fn intrinsic(&self, price: f64) -> f64 {
+        match self.kind {
+            OptionKind::Call => (price - self.strike).max(0.0),
+            OptionKind::Put => (self.strike - price).max(0.0),
+        }
+    }
+}
+
+/// Parse legs separated by commas or semicolons. Each is a side (`buy`/`sell`, `long`/`short`,
+/// `+`/`-`), an optional contract count, and a strike with `C` or `P`: `buy 1 450C, sell 1 455C`
+/// or `+450C -455C`.
+pub fn parse_legs(input: &str) -> Result<Vec<Leg>, String> {
+    let legs = input
+        .split([',', ';'])
+        .flat_map(split_signed)
+        .filter(|leg| !leg.trim().is_empty())
+        .map(parse_leg)
+        .collect::<Result<Vec<_>, _>>()?;
+    if legs.is_empty() {
+        return Err("add at least one leg, e.g. `buy 1 450C`".to_string());
+    }
+    Ok(legs)
+}
+
+/// `+450C -455C` written without commas is two legs.
+fn split_signed(part: &str) -> Vec<&str> {
+    let mut legs = Vec::new();
+    let mut start = 0;
+    for (i, c) in part.char_indices() {
+        if i > start && matches!(c, '+' | '-') && part[..i].ends_with(char::is_whitespace) {
+            legs.push(&part[start..i]);
+            start = i;
+        }
+    }
+    legs.push(&part[start..]);
+    legs
+}
+
+fn parse_leg(raw: &str) -> Result<Leg, String> {
+    let invalid = || format!("`{}` is not a leg like `buy 1 450C`", raw.trim());
+    let mut text = raw.trim().to_ascii_lowercase();
+    let side = if let Some(rest) = text.strip_prefix('+') {
+        text = rest.to_string();
+        Side::Buy
+    } else if let Some(rest) = text.strip_prefix('-') {
+        text = rest.to_string();
+        Side::Sell
+    } else {
+        let (word, rest) = text.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let side = match word {
+            "buy" | "b" | "long" => Side::Buy,
+            "sell" | "s" | "short" => Side::Sell,
+            _ => return Err(invalid()),
+        };
+        text = rest.to_string();
+        side
+    };
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (quantity, contract) = match words.as_slice() {
+        [contract] => (1, *contract),
+        [quantity, contract] => (
+            quantity
+                .trim_end_matches('x')
+                .parse::<u32>()
+                .map_err(|_| invalid())?,
+            *contract,
+        ),
+        _ => return Err(invalid()),
+    };
+    if quantity == 0 {
+        return Err(format!("`{}` has no contracts", raw.trim()));
+    }
+
+    let (strike, kind) = if let Some(strike) = contract.strip_suffix('c') {
+        (strike, OptionKind::Call)
+    } else if let Some(strike) = contract.strip_suffix('p') {
+        (strike, OptionKind::Put)
+    } else {
+        return Err(invalid());
+    };
+    let strike = strike
+        .trim_start_matches('$')
+        .parse::<f64>()
+        .ok()
+        .filter(|s| s.is_finite() && *s > 0.0)
+        .ok_or_else(invalid)?;
+
+    Ok(Leg {
+        side,
+        quantity,
+        strike,
+        kind,
+    })
+}
+
+/// A leg with the per-share premium it was priced at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PricedLeg {
+    pub leg: Leg,
+    pub premium: f64,
+}
+
+/// Payoff profile of a set of priced legs at expiration, per the whole position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payoff {
+    legs: Vec<PricedLeg>,
+}
+
+impl Payoff {
+    pub fn new(legs: Vec<PricedLeg>) -> Self {
+        Self { legs }
+    }
+
+    pub fn legs(&self) -> &[PricedLeg] {
+        &self.legs
+    }
+
+    /// Premium paid to open, positive for a debit and negative for a credit.
+    pub fn net_debit(&self) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| l.leg.side.sign() * l.leg.quantity as f64 * l.premium)
+            .sum::<f64>()
+            * CONTRACT_MULTIPLIER
+    }
+
+    /// Profit or loss at expiration with the underlying at `price`.
+    pub fn profit_at(&self, price: f64) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| {
+                l.leg.side.sign() * l.leg.quantity as f64 * (l.leg.intrinsic(price) - l.premium)
+            })
+            .sum::<f64>()
+            * CONTRACT_MULTIPLIER
+    }
+
+    /// Change in profit per $1 above the highest strike, where only calls still move.
+    fn upside_slope(&self) -> f64 {
+        self.legs
+            .iter()
+            .filter(|l| l.leg.kind == OptionKind::Call)
+            .map(|l| l.leg.side.sign() * l.leg.quantity as f64)
+            .sum::<f64>()
+            * CONTRACT_MULTIPLIER
+    }
+
+    /// Zero and every strike, ascending: the payoff is linear between them.
+    fn kinks(&self) -> Vec<f64> {
+        let mut prices: Vec<f64> = std::iter::once(0.0)
+            .chain(self.legs.iter().map(|l| l.leg.strike))
+            .collect();
+        prices.sort_by(f64::total_cmp);
+        prices.dedup();
+        prices
+    }
+
+    /// Largest profit, or `None` when it grows without bound as the underlying rises.
+    pub fn max_profit(&self) -> Option<f64> {
+        if self.upside_slope() > 0.0 {
+            return None;
+        }
+        self.kinks()
+            .into_iter()
+            .map(|p| self.profit_at(p))
+            .max_by(f64::total_cmp)
+    }
+
+    /// Largest loss as a negative amount, or `None` when it is unbounded.
+    pub fn max_loss(&self) -> Option<f64> {
+        if self.upside_slope() < 0.0 {
+            return None;
+        }
+        self.kinks()
+            .into_iter()
+            .map(|p| self.profit_at(p))
+            .min_by(f64::total_cmp)
+    }
+
+    /// Underlying prices at expiration where the position breaks even, ascending.
+    pub fn breakevens(&self) -> Vec<f64> {
+        let kinks = self.kinks();
+        let mut points: Vec<(f64, f64)> = kinks.iter().map(|p| (*p, self.profit_at(*p))).collect();
+        // One step past the last strike along the final slope, to catch a crossing there.
+        if let Some(&(last, profit)) = points.last() {
+            let slope = self.upside_slope();
+            if slope != 0.0 && profit.signum() != slope.signum() {
+                let beyond = last + profit.abs() / slope.abs() + 1.0;
+                points.push((beyond, self.profit_at(beyond)));
+            }
+        }
+
+        let mut breakevens = Vec::new();
+        for pair in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            if y0 == 0.0 {
+                breakevens.push(x0);
+            } else if y0.signum() != y1.signum() && y1 != 0.0 {
+                breakevens.push(x0 + (x1 - x0) * y0 / (y0 - y1));
+            }
+        }
+        if let Some(&(x, y)) = points.last() {
+            if y == 0.0 {
+                breakevens.push(x);
+            }
+        }
+        breakevens.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+        breakevens
+    }
+
+    /// Strikes spanned by the legs.
+    pub fn strike_range(&self) -> (f64, f64) {
+        self.legs
+            .iter()
+            .map(|l| l.leg.strike)
+            .fold((f64::INFINITY, 0.0), |(lo, hi), s| (lo.min(s), hi.max(s)))
+    }
+}
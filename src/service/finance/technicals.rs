@@ -0,0 +1,61 @@
+//! Indicators over a price series, oldest first. Each returns one value per input, `None` until
+//! the indicator has enough history.
+
+/// Simple moving average of the last `window` values.
+pub fn sma(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    if window == 0 {
+        return vec![None; values.len()];
+    }
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            sum += value;
+            if i >= window {
+                sum -= values[i - window];
+            }
+            (i + 1 >= window).then(|| sum / window as f64)
+        })
+        .collect()
+}
+
+/// Relative strength index over `period` changes with Wilder's smoothing, 0–100. A stretch
+/// without losses reads 100; one without any change reads 50.
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return out;
+    }
+    let changes: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let mut gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / period as f64;
+    let mut loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / period as f64;
+    out[period] = Some(rsi_value(gain, loss));
+    for (i, change) in changes.iter().enumerate().skip(period) {
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        out[i + 1] = Some(rsi_value(gain, loss));
+    }
+    out
+}
+
+fn rsi_value(gain: f64, loss: f64) -> f64 {
+    if loss == 0.0 {
+        return if gain == 0.0 { 50.0 } else { 100.0 };
+    }
+    100.0 - 100.0 / (1.0 + gain / loss)
+}
+
+/// Largest peak-to-trough decline as a negative fraction, e.g. `-0.25` for a 25% drop; `0.0`
+/// for a series that never fell.
+pub fn max_drawdown(values: &[f64]) -> f64 {
+    let mut peak = f64::NEG_INFINITY;
+    let mut worst = 0.0f64;
+    for &value in values {
+        peak = peak.max(value);
+        if peak > 0.0 {
+            worst = worst.min(value / peak - 1.0);
+        }
+    }
+    worst
+}
@@ -0,0 +1,105 @@
+//! Number formatting shared by commands and automations.
+
+const SUFFIXES: [(f64, &str); 4] = [
+    (1_000_000_000_000.0, "T"),
+    (1_000_000_000.0, "B"),
+    (1_000_000.0, "M"),
+    (1_000.0, "K"),
+];
+
+/// Scale to the largest fitting suffix: `94_930_000_000.0` → `94.93B`, `950.0` → `950.00`.
+pub fn compact(value: f64) -> String {
+    match suffix_for(value) {
+        Some((scale, suffix)) => format!("{:.2}{suffix}", value / scale),
+        None => format!("{value:.2}"),
+    }
+}
+
+/// Dollar amount with magnitude suffix: `-1_200_000.0` → `-$1.20M`.
+pub fn currency(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{sign}${}", compact(value.abs()))
+}
+
+/// Per-share amounts (EPS, dividends, prices) are never scaled: `1.456` → `$1.46`.
+pub fn per_share(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{sign}${:.2}", value.abs())
+}
+
+/// Amount in a named currency: dollars keep the `$` form, others get the ISO code
+/// (`1_200_000.0, "EUR"` → `1.20M EUR`).
+pub fn currency_in(value: f64, code: &str) -> String {
+    if code == "USD" {
+        return currency(value);
+    }
+    format!("{} {code}", compact(value))
+}
+
+/// Per-share amount in a named currency (`1.456, "JPY"` → `1.46 JPY`).
+pub fn per_share_in(value: f64, code: &str) -> String {
+    if code == "USD" {
+        return per_share(value);
+    }
+    format!("{value:.2} {code}")
+}
+
+/// Share counts: whole numbers below 1,000, otherwise scaled like [`compact`].
+pub fn shares(value: f64) -> String {
+    match suffix_for(value) {
+        Some(_) => compact(value),
+        None => format!("{value:.0}"),
+    }
+}
+
+/// A value already in percent units: `12.345` → `12.35%`.
+pub fn percent(value: f64) -> String {
+    format!("{value:.2}%")
+}
+
+/// A fraction rendered as a percentage: `0.12345` → `12.35%`.
+pub fn fraction_percent(value: f64) -> String {
+    percent(value * 100.0)
+}
+
+/// Option strikes drop the decimals when whole: `450.0` → `450`, `452.5` → `452.5`.
+pub fn strike(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value}")
+    }
+}
+
+/// Index levels keep two decimals with thousands separators: `42_012.5` → `42,012.50`.
+pub fn level(value: f64) -> String {
+    let fixed = format!("{:.2}", value.abs());
+    let (whole, decimals) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{sign}{}.{decimals}", group_thousands(whole))
+}
+
+/// Whole counts with thousands separators: `3412` → `3,412`.
+pub fn count(value: i64) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    format!(
+        "{sign}{}",
+        group_thousands(&value.unsigned_abs().to_string())
+    )
+}
+
+fn group_thousands(digits: &str) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn suffix_for(value: f64) -> Option<(f64, &'static str)> {
+    let abs = value.abs();
+    SUFFIXES.into_iter().find(|(scale, _)| abs >= *scale)
+}
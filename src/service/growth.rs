@@ -0,0 +1,73 @@
+//! Growth rates over dated figures: year-over-year change and compound annual growth, as
+//! fractions (`0.12` = +12%). Rates are only defined from a positive starting value; a loss
+//! turning into a profit has no meaningful percentage.
+
+use chrono::{Datelike, NaiveDate};
+
+/// How far a period end may drift from exactly one or three years earlier and still count
+/// as the same period. Fiscal years ending on "the last Saturday of September" move by days.
+const PERIOD_TOLERANCE_DAYS: i64 = 31;
+
+/// Growth of one value against the same period in earlier years.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Growth {
+    pub yoy: Option<f64>,
+    pub cagr_3y: Option<f64>,
+}
+
+impl Growth {
+    /// `YoY +8.1% · 3y CAGR +5.4%`, leaving out whichever is unknown; `None` when both are.
+    pub fn summary(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            self.yoy.map(|v| format!("YoY {}", signed_percent(v))),
+            self.cagr_3y
+                .map(|v| format!("3y CAGR {}", signed_percent(v))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+/// Change from `earlier` to `later`, or `None` when `earlier` isn't positive.
+pub fn change(earlier: f64, later: f64) -> Option<f64> {
+    (earlier > 0.0 && later.is_finite()).then(|| later / earlier - 1.0)
+}
+
+/// Compound annual growth rate from `start` to `end` over `years`, or `None` unless both
+/// values are positive and `years` is.
+pub fn cagr(start: f64, end: f64, years: f64) -> Option<f64> {
+    (start > 0.0 && end > 0.0 && years > 0.0).then(|| (end / start).powf(1.0 / years) - 1.0)
+}
+
+/// YoY change and 3-year CAGR of the value at `at` among `points`, from the values of the
+/// periods ending about one and three years before it. Missing history leaves a rate `None`.
+pub fn growth_at(points: &[(NaiveDate, f64)], at: NaiveDate) -> Growth {
+    let Some(current) = value_near(points, at, 0) else {
+        return Growth::default();
+    };
+    Growth {
+        yoy: value_near(points, at, 1).and_then(|earlier| change(earlier, current)),
+        cagr_3y: value_near(points, at, 3).and_then(|start| cagr(start, current, 3.0)),
+    }
+}
+
+/// The value of the period ending closest to `years` years before `at`, within
+/// [`PERIOD_TOLERANCE_DAYS`].
+fn value_near(points: &[(NaiveDate, f64)], at: NaiveDate, years: i32) -> Option<f64> {
+    let target = at.with_year(at.year() - years).or_else(|| {
+        // Feb 29 has no match in most years.
+        (at - chrono::Duration::days(1)).with_year(at.year() - years)
+    })?;
+    points
+        .iter()
+        .map(|(date, value)| ((*date - target).num_days().abs(), *value))
+        .filter(|(distance, _)| *distance <= PERIOD_TOLERANCE_DAYS)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, value)| value)
+}
+
+fn signed_percent(fraction: f64) -> String {
+    format!("{:+.1}%", fraction * 100.0)
+}
@@ -0,0 +1,115 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::service::caching::RedisCache;
+use crate::service::metrics::METRICS;
+use crate::service::shutdown::Shutdown;
+
+/// A Redis that takes longer than this to answer the PING counts as unreachable, so a hung
+/// connection can't hang the health check.
+const REDIS_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize)]
+struct HealthReport {
+    status: &'static str,
+    gateway_connected: bool,
+    /// `None` when Redis is not configured.
+    redis_reachable: Option<bool>,
+    /// Last successful run per automation (RFC 3339), `null` if it has not succeeded yet.
+    automations: Vec<AutomationRun>,
+}
+
+#[derive(Serialize)]
+struct AutomationRun {
+    task: &'static str,
+    last_success: Option<String>,
+}
+
+#[derive(Clone)]
+struct HealthState {
+    cache: Option<Arc<RedisCache>>,
+}
+
+/// Serve `/healthz` and `/metrics` on `addr` (`health.addr` / `HEALTH_ADDR`, e.g.
+/// `0.0.0.0:8080`).
+///
+/// Not started when no address is configured. `/healthz` returns 503 while the gateway is
+/// disconnected or Redis (when configured) does not answer a PING within two seconds.
+pub fn spawn_health_server(
+    addr: Option<SocketAddr>,
+    cache: Option<Arc<RedisCache>>,
+    shutdown: &Shutdown,
+) -> Option<JoinHandle<()>> {
+    let addr = addr?;
+
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/metrics", get(metrics))
+        .with_state(HealthState { cache });
+
+    let stop = shutdown.clone();
+    Some(shutdown.spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("failed to bind health server on {addr}: {err}");
+                return;
+            }
+        };
+        info!("Health server listening on {addr}");
+
+        let server = axum::serve(listener, app)
+            .with_graceful_shutdown(async move { stop.cancelled().await });
+        if let Err(err) = server.await {
+            error!("health server failed: {err}");
+        }
+    }))
+}
+
+async fn healthz(State(state): State<HealthState>) -> impl IntoResponse {
+    let gateway_connected = METRICS.gateway_connected();
+    let redis_reachable = match &state.cache {
+        Some(cache) => Some(matches!(
+            tokio::time::timeout(REDIS_PING_TIMEOUT, cache.ping()).await,
+            Ok(Ok(_))
+        )),
+        None => None,
+    };
+
+    let healthy = gateway_connected && redis_reachable.unwrap_or(true);
+    let report = HealthReport {
+        status: if healthy { "ok" } else { "degraded" },
+        gateway_connected,
+        redis_reachable,
+        automations: METRICS
+            .automation_runs()
+            .into_iter()
+            .map(|(task, last)| AutomationRun {
+                task,
+                last_success: last.map(|ts| ts.to_rfc3339()),
+            })
+            .collect(),
+    };
+
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+async fn metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        METRICS.render_prometheus(),
+    )
+}
@@ -0,0 +1,397 @@
+//! Translations for command registration and fixed response strings.
+//!
+//! Discord shows localized command names and descriptions from the user's client language;
+//! responses follow the guild's `/config language` setting. Text built from data (tickers,
+//! error details, report bodies) stays English for now.
+
+use serenity::all::{CreateCommand, GuildId};
+use tracing::warn;
+
+use crate::service::store::StateStore;
+
+/// A language the bot can answer in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+/// Discord locale codes registered for each language. Spanish has separate codes for Spain
+/// and Latin America; both get the same text.
+const SPANISH_DISCORD_LOCALES: [&str; 2] = ["es-ES", "es-419"];
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// Stable code used in the state store and `/config language`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|locale| locale.code() == code)
+    }
+
+    /// The language's own name, for choices and confirmations.
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+        }
+    }
+}
+
+/// The language `guild_id` picked, or English outside a guild, without a state store or when
+/// the setting can't be read.
+pub async fn guild_locale(store: Option<&dyn StateStore>, guild_id: Option<GuildId>) -> Locale {
+    let (Some(store), Some(guild_id)) = (store, guild_id) else {
+        return Locale::default();
+    };
+    match store.guild_locale(guild_id.get()).await {
+        Ok(code) => code
+            .as_deref()
+            .and_then(Locale::from_code)
+            .unwrap_or_default(),
+        Err(e) => {
+            warn!("failed to load language for guild {guild_id}: {e}");
+            Locale::default()
+        }
+    }
+}
+
+/// Fixed response strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    CommandNotImplemented,
+    RateLimited,
+    UpstreamTimeout,
+    Unavailable,
+    Internal,
+    /// Followed by a list of role mentions.
+    RestrictedToRoles,
+    LimitedTo,
+    OpenToEveryone,
+    NoRestrictions,
+    RestrictionsHeader,
+    AuditChannelSet,
+    AuditChannelOff,
+    TopicChannelSet,
+    TopicChannelOff,
+    LanguageSet,
+    /// Followed by the timezone name and the current time there.
+    GuildTimezoneSet,
+    UserTimezoneSet,
+    UserTimezoneCleared,
+    /// Followed by the filter's description.
+    SymbolFilterSet,
+    SymbolFilterCleared,
+    /// Followed by the symbol and the channel.
+    ChannelTickerSet,
+    ChannelTickerCleared,
+    /// Preceded by the route name, followed by the channel.
+    RouteSet,
+    /// Preceded by the route name.
+    RouteCleared,
+    NoRoutes,
+    RoutesHeader,
+}
+
+impl Text {
+    pub fn get(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (Text::CommandNotImplemented, Locale::En) => "Command not implemented.",
+            (Text::CommandNotImplemented, Locale::Es) => "Comando no implementado.",
+            (Text::RateLimited, Locale::En) => {
+                "⏳ The market data provider is rate limiting requests. Please try again in a minute."
+            }
+            (Text::RateLimited, Locale::Es) => {
+                "⏳ El proveedor de datos de mercado está limitando las solicitudes. Inténtalo de nuevo en un minuto."
+            }
+            (Text::UpstreamTimeout, Locale::En) => {
+                "⌛ The market data provider took too long to respond. Please try again later."
+            }
+            (Text::UpstreamTimeout, Locale::Es) => {
+                "⌛ El proveedor de datos de mercado tardó demasiado en responder. Inténtalo más tarde."
+            }
+            (Text::Unavailable, Locale::En) => {
+                "🚧 Data source temporarily unavailable. Please try again in a few minutes."
+            }
+            (Text::Unavailable, Locale::Es) => {
+                "🚧 Fuente de datos no disponible temporalmente. Inténtalo de nuevo en unos minutos."
+            }
+            (Text::Internal, Locale::En) => {
+                "❌ Something went wrong on our side. Please try again later."
+            }
+            (Text::Internal, Locale::Es) => {
+                "❌ Algo salió mal de nuestro lado. Inténtalo más tarde."
+            }
+            (Text::RestrictedToRoles, Locale::En) => "is limited to members with one of these roles:",
+            (Text::RestrictedToRoles, Locale::Es) => {
+                "está limitado a miembros con uno de estos roles:"
+            }
+            (Text::LimitedTo, Locale::En) => "is now limited to:",
+            (Text::LimitedTo, Locale::Es) => "ahora está limitado a:",
+            (Text::OpenToEveryone, Locale::En) => "is open to everyone.",
+            (Text::OpenToEveryone, Locale::Es) => "está abierto a todos.",
+            (Text::NoRestrictions, Locale::En) => "No commands are restricted in this server.",
+            (Text::NoRestrictions, Locale::Es) => "No hay comandos restringidos en este servidor.",
+            (Text::RestrictionsHeader, Locale::En) => {
+                "Restricted commands (Manage Server members can always run them)"
+            }
+            (Text::RestrictionsHeader, Locale::Es) => {
+                "Comandos restringidos (los miembros con Gestionar servidor siempre pueden usarlos)"
+            }
+            (Text::AuditChannelSet, Locale::En) => "📝 Audit log now posts to",
+            (Text::AuditChannelSet, Locale::Es) => "📝 El registro de auditoría ahora se publica en",
+            (Text::AuditChannelOff, Locale::En) => "📝 Audit log turned off.",
+            (Text::AuditChannelOff, Locale::Es) => "📝 Registro de auditoría desactivado.",
+            (Text::TopicChannelSet, Locale::En) => {
+                "📈 Index levels now show in the topic of"
+            }
+            (Text::TopicChannelSet, Locale::Es) => {
+                "📈 Los niveles de los índices ahora se muestran en el tema de"
+            }
+            (Text::TopicChannelOff, Locale::En) => "📈 Channel topic updates turned off.",
+            (Text::TopicChannelOff, Locale::Es) => {
+                "📈 Actualizaciones del tema del canal desactivadas."
+            }
+            (Text::LanguageSet, Locale::En) => "🌐 The bot now answers in",
+            (Text::LanguageSet, Locale::Es) => "🌐 El bot ahora responde en",
+            (Text::GuildTimezoneSet, Locale::En) => "🕒 Plain-text times on this server now use",
+            (Text::GuildTimezoneSet, Locale::Es) => {
+                "🕒 Las horas en texto plano de este servidor ahora usan"
+            }
+            (Text::UserTimezoneSet, Locale::En) => "🕒 Your timezone is now",
+            (Text::UserTimezoneSet, Locale::Es) => "🕒 Tu zona horaria ahora es",
+            (Text::UserTimezoneCleared, Locale::En) => {
+                "🕒 Your timezone is cleared; the server's setting applies again."
+            }
+            (Text::UserTimezoneCleared, Locale::Es) => {
+                "🕒 Se borró tu zona horaria; vuelve a aplicarse la del servidor."
+            }
+            (Text::SymbolFilterSet, Locale::En) => "🔎 Earnings and options posts now show",
+            (Text::SymbolFilterSet, Locale::Es) => {
+                "🔎 Las publicaciones de resultados y opciones ahora muestran"
+            }
+            (Text::SymbolFilterCleared, Locale::En) => {
+                "🔎 Symbol filter cleared; every symbol is shown again."
+            }
+            (Text::SymbolFilterCleared, Locale::Es) => {
+                "🔎 Filtro de símbolos borrado; se vuelven a mostrar todos los símbolos."
+            }
+            (Text::RouteSet, Locale::En) => "posts now go to",
+            (Text::RouteSet, Locale::Es) => "ahora se publica en",
+            (Text::RouteCleared, Locale::En) => {
+                "route cleared; those posts go to the bot's configured channel again."
+            }
+            (Text::RouteCleared, Locale::Es) => {
+                "ruta borrada; esas publicaciones vuelven al canal configurado del bot."
+            }
+            (Text::NoRoutes, Locale::En) => {
+                "No routes set; automation posts go to the bot's configured channels."
+            }
+            (Text::NoRoutes, Locale::Es) => {
+                "No hay rutas; las publicaciones automáticas van a los canales configurados del bot."
+            }
+            (Text::RoutesHeader, Locale::En) => "Notification routes",
+            (Text::RoutesHeader, Locale::Es) => "Rutas de notificaciones",
+            (Text::ChannelTickerSet, Locale::En) => {
+                "📌 Commands that leave out the ticker now use"
+            }
+            (Text::ChannelTickerSet, Locale::Es) => {
+                "📌 Los comandos sin ticker ahora usan"
+            }
+            (Text::ChannelTickerCleared, Locale::En) => {
+                "📌 This channel has no default ticker anymore."
+            }
+            (Text::ChannelTickerCleared, Locale::Es) => {
+                "📌 Este canal ya no tiene un ticker predeterminado."
+            }
+        }
+    }
+}
+
+/// Spanish command names and descriptions, by command name. Names stay lowercase with
+/// hyphens, as Discord requires; context-menu commands have no description.
+const SPANISH_COMMANDS: &[(&str, &str, &str)] = &[
+    ("ping", "ping", "Comprueba que el bot responde"),
+    ("quote", "cotizacion", "Cotización de un ticker"),
+    ("news", "noticias", "Últimos titulares de un ticker"),
+    (
+        "news-multi",
+        "noticias-varios",
+        "Último titular de hasta 10 tickers a la vez",
+    ),
+    (
+        "holders",
+        "accionistas",
+        "Principales accionistas de un ticker",
+    ),
+    (
+        "holders-trend",
+        "tendencia-accionistas",
+        "Propiedad institucional en los últimos cuatro trimestres",
+    ),
+    (
+        "etf",
+        "etf",
+        "Principales posiciones, sectores, comisión y activos de un ETF",
+    ),
+    (
+        "held-by",
+        "en-etfs",
+        "Qué ETFs seguidos tienen una acción entre sus diez mayores posiciones",
+    ),
+    (
+        "income",
+        "estado-resultados",
+        "Estado de resultados de un ticker",
+    ),
+    ("balance", "balance", "Balance general de un ticker"),
+    (
+        "cashflow",
+        "flujo-de-caja",
+        "Estado de flujo de caja de un ticker",
+    ),
+    (
+        "statement",
+        "estado-financiero",
+        "Descarga un estado financiero completo",
+    ),
+    (
+        "fundamentals-chart",
+        "grafico-fundamentales",
+        "Gráfico de ingresos, beneficio neto o flujo de caja libre por periodo",
+    ),
+    (
+        "dcf",
+        "dcf",
+        "Valor intrínseco por acción según flujos de caja descontados",
+    ),
+    (
+        "quality",
+        "calidad",
+        "Puntuación F de Piotroski y Z de Altman según los estados anuales",
+    ),
+    (
+        "backtest",
+        "backtest",
+        "Prueba una estrategia simple con precios pasados frente a comprar y mantener",
+    ),
+    (
+        "seasonality",
+        "estacionalidad",
+        "Rentabilidad media por mes del año en los últimos 10 años",
+    ),
+    (
+        "risk",
+        "riesgo",
+        "Volatilidad, caída máxima y ratio de Sharpe frente a SPY, y distancia del máximo",
+    ),
+    (
+        "rs",
+        "fuerza-relativa",
+        "Ordena la lista de símbolos del servidor por fuerza relativa a 1 y 3 meses frente a SPY",
+    ),
+    (
+        "stats",
+        "estadisticas",
+        "Uso de comandos en los últimos 7 días (solo administradores)",
+    ),
+    (
+        "weekly-earnings",
+        "resultados-semana",
+        "Calendario semanal de resultados",
+    ),
+    (
+        "daily-earnings",
+        "resultados-hoy",
+        "Resultados de hoy con movimiento implícito",
+    ),
+    (
+        "er-reports",
+        "informes-resultados",
+        "Resultados publicados antes o después del mercado",
+    ),
+    (
+        "earnings-ics",
+        "calendario-resultados",
+        "Descarga los resultados de la semana como calendario (.ics)",
+    ),
+    (
+        "implied-moves",
+        "movimientos-implicitos",
+        "Ordena los resultados del día por movimiento implícito",
+    ),
+    (
+        "expected-move",
+        "movimiento-esperado",
+        "Cono de movimiento esperado (±1σ) según las opciones",
+    ),
+    (
+        "oi-change",
+        "cambio-interes-abierto",
+        "Mayores aumentos y caídas diarias de interés abierto por strike",
+    ),
+    (
+        "vol-surface",
+        "superficie-volatilidad",
+        "Estructura temporal de la volatilidad implícita ATM por vencimiento",
+    ),
+    (
+        "strategy",
+        "estrategia",
+        "Resultado al vencimiento de una estrategia de opciones a precios actuales",
+    ),
+    (
+        "admin",
+        "admin",
+        "Gestiona las automatizaciones del bot (solo administradores)",
+    ),
+    (
+        "config",
+        "config",
+        "Ajustes del servidor para el bot (solo administradores)",
+    ),
+    (
+        "bind-ticker",
+        "fijar-ticker",
+        "Símbolo predeterminado de este canal para los comandos sin ticker",
+    ),
+    (
+        "event-alert",
+        "alerta-evento",
+        "Avisos cuando cambia la fecha de resultados o el precio objetivo de un ticker",
+    ),
+    (
+        "brief",
+        "resumen-matinal",
+        "Resumen matinal de tus tickers por mensaje directo los días laborables",
+    ),
+    (
+        "timezone",
+        "zona-horaria",
+        "Tu zona horaria para las horas en texto plano",
+    ),
+    ("Ask Stacks-bot", "Preguntar a Stacks-bot", ""),
+];
+
+/// Add the translated name and description to a command definition, if there are any.
+pub fn localize_command(command: CreateCommand, name: &str) -> CreateCommand {
+    let Some((_, es_name, es_description)) = SPANISH_COMMANDS.iter().find(|(n, ..)| *n == name)
+    else {
+        return command;
+    };
+    SPANISH_DISCORD_LOCALES.iter().fold(command, |cmd, locale| {
+        let cmd = cmd.name_localized(*locale, *es_name);
+        if es_description.is_empty() {
+            cmd
+        } else {
+            cmd.description_localized(*locale, *es_description)
+        }
+    })
+}
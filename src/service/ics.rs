@@ -0,0 +1,85 @@
+//! Minimal iCalendar (RFC 5545) writing for calendar file attachments: text escaping,
+//! 75-octet line folding and `\r\n` line endings.
+
+use chrono::{DateTime, Utc};
+
+/// Longest content line in octets before it is folded.
+const LINE_LIMIT: usize = 75;
+
+/// One `VEVENT`, in UTC.
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// Stable across exports, so re-importing updates the event instead of duplicating it.
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub duration_minutes: u32,
+    pub summary: String,
+    pub description: String,
+}
+
+/// Escape backslashes, semicolons, commas and line breaks in a TEXT value.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.replace("\r\n", "\n").chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// One content line, folded every 75 octets without splitting a character, with its `\r\n`.
+pub fn line(content: &str) -> String {
+    let mut out = String::with_capacity(content.len() + 2);
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            // The leading space of a continuation line counts towards its length.
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// A `VCALENDAR` named `name` holding `events`, stamped with `stamp`.
+pub fn calendar(name: &str, events: &[Event], stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    out.push_str(&line("BEGIN:VCALENDAR"));
+    out.push_str(&line("VERSION:2.0"));
+    out.push_str(&line("PRODID:-//stacks-bot//earnings//EN"));
+    out.push_str(&line("CALSCALE:GREGORIAN"));
+    out.push_str(&line("METHOD:PUBLISH"));
+    out.push_str(&line(&format!("X-WR-CALNAME:{}", escape(name))));
+    for event in events {
+        out.push_str(&line("BEGIN:VEVENT"));
+        out.push_str(&line(&format!("UID:{}", escape(&event.uid))));
+        out.push_str(&line(&format!("DTSTAMP:{}", timestamp(stamp))));
+        out.push_str(&line(&format!("DTSTART:{}", timestamp(event.start))));
+        out.push_str(&line(&format!("DURATION:PT{}M", event.duration_minutes)));
+        out.push_str(&line(&format!("SUMMARY:{}", escape(&event.summary))));
+        if !event.description.is_empty() {
+            out.push_str(&line(&format!(
+                "DESCRIPTION:{}",
+                escape(&event.description)
+            )));
+        }
+        out.push_str(&line("TRANSP:TRANSPARENT"));
+        out.push_str(&line("END:VEVENT"));
+    }
+    out.push_str(&line("END:VCALENDAR"));
+    out
+}
+
+/// UTC date-time form, e.g. `20261020T200000Z`.
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
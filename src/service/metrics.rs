@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+/// Process-wide metrics, rendered by the health server (see `service::health`).
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+/// Upper bounds (seconds) of the latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    count: u64,
+    sum: f64,
+    /// Cumulative counts per entry of `LATENCY_BUCKETS`.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+}
+
+impl Histogram {
+    fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        self.count += 1;
+        self.sum += secs;
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+
+    /// Write the `_bucket`, `_sum` and `_count` lines for one label set.
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(self.buckets) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CommandStats {
+    errors: u64,
+    latency: Histogram,
+}
+
+/// Metric name, help text and value of one per-endpoint upstream counter.
+type UpstreamCounter = (&'static str, &'static str, fn(&UpstreamStats) -> u64);
+
+/// How one upstream request (a single attempt, before any retry) ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamOutcome {
+    /// Success, or a definitive answer such as "not found".
+    Ok,
+    /// HTTP 429 or an explicit rate-limit response.
+    RateLimited,
+    /// Network errors, timeouts and 5xx responses.
+    Failed,
+}
+
+#[derive(Debug, Clone, Default)]
+struct UpstreamStats {
+    errors: u64,
+    rate_limited: u64,
+    /// Calls refused without a request while the circuit breaker was open.
+    rejected: u64,
+    circuit_open: bool,
+    latency: Histogram,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AutomationStats {
+    last_success: Option<DateTime<Utc>>,
+    failures: u64,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    shards: Mutex<BTreeMap<u32, bool>>,
+    commands: Mutex<BTreeMap<String, CommandStats>>,
+    upstream: Mutex<BTreeMap<&'static str, UpstreamStats>>,
+    automations: Mutex<BTreeMap<&'static str, AutomationStats>>,
+    /// Time spent drawing and encoding each kind of image (see `service::render`).
+    renders: Mutex<BTreeMap<&'static str, Histogram>>,
+}
+
+impl Metrics {
+    pub fn set_shard_connected(&self, shard: u32, connected: bool) {
+        let mut shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+        shards.insert(shard, connected);
+    }
+
+    /// True once every shard this process runs has connected and none has dropped since.
+    pub fn gateway_connected(&self) -> bool {
+        let shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+        !shards.is_empty() && shards.values().all(|connected| *connected)
+    }
+
+    pub fn record_command(&self, name: &str, elapsed: Duration, ok: bool) {
+        let mut commands = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = commands.entry(name.to_string()).or_default();
+        if !ok {
+            stats.errors += 1;
+        }
+        stats.latency.observe(elapsed);
+    }
+
+    /// Record one request to an upstream endpoint (`quote`, `earnings`, ...).
+    pub fn record_upstream_request(
+        &self,
+        endpoint: &'static str,
+        elapsed: Duration,
+        outcome: UpstreamOutcome,
+    ) {
+        let mut upstream = self.upstream.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = upstream.entry(endpoint).or_default();
+        stats.latency.observe(elapsed);
+        match outcome {
+            UpstreamOutcome::Ok => {}
+            UpstreamOutcome::RateLimited => {
+                stats.errors += 1;
+                stats.rate_limited += 1;
+            }
+            UpstreamOutcome::Failed => stats.errors += 1,
+        }
+    }
+
+    /// Count a call refused because the endpoint's circuit breaker is open.
+    pub fn record_upstream_rejected(&self, endpoint: &'static str) {
+        let mut upstream = self.upstream.lock().unwrap_or_else(|e| e.into_inner());
+        upstream.entry(endpoint).or_default().rejected += 1;
+    }
+
+    pub fn set_circuit_open(&self, endpoint: &'static str, open: bool) {
+        let mut upstream = self.upstream.lock().unwrap_or_else(|e| e.into_inner());
+        upstream.entry(endpoint).or_default().circuit_open = open;
+    }
+
+    /// Record how long one image (`calendar`, `statement`, ...) took to draw and encode.
+    pub fn record_render(&self, image: &'static str, elapsed: Duration) {
+        let mut renders = self.renders.lock().unwrap_or_else(|e| e.into_inner());
+        renders.entry(image).or_default().observe(elapsed);
+    }
+
+    pub fn record_automation_success(&self, task: &'static str) {
+        let mut automations = self.automations.lock().unwrap_or_else(|e| e.into_inner());
+        automations.entry(task).or_default().last_success = Some(Utc::now());
+    }
+
+    pub fn record_automation_failure(&self, task: &'static str) {
+        let mut automations = self.automations.lock().unwrap_or_else(|e| e.into_inner());
+        automations.entry(task).or_default().failures += 1;
+    }
+
+    /// Last successful run per automation task.
+    pub fn automation_runs(&self) -> BTreeMap<&'static str, Option<DateTime<Utc>>> {
+        let automations = self.automations.lock().unwrap_or_else(|e| e.into_inner());
+        automations
+            .iter()
+            .map(|(task, stats)| (*task, stats.last_success))
+            .collect()
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP bot_gateway_connected Whether every gateway shard is connected."
+        );
+        let _ = writeln!(out, "# TYPE bot_gateway_connected gauge");
+        let _ = writeln!(
+            out,
+            "bot_gateway_connected {}",
+            u8::from(self.gateway_connected())
+        );
+        {
+            let shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(
+                out,
+                "# HELP bot_shard_connected Whether each gateway shard is connected."
+            );
+            let _ = writeln!(out, "# TYPE bot_shard_connected gauge");
+            for (shard, connected) in shards.iter() {
+                let _ = writeln!(
+                    out,
+                    "bot_shard_connected{{shard=\"{shard}\"}} {}",
+                    u8::from(*connected)
+                );
+            }
+        }
+
+        {
+            let commands = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(out, "# HELP bot_commands_total Slash commands handled.");
+            let _ = writeln!(out, "# TYPE bot_commands_total counter");
+            for (name, stats) in commands.iter() {
+                let _ = writeln!(
+                    out,
+                    "bot_commands_total{{command=\"{name}\"}} {}",
+                    stats.latency.count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "# HELP bot_command_errors_total Slash commands that returned an error."
+            );
+            let _ = writeln!(out, "# TYPE bot_command_errors_total counter");
+            for (name, stats) in commands.iter() {
+                let _ = writeln!(
+                    out,
+                    "bot_command_errors_total{{command=\"{name}\"}} {}",
+                    stats.errors
+                );
+            }
+            let _ = writeln!(
+                out,
+                "# HELP bot_command_duration_seconds Slash command latency."
+            );
+            let _ = writeln!(out, "# TYPE bot_command_duration_seconds histogram");
+            for (name, stats) in commands.iter() {
+                stats.latency.render(
+                    &mut out,
+                    "bot_command_duration_seconds",
+                    &format!("command=\"{name}\""),
+                );
+            }
+        }
+
+        {
+            let upstream = self.upstream.lock().unwrap_or_else(|e| e.into_inner());
+            let counters: [UpstreamCounter; 4] = [
+                (
+                    "bot_upstream_requests_total",
+                    "Requests sent to upstream endpoints, retries included.",
+                    |s| s.latency.count,
+                ),
+                (
+                    "bot_upstream_errors_total",
+                    "Upstream requests that failed with a network, 5xx or rate-limit error.",
+                    |s| s.errors,
+                ),
+                (
+                    "bot_upstream_rate_limited_total",
+                    "Upstream requests rejected with a rate limit.",
+                    |s| s.rate_limited,
+                ),
+                (
+                    "bot_upstream_rejected_total",
+                    "Calls refused without a request while the endpoint's circuit was open.",
+                    |s| s.rejected,
+                ),
+            ];
+            for (name, help, value) in counters {
+                let _ = writeln!(out, "# HELP {name} {help}");
+                let _ = writeln!(out, "# TYPE {name} counter");
+                for (endpoint, stats) in upstream.iter() {
+                    let _ = writeln!(out, "{name}{{endpoint=\"{endpoint}\"}} {}", value(stats));
+                }
+            }
+            let _ = writeln!(
+                out,
+                "# HELP bot_upstream_circuit_open Whether the endpoint's circuit breaker is open."
+            );
+            let _ = writeln!(out, "# TYPE bot_upstream_circuit_open gauge");
+            for (endpoint, stats) in upstream.iter() {
+                let _ = writeln!(
+                    out,
+                    "bot_upstream_circuit_open{{endpoint=\"{endpoint}\"}} {}",
+                    u8::from(stats.circuit_open)
+                );
+            }
+            let _ = writeln!(
+                out,
+                "# HELP bot_upstream_duration_seconds Upstream request latency."
+            );
+            let _ = writeln!(out, "# TYPE bot_upstream_duration_seconds histogram");
+            for (endpoint, stats) in upstream.iter() {
+                stats.latency.render(
+                    &mut out,
+                    "bot_upstream_duration_seconds",
+                    &format!("endpoint=\"{endpoint}\""),
+                );
+            }
+        }
+
+        {
+            let renders = self.renders.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(
+                out,
+                "# HELP bot_render_duration_seconds Time to draw and encode an image."
+            );
+            let _ = writeln!(out, "# TYPE bot_render_duration_seconds histogram");
+            for (image, latency) in renders.iter() {
+                latency.render(
+                    &mut out,
+                    "bot_render_duration_seconds",
+                    &format!("image=\"{image}\""),
+                );
+            }
+        }
+
+        {
+            let automations = self.automations.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(
+                out,
+                "# HELP bot_automation_last_success_timestamp_seconds Unix time of the last successful automation run."
+            );
+            let _ = writeln!(
+                out,
+                "# TYPE bot_automation_last_success_timestamp_seconds gauge"
+            );
+            for (task, stats) in automations.iter() {
+                if let Some(ts) = stats.last_success {
+                    let _ = writeln!(
+                        out,
+                        "bot_automation_last_success_timestamp_seconds{{task=\"{task}\"}} {}",
+                        ts.timestamp()
+                    );
+                }
+            }
+            let _ = writeln!(
+                out,
+                "# HELP bot_automation_failures_total Failed automation runs."
+            );
+            let _ = writeln!(out, "# TYPE bot_automation_failures_total counter");
+            for (task, stats) in automations.iter() {
+                let _ = writeln!(
+                    out,
+                    "bot_automation_failures_total{{task=\"{task}\"}} {}",
+                    stats.failures
+                );
+            }
+        }
+
+        out
+    }
+}
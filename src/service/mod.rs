@@ -1,4 +1,23 @@
+pub mod audit;
 pub mod automation;
 pub mod caching;
+pub mod chunks;
 pub mod command;
+pub mod config;
+pub mod csv;
+pub mod error_reporting;
 pub mod finance;
+pub mod format;
+pub mod growth;
+pub mod health;
+pub mod i18n;
+pub mod ics;
+pub mod metrics;
+pub mod quickchart;
+pub mod render;
+pub mod routing;
+pub mod shutdown;
+pub mod sparkline;
+pub mod store;
+pub mod symbol_filter;
+pub mod timefmt;
@@ -0,0 +1,48 @@
+//! Chart.js charts rendered to PNG by quickchart.io.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+const RENDER_URL: &str = "https://quickchart.io/chart";
+/// A chart that takes longer is dropped and the reply goes out as text, with a note.
+const RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(RENDER_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Render a Chart.js config (`{"type": ..., "data": ..., "options": ...}`) on a white
+/// background. Time axes can use the date-fns adapter.
+pub async fn render(chart: Value, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let body = json!({
+        "chart": chart,
+        "width": width,
+        "height": height,
+        "backgroundColor": "white",
+        "plugins": ["chartjs-adapter-date-fns"],
+        "version": "4.4.0"
+    });
+
+    let resp = CLIENT
+        .post(RENDER_URL)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("chart request failed: {e}"))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!("chart service status {status}: {text}"));
+    }
+
+    resp.bytes()
+        .await
+        .map(|b| b.to_vec())
+        .map_err(|e| format!("chart bytes error: {e}"))
+}
@@ -0,0 +1,110 @@
+//! Image rendering off the async runtime. Drawing a calendar or statement (text layout,
+//! Lanczos resizing, encoding) takes long enough on large images to hold up the gateway
+//! heartbeat, so it runs on tokio's blocking pool, timed into `bot_render_duration_seconds`.
+//!
+//! Rendered images are encoded in the `[images]` format and scaled down while the file is
+//! over `images.max_bytes`, so a packed calendar still fits Discord's upload limit.
+
+use std::io::Cursor;
+use std::time::Instant;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::DynamicImage;
+use tracing::{info, warn};
+
+use crate::service::config::{ImageConfig, ImageEncoding};
+use crate::service::metrics::METRICS;
+
+/// Each downscale step keeps this fraction of the width and height.
+const DOWNSCALE_STEP: f64 = 0.75;
+/// Narrower than this and card text stops being legible; an image this small goes out as is.
+const MIN_WIDTH: u32 = 320;
+
+/// An encoded image ready to attach.
+#[derive(Debug, Clone)]
+pub struct EncodedImage {
+    pub bytes: Vec<u8>,
+    pub encoding: ImageEncoding,
+}
+
+impl EncodedImage {
+    /// `stem` with the extension of the encoding: `earnings-calendar.webp`.
+    pub fn file_name(&self, stem: &str) -> String {
+        format!("{stem}.{}", self.encoding.extension())
+    }
+}
+
+/// Run `draw` on the blocking pool and record how long it took under `image`.
+pub async fn blocking<T, F>(image: &'static str, draw: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let started = Instant::now();
+        let result = draw();
+        METRICS.record_render(image, started.elapsed());
+        result
+    })
+    .await
+    .map_err(|e| format!("{image} render failed: {e}"))?
+}
+
+/// Encode `image` as `config.format`, scaling it down while the result is over
+/// `config.max_bytes`. An image that still doesn't fit at the minimum width is returned anyway
+/// and the upload is left to fail.
+pub fn encode(image: &DynamicImage, config: &ImageConfig) -> Result<EncodedImage, String> {
+    let mut bytes = encode_once(image, config)?;
+    let mut scaled = None;
+    while bytes.len() as u64 > config.max_bytes {
+        let current: &DynamicImage = scaled.as_ref().unwrap_or(image);
+        let width = (current.width() as f64 * DOWNSCALE_STEP) as u32;
+        let height = (current.height() as f64 * DOWNSCALE_STEP) as u32;
+        if width < MIN_WIDTH || height == 0 {
+            warn!(
+                "{}x{} image is {} bytes, over the {} byte limit at the smallest size",
+                current.width(),
+                current.height(),
+                bytes.len(),
+                config.max_bytes
+            );
+            break;
+        }
+        let smaller = current.resize_exact(width, height, FilterType::Lanczos3);
+        bytes = encode_once(&smaller, config)?;
+        scaled = Some(smaller);
+    }
+    if let Some(scaled) = &scaled {
+        info!(
+            "Scaled {}x{} image to {}x{} to fit {} bytes",
+            image.width(),
+            image.height(),
+            scaled.width(),
+            scaled.height(),
+            config.max_bytes
+        );
+    }
+    Ok(EncodedImage {
+        bytes,
+        encoding: config.format,
+    })
+}
+
+fn encode_once(image: &DynamicImage, config: &ImageConfig) -> Result<Vec<u8>, String> {
+    let mut buffer = Vec::new();
+    let result = match config.format {
+        ImageEncoding::Png => image.write_with_encoder(PngEncoder::new(Cursor::new(&mut buffer))),
+        ImageEncoding::Webp => {
+            image.write_with_encoder(WebPEncoder::new_lossless(Cursor::new(&mut buffer)))
+        }
+        // JPEG has no alpha channel.
+        ImageEncoding::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(
+            JpegEncoder::new_with_quality(Cursor::new(&mut buffer), config.jpeg_quality),
+        ),
+    };
+    result.map_err(|e| format!("failed to encode {}: {e}", config.format.extension()))?;
+    Ok(buffer)
+}
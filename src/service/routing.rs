@@ -0,0 +1,114 @@
+//! Per-guild notification routes (`/config routes`): which channel each category of automation
+//! posts goes to, e.g. earnings → #earnings, options → #options, insider and dividend scans →
+//! #alerts and automation failures → #bot-ops.
+//!
+//! A guild's route wins over the channel in the bot's config (`channels.*`), which stays the
+//! destination for guilds that route nothing.
+
+use std::collections::HashMap;
+
+use serenity::all::{ChannelId, GuildId};
+use tracing::warn;
+
+use crate::service::store::StateStore;
+
+/// A category of automation posts a guild can send to its own channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Route {
+    /// Weekly calendar, daily and post-earnings reports and the weekly recap.
+    Earnings,
+    /// SPY options pings.
+    Options,
+    /// Insider net-buying scans and split and dividend alerts. Price alerts and `/event-alert`s
+    /// are not routed: each one posts in the channel it was set for, which a server-wide route
+    /// would silently override.
+    Alerts,
+    /// Automation failures, instead of the audit channel.
+    Errors,
+}
+
+impl Route {
+    pub const ALL: [Route; 4] = [
+        Route::Earnings,
+        Route::Options,
+        Route::Alerts,
+        Route::Errors,
+    ];
+
+    /// Stable identifier used in `/config routes`, the state store and exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Route::Earnings => "earnings",
+            Route::Options => "options",
+            Route::Alerts => "alerts",
+            Route::Errors => "errors",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|route| route.name() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Route::Earnings => "Earnings calendars, reports and recaps",
+            Route::Options => "SPY options pings",
+            Route::Alerts => "Insider buying and split/dividend alerts (not price or event alerts)",
+            Route::Errors => "Automation failures",
+        }
+    }
+}
+
+/// The guild's channel for `route`, if it set one. A store error counts as no route.
+pub async fn guild_route(
+    store: Option<&dyn StateStore>,
+    guild_id: GuildId,
+    route: Route,
+) -> Option<ChannelId> {
+    let store = store?;
+    match store.guild_routes(guild_id.get()).await {
+        Ok(routes) => routes.get(route.name()).copied().map(ChannelId::new),
+        Err(e) => {
+            warn!("failed to load routes for guild {guild_id}: {e}");
+            None
+        }
+    }
+}
+
+/// Every guild's channel for `route`. Empty without a store or when the routes can't be read.
+pub async fn route_channels(
+    store: Option<&dyn StateStore>,
+    route: Route,
+) -> HashMap<GuildId, ChannelId> {
+    let Some(store) = store else {
+        return HashMap::new();
+    };
+    match store.route_channels(route.name()).await {
+        Ok(channels) => channels
+            .into_iter()
+            .map(|(guild_id, channel_id)| (GuildId::new(guild_id), ChannelId::new(channel_id)))
+            .collect(),
+        Err(e) => {
+            warn!("failed to load {} routes: {e}", route.name());
+            HashMap::new()
+        }
+    }
+}
+
+/// Where a scheduled post goes: every routed channel, plus the configured `fallback` channel
+/// unless its guild routes the category itself. `fallback` carries the guild that owns the
+/// channel, when known. Sorted by channel id, without duplicates.
+pub fn targets(
+    routed: &HashMap<GuildId, ChannelId>,
+    fallback: Option<(ChannelId, Option<GuildId>)>,
+) -> Vec<ChannelId> {
+    let mut channels: Vec<ChannelId> = routed.values().copied().collect();
+    if let Some((channel_id, guild_id)) = fallback {
+        if !guild_id.is_some_and(|guild_id| routed.contains_key(&guild_id)) {
+            channels.push(channel_id);
+        }
+    }
+    channels.sort();
+    channels.dedup();
+    channels
+}
@@ -0,0 +1,99 @@
+use std::{future::Future, time::Duration};
+
+use serenity::all::{ChannelId, Http};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{info, warn};
+
+use crate::service::config::ShutdownConfig;
+
+/// How long background tasks get to finish in-flight work once shutdown starts.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(15);
+
+/// Coordinates shutdown of background tasks (automation posters, alert streams).
+///
+/// Tasks started with [`Shutdown::spawn`] are tracked; loops should select on
+/// [`Shutdown::cancelled`] between iterations so work already in progress (Discord posts,
+/// Redis writes) completes before the task exits.
+#[derive(Clone, Default)]
+pub struct Shutdown {
+    token: CancellationToken,
+    tracker: TaskTracker,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn<F>(&self, task: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(task)
+    }
+
+    /// Resolves once shutdown has been requested.
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Signal every task to stop, then wait up to `grace` for them to finish.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.token.cancel();
+        self.tracker.close();
+        if tokio::time::timeout(grace, self.tracker.wait())
+            .await
+            .is_err()
+        {
+            warn!(
+                "{} background task(s) still running after {:?}; exiting anyway",
+                self.tracker.len(),
+                grace
+            );
+        }
+    }
+}
+
+/// Wait for Ctrl-C or, on Unix, SIGTERM (sent by container runtimes on stop).
+pub async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(err) => {
+                warn!("failed to install SIGTERM handler: {err}");
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Post a "bot restarting" notice when `shutdown.notice_channel` is configured.
+pub async fn post_shutdown_notice(http: &Http, config: &ShutdownConfig) {
+    let Some(channel_id) = config.notice_channel.map(ChannelId::new) else {
+        return;
+    };
+
+    match channel_id.say(http, &config.notice).await {
+        Ok(_) => info!("Posted shutdown notice to channel {}", channel_id),
+        Err(err) => warn!("failed to post shutdown notice: {err}"),
+    }
+}
@@ -0,0 +1,102 @@
+//! Small price sparklines attached to `/quote` and `/news` replies: the last week of daily
+//! closes as a line, green when the week is up and red when it is down.
+
+use std::io::Cursor;
+
+use image::{ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing::{draw_antialiased_line_segment_mut, draw_filled_circle_mut};
+use imageproc::pixelops::interpolate;
+
+use crate::service::finance::FinanceService;
+
+/// Daily closes drawn, latest last.
+pub const SESSIONS: usize = 7;
+/// Chart range fetched; a month always holds seven sessions.
+const RANGE: &str = "1mo";
+const WIDTH: u32 = 240;
+const HEIGHT: u32 = 64;
+/// Keeps the line and the end dot off the edges.
+const PADDING: f32 = 6.0;
+
+const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const UP_COLOR: Rgba<u8> = Rgba([46, 160, 67, 255]);
+const DOWN_COLOR: Rgba<u8> = Rgba([218, 54, 51, 255]);
+const BASELINE_COLOR: Rgba<u8> = Rgba([220, 220, 220, 255]);
+
+/// The sparkline PNG for `symbol`'s last [`SESSIONS`] closes.
+pub async fn for_symbol(finance: &FinanceService, symbol: &str) -> Result<Vec<u8>, String> {
+    let closes = finance
+        .daily_closes(symbol, RANGE)
+        .await
+        .map_err(|e| format!("no price history for {symbol}: {e}"))?;
+    let skip = closes.len().saturating_sub(SESSIONS);
+    let values: Vec<f64> = closes[skip..].iter().map(|c| c.close).collect();
+    render(&values)
+}
+
+/// Pixel positions of `closes` in a `width` × `height` image: evenly spaced left to right,
+/// the highest close at the top. A flat series sits in the middle.
+pub fn points(closes: &[f64], width: u32, height: u32) -> Vec<(f32, f32)> {
+    let min = closes.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = closes.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    let inner_w = width as f32 - 2.0 * PADDING;
+    let inner_h = height as f32 - 2.0 * PADDING;
+    let step = inner_w / (closes.len().max(2) - 1) as f32;
+    closes
+        .iter()
+        .enumerate()
+        .map(|(i, close)| {
+            let level = if span > 0.0 {
+                ((close - min) / span) as f32
+            } else {
+                0.5
+            };
+            (PADDING + i as f32 * step, PADDING + (1.0 - level) * inner_h)
+        })
+        .collect()
+}
+
+/// Draw `closes` as a sparkline with a faint baseline at the first close.
+pub fn render(closes: &[f64]) -> Result<Vec<u8>, String> {
+    if closes.len() < 2 {
+        return Err("not enough closes for a sparkline".to_string());
+    }
+    let color = if closes[closes.len() - 1] >= closes[0] {
+        UP_COLOR
+    } else {
+        DOWN_COLOR
+    };
+    let points = points(closes, WIDTH, HEIGHT);
+    let pixel = |(x, y): (f32, f32)| (x.round() as i32, y.round() as i32);
+
+    let mut img = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    let baseline = points[0].1.round() as i32;
+    draw_antialiased_line_segment_mut(
+        &mut img,
+        (PADDING as i32, baseline),
+        ((WIDTH as f32 - PADDING) as i32, baseline),
+        BASELINE_COLOR,
+        interpolate,
+    );
+    for pair in points.windows(2) {
+        let (start, end) = (pixel(pair[0]), pixel(pair[1]));
+        // Two passes a pixel apart read as a 2px line at this size.
+        for offset in [0, 1] {
+            draw_antialiased_line_segment_mut(
+                &mut img,
+                (start.0, start.1 + offset),
+                (end.0, end.1 + offset),
+                color,
+                interpolate,
+            );
+        }
+    }
+    draw_filled_circle_mut(&mut img, pixel(points[points.len() - 1]), 3, color);
+
+    let mut buffer = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|e| format!("failed to encode png: {e}"))?;
+    Ok(buffer)
+}
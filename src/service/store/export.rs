@@ -0,0 +1,193 @@
+//! One guild's stored state as a JSON document, for `/admin export` and `/admin import`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{StateStore, StoreError};
+use crate::service::caching::collections::price_alerts::PriceAlert;
+use crate::service::symbol_filter::SymbolFilter;
+
+/// Bumped whenever a field changes meaning; imports of other versions are refused.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildExport {
+    pub version: u32,
+    pub guild_id: u64,
+    pub exported_at: DateTime<Utc>,
+    /// `/config restrict` settings: command name to allowed role ids.
+    #[serde(default)]
+    pub command_roles: BTreeMap<String, Vec<u64>>,
+    #[serde(default)]
+    pub paused_jobs: BTreeSet<String>,
+    /// `/config audit-channel`.
+    #[serde(default)]
+    pub audit_channel: Option<u64>,
+    /// `/config topic-channel`.
+    #[serde(default)]
+    pub topic_channel: Option<u64>,
+    /// `/config language`.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// `/config timezone`.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// `/config symbols`.
+    #[serde(default)]
+    pub symbol_filter: Option<SymbolFilter>,
+    /// `/config routes`: route name to channel id.
+    #[serde(default)]
+    pub routes: BTreeMap<String, u64>,
+    /// Price alerts posting into this guild.
+    #[serde(default)]
+    pub price_alerts: Vec<PriceAlert>,
+}
+
+impl GuildExport {
+    /// Every channel the import would post into or manage.
+    pub fn channel_ids(&self) -> BTreeSet<u64> {
+        self.audit_channel
+            .into_iter()
+            .chain(self.topic_channel)
+            .chain(self.routes.values().copied())
+            .chain(
+                self.price_alerts
+                    .iter()
+                    .map(|alert| alert.target_channel_id),
+            )
+            .collect()
+    }
+
+    /// Channels outside `guild_id`, given the guild each channel resolved to. The file is
+    /// user-editable, so a channel that could not be resolved counts as foreign too.
+    pub fn foreign_channels(&self, guild_id: u64, channel_guilds: &BTreeMap<u64, u64>) -> Vec<u64> {
+        self.channel_ids()
+            .into_iter()
+            .filter(|channel_id| channel_guilds.get(channel_id) != Some(&guild_id))
+            .collect()
+    }
+}
+
+/// What an import wrote.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub command_roles: usize,
+    pub paused_jobs: usize,
+    pub price_alerts: usize,
+}
+
+pub async fn export_guild(
+    store: &dyn StateStore,
+    guild_id: u64,
+) -> Result<GuildExport, StoreError> {
+    let price_alerts = store
+        .price_alerts()
+        .await?
+        .into_values()
+        .flatten()
+        .filter(|alert| alert.target_guild_id == guild_id)
+        .collect();
+    Ok(GuildExport {
+        version: EXPORT_VERSION,
+        guild_id,
+        exported_at: Utc::now(),
+        command_roles: store.all_command_roles(guild_id).await?,
+        paused_jobs: store.paused_jobs(guild_id).await?.into_iter().collect(),
+        audit_channel: store.audit_channel(guild_id).await?,
+        topic_channel: store.topic_channel(guild_id).await?,
+        locale: store.guild_locale(guild_id).await?,
+        timezone: store.guild_timezone(guild_id).await?,
+        symbol_filter: store.guild_symbol_filter(guild_id).await?,
+        routes: store.guild_routes(guild_id).await?,
+        price_alerts,
+    })
+}
+
+/// Replace the guild's stored state with `export`. Anything the export does not list is
+/// removed, so importing a fresh export restores the guild exactly; other guilds' state is
+/// left alone.
+pub async fn import_guild(
+    store: &dyn StateStore,
+    guild_id: u64,
+    export: &GuildExport,
+) -> Result<ImportSummary, StoreError> {
+    let mut summary = ImportSummary::default();
+
+    for command in store.all_command_roles(guild_id).await?.into_keys() {
+        if !export.command_roles.contains_key(&command) {
+            store.set_command_roles(guild_id, &command, &[]).await?;
+        }
+    }
+    for (command, roles) in &export.command_roles {
+        store.set_command_roles(guild_id, command, roles).await?;
+        if !roles.is_empty() {
+            summary.command_roles += 1;
+        }
+    }
+
+    for job in store.paused_jobs(guild_id).await? {
+        if !export.paused_jobs.contains(&job) {
+            store.set_job_paused(guild_id, &job, false).await?;
+        }
+    }
+    for job in &export.paused_jobs {
+        store.set_job_paused(guild_id, job, true).await?;
+        summary.paused_jobs += 1;
+    }
+
+    store
+        .set_audit_channel(guild_id, export.audit_channel)
+        .await?;
+    store
+        .set_topic_channel(guild_id, export.topic_channel)
+        .await?;
+    store
+        .set_guild_locale(guild_id, export.locale.as_deref())
+        .await?;
+    store
+        .set_guild_timezone(guild_id, export.timezone.as_deref())
+        .await?;
+    store
+        .set_guild_symbol_filter(guild_id, export.symbol_filter.as_ref())
+        .await?;
+
+    for route in store.guild_routes(guild_id).await?.into_keys() {
+        if !export.routes.contains_key(&route) {
+            store.set_guild_route(guild_id, &route, None).await?;
+        }
+    }
+    for (route, channel_id) in &export.routes {
+        store
+            .set_guild_route(guild_id, route, Some(*channel_id))
+            .await?;
+    }
+
+    let mut alerts = store.price_alerts().await?;
+    let mut touched = BTreeSet::new();
+    for (symbol, list) in alerts.iter_mut() {
+        let before = list.len();
+        list.retain(|alert| alert.target_guild_id != guild_id);
+        if list.len() != before {
+            touched.insert(symbol.clone());
+        }
+    }
+    for alert in &export.price_alerts {
+        if alert.target_guild_id != guild_id {
+            continue;
+        }
+        alerts
+            .entry(alert.symbol.clone())
+            .or_default()
+            .push(alert.clone());
+        touched.insert(alert.symbol.clone());
+        summary.price_alerts += 1;
+    }
+    for symbol in touched {
+        let list = alerts.get(&symbol).map(Vec::as_slice).unwrap_or_default();
+        store.save_price_alerts(&symbol, list).await?;
+    }
+
+    Ok(summary)
+}
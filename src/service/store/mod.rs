@@ -0,0 +1,279 @@
+//! Persistent bot state behind one interface.
+//!
+//! Redis stays the default backend; `storage.backend = "sqlite"` or `"postgres"` keeps the
+//! same state in a SQL database instead (migrations in `migrations/` run on connect).
+//! Callers take `Option<&dyn StateStore>` and fall back to in-memory state when it is `None`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serenity::async_trait;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::models::EarningsRecord;
+use crate::service::caching::collections::corporate_actions::CorporateActionsCacheError;
+use crate::service::caching::collections::earnings_records::EarningsRecordStoreError;
+use crate::service::caching::collections::earnings_threads::EarningsThreadStoreError;
+use crate::service::caching::collections::event_alerts::{EventAlert, EventAlertStoreError};
+use crate::service::caching::collections::guild_settings::GuildSettingsError;
+use crate::service::caching::collections::morning_briefs::{MorningBrief, MorningBriefStoreError};
+use crate::service::caching::collections::ownership::OwnershipCacheError;
+use crate::service::caching::collections::price_alerts::{PriceAlert, PriceAlertStoreError};
+use crate::service::caching::RedisCache;
+use crate::service::config::{StorageBackend, StorageConfig};
+use crate::service::finance::corporate_actions::CorporateActions;
+use crate::service::finance::ownership::OwnershipSnapshot;
+use crate::service::symbol_filter::SymbolFilter;
+
+pub mod export;
+mod redis;
+mod sql;
+
+pub use self::redis::RedisStore;
+pub use self::sql::SqlStore;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error(transparent)]
+    GuildSettings(#[from] GuildSettingsError),
+    #[error(transparent)]
+    EarningsThreads(#[from] EarningsThreadStoreError),
+    #[error(transparent)]
+    EarningsRecords(#[from] EarningsRecordStoreError),
+    #[error(transparent)]
+    PriceAlerts(#[from] PriceAlertStoreError),
+    #[error(transparent)]
+    EventAlerts(#[from] EventAlertStoreError),
+    #[error(transparent)]
+    MorningBriefs(#[from] MorningBriefStoreError),
+    #[error(transparent)]
+    Ownership(#[from] OwnershipCacheError),
+    #[error(transparent)]
+    CorporateActions(#[from] CorporateActionsCacheError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("database error: {0}")]
+    Sql(#[from] sqlx::Error),
+    #[error("database migration failed: {0}")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+}
+
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Short backend name for logs, e.g. "redis" or "sqlite".
+    fn backend(&self) -> &'static str;
+
+    /// Roles allowed to run `command` in a guild. Empty means everyone may run it.
+    async fn command_roles(&self, guild_id: u64, command: &str) -> Result<Vec<u64>, StoreError>;
+
+    /// Every restricted command in a guild with its allowed roles.
+    async fn all_command_roles(
+        &self,
+        guild_id: u64,
+    ) -> Result<BTreeMap<String, Vec<u64>>, StoreError>;
+
+    /// Replace the allowed roles for `command`; an empty list lifts the restriction.
+    async fn set_command_roles(
+        &self,
+        guild_id: u64,
+        command: &str,
+        roles: &[u64],
+    ) -> Result<(), StoreError>;
+
+    /// Automations paused in a guild, by job name.
+    async fn paused_jobs(&self, guild_id: u64) -> Result<HashSet<String>, StoreError>;
+
+    async fn set_job_paused(
+        &self,
+        guild_id: u64,
+        job: &str,
+        paused: bool,
+    ) -> Result<(), StoreError>;
+
+    /// Channel the guild's audit log posts to.
+    async fn audit_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError>;
+
+    /// Set or, with `None`, clear the guild's audit channel.
+    async fn set_audit_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError>;
+
+    /// Channel whose topic the bot keeps updated with index levels (`/config topic-channel`).
+    async fn topic_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError>;
+
+    /// Topic channels of every guild that set one, by guild id.
+    async fn topic_channels(&self) -> Result<HashMap<u64, u64>, StoreError>;
+
+    /// Set or, with `None`, clear the guild's topic channel.
+    async fn set_topic_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError>;
+
+    /// Channels the guild sends each category of automation posts to (`/config routes`), by
+    /// route name.
+    async fn guild_routes(&self, guild_id: u64) -> Result<BTreeMap<String, u64>, StoreError>;
+
+    /// Every guild's channel for `route`, by guild id.
+    async fn route_channels(&self, route: &str) -> Result<HashMap<u64, u64>, StoreError>;
+
+    /// Route or, with `None`, unroute one category of the guild's automation posts.
+    async fn set_guild_route(
+        &self,
+        guild_id: u64,
+        route: &str,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError>;
+
+    /// Language code the guild answers in (`/config language`); `None` means English.
+    async fn guild_locale(&self, guild_id: u64) -> Result<Option<String>, StoreError>;
+
+    async fn set_guild_locale(&self, guild_id: u64, locale: Option<&str>)
+        -> Result<(), StoreError>;
+
+    /// IANA timezone for times the guild sees as plain text (`/config timezone`); `None`
+    /// means US Eastern.
+    async fn guild_timezone(&self, guild_id: u64) -> Result<Option<String>, StoreError>;
+
+    async fn set_guild_timezone(
+        &self,
+        guild_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError>;
+
+    /// Symbols the guild's automations and calendars show (`/config symbols`).
+    async fn guild_symbol_filter(&self, guild_id: u64) -> Result<Option<SymbolFilter>, StoreError>;
+
+    /// Set or, with `None`, clear the guild's symbol filter.
+    async fn set_guild_symbol_filter(
+        &self,
+        guild_id: u64,
+        filter: Option<&SymbolFilter>,
+    ) -> Result<(), StoreError>;
+
+    /// A user's own IANA timezone (`/timezone`), which overrides the guild's.
+    async fn user_timezone(&self, user_id: u64) -> Result<Option<String>, StoreError>;
+
+    async fn set_user_timezone(
+        &self,
+        user_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError>;
+
+    /// Symbol commands in a channel default to (`/bind-ticker`).
+    async fn channel_ticker(&self, channel_id: u64) -> Result<Option<String>, StoreError>;
+
+    /// Bind or, with `None`, unbind the channel's default symbol.
+    async fn set_channel_ticker(
+        &self,
+        channel_id: u64,
+        ticker: Option<&str>,
+    ) -> Result<(), StoreError>;
+
+    /// Thread created for a trading day's earnings reports.
+    async fn earnings_thread(&self, day: NaiveDate) -> Result<Option<u64>, StoreError>;
+
+    async fn save_earnings_thread(&self, day: NaiveDate, thread_id: u64) -> Result<(), StoreError>;
+
+    /// Merge a report's observations into the stored record for its day and symbol.
+    async fn save_earnings_record(&self, record: &EarningsRecord) -> Result<(), StoreError>;
+
+    /// Records for reports from `from` through `to`.
+    async fn earnings_records(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<EarningsRecord>, StoreError>;
+
+    /// All price alerts, by symbol.
+    async fn price_alerts(&self) -> Result<HashMap<String, Vec<PriceAlert>>, StoreError>;
+
+    /// Replace a symbol's alerts; an empty slice removes the symbol.
+    async fn save_price_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[PriceAlert],
+    ) -> Result<(), StoreError>;
+
+    /// Every `/event-alert` definition, by symbol. The snapshots the alerts compare against are
+    /// left at their default; they live in the cache.
+    async fn event_alerts(&self) -> Result<HashMap<String, Vec<EventAlert>>, StoreError>;
+
+    /// Replace a symbol's event alerts; an empty slice removes the symbol.
+    async fn save_event_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[EventAlert],
+    ) -> Result<(), StoreError>;
+
+    /// Every `/brief` subscription, by user id.
+    async fn morning_briefs(&self) -> Result<HashMap<u64, MorningBrief>, StoreError>;
+
+    async fn morning_brief(&self, user_id: u64) -> Result<Option<MorningBrief>, StoreError>;
+
+    /// Store the user's brief, replacing an earlier one.
+    async fn save_morning_brief(&self, brief: &MorningBrief) -> Result<(), StoreError>;
+
+    /// Remove the user's brief; `false` when there was none.
+    async fn delete_morning_brief(&self, user_id: u64) -> Result<bool, StoreError>;
+
+    /// Store a symbol's ownership for a quarter, replacing an earlier snapshot of the same
+    /// quarter since late filings keep updating it, and drop all but the latest
+    /// [`KEEP_QUARTERS`](crate::service::finance::ownership::KEEP_QUARTERS).
+    async fn save_ownership_snapshot(
+        &self,
+        symbol: &str,
+        snapshot: &OwnershipSnapshot,
+    ) -> Result<(), StoreError>;
+
+    /// Every stored ownership snapshot of `symbol`, oldest quarter first.
+    async fn ownership_snapshots(&self, symbol: &str)
+        -> Result<Vec<OwnershipSnapshot>, StoreError>;
+
+    /// The dividend and split state the split and dividend alerts saw for `symbol` on their
+    /// previous run, or `None` for a symbol not seen in the last 90 days.
+    async fn corporate_actions_state(
+        &self,
+        symbol: &str,
+    ) -> Result<Option<CorporateActions>, StoreError>;
+
+    /// Store the state seen for `symbol` on this run.
+    async fn save_corporate_actions_state(
+        &self,
+        symbol: &str,
+        state: &CorporateActions,
+    ) -> Result<(), StoreError>;
+}
+
+/// Open the store selected by `config`. The Redis backend reuses `cache` and is `None` when
+/// Redis is not configured; SQL backends fail loudly rather than running without persistence.
+pub async fn open(
+    config: &StorageConfig,
+    cache: Option<Arc<RedisCache>>,
+) -> Result<Option<Arc<dyn StateStore>>, StoreError> {
+    let store: Arc<dyn StateStore> = match config.backend {
+        StorageBackend::Redis => match cache {
+            Some(cache) => Arc::new(RedisStore::new(cache)),
+            None => {
+                warn!(
+                    "No state store: Redis is not configured, so server settings, paused jobs \
+                     and price alerts are kept in memory and lost on restart, and ownership \
+                     history, event alerts, morning briefs and split and dividend alerts are off. Set redis.url or storage.backend = \"sqlite\" \
+                     to persist them."
+                );
+                return Ok(None);
+            }
+        },
+        StorageBackend::Sqlite | StorageBackend::Postgres => {
+            let url = config.sql_url();
+            Arc::new(SqlStore::connect(&url).await?)
+        }
+    };
+    info!("State store: {}", store.backend());
+    Ok(Some(store))
+}
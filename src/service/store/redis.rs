@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serenity::async_trait;
+
+use super::{StateStore, StoreError};
+use crate::models::EarningsRecord;
+use crate::service::caching::collections::event_alerts::EventAlert;
+use crate::service::caching::collections::morning_briefs::MorningBrief;
+use crate::service::caching::collections::price_alerts::PriceAlert;
+use crate::service::caching::collections::{
+    corporate_actions, earnings_records, earnings_threads, event_alerts, guild_settings,
+    morning_briefs, ownership, price_alerts,
+};
+use crate::service::caching::RedisCache;
+use crate::service::finance::corporate_actions::CorporateActions;
+use crate::service::finance::ownership::OwnershipSnapshot;
+use crate::service::symbol_filter::SymbolFilter;
+
+/// The original Redis layout, via the `caching::collections` modules.
+pub struct RedisStore {
+    cache: Arc<RedisCache>,
+}
+
+impl RedisStore {
+    pub fn new(cache: Arc<RedisCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl StateStore for RedisStore {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn command_roles(&self, guild_id: u64, command: &str) -> Result<Vec<u64>, StoreError> {
+        Ok(guild_settings::load_command_roles(&self.cache, guild_id, command).await?)
+    }
+
+    async fn all_command_roles(
+        &self,
+        guild_id: u64,
+    ) -> Result<BTreeMap<String, Vec<u64>>, StoreError> {
+        Ok(guild_settings::load_all_command_roles(&self.cache, guild_id).await?)
+    }
+
+    async fn set_command_roles(
+        &self,
+        guild_id: u64,
+        command: &str,
+        roles: &[u64],
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_command_roles(&self.cache, guild_id, command, roles).await?)
+    }
+
+    async fn paused_jobs(&self, guild_id: u64) -> Result<HashSet<String>, StoreError> {
+        Ok(guild_settings::load_paused_jobs(&self.cache, guild_id).await?)
+    }
+
+    async fn set_job_paused(
+        &self,
+        guild_id: u64,
+        job: &str,
+        paused: bool,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_job_paused(&self.cache, guild_id, job, paused).await?)
+    }
+
+    async fn audit_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError> {
+        Ok(guild_settings::load_audit_channel(&self.cache, guild_id).await?)
+    }
+
+    async fn set_audit_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_audit_channel(&self.cache, guild_id, channel_id).await?)
+    }
+
+    async fn topic_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError> {
+        Ok(guild_settings::load_topic_channel(&self.cache, guild_id).await?)
+    }
+
+    async fn topic_channels(&self) -> Result<HashMap<u64, u64>, StoreError> {
+        Ok(guild_settings::load_topic_channels(&self.cache).await?)
+    }
+
+    async fn set_topic_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_topic_channel(&self.cache, guild_id, channel_id).await?)
+    }
+
+    async fn guild_routes(&self, guild_id: u64) -> Result<BTreeMap<String, u64>, StoreError> {
+        Ok(guild_settings::load_guild_routes(&self.cache, guild_id).await?)
+    }
+
+    async fn route_channels(&self, route: &str) -> Result<HashMap<u64, u64>, StoreError> {
+        Ok(guild_settings::load_route_channels(&self.cache, route).await?)
+    }
+
+    async fn set_guild_route(
+        &self,
+        guild_id: u64,
+        route: &str,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_guild_route(&self.cache, guild_id, route, channel_id).await?)
+    }
+
+    async fn guild_locale(&self, guild_id: u64) -> Result<Option<String>, StoreError> {
+        Ok(guild_settings::load_locale(&self.cache, guild_id).await?)
+    }
+
+    async fn set_guild_locale(
+        &self,
+        guild_id: u64,
+        locale: Option<&str>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_locale(&self.cache, guild_id, locale).await?)
+    }
+
+    async fn guild_timezone(&self, guild_id: u64) -> Result<Option<String>, StoreError> {
+        Ok(guild_settings::load_timezone(&self.cache, guild_id).await?)
+    }
+
+    async fn set_guild_timezone(
+        &self,
+        guild_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_timezone(&self.cache, guild_id, timezone).await?)
+    }
+
+    async fn guild_symbol_filter(&self, guild_id: u64) -> Result<Option<SymbolFilter>, StoreError> {
+        Ok(guild_settings::load_symbol_filter(&self.cache, guild_id).await?)
+    }
+
+    async fn set_guild_symbol_filter(
+        &self,
+        guild_id: u64,
+        filter: Option<&SymbolFilter>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_symbol_filter(&self.cache, guild_id, filter).await?)
+    }
+
+    async fn user_timezone(&self, user_id: u64) -> Result<Option<String>, StoreError> {
+        Ok(guild_settings::load_user_timezone(&self.cache, user_id).await?)
+    }
+
+    async fn set_user_timezone(
+        &self,
+        user_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_user_timezone(&self.cache, user_id, timezone).await?)
+    }
+
+    async fn channel_ticker(&self, channel_id: u64) -> Result<Option<String>, StoreError> {
+        Ok(guild_settings::load_channel_ticker(&self.cache, channel_id).await?)
+    }
+
+    async fn set_channel_ticker(
+        &self,
+        channel_id: u64,
+        ticker: Option<&str>,
+    ) -> Result<(), StoreError> {
+        Ok(guild_settings::set_channel_ticker(&self.cache, channel_id, ticker).await?)
+    }
+
+    async fn earnings_thread(&self, day: NaiveDate) -> Result<Option<u64>, StoreError> {
+        Ok(earnings_threads::load_thread(&self.cache, day).await?)
+    }
+
+    async fn save_earnings_thread(&self, day: NaiveDate, thread_id: u64) -> Result<(), StoreError> {
+        Ok(earnings_threads::save_thread(&self.cache, day, thread_id).await?)
+    }
+
+    async fn save_earnings_record(&self, record: &EarningsRecord) -> Result<(), StoreError> {
+        Ok(earnings_records::save_record(&self.cache, record).await?)
+    }
+
+    async fn earnings_records(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<EarningsRecord>, StoreError> {
+        Ok(earnings_records::load_range(&self.cache, from, to).await?)
+    }
+
+    async fn price_alerts(&self) -> Result<HashMap<String, Vec<PriceAlert>>, StoreError> {
+        Ok(price_alerts::load_all(&self.cache).await?)
+    }
+
+    async fn save_price_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[PriceAlert],
+    ) -> Result<(), StoreError> {
+        Ok(price_alerts::save_symbol_alerts(&self.cache, symbol, alerts).await?)
+    }
+
+    async fn event_alerts(&self) -> Result<HashMap<String, Vec<EventAlert>>, StoreError> {
+        Ok(event_alerts::load_all(&self.cache).await?)
+    }
+
+    async fn save_event_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[EventAlert],
+    ) -> Result<(), StoreError> {
+        Ok(event_alerts::save_symbol_alerts(&self.cache, symbol, alerts).await?)
+    }
+
+    async fn morning_briefs(&self) -> Result<HashMap<u64, MorningBrief>, StoreError> {
+        Ok(morning_briefs::load_all(&self.cache).await?)
+    }
+
+    async fn morning_brief(&self, user_id: u64) -> Result<Option<MorningBrief>, StoreError> {
+        Ok(morning_briefs::load(&self.cache, user_id).await?)
+    }
+
+    async fn save_morning_brief(&self, brief: &MorningBrief) -> Result<(), StoreError> {
+        Ok(morning_briefs::save(&self.cache, brief).await?)
+    }
+
+    async fn delete_morning_brief(&self, user_id: u64) -> Result<bool, StoreError> {
+        Ok(morning_briefs::delete(&self.cache, user_id).await?)
+    }
+
+    async fn save_ownership_snapshot(
+        &self,
+        symbol: &str,
+        snapshot: &OwnershipSnapshot,
+    ) -> Result<(), StoreError> {
+        Ok(ownership::save_snapshot(&self.cache, symbol, snapshot).await?)
+    }
+
+    async fn ownership_snapshots(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<OwnershipSnapshot>, StoreError> {
+        Ok(ownership::load_snapshots(&self.cache, symbol).await?)
+    }
+
+    async fn corporate_actions_state(
+        &self,
+        symbol: &str,
+    ) -> Result<Option<CorporateActions>, StoreError> {
+        Ok(corporate_actions::load_state(&self.cache, symbol).await?)
+    }
+
+    async fn save_corporate_actions_state(
+        &self,
+        symbol: &str,
+        state: &CorporateActions,
+    ) -> Result<(), StoreError> {
+        Ok(corporate_actions::save_state(&self.cache, symbol, state).await?)
+    }
+}
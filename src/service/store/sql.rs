@@ -0,0 +1,699 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate, Utc};
+use serenity::async_trait;
+use sqlx::any::AnyPoolOptions;
+use sqlx::AnyPool;
+
+use super::{StateStore, StoreError};
+use crate::models::EarningsRecord;
+use crate::service::caching::collections::event_alerts::EventAlert;
+use crate::service::caching::collections::morning_briefs::MorningBrief;
+use crate::service::caching::collections::price_alerts::PriceAlert;
+use crate::service::finance::corporate_actions::CorporateActions;
+use crate::service::finance::ownership::{OwnershipSnapshot, KEEP_QUARTERS};
+use crate::service::symbol_filter::SymbolFilter;
+
+/// Earnings threads are only posted into during the week they were created for.
+const THREAD_RETENTION_DAYS: i64 = 8;
+/// Earnings records feed the Friday summary; two weeks leaves room for a late `/admin run`.
+const RECORD_RETENTION_DAYS: i64 = 15;
+/// A symbol dropped from a watchlist and added back months later starts from a fresh
+/// split and dividend baseline, as with the Redis key's expiry.
+const ACTION_STATE_RETENTION_DAYS: i64 = 90;
+
+/// SQLite or Postgres through sqlx's `Any` driver. Queries stick to SQL both understand and
+/// bind parameters in order, so the same statements run on either.
+pub struct SqlStore {
+    pool: AnyPool,
+    backend: &'static str,
+}
+
+impl SqlStore {
+    /// Connect and bring the schema up to date.
+    pub async fn connect(url: &str) -> Result<Self, StoreError> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .max_connections(5)
+            .connect(url)
+            .await?;
+        sqlx::migrate!().run(&pool).await?;
+        let backend = if url.starts_with("postgres") {
+            "postgres"
+        } else {
+            "sqlite"
+        };
+        Ok(Self { pool, backend })
+    }
+}
+
+fn day_key(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+#[async_trait]
+impl StateStore for SqlStore {
+    fn backend(&self) -> &'static str {
+        self.backend
+    }
+
+    async fn command_roles(&self, guild_id: u64, command: &str) -> Result<Vec<u64>, StoreError> {
+        let raw: Option<String> = sqlx::query_scalar(
+            "SELECT roles FROM guild_command_roles WHERE guild_id = $1 AND command = $2",
+        )
+        .bind(guild_id as i64)
+        .bind(command)
+        .fetch_optional(&self.pool)
+        .await?;
+        match raw {
+            Some(raw) => Ok(serde_json::from_str(&raw)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn all_command_roles(
+        &self,
+        guild_id: u64,
+    ) -> Result<BTreeMap<String, Vec<u64>>, StoreError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT command, roles FROM guild_command_roles WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_all(&self.pool)
+                .await?;
+        let mut out = BTreeMap::new();
+        for (command, roles) in rows {
+            out.insert(command, serde_json::from_str(&roles)?);
+        }
+        Ok(out)
+    }
+
+    async fn set_command_roles(
+        &self,
+        guild_id: u64,
+        command: &str,
+        roles: &[u64],
+    ) -> Result<(), StoreError> {
+        if roles.is_empty() {
+            sqlx::query("DELETE FROM guild_command_roles WHERE guild_id = $1 AND command = $2")
+                .bind(guild_id as i64)
+                .bind(command)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+        sqlx::query(
+            "INSERT INTO guild_command_roles (guild_id, command, roles) VALUES ($1, $2, $3) \
+             ON CONFLICT (guild_id, command) DO UPDATE SET roles = excluded.roles",
+        )
+        .bind(guild_id as i64)
+        .bind(command)
+        .bind(serde_json::to_string(roles)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn paused_jobs(&self, guild_id: u64) -> Result<HashSet<String>, StoreError> {
+        let jobs: Vec<String> =
+            sqlx::query_scalar("SELECT job FROM guild_paused_jobs WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(jobs.into_iter().collect())
+    }
+
+    async fn set_job_paused(
+        &self,
+        guild_id: u64,
+        job: &str,
+        paused: bool,
+    ) -> Result<(), StoreError> {
+        let sql = if paused {
+            "INSERT INTO guild_paused_jobs (guild_id, job) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        } else {
+            "DELETE FROM guild_paused_jobs WHERE guild_id = $1 AND job = $2"
+        };
+        sqlx::query(sql)
+            .bind(guild_id as i64)
+            .bind(job)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn audit_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError> {
+        let id: Option<i64> =
+            sqlx::query_scalar("SELECT channel_id FROM guild_audit_channels WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(id.map(|id| id as u64))
+    }
+
+    async fn set_audit_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        let Some(channel_id) = channel_id else {
+            sqlx::query("DELETE FROM guild_audit_channels WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_audit_channels (guild_id, channel_id) VALUES ($1, $2) \
+             ON CONFLICT (guild_id) DO UPDATE SET channel_id = excluded.channel_id",
+        )
+        .bind(guild_id as i64)
+        .bind(channel_id as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn topic_channel(&self, guild_id: u64) -> Result<Option<u64>, StoreError> {
+        let id: Option<i64> =
+            sqlx::query_scalar("SELECT channel_id FROM guild_topic_channels WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(id.map(|id| id as u64))
+    }
+
+    async fn topic_channels(&self) -> Result<HashMap<u64, u64>, StoreError> {
+        let rows: Vec<(i64, i64)> =
+            sqlx::query_as("SELECT guild_id, channel_id FROM guild_topic_channels")
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(guild_id, channel_id)| (guild_id as u64, channel_id as u64))
+            .collect())
+    }
+
+    async fn set_topic_channel(
+        &self,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        let Some(channel_id) = channel_id else {
+            sqlx::query("DELETE FROM guild_topic_channels WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_topic_channels (guild_id, channel_id) VALUES ($1, $2) \
+             ON CONFLICT (guild_id) DO UPDATE SET channel_id = excluded.channel_id",
+        )
+        .bind(guild_id as i64)
+        .bind(channel_id as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn guild_routes(&self, guild_id: u64) -> Result<BTreeMap<String, u64>, StoreError> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT route, channel_id FROM guild_routes WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(route, channel_id)| (route, channel_id as u64))
+            .collect())
+    }
+
+    async fn route_channels(&self, route: &str) -> Result<HashMap<u64, u64>, StoreError> {
+        let rows: Vec<(i64, i64)> =
+            sqlx::query_as("SELECT guild_id, channel_id FROM guild_routes WHERE route = $1")
+                .bind(route)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(guild_id, channel_id)| (guild_id as u64, channel_id as u64))
+            .collect())
+    }
+
+    async fn set_guild_route(
+        &self,
+        guild_id: u64,
+        route: &str,
+        channel_id: Option<u64>,
+    ) -> Result<(), StoreError> {
+        let Some(channel_id) = channel_id else {
+            sqlx::query("DELETE FROM guild_routes WHERE guild_id = $1 AND route = $2")
+                .bind(guild_id as i64)
+                .bind(route)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_routes (guild_id, route, channel_id) VALUES ($1, $2, $3) \
+             ON CONFLICT (guild_id, route) DO UPDATE SET channel_id = excluded.channel_id",
+        )
+        .bind(guild_id as i64)
+        .bind(route)
+        .bind(channel_id as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn guild_locale(&self, guild_id: u64) -> Result<Option<String>, StoreError> {
+        let locale: Option<String> =
+            sqlx::query_scalar("SELECT locale FROM guild_locales WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(locale)
+    }
+
+    async fn set_guild_locale(
+        &self,
+        guild_id: u64,
+        locale: Option<&str>,
+    ) -> Result<(), StoreError> {
+        let Some(locale) = locale else {
+            sqlx::query("DELETE FROM guild_locales WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_locales (guild_id, locale) VALUES ($1, $2) \
+             ON CONFLICT (guild_id) DO UPDATE SET locale = excluded.locale",
+        )
+        .bind(guild_id as i64)
+        .bind(locale)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn guild_timezone(&self, guild_id: u64) -> Result<Option<String>, StoreError> {
+        let timezone: Option<String> =
+            sqlx::query_scalar("SELECT timezone FROM guild_timezones WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(timezone)
+    }
+
+    async fn set_guild_timezone(
+        &self,
+        guild_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError> {
+        let Some(timezone) = timezone else {
+            sqlx::query("DELETE FROM guild_timezones WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_timezones (guild_id, timezone) VALUES ($1, $2) \
+             ON CONFLICT (guild_id) DO UPDATE SET timezone = excluded.timezone",
+        )
+        .bind(guild_id as i64)
+        .bind(timezone)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn guild_symbol_filter(&self, guild_id: u64) -> Result<Option<SymbolFilter>, StoreError> {
+        let filter: Option<String> =
+            sqlx::query_scalar("SELECT filter FROM guild_symbol_filters WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(filter.map(|raw| serde_json::from_str(&raw)).transpose()?)
+    }
+
+    async fn set_guild_symbol_filter(
+        &self,
+        guild_id: u64,
+        filter: Option<&SymbolFilter>,
+    ) -> Result<(), StoreError> {
+        let Some(filter) = filter else {
+            sqlx::query("DELETE FROM guild_symbol_filters WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO guild_symbol_filters (guild_id, filter) VALUES ($1, $2) \
+             ON CONFLICT (guild_id) DO UPDATE SET filter = excluded.filter",
+        )
+        .bind(guild_id as i64)
+        .bind(serde_json::to_string(filter)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn user_timezone(&self, user_id: u64) -> Result<Option<String>, StoreError> {
+        let timezone: Option<String> =
+            sqlx::query_scalar("SELECT timezone FROM user_timezones WHERE user_id = $1")
+                .bind(user_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(timezone)
+    }
+
+    async fn set_user_timezone(
+        &self,
+        user_id: u64,
+        timezone: Option<&str>,
+    ) -> Result<(), StoreError> {
+        let Some(timezone) = timezone else {
+            sqlx::query("DELETE FROM user_timezones WHERE user_id = $1")
+                .bind(user_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO user_timezones (user_id, timezone) VALUES ($1, $2) \
+             ON CONFLICT (user_id) DO UPDATE SET timezone = excluded.timezone",
+        )
+        .bind(user_id as i64)
+        .bind(timezone)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn channel_ticker(&self, channel_id: u64) -> Result<Option<String>, StoreError> {
+        let ticker: Option<String> =
+            sqlx::query_scalar("SELECT ticker FROM channel_tickers WHERE channel_id = $1")
+                .bind(channel_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(ticker)
+    }
+
+    async fn set_channel_ticker(
+        &self,
+        channel_id: u64,
+        ticker: Option<&str>,
+    ) -> Result<(), StoreError> {
+        let Some(ticker) = ticker else {
+            sqlx::query("DELETE FROM channel_tickers WHERE channel_id = $1")
+                .bind(channel_id as i64)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            "INSERT INTO channel_tickers (channel_id, ticker) VALUES ($1, $2) \
+             ON CONFLICT (channel_id) DO UPDATE SET ticker = excluded.ticker",
+        )
+        .bind(channel_id as i64)
+        .bind(ticker)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn earnings_thread(&self, day: NaiveDate) -> Result<Option<u64>, StoreError> {
+        let id: Option<i64> =
+            sqlx::query_scalar("SELECT thread_id FROM earnings_threads WHERE day = $1")
+                .bind(day_key(day))
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(id.map(|id| id as u64))
+    }
+
+    async fn save_earnings_thread(&self, day: NaiveDate, thread_id: u64) -> Result<(), StoreError> {
+        sqlx::query(
+            "INSERT INTO earnings_threads (day, thread_id) VALUES ($1, $2) \
+             ON CONFLICT (day) DO UPDATE SET thread_id = excluded.thread_id",
+        )
+        .bind(day_key(day))
+        .bind(thread_id as i64)
+        .execute(&self.pool)
+        .await?;
+        // ISO dates sort as text, so this drops every thread older than the retention window.
+        sqlx::query("DELETE FROM earnings_threads WHERE day < $1")
+            .bind(day_key(day - Duration::days(THREAD_RETENTION_DAYS)))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn save_earnings_record(&self, record: &EarningsRecord) -> Result<(), StoreError> {
+        let stored: Option<String> = sqlx::query_scalar(
+            "SELECT record FROM earnings_records WHERE day = $1 AND symbol = $2",
+        )
+        .bind(day_key(record.day))
+        .bind(&record.symbol)
+        .fetch_optional(&self.pool)
+        .await?;
+        let merged = match stored {
+            Some(json) => {
+                let mut existing: EarningsRecord = serde_json::from_str(&json)?;
+                existing.merge(record);
+                existing
+            }
+            None => record.clone(),
+        };
+        sqlx::query(
+            "INSERT INTO earnings_records (day, symbol, record) VALUES ($1, $2, $3) \
+             ON CONFLICT (day, symbol) DO UPDATE SET record = excluded.record",
+        )
+        .bind(day_key(record.day))
+        .bind(&record.symbol)
+        .bind(serde_json::to_string(&merged)?)
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("DELETE FROM earnings_records WHERE day < $1")
+            .bind(day_key(record.day - Duration::days(RECORD_RETENTION_DAYS)))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn earnings_records(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<EarningsRecord>, StoreError> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT record FROM earnings_records WHERE day >= $1 AND day <= $2 \
+             ORDER BY day, symbol",
+        )
+        .bind(day_key(from))
+        .bind(day_key(to))
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|json| Ok(serde_json::from_str(json)?))
+            .collect()
+    }
+
+    async fn price_alerts(&self) -> Result<HashMap<String, Vec<PriceAlert>>, StoreError> {
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT symbol, alerts FROM price_alerts")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut out = HashMap::new();
+        for (symbol, alerts) in rows {
+            let alerts: Vec<PriceAlert> = serde_json::from_str(&alerts)?;
+            if !alerts.is_empty() {
+                out.insert(symbol, alerts);
+            }
+        }
+        Ok(out)
+    }
+
+    async fn save_price_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[PriceAlert],
+    ) -> Result<(), StoreError> {
+        if alerts.is_empty() {
+            sqlx::query("DELETE FROM price_alerts WHERE symbol = $1")
+                .bind(symbol)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+        sqlx::query(
+            "INSERT INTO price_alerts (symbol, alerts) VALUES ($1, $2) \
+             ON CONFLICT (symbol) DO UPDATE SET alerts = excluded.alerts",
+        )
+        .bind(symbol)
+        .bind(serde_json::to_string(alerts)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn event_alerts(&self) -> Result<HashMap<String, Vec<EventAlert>>, StoreError> {
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT symbol, alerts FROM event_alerts")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut out = HashMap::new();
+        for (symbol, alerts) in rows {
+            let alerts: Vec<EventAlert> = serde_json::from_str(&alerts)?;
+            if !alerts.is_empty() {
+                out.insert(symbol, alerts);
+            }
+        }
+        Ok(out)
+    }
+
+    async fn save_event_alerts(
+        &self,
+        symbol: &str,
+        alerts: &[EventAlert],
+    ) -> Result<(), StoreError> {
+        if alerts.is_empty() {
+            sqlx::query("DELETE FROM event_alerts WHERE symbol = $1")
+                .bind(symbol)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+        sqlx::query(
+            "INSERT INTO event_alerts (symbol, alerts) VALUES ($1, $2) \
+             ON CONFLICT (symbol) DO UPDATE SET alerts = excluded.alerts",
+        )
+        .bind(symbol)
+        .bind(serde_json::to_string(alerts)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn morning_briefs(&self) -> Result<HashMap<u64, MorningBrief>, StoreError> {
+        let rows: Vec<(i64, String)> = sqlx::query_as("SELECT user_id, brief FROM morning_briefs")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter()
+            .map(|(user_id, json)| Ok((user_id as u64, serde_json::from_str(&json)?)))
+            .collect()
+    }
+
+    async fn morning_brief(&self, user_id: u64) -> Result<Option<MorningBrief>, StoreError> {
+        let brief: Option<String> =
+            sqlx::query_scalar("SELECT brief FROM morning_briefs WHERE user_id = $1")
+                .bind(user_id as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(brief.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    async fn save_morning_brief(&self, brief: &MorningBrief) -> Result<(), StoreError> {
+        sqlx::query(
+            "INSERT INTO morning_briefs (user_id, brief) VALUES ($1, $2) \
+             ON CONFLICT (user_id) DO UPDATE SET brief = excluded.brief",
+        )
+        .bind(brief.user_id as i64)
+        .bind(serde_json::to_string(brief)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_morning_brief(&self, user_id: u64) -> Result<bool, StoreError> {
+        let result = sqlx::query("DELETE FROM morning_briefs WHERE user_id = $1")
+            .bind(user_id as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn save_ownership_snapshot(
+        &self,
+        symbol: &str,
+        snapshot: &OwnershipSnapshot,
+    ) -> Result<(), StoreError> {
+        let symbol = symbol.to_uppercase();
+        sqlx::query(
+            "INSERT INTO ownership_snapshots (symbol, quarter, snapshot) VALUES ($1, $2, $3) \
+             ON CONFLICT (symbol, quarter) DO UPDATE SET snapshot = excluded.snapshot",
+        )
+        .bind(&symbol)
+        .bind(day_key(snapshot.quarter))
+        .bind(serde_json::to_string(snapshot)?)
+        .execute(&self.pool)
+        .await?;
+        let quarters: Vec<String> = sqlx::query_scalar(
+            "SELECT quarter FROM ownership_snapshots WHERE symbol = $1 ORDER BY quarter DESC",
+        )
+        .bind(&symbol)
+        .fetch_all(&self.pool)
+        .await?;
+        if let Some(oldest_kept) = quarters.get(KEEP_QUARTERS - 1) {
+            sqlx::query("DELETE FROM ownership_snapshots WHERE symbol = $1 AND quarter < $2")
+                .bind(&symbol)
+                .bind(oldest_kept)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn ownership_snapshots(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<OwnershipSnapshot>, StoreError> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT snapshot FROM ownership_snapshots WHERE symbol = $1 ORDER BY quarter",
+        )
+        .bind(symbol.to_uppercase())
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|json| Ok(serde_json::from_str(json)?))
+            .collect()
+    }
+
+    async fn corporate_actions_state(
+        &self,
+        symbol: &str,
+    ) -> Result<Option<CorporateActions>, StoreError> {
+        let cutoff = Utc::now().date_naive() - Duration::days(ACTION_STATE_RETENTION_DAYS);
+        let state: Option<String> = sqlx::query_scalar(
+            "SELECT state FROM corporate_actions_state WHERE symbol = $1 AND updated >= $2",
+        )
+        .bind(symbol.to_uppercase())
+        .bind(day_key(cutoff))
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(state.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    async fn save_corporate_actions_state(
+        &self,
+        symbol: &str,
+        state: &CorporateActions,
+    ) -> Result<(), StoreError> {
+        let today = Utc::now().date_naive();
+        sqlx::query(
+            "INSERT INTO corporate_actions_state (symbol, state, updated) VALUES ($1, $2, $3) \
+             ON CONFLICT (symbol) DO UPDATE SET state = excluded.state, updated = excluded.updated",
+        )
+        .bind(symbol.to_uppercase())
+        .bind(serde_json::to_string(state)?)
+        .bind(day_key(today))
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("DELETE FROM corporate_actions_state WHERE updated < $1")
+            .bind(day_key(today - Duration::days(ACTION_STATE_RETENTION_DAYS)))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Per-guild allow/deny symbol lists (`/config symbols`) applied to the earnings calendar,
+//! the earnings reports and the options pinger.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::models::EarningsEvent;
+use crate::service::finance::constituents::Index;
+use crate::service::finance::FinanceService;
+use crate::service::format;
+use crate::service::store::StateStore;
+
+/// A guild's stored filter. List entries are tickers (`AAPL`) or index names (`sp500`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolFilter {
+    /// When non-empty, only these symbols are shown.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Hide companies below this market cap (USD) when the data includes one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_market_cap: Option<f64>,
+}
+
+impl SymbolFilter {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && self.min_market_cap.is_none()
+    }
+
+    /// Parse a comma- or space-separated list: tickers are uppercased, index names kept.
+    pub fn parse_list(input: &str) -> Result<Vec<String>, String> {
+        let mut entries = Vec::new();
+        for raw in input.split([',', ' ']).filter(|s| !s.is_empty()) {
+            let entry = match Index::from_name(raw) {
+                Some(index) => index.name().to_string(),
+                None => {
+                    let ticker = raw.trim_start_matches('$').to_uppercase();
+                    let valid = !ticker.is_empty()
+                        && ticker
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '^'));
+                    if !valid {
+                        return Err(format!("`{raw}` is not a ticker or index name"));
+                    }
+                    ticker
+                }
+            };
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Expand index names into their members. An index that fails to load is skipped with a
+    /// warning; if that empties the allow list, everything is allowed rather than nothing.
+    pub async fn resolve(&self, finance: &FinanceService) -> ResolvedFilter {
+        let allow = expand(&self.allow, finance).await;
+        ResolvedFilter {
+            allow: (!allow.is_empty()).then_some(allow),
+            deny: expand(&self.deny, finance).await,
+            min_market_cap: self.min_market_cap,
+        }
+    }
+
+    /// `allow: sp500, MSFT · deny: TSLA · min market cap: $2.00B`, or `none`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.allow.is_empty() {
+            parts.push(format!("allow: {}", self.allow.join(", ")));
+        }
+        if !self.deny.is_empty() {
+            parts.push(format!("deny: {}", self.deny.join(", ")));
+        }
+        if let Some(cap) = self.min_market_cap {
+            parts.push(format!("min market cap: {}", format::currency(cap)));
+        }
+        if parts.is_empty() {
+            "none".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
+async fn expand(entries: &[String], finance: &FinanceService) -> HashSet<String> {
+    let mut symbols = HashSet::new();
+    for entry in entries {
+        match Index::from_name(entry) {
+            Some(index) => match finance.constituents(index).await {
+                Ok(members) => symbols.extend(members.iter().cloned()),
+                Err(e) => warn!("symbol filter skips {}: {e}", index.label()),
+            },
+            None => {
+                symbols.insert(entry.clone());
+            }
+        }
+    }
+    symbols
+}
+
+/// A filter with index names expanded, ready to test symbols against.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedFilter {
+    allow: Option<HashSet<String>>,
+    deny: HashSet<String>,
+    min_market_cap: Option<f64>,
+}
+
+impl ResolvedFilter {
+    /// Whether `symbol` is shown. A missing market cap never hides a symbol.
+    pub fn allows(&self, symbol: &str, market_cap: Option<f64>) -> bool {
+        let symbol = symbol.to_uppercase();
+        if self.deny.contains(&symbol) {
+            return false;
+        }
+        if let Some(allow) = &self.allow {
+            if !allow.contains(&symbol) {
+                return false;
+            }
+        }
+        match (self.min_market_cap, market_cap) {
+            (Some(min), Some(cap)) => cap >= min,
+            _ => true,
+        }
+    }
+
+    /// Also require membership of `members`, e.g. an index picked with `universe:`.
+    pub fn restrict_to(mut self, members: &HashSet<String>) -> Self {
+        self.allow = Some(match self.allow {
+            Some(allow) => allow.intersection(members).cloned().collect(),
+            None => members.clone(),
+        });
+        self
+    }
+
+    /// The allowed symbols minus denied ones, sorted; `None` when no allow list is set and every
+    /// symbol is shown.
+    pub fn watchlist(&self) -> Option<Vec<String>> {
+        let allow = self.allow.as_ref()?;
+        let mut symbols: Vec<String> = allow.difference(&self.deny).cloned().collect();
+        symbols.sort();
+        Some(symbols)
+    }
+
+    /// Drop the events whose symbol is not shown.
+    pub fn retain_events(&self, events: &mut Vec<EarningsEvent>) {
+        events.retain(|ev| self.allows(&ev.symbol, ev.market_cap));
+    }
+}
+
+/// The resolved filter of `guild_id`; allows everything without a guild or a store.
+pub async fn guild_filter(
+    store: Option<&dyn StateStore>,
+    finance: &FinanceService,
+    guild_id: Option<u64>,
+) -> ResolvedFilter {
+    let (Some(store), Some(guild_id)) = (store, guild_id) else {
+        return ResolvedFilter::default();
+    };
+    match store.guild_symbol_filter(guild_id).await {
+        Ok(Some(filter)) => filter.resolve(finance).await,
+        Ok(None) => ResolvedFilter::default(),
+        Err(e) => {
+            warn!("failed to load symbol filter for guild {guild_id}: {e}");
+            ResolvedFilter::default()
+        }
+    }
+}
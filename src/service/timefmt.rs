@@ -0,0 +1,134 @@
+//! Shared time formatting.
+//!
+//! Message text uses Discord timestamp markup (`<t:1760716800:f>`), which each client renders
+//! in the reader's own timezone. Text where markup does not render, such as forum post titles,
+//! uses the timezone picked with `/timezone` or `/config timezone`, falling back to US
+//! Eastern, the market's clock.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone};
+use chrono_tz::{America::New_York, Tz, TZ_VARIANTS};
+use serenity::all::{GuildId, UserId};
+use tracing::warn;
+
+use crate::service::store::StateStore;
+
+/// Timezone for plain-text times when no preference is set.
+pub const DEFAULT_TIMEZONE: Tz = New_York;
+
+/// Discord timestamp styles. Rendering follows the reader's client locale, e.g. for
+/// [`TimestampStyle::ShortDateTime`] `October 17, 2026 9:30 AM` in en-US.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampStyle {
+    /// `9:30 AM`
+    ShortTime,
+    /// `9:30:00 AM`
+    LongTime,
+    /// `10/17/2026`
+    ShortDate,
+    /// `October 17, 2026`
+    LongDate,
+    /// `October 17, 2026 9:30 AM`
+    ShortDateTime,
+    /// `Saturday, October 17, 2026 9:30 AM`
+    LongDateTime,
+    /// `in 2 hours`, `3 minutes ago`
+    Relative,
+}
+
+impl TimestampStyle {
+    fn code(self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::LongTime => 'T',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::ShortDateTime => 'f',
+            TimestampStyle::LongDateTime => 'F',
+            TimestampStyle::Relative => 'R',
+        }
+    }
+}
+
+/// Discord markup showing `at` in each reader's own timezone.
+pub fn discord<T: TimeZone>(at: &DateTime<T>, style: TimestampStyle) -> String {
+    format!("<t:{}:{}>", at.timestamp(), style.code())
+}
+
+/// `at` in `tz` for plain text, e.g. `Oct 17 9:30 AM EDT`.
+pub fn plain<T: TimeZone>(at: &DateTime<T>, tz: Tz) -> String {
+    at.with_timezone(&tz)
+        .format("%b %-d %-I:%M %p %Z")
+        .to_string()
+}
+
+/// Short names people type instead of an IANA zone, mapped to the zone they usually mean.
+const ALIASES: [(&str, Tz); 9] = [
+    ("ET", Tz::America__New_York),
+    ("EST", Tz::America__New_York),
+    ("EDT", Tz::America__New_York),
+    ("CT", Tz::America__Chicago),
+    ("MT", Tz::America__Denver),
+    ("PT", Tz::America__Los_Angeles),
+    ("UTC", Tz::UTC),
+    ("GMT", Tz::UTC),
+    ("Z", Tz::UTC),
+];
+
+/// Parse an IANA timezone name (`Europe/London`, case-insensitive) or a short US alias such
+/// as `ET` or `PT`.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    let name = name.trim();
+    if let Some((_, tz)) = ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+    {
+        return Some(*tz);
+    }
+    Tz::from_str(name).ok().or_else(|| {
+        TZ_VARIANTS
+            .iter()
+            .copied()
+            .find(|tz| tz.name().eq_ignore_ascii_case(name))
+    })
+}
+
+/// Timezone `guild_id` picked with `/config timezone`, if any.
+pub async fn guild_timezone(store: Option<&dyn StateStore>, guild_id: GuildId) -> Option<Tz> {
+    let store = store?;
+    match store.guild_timezone(guild_id.get()).await {
+        Ok(name) => name.as_deref().and_then(parse_timezone),
+        Err(e) => {
+            warn!("failed to load timezone for guild {guild_id}: {e}");
+            None
+        }
+    }
+}
+
+/// Timezone `user_id` picked with `/timezone`, if any.
+pub async fn user_timezone(store: Option<&dyn StateStore>, user_id: UserId) -> Option<Tz> {
+    let store = store?;
+    match store.user_timezone(user_id.get()).await {
+        Ok(name) => name.as_deref().and_then(parse_timezone),
+        Err(e) => {
+            warn!("failed to load timezone for user {user_id}: {e}");
+            None
+        }
+    }
+}
+
+/// The user's own timezone, else the guild's, else `None` when neither picked one.
+pub async fn preferred_timezone(
+    store: Option<&dyn StateStore>,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+) -> Option<Tz> {
+    if let Some(tz) = user_timezone(store, user_id).await {
+        return Some(tz);
+    }
+    match guild_id {
+        Some(guild_id) => guild_timezone(store, guild_id).await,
+        None => None,
+    }
+}
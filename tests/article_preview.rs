@@ -0,0 +1,62 @@
+use reqwest::Url;
+use stacks_bot::service::finance::article_preview::{parse_open_graph, ArticlePreview};
+
+fn page() -> Url {
+    Url::parse("https://example.com/news/story.html").unwrap()
+}
+
+#[test]
+fn reads_open_graph_tags() {
+    let html = r#"<html><head>
+        <meta property="og:title" content="Chipmaker beats estimates">
+        <meta property="og:description" content="Revenue rose 20% &amp; guidance was raised.">
+        <meta property="og:image" content="https://cdn.example.com/a.jpg" />
+        <meta property="og:site_name" content="Example News">
+        </head><body></body></html>"#;
+    assert_eq!(
+        parse_open_graph(html, &page()),
+        ArticlePreview {
+            title: Some("Chipmaker beats estimates".to_string()),
+            description: Some("Revenue rose 20% & guidance was raised.".to_string()),
+            image: Some("https://cdn.example.com/a.jpg".to_string()),
+            site_name: Some("Example News".to_string()),
+        }
+    );
+}
+
+#[test]
+fn falls_back_to_twitter_and_description_tags() {
+    let html = r#"<meta name='description' content='Plain summary'>
+        <meta content="/img/lead.png" name="twitter:image">"#;
+    let preview = parse_open_graph(html, &page());
+    assert_eq!(preview.description.as_deref(), Some("Plain summary"));
+    assert_eq!(
+        preview.image.as_deref(),
+        Some("https://example.com/img/lead.png")
+    );
+}
+
+#[test]
+fn open_graph_wins_over_fallbacks_whatever_the_order() {
+    let html = r#"<meta name="description" content="Fallback">
+        <meta property="og:description" content="Preferred">"#;
+    assert_eq!(
+        parse_open_graph(html, &page()).description.as_deref(),
+        Some("Preferred")
+    );
+}
+
+#[test]
+fn long_descriptions_are_shortened() {
+    let long = "word ".repeat(200);
+    let html = format!(r#"<meta property="og:description" content="{long}">"#);
+    let description = parse_open_graph(&html, &page()).description.unwrap();
+    assert!(description.chars().count() <= 300);
+    assert!(description.ends_with('…'));
+}
+
+#[test]
+fn pages_without_tags_have_an_empty_preview() {
+    let preview = parse_open_graph("<html><head><title>x</title></head></html>", &page());
+    assert!(preview.is_empty());
+}
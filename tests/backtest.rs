@@ -0,0 +1,130 @@
+mod common;
+
+use chrono::Duration;
+use common::{close_enough, date};
+use stacks_bot::service::command::backtest::backtest_text;
+use stacks_bot::service::finance::backtest::{run, Period, Strategy, SMA_SLOW};
+use stacks_bot::service::finance::history::DailyClose;
+use stacks_bot::service::finance::technicals::{max_drawdown, rsi, sma};
+
+/// One close a day from Jan 1, 2025.
+fn history(closes: &[f64]) -> Vec<DailyClose> {
+    closes
+        .iter()
+        .enumerate()
+        .map(|(i, close)| DailyClose {
+            date: date(2025, 1, 1) + Duration::days(i as i64),
+            close: *close,
+        })
+        .collect()
+}
+
+#[test]
+fn indicators_warm_up_before_reading() {
+    let averages = sma(&[1.0, 2.0, 3.0, 4.0], 3);
+    assert_eq!(averages, vec![None, None, Some(2.0), Some(3.0)]);
+
+    let rising: Vec<f64> = (0..20).map(f64::from).collect();
+    let readings = rsi(&rising, 14);
+    assert!(readings[..14].iter().all(Option::is_none));
+    assert_eq!(readings[14], Some(100.0));
+    assert_eq!(rsi(&[5.0; 20], 14)[19], Some(50.0));
+    // Equal average gains and losses read 50.
+    let zigzag: Vec<f64> = (0..15)
+        .map(|i| if i % 2 == 0 { 10.0 } else { 11.0 })
+        .collect();
+    assert!(close_enough(rsi(&zigzag, 14)[14].unwrap(), 50.0));
+
+    assert!(close_enough(
+        max_drawdown(&[1.0, 1.2, 0.9, 1.1, 0.6, 1.5]),
+        -0.5
+    ));
+    assert_eq!(max_drawdown(&[1.0, 2.0, 3.0]), 0.0);
+}
+
+#[test]
+fn buy_and_hold_matches_the_benchmark_and_skips_warm_up() {
+    let history = history(&[50.0, 80.0, 100.0, 110.0, 90.0, 120.0]);
+    let result = run(Strategy::BuyHold, &history, date(2025, 1, 3)).unwrap();
+    assert_eq!(
+        (result.start(), result.end()),
+        (date(2025, 1, 3), date(2025, 1, 6))
+    );
+    assert!(close_enough(result.total_return(), 0.2));
+    assert!(close_enough(result.benchmark_return(), 0.2));
+    assert!(close_enough(result.max_drawdown(), 90.0 / 110.0 - 1.0));
+    assert_eq!(result.trades.len(), 1);
+    assert!(result.trades[0].open);
+    assert_eq!(result.win_rate(), Some(1.0));
+    assert!(close_enough(result.exposure(), 1.0));
+
+    assert!(run(Strategy::BuyHold, &history, date(2025, 1, 6)).is_none());
+    assert!(run(Strategy::BuyHold, &history, date(2025, 2, 1)).is_none());
+}
+
+#[test]
+fn rsi_strategy_buys_oversold_and_sells_overbought() {
+    // A slide to oversold, a rally to overbought, then a second slide left open.
+    let mut closes: Vec<f64> = (0..20).map(|i| 100.0 - 2.0 * f64::from(i)).collect();
+    closes.extend((1..=20).map(|i| 62.0 + 3.0 * f64::from(i)));
+    closes.extend((1..=20).map(|i| 122.0 - 3.0 * f64::from(i)));
+    let history = history(&closes);
+    let result = run(Strategy::Rsi, &history, history[0].date).unwrap();
+
+    assert_eq!(result.trades.len(), 2);
+    let first = result.trades[0];
+    // The first reading, 14 closes in, is already oversold.
+    assert_eq!(first.entry, date(2025, 1, 15));
+    assert!(!first.open && first.return_fraction() > 0.0);
+    assert!(result.trades[1].open && result.trades[1].return_fraction() < 0.0);
+    assert_eq!(result.win_rate(), Some(0.5));
+    assert!(result.exposure() < 1.0);
+}
+
+#[test]
+fn sma_cross_holds_from_the_first_day_after_warm_up() {
+    let closes: Vec<f64> = (0..SMA_SLOW + 30).map(|i| 100.0 + i as f64).collect();
+    let history = history(&closes);
+    let start = history[SMA_SLOW + 10].date;
+    let result = run(Strategy::SmaCross, &history, start).unwrap();
+    assert_eq!(result.trades.len(), 1);
+    assert_eq!(result.trades[0].entry, start);
+    assert!(close_enough(
+        result.total_return(),
+        result.benchmark_return()
+    ));
+
+    // Without enough history the slow average never forms and nothing trades.
+    let short = run(
+        Strategy::SmaCross,
+        &history[..SMA_SLOW - 1],
+        history[0].date,
+    )
+    .unwrap();
+    assert!(short.trades.is_empty());
+    assert_eq!(short.total_return(), 0.0);
+}
+
+#[test]
+fn options_and_summary() {
+    assert_eq!(Strategy::from_name("sma_cross"), Some(Strategy::SmaCross));
+    assert_eq!(Strategy::from_name("macd"), None);
+    assert_eq!(Period::from_name("1y"), Some(Period::OneYear));
+    assert_eq!(Period::FiveYears.history_range(), "10y");
+
+    let history = history(&[100.0, 110.0, 99.0, 121.0]);
+    let result = run(Strategy::BuyHold, &history, history[0].date).unwrap();
+    let text = backtest_text("ACME", &result);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "🧪 **ACME backtest** · Buy and hold · Jan 1, 2025 – Jan 4, 2025"
+    );
+    assert_eq!(lines[1], "Total return **+21.00%** (buy and hold +21.00%)");
+    assert_eq!(lines[2], "Max drawdown **-10.00%** (buy and hold -10.00%)");
+    assert_eq!(
+        lines[3],
+        "Win rate **100%** (1 of 1 trades) · in the market 100% of sessions"
+    );
+    assert!(text.contains("• Jan 1, 2025 → open: $100.00 → $121.00 (+21.00%)"));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::event;
+use stacks_bot::models::EarningsEvent;
+use stacks_bot::service::automation::earnings::weekly_report::alt_text;
+
+fn ranked(symbol: &str, day: u32, time_of_day: &str, importance: Option<i64>) -> EarningsEvent {
+    EarningsEvent {
+        importance,
+        ..event(symbol, day, time_of_day)
+    }
+}
+
+#[test]
+fn describes_the_range_and_leads_with_the_most_important_names() {
+    let events = vec![
+        ranked("KO", 19, "bmo", Some(3)),
+        ranked("AAPL", 22, "amc", Some(5)),
+        ranked("TSLA", 21, "amc", Some(5)),
+        ranked("GE", 20, "bmo", None),
+    ];
+    assert_eq!(
+        alt_text(&events, 4),
+        "Earnings calendar for Mon Oct 19 to Thu Oct 22: 4 companies reporting. \
+         Most important: AAPL (Thu AMC), TSLA (Wed AMC), KO (Mon BMO), GE (Tue BMO)."
+    );
+}
+
+#[test]
+fn notes_events_left_off_the_image_and_caps_the_names() {
+    let events: Vec<EarningsEvent> = (0..30)
+        .map(|i| ranked(&format!("S{i:02}"), 20, "amc", Some(1)))
+        .collect();
+    let text = alt_text(&events, 12);
+    assert!(text.starts_with("Earnings calendar for Tue Oct 20: 30 companies reporting, 12 shown."));
+    assert!(text.contains("S09 (Tue AMC)."));
+    assert!(!text.contains("S10"));
+    assert!(text.chars().count() <= 1024);
+}
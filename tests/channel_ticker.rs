@@ -0,0 +1,31 @@
+use serenity::all::CommandDataOptionValue;
+use stacks_bot::service::command::args::CommandSpec;
+use stacks_bot::service::command::framework::{ticker_option, TICKER_OPTION};
+use stacks_bot::service::command::{bind_ticker, holders, quotes};
+
+#[test]
+fn bound_ticker_becomes_a_string_option() {
+    let option = ticker_option("SPY").expect("option");
+    assert_eq!(option.name, TICKER_OPTION);
+    assert_eq!(option.value, CommandDataOptionValue::String("SPY".into()));
+}
+
+#[test]
+fn ticker_only_commands_take_the_bound_ticker() {
+    let quote = CommandSpec::from_slash(quotes::register_command(), &[], &[]);
+    assert!(quote.parse(&[]).is_ok());
+    assert!(quote.usage().starts_with("quote [TICKER]"));
+
+    // Discord puts required options first, so a ticker ahead of one stays required.
+    let holders = CommandSpec::from_slash(holders::register_command(), &[], &[]);
+    assert!(holders.parse(&[]).is_err());
+}
+
+#[test]
+fn bind_ticker_is_a_channel_manager_command() {
+    let json = serde_json::to_value(bind_ticker::register_command()).unwrap();
+    assert_eq!(json["name"], "bind-ticker");
+    assert_eq!(json["dm_permission"], false);
+    assert_eq!(json["options"][0]["name"], TICKER_OPTION);
+    assert_ne!(json["options"][0]["required"], true);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use common::quote;
+use stacks_bot::service::automation::channel_topic::{edit_due, topic_text};
+use stacks_bot::service::format;
+
+#[test]
+fn topic_lists_indexes_and_skips_missing_ones() {
+    let quotes = vec![
+        ("S&P 500", Some(quote("^GSPC", Some(5812.344), Some(0.45)))),
+        ("Nasdaq", None),
+        ("Dow", Some(quote("^DJI", Some(42012.5), Some(-0.3)))),
+        ("VIX", Some(quote("^VIX", Some(16.2), None))),
+    ];
+    assert_eq!(
+        topic_text(&quotes),
+        "S&P 500 5,812.34 (+0.45%) · Dow 42,012.50 (-0.30%) · VIX 16.20"
+    );
+    assert_eq!(
+        topic_text(&[("VIX", Some(quote("^VIX", None, Some(1.0))))]),
+        ""
+    );
+}
+
+#[test]
+fn edits_wait_ten_minutes_per_channel() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    assert!(edit_due(None, now));
+    assert!(!edit_due(Some(now - Duration::minutes(9)), now));
+    assert!(edit_due(Some(now - Duration::minutes(10)), now));
+}
+
+#[test]
+fn levels_group_thousands() {
+    assert_eq!(format::level(16.2), "16.20");
+    assert_eq!(format::level(999.999), "1,000.00");
+    assert_eq!(format::level(1234567.891), "1,234,567.89");
+    assert_eq!(format::level(-42012.5), "-42,012.50");
+}
@@ -0,0 +1,35 @@
+use stacks_bot::service::chunks;
+
+#[test]
+fn split_breaks_between_lines_within_the_limit() {
+    let lines: Vec<String> = (0..300).map(|i| format!("AAPL line {i:03}")).collect();
+    let content = lines.join("\n");
+    let chunks = chunks::split(&content, chunks::MESSAGE_LIMIT);
+
+    assert!(chunks.len() > 1);
+    assert!(chunks
+        .iter()
+        .all(|c| c.chars().count() <= chunks::MESSAGE_LIMIT));
+    assert_eq!(chunks.join("\n"), content);
+}
+
+#[test]
+fn split_cuts_overlong_lines_and_keeps_short_content() {
+    assert_eq!(chunks::split("short", 10), vec!["short"]);
+
+    let chunks = chunks::split("ééééééééééééé\nok", 5);
+    assert_eq!(chunks, vec!["ééééé", "ééééé", "ééé", "ok"]);
+}
+
+#[test]
+fn spoilers_wrap_each_piece_within_the_limit() {
+    assert_eq!(chunks::spoilers("AAPL\nMSFT", 100), vec!["||AAPL\nMSFT||"]);
+
+    let lines: Vec<String> = (0..300).map(|i| format!("NVDA line {i:03}")).collect();
+    let pieces = chunks::spoilers(&lines.join("\n"), chunks::MESSAGE_LIMIT);
+    assert!(pieces.len() > 1);
+    for piece in &pieces {
+        assert!(piece.chars().count() <= chunks::MESSAGE_LIMIT);
+        assert!(piece.starts_with("||") && piece.ends_with("||"));
+    }
+}
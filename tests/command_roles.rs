@@ -0,0 +1,86 @@
+use serenity::all::{GuildId, RoleId};
+use stacks_bot::service::command::error::BotError;
+use stacks_bot::service::command::framework::{check_roles, Invoker};
+use stacks_bot::service::command::mention::slash_name;
+use stacks_bot::service::i18n::Locale;
+use stacks_bot::service::store::{SqlStore, StateStore};
+
+fn member(roles: &[u64]) -> Invoker {
+    Invoker {
+        guild_id: GuildId::new(1),
+        roles: roles.iter().copied().map(RoleId::new).collect(),
+        manager: false,
+    }
+}
+
+#[test]
+fn restricted_commands_need_one_of_the_roles() {
+    assert!(member(&[]).allowed(&[]));
+    assert!(member(&[10]).allowed(&[10, 11]));
+    assert!(!member(&[12]).allowed(&[10, 11]));
+    assert!(!member(&[]).allowed(&[10]));
+
+    let manager = Invoker {
+        manager: true,
+        ..member(&[])
+    };
+    assert!(manager.allowed(&[10]));
+}
+
+#[test]
+fn mention_commands_map_to_their_slash_command() {
+    assert_eq!(slash_name("quote", None), "quote");
+    assert_eq!(slash_name("earnings", Some("weekly")), "weekly-earnings");
+    assert_eq!(slash_name("earnings", Some("daily")), "daily-earnings");
+    assert_eq!(slash_name("earnings", Some("reports")), "er-reports");
+}
+
+#[tokio::test]
+async fn check_roles_enforces_the_store_and_fails_closed() -> Result<(), Box<dyn std::error::Error>>
+{
+    let path = std::env::temp_dir().join(format!("stacks-bot-roles-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("sqlite://{}?mode=rwc", path.display());
+    let sql = SqlStore::connect(&url).await?;
+    let store: &dyn StateStore = &sql;
+
+    store.set_command_roles(1, "quote", &[10]).await?;
+
+    assert!(
+        check_roles("news", Some(&member(&[])), Some(store), Locale::En)
+            .await
+            .is_ok()
+    );
+    assert!(
+        check_roles("quote", Some(&member(&[10])), Some(store), Locale::En)
+            .await
+            .is_ok()
+    );
+    let denied = check_roles("quote", Some(&member(&[11])), Some(store), Locale::En).await;
+    assert!(matches!(denied, Err(BotError::Forbidden(msg)) if msg.contains("<@&10>")));
+    // Outside a guild nothing is restricted.
+    assert!(check_roles("quote", None, Some(store), Locale::En)
+        .await
+        .is_ok());
+
+    // With the restrictions unreadable the command is denied, not run unrestricted.
+    sqlx::any::install_default_drivers();
+    let pool = sqlx::AnyPool::connect(&url).await?;
+    sqlx::query("DROP TABLE guild_command_roles")
+        .execute(&pool)
+        .await?;
+    let failed = check_roles("quote", Some(&member(&[])), Some(store), Locale::En).await;
+    assert!(matches!(failed, Err(BotError::Internal(_))));
+    let manager = Invoker {
+        manager: true,
+        ..member(&[])
+    };
+    assert!(
+        check_roles("quote", Some(&manager), Some(store), Locale::En)
+            .await
+            .is_ok()
+    );
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
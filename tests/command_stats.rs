@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use stacks_bot::service::caching::collections::command_stats::{
+    day_key, CommandUsage, StatsSummary,
+};
+use stacks_bot::service::command::stats::format_summary;
+
+fn usage(command: &str, count: u64, errors: u64, total_latency_ms: u64) -> CommandUsage {
+    CommandUsage {
+        command: command.to_string(),
+        count,
+        errors,
+        total_latency_ms,
+    }
+}
+
+#[test]
+fn buckets_are_scoped_per_guild() {
+    let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+    assert_eq!(day_key(Some(42), day, "count"), "stats:42:2026-10-16:count");
+    assert_eq!(day_key(None, day, "count"), "stats:dm:2026-10-16:count");
+    assert_ne!(
+        day_key(Some(42), day, "tickers"),
+        day_key(Some(43), day, "tickers")
+    );
+}
+
+#[test]
+fn summary_lists_totals_commands_and_tickers() {
+    let summary = StatsSummary {
+        commands: vec![usage("quote", 8, 2, 800), usage("news", 2, 0, 300)],
+        tickers: vec![("AAPL".to_string(), 5)],
+    };
+
+    let text = format_summary(&summary);
+    assert!(text.contains("10 commands, 2 failed (20.0%)"), "{text}");
+    assert!(
+        text.contains("• /quote — 8 uses, 25.0% failed, avg 100 ms"),
+        "{text}"
+    );
+    assert!(
+        text.contains("• /news — 2 uses, 0.0% failed, avg 150 ms"),
+        "{text}"
+    );
+    assert!(text.contains("• AAPL — 5"), "{text}");
+}
+
+#[test]
+fn empty_summary_says_nothing_was_recorded() {
+    let text = format_summary(&StatsSummary::default());
+    assert_eq!(text, "No commands recorded in the last 7 days.");
+}
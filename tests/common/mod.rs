@@ -0,0 +1,56 @@
+//! Helpers shared by the integration tests; each test file pulls them in with `mod common;`.
+// Every test file is its own crate, and most use only some of these.
+#![allow(dead_code)]
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use stacks_bot::models::{EarningsEvent, PriceQuote};
+use stacks_bot::service::finance::history::DailyClose;
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+pub fn close(y: i32, m: u32, d: u32, close: f64) -> DailyClose {
+    DailyClose {
+        date: date(y, m, d),
+        close,
+    }
+}
+
+/// Float equality for values computed along different paths.
+pub fn close_enough(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// A USD quote named after its symbol, without a change or extended-hours prices.
+pub fn quote(symbol: &str, price: Option<f64>, percent_change: Option<f64>) -> PriceQuote {
+    PriceQuote {
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        price,
+        currency: Some("USD".to_string()),
+        change: None,
+        percent_change,
+        pre_market_price: None,
+        after_hours_price: None,
+    }
+}
+
+/// A report on `day` of October 2026, without estimates or results.
+pub fn event(symbol: &str, day: u32, time_of_day: &str) -> EarningsEvent {
+    EarningsEvent {
+        symbol: symbol.to_string(),
+        date: Utc.with_ymd_and_hms(2026, 10, day, 0, 0, 0).unwrap(),
+        date_end: None,
+        time_of_day: Some(time_of_day.to_string()),
+        eps_estimate: None,
+        eps_actual: None,
+        revenue_estimate: None,
+        revenue_actual: None,
+        market_cap: None,
+        importance: None,
+        title: None,
+        emoji: None,
+        logo: None,
+    }
+}
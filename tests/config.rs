@@ -0,0 +1,286 @@
+use std::env;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use stacks_bot::service::command::admin::format_changes;
+use stacks_bot::service::config::{
+    Config, ConfigError, ConfigHandle, HealthConfig, RedisConfig, StorageBackend, StorageConfig,
+};
+
+/// Env vars are process-wide; tests that set them take turns.
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Every variable these tests touch, cleared before each one.
+const VARS: [&str; 19] = [
+    "CONFIG_PATH",
+    "DISCORD_TOKEN",
+    "APPLICATION_ID",
+    "GUILD_IDS",
+    "GUILD_ID",
+    "SHARD_COUNT",
+    "SHARD_RANGE",
+    "EARNINGS_DAY_THREADS",
+    "OPTIONS_CHANNEL_ID",
+    "HEALTH_ADDR",
+    "SHUTDOWN_NOTICE",
+    "SHUTDOWN_NOTICE_CHANNEL_ID",
+    "REDIS_URL",
+    "STORAGE_BACKEND",
+    "DATABASE_URL",
+    "RISK_FREE_RATE",
+    "SP500_CONSTITUENTS_URL",
+    "NASDAQ100_CONSTITUENTS_URL",
+    "DOW30_CONSTITUENTS_URL",
+];
+
+fn with_env(vars: &[(&str, &str)], test: impl FnOnce()) {
+    let _guard = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    for var in VARS {
+        env::remove_var(var);
+    }
+    for (var, value) in vars {
+        env::set_var(var, value);
+    }
+    test();
+    for (var, _) in vars {
+        env::remove_var(var);
+    }
+}
+
+fn config_file(name: &str, toml: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("stacks-bot-{name}-{}.toml", std::process::id()));
+    std::fs::write(&path, toml).unwrap();
+    path
+}
+
+const BASE: &str = r#"
+[discord]
+token = "file-token"
+application_id = 1
+guild_ids = [5]
+
+[channels]
+earnings = 100
+
+[health]
+addr = "127.0.0.1:9000"
+"#;
+
+#[test]
+fn env_overrides_file_values() {
+    let path = config_file("overrides", BASE);
+    let vars = [
+        ("CONFIG_PATH", path.to_str().unwrap()),
+        ("DISCORD_TOKEN", "env-token"),
+        ("GUILD_IDS", "7, 8,"),
+        ("EARNINGS_DAY_THREADS", "yes"),
+        ("OPTIONS_CHANNEL_ID", "   "),
+        ("HEALTH_ADDR", "0.0.0.0:8080"),
+        ("SHUTDOWN_NOTICE", "brb"),
+        ("SHUTDOWN_NOTICE_CHANNEL_ID", "55"),
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("SP500_CONSTITUENTS_URL", "https://example.com/sp500.csv"),
+        ("DOW30_CONSTITUENTS_URL", "https://example.com/dow.txt"),
+    ];
+    with_env(&vars, || {
+        let config = Config::load().unwrap();
+        assert_eq!(config.discord.token, "env-token");
+        assert_eq!(config.discord.application_id, 1);
+        assert_eq!(config.discord.guild_ids, vec![7, 8]);
+        assert_eq!(config.channels.earnings, Some(100));
+        // Blank values count as unset.
+        assert_eq!(config.channels.options, None);
+        assert!(config.earnings.day_threads);
+        assert_eq!(config.health.addr, Some("0.0.0.0:8080".parse().unwrap()));
+        assert_eq!(config.shutdown.notice, "brb");
+        assert_eq!(config.shutdown.notice_channel, Some(55));
+        assert_eq!(config.redis.url.as_deref(), Some("redis://localhost:6379"));
+        assert_eq!(
+            config.constituents.sp500_url,
+            "https://example.com/sp500.csv"
+        );
+        assert_eq!(config.constituents.dow30_url, "https://example.com/dow.txt");
+        assert!(config
+            .constituents
+            .nasdaq100_url
+            .starts_with("https://api.nasdaq.com/"));
+    });
+}
+
+#[test]
+fn single_guild_id_is_used_without_a_list() {
+    let path = config_file("guild-id", BASE);
+    let vars = [("CONFIG_PATH", path.to_str().unwrap()), ("GUILD_ID", "9")];
+    with_env(&vars, || {
+        assert_eq!(Config::load().unwrap().discord.guild_ids, vec![9]);
+    });
+}
+
+#[test]
+fn malformed_env_values_name_the_variable() {
+    let path = config_file("malformed", BASE);
+    let cases = [
+        ("APPLICATION_ID", "abc"),
+        ("EARNINGS_DAY_THREADS", "maybe"),
+        ("GUILD_IDS", "1,x"),
+        ("HEALTH_ADDR", "localhost"),
+        ("SHARD_RANGE", "3"),
+    ];
+    for (var, value) in cases {
+        let vars = [("CONFIG_PATH", path.to_str().unwrap()), (var, value)];
+        with_env(&vars, || match Config::load() {
+            Err(ConfigError::Env { var: got, .. }) => assert_eq!(got, var),
+            other => panic!("{var}={value}: expected an env error, got {other:?}"),
+        });
+    }
+}
+
+#[test]
+fn validation_reports_every_problem() {
+    let path = config_file(
+        "invalid",
+        r#"
+[discord]
+shard_range = [0, 1]
+
+[channels]
+earnings = 0
+
+[options]
+risk_free_rate = 0.5
+
+[constituents]
+sp500_url = "http://example.com/sp500.csv"
+"#,
+    );
+    let vars = [("CONFIG_PATH", path.to_str().unwrap())];
+    with_env(&vars, || {
+        let Err(ConfigError::Invalid(problems)) = Config::load() else {
+            panic!("expected validation to fail");
+        };
+        for expected in [
+            "discord.token (DISCORD_TOKEN) is required",
+            "discord.application_id (APPLICATION_ID) is required",
+            "channels.earnings must not be 0",
+            "discord.shard_range (SHARD_RANGE) needs discord.shard_count",
+            "options.risk_free_rate must be a fraction between 0 and 0.25",
+            "constituents.sp500_url (SP500_CONSTITUENTS_URL) must be an https URL",
+        ] {
+            assert!(
+                problems.contains(expected),
+                "{expected} missing: {problems}"
+            );
+        }
+    });
+}
+
+#[test]
+fn section_configs_read_only_their_env() {
+    with_env(&[("STORAGE_BACKEND", "postgres")], || {
+        assert!(matches!(
+            StorageConfig::from_env(),
+            Err(ConfigError::Invalid(_))
+        ));
+    });
+    with_env(
+        &[
+            ("STORAGE_BACKEND", "postgres"),
+            ("DATABASE_URL", "postgres://bot@localhost/bot"),
+        ],
+        || {
+            let storage = StorageConfig::from_env().unwrap();
+            assert_eq!(storage.backend, StorageBackend::Postgres);
+            assert_eq!(storage.sql_url(), "postgres://bot@localhost/bot");
+        },
+    );
+    with_env(&[("STORAGE_BACKEND", "mongo")], || {
+        assert!(matches!(
+            StorageConfig::from_env(),
+            Err(ConfigError::Env {
+                var: "STORAGE_BACKEND",
+                ..
+            })
+        ));
+    });
+    with_env(&[("HEALTH_ADDR", "127.0.0.1:8081")], || {
+        let health = HealthConfig::from_env().unwrap();
+        assert_eq!(health.addr, Some("127.0.0.1:8081".parse().unwrap()));
+    });
+    with_env(&[], || {
+        assert_eq!(RedisConfig::from_env().url, None);
+        assert_eq!(
+            StorageConfig::from_env().unwrap().backend,
+            StorageBackend::Redis
+        );
+    });
+}
+
+#[test]
+fn diff_lists_changed_keys_and_hides_secrets() {
+    let old = Config::default();
+    let mut new = old.clone();
+    new.discord.token = "secret".to_string();
+    new.channels.earnings = Some(100);
+    new.shutdown.notice = "brb".to_string();
+
+    let changes = old.diff(&new);
+    let keys: Vec<_> = changes.iter().map(|c| c.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        vec!["channels.earnings", "discord.token", "shutdown.notice"]
+    );
+
+    let earnings = &changes[0];
+    assert_eq!(
+        (earnings.old.as_str(), earnings.new.as_str()),
+        ("unset", "100")
+    );
+    assert!(!earnings.needs_restart);
+    let token = &changes[1];
+    assert_eq!(
+        (token.old.as_str(), token.new.as_str()),
+        ("(hidden)", "(hidden)")
+    );
+    assert!(token.needs_restart);
+
+    assert!(old.diff(&old.clone()).is_empty());
+}
+
+#[test]
+fn reload_reply_names_keys_without_values() {
+    let old = Config::default();
+    let mut new = old.clone();
+    new.channels.operator = Some(4242);
+    new.redis.url = Some("redis://internal:6379".to_string());
+
+    let text = format_changes(&old.diff(&new));
+    assert!(text.contains("• `channels.operator` changed"), "{text}");
+    assert!(
+        text.contains("• `redis.url` changed (applies after restart)"),
+        "{text}"
+    );
+    assert!(
+        !text.contains("4242") && !text.contains("internal"),
+        "{text}"
+    );
+    assert_eq!(format_changes(&[]), "🔄 Config reloaded; nothing changed.");
+}
+
+#[test]
+fn reload_swaps_a_valid_config_and_keeps_the_old_one_on_error() {
+    let path = config_file("reload", BASE);
+    let vars = [("CONFIG_PATH", path.to_str().unwrap())];
+    with_env(&vars, || {
+        let handle = ConfigHandle::new(Config::load().unwrap());
+
+        std::fs::write(&path, BASE.replace("earnings = 100", "earnings = 200")).unwrap();
+        let changes = handle.reload().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "channels.earnings");
+        assert_eq!(handle.current().channels.earnings, Some(200));
+
+        std::fs::write(&path, BASE.replace("earnings = 100", "earnings = 0")).unwrap();
+        assert!(matches!(handle.reload(), Err(ConfigError::Invalid(_))));
+        assert_eq!(handle.current().channels.earnings, Some(200));
+    });
+}
@@ -0,0 +1,113 @@
+use stacks_bot::service::finance::constituents::{
+    parse_nasdaq_rows, parse_symbol_column, parse_wiki_components, Index,
+};
+
+/// The head of the `datasets/s-and-p-500-companies` CSV, with a quoted name and share classes.
+const SP500_CSV: &str = "\
+Symbol,Security,GICS Sector,GICS Sub-Industry,Headquarters Location,Date added,CIK,Founded
+MMM,3M,Industrials,Industrial Conglomerates,\"Saint Paul, Minnesota\",1957-03-04,0000066740,1902
+AOS,A. O. Smith,Industrials,Building Products,\"Milwaukee, Wisconsin\",2017-07-26,0000091142,1916
+BRK.B,Berkshire Hathaway,Financials,Multi-Sector Holdings,\"Omaha, Nebraska\",1976-06-30,0001067983,1839
+BF.B,Brown–Forman,Consumer Staples,Distillers & Vintners,\"Louisville, Kentucky\",1982-10-31,0000014693,1870
+aapl ,Apple Inc.,Information Technology,Technology Hardware,\"Cupertino, California\",1982-11-30,0000320193,1977
+
+";
+
+/// A trimmed Nasdaq list API response.
+const NASDAQ_JSON: &str = r#"{
+  "data": {
+    "filters": null,
+    "headers": null,
+    "data": {
+      "asOf": "Oct 16, 2026",
+      "headers": {"symbol": "Symbol", "companyName": "Name"},
+      "rows": [
+        {"symbol": "AAPL", "companyName": "Apple Inc. Common Stock"},
+        {"symbol": "GOOGL", "companyName": "Alphabet Inc. Class A Common Stock"},
+        {"symbol": "GOOG", "companyName": "Alphabet Inc. Class C Capital Stock"},
+        {"symbol": " msft ", "companyName": "Microsoft Corporation Common Stock"},
+        {"companyName": "No symbol"}
+      ]
+    }
+  },
+  "message": null,
+  "status": {"rCode": 200}
+}"#;
+
+/// Wikitext around the Dow article's components table: members in the "Components" section,
+/// former members and citation templates elsewhere.
+const DOW_WIKITEXT: &str = "\
+== History ==
+Exxon Mobil ({{NYSE|XOM}}) was replaced in 2020.
+== Components ==
+{| class=\"wikitable sortable\"
+! Company !! Exchange !! Symbol
+|-
+| [[3M]] || NYSE || {{NYSE|MMM}}
+|-
+| [[Apple Inc.]] || NASDAQ || {{NASDAQ|AAPL}}
+|-
+| [[Nvidia]] || {{nowrap|NASDAQ}} || {{NasdaqSelect|NVDA}}
+|-
+| [[Travelers]] || NYSE || {{nyse|TRV}}<ref>{{cite web|url=https://example.com|title=Dow}}</ref>
+|}
+=== Former components ===
+{{NYSE|INTC}}
+";
+
+fn sorted(symbols: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut symbols: Vec<String> = symbols.into_iter().collect();
+    symbols.sort();
+    symbols
+}
+
+#[test]
+fn csv_first_column_becomes_yahoo_symbols() {
+    assert_eq!(
+        sorted(parse_symbol_column(SP500_CSV)),
+        ["AAPL", "AOS", "BF-B", "BRK-B", "MMM"]
+    );
+}
+
+#[test]
+fn csv_without_rows_has_no_symbols() {
+    assert!(parse_symbol_column("Symbol,Security\n").is_empty());
+    assert!(parse_symbol_column("").is_empty());
+}
+
+#[test]
+fn nasdaq_rows_become_yahoo_symbols() {
+    assert_eq!(
+        sorted(parse_nasdaq_rows(NASDAQ_JSON)),
+        ["AAPL", "GOOG", "GOOGL", "MSFT"]
+    );
+    assert!(parse_nasdaq_rows(r#"{"data": null}"#).is_empty());
+    assert!(parse_nasdaq_rows("<html>Access Denied</html>").is_empty());
+}
+
+#[test]
+fn wiki_components_section_lists_current_members_only() {
+    assert_eq!(
+        sorted(parse_wiki_components(DOW_WIKITEXT)),
+        ["AAPL", "MMM", "NVDA", "TRV"]
+    );
+    assert!(parse_wiki_components("== History ==\n{{NYSE|XOM}}").is_empty());
+}
+
+#[test]
+fn lists_of_the_wrong_size_are_rejected() {
+    let sp500 = Index::Sp500.expected_members();
+    assert!(sp500.contains(&503));
+    assert!(!sp500.contains(&5));
+    assert!(!sp500.contains(&1500));
+
+    let nasdaq100 = Index::Nasdaq100.expected_members();
+    assert!(nasdaq100.contains(&101));
+    assert!(!nasdaq100.contains(&0));
+    assert!(!nasdaq100.contains(&3000));
+
+    let dow30 = Index::Dow30.expected_members();
+    assert!(dow30.contains(&30));
+    assert!(!dow30.contains(&29));
+    assert!(!dow30.contains(&31));
+}
@@ -0,0 +1,136 @@
+use chrono::{NaiveDate, TimeZone};
+use chrono_tz::America::New_York;
+use serde_json::json;
+use stacks_bot::models::QuoteSummary;
+use stacks_bot::service::automation::corporate_actions::{format_alerts, SymbolActions};
+use stacks_bot::service::automation::jobs::Job;
+use stacks_bot::service::finance::corporate_actions::{
+    changes, split_label, ActionChange, CorporateActions,
+};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+}
+
+fn paying(rate: f64) -> CorporateActions {
+    CorporateActions {
+        dividend_rate: Some(rate),
+        ex_dividend_date: Some(day(30)),
+        ..CorporateActions::default()
+    }
+}
+
+#[test]
+fn state_comes_from_summary_detail_and_key_statistics() {
+    let summary = QuoteSummary::from_response(&json!({ "quoteSummary": { "result": [{
+        "summaryDetail": {
+            "dividendRate": { "raw": 1.04, "fmt": "1.04" },
+            "exDividendDate": { "raw": 1_762_387_200, "fmt": "2025-11-06" }
+        },
+        "defaultKeyStatistics": {
+            "lastSplitFactor": "4:1",
+            "lastSplitDate": { "raw": 1_598_832_000, "fmt": "2020-08-31" }
+        }
+    }] } }))
+    .unwrap()
+    .unwrap();
+    let state = CorporateActions::from_summary(&summary);
+    assert_eq!(state.dividend_rate, Some(1.04));
+    assert_eq!(state.ex_dividend_date, NaiveDate::from_ymd_opt(2025, 11, 6));
+    assert_eq!(state.split_factor.as_deref(), Some("4:1"));
+    assert_eq!(state.split_date, NaiveDate::from_ymd_opt(2020, 8, 31));
+}
+
+#[test]
+fn dividend_moves_past_a_percent_are_reported() {
+    let today = day(19);
+    assert_eq!(
+        changes(&paying(1.00), &paying(1.10), today),
+        vec![ActionChange::DividendIncrease {
+            from: 1.00,
+            to: 1.10,
+            ex_date: Some(day(30)),
+        }]
+    );
+    assert!(matches!(
+        changes(&paying(1.00), &paying(0.50), today)[..],
+        [ActionChange::DividendCut { .. }]
+    ));
+    assert!(changes(&paying(1.00), &paying(1.005), today).is_empty());
+    assert!(matches!(
+        changes(&CorporateActions::default(), &paying(0.40), today)[..],
+        [ActionChange::DividendInitiated { .. }]
+    ));
+    // A dropped field is not a suspension.
+    assert!(changes(&paying(1.00), &CorporateActions::default(), today).is_empty());
+}
+
+#[test]
+fn only_new_recent_splits_are_reported() {
+    let split = |date| CorporateActions {
+        split_factor: Some("10:1".to_string()),
+        split_date: Some(date),
+        ..CorporateActions::default()
+    };
+    let today = day(19);
+    assert_eq!(
+        changes(&CorporateActions::default(), &split(day(17)), today),
+        vec![ActionChange::Split {
+            factor: "10:1".to_string(),
+            date: day(17),
+        }]
+    );
+    assert!(changes(&split(day(17)), &split(day(17)), today).is_empty());
+    let old = NaiveDate::from_ymd_opt(2024, 6, 10).unwrap();
+    assert!(changes(&CorporateActions::default(), &split(old), today).is_empty());
+}
+
+#[test]
+fn split_factors_read_naturally() {
+    assert_eq!(split_label("4:1"), "4-for-1");
+    assert_eq!(split_label("1:10"), "1-for-10 reverse");
+    assert_eq!(split_label("3:2"), "3-for-2");
+}
+
+#[test]
+fn alerts_list_each_change() {
+    let found = vec![
+        SymbolActions {
+            symbol: "KO".to_string(),
+            changes: vec![ActionChange::DividendIncrease {
+                from: 1.94,
+                to: 2.04,
+                ex_date: Some(day(30)),
+            }],
+        },
+        SymbolActions {
+            symbol: "NVDA".to_string(),
+            changes: vec![ActionChange::Split {
+                factor: "10:1".to_string(),
+                date: day(17),
+            }],
+        },
+    ];
+    let text = format_alerts(&found, 40, 1);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "📣 **Splits and dividends**");
+    assert_eq!(
+        lines[1],
+        "📈 **KO** dividend raised $1.94 → $2.04 a year (+5.2%) · ex-div Oct 30"
+    );
+    assert_eq!(lines[2], "✂️ **NVDA** 10-for-1 split · Oct 17, 2026");
+    assert_eq!(
+        lines.last().copied(),
+        Some("*Checked 40 watchlist symbols · 1 failed to load*")
+    );
+}
+
+#[test]
+fn check_runs_weekday_mornings() {
+    let saturday = New_York.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap();
+    let next = Job::CorporateActions.next_run(saturday).unwrap();
+    assert_eq!(
+        next,
+        New_York.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap()
+    );
+}
@@ -0,0 +1,36 @@
+use stacks_bot::service::csv;
+
+#[test]
+fn plain_cells_are_written_as_is() {
+    assert_eq!(csv::field("AAPL"), "AAPL");
+    assert_eq!(csv::row(&["AAPL", "Apple Inc", ""]), "AAPL,Apple Inc,\n");
+}
+
+#[test]
+fn commas_quotes_and_line_breaks_are_quoted() {
+    assert_eq!(csv::field("Apple, Inc."), "\"Apple, Inc.\"");
+    assert_eq!(csv::field("the \"big\" one"), "\"the \"\"big\"\" one\"");
+    assert_eq!(csv::field("line one\nline two"), "\"line one\nline two\"");
+    assert_eq!(csv::field("a\r\nb"), "\"a\r\nb\"");
+}
+
+#[test]
+fn formula_like_text_is_escaped() {
+    assert_eq!(
+        csv::field("=HYPERLINK(\"x\")"),
+        "\"'=HYPERLINK(\"\"x\"\")\""
+    );
+    assert_eq!(csv::field("+cmd"), "'+cmd");
+    assert_eq!(csv::field("-2+3"), "'-2+3");
+    assert_eq!(csv::field("@SUM(A1)"), "'@SUM(A1)");
+    assert_eq!(csv::field("\tcmd"), "'\tcmd");
+}
+
+#[test]
+fn numbers_keep_their_sign() {
+    assert_eq!(csv::field("-0.42"), "-0.42");
+    assert_eq!(csv::field("+1.5"), "+1.5");
+    assert_eq!(csv::number(Some(-1.25)), "-1.25");
+    assert_eq!(csv::number(None), "");
+    assert_eq!(csv::row(&[csv::number(Some(-3.0))]), "-3\n");
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::date;
+use stacks_bot::service::command::dcf::dcf_text;
+use stacks_bot::service::finance::dcf::{
+    default_growth, present_value, value_range, DcfInputs, DEFAULT_GROWTH,
+};
+
+fn inputs(growth: f64, discount: f64, terminal_growth: f64) -> DcfInputs {
+    DcfInputs {
+        fcf: 100.0,
+        growth,
+        discount,
+        terminal_growth,
+        years: 2,
+    }
+}
+
+#[test]
+fn discounts_projected_cash_flows_and_the_terminal_value() {
+    let value = present_value(&inputs(0.10, 0.10, 0.0)).unwrap();
+    // 110 / 1.1 + 121 / 1.21 = 200; terminal 121 / 0.1 = 1210, discounted to 1000.
+    assert!((value.total - 1200.0).abs() < 1e-9);
+    assert!((value.terminal - 1000.0).abs() < 1e-9);
+
+    assert!(present_value(&inputs(0.10, 0.03, 0.03)).is_none());
+}
+
+#[test]
+fn range_moves_growth_and_discount_against_each_other() {
+    let range = value_range(&inputs(0.10, 0.10, 0.0), 10.0).unwrap();
+    assert!((range.base - 120.0).abs() < 1e-9);
+    assert!(range.low < range.base && range.base < range.high);
+    assert!((range.terminal_share - 1000.0 / 1200.0).abs() < 1e-9);
+
+    // Lowering the discount to 3% would meet terminal growth, so the bull case keeps 4%.
+    let tight = value_range(&inputs(0.05, 0.04, 0.03), 1.0).unwrap();
+    assert!(tight.high.is_finite() && tight.high > tight.base);
+
+    assert!(value_range(&inputs(0.10, 0.10, 0.0), 0.0).is_none());
+}
+
+#[test]
+fn default_growth_is_the_clamped_three_year_cagr() {
+    let at = date(2024, 9, 30);
+    let steady = [(date(2021, 9, 30), 100.0), (at, 133.1)];
+    assert!((default_growth(&steady, at) - 0.10).abs() < 1e-9);
+
+    let boom = [(date(2021, 9, 30), 100.0), (at, 300.0)];
+    assert_eq!(default_growth(&boom, at), 0.15);
+
+    let shrinking = [(date(2021, 9, 30), 100.0), (at, 50.0)];
+    assert_eq!(default_growth(&shrinking, at), 0.0);
+
+    assert_eq!(default_growth(&[(at, 100.0)], at), DEFAULT_GROWTH);
+}
+
+#[test]
+fn text_reports_the_range_against_the_price() {
+    let inputs = DcfInputs {
+        fcf: 100e9,
+        growth: 0.08,
+        discount: 0.10,
+        terminal_growth: 0.025,
+        years: 5,
+    };
+    let range = value_range(&inputs, 15e9).unwrap();
+    let text = dcf_text("AAPL", &inputs, &range, 200.0, "USD", "TTM Jun '25");
+
+    assert!(text.starts_with("🧮 **AAPL DCF**"));
+    assert!(text.contains(&format!("(base ${:.2}) vs price $200.00", range.base)));
+    assert!(text.contains("• FCF base (TTM Jun '25): $100.00B"));
+    assert!(text.contains("• Growth 8.0% a year for 5 years, then 2.5% terminal"));
+    assert!(text.contains("• Discount rate 10.0%"));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::event;
+use stacks_bot::models::EarningsEstimate;
+
+fn estimate(report_dates: &[u32]) -> EarningsEstimate {
+    EarningsEstimate {
+        eps: Some(1.6),
+        revenue: Some(9.4e10),
+        market_cap: Some(3.5e12),
+        report_dates: report_dates
+            .iter()
+            .map(|day| NaiveDate::from_ymd_opt(2026, 10, *day).unwrap())
+            .collect(),
+    }
+}
+
+#[test]
+fn estimates_for_the_listed_report_are_filled() {
+    let mut ev = event("AAPL", 29, "amc");
+    ev.fill_estimates(&estimate(&[29]));
+    assert_eq!(ev.eps_estimate, Some(1.6));
+    assert_eq!(ev.revenue_estimate, Some(9.4e10));
+    assert_eq!(ev.market_cap, Some(3.5e12));
+}
+
+#[test]
+fn a_day_either_side_or_inside_a_window_still_matches() {
+    assert!(estimate(&[29]).is_for(NaiveDate::from_ymd_opt(2026, 10, 30).unwrap()));
+    assert!(estimate(&[29]).is_for(NaiveDate::from_ymd_opt(2026, 10, 28).unwrap()));
+    assert!(estimate(&[20, 24]).is_for(NaiveDate::from_ymd_opt(2026, 10, 22).unwrap()));
+    assert!(!estimate(&[20, 24]).is_for(NaiveDate::from_ymd_opt(2026, 10, 26).unwrap()));
+}
+
+#[test]
+fn estimates_for_another_report_only_fill_the_market_cap() {
+    // Yahoo already points at the next quarter, so its consensus is not for this report.
+    let mut ev = event("AAPL", 1, "amc");
+    ev.fill_estimates(&estimate(&[29]));
+    assert_eq!((ev.eps_estimate, ev.revenue_estimate), (None, None));
+    assert_eq!(ev.market_cap, Some(3.5e12));
+
+    let mut undated = event("AAPL", 29, "amc");
+    undated.fill_estimates(&estimate(&[]));
+    assert_eq!(undated.eps_estimate, None);
+}
+
+#[test]
+fn calendar_values_are_kept() {
+    let mut ev = event("AAPL", 29, "amc");
+    ev.eps_estimate = Some(1.5);
+    ev.fill_estimates(&estimate(&[29]));
+    assert_eq!(ev.eps_estimate, Some(1.5));
+    assert_eq!(ev.revenue_estimate, Some(9.4e10));
+}
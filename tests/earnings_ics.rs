@@ -0,0 +1,57 @@
+mod common;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use common::event;
+use stacks_bot::models::EarningsEvent;
+use stacks_bot::service::command::earnings_ics::{calendar, week_of};
+use stacks_bot::service::ics;
+
+fn estimated(symbol: &str, time_of_day: &str) -> EarningsEvent {
+    EarningsEvent {
+        eps_estimate: Some(1.25),
+        title: Some(format!("{symbol} Inc, Q3")),
+        ..event(symbol, 21, time_of_day)
+    }
+}
+
+#[test]
+fn text_values_are_escaped() {
+    assert_eq!(ics::escape("a,b;c\\d\ne"), r"a\,b\;c\\d\ne");
+}
+
+#[test]
+fn long_lines_fold_within_75_octets() {
+    let folded = ics::line(&format!("DESCRIPTION:{}", "é".repeat(60)));
+    let lines: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|l| l.len() <= 75));
+    assert!(lines[1].starts_with(' '));
+}
+
+#[test]
+fn calendar_has_an_event_per_report_in_time_order() {
+    let stamp = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+    let events = vec![estimated("NVDA", "amc"), estimated("KO", "bmo")];
+    let body = calendar("Earnings — week of Oct 19", &events, stamp);
+
+    assert!(body.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(body.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(body.matches("BEGIN:VEVENT").count(), 2);
+    assert!(body.contains("X-WR-CALNAME:Earnings — week of Oct 19\r\n"));
+    // 8:00 AM and 4:00 PM New York time are 12:00 and 20:00 UTC in October.
+    let ko = body.find("UID:KO-20261021@stacks-bot").unwrap();
+    let nvda = body.find("UID:NVDA-20261021@stacks-bot").unwrap();
+    assert!(ko < nvda);
+    assert!(body.contains("DTSTART:20261021T120000Z\r\n"));
+    assert!(body.contains("DTSTART:20261021T200000Z\r\n"));
+    assert!(body.contains("SUMMARY:NVDA earnings (AMC)\r\n"));
+    assert!(body.contains("DESCRIPTION:NVDA Inc\\, Q3\\nEPS estimate: "));
+}
+
+#[test]
+fn weekends_export_the_coming_week() {
+    let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+    assert_eq!(week_of(day(14)), (day(12), day(16)));
+    assert_eq!(week_of(day(17)), (day(19), day(23)));
+    assert_eq!(week_of(day(18)), (day(19), day(23)));
+}
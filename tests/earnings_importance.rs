@@ -0,0 +1,21 @@
+use stacks_bot::service::finance::importance::score;
+
+#[test]
+fn mega_caps_with_heavy_volume_score_highest() {
+    assert_eq!(score(Some(3.5e12), Some(50e6)), Some(5));
+    assert_eq!(score(Some(800e9), Some(3e6)), Some(4));
+    assert_eq!(score(Some(40e9), Some(12e6)), Some(4));
+    assert_eq!(score(Some(40e9), Some(2e6)), Some(3));
+}
+
+#[test]
+fn small_caps_score_low() {
+    assert_eq!(score(Some(5e9), None), Some(2));
+    assert_eq!(score(Some(300e6), Some(100e3)), Some(1));
+    assert_eq!(score(None, Some(25e6)), Some(2));
+}
+
+#[test]
+fn nothing_known_leaves_it_unscored() {
+    assert_eq!(score(None, None), None);
+}
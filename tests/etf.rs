@@ -0,0 +1,85 @@
+use serde_json::json;
+use stacks_bot::models::etf::sector_label;
+use stacks_bot::models::{EtfProfile, QuoteSummary};
+use stacks_bot::service::command::etf::profile_text;
+
+fn summary(value: serde_json::Value) -> QuoteSummary {
+    QuoteSummary::from_response(&json!({ "quoteSummary": { "result": [value], "error": null } }))
+        .unwrap()
+        .unwrap()
+}
+
+fn spy() -> QuoteSummary {
+    summary(json!({
+        "price": {
+            "symbol": "SPY",
+            "shortName": "SPDR S&P 500",
+            "longName": "SPDR S&P 500 ETF Trust",
+            "quoteType": "ETF",
+            "currency": "USD"
+        },
+        "summaryDetail": { "totalAssets": { "raw": 580_120_000_000.0, "fmt": "580.12B" } },
+        "topHoldings": {
+            "holdings": [
+                { "symbol": "NVDA", "holdingName": "NVIDIA Corp", "holdingPercent": { "raw": 0.0752, "fmt": "7.52%" } },
+                { "symbol": "MSFT", "holdingName": "Microsoft Corp", "holdingPercent": { "raw": 0.0661, "fmt": "6.61%" } },
+                { "symbol": "", "holdingName": "Cash", "holdingPercent": {} }
+            ],
+            "sectorWeightings": [
+                { "realestate": { "raw": 0.0204, "fmt": "2.04%" } },
+                { "technology": { "raw": 0.3312, "fmt": "33.12%" } },
+                { "consumer_cyclical": { "raw": 0.1047, "fmt": "10.47%" } },
+                { "utilities": {} }
+            ]
+        },
+        "fundProfile": {
+            "family": "State Street Investment Management",
+            "categoryName": "Large Blend",
+            "feesExpensesInvestment": { "annualReportExpenseRatio": { "raw": 0.000945, "fmt": "0.09%" } }
+        }
+    }))
+}
+
+#[test]
+fn fund_modules_become_a_profile() {
+    let profile = EtfProfile::from_summary("spy", spy()).unwrap();
+    assert_eq!(profile.symbol, "SPY");
+    assert_eq!(profile.name.as_deref(), Some("SPDR S&P 500 ETF Trust"));
+    assert_eq!(profile.category.as_deref(), Some("Large Blend"));
+    assert_eq!(profile.expense_ratio, Some(0.000945));
+    assert_eq!(profile.net_assets, Some(580_120_000_000.0));
+    // Holdings without a weight are dropped.
+    assert_eq!(profile.holdings.len(), 2);
+    let sectors: Vec<&str> = profile.sectors.iter().map(|s| s.sector.as_str()).collect();
+    assert_eq!(sectors, ["Technology", "Consumer Cyclical", "Real Estate"]);
+}
+
+#[test]
+fn stocks_are_not_funds() {
+    let aapl = summary(json!({
+        "price": { "symbol": "AAPL", "longName": "Apple Inc.", "quoteType": "EQUITY" },
+        "summaryDetail": { "trailingPE": { "raw": 33.1 } }
+    }));
+    assert!(EtfProfile::from_summary("AAPL", aapl).is_none());
+}
+
+#[test]
+fn profile_lists_costs_holdings_and_sectors() {
+    let text = profile_text(&EtfProfile::from_summary("SPY", spy()).unwrap());
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "📦 **SPY — SPDR S&P 500 ETF Trust**");
+    assert_eq!(lines[1], "State Street Investment Management · Large Blend");
+    assert_eq!(lines[2], "Expense ratio 0.09% · AUM $580.12B");
+    assert!(text.contains("1. **NVDA** NVIDIA Corp — 7.52%"));
+    assert!(text.contains("*Top 2 together: 14.13%*"));
+    assert!(text.contains("• Technology 33.12%"));
+}
+
+#[test]
+fn sector_keys_read_as_names() {
+    assert_eq!(
+        sector_label("communication_services"),
+        "Communication Services"
+    );
+    assert_eq!(sector_label("realestate"), "Real Estate");
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use common::date;
+use stacks_bot::service::automation::event_alerts::Fired;
+use stacks_bot::service::caching::collections::event_alerts::EventAlert;
+use stacks_bot::service::command::event_alert::{alert_line, list_text};
+use stacks_bot::service::finance::fundamental_events::{
+    evaluate, EventChange, EventKind, EventSnapshot,
+};
+
+fn snapshot(earnings_date: Option<NaiveDate>, target_mean: Option<f64>) -> EventSnapshot {
+    EventSnapshot {
+        earnings_date,
+        target_mean,
+    }
+}
+
+fn alert(kind: EventKind, seen: EventSnapshot) -> EventAlert {
+    EventAlert {
+        id: "abc".to_string(),
+        symbol: "NVDA".to_string(),
+        kind,
+        threshold: 0.05,
+        guild_id: 1,
+        channel_id: 2,
+        user_id: 3,
+        created_at: Utc.with_ymd_and_hms(2025, 8, 1, 12, 0, 0).unwrap(),
+        seen,
+    }
+}
+
+#[test]
+fn earnings_dates_fire_when_scheduled_or_moved() {
+    let today = date(2025, 8, 4);
+    let kind = EventKind::EarningsDate;
+    let aug20 = snapshot(Some(date(2025, 8, 20)), None);
+    let aug27 = snapshot(Some(date(2025, 8, 27)), None);
+
+    let (change, next) = evaluate(kind, 0.05, &EventSnapshot::default(), &aug20, today);
+    assert_eq!(
+        change,
+        Some(EventChange::EarningsScheduled {
+            date: date(2025, 8, 20)
+        })
+    );
+    assert_eq!(next, aug20);
+
+    let (change, next) = evaluate(kind, 0.05, &aug20, &aug27, today);
+    assert_eq!(
+        change,
+        Some(EventChange::EarningsMoved {
+            from: date(2025, 8, 20),
+            to: date(2025, 8, 27)
+        })
+    );
+    assert_eq!(next, aug27);
+
+    assert_eq!(evaluate(kind, 0.05, &aug27, &aug27, today).0, None);
+    // Yahoo dropping the date keeps the last one rather than firing when it comes back.
+    let (change, next) = evaluate(kind, 0.05, &aug27, &EventSnapshot::default(), today);
+    assert_eq!((change, next), (None, aug27.clone()));
+
+    // After the report, the next quarter's date is a new schedule, not a move.
+    let november = snapshot(Some(date(2025, 11, 19)), None);
+    let (change, _) = evaluate(kind, 0.05, &aug27, &november, date(2025, 8, 28));
+    assert_eq!(
+        change,
+        Some(EventChange::EarningsScheduled {
+            date: date(2025, 11, 19)
+        })
+    );
+}
+
+#[test]
+fn targets_fire_on_the_threshold_from_the_last_fired_value() {
+    let kind = EventKind::TargetPrice;
+    let today = date(2025, 8, 4);
+    let seen = snapshot(None, Some(100.0));
+
+    // +3%: below the threshold, and the baseline stays so drift accumulates.
+    let (change, next) = evaluate(kind, 0.05, &seen, &snapshot(None, Some(103.0)), today);
+    assert_eq!(change, None);
+    assert_eq!(next, seen);
+
+    let (change, next) = evaluate(kind, 0.05, &next, &snapshot(None, Some(106.0)), today);
+    assert_eq!(
+        change,
+        Some(EventChange::TargetMoved {
+            from: 100.0,
+            to: 106.0
+        })
+    );
+    assert_eq!(next.target_mean, Some(106.0));
+
+    // A first target only sets the baseline.
+    let (change, next) = evaluate(
+        kind,
+        0.05,
+        &EventSnapshot::default(),
+        &snapshot(None, Some(50.0)),
+        today,
+    );
+    assert_eq!((change, next.target_mean), (None, Some(50.0)));
+}
+
+#[test]
+fn lines_describe_alerts_and_changes() {
+    let earnings = alert(
+        EventKind::EarningsDate,
+        snapshot(Some(date(2025, 8, 27)), None),
+    );
+    assert_eq!(
+        alert_line(&earnings),
+        "**NVDA** next earnings date (now Aug 27, 2025) · ID `abc`"
+    );
+    let target = alert(EventKind::TargetPrice, snapshot(None, Some(180.0)));
+    assert_eq!(
+        alert_line(&target),
+        "**NVDA** analyst mean target (±5%, from $180.00) · ID `abc`"
+    );
+
+    let list = list_text(&[&earnings]);
+    assert!(list.starts_with("🔔 **Event alerts** (1/25)"));
+    assert!(list.contains("· <#2> by <@3>"));
+    assert!(list_text(&[]).starts_with("No event alerts"));
+
+    let fired = Fired {
+        alert: target,
+        change: EventChange::TargetMoved {
+            from: 180.0,
+            to: 200.0,
+        },
+    };
+    assert_eq!(
+        fired.line(),
+        "🔔 <@3> **NVDA** analyst mean target $180.00 → $200.00 (+11.1%)"
+    );
+    let moved = Fired {
+        alert: earnings,
+        change: EventChange::EarningsMoved {
+            from: date(2025, 8, 20),
+            to: date(2025, 8, 27),
+        },
+    };
+    assert_eq!(
+        moved.line(),
+        "🔔 <@3> **NVDA** earnings moved Aug 20 → Aug 27, 2025"
+    );
+}
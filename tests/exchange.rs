@@ -0,0 +1,76 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use stacks_bot::service::finance::exchange::{normalize_symbol, Exchange};
+
+#[test]
+fn normalizes_prefixes_aliases_and_case() {
+    assert_eq!(normalize_symbol("TSX:RY"), "RY.TO");
+    assert_eq!(normalize_symbol("RY.TSX"), "RY.TO");
+    assert_eq!(normalize_symbol(" ry.to "), "RY.TO");
+    assert_eq!(normalize_symbol("sap.xetra"), "SAP.DE");
+    assert_eq!(normalize_symbol("TYO:7203"), "7203.T");
+    assert_eq!(normalize_symbol("NASDAQ:aapl"), "AAPL");
+}
+
+#[test]
+fn pads_hong_kong_codes() {
+    assert_eq!(normalize_symbol("700.HK"), "0700.HK");
+    assert_eq!(normalize_symbol("HKG:5"), "0005.HK");
+    assert_eq!(normalize_symbol("9988.hk"), "9988.HK");
+}
+
+#[test]
+fn leaves_other_input_alone() {
+    assert_eq!(normalize_symbol("BRK.B"), "BRK.B");
+    assert_eq!(normalize_symbol("GC=F"), "GC=F");
+    assert_eq!(normalize_symbol("Royal Bank"), "Royal Bank");
+    assert_eq!(normalize_symbol("FOO:BAR"), "FOO:BAR");
+    assert_eq!(normalize_symbol(".TO"), ".TO");
+}
+
+#[test]
+fn finds_the_exchange_from_the_suffix() {
+    assert_eq!(Exchange::for_symbol("RY.TO").currency, "CAD");
+    assert_eq!(Exchange::for_symbol("VOD.L").currency, "GBp");
+    assert_eq!(Exchange::for_symbol("7203.T").timezone, Tz::Asia__Tokyo);
+    assert!(!Exchange::for_symbol("7203.T").has_options);
+
+    let us = Exchange::for_symbol("BRK.B");
+    assert_eq!(us.suffix, "");
+    assert!(us.has_options);
+    assert_eq!(Exchange::for_symbol("AAPL").currency, "USD");
+}
+
+#[test]
+fn sessions_follow_local_hours_on_weekdays() {
+    let tokyo = Exchange::for_symbol("7203.T");
+    let us = Exchange::for_symbol("AAPL");
+
+    // Friday 2026-10-16, 01:00 UTC is 10:00 in Tokyo and 21:00 Thursday in New York.
+    let friday_morning = Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap();
+    assert!(tokyo.is_open(friday_morning));
+    assert!(!us.is_open(friday_morning));
+
+    // Saturday is closed everywhere.
+    let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 15, 0, 0).unwrap();
+    assert!(!us.is_open(saturday));
+}
+
+#[test]
+fn session_status_reports_the_next_change() {
+    let us = Exchange::for_symbol("AAPL");
+    let tokyo = Exchange::for_symbol("7203.T");
+
+    let us_session = Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    assert_eq!(us.session_status(us_session, None), "open until 16:00 EDT");
+
+    let friday_evening = Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap();
+    assert_eq!(
+        tokyo.session_status(friday_evening, None),
+        "closed (opens Mon 09:00 JST)"
+    );
+    assert_eq!(
+        tokyo.session_status(friday_evening, Some(Tz::America__New_York)),
+        "closed (opens Sun 20:00 EDT)"
+    );
+}
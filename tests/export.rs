@@ -0,0 +1,182 @@
+use serde_json::{json, Value};
+use stacks_bot::models::{
+    FinancialStatement, Frequency, HolderType, HoldersOverview, StatementType,
+};
+use stacks_bot::service::command::export::Table;
+use stacks_bot::service::command::fundamentals::statement_table;
+use stacks_bot::service::command::holders::export_table;
+use stacks_bot::service::finance::fx::Conversion;
+
+fn holders() -> HoldersOverview {
+    serde_json::from_value(json!({
+        "symbol": "AAPL",
+        "major_breakdown": { "breakdownData": {
+            "insidersPercentHeld": { "raw": 0.02, "fmt": "2.00%" },
+            "institutionsCount": 6500
+        } },
+        "institutional_holders": [{
+            "holder": "Vanguard", "shares": 1300, "dateReported": "2026-06-30T00:00:00Z",
+            "percentOut": 0.09, "value": 250000
+        }],
+        "mutualfund_holders": [{
+            "holder": "Index Fund", "shares": 400, "dateReported": "2026-07-31T00:00:00Z"
+        }],
+        "insider_transactions": [{
+            "startDate": "2026-08-01T00:00:00Z", "insider": "COOK TIMOTHY", "position": "CEO",
+            "transaction": "Sale", "shares": 100, "value": 20000, "ownership": "D"
+        }],
+        "insider_purchases": { "period": "6m", "purchasesShares": 10, "netShares": -90 },
+        "insider_roster": [{
+            "name": "COOK TIMOTHY", "position": "CEO", "latestTransactionDate": "2026-08-01T00:00:00Z",
+            "sharesOwnedDirectly": 3000000
+        }]
+    }))
+    .unwrap()
+}
+
+fn columns(table: &Table) -> Vec<&str> {
+    table.columns().iter().map(String::as_str).collect()
+}
+
+fn rows(table: &Table) -> Vec<Value> {
+    serde_json::from_str::<Vec<Value>>(&table.to_json().unwrap()).unwrap()
+}
+
+#[test]
+fn major_holders_list_each_metric() {
+    let table = export_table(&holders(), HolderType::Major);
+    assert_eq!(columns(&table), ["metric", "value"]);
+    assert_eq!(
+        table.to_csv(),
+        "metric,value\ninsidersPercentHeld,0.02\ninstitutionsCount,6500\n"
+    );
+}
+
+#[test]
+fn institutional_and_fund_holders_share_columns() {
+    let holders = holders();
+    for kind in [HolderType::Institutional, HolderType::MutualFund] {
+        let table = export_table(&holders, kind);
+        assert_eq!(
+            columns(&table),
+            ["holder", "shares", "percent_out", "value", "date_reported"]
+        );
+        assert_eq!(table.len(), 2);
+    }
+    let table = export_table(&holders, HolderType::Institutional);
+    assert_eq!(
+        table.to_csv().lines().nth(2),
+        Some("Index Fund,400,,,2026-07-31")
+    );
+}
+
+#[test]
+fn insider_tables_have_their_own_columns() {
+    let holders = holders();
+
+    let transactions = export_table(&holders, HolderType::InsiderTransactions);
+    assert_eq!(
+        columns(&transactions),
+        [
+            "date",
+            "insider",
+            "position",
+            "transaction",
+            "shares",
+            "value",
+            "ownership"
+        ]
+    );
+    assert_eq!(rows(&transactions)[0]["date"], "2026-08-01");
+
+    let purchases = export_table(&holders, HolderType::InsiderPurchases);
+    assert_eq!(
+        columns(&purchases),
+        [
+            "period",
+            "purchases_shares",
+            "purchases_transactions",
+            "sales_shares",
+            "sales_transactions",
+            "net_shares",
+            "net_transactions",
+            "total_insider_shares",
+        ]
+    );
+    let row = &rows(&purchases)[0];
+    assert_eq!(
+        (row["net_shares"].clone(), row["sales_shares"].clone()),
+        (json!(-90), Value::Null)
+    );
+
+    let roster = export_table(&holders, HolderType::InsiderRoster);
+    assert_eq!(
+        columns(&roster),
+        [
+            "name",
+            "position",
+            "most_recent_transaction",
+            "latest_transaction_date",
+            "shares_owned_directly",
+            "shares_owned_indirectly",
+        ]
+    );
+    assert_eq!(rows(&roster)[0]["latest_transaction_date"], "2026-08-01");
+}
+
+#[test]
+fn missing_sections_give_an_empty_table() {
+    let mut holders = holders();
+    holders.insider_roster = None;
+    let table = export_table(&holders, HolderType::InsiderRoster);
+    assert!(table.is_empty());
+    assert_eq!(table.to_json().unwrap(), "[]");
+}
+
+#[test]
+fn statements_have_a_column_per_metric_newest_first() {
+    let statement: FinancialStatement = serde_json::from_value(json!({
+        "symbol": "AAPL",
+        "statement_type": "income",
+        "frequency": "annual",
+        "statement": {
+            "TotalRevenue": {
+                "2024-09-30": { "reportedValue": { "raw": 391.0 } },
+                "2025-09-30": { "reportedValue": { "raw": 416.0 } }
+            },
+            "DilutedAverageShares": { "2025-09-30": { "reportedValue": { "raw": 15.0 } } }
+        }
+    }))
+    .unwrap();
+    let fx = Conversion {
+        from: "USD".to_string(),
+        to: "EUR".to_string(),
+        rate: 0.5,
+    };
+
+    let table = statement_table(
+        &[statement],
+        StatementType::IncomeStatement,
+        Frequency::Annual,
+        None,
+        None,
+        &fx,
+    )
+    .expect("statement rows");
+    assert_eq!(
+        columns(&table),
+        [
+            "date",
+            "currency",
+            "diluted_average_shares",
+            "total_revenue"
+        ]
+    );
+    // Amounts are converted; share counts are not.
+    assert_eq!(
+        table.to_csv(),
+        "date,currency,diluted_average_shares,total_revenue\n\
+         2025-09-30,EUR,15.0,208.0\n\
+         2024-09-30,EUR,,195.5\n"
+    );
+}
@@ -0,0 +1,55 @@
+use chrono::{Duration, TimeZone, Utc};
+use stacks_bot::service::automation::failure_streak::{FailureStreak, StreakEvent};
+
+fn failed() -> Result<(), String> {
+    Err("quote feed timed out".to_string())
+}
+
+#[test]
+fn backoff_doubles_per_failure_and_caps_at_two_hours() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    let mut streak = FailureStreak::new(Duration::minutes(15));
+    let mut waits = Vec::new();
+    for _ in 0..6 {
+        streak.record(&failed(), now, 10);
+        waits.push(streak.backoff().num_minutes());
+    }
+    assert_eq!(waits, vec![15, 30, 60, 120, 120, 120]);
+
+    assert!(streak.backing_off(now + Duration::minutes(60)));
+    // Runs due within a minute of the retry time go ahead.
+    assert!(!streak.backing_off(now + Duration::minutes(119)));
+}
+
+#[test]
+fn alerts_once_at_the_threshold_and_reports_recovery() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    let mut streak = FailureStreak::new(Duration::minutes(15));
+
+    assert_eq!(streak.record(&failed(), now, 3), None);
+    assert_eq!(streak.record(&failed(), now, 3), None);
+    assert_eq!(
+        streak.record(&failed(), now, 3),
+        Some(StreakEvent::Alert {
+            failures: 3,
+            last_error: "quote feed timed out".to_string(),
+        })
+    );
+    assert_eq!(streak.record(&failed(), now, 3), None);
+
+    assert_eq!(
+        streak.record(&Ok(()), now, 3),
+        Some(StreakEvent::Recovered { failures: 4 })
+    );
+    assert_eq!(streak.failures(), 0);
+    assert!(!streak.backing_off(now));
+}
+
+#[test]
+fn success_before_the_threshold_is_silent() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    let mut streak = FailureStreak::new(Duration::minutes(15));
+    assert_eq!(streak.record(&failed(), now, 3), None);
+    assert_eq!(streak.record(&Ok(()), now, 3), None);
+    assert_eq!(streak.backoff(), Duration::minutes(15));
+}
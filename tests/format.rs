@@ -0,0 +1,57 @@
+use stacks_bot::service::format;
+
+#[test]
+fn compact_scales_to_the_largest_fitting_suffix() {
+    assert_eq!(format::compact(94_930_000_000.0), "94.93B");
+    assert_eq!(format::compact(2_900_000_000_000.0), "2.90T");
+    assert_eq!(format::compact(1_500_000.0), "1.50M");
+    assert_eq!(format::compact(12_345.0), "12.35K");
+    assert_eq!(format::compact(950.0), "950.00");
+    assert_eq!(format::compact(-3_200_000.0), "-3.20M");
+}
+
+#[test]
+fn currency_puts_the_sign_before_the_dollar() {
+    assert_eq!(format::currency(-1_200_000.0), "-$1.20M");
+    assert_eq!(format::currency(85_780_000_000.0), "$85.78B");
+    assert_eq!(format::currency_in(1_200_000.0, "EUR"), "1.20M EUR");
+    assert_eq!(format::currency_in(1_200_000.0, "USD"), "$1.20M");
+}
+
+#[test]
+fn per_share_amounts_are_never_scaled() {
+    assert_eq!(format::per_share(1.456), "$1.46");
+    assert_eq!(format::per_share(-0.42), "-$0.42");
+    assert_eq!(format::per_share(0.004), "$0.00");
+    assert_eq!(format::per_share_in(1.456, "JPY"), "1.46 JPY");
+    assert_eq!(format::per_share_in(1.456, "USD"), "$1.46");
+}
+
+#[test]
+fn shares_are_whole_below_a_thousand() {
+    assert_eq!(format::shares(950.0), "950");
+    assert_eq!(format::shares(15_204_137_000.0), "15.20B");
+}
+
+#[test]
+fn percents_and_fractions() {
+    assert_eq!(format::percent(12.345), "12.35%");
+    assert_eq!(format::fraction_percent(0.12345), "12.35%");
+    assert_eq!(format::fraction_percent(-0.05), "-5.00%");
+}
+
+#[test]
+fn strikes_drop_whole_decimals() {
+    assert_eq!(format::strike(450.0), "450");
+    assert_eq!(format::strike(452.5), "452.5");
+}
+
+#[test]
+fn levels_and_counts_group_thousands() {
+    assert_eq!(format::level(42_012.5), "42,012.50");
+    assert_eq!(format::level(-1_234_567.891), "-1,234,567.89");
+    assert_eq!(format::level(999.0), "999.00");
+    assert_eq!(format::count(3412), "3,412");
+    assert_eq!(format::count(-1_000_000), "-1,000,000");
+    assert_eq!(format::count(12), "12");
+}
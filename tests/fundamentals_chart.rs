@@ -0,0 +1,125 @@
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use common::date;
+use serde_json::json;
+use stacks_bot::models::{FinancialStatement, Frequency};
+use stacks_bot::service::command::fundamentals_chart::{
+    chart_text, metric_series, period_label, ChartMetric, MAX_PERIODS,
+};
+
+fn statement(
+    statement_type: &str,
+    frequency: &str,
+    field: &str,
+    values: &[(&str, f64)],
+) -> FinancialStatement {
+    let series = values
+        .iter()
+        .map(|(date, raw)| {
+            let value = json!({
+                "asOfDate": date,
+                "currencyCode": "USD",
+                "reportedValue": { "raw": raw, "fmt": "n/a" }
+            });
+            (date.to_string(), value)
+        })
+        .collect();
+    FinancialStatement {
+        symbol: "AAPL".to_string(),
+        statement_type: statement_type.to_string(),
+        frequency: frequency.to_string(),
+        statement: HashMap::from([(field.to_string(), series)]),
+    }
+}
+
+#[test]
+fn picks_the_metric_at_the_frequency_oldest_first() {
+    let statements = vec![
+        statement(
+            "income",
+            "quarterly",
+            "TotalRevenue",
+            &[
+                ("2025-06-30", 94.0e9),
+                ("2024-12-31", 124.3e9),
+                ("2025-03-31", 95.4e9),
+            ],
+        ),
+        statement(
+            "income",
+            "annual",
+            "TotalRevenue",
+            &[("2024-09-30", 391.0e9)],
+        ),
+        statement(
+            "cashflow",
+            "quarterly",
+            "FreeCashFlow",
+            &[("2025-06-30", 24.4e9)],
+        ),
+    ];
+
+    let series = metric_series(&statements, ChartMetric::Revenue, Frequency::Quarterly);
+    let ends: Vec<NaiveDate> = series.periods.iter().map(|p| p.end).collect();
+    assert_eq!(
+        ends,
+        [date(2024, 12, 31), date(2025, 3, 31), date(2025, 6, 30)]
+    );
+    assert_eq!(series.periods[2].value, 94.0e9);
+    assert_eq!(series.currency.as_deref(), Some("USD"));
+
+    let fcf = metric_series(&statements, ChartMetric::FreeCashFlow, Frequency::Quarterly);
+    assert_eq!(fcf.periods.len(), 1);
+    assert!(
+        metric_series(&statements, ChartMetric::NetIncome, Frequency::Quarterly)
+            .periods
+            .is_empty()
+    );
+}
+
+#[test]
+fn keeps_only_the_most_recent_periods() {
+    let dates: Vec<String> = (2010..2026).map(|y| format!("{y}-12-31")).collect();
+    let values: Vec<(&str, f64)> = dates.iter().map(|d| (d.as_str(), 1.0)).collect();
+    let statements = vec![statement("income", "annual", "NetIncome", &values)];
+
+    let series = metric_series(&statements, ChartMetric::NetIncome, Frequency::Annual);
+    assert_eq!(series.periods.len(), MAX_PERIODS);
+    assert_eq!(series.periods.last().unwrap().end, date(2025, 12, 31));
+}
+
+#[test]
+fn labels_and_text_compare_with_a_year_earlier() {
+    assert_eq!(
+        period_label(date(2025, 6, 28), Frequency::Quarterly),
+        "Jun '25"
+    );
+    assert_eq!(period_label(date(2024, 9, 30), Frequency::Annual), "2024");
+
+    let statements = vec![statement(
+        "income",
+        "quarterly",
+        "NetIncome",
+        &[
+            ("2024-06-30", 20.0e9),
+            ("2024-09-30", -1.0e9),
+            ("2024-12-31", 36.0e9),
+            ("2025-03-31", 25.0e9),
+            ("2025-06-30", 23.0e9),
+        ],
+    )];
+    let series = metric_series(&statements, ChartMetric::NetIncome, Frequency::Quarterly);
+    let text = chart_text(
+        "AAPL",
+        ChartMetric::NetIncome,
+        Frequency::Quarterly,
+        &series,
+        "USD",
+    );
+    assert!(text.starts_with("📊 **AAPL net income (quarterly)**"));
+    assert!(text.contains("• **Sep '24** · -$1.00B"));
+    assert!(text.ends_with("Jun '25 vs Jun '24: +15.0%"));
+}
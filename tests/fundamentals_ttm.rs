@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use stacks_bot::models::{FinancialStatement, Frequency};
+use stacks_bot::service::command::fundamentals_chart::{metric_series, ChartMetric};
+use stacks_bot::service::finance::fundamentals::reshape_timeseries_to_financial_statements;
+
+fn series(field: &str, values: &[(&str, f64)]) -> Value {
+    let items: Vec<Value> = values
+        .iter()
+        .map(|(date, raw)| {
+            json!({
+                "asOfDate": date,
+                "currencyCode": "USD",
+                "reportedValue": { "raw": raw, "fmt": "n/a" }
+            })
+        })
+        .collect();
+    json!({
+        "meta": { "symbol": ["AAPL"], "type": [field] },
+        field: items,
+    })
+}
+
+fn ttm<'a>(
+    statements: &'a [FinancialStatement],
+    statement_type: &str,
+) -> Option<&'a FinancialStatement> {
+    statements
+        .iter()
+        .find(|s| s.statement_type == statement_type && s.frequency == Frequency::Ttm.as_str())
+}
+
+#[test]
+fn sums_runs_of_four_consecutive_quarters() {
+    let raw = json!({ "timeseries": { "result": [
+        series("quarterlyTotalRevenue", &[
+            ("2024-06-30", 85.0),
+            ("2024-09-30", 95.0),
+            ("2024-12-31", 124.0),
+            ("2025-03-31", 95.0),
+            ("2025-06-30", 94.0),
+        ]),
+        series("quarterlyBasicAverageShares", &[
+            ("2024-06-30", 15.0),
+            ("2024-09-30", 15.0),
+            ("2024-12-31", 15.0),
+            ("2025-03-31", 15.0),
+        ]),
+        series("annualTotalRevenue", &[("2024-09-30", 391.0)]),
+    ]}});
+    let statements = reshape_timeseries_to_financial_statements(&raw);
+
+    let income = ttm(&statements, "income").expect("ttm income statement");
+    let revenue = &income.statement["TotalRevenue"];
+    assert_eq!(revenue.len(), 2);
+    assert_eq!(revenue["2025-03-31"]["reportedValue"]["raw"], json!(399.0));
+    assert_eq!(revenue["2025-06-30"]["reportedValue"]["raw"], json!(408.0));
+    assert_eq!(revenue["2025-06-30"]["currencyCode"], json!("USD"));
+    // Share counts are averages, not flows.
+    assert!(!income.statement.contains_key("BasicAverageShares"));
+
+    let chart = metric_series(&statements, ChartMetric::Revenue, Frequency::Ttm);
+    let ends: Vec<NaiveDate> = chart.periods.iter().map(|p| p.end).collect();
+    assert_eq!(
+        ends,
+        [
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn skips_gaps_and_balance_sheets() {
+    let raw = json!({ "timeseries": { "result": [
+        // 2024-12-31 is missing, so no window covers four consecutive quarters.
+        series("quarterlyFreeCashFlow", &[
+            ("2024-06-30", 10.0),
+            ("2024-09-30", 20.0),
+            ("2025-03-31", 30.0),
+            ("2025-06-30", 40.0),
+        ]),
+        series("quarterlyTotalAssets", &[
+            ("2024-09-30", 1.0),
+            ("2024-12-31", 1.0),
+            ("2025-03-31", 1.0),
+            ("2025-06-30", 1.0),
+        ]),
+    ]}});
+    let statements = reshape_timeseries_to_financial_statements(&raw);
+
+    let cashflow = ttm(&statements, "cashflow").expect("ttm cash flow statement");
+    assert!(cashflow.statement.is_empty());
+    assert!(ttm(&statements, "balance").is_none());
+    assert_eq!(Frequency::Ttm.fetched(), Frequency::Quarterly);
+}
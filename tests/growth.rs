@@ -0,0 +1,60 @@
+mod common;
+
+use common::date;
+use stacks_bot::service::growth::{self, Growth};
+
+fn close(actual: Option<f64>, expected: f64) -> bool {
+    actual.is_some_and(|v| (v - expected).abs() < 1e-9)
+}
+
+#[test]
+fn change_and_cagr_need_a_positive_start() {
+    assert!(close(growth::change(100.0, 112.0), 0.12));
+    assert_eq!(growth::change(0.0, 5.0), None);
+    assert_eq!(growth::change(-10.0, 5.0), None);
+
+    assert!(close(growth::cagr(100.0, 133.1, 3.0), 0.1));
+    assert_eq!(growth::cagr(100.0, -5.0, 3.0), None);
+    assert_eq!(growth::cagr(100.0, 120.0, 0.0), None);
+}
+
+#[test]
+fn growth_matches_periods_a_year_and_three_years_back() {
+    // Fiscal years ending on the last Saturday of September.
+    let points = [
+        (date(2021, 9, 25), 100.0),
+        (date(2022, 9, 24), 110.0),
+        (date(2023, 9, 30), 120.0),
+        (date(2024, 9, 28), 133.1),
+    ];
+    let growth = growth::growth_at(&points, date(2024, 9, 28));
+    assert!(close(growth.yoy, 133.1 / 120.0 - 1.0));
+    assert!(close(growth.cagr_3y, 0.1));
+    assert_eq!(
+        growth.summary().as_deref(),
+        Some("YoY +10.9% · 3y CAGR +10.0%")
+    );
+
+    // Two years of history: no CAGR yet.
+    let growth = growth::growth_at(&points, date(2022, 9, 24));
+    assert!(close(growth.yoy, 0.1));
+    assert_eq!(growth.cagr_3y, None);
+    assert_eq!(growth.summary().as_deref(), Some("YoY +10.0%"));
+}
+
+#[test]
+fn quarters_compare_with_the_same_quarter() {
+    let points = [
+        (date(2024, 3, 31), 80.0),
+        (date(2024, 6, 30), 90.0),
+        (date(2025, 3, 31), 60.0),
+        (date(2025, 6, 30), 99.0),
+    ];
+    let growth = growth::growth_at(&points, date(2025, 3, 31));
+    assert!(close(growth.yoy, -0.25));
+    assert_eq!(
+        growth::growth_at(&points, date(2024, 6, 30)),
+        Growth::default()
+    );
+    assert_eq!(Growth::default().summary(), None);
+}
@@ -0,0 +1,81 @@
+use stacks_bot::models::{EtfHolding, EtfProfile};
+use stacks_bot::service::command::held_by::held_by_text;
+use stacks_bot::service::finance::etf::{positions, FundPosition, HeldBy};
+
+fn fund(symbol: &str, holdings: &[(&str, f64)]) -> EtfProfile {
+    EtfProfile {
+        symbol: symbol.to_string(),
+        name: Some(format!("{symbol} Fund")),
+        family: None,
+        category: None,
+        expense_ratio: None,
+        net_assets: None,
+        currency: Some("USD".to_string()),
+        holdings: holdings
+            .iter()
+            .map(|(s, w)| EtfHolding {
+                symbol: Some(s.to_string()),
+                name: s.to_string(),
+                weight: *w,
+            })
+            .collect(),
+        sectors: Vec::new(),
+    }
+}
+
+#[test]
+fn funds_holding_the_stock_sort_by_weight() {
+    let profiles = vec![
+        fund("SPY", &[("AAPL", 0.07), ("NVDA", 0.065)]),
+        fund("SMH", &[("NVDA", 0.20), ("TSM", 0.12)]),
+        fund("XLE", &[("XOM", 0.22)]),
+    ];
+    let found = positions("nvda", &profiles);
+    assert_eq!(
+        found,
+        vec![
+            FundPosition {
+                fund: "SMH".to_string(),
+                fund_name: Some("SMH Fund".to_string()),
+                weight: 0.20,
+                rank: 1,
+            },
+            FundPosition {
+                fund: "SPY".to_string(),
+                fund_name: Some("SPY Fund".to_string()),
+                weight: 0.065,
+                rank: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn share_classes_match_either_spelling() {
+    let profiles = vec![fund("XLF", &[("BRK-B", 0.13)])];
+    assert_eq!(positions("BRK.B", &profiles).len(), 1);
+}
+
+#[test]
+fn reply_lists_funds_and_notes_failures() {
+    let profiles = vec![fund("SMH", &[("NVDA", 0.2011)])];
+    let held = HeldBy {
+        positions: positions("NVDA", &profiles),
+        searched: 18,
+        failed: vec!["ARKK".to_string()],
+    };
+    let text = held_by_text("NVDA", &held);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "🧺 **Funds holding NVDA**");
+    assert_eq!(lines[1], "1. **SMH** SMH Fund — 20.11% · #1 holding");
+    assert_eq!(
+        lines.last().copied(),
+        Some("*Top ten holdings of 18 tracked funds · 1 failed to load (ARKK)*")
+    );
+
+    let none = HeldBy {
+        searched: 18,
+        ..HeldBy::default()
+    };
+    assert!(held_by_text("KO", &none).contains("KO is not a top-ten holding of any tracked fund."));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::date;
+use serde_json::json;
+use stacks_bot::models::{HoldersOverview, InstitutionalHolder, MajorHoldersBreakdown};
+use stacks_bot::service::command::holders_trend::trend_text;
+use stacks_bot::service::finance::ownership::{
+    previous_quarter_end, quarter_end, quarter_label, OwnershipSnapshot,
+};
+
+fn overview() -> HoldersOverview {
+    HoldersOverview {
+        symbol: "AAPL".to_string(),
+        major_breakdown: None,
+        institutional_holders: None,
+        mutualfund_holders: None,
+        insider_transactions: None,
+        insider_purchases: None,
+        insider_roster: None,
+    }
+}
+
+fn holder(name: &str, shares: i64, percent_out: f64) -> InstitutionalHolder {
+    InstitutionalHolder {
+        holder: name.to_string(),
+        shares,
+        date_reported: Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap(),
+        percent_out: Some(percent_out),
+        value: None,
+    }
+}
+
+#[test]
+fn quarters_end_on_the_last_day_of_march_june_september_and_december() {
+    assert_eq!(quarter_end(date(2026, 2, 14)), date(2026, 3, 31));
+    assert_eq!(quarter_end(date(2026, 6, 30)), date(2026, 6, 30));
+    assert_eq!(quarter_end(date(2026, 11, 2)), date(2026, 12, 31));
+    assert_eq!(previous_quarter_end(date(2026, 10, 17)), date(2026, 9, 30));
+    assert_eq!(previous_quarter_end(date(2026, 1, 5)), date(2025, 12, 31));
+    assert_eq!(quarter_label(date(2026, 9, 30)), "Q3 2026");
+}
+
+#[test]
+fn snapshot_sums_the_ten_largest_holders_in_the_filing_quarter() {
+    let mut major = overview();
+    let mut breakdown = std::collections::HashMap::new();
+    breakdown.insert(
+        "institutionsPercentHeld".to_string(),
+        json!({"raw": 0.6123, "fmt": "61.23%"}),
+    );
+    breakdown.insert("institutionsCount".to_string(), json!({"raw": 3412}));
+    major.major_breakdown = Some(MajorHoldersBreakdown {
+        breakdown_data: breakdown,
+    });
+    let mut institutional = overview();
+    institutional.institutional_holders = Some(
+        (0..12)
+            .map(|i| holder(&format!("Fund {i}"), 1_000 - i, 0.01))
+            .collect(),
+    );
+
+    let snapshot =
+        OwnershipSnapshot::from_holders(Some(&major), Some(&institutional), date(2026, 10, 17));
+    assert_eq!(snapshot.quarter, date(2026, 6, 30));
+    assert_eq!(snapshot.institutions_percent, Some(0.6123));
+    assert_eq!(snapshot.institutions_count, Some(3412));
+    assert!(snapshot
+        .top10_percent
+        .is_some_and(|p| (p - 0.10).abs() < 1e-9));
+
+    let without_list = OwnershipSnapshot::from_holders(Some(&major), None, date(2026, 10, 17));
+    assert_eq!(without_list.quarter, date(2026, 9, 30));
+    assert_eq!(without_list.top10_percent, None);
+}
+
+#[test]
+fn trend_reports_the_change_since_the_first_quarter() {
+    let snapshot = |quarter, institutions, top10, count| OwnershipSnapshot {
+        quarter,
+        institutions_percent: Some(institutions),
+        top10_percent: Some(top10),
+        institutions_count: Some(count),
+    };
+    let text = trend_text(
+        "AAPL",
+        &[
+            snapshot(date(2025, 12, 31), 0.60, 0.25, 3_300),
+            snapshot(date(2026, 3, 31), 0.61, 0.245, 3_350),
+            snapshot(date(2026, 6, 30), 0.6123, 0.24, 3_280),
+        ],
+    );
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "🏦 **AAPL institutional ownership**");
+    assert_eq!(
+        lines[3],
+        "• **Q2 2026** · Institutions 61.23% · Top 10 24.00% · 3,280 holders"
+    );
+    assert_eq!(
+        lines.last().copied(),
+        Some("Since Q4 2025: institutions +1.23 pts · top 10 -1.00 pts · holders -20")
+    );
+
+    let first = trend_text("AAPL", &[snapshot(date(2026, 6, 30), 0.6, 0.25, 3_300)]);
+    assert!(first
+        .ends_with("📸 Saved Q2 2026. Earlier quarters show here once they have been recorded."));
+}
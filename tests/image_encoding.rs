@@ -0,0 +1,91 @@
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use stacks_bot::service::config::{ImageConfig, ImageEncoding};
+use stacks_bot::service::render;
+
+/// Noise compresses badly, so size limits bite at small dimensions.
+fn noisy(width: u32, height: u32) -> DynamicImage {
+    let mut seed: u32 = 0x2545_f491;
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |_, _| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let [r, g, b, _] = seed.to_le_bytes();
+        Rgba([r, g, b, 255])
+    }))
+}
+
+fn config(format: ImageEncoding) -> ImageConfig {
+    ImageConfig {
+        format,
+        ..ImageConfig::default()
+    }
+}
+
+#[test]
+fn encodes_in_the_configured_format() {
+    let image = noisy(64, 48);
+    for (format, expected, file_name) in [
+        (ImageEncoding::Png, ImageFormat::Png, "calendar.png"),
+        (ImageEncoding::Webp, ImageFormat::WebP, "calendar.webp"),
+        (ImageEncoding::Jpeg, ImageFormat::Jpeg, "calendar.jpg"),
+    ] {
+        let encoded = render::encode(&image, &config(format)).unwrap();
+        assert_eq!(encoded.encoding, format);
+        assert_eq!(encoded.file_name("calendar"), file_name);
+        assert_eq!(image::guess_format(&encoded.bytes).unwrap(), expected);
+        let decoded = image::load_from_memory(&encoded.bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (64, 48));
+    }
+}
+
+#[test]
+fn lower_jpeg_quality_gives_smaller_files() {
+    let image = noisy(200, 200);
+    let size = |quality| {
+        let config = ImageConfig {
+            jpeg_quality: quality,
+            ..config(ImageEncoding::Jpeg)
+        };
+        render::encode(&image, &config).unwrap().bytes.len()
+    };
+    assert!(size(30) < size(95));
+}
+
+#[test]
+fn oversized_images_are_scaled_down_to_fit() {
+    let image = noisy(1200, 900);
+    let full = render::encode(&image, &config(ImageEncoding::Png)).unwrap();
+    let limit = full.bytes.len() as u64 / 3;
+    let config = ImageConfig {
+        max_bytes: limit,
+        ..config(ImageEncoding::Png)
+    };
+
+    let encoded = render::encode(&image, &config).unwrap();
+    assert!(encoded.bytes.len() as u64 <= limit);
+    let decoded = image::load_from_memory(&encoded.bytes).unwrap();
+    assert!(decoded.width() < 1200);
+    // Aspect ratio is kept.
+    assert_eq!(decoded.width() * 3 / 4, decoded.height());
+}
+
+#[test]
+fn stops_shrinking_at_the_minimum_width() {
+    let image = noisy(400, 300);
+    let config = ImageConfig {
+        max_bytes: 1,
+        ..config(ImageEncoding::Png)
+    };
+    let encoded = render::encode(&image, &config).unwrap();
+    let decoded = image::load_from_memory(&encoded.bytes).unwrap();
+    assert_eq!(decoded.width(), 400);
+}
+
+#[test]
+fn format_names_parse() {
+    assert_eq!("WebP".parse(), Ok(ImageEncoding::Webp));
+    assert_eq!("jpg".parse(), Ok(ImageEncoding::Jpeg));
+    assert_eq!("jpeg".parse(), Ok(ImageEncoding::Jpeg));
+    assert_eq!("png".parse(), Ok(ImageEncoding::Png));
+    assert!("gif".parse::<ImageEncoding>().is_err());
+}
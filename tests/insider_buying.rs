@@ -0,0 +1,109 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use stacks_bot::models::{InsiderPurchase, InsiderTransaction};
+use stacks_bot::service::automation::insider_buying::{format_post, rank, InsiderActivity};
+use stacks_bot::service::automation::jobs::Job;
+use stacks_bot::service::finance::holders::insider_purchases_since;
+
+fn tx(day: u32, text: &str, shares: i64) -> InsiderTransaction {
+    InsiderTransaction {
+        start_date: Utc.with_ymd_and_hms(2026, 10, day, 0, 0, 0).unwrap(),
+        insider: "Jane Doe".to_string(),
+        position: "Director".to_string(),
+        transaction: text.to_string(),
+        shares: Some(shares),
+        value: None,
+        ownership: None,
+    }
+}
+
+fn activity(symbol: &str, net_transactions: i64, net_shares: i64) -> InsiderActivity {
+    InsiderActivity {
+        symbol: symbol.to_string(),
+        summary: InsiderPurchase {
+            period: "30d".to_string(),
+            purchases_shares: None,
+            purchases_transactions: Some(net_transactions.max(0)),
+            sales_shares: None,
+            sales_transactions: Some(0),
+            net_shares: Some(net_shares),
+            net_transactions: Some(net_transactions),
+            total_insider_shares: None,
+            net_percent_insider_shares: None,
+            buy_percent_insider_shares: None,
+            sell_percent_insider_shares: None,
+        },
+    }
+}
+
+#[test]
+fn only_transactions_in_the_window_count() {
+    let txs = vec![
+        tx(2, "Purchase at price 10.00 per share.", 5_000),
+        tx(12, "Purchase at price 11.00 per share.", 1_000),
+        tx(14, "Sale at price 12.00 per share.", 400),
+        tx(15, "Stock Award(Grant) at price 0.00 per share.", 9_000),
+    ];
+    let since = Utc.with_ymd_and_hms(2026, 10, 10, 0, 0, 0).unwrap();
+    let summary = insider_purchases_since(&txs, since, "30d").unwrap();
+    assert_eq!(summary.period, "30d");
+    assert_eq!(summary.net_shares, Some(600));
+    assert_eq!(summary.net_transactions, Some(0));
+    assert_eq!(
+        (summary.purchases_transactions, summary.sales_transactions),
+        (Some(1), Some(1))
+    );
+
+    let later = Utc.with_ymd_and_hms(2026, 10, 20, 0, 0, 0).unwrap();
+    assert!(insider_purchases_since(&txs, later, "30d").is_none());
+}
+
+#[test]
+fn ranking_prefers_more_net_buys_and_drops_net_sellers() {
+    let mut entries = vec![
+        activity("AAA", 1, 900_000),
+        activity("BBB", 3, 2_000),
+        activity("CCC", 3, 50_000),
+        activity("DDD", -2, -10_000),
+    ];
+    entries.extend((0..12).map(|i| activity(&format!("Z{i:02}"), 1, 10)));
+    let ranked = rank(entries);
+    let symbols: Vec<&str> = ranked.iter().map(|a| a.symbol.as_str()).collect();
+    assert_eq!(symbols.len(), 10);
+    assert_eq!(&symbols[..3], ["CCC", "BBB", "AAA"]);
+    assert!(!symbols.contains(&"DDD"));
+}
+
+#[test]
+fn post_lists_names_and_what_was_scanned() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+    let text = format_post("S&P 500", today, &[activity("CCC", 1, 125_400)], 503, 2);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "🕵️ **Insider net buying — S&P 500, 30 days to Oct 17**"
+    );
+    assert_eq!(
+        lines[1],
+        "1. **CCC** — net +125.40K shares · 1 buy / 0 sales"
+    );
+    assert_eq!(
+        lines.last().copied(),
+        Some("*Scanned 503 names · 2 failed to load*")
+    );
+
+    let empty = format_post("Dow 30", today, &[], 30, 0);
+    assert!(empty.contains("No Dow 30 name had more insider buying than selling."));
+}
+
+#[test]
+fn scan_runs_saturday_mornings() {
+    // Friday afternoon
+    let after = New_York.with_ymd_and_hms(2026, 10, 16, 15, 0, 0).unwrap();
+    let next = Job::InsiderBuying.next_run(after).unwrap();
+    assert_eq!(
+        next,
+        New_York.with_ymd_and_hms(2026, 10, 17, 10, 0, 0).unwrap()
+    );
+    assert_eq!(Job::from_name("insider_buying"), Some(Job::InsiderBuying));
+}
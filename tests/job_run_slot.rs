@@ -0,0 +1,58 @@
+use chrono::{DateTime, TimeZone};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use stacks_bot::service::automation::jobs::Job;
+use stacks_bot::service::automation::leader::run_lock_key;
+
+fn et(day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Tz> {
+    New_York
+        .with_ymd_and_hms(2026, 10, day, hour, minute, second)
+        .unwrap()
+}
+
+#[test]
+fn replicas_firing_late_in_a_window_share_the_slot() {
+    // Thursday: the daily report may fire anywhere from 18:00 to 18:04.
+    let slot = et(15, 18, 0, 0);
+    assert_eq!(Job::DailyEarnings.run_slot(et(15, 18, 0, 2)), slot);
+    assert_eq!(Job::DailyEarnings.run_slot(et(15, 18, 3, 40)), slot);
+
+    assert_eq!(
+        Job::AfterDaily.run_slot(et(15, 17, 51, 0)),
+        et(15, 17, 50, 0)
+    );
+    assert_eq!(
+        Job::OptionsPinger.run_slot(et(15, 10, 15, 30)),
+        et(15, 10, 15, 0)
+    );
+}
+
+#[test]
+fn a_clock_slightly_behind_still_finds_the_slot() {
+    assert_eq!(
+        Job::OptionsPinger.run_slot(et(15, 10, 14, 30)),
+        et(15, 10, 15, 0)
+    );
+}
+
+#[test]
+fn outside_any_slot_falls_back_to_the_minute() {
+    // No weekly calendar on a Thursday.
+    assert_eq!(
+        Job::WeeklyEarnings.run_slot(et(15, 17, 0, 45)),
+        et(15, 17, 0, 0)
+    );
+}
+
+#[test]
+fn run_lock_keys_are_per_job_and_slot() {
+    let slot = et(15, 18, 0, 0).timestamp();
+    assert_eq!(
+        run_lock_key("daily_earnings", slot),
+        format!("automation:run:daily_earnings:{slot}")
+    );
+    assert_ne!(
+        run_lock_key("daily_earnings", slot),
+        run_lock_key("after_daily", slot)
+    );
+}
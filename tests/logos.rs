@@ -0,0 +1,42 @@
+use stacks_bot::service::finance::logos::{clearbit_url, fmp_url, website_domain, LogoProvider};
+
+#[test]
+fn website_domain_is_the_bare_host() {
+    assert_eq!(
+        website_domain("https://www.apple.com/").as_deref(),
+        Some("apple.com")
+    );
+    assert_eq!(
+        website_domain("http://investor.nvidia.com/home").as_deref(),
+        Some("investor.nvidia.com")
+    );
+    assert_eq!(
+        website_domain(" www.Tesla.com ").as_deref(),
+        Some("tesla.com")
+    );
+}
+
+#[test]
+fn website_domain_rejects_non_domains() {
+    assert_eq!(website_domain(""), None);
+    assert_eq!(website_domain("localhost"), None);
+    assert_eq!(website_domain("not a url"), None);
+}
+
+#[test]
+fn provider_urls() {
+    assert_eq!(
+        fmp_url("brk-b"),
+        "https://financialmodelingprep.com/image-stock/BRK-B.png"
+    );
+    assert_eq!(
+        clearbit_url("apple.com"),
+        "https://logo.clearbit.com/apple.com"
+    );
+}
+
+#[test]
+fn calendar_url_is_tried_first() {
+    let names: Vec<&str> = LogoProvider::CHAIN.iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["calendar", "fmp", "clearbit"]);
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use common::quote;
+use stacks_bot::service::automation::presence::{status_text, MARKET_CLOSED};
+
+#[test]
+fn open_market_shows_price_and_change() {
+    let spy = quote("SPY", Some(581.234), Some(-0.456));
+    assert_eq!(status_text("SPY", Some(&spy), true), "SPY 581.23 (-0.46%)");
+
+    let no_change = quote("SPY", Some(581.2), None);
+    assert_eq!(status_text("SPY", Some(&no_change), true), "SPY 581.20");
+    assert_eq!(
+        status_text("SPY", Some(&quote("SPY", None, None)), true),
+        "SPY"
+    );
+}
+
+#[test]
+fn closed_market_ignores_the_quote() {
+    let spy = quote("SPY", Some(581.23), Some(0.45));
+    assert_eq!(status_text("SPY", Some(&spy), false), MARKET_CLOSED);
+    assert_eq!(status_text("SPY", None, false), MARKET_CLOSED);
+}
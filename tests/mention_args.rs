@@ -0,0 +1,41 @@
+use stacks_bot::service::command::args::{tokenize, CommandSpec};
+use stacks_bot::service::command::{news, quotes};
+
+fn tokens(input: &str) -> Vec<String> {
+    tokenize(input).expect("tokenize")
+}
+
+#[test]
+fn symbols_containing_equals_stay_positional() {
+    let quote = CommandSpec::from_slash(quotes::register_command(), &[], &[]);
+
+    let args = quote.parse(&tokens("EURUSD=X")).unwrap();
+    assert_eq!(args.get("ticker"), Some("EURUSD=X"));
+
+    let args = quote.parse(&tokens("GC=F eur")).unwrap();
+    assert_eq!(args.get("ticker"), Some("GC=F"));
+    assert_eq!(args.get("currency"), Some("eur"));
+}
+
+#[test]
+fn declared_args_and_aliases_are_flags() {
+    let quote = CommandSpec::from_slash(quotes::register_command(), &[], &[]);
+    let args = quote.parse(&tokens("currency=EUR EURUSD=X")).unwrap();
+    assert_eq!(args.get("currency"), Some("EUR"));
+    assert_eq!(args.get("ticker"), Some("EURUSD=X"));
+
+    let args = quote.parse(&tokens("CUR=jpy GC=F")).unwrap();
+    assert_eq!(args.get("currency"), Some("jpy"));
+    assert_eq!(args.get("ticker"), Some("GC=F"));
+
+    let news = CommandSpec::from_slash(news::register_command(), &[], &["preview"]);
+    let args = news.parse(&tokens("n=3 CL=F")).unwrap();
+    assert_eq!(args.get("limit"), Some("3"));
+    assert_eq!(args.get("ticker"), Some("CL=F"));
+}
+
+#[test]
+fn extra_tokens_are_still_rejected() {
+    let quote = CommandSpec::from_slash(quotes::register_command(), &[], &[]);
+    assert!(quote.parse(&tokens("AAPL EUR limit=3")).is_err());
+}
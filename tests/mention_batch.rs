@@ -0,0 +1,27 @@
+use stacks_bot::service::command::mention::split_commands;
+
+#[test]
+fn semicolons_separate_commands() {
+    assert_eq!(
+        split_commands("quote SPY; quote QQQ;news NVDA 2"),
+        vec!["quote SPY", "quote QQQ", "news NVDA 2"]
+    );
+}
+
+#[test]
+fn empty_parts_and_a_trailing_separator_are_dropped() {
+    assert_eq!(split_commands("quote SPY;"), vec!["quote SPY"]);
+    assert_eq!(split_commands("q SPY;; h AAPL"), vec!["q SPY", "h AAPL"]);
+}
+
+#[test]
+fn free_text_with_semicolons_stays_whole() {
+    assert_eq!(
+        split_commands("thoughts on $NVDA; and $AMD?"),
+        vec!["thoughts on $NVDA; and $AMD?"]
+    );
+    assert_eq!(
+        split_commands("quote SPY; what about $AMD"),
+        vec!["quote SPY; what about $AMD"]
+    );
+}
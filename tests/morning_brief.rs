@@ -0,0 +1,101 @@
+mod common;
+
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+use common::{date, event, quote};
+use stacks_bot::models::{NewsItem, PriceQuote, SourceType};
+use stacks_bot::service::automation::morning_brief::{due_date, render, BriefData};
+use stacks_bot::service::caching::collections::morning_briefs::MorningBrief;
+use stacks_bot::service::command::brief::parse_time;
+
+fn time(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn brief(timezone: &str, last_sent: Option<NaiveDate>) -> MorningBrief {
+    MorningBrief {
+        user_id: 7,
+        symbols: vec!["AAPL".to_string(), "KO".to_string()],
+        time: time(7, 30),
+        timezone: timezone.to_string(),
+        last_sent,
+    }
+}
+
+#[test]
+fn times_parse_in_24_and_12_hour_forms() {
+    assert_eq!(parse_time("07:30"), Some(time(7, 30)));
+    assert_eq!(parse_time("19:05"), Some(time(19, 5)));
+    assert_eq!(parse_time("7:30am"), Some(time(7, 30)));
+    assert_eq!(parse_time("7 AM"), Some(time(7, 0)));
+    assert_eq!(parse_time("12:15am"), Some(time(0, 15)));
+    assert_eq!(parse_time("12pm"), Some(time(12, 0)));
+    assert_eq!(parse_time("13pm"), None);
+    assert_eq!(parse_time("breakfast"), None);
+}
+
+#[test]
+fn briefs_are_due_once_on_weekdays_in_the_users_timezone() {
+    // Monday Oct 19, 2026, 11:40 UTC is 7:40 in New York and 12:40 in London.
+    let monday = Utc.with_ymd_and_hms(2026, 10, 19, 11, 40, 0).unwrap();
+    let ny = brief("America/New_York", None);
+    assert_eq!(due_date(&ny, monday), Some(date(2026, 10, 19)));
+    assert_eq!(due_date(&brief("Europe/London", None), monday), None);
+
+    let sent = brief("America/New_York", Some(date(2026, 10, 19)));
+    assert_eq!(due_date(&sent, monday), None);
+
+    // Before the time, past the catch-up window and on weekends nothing is due.
+    let early = Utc.with_ymd_and_hms(2026, 10, 19, 11, 20, 0).unwrap();
+    let late = Utc.with_ymd_and_hms(2026, 10, 19, 12, 5, 0).unwrap();
+    let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 11, 40, 0).unwrap();
+    for now in [early, late, saturday] {
+        assert_eq!(due_date(&ny, now), None, "{now}");
+    }
+}
+
+#[test]
+fn brief_lists_quotes_reports_dividends_and_headlines() {
+    let data = BriefData {
+        date: date(2026, 10, 19),
+        quotes: vec![
+            (
+                "AAPL".to_string(),
+                Some(PriceQuote {
+                    pre_market_price: Some(233.1),
+                    ..quote("AAPL", Some(231.5), Some(1.234))
+                }),
+            ),
+            ("KO".to_string(), Some(quote("KO", Some(69.0), Some(-0.5)))),
+            ("XYZ".to_string(), None),
+        ],
+        reporting: vec![event("AAPL", 19, "amc")],
+        ex_dividends: vec![
+            ("KO".to_string(), date(2026, 10, 19)),
+            ("PEP".to_string(), date(2026, 10, 21)),
+        ],
+        headlines: vec![
+            (
+                "AAPL".to_string(),
+                Ok(Some(NewsItem {
+                    title: "Apple ships".to_string(),
+                    link: "https://example.com/apple".to_string(),
+                    source: None,
+                    published_at: None,
+                    thumbnail: None,
+                    source_type: SourceType::Media,
+                })),
+            ),
+            ("KO".to_string(), Ok(None)),
+        ],
+    };
+    let text = render(&data);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "☀️ **Morning brief** · Monday, Oct 19");
+    assert_eq!(lines[1], "🟢 **AAPL** 231.50 (+1.23%) · pre 233.10");
+    assert_eq!(lines[2], "🔴 **KO** 69.00 (-0.50%)");
+    assert_eq!(lines[3], "⚪ **XYZ** n/a");
+    assert!(text.contains("📅 **Reporting today:** AAPL (after close)"));
+    assert!(text.contains("💵 **Ex-dividend:** KO today, PEP Wed Oct 21"));
+    assert!(text.contains("**AAPL** · [Apple ships](<https://example.com/apple>)"));
+    assert!(text.contains("No recent headlines: KO"));
+}
@@ -0,0 +1,34 @@
+use stacks_bot::service::finance::news::canonical_url;
+
+#[test]
+fn tracking_parameters_and_fragments_are_dropped() {
+    assert_eq!(
+        canonical_url("https://finance.yahoo.com/news/apple-earnings-123.html?.tsrc=rss&utm_source=x#comments"),
+        "https://finance.yahoo.com/news/apple-earnings-123.html"
+    );
+}
+
+#[test]
+fn scheme_www_and_trailing_slash_are_normalized() {
+    assert_eq!(
+        canonical_url(" http://www.reuters.com/markets/story/ "),
+        "https://reuters.com/markets/story"
+    );
+}
+
+#[test]
+fn remaining_parameters_are_sorted() {
+    assert_eq!(
+        canonical_url("https://example.com/a?b=2&ncid=foo&a=1"),
+        canonical_url("https://example.com/a?a=1&b=2")
+    );
+    assert_eq!(
+        canonical_url("https://example.com/a?b=2&a=1"),
+        "https://example.com/a?a=1&b=2"
+    );
+}
+
+#[test]
+fn unparseable_links_are_trimmed() {
+    assert_eq!(canonical_url("  not a url "), "not a url");
+}
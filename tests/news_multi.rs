@@ -0,0 +1,57 @@
+use chrono::{TimeZone, Utc};
+use stacks_bot::models::{NewsItem, SourceType};
+use stacks_bot::service::command::news_multi::{parse_tickers, roundup_text, Headline};
+
+fn item(title: &str, link: &str) -> NewsItem {
+    NewsItem {
+        title: title.to_string(),
+        link: link.to_string(),
+        source: Some("Reuters".to_string()),
+        published_at: Some(Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap()),
+        thumbnail: None,
+        source_type: SourceType::Media,
+    }
+}
+
+#[test]
+fn tickers_are_uppercased_and_deduplicated_in_order() {
+    assert_eq!(
+        parse_tickers("aapl, $msft nvda,AAPL").unwrap(),
+        vec!["AAPL", "MSFT", "NVDA"]
+    );
+}
+
+#[test]
+fn more_than_ten_tickers_or_none_are_rejected() {
+    let eleven = "A B C D E F G H I J K";
+    assert!(parse_tickers(eleven).is_err());
+    assert!(parse_tickers(" , ").is_err());
+    assert!(parse_tickers("AAPL; MSFT").is_err());
+}
+
+#[test]
+fn a_shared_story_is_listed_once() {
+    let headlines: Vec<Headline> = vec![
+        (
+            "AAPL".to_string(),
+            Ok(Some(item(
+                "Big tech rallies",
+                "https://example.com/a?utm_source=x",
+            ))),
+        ),
+        (
+            "MSFT".to_string(),
+            Ok(Some(item("Big tech rallies", "https://www.example.com/a"))),
+        ),
+        ("XYZ".to_string(), Ok(None)),
+        ("BAD".to_string(), Err("timed out".to_string())),
+    ];
+    let text = roundup_text(&headlines);
+    assert_eq!(
+        text,
+        "🗞️ **News roundup**\n\
+         **AAPL, MSFT** · [Big tech rallies](<https://example.com/a?utm_source=x>) — Reuters, <t:1792159200:R>\n\
+         No recent headlines: XYZ\n\
+         *Failed to load: BAD*"
+    );
+}
@@ -0,0 +1,38 @@
+use stacks_bot::models::SourceType;
+use stacks_bot::service::finance::news::classify_source;
+
+#[test]
+fn wire_services_are_press_releases() {
+    for publisher in [
+        "PR Newswire",
+        "Business Wire",
+        "GlobeNewswire",
+        "ACCESSWIRE",
+        "Newsfile Corp",
+    ] {
+        assert_eq!(
+            classify_source(Some(publisher)),
+            SourceType::PressRelease,
+            "{publisher}"
+        );
+    }
+}
+
+#[test]
+fn newsrooms_and_unknown_publishers_are_media() {
+    assert_eq!(classify_source(Some("Reuters")), SourceType::Media);
+    assert_eq!(classify_source(Some("Motley Fool")), SourceType::Media);
+    assert_eq!(classify_source(None), SourceType::Media);
+}
+
+#[test]
+fn option_values_round_trip() {
+    for kind in [SourceType::PressRelease, SourceType::Media] {
+        assert_eq!(SourceType::from_name(kind.name()), Some(kind));
+    }
+    assert_eq!(
+        SourceType::from_name("PRESS"),
+        Some(SourceType::PressRelease)
+    );
+    assert_eq!(SourceType::from_name("blog"), None);
+}
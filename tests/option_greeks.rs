@@ -0,0 +1,30 @@
+use stacks_bot::service::finance::options::{Greeks, OptionKind};
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn black_scholes_matches_textbook_values() {
+    // S = K = 100, one year, 20% vol, 5% rate.
+    let call = Greeks::black_scholes(OptionKind::Call, 100.0, 100.0, 1.0, 0.2, 0.05).unwrap();
+    assert_close(call.delta, 0.6368, 1e-4);
+    assert_close(call.gamma, 0.01876, 1e-5);
+    assert_close(call.vega, 0.3752, 1e-4);
+    assert_close(call.theta, -6.414 / 365.0, 1e-5);
+
+    let put = Greeks::black_scholes(OptionKind::Put, 100.0, 100.0, 1.0, 0.2, 0.05).unwrap();
+    assert_close(put.delta, call.delta - 1.0, 1e-9);
+    assert_close(put.gamma, call.gamma, 1e-12);
+    assert_close(put.vega, call.vega, 1e-12);
+    assert_close(put.theta, -1.658 / 365.0, 1e-5);
+}
+
+#[test]
+fn black_scholes_rejects_expired_or_volatility_free_contracts() {
+    assert!(Greeks::black_scholes(OptionKind::Call, 100.0, 100.0, 0.0, 0.2, 0.05).is_none());
+    assert!(Greeks::black_scholes(OptionKind::Put, 100.0, 100.0, 0.5, 0.0, 0.05).is_none());
+}
@@ -0,0 +1,44 @@
+use chrono::NaiveDate;
+use stacks_bot::service::finance::open_interest::{open_interest_changes, ContractOpenInterest};
+use stacks_bot::service::finance::options::OptionKind;
+
+fn contract(day: u32, kind: OptionKind, strike: f64, open_interest: u64) -> ContractOpenInterest {
+    ContractOpenInterest {
+        expiration: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+        kind,
+        strike,
+        open_interest,
+    }
+}
+
+#[test]
+fn changes_compare_shared_expirations_largest_build_first() {
+    let previous = vec![
+        contract(16, OptionKind::Call, 580.0, 9_000),
+        contract(23, OptionKind::Call, 580.0, 1_000),
+        contract(23, OptionKind::Put, 570.0, 5_000),
+        contract(23, OptionKind::Put, 560.0, 700),
+    ];
+    let current = vec![
+        contract(23, OptionKind::Call, 580.0, 4_000),
+        contract(23, OptionKind::Put, 570.0, 3_500),
+        contract(23, OptionKind::Put, 560.0, 700),
+        contract(23, OptionKind::Call, 590.0, 250),
+        // An expiration new to the window is not a build.
+        contract(30, OptionKind::Call, 600.0, 20_000),
+    ];
+
+    let changes = open_interest_changes(&previous, &current);
+    let summary: Vec<(OptionKind, f64, i64)> = changes
+        .iter()
+        .map(|c| (c.kind, c.strike, c.change()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (OptionKind::Call, 580.0, 3_000),
+            (OptionKind::Call, 590.0, 250),
+            (OptionKind::Put, 570.0, -1_500),
+        ]
+    );
+}
@@ -0,0 +1,85 @@
+use stacks_bot::service::finance::options::OptionKind;
+use stacks_bot::service::finance::strategy::{parse_legs, Leg, Payoff, PricedLeg, Side};
+
+fn priced(legs: &str, premiums: &[f64]) -> Payoff {
+    let legs = parse_legs(legs).unwrap();
+    Payoff::new(
+        legs.into_iter()
+            .zip(premiums)
+            .map(|(leg, premium)| PricedLeg {
+                leg,
+                premium: *premium,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn parses_words_signs_and_counts() {
+    let legs = parse_legs("buy 1 450C, sell 2 455c; +440P -2x 430.5p").unwrap();
+    assert_eq!(
+        legs,
+        vec![
+            Leg {
+                side: Side::Buy,
+                quantity: 1,
+                strike: 450.0,
+                kind: OptionKind::Call
+            },
+            Leg {
+                side: Side::Sell,
+                quantity: 2,
+                strike: 455.0,
+                kind: OptionKind::Call
+            },
+            Leg {
+                side: Side::Buy,
+                quantity: 1,
+                strike: 440.0,
+                kind: OptionKind::Put
+            },
+            Leg {
+                side: Side::Sell,
+                quantity: 2,
+                strike: 430.5,
+                kind: OptionKind::Put
+            },
+        ]
+    );
+    assert!(parse_legs("hold 1 450C").is_err());
+    assert!(parse_legs("buy 1 450").is_err());
+    assert!(parse_legs("sell 0 450C").is_err());
+    assert!(parse_legs(" , ").is_err());
+}
+
+#[test]
+fn bull_call_spread_is_capped_both_ways() {
+    let payoff = priced("buy 1 450C, sell 1 455C", &[4.20, 2.05]);
+    assert!((payoff.net_debit() - 215.0).abs() < 1e-9);
+    assert!((payoff.max_profit().unwrap() - 285.0).abs() < 1e-9);
+    assert!((payoff.max_loss().unwrap() + 215.0).abs() < 1e-9);
+    let breakevens = payoff.breakevens();
+    assert_eq!(breakevens.len(), 1);
+    assert!((breakevens[0] - 452.15).abs() < 1e-9);
+}
+
+#[test]
+fn short_call_has_unlimited_loss_and_one_breakeven() {
+    let payoff = priced("sell 1 100C", &[3.0]);
+    assert!((payoff.max_profit().unwrap() - 300.0).abs() < 1e-9);
+    assert!(payoff.max_loss().is_none());
+    let breakevens = payoff.breakevens();
+    assert_eq!(breakevens.len(), 1);
+    assert!((breakevens[0] - 103.0).abs() < 1e-9);
+}
+
+#[test]
+fn long_straddle_breaks_even_on_both_sides() {
+    let payoff = priced("buy 100C, buy 100P", &[4.0, 3.0]);
+    assert!(payoff.max_profit().is_none());
+    assert!((payoff.max_loss().unwrap() + 700.0).abs() < 1e-9);
+    let breakevens = payoff.breakevens();
+    assert_eq!(breakevens.len(), 2);
+    assert!((breakevens[0] - 93.0).abs() < 1e-9);
+    assert!((breakevens[1] - 107.0).abs() < 1e-9);
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+use stacks_bot::service::finance::options::{EarningsKink, TermPoint, TermShape};
+
+fn point(day: u32, days: i64, atm_iv: f64) -> TermPoint {
+    TermPoint {
+        expiration: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+        days,
+        atm_iv,
+    }
+}
+
+#[test]
+fn earnings_kink_uses_the_first_expiration_after_the_report() {
+    let points = [point(23, 6, 0.30), point(30, 13, 0.48), point(31, 14, 0.36)];
+    // A report on an expiration day belongs to the next expiration.
+    let earnings = NaiveDate::from_ymd_opt(2026, 10, 23).unwrap();
+
+    let kink = EarningsKink::find(&points, earnings).unwrap();
+    assert_eq!(kink.before, Some(points[0]));
+    assert_eq!(kink.event, points[1]);
+    assert_eq!(kink.after, Some(points[2]));
+    assert_eq!(kink.entering_event(), Some(TermShape::Contango));
+    assert_eq!(kink.leaving_event(), Some(TermShape::Backwardation));
+
+    let later = NaiveDate::from_ymd_opt(2026, 11, 5).unwrap();
+    assert!(EarningsKink::find(&points, later).is_none());
+}
+
+#[test]
+fn small_iv_differences_read_as_flat() {
+    assert_eq!(TermShape::between(0.300, 0.303), TermShape::Flat);
+    assert_eq!(TermShape::between(0.30, 0.32), TermShape::Contango);
+    assert_eq!(TermShape::between(0.32, 0.30), TermShape::Backwardation);
+}
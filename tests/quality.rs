@@ -0,0 +1,159 @@
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use common::date;
+use serde_json::json;
+use stacks_bot::models::FinancialStatement;
+use stacks_bot::service::command::quality::quality_text;
+use stacks_bot::service::finance::quality::{
+    altman, fiscal_years, piotroski, quality_report, FiscalYear, ZZone,
+};
+
+fn year(end: NaiveDate, values: &[(&str, f64)]) -> FiscalYear {
+    FiscalYear {
+        end,
+        values: values.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+    }
+}
+
+fn statement(statement_type: &str, values: &[(&str, &str, f64)]) -> FinancialStatement {
+    let mut statement: HashMap<String, HashMap<String, serde_json::Value>> = HashMap::new();
+    for (field, day, raw) in values {
+        statement.entry(field.to_string()).or_default().insert(
+            day.to_string(),
+            json!({ "asOfDate": day, "currencyCode": "USD", "reportedValue": { "raw": raw } }),
+        );
+    }
+    FinancialStatement {
+        symbol: "ACME".to_string(),
+        statement_type: statement_type.to_string(),
+        frequency: "annual".to_string(),
+        statement,
+    }
+}
+
+#[test]
+fn merges_annual_statements_by_year_end_newest_first() {
+    let statements = [
+        statement(
+            "income",
+            &[("NetIncome", "2024-12-31", 10.0), ("NetIncome", "2023-12-31", 8.0)],
+        ),
+        statement("balance", &[("TotalAssets", "2024-12-31", 100.0)]),
+    ];
+    let years = fiscal_years(&statements);
+    assert_eq!(years.len(), 2);
+    assert_eq!(years[0].end, date(2024, 12, 31));
+    assert_eq!(years[0].get("NetIncome"), Some(10.0));
+    assert_eq!(years[0].get("TotalAssets"), Some(100.0));
+    assert_eq!(years[1].get("TotalAssets"), None);
+}
+
+#[test]
+fn piotroski_passes_improving_years_and_skips_unreported_tests() {
+    let prior = year(
+        date(2023, 12, 31),
+        &[
+            ("NetIncome", 8.0),
+            ("TotalAssets", 100.0),
+            ("LongTermDebt", 30.0),
+            ("CurrentAssets", 40.0),
+            ("CurrentLiabilities", 40.0),
+            ("OrdinarySharesNumber", 10.0),
+            ("GrossProfit", 40.0),
+            ("TotalRevenue", 100.0),
+        ],
+    );
+    let current = year(
+        date(2024, 12, 31),
+        &[
+            ("NetIncome", 12.0),
+            ("OperatingCashFlow", 15.0),
+            ("TotalAssets", 100.0),
+            ("LongTermDebt", 20.0),
+            ("CurrentAssets", 50.0),
+            ("CurrentLiabilities", 40.0),
+            ("OrdinarySharesNumber", 11.0),
+            ("TotalRevenue", 120.0),
+        ],
+    );
+    let f = piotroski(&current, &prior);
+    let passed: Vec<Option<bool>> = f.criteria.iter().map(|c| c.passed).collect();
+    assert_eq!(
+        passed,
+        [
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+        ]
+    );
+    assert_eq!(f.score(), 7);
+    assert_eq!(f.evaluated(), 8);
+}
+
+#[test]
+fn altman_weights_the_five_ratios() {
+    let fy = year(
+        date(2024, 12, 31),
+        &[
+            ("TotalAssets", 100.0),
+            ("TotalLiabilitiesNetMinorityInterest", 50.0),
+            ("CurrentAssets", 30.0),
+            ("CurrentLiabilities", 20.0),
+            ("RetainedEarnings", 20.0),
+            ("EBIT", 10.0),
+            ("TotalRevenue", 100.0),
+        ],
+    );
+    let z = altman(&fy, 100.0).unwrap();
+    // 1.2·0.1 + 1.4·0.2 + 3.3·0.1 + 0.6·2 + 1.0·1
+    assert!((z.z - 2.93).abs() < 1e-9);
+    assert_eq!(z.zone(), ZZone::Grey);
+    assert_eq!(ZZone::of(3.5), ZZone::Safe);
+    assert_eq!(ZZone::of(1.2), ZZone::Distress);
+
+    let without_ebit = year(date(2024, 12, 31), &[("TotalAssets", 100.0)]);
+    assert!(altman(&without_ebit, 100.0).is_none());
+}
+
+#[test]
+fn text_lists_each_test_and_the_zone() {
+    let statements = [
+        statement(
+            "income",
+            &[
+                ("NetIncome", "2024-12-31", 12.0),
+                ("NetIncome", "2023-12-31", 8.0),
+                ("EBIT", "2024-12-31", 10.0),
+                ("TotalRevenue", "2024-12-31", 100.0),
+            ],
+        ),
+        statement(
+            "balance",
+            &[
+                ("TotalAssets", "2024-12-31", 100.0),
+                ("TotalAssets", "2023-12-31", 100.0),
+                ("TotalLiabilitiesNetMinorityInterest", "2024-12-31", 50.0),
+                ("WorkingCapital", "2024-12-31", 10.0),
+                ("RetainedEarnings", "2024-12-31", 20.0),
+            ],
+        ),
+    ];
+    let report = quality_report(&statements, Some(100.0)).unwrap();
+    assert_eq!(report.currency.as_deref(), Some("USD"));
+    let text = quality_text("ACME", &report);
+
+    assert!(text.starts_with("🩺 **ACME quality** (fiscal year ended Dec 31, 2024)"));
+    assert!(text.contains("**Piotroski F-score 3/9** (6 not reported)"));
+    assert!(text.contains("✅ Higher return on assets"));
+    assert!(text.contains("➖ Positive operating cash flow"));
+    assert!(text.contains("**Altman Z-score 2.93** · grey zone"));
+}
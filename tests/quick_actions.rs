@@ -0,0 +1,40 @@
+use chrono::{TimeZone, Utc};
+use serenity::all::GuildId;
+use stacks_bot::service::caching::collections::price_alerts::PriceDirection;
+use stacks_bot::service::command::quick_actions::{default_alert, QuickAction};
+
+#[test]
+fn button_ids_round_trip() {
+    for action in [
+        QuickAction::News,
+        QuickAction::Fundamentals,
+        QuickAction::Alert,
+    ] {
+        let id = action.custom_id("BRK-B");
+        assert!(id.starts_with("quote:"));
+        assert_eq!(QuickAction::parse(&id), Some((action, "BRK-B")));
+    }
+}
+
+#[test]
+fn unknown_button_ids_are_rejected() {
+    assert_eq!(QuickAction::parse("quote:chart:AAPL"), None);
+    assert_eq!(QuickAction::parse("quote:news:"), None);
+    assert_eq!(QuickAction::parse("weekly-earnings:2026-10-12"), None);
+}
+
+#[test]
+fn default_alert_brackets_the_price_by_five_percent() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 15, 0, 0).unwrap();
+    let alert = default_alert("NVDA", 181.23, GuildId::new(1), 2, now);
+    assert_eq!(alert.symbol, "NVDA");
+    assert_eq!(alert.target_guild_id, 1);
+    assert_eq!(alert.target_channel_id, 2);
+    assert_eq!(alert.created_price, 181.23);
+    assert_eq!(alert.levels.len(), 2);
+    assert_eq!(alert.levels[0].target, 190.29);
+    assert_eq!(alert.levels[0].direction, PriceDirection::AtOrAbove);
+    assert_eq!(alert.levels[1].target, 172.17);
+    assert_eq!(alert.levels[1].direction, PriceDirection::AtOrBelow);
+    assert!(alert.levels.iter().all(|level| !level.fired));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use chrono_tz::America::New_York;
+use common::quote;
+use stacks_bot::service::automation::quote_board::{in_session, render};
+
+#[test]
+fn board_lists_each_symbol_in_order() {
+    let updated = Utc.with_ymd_and_hms(2026, 10, 16, 14, 35, 0).unwrap();
+    let quotes = vec![
+        (
+            "SPY".to_string(),
+            Some(quote("SPY", Some(581.234), Some(0.45))),
+        ),
+        (
+            "QQQ".to_string(),
+            Some(quote("QQQ", Some(498.1), Some(-1.2))),
+        ),
+        ("IWM".to_string(), Some(quote("IWM", Some(221.0), None))),
+        ("DIA".to_string(), None),
+    ];
+    let text = render(&quotes, updated);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            format!("📋 **Quote board** · updated <t:{}:R>", updated.timestamp()).as_str(),
+            "🟢 **SPY** 581.23 (+0.45%)",
+            "🔴 **QQQ** 498.10 (-1.20%)",
+            "⚪ **IWM** 221.00",
+            "⚪ **DIA** n/a",
+        ]
+    );
+}
+
+#[test]
+fn session_runs_from_the_open_until_just_after_the_close() {
+    let at = |day, hour, minute| {
+        New_York
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    };
+    assert!(!in_session(at(16, 9, 29)));
+    assert!(in_session(at(16, 9, 30)));
+    assert!(in_session(at(16, 16, 4)));
+    assert!(!in_session(at(16, 16, 5)));
+    // Saturday
+    assert!(!in_session(at(17, 12, 0)));
+}
@@ -0,0 +1,133 @@
+use chrono::NaiveDate;
+use serde_json::json;
+use stacks_bot::models::quote_summary::QuoteSummary;
+
+fn parse(result: serde_json::Value) -> QuoteSummary {
+    let response = json!({ "quoteSummary": { "result": [result], "error": null } });
+    QuoteSummary::from_response(&response)
+        .expect("valid response")
+        .expect("one result")
+}
+
+#[test]
+fn reads_wrapped_bare_and_missing_numbers() {
+    let summary = parse(json!({
+        "price": {
+            "symbol": "AAPL",
+            "currency": "USD",
+            "regularMarketPrice": { "raw": 189.5, "fmt": "189.50" },
+            "marketCap": 2.9e12
+        },
+        "summaryDetail": {
+            "trailingPE": { "raw": 29.1, "fmt": "29.10" },
+            "forwardPE": {},
+            "dividendYield": { "raw": null, "fmt": null }
+        },
+        "defaultKeyStatistics": { "trailingEps": { "raw": -0.42, "fmt": "-0.42" } }
+    }));
+
+    let price = summary.price.expect("price module");
+    assert_eq!(price.symbol.as_deref(), Some("AAPL"));
+    assert_eq!(price.regular_market_price, Some(189.5));
+    assert_eq!(price.market_cap, Some(2.9e12));
+
+    let detail = summary.summary_detail.expect("summaryDetail module");
+    assert_eq!(detail.trailing_pe, Some(29.1));
+    assert_eq!(detail.forward_pe, None);
+    assert_eq!(detail.dividend_yield, None);
+    assert_eq!(detail.market_cap, None);
+
+    let stats = summary
+        .default_key_statistics
+        .expect("keyStatistics module");
+    assert_eq!(stats.trailing_eps, Some(-0.42));
+    assert!(summary.financial_data.is_none());
+}
+
+#[test]
+fn reads_earnings_history_and_calendar_dates() {
+    let summary = parse(json!({
+        "earnings": {
+            "earningsChart": {
+                "quarterly": [
+                    { "date": "1Q2024", "actual": { "raw": 1.53 }, "estimate": { "raw": 1.5 } },
+                    { "date": "2Q2024", "actual": {}, "estimate": { "raw": 1.35 } }
+                ]
+            },
+            "financialsChart": {
+                "quarterly": [{ "date": "2Q2024", "revenue": { "raw": 8.58e10 }, "earnings": {} }]
+            }
+        },
+        "calendarEvents": {
+            "earnings": {
+                "earningsAverage": { "raw": 1.6 },
+                "earningsDate": [{ "raw": 1722542400 }, { "raw": 1722974400 }]
+            }
+        }
+    }));
+
+    let earnings = summary.earnings.expect("earnings module");
+    let quarters = earnings.earnings_chart.expect("earnings chart").quarterly;
+    assert_eq!(quarters.len(), 2);
+    assert_eq!(quarters[0].date.as_deref(), Some("1Q2024"));
+    assert_eq!(quarters[0].actual, Some(1.53));
+    assert_eq!(quarters[1].actual, None);
+    assert_eq!(quarters[1].estimate, Some(1.35));
+
+    let periods = earnings
+        .financials_chart
+        .expect("financials chart")
+        .quarterly;
+    assert_eq!(periods[0].revenue, Some(8.58e10));
+    assert_eq!(periods[0].earnings, None);
+
+    let calendar = summary
+        .calendar_events
+        .and_then(|events| events.earnings)
+        .expect("calendar earnings");
+    assert_eq!(calendar.earnings_average, Some(1.6));
+    assert_eq!(calendar.revenue_average, None);
+    assert_eq!(
+        calendar.earnings_date,
+        vec![
+            NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 8, 6).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn reads_fund_sector_weightings_in_order() {
+    let summary = parse(json!({
+        "topHoldings": {
+            "holdings": [{ "symbol": "MSFT", "holdingName": "Microsoft", "holdingPercent": { "raw": 0.07 } }],
+            "sectorWeightings": [
+                { "technology": { "raw": 0.31 } },
+                { "realestate": {} },
+                { "healthcare": { "raw": 0.12 } }
+            ]
+        }
+    }));
+
+    let holdings = summary.top_holdings.expect("topHoldings module");
+    assert_eq!(holdings.holdings[0].symbol.as_deref(), Some("MSFT"));
+    assert_eq!(holdings.holdings[0].holding_percent, Some(0.07));
+    assert_eq!(
+        holdings.sector_weightings,
+        vec![
+            ("technology".to_string(), 0.31),
+            ("healthcare".to_string(), 0.12)
+        ]
+    );
+}
+
+#[test]
+fn empty_or_null_result_is_none() {
+    let empty = json!({ "quoteSummary": { "result": [], "error": null } });
+    assert!(QuoteSummary::from_response(&empty).unwrap().is_none());
+
+    let null = json!({ "quoteSummary": { "result": null, "error": { "code": "Not Found" } } });
+    assert!(QuoteSummary::from_response(&null).unwrap().is_none());
+
+    assert!(QuoteSummary::from_response(&json!({ "chart": {} })).is_err());
+}
@@ -0,0 +1,89 @@
+use serde_json::{json, Value};
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, GuildId, Permissions};
+use stacks_bot::service::command::registration::{comparable, Scope};
+
+fn quote_builder(description: &str) -> Value {
+    let builder = CreateCommand::new("quote")
+        .description(description)
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "symbol", "Ticker symbol")
+                .required(true),
+        );
+    serde_json::to_value(builder).unwrap()
+}
+
+/// `/quote` as Discord returns it from the commands endpoint.
+fn registered_quote() -> Value {
+    json!({
+        "id": "1180000000000000001",
+        "application_id": "1170000000000000000",
+        "version": "1180000000000000002",
+        "type": 1,
+        "name": "quote",
+        "name_localizations": null,
+        "description": "Get a stock quote",
+        "description_localizations": null,
+        "options": [{
+            "type": 3,
+            "name": "symbol",
+            "name_localizations": null,
+            "description": "Ticker symbol",
+            "description_localizations": null,
+            "required": true,
+            "autocomplete": false
+        }],
+        "default_member_permissions": null,
+        "dm_permission": true,
+        "contexts": null,
+        "integration_types": [0],
+        "nsfw": false
+    })
+}
+
+#[test]
+fn unchanged_commands_compare_equal() {
+    let wanted = comparable(&quote_builder("Get a stock quote"), Scope::Global);
+    let have = comparable(&registered_quote(), Scope::Global);
+    assert_eq!(wanted, have);
+
+    let guild = Scope::Guild(GuildId::new(42));
+    assert_eq!(
+        comparable(&quote_builder("Get a stock quote"), guild),
+        comparable(&registered_quote(), guild)
+    );
+}
+
+#[test]
+fn changed_descriptions_and_permissions_differ() {
+    let have = comparable(&registered_quote(), Scope::Global);
+    assert_ne!(
+        comparable(&quote_builder("Latest price for a ticker"), Scope::Global),
+        have
+    );
+
+    let restricted = CreateCommand::new("quote")
+        .description("Get a stock quote")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "symbol", "Ticker symbol")
+                .required(true),
+        )
+        .default_member_permissions(Permissions::MANAGE_GUILD);
+    let restricted = serde_json::to_value(restricted).unwrap();
+    assert_ne!(comparable(&restricted, Scope::Global), have);
+}
+
+#[test]
+fn dm_permission_only_matters_globally() {
+    let mut guild_only = registered_quote();
+    guild_only["dm_permission"] = json!(false);
+    guild_only["contexts"] = json!([0]);
+
+    let wanted = quote_builder("Get a stock quote");
+    assert_ne!(
+        comparable(&wanted, Scope::Global),
+        comparable(&guild_only, Scope::Global)
+    );
+
+    let guild = Scope::Guild(GuildId::new(42));
+    assert_eq!(comparable(&wanted, guild), comparable(&guild_only, guild));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{close, close_enough};
+use stacks_bot::service::command::rs::rs_text;
+use stacks_bot::service::finance::relative_strength::{
+    trailing_return, Ranking, RelativeStrength, Returns, SymbolStrength,
+};
+
+fn returns(one_month: f64, three_months: f64) -> Returns {
+    Returns {
+        one_month: Some(one_month),
+        three_months: Some(three_months),
+    }
+}
+
+#[test]
+fn lookbacks_start_from_the_last_close_on_or_before_the_date() {
+    let closes = [
+        close(2026, 7, 15, 80.0),
+        close(2026, 7, 16, 90.0),
+        close(2026, 9, 15, 100.0),
+        close(2026, 9, 17, 105.0),
+        close(2026, 10, 16, 110.0),
+    ];
+    assert!(close_enough(trailing_return(&closes, 1).unwrap(), 0.1));
+    assert!(close_enough(
+        trailing_return(&closes, 3).unwrap(),
+        110.0 / 90.0 - 1.0
+    ));
+    assert_eq!(trailing_return(&closes[2..], 3), None);
+    assert_eq!(trailing_return(&[], 1), None);
+}
+
+#[test]
+fn strength_is_the_change_of_the_ratio_to_spy() {
+    let spy = returns(0.1, -0.2);
+    let s = SymbolStrength::new("ACME", returns(0.21, -0.2), spy);
+    assert!(close_enough(s.relative.one_month.unwrap(), 0.1));
+    assert!(close_enough(s.relative.three_months.unwrap(), 0.0));
+    assert!(close_enough(s.score(Ranking::Blend).unwrap(), 0.05));
+
+    let young = SymbolStrength::new(
+        "NEWCO",
+        Returns {
+            one_month: Some(0.1),
+            three_months: None,
+        },
+        spy,
+    );
+    assert_eq!(young.score(Ranking::Blend), None);
+    assert_eq!(young.score(Ranking::ThreeMonths), None);
+    assert!(young.score(Ranking::OneMonth).is_some());
+}
+
+#[test]
+fn ranking_lists_leaders_and_laggards() {
+    let spy = returns(0.0, 0.0);
+    let mut symbols: Vec<SymbolStrength> = (1..=12)
+        .map(|i| {
+            let r = i as f64 / 100.0;
+            SymbolStrength::new(format!("S{i:02}"), returns(r, -r), spy)
+        })
+        .collect();
+    symbols.push(SymbolStrength::new(
+        "NEWCO",
+        Returns {
+            one_month: Some(0.5),
+            three_months: None,
+        },
+        spy,
+    ));
+    let strength = RelativeStrength {
+        benchmark: returns(0.02, 0.05),
+        symbols,
+        failed: vec!["BAD".to_string()],
+    };
+
+    let ranked = strength.ranked(Ranking::OneMonth);
+    assert_eq!(ranked.len(), 13);
+    assert_eq!(ranked[0].symbol, "NEWCO");
+    assert_eq!(ranked[12].symbol, "S01");
+    assert_eq!(strength.ranked(Ranking::ThreeMonths)[0].symbol, "S01");
+
+    let text = rs_text(&strength, Ranking::ThreeMonths);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "💪 **Relative strength vs SPY** · 12 symbols by 3 months"
+    );
+    assert_eq!(lines[1], "SPY 1M +2.00% · 3M +5.00%");
+    assert_eq!(lines[3], "🟢 **Leaders**");
+    assert_eq!(lines[4], "1. **S01** 1M +1.00% · 3M -1.00%");
+    assert_eq!(lines[8], "5. **S05** 1M +5.00% · 3M -5.00%");
+    assert_eq!(lines[10], "🔴 **Laggards**");
+    assert_eq!(lines[11], "8. **S08** 1M +8.00% · 3M -8.00%");
+    assert_eq!(lines[15], "12. **S12** 1M +12.00% · 3M -12.00%");
+    assert_eq!(
+        lines[17],
+        "*Return over SPY's, as the change of the price ratio · 1 without enough history · 1 failed to load (BAD)*"
+    );
+}
+
+#[test]
+fn short_lists_split_between_leaders_and_laggards() {
+    let spy = returns(0.0, 0.0);
+    let strength = RelativeStrength {
+        benchmark: spy,
+        symbols: vec![
+            SymbolStrength::new("AAA", returns(0.01, 0.01), spy),
+            SymbolStrength::new("BBB", returns(0.03, 0.03), spy),
+            SymbolStrength::new("CCC", returns(0.02, 0.02), spy),
+        ],
+        failed: Vec::new(),
+    };
+    let text = rs_text(&strength, Ranking::Blend);
+    assert!(text.contains(
+        "🟢 **Leaders**\n1. **BBB** 1M +3.00% · 3M +3.00%\n2. **CCC** 1M +2.00% · 3M +2.00%\n\n🔴 **Laggards**\n3. **AAA**"
+    ));
+
+    let empty = RelativeStrength {
+        benchmark: spy,
+        symbols: Vec::new(),
+        failed: vec!["BAD".to_string()],
+    };
+    assert!(rs_text(&empty, Ranking::Blend).contains("No symbol has enough history to rank."));
+}
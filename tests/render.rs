@@ -0,0 +1,26 @@
+use stacks_bot::service::metrics::METRICS;
+use stacks_bot::service::render;
+
+#[tokio::test]
+async fn renders_run_off_the_runtime_and_are_timed() {
+    let png = render::blocking("test-image", || Ok(vec![1u8, 2, 3]))
+        .await
+        .unwrap();
+    assert_eq!(png, [1, 2, 3]);
+
+    let err = render::blocking::<Vec<u8>, _>("test-image", || Err("no font".to_string()))
+        .await
+        .unwrap_err();
+    assert_eq!(err, "no font");
+
+    let metrics = METRICS.render_prometheus();
+    assert!(metrics.contains("bot_render_duration_seconds_count{image=\"test-image\"} 2"));
+}
+
+#[tokio::test]
+async fn a_panicking_render_is_an_error() {
+    let err = render::blocking::<(), _>("test-panic", || panic!("bad layout"))
+        .await
+        .unwrap_err();
+    assert!(err.starts_with("test-panic render failed"));
+}
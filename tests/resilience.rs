@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use stacks_bot::service::finance::resilience::{Resilience, RetryPolicy};
+use stacks_bot::service::finance::FinanceServiceError;
+
+fn quick(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(2),
+    }
+}
+
+#[test]
+fn backoff_doubles_and_caps_at_the_max_delay() {
+    let policy = RetryPolicy::default();
+    let delays: Vec<_> = (1..=5).map(|attempt| policy.delay(attempt)).collect();
+    assert_eq!(
+        delays,
+        vec![
+            Duration::from_millis(250),
+            Duration::from_millis(500),
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            Duration::from_secs(2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn transient_errors_are_retried_until_the_call_succeeds() {
+    let resilience = Resilience::new(quick(3));
+    let calls = AtomicU32::new(0);
+
+    let result = resilience
+        .call("test_retry", || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(FinanceServiceError::Http("502 Bad Gateway".to_string()))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn definitive_errors_are_not_retried() {
+    let resilience = Resilience::new(quick(3));
+    let calls = AtomicU32::new(0);
+
+    let result: Result<(), _> = resilience
+        .call("test_not_found", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(FinanceServiceError::NotFound("NOPE".to_string()))
+        })
+        .await;
+
+    assert!(matches!(result, Err(FinanceServiceError::NotFound(_))));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn circuit_opens_after_repeated_failures() {
+    let resilience = Resilience::new(quick(1));
+    let calls = AtomicU32::new(0);
+    let failing = || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(FinanceServiceError::Timeout("quote".to_string()))
+    };
+
+    for _ in 0..5 {
+        let result = resilience.call("test_breaker", failing).await;
+        assert!(matches!(result, Err(FinanceServiceError::Timeout(_))));
+    }
+
+    let result = resilience.call("test_breaker", failing).await;
+    assert!(matches!(
+        result,
+        Err(FinanceServiceError::Unavailable("test_breaker"))
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+    // Other endpoints keep their own breaker.
+    let other = resilience.call("test_other", || async { Ok::<_, FinanceServiceError>(1) });
+    assert_eq!(other.await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn a_success_resets_the_failure_count() {
+    let resilience = Resilience::new(quick(1));
+    let fail = || async { Err::<(), _>(FinanceServiceError::Http("503".to_string())) };
+
+    for _ in 0..4 {
+        let _ = resilience.call("test_reset", fail).await;
+    }
+    resilience
+        .call("test_reset", || async { Ok::<_, FinanceServiceError>(()) })
+        .await
+        .unwrap();
+    for _ in 0..4 {
+        let _ = resilience.call("test_reset", fail).await;
+    }
+
+    let result = resilience.call("test_reset", fail).await;
+    assert!(matches!(result, Err(FinanceServiceError::Http(_))));
+}
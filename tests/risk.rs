@@ -0,0 +1,146 @@
+mod common;
+
+use common::{close, close_enough};
+use stacks_bot::service::command::risk::{risk_text, RiskReport};
+use stacks_bot::service::finance::history::DailyClose;
+use stacks_bot::service::finance::risk::{
+    all_time_high, beta, max_drawdown, risk_stats, trailing, Period,
+};
+
+#[test]
+fn drawdown_runs_from_the_highest_close_before_the_trough() {
+    let closes = [
+        close(2026, 1, 2, 100.0),
+        close(2026, 1, 5, 120.0),
+        close(2026, 1, 6, 90.0),
+        close(2026, 1, 7, 130.0),
+        close(2026, 1, 8, 110.0),
+    ];
+    let drawdown = max_drawdown(&closes).unwrap();
+    assert_eq!(drawdown.peak, closes[1]);
+    assert_eq!(drawdown.trough, closes[2]);
+    assert!(close_enough(drawdown.change(), -0.25));
+    assert_eq!(all_time_high(&closes), Some(closes[3]));
+
+    let rising = [close(2026, 1, 2, 100.0), close(2026, 1, 5, 101.0)];
+    assert_eq!(max_drawdown(&rising), None);
+}
+
+#[test]
+fn volatility_and_sharpe_come_from_daily_returns() {
+    // Returns alternate +10% and -10%.
+    let closes = [
+        close(2025, 1, 2, 100.0),
+        close(2025, 1, 3, 110.0),
+        close(2025, 1, 6, 99.0),
+        close(2025, 1, 7, 108.9),
+        close(2026, 1, 2, 98.01),
+    ];
+    let stats = risk_stats(&closes, 0.04).unwrap();
+    // Sample standard deviation of [0.1, -0.1, 0.1, -0.1] is 0.2 / √3.
+    let volatility = 0.2 / 3f64.sqrt() * 252f64.sqrt();
+    assert!(close_enough(stats.volatility, volatility));
+    assert!(close_enough(stats.sharpe.unwrap(), -0.04 / volatility));
+    // A calendar year is 365 of 365.25 days, so close to the -1.99% total.
+    assert!((stats.annual_return + 0.0199).abs() < 1e-4);
+    assert_eq!(stats.start, closes[0].date);
+    assert_eq!(stats.end, closes[4].date);
+
+    assert!(risk_stats(&closes[..2], 0.04).is_none());
+    let flat = [
+        close(2026, 1, 2, 50.0),
+        close(2026, 1, 5, 50.0),
+        close(2026, 1, 6, 50.0),
+    ];
+    assert_eq!(risk_stats(&flat, 0.04).unwrap().sharpe, None);
+}
+
+#[test]
+fn beta_pairs_returns_on_shared_dates() {
+    let market = [
+        close(2026, 3, 2, 100.0),
+        close(2026, 3, 3, 101.0),
+        close(2026, 3, 4, 99.0),
+        close(2026, 3, 5, 100.0),
+    ];
+    // Twice the market's moves, plus a day the market has no close for.
+    let mut asset: Vec<DailyClose> = vec![close(2026, 3, 2, 50.0)];
+    for w in market.windows(2) {
+        let r = w[1].close / w[0].close - 1.0;
+        let last = asset.last().unwrap().close;
+        asset.push(DailyClose {
+            date: w[1].date,
+            close: last * (1.0 + 2.0 * r),
+        });
+    }
+    asset.push(close(2026, 3, 6, 80.0));
+    assert!(close_enough(beta(&asset, &market).unwrap(), 2.0));
+    assert_eq!(beta(&asset, &[]), None);
+}
+
+#[test]
+fn trailing_keeps_the_closes_within_the_period() {
+    let closes = [
+        close(2024, 10, 15, 1.0),
+        close(2025, 10, 16, 2.0),
+        close(2025, 10, 17, 3.0),
+        close(2026, 10, 16, 4.0),
+    ];
+    assert_eq!(trailing(&closes, Period::OneYear.years()), &closes[1..]);
+    assert_eq!(trailing(&closes, Period::FiveYears.years()), &closes[..]);
+    assert!(trailing(&[], 1).is_empty());
+    assert_eq!(Period::from_name("10y"), Some(Period::TenYears));
+    assert_eq!(Period::from_name("3y"), None);
+}
+
+#[test]
+fn text_compares_each_statistic_with_spy() {
+    let closes = [
+        close(2025, 10, 16, 100.0),
+        close(2025, 10, 17, 120.0),
+        close(2026, 3, 2, 90.0),
+        close(2026, 10, 16, 110.0),
+    ];
+    let spy = [
+        close(2025, 10, 16, 100.0),
+        close(2025, 10, 17, 101.0),
+        close(2026, 3, 2, 95.0),
+        close(2026, 10, 16, 105.0),
+    ];
+    let report = RiskReport {
+        period: Period::OneYear,
+        stats: risk_stats(&closes, 0.045).unwrap(),
+        benchmark: risk_stats(&spy, 0.045),
+        beta: beta(&closes, &spy),
+        high: all_time_high(&closes).unwrap(),
+        last: closes[3],
+        risk_free: 0.045,
+    };
+    let text = risk_text("ACME", &report);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "⚖️ **ACME risk** · 1 year (Oct 16, 2025 – Oct 16, 2026)"
+    );
+    assert!(lines[1].starts_with("Volatility **") && lines[1].contains("annualized (SPY "));
+    assert!(
+        lines[2].starts_with("Max drawdown **-25.0%** (Oct 17, 2025 – Mar 2, 2026) (SPY -5.9%)")
+    );
+    assert!(lines[3].starts_with("Sharpe ratio **") && lines[3].ends_with("· risk-free 4.50%"));
+    assert!(lines[4].starts_with("Return **+10.0%** a year (SPY +5.0%) · beta "));
+    assert_eq!(
+        lines[6],
+        "🏔️ **-8.3%** from its all-time closing high of $120.00 (Oct 17, 2025)"
+    );
+
+    let at_high = RiskReport {
+        benchmark: None,
+        beta: None,
+        last: report.high,
+        ..report
+    };
+    let text = risk_text("ACME", &at_high);
+    assert!(text.contains("Max drawdown **-25.0%** (Oct 17, 2025 – Mar 2, 2026)\n"));
+    assert!(!text.contains("SPY"));
+    assert!(text.ends_with("🏔️ At its all-time closing high of $120.00"));
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use serenity::all::{ChannelId, GuildId};
+use stacks_bot::service::automation::jobs::Job;
+use stacks_bot::service::routing::{targets, Route};
+
+fn channel(id: u64) -> ChannelId {
+    ChannelId::new(id)
+}
+
+fn guild(id: u64) -> GuildId {
+    GuildId::new(id)
+}
+
+#[test]
+fn route_names_round_trip() {
+    for route in Route::ALL {
+        assert_eq!(Route::from_name(route.name()), Some(route));
+    }
+    assert_eq!(Route::from_name("bot-ops"), None);
+    assert_eq!(Job::DailyEarnings.route(), Route::Earnings);
+    assert_eq!(Job::OptionsPinger.route(), Route::Options);
+    assert_eq!(Job::CorporateActions.route(), Route::Alerts);
+}
+
+#[test]
+fn routed_guilds_replace_the_configured_channel() {
+    let routed = HashMap::from([(guild(1), channel(30)), (guild(2), channel(20))]);
+
+    // The configured channel's guild routes the category, so it only gets its route.
+    assert_eq!(
+        targets(&routed, Some((channel(10), Some(guild(1))))),
+        vec![channel(20), channel(30)]
+    );
+    // A guild without a route, or an unknown one, keeps the configured channel.
+    assert_eq!(
+        targets(&routed, Some((channel(10), Some(guild(3))))),
+        vec![channel(10), channel(20), channel(30)]
+    );
+    assert_eq!(
+        targets(&routed, Some((channel(20), None))),
+        vec![channel(20), channel(30)]
+    );
+    assert_eq!(targets(&HashMap::new(), None), Vec::<ChannelId>::new());
+    assert_eq!(
+        targets(&HashMap::new(), Some((channel(10), None))),
+        vec![channel(10)]
+    );
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{close, close_enough, date};
+use stacks_bot::service::command::seasonality::seasonality_text;
+use stacks_bot::service::finance::seasonality::{monthly_returns, seasonality, MonthReturn};
+
+#[test]
+fn months_run_from_the_prior_months_last_close() {
+    let closes = [
+        close(2024, 9, 27, 90.0),
+        close(2024, 9, 30, 100.0),
+        close(2024, 10, 1, 104.0),
+        close(2024, 10, 31, 110.0),
+        close(2024, 11, 29, 99.0),
+    ];
+    let returns = monthly_returns(&closes);
+    assert_eq!(returns.len(), 2);
+    assert_eq!((returns[0].year, returns[0].month), (2024, 10));
+    assert!(close_enough(returns[0].change, 0.1));
+    assert_eq!(returns[1].month, 11);
+    assert!(close_enough(returns[1].change, -0.1));
+    assert!(monthly_returns(&closes[..2]).is_empty());
+}
+
+#[test]
+fn the_month_under_way_is_left_out_of_the_averages() {
+    let closes = [
+        close(2023, 9, 29, 100.0),
+        close(2023, 10, 31, 90.0),
+        close(2024, 9, 30, 100.0),
+        close(2024, 10, 31, 120.0),
+        close(2025, 9, 30, 100.0),
+        close(2025, 10, 31, 110.0),
+        close(2026, 9, 30, 100.0),
+        close(2026, 10, 16, 105.0),
+    ];
+    let today = date(2026, 10, 17);
+    let stats = seasonality(&closes, today);
+
+    let october = stats.month(10);
+    assert_eq!(october.returns.len(), 3);
+    assert!(close_enough(october.average().unwrap(), 0.2 / 3.0));
+    assert_eq!(october.positive(), 2);
+    assert_eq!(october.best().map(|r| r.year), Some(2024));
+    assert_eq!(october.worst().map(|r| r.year), Some(2023));
+    assert_eq!(stats.years(), 3);
+    // Septembers follow a year without closes, so none has a prior close.
+    assert!(stats.month(9).returns.is_empty());
+    let current = stats.current.unwrap();
+    assert_eq!((current.year, current.month), (2026, 10));
+    assert!(close_enough(current.change, 0.05));
+
+    let text = seasonality_text("ACME", &stats, today);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "🗓️ **ACME seasonality** · average monthly returns over 3 years"
+    );
+    assert_eq!(lines[3], "**October**");
+    assert_eq!(lines[4], "Average +6.67% · up in 2 of 3 years");
+    assert_eq!(lines[5], "Best 2024 (+20.00%) · worst 2023 (-10.00%)");
+    assert_eq!(lines[6], "2026 so far: +5.00%");
+    assert_eq!(
+        lines[7],
+        "By year: 2025 +10.00% · 2024 +20.00% · 2023 -10.00%"
+    );
+}
+
+#[test]
+fn strongest_and_weakest_months_are_ranked_by_average() {
+    let closes = [
+        close(2025, 1, 31, 100.0),
+        close(2025, 2, 28, 95.0),
+        close(2025, 3, 31, 114.0),
+        close(2025, 4, 30, 114.0),
+    ];
+    let stats = seasonality(&closes, date(2026, 10, 17));
+    assert_eq!(
+        stats.month(3).returns,
+        vec![MonthReturn {
+            year: 2025,
+            month: 3,
+            change: 114.0 / 95.0 - 1.0
+        }]
+    );
+    let text = seasonality_text("ACME", &stats, date(2026, 10, 17));
+    assert!(text.contains("Strongest **March** (+20.00%) · weakest **February** (-5.00%)"));
+    assert!(text.contains("No full month on record yet."));
+    assert!(stats.current.is_none());
+}
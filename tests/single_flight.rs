@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use stacks_bot::service::finance::single_flight::SingleFlight;
+use stacks_bot::service::finance::FinanceServiceError;
+
+async fn slow_fetch(calls: &AtomicU32, value: u32) -> Result<u32, FinanceServiceError> {
+    calls.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    Ok(value)
+}
+
+#[tokio::test]
+async fn concurrent_identical_requests_share_one_fetch() {
+    let flights = SingleFlight::default();
+    let calls = AtomicU32::new(0);
+
+    let (first, second, third) = tokio::join!(
+        flights.run("quote:AAPL".to_string(), || slow_fetch(&calls, 1)),
+        flights.run("quote:AAPL".to_string(), || slow_fetch(&calls, 2)),
+        flights.run("quote:AAPL".to_string(), || slow_fetch(&calls, 3)),
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!((first.unwrap(), second.unwrap(), third.unwrap()), (1, 1, 1));
+}
+
+#[tokio::test]
+async fn different_keys_fetch_separately() {
+    let flights = SingleFlight::default();
+    let calls = AtomicU32::new(0);
+
+    let (aapl, msft) = tokio::join!(
+        flights.run("quote:AAPL".to_string(), || slow_fetch(&calls, 1)),
+        flights.run("quote:MSFT".to_string(), || slow_fetch(&calls, 2)),
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!((aapl.unwrap(), msft.unwrap()), (1, 2));
+}
+
+#[tokio::test]
+async fn completed_results_are_not_cached() {
+    let flights = SingleFlight::default();
+    let calls = AtomicU32::new(0);
+
+    let first = flights
+        .run("quote:AAPL".to_string(), || slow_fetch(&calls, 1))
+        .await;
+    let second = flights
+        .run("quote:AAPL".to_string(), || slow_fetch(&calls, 2))
+        .await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!((first.unwrap(), second.unwrap()), (1, 2));
+}
+
+#[tokio::test]
+async fn errors_reach_every_waiting_caller() {
+    let flights = SingleFlight::<u32>::default();
+    let failing = || async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Err(FinanceServiceError::NotFound("NOPE".to_string()))
+    };
+
+    let (leader, waiter) = tokio::join!(
+        flights.run("quote:NOPE".to_string(), failing),
+        flights.run("quote:NOPE".to_string(), failing),
+    );
+
+    assert!(is_not_found(&leader.unwrap_err()));
+    assert!(is_not_found(&waiter.unwrap_err()));
+}
+
+fn is_not_found(err: &FinanceServiceError) -> bool {
+    match err {
+        FinanceServiceError::NotFound(_) => true,
+        FinanceServiceError::Shared(err) => is_not_found(err),
+        _ => false,
+    }
+}
+
+#[tokio::test]
+async fn waiters_fetch_themselves_when_the_leader_is_cancelled() {
+    let flights = SingleFlight::default();
+    let calls = AtomicU32::new(0);
+
+    let leader = flights.run("quote:AAPL".to_string(), || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        std::future::pending::<Result<u32, FinanceServiceError>>().await
+    });
+    let waiter = flights.run("quote:AAPL".to_string(), || slow_fetch(&calls, 2));
+
+    // The leader never finishes; dropping it after the timeout must release the waiter.
+    let (_, waited) = tokio::join!(
+        tokio::time::timeout(Duration::from_millis(20), leader),
+        waiter
+    );
+
+    assert_eq!(waited.unwrap(), 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,24 @@
+use stacks_bot::service::sparkline::{points, render};
+
+#[test]
+fn closes_spread_across_the_width_highest_on_top() {
+    let pts = points(&[10.0, 12.0, 11.0], 240, 64);
+    assert_eq!(pts.len(), 3);
+    assert_eq!(pts[0], (6.0, 58.0));
+    assert_eq!(pts[1], (120.0, 6.0));
+    assert_eq!(pts[2], (234.0, 32.0));
+}
+
+#[test]
+fn a_flat_week_sits_in_the_middle() {
+    let pts = points(&[5.0, 5.0, 5.0, 5.0], 240, 64);
+    assert!(pts.iter().all(|&(_, y)| y == 32.0));
+}
+
+#[test]
+fn renders_a_small_png() {
+    let png = render(&[100.0, 101.5, 99.0, 103.2, 104.0, 102.1, 105.3]).unwrap();
+    let img = image::load_from_memory(&png).unwrap();
+    assert_eq!((img.width(), img.height()), (240, 64));
+    assert!(render(&[100.0]).is_err());
+}
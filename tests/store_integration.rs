@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, Utc};
+
+use stacks_bot::models::EarningsRecord;
+use stacks_bot::service::caching::collections::event_alerts::EventAlert;
+use stacks_bot::service::caching::collections::morning_briefs::MorningBrief;
+use stacks_bot::service::caching::collections::price_alerts::PriceAlert;
+use stacks_bot::service::finance::corporate_actions::CorporateActions;
+use stacks_bot::service::finance::fundamental_events::{EventKind, EventSnapshot};
+use stacks_bot::service::finance::ownership::{OwnershipSnapshot, KEEP_QUARTERS};
+use stacks_bot::service::store::{export, SqlStore, StateStore};
+use stacks_bot::service::symbol_filter::SymbolFilter;
+
+/// Runs the migrations against a throwaway SQLite file and round-trips each kind of state.
+#[tokio::test]
+async fn sqlite_store_round_trips_state() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("stacks-bot-store-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let store = SqlStore::connect(&format!("sqlite://{}?mode=rwc", path.display())).await?;
+
+    store.set_command_roles(1, "er-reports", &[10, 11]).await?;
+    assert_eq!(store.command_roles(1, "er-reports").await?, vec![10, 11]);
+    store.set_command_roles(1, "er-reports", &[12]).await?;
+    assert_eq!(store.all_command_roles(1).await?.len(), 1);
+    store.set_command_roles(1, "er-reports", &[]).await?;
+    assert!(store.command_roles(1, "er-reports").await?.is_empty());
+
+    store.set_audit_channel(1, Some(99)).await?;
+    assert_eq!(store.audit_channel(1).await?, Some(99));
+    store.set_audit_channel(1, None).await?;
+    assert_eq!(store.audit_channel(1).await?, None);
+
+    store.set_topic_channel(1, Some(77)).await?;
+    store.set_topic_channel(2, Some(88)).await?;
+    store.set_topic_channel(1, Some(78)).await?;
+    assert_eq!(store.topic_channel(1).await?, Some(78));
+    let topics = store.topic_channels().await?;
+    assert_eq!((topics.len(), topics.get(&2)), (2, Some(&88)));
+    store.set_topic_channel(2, None).await?;
+    assert_eq!(store.topic_channels().await?.len(), 1);
+
+    store.set_guild_route(1, "earnings", Some(501)).await?;
+    store.set_guild_route(1, "errors", Some(502)).await?;
+    store.set_guild_route(2, "earnings", Some(601)).await?;
+    store.set_guild_route(1, "earnings", Some(503)).await?;
+    let routes = store.guild_routes(1).await?;
+    assert_eq!(
+        routes.into_iter().collect::<Vec<_>>(),
+        vec![("earnings".to_string(), 503), ("errors".to_string(), 502)]
+    );
+    let earnings = store.route_channels("earnings").await?;
+    assert_eq!(
+        (earnings.get(&1), earnings.get(&2)),
+        (Some(&503), Some(&601))
+    );
+    store.set_guild_route(2, "earnings", None).await?;
+    assert_eq!(store.route_channels("earnings").await?.len(), 1);
+    assert!(store.route_channels("options").await?.is_empty());
+
+    store.set_guild_locale(1, Some("es")).await?;
+    assert_eq!(store.guild_locale(1).await?.as_deref(), Some("es"));
+    store.set_guild_locale(1, None).await?;
+    assert_eq!(store.guild_locale(1).await?, None);
+
+    store.set_guild_timezone(1, Some("Europe/London")).await?;
+    store.set_user_timezone(7, Some("Asia/Tokyo")).await?;
+    assert_eq!(
+        store.guild_timezone(1).await?.as_deref(),
+        Some("Europe/London")
+    );
+    assert_eq!(store.user_timezone(7).await?.as_deref(), Some("Asia/Tokyo"));
+    store.set_user_timezone(7, None).await?;
+    assert_eq!(store.user_timezone(7).await?, None);
+    assert_eq!(
+        store.guild_timezone(1).await?.as_deref(),
+        Some("Europe/London")
+    );
+
+    store.set_channel_ticker(88, Some("SPY")).await?;
+    assert_eq!(store.channel_ticker(88).await?.as_deref(), Some("SPY"));
+    assert_eq!(store.channel_ticker(89).await?, None);
+    store.set_channel_ticker(88, None).await?;
+    assert_eq!(store.channel_ticker(88).await?, None);
+
+    let filter = SymbolFilter {
+        allow: SymbolFilter::parse_list("sp500, $msft")?,
+        deny: vec!["TSLA".to_string()],
+        min_market_cap: Some(2e9),
+    };
+    store.set_guild_symbol_filter(1, Some(&filter)).await?;
+    assert_eq!(store.guild_symbol_filter(1).await?, Some(filter));
+    store.set_guild_symbol_filter(1, None).await?;
+    assert_eq!(store.guild_symbol_filter(1).await?, None);
+
+    store.set_job_paused(1, "weekly_earnings", true).await?;
+    store.set_job_paused(1, "weekly_earnings", true).await?;
+    assert!(store.paused_jobs(1).await?.contains("weekly_earnings"));
+    store.set_job_paused(1, "weekly_earnings", false).await?;
+    assert!(store.paused_jobs(1).await?.is_empty());
+
+    let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+    store.save_earnings_thread(monday, 42).await?;
+    assert_eq!(store.earnings_thread(monday).await?, Some(42));
+    let later = monday + chrono::Duration::days(30);
+    store.save_earnings_thread(later, 43).await?;
+    assert_eq!(store.earnings_thread(monday).await?, None);
+
+    let mut record = EarningsRecord::new("NVDA", monday, "AMC");
+    record.implied_move = Some(6.4);
+    store.save_earnings_record(&record).await?;
+    let mut results = EarningsRecord::new("NVDA", monday, "TBA");
+    results.eps_actual = Some(0.81);
+    results.eps_estimate = Some(0.72);
+    store.save_earnings_record(&results).await?;
+    let records = store.earnings_records(monday, later).await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].session, "AMC");
+    assert_eq!(records[0].implied_move, Some(6.4));
+    assert!(records[0]
+        .eps_surprise()
+        .is_some_and(|s| (s - 12.5).abs() < 1e-9));
+
+    assert!(store.price_alerts().await?.is_empty());
+
+    let alert = EventAlert {
+        id: "199a3c5e2f1".to_string(),
+        symbol: "NVDA".to_string(),
+        kind: EventKind::TargetPrice,
+        threshold: 0.05,
+        guild_id: 1,
+        channel_id: 501,
+        user_id: 7,
+        created_at: Utc::now(),
+        seen: EventSnapshot {
+            earnings_date: Some(monday),
+            target_mean: Some(180.0),
+        },
+    };
+    store
+        .save_event_alerts("NVDA", std::slice::from_ref(&alert))
+        .await?;
+    // The snapshot is cache data and is not stored with the definition.
+    let definition = EventAlert {
+        seen: EventSnapshot::default(),
+        ..alert
+    };
+    assert_eq!(
+        store.event_alerts().await?.get("NVDA"),
+        Some(&vec![definition])
+    );
+    store.save_event_alerts("NVDA", &[]).await?;
+    assert!(store.event_alerts().await?.is_empty());
+
+    let mut brief = MorningBrief {
+        user_id: 7,
+        symbols: vec!["AAPL".to_string(), "KO".to_string()],
+        time: chrono::NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+        timezone: "America/New_York".to_string(),
+        last_sent: None,
+    };
+    store.save_morning_brief(&brief).await?;
+    brief.last_sent = Some(monday);
+    store.save_morning_brief(&brief).await?;
+    assert_eq!(store.morning_brief(7).await?, Some(brief.clone()));
+    assert_eq!(store.morning_briefs().await?.get(&7), Some(&brief));
+    assert!(store.delete_morning_brief(7).await?);
+    assert!(!store.delete_morning_brief(7).await?);
+    assert_eq!(store.morning_brief(7).await?, None);
+
+    let quarter_end =
+        |year: i32, month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let quarters: Vec<NaiveDate> = (2024..=2026)
+        .flat_map(|year| {
+            [(3, 31), (6, 30), (9, 30), (12, 31)].map(|(m, d)| quarter_end(year, m, d))
+        })
+        .take(KEEP_QUARTERS + 2)
+        .collect();
+    for quarter in &quarters {
+        let snapshot = OwnershipSnapshot {
+            quarter: *quarter,
+            institutions_percent: Some(0.6),
+            top10_percent: Some(0.3),
+            institutions_count: Some(4000),
+        };
+        store.save_ownership_snapshot("aapl", &snapshot).await?;
+    }
+    let latest = OwnershipSnapshot {
+        quarter: *quarters.last().unwrap(),
+        institutions_percent: Some(0.62),
+        top10_percent: None,
+        institutions_count: Some(4100),
+    };
+    store.save_ownership_snapshot("AAPL", &latest).await?;
+    let snapshots = store.ownership_snapshots("AAPL").await?;
+    assert_eq!(snapshots.len(), KEEP_QUARTERS);
+    assert_eq!(snapshots[0].quarter, quarters[2]);
+    assert_eq!(snapshots.last(), Some(&latest));
+    assert!(store.ownership_snapshots("MSFT").await?.is_empty());
+
+    assert_eq!(store.corporate_actions_state("KO").await?, None);
+    let mut actions = CorporateActions {
+        dividend_rate: Some(1.94),
+        ..CorporateActions::default()
+    };
+    store.save_corporate_actions_state("ko", &actions).await?;
+    actions.dividend_rate = Some(2.04);
+    store.save_corporate_actions_state("KO", &actions).await?;
+    assert_eq!(store.corporate_actions_state("KO").await?, Some(actions));
+
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// An export imported back restores the guild and clears anything added since.
+#[tokio::test]
+async fn guild_export_round_trips_through_import() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("stacks-bot-export-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let store = SqlStore::connect(&format!("sqlite://{}?mode=rwc", path.display())).await?;
+
+    store.set_command_roles(1, "er-reports", &[10]).await?;
+    store.set_job_paused(1, "daily_earnings", true).await?;
+    store.set_command_roles(2, "quote", &[20]).await?;
+    store.set_guild_route(1, "options", Some(301)).await?;
+    let saved = export::export_guild(&store, 1).await?;
+    assert_eq!(saved.command_roles.len(), 1);
+    assert_eq!(saved.routes.get("options"), Some(&301));
+
+    store.set_command_roles(1, "news", &[11]).await?;
+    store.set_guild_route(1, "alerts", Some(302)).await?;
+    store.set_job_paused(1, "daily_earnings", false).await?;
+    let summary = export::import_guild(&store, 1, &saved).await?;
+    assert_eq!((summary.command_roles, summary.paused_jobs), (1, 1));
+
+    let roles = store.all_command_roles(1).await?;
+    assert_eq!(roles.keys().collect::<Vec<_>>(), vec!["er-reports"]);
+    assert!(store.paused_jobs(1).await?.contains("daily_earnings"));
+    assert_eq!(store.command_roles(2, "quote").await?, vec![20]);
+    let routes = store.guild_routes(1).await?;
+    assert_eq!(routes.keys().collect::<Vec<_>>(), vec!["options"]);
+
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Channels in an import must resolve to the importing guild; an edited file naming another
+/// server's channel is refused.
+#[test]
+fn export_flags_channels_from_other_guilds() {
+    let mut data = export::GuildExport {
+        version: export::EXPORT_VERSION,
+        guild_id: 1,
+        exported_at: Utc::now(),
+        command_roles: BTreeMap::new(),
+        paused_jobs: Default::default(),
+        audit_channel: Some(101),
+        topic_channel: None,
+        locale: None,
+        timezone: None,
+        symbol_filter: None,
+        routes: BTreeMap::from([("options".to_string(), 102)]),
+        price_alerts: vec![PriceAlert {
+            id: "a1".to_string(),
+            symbol: "AAPL".to_string(),
+            created_at: Utc::now(),
+            created_price: 190.0,
+            target_guild_id: 1,
+            target_channel_id: 103,
+            levels: Vec::new(),
+        }],
+    };
+    let channel_guilds = BTreeMap::from([(101, 1), (102, 1), (103, 1), (201, 2)]);
+
+    assert_eq!(
+        data.channel_ids().into_iter().collect::<Vec<_>>(),
+        vec![101, 102, 103]
+    );
+    assert!(data.foreign_channels(1, &channel_guilds).is_empty());
+
+    data.topic_channel = Some(201);
+    data.routes.insert("alerts".to_string(), 999);
+    assert_eq!(data.foreign_channels(1, &channel_guilds), vec![201, 999]);
+}
@@ -0,0 +1,83 @@
+use stacks_bot::service::config::ConstituentsConfig;
+use stacks_bot::service::finance::FinanceService;
+use stacks_bot::service::symbol_filter::SymbolFilter;
+
+fn list(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|s| s.to_string()).collect()
+}
+
+/// A finance service whose index lists can't be downloaded.
+fn offline_finance() -> FinanceService {
+    let unreachable = "https://127.0.0.1:9/members".to_string();
+    FinanceService::new(None)
+        .unwrap()
+        .with_constituent_sources(ConstituentsConfig {
+            sp500_url: unreachable.clone(),
+            nasdaq100_url: unreachable.clone(),
+            dow30_url: unreachable,
+        })
+}
+
+#[test]
+fn list_keeps_index_names_and_uppercases_tickers() {
+    assert_eq!(
+        SymbolFilter::parse_list("SPX, $msft  brk.b,MSFT ndx ^vix").unwrap(),
+        list(&["sp500", "MSFT", "BRK.B", "nasdaq100", "^VIX"])
+    );
+    assert!(SymbolFilter::parse_list(" , ").unwrap().is_empty());
+    assert_eq!(
+        SymbolFilter::parse_list("AAPL, foo!").unwrap_err(),
+        "`foo!` is not a ticker or index name"
+    );
+    assert!(SymbolFilter::parse_list("$").is_err());
+}
+
+#[test]
+fn describe_lists_each_part() {
+    assert_eq!(SymbolFilter::default().describe(), "none");
+    let filter = SymbolFilter {
+        allow: list(&["sp500", "MSFT"]),
+        deny: list(&["TSLA"]),
+        min_market_cap: Some(2e9),
+    };
+    assert_eq!(
+        filter.describe(),
+        "allow: sp500, MSFT · deny: TSLA · min market cap: $2.00B"
+    );
+    let deny_only = SymbolFilter {
+        deny: list(&["GME", "AMC"]),
+        ..SymbolFilter::default()
+    };
+    assert_eq!(deny_only.describe(), "deny: GME, AMC");
+}
+
+#[tokio::test]
+async fn resolved_filter_applies_allow_deny_and_market_cap() {
+    let filter = SymbolFilter {
+        allow: list(&["AAPL", "MSFT", "NVDA"]),
+        deny: list(&["MSFT"]),
+        min_market_cap: Some(1e9),
+    };
+    let resolved = filter.resolve(&offline_finance()).await;
+
+    assert!(resolved.allows("aapl", None));
+    assert!(resolved.allows("NVDA", Some(3e12)));
+    assert!(!resolved.allows("NVDA", Some(5e8)));
+    assert!(!resolved.allows("MSFT", Some(3e12)));
+    assert!(!resolved.allows("TSLA", None));
+    assert_eq!(resolved.watchlist(), Some(list(&["AAPL", "NVDA"])));
+}
+
+#[tokio::test]
+async fn unloadable_index_allows_everything_instead_of_nothing() {
+    let filter = SymbolFilter {
+        allow: list(&["dow30"]),
+        deny: list(&["TSLA", "nasdaq100"]),
+        min_market_cap: None,
+    };
+    let resolved = filter.resolve(&offline_finance()).await;
+
+    assert!(resolved.allows("AAPL", None));
+    assert!(!resolved.allows("TSLA", None));
+    assert_eq!(resolved.watchlist(), None);
+}
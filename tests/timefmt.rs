@@ -0,0 +1,92 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use serenity::all::{GuildId, UserId};
+use stacks_bot::service::store::{SqlStore, StateStore};
+use stacks_bot::service::timefmt::{self, TimestampStyle};
+
+#[test]
+fn discord_markup_uses_the_unix_timestamp_and_style_code() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 17, 13, 30, 0).unwrap();
+    assert_eq!(
+        timefmt::discord(&at, TimestampStyle::ShortDateTime),
+        "<t:1792243800:f>"
+    );
+    assert_eq!(
+        timefmt::discord(
+            &at.with_timezone(&Tz::Asia__Tokyo),
+            TimestampStyle::Relative
+        ),
+        "<t:1792243800:R>"
+    );
+}
+
+#[test]
+fn plain_text_is_shown_in_the_given_zone() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 17, 13, 30, 0).unwrap();
+    assert_eq!(
+        timefmt::plain(&at, timefmt::DEFAULT_TIMEZONE),
+        "Oct 17 9:30 AM EDT"
+    );
+    assert_eq!(timefmt::plain(&at, Tz::Asia__Tokyo), "Oct 17 10:30 PM JST");
+
+    let winter = Utc.with_ymd_and_hms(2026, 12, 1, 14, 30, 0).unwrap();
+    assert_eq!(
+        timefmt::plain(&winter, timefmt::DEFAULT_TIMEZONE),
+        "Dec 1 9:30 AM EST"
+    );
+}
+
+#[test]
+fn parses_iana_names_and_short_aliases() {
+    assert_eq!(
+        timefmt::parse_timezone("Europe/London"),
+        Some(Tz::Europe__London)
+    );
+    assert_eq!(
+        timefmt::parse_timezone(" europe/london "),
+        Some(Tz::Europe__London)
+    );
+    assert_eq!(
+        timefmt::parse_timezone("pt"),
+        Some(Tz::America__Los_Angeles)
+    );
+    assert_eq!(timefmt::parse_timezone("GMT"), Some(Tz::UTC));
+    assert_eq!(timefmt::parse_timezone("Mars/Olympus"), None);
+    assert_eq!(timefmt::parse_timezone(""), None);
+}
+
+#[tokio::test]
+async fn user_preference_wins_over_the_guild() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("stacks-bot-timefmt-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let store = SqlStore::connect(&format!("sqlite://{}?mode=rwc", path.display())).await?;
+    let store: &dyn StateStore = &store;
+
+    let guild = Some(GuildId::new(1));
+    let user = UserId::new(7);
+    assert_eq!(
+        timefmt::preferred_timezone(Some(store), guild, user).await,
+        None
+    );
+    assert_eq!(timefmt::preferred_timezone(None, guild, user).await, None);
+
+    store.set_guild_timezone(1, Some("Europe/London")).await?;
+    assert_eq!(
+        timefmt::preferred_timezone(Some(store), guild, user).await,
+        Some(Tz::Europe__London)
+    );
+    // Outside a server only the user's own choice applies.
+    assert_eq!(
+        timefmt::preferred_timezone(Some(store), None, user).await,
+        None
+    );
+
+    store.set_user_timezone(7, Some("Asia/Tokyo")).await?;
+    assert_eq!(
+        timefmt::preferred_timezone(Some(store), guild, user).await,
+        Some(Tz::Asia__Tokyo)
+    );
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use stacks_bot::models::EarningsRange;
+use stacks_bot::service::command::earnings::{requested_week, week_label};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+}
+
+#[test]
+fn week_option_moves_from_the_current_week() {
+    // Wednesday, Oct 14
+    let today = day(14);
+    assert_eq!(
+        requested_week(None, None, today).unwrap(),
+        (day(12), day(16))
+    );
+    assert_eq!(
+        requested_week(Some("last"), None, today).unwrap(),
+        (day(5), day(9))
+    );
+    assert_eq!(
+        requested_week(Some("next"), None, today).unwrap(),
+        (day(19), day(23))
+    );
+    // Sunday already shows the coming week, so "next" is the one after.
+    assert_eq!(
+        requested_week(Some("next"), None, day(18)).unwrap(),
+        (day(26), day(30))
+    );
+}
+
+#[test]
+fn date_option_picks_its_week() {
+    let today = day(14);
+    assert_eq!(
+        requested_week(None, Some("2026-09-30"), today).unwrap(),
+        (NaiveDate::from_ymd_opt(2026, 9, 28).unwrap(), day(2))
+    );
+    assert!(requested_week(None, Some("next tuesday"), today).is_err());
+    assert!(requested_week(Some("next"), Some("2026-10-20"), today).is_err());
+}
+
+#[test]
+fn weeks_are_labeled_relative_to_the_current_one() {
+    let today = day(14);
+    assert_eq!(week_label(day(12), today), None);
+    assert_eq!(week_label(day(5), today).as_deref(), Some("Last week"));
+    assert_eq!(week_label(day(19), today).as_deref(), Some("Next week"));
+    assert_eq!(week_label(day(26), today).as_deref(), Some("In 2 weeks"));
+    assert_eq!(
+        week_label(NaiveDate::from_ymd_opt(2026, 9, 28).unwrap(), today).as_deref(),
+        Some("2 weeks ago")
+    );
+}
+
+#[test]
+fn partial_range_names_the_missing_days() {
+    let complete = EarningsRange::complete(Vec::new());
+    assert_eq!(complete.missing_note(), None);
+
+    let partial = EarningsRange {
+        events: Vec::new(),
+        missing_days: vec![day(13), day(15)],
+    };
+    assert_eq!(
+        partial.missing_note().as_deref(),
+        Some("⚠️ Earnings for Tue Oct 13, Thu Oct 15 could not be loaded; the list may be incomplete.")
+    );
+}
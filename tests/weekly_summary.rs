@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use stacks_bot::models::EarningsRecord;
+use stacks_bot::service::automation::earnings::format_summary;
+
+fn monday() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+}
+
+fn record(
+    symbol: &str,
+    eps: Option<(f64, f64)>,
+    price_move: Option<f64>,
+    implied_move: Option<f64>,
+) -> EarningsRecord {
+    let mut record = EarningsRecord::new(symbol, monday(), "AMC");
+    if let Some((estimate, actual)) = eps {
+        record.eps_estimate = Some(estimate);
+        record.eps_actual = Some(actual);
+    }
+    record.price_move = price_move;
+    record.implied_move = implied_move;
+    record
+}
+
+#[test]
+fn merge_prefers_newer_values_and_keeps_known_ones() {
+    let mut stored = EarningsRecord::new("NVDA", monday(), "AMC");
+    stored.implied_move = Some(6.0);
+    stored.eps_estimate = Some(1.0);
+
+    let mut results = EarningsRecord::new("NVDA", monday(), "TBA");
+    results.eps_estimate = Some(1.05);
+    results.eps_actual = Some(1.2);
+    results.price_move = Some(-4.5);
+    stored.merge(&results);
+
+    assert_eq!(stored.session, "AMC");
+    assert_eq!(stored.implied_move, Some(6.0));
+    assert_eq!(stored.eps_estimate, Some(1.05));
+    assert_eq!(stored.eps_actual, Some(1.2));
+    assert_eq!(stored.revenue_actual, None);
+    assert_eq!(stored.price_move, Some(-4.5));
+
+    stored.merge(&EarningsRecord::new("NVDA", monday(), "BMO"));
+    assert_eq!(stored.session, "BMO");
+    assert_eq!(stored.eps_actual, Some(1.2));
+}
+
+#[test]
+fn eps_surprise_needs_both_numbers_and_a_nonzero_estimate() {
+    let surprise = |estimate, actual| {
+        record("X", Some((estimate, actual)), None, None)
+            .eps_surprise()
+            .unwrap()
+    };
+    assert!((surprise(1.0, 1.2) - 20.0).abs() < 1e-9);
+    assert!((surprise(0.2, 0.1) + 50.0).abs() < 1e-9);
+    // A smaller loss than expected is a beat, a bigger one a miss.
+    assert!((surprise(-0.5, -0.25) - 50.0).abs() < 1e-9);
+    assert!((surprise(-0.5, -0.75) + 50.0).abs() < 1e-9);
+
+    assert_eq!(
+        record("X", Some((0.0, 0.1)), None, None).eps_surprise(),
+        None
+    );
+    let mut missing_estimate = record("X", None, None, None);
+    missing_estimate.eps_actual = Some(0.3);
+    assert_eq!(missing_estimate.eps_surprise(), None);
+    let mut missing_actual = record("X", None, None, None);
+    missing_actual.eps_estimate = Some(0.3);
+    assert_eq!(missing_actual.eps_surprise(), None);
+}
+
+#[test]
+fn summary_ranks_surprises_and_moves() {
+    let mut pltr = record("PLTR", None, Some(2.0), Some(4.0));
+    pltr.eps_actual = Some(0.1);
+    let mut msft = record("MSFT", None, None, Some(5.0));
+    msft.eps_estimate = Some(3.0);
+    let records = [
+        record("NVDA", Some((1.0, 1.2)), Some(8.0), Some(6.0)),
+        record("TSLA", Some((-0.5, -0.25)), Some(-3.0), Some(5.0)),
+        record("INTC", Some((0.2, 0.1)), Some(-12.0), None),
+        record("AMD", Some((0.8, 0.8)), None, None),
+        pltr,
+        msft,
+    ];
+
+    let text = format_summary(monday(), &records);
+    let expected = [
+        "📋 Earnings week in review — Week of Oct 12",
+        "5 reports · 2 beats · 1 misses · 1 in line",
+        "",
+        "**✅ Biggest beats**",
+        "• TSLA +50.0% (EPS -0.25 vs -0.50 est)",
+        "• NVDA +20.0% (EPS 1.20 vs 1.00 est)",
+        "",
+        "**❌ Biggest misses**",
+        "• INTC -50.0% (EPS 0.10 vs 0.20 est)",
+        "",
+        "**📈 Biggest moves**",
+        "• INTC -12.0%",
+        "• NVDA +8.0% (implied ±6.0%)",
+        "• TSLA -3.0% (implied ±5.0%)",
+        "• PLTR +2.0% (implied ±4.0%)",
+        "",
+        "**🎯 Implied vs actual**",
+        "Average move 4.3% vs ±5.0% implied · 1/3 moved more than implied",
+    ]
+    .join("\n");
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn summary_without_results_says_so() {
+    let upcoming = record("MSFT", None, None, Some(5.0));
+    assert_eq!(
+        format_summary(monday(), &[upcoming]),
+        "📋 Earnings week in review — Week of Oct 12\nNo earnings results were recorded this week."
+    );
+}